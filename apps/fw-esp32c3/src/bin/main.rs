@@ -60,6 +60,7 @@ async fn main(_spawner: Spawner) {
         power_budget_ma: 1000, // 1A budget
         led_white_power_ma: 50,
         led_idle_power_ma: 1,
+        min_scale: Fixed::ZERO,
     };
 
     let options =
@@ -24,7 +24,7 @@ fn benchmark_size(width: usize, height: usize) {
     let elapsed = start.elapsed();
     let total_us = elapsed.as_micros() as u64;
     let avg_us = total_us / FRAME_COUNT as u64;
-    let fps = if avg_us > 0 { 1_000_000 / avg_us } else { 0 };
+    let fps = 1_000_000u64.checked_div(avg_us).unwrap_or(0);
 
     println!("{}x{}: {}us/frame ({} FPS)", width, height, avg_us, fps);
 }
@@ -0,0 +1,149 @@
+//! Frame buffer comparison for validating rendered output against a reference.
+
+use std::fmt;
+
+/// Result of comparing two byte buffers value by value
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompareReport {
+    /// Largest absolute difference allowed before a value counts as exceeding
+    pub tolerance: u8,
+    /// Number of values compared (the shorter buffer's length)
+    pub compared: usize,
+    /// Lengths of the two inputs, which differ when one buffer was truncated
+    pub len_a: usize,
+    pub len_b: usize,
+    /// Largest absolute difference, and the first index where it occurs
+    pub max_diff: u8,
+    pub max_diff_index: Option<usize>,
+    /// Mean absolute difference over all compared values
+    pub mean_diff: f64,
+    /// Indices whose difference exceeds `tolerance`
+    pub exceeding: Vec<usize>,
+}
+
+impl CompareReport {
+    /// Whether the buffers have the same length and every value is within tolerance
+    pub fn passed(&self) -> bool {
+        self.len_a == self.len_b && self.exceeding.is_empty()
+    }
+}
+
+/// Compare `a` and `b` value by value, allowing differences up to `tolerance`
+///
+/// Only the overlapping prefix is compared; a length mismatch is recorded in
+/// the report and makes it fail.
+pub fn compare_buffers(a: &[u8], b: &[u8], tolerance: u8) -> CompareReport {
+    let compared = a.len().min(b.len());
+    let mut max_diff = 0u8;
+    let mut max_diff_index = None;
+    let mut total_diff = 0u64;
+    let mut exceeding = Vec::new();
+
+    for (i, (&x, &y)) in a.iter().zip(b).enumerate() {
+        let diff = x.abs_diff(y);
+        total_diff += diff as u64;
+        if diff > max_diff {
+            max_diff = diff;
+            max_diff_index = Some(i);
+        }
+        if diff > tolerance {
+            exceeding.push(i);
+        }
+    }
+
+    let mean_diff = if compared > 0 {
+        total_diff as f64 / compared as f64
+    } else {
+        0.0
+    };
+
+    CompareReport {
+        tolerance,
+        compared,
+        len_a: a.len(),
+        len_b: b.len(),
+        max_diff,
+        max_diff_index,
+        mean_diff,
+        exceeding,
+    }
+}
+
+impl fmt::Display for CompareReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.passed() { "PASS" } else { "FAIL" };
+        writeln!(
+            f,
+            "{}: {} values compared (tolerance ±{})",
+            status, self.compared, self.tolerance
+        )?;
+        if self.len_a != self.len_b {
+            writeln!(f, "  length mismatch: {} vs {}", self.len_a, self.len_b)?;
+        }
+        match self.max_diff_index {
+            Some(index) => writeln!(f, "  max diff: {} at index {}", self.max_diff, index)?,
+            None => writeln!(f, "  max diff: 0")?,
+        }
+        writeln!(f, "  mean diff: {:.3}", self.mean_diff)?;
+        write!(f, "  exceeding tolerance: {}", self.exceeding.len())?;
+
+        // List the first few offending values
+        const SHOWN: usize = 10;
+        if !self.exceeding.is_empty() {
+            let shown: Vec<String> = self
+                .exceeding
+                .iter()
+                .take(SHOWN)
+                .map(|i| i.to_string())
+                .collect();
+            write!(f, " (indices {}", shown.join(", "))?;
+            if self.exceeding.len() > SHOWN {
+                write!(f, ", ...")?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_buffers_have_no_diffs() {
+        let buffer = [0u8, 10, 128, 255, 7, 9];
+        let report = compare_buffers(&buffer, &buffer, 0);
+
+        assert!(report.passed());
+        assert_eq!(report.compared, 6);
+        assert_eq!(report.max_diff, 0);
+        assert_eq!(report.max_diff_index, None);
+        assert_eq!(report.mean_diff, 0.0);
+        assert!(report.exceeding.is_empty());
+    }
+
+    #[test]
+    fn different_buffers_report_max_and_exceeding() {
+        let a = [100u8, 100, 100, 100];
+        let b = [100u8, 105, 70, 111];
+        let report = compare_buffers(&a, &b, 10);
+
+        assert!(!report.passed());
+        assert_eq!(report.max_diff, 30);
+        assert_eq!(report.max_diff_index, Some(2));
+        assert_eq!(report.mean_diff, (5 + 30 + 11) as f64 / 4.0);
+        assert_eq!(report.exceeding, vec![2, 3]);
+
+        let text = report.to_string();
+        assert!(text.starts_with("FAIL"));
+        assert!(text.contains("max diff: 30 at index 2"));
+    }
+
+    #[test]
+    fn length_mismatch_fails() {
+        let report = compare_buffers(&[1, 2, 3], &[1, 2], 10);
+        assert_eq!(report.compared, 2);
+        assert!(!report.passed());
+    }
+}
@@ -1 +1,2 @@
+pub mod compare;
 pub mod lpa_format;
@@ -3,7 +3,6 @@ extern crate alloc;
 use alloc::format;
 use alloc::string::String;
 
-use lp_script::shared::Type;
 use lp_script::vm::opcodes::LpsOpCode;
 use lp_script::vm::{FunctionDef, LpsProgram};
 
@@ -29,18 +28,14 @@ fn function_to_lpa(func: &FunctionDef, _func_idx: usize, program: &LpsProgram) -
     let mut output = String::new();
 
     // Function header
-    output.push_str(&format!(
-        "function {} {}(",
-        type_to_string(&func.return_type),
-        func.name
-    ));
+    output.push_str(&format!("function {} {}(", func.return_type, func.name));
 
     // Parameters
     for (i, param) in func.params.iter().enumerate() {
         if i > 0 {
             output.push_str(", ");
         }
-        output.push_str(&format!("{} {}", type_to_string(&param.ty), param.name));
+        output.push_str(&format!("{} {}", param.ty, param.name));
     }
     output.push_str(") {\n");
 
@@ -48,11 +43,7 @@ fn function_to_lpa(func: &FunctionDef, _func_idx: usize, program: &LpsProgram) -
     if !func.locals.is_empty() {
         output.push_str("  ; Locals:\n");
         for local in &func.locals {
-            output.push_str(&format!(
-                "  ;   {} {}\n",
-                type_to_string(&local.ty),
-                local.name
-            ));
+            output.push_str(&format!("  ;   {} {}\n", local.ty, local.name));
         }
         output.push('\n');
     }
@@ -87,6 +78,10 @@ fn opcode_to_pretty_string(opcode: &LpsOpCode, func: &FunctionDef, program: &Lps
                 LoadSource::TimeNorm => "builtin.timeNorm",
                 LoadSource::CenterDist => "builtin.centerDist",
                 LoadSource::CenterAngle => "builtin.centerAngle",
+                LoadSource::CenterX => "builtin.centerX",
+                LoadSource::CenterY => "builtin.centerY",
+                LoadSource::Width => "builtin.width",
+                LoadSource::Height => "builtin.height",
             };
             format!("load {}", source_name)
         }
@@ -202,20 +197,6 @@ fn opcode_to_pretty_string(opcode: &LpsOpCode, func: &FunctionDef, program: &Lps
     }
 }
 
-/// Convert type to string
-fn type_to_string(ty: &Type) -> &'static str {
-    match ty {
-        Type::Fixed => "float",
-        Type::Int32 => "int",
-        Type::Vec2 => "vec2",
-        Type::Vec3 => "vec3",
-        Type::Vec4 => "vec4",
-        Type::Mat3 => "mat3",
-        Type::Void => "void",
-        Type::Bool => "bool",
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use lp_script::{parse_expr, parse_script};
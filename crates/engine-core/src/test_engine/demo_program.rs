@@ -199,12 +199,19 @@ mod tests {
     #[test]
     fn test_simple_white() {
         // First test: just output white for everything
-        use lp_script::vm::execute_program_lps;
+        use lp_script::vm::{execute_program_lps, CoordConfig};
         let mut output = vec![Fixed::ZERO; 16 * 16];
 
         let program = parse_expr("1.0");
 
-        execute_program_lps(&program, &mut output, 16, 16, Fixed::ZERO);
+        execute_program_lps(
+            &program,
+            &mut output,
+            16,
+            16,
+            Fixed::ZERO,
+            &CoordConfig::default(),
+        );
 
         // All pixels should be white
         assert_eq!(output[0], Fixed::ONE, "First pixel should be white");
@@ -218,12 +225,19 @@ mod tests {
     #[test]
     fn test_yint_load() {
         // Test that YInt loads correctly
-        use lp_script::vm::execute_program_lps;
+        use lp_script::vm::{execute_program_lps, CoordConfig};
         let mut output = vec![Fixed::ZERO; 16 * 16];
 
         let program = parse_expr("coord.y");
 
-        execute_program_lps(&program, &mut output, 16, 16, Fixed::ZERO);
+        execute_program_lps(
+            &program,
+            &mut output,
+            16,
+            16,
+            Fixed::ZERO,
+            &CoordConfig::default(),
+        );
 
         // Row 0 should have Y values of 0.5 in fixed-point
         println!(
@@ -245,7 +259,7 @@ mod tests {
     #[test]
     fn test_normalized_center_line() {
         // Test the normalized Y coordinate approach
-        use lp_script::vm::execute_program_lps;
+        use lp_script::vm::{execute_program_lps, CoordConfig};
 
         // Test with 16x16 - center should be between row 7 and 8
         let mut output = vec![Fixed::ZERO; 16 * 16];
@@ -254,7 +268,14 @@ mod tests {
         // Row 7: uv.y = 0.4688, Row 8: uv.y = 0.5312
         let program = parse_expr("(uv.y > 0.46 && uv.y < 0.54) ? 1.0 : 0.0");
 
-        execute_program_lps(&program, &mut output, 16, 16, Fixed::ZERO);
+        execute_program_lps(
+            &program,
+            &mut output,
+            16,
+            16,
+            Fixed::ZERO,
+            &CoordConfig::default(),
+        );
 
         // Center rows (7 and 8) should be white
         assert_eq!(
@@ -274,7 +295,14 @@ mod tests {
         // Test with 8x8 - center should be between row 3 and 4
         let mut output8 = vec![Fixed::ZERO; 8 * 8];
         // Row 3: (3+0.5)/8 = 0.4375, Row 4: (4+0.5)/8 = 0.5625
-        execute_program_lps(&program, &mut output8, 8, 8, Fixed::ZERO);
+        execute_program_lps(
+            &program,
+            &mut output8,
+            8,
+            8,
+            Fixed::ZERO,
+            &CoordConfig::default(),
+        );
 
         // Center rows (3 and 4) should be white with the range 0.46-0.54
         assert_eq!(
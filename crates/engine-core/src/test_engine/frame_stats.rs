@@ -0,0 +1,136 @@
+/// Frame statistics for auto-exposure and debugging
+///
+/// Summarizes an RGB byte buffer (R,G,B,R,G,B,...) per channel and estimates
+/// its current draw with the same model as power limiting.
+use smart_leds::RGB8;
+
+use crate::test_engine::power_limit::{calculate_led_power, PowerLimitConfig};
+
+/// Statistics for one color channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ChannelStats {
+    pub min: u8,
+    pub max: u8,
+    /// Mean value, rounded to the nearest integer
+    pub mean: u8,
+}
+
+/// Statistics for a whole RGB frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FrameStats {
+    pub r: ChannelStats,
+    pub g: ChannelStats,
+    pub b: ChannelStats,
+    /// Estimated current draw of the frame in milliamps
+    pub current_ma: u32,
+}
+
+/// Compute frame statistics using the default power model
+///
+/// See [`frame_stats_with_config`].
+pub fn frame_stats(buffer: &[u8]) -> FrameStats {
+    frame_stats_with_config(buffer, &PowerLimitConfig::default())
+}
+
+/// Compute per-channel min/max/mean and estimated current of an RGB buffer
+///
+/// Current uses `config.led_white_power_ma`/`led_idle_power_ma` per LED.
+/// Trailing bytes that don't form a whole LED are ignored. An empty buffer
+/// yields all-zero stats.
+pub fn frame_stats_with_config(buffer: &[u8], config: &PowerLimitConfig) -> FrameStats {
+    let led_count = buffer.len() / 3;
+    if led_count == 0 {
+        return FrameStats::default();
+    }
+
+    let mut min = [u8::MAX; 3];
+    let mut max = [0u8; 3];
+    let mut sum = [0u64; 3];
+    let mut current_ma: u32 = 0;
+
+    for chunk in buffer.chunks_exact(3) {
+        for channel in 0..3 {
+            let value = chunk[channel];
+            min[channel] = min[channel].min(value);
+            max[channel] = max[channel].max(value);
+            sum[channel] += value as u64;
+        }
+        let led = RGB8 {
+            r: chunk[0],
+            g: chunk[1],
+            b: chunk[2],
+        };
+        current_ma += calculate_led_power(led, config.led_white_power_ma, config.led_idle_power_ma);
+    }
+
+    let channel = |i: usize| ChannelStats {
+        min: min[i],
+        max: max[i],
+        mean: ((sum[i] + led_count as u64 / 2) / led_count as u64) as u8,
+    };
+
+    FrameStats {
+        r: channel(0),
+        g: channel(1),
+        b: channel(2),
+        current_ma,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_stats_known_buffer() {
+        let buffer = [
+            255, 0, 10, //
+            0, 0, 20, //
+            100, 255, 31, //
+            1, 0, 40, //
+        ];
+        let stats = frame_stats(&buffer);
+
+        assert_eq!(
+            stats.r,
+            ChannelStats {
+                min: 0,
+                max: 255,
+                mean: 89
+            }
+        );
+        assert_eq!(
+            stats.g,
+            ChannelStats {
+                min: 0,
+                max: 255,
+                mean: 64
+            }
+        );
+        assert_eq!(
+            stats.b,
+            ChannelStats {
+                min: 10,
+                max: 40,
+                mean: 25
+            }
+        );
+
+        // Same model as power limiting: idle + (white - idle) * sum / 765 per LED
+        // (1 + 49*265/765) + (1 + 49*20/765) + (1 + 49*386/765) + (1 + 49*41/765)
+        assert_eq!(stats.current_ma, 17 + 2 + 25 + 3);
+    }
+
+    #[test]
+    fn test_frame_stats_white_and_empty() {
+        let white = [255u8; 30];
+        let stats = frame_stats(&white);
+        assert_eq!(stats.r.mean, 255);
+        assert_eq!(
+            stats.current_ma,
+            10 * PowerLimitConfig::default().led_white_power_ma
+        );
+
+        assert_eq!(frame_stats(&[]), FrameStats::default());
+    }
+}
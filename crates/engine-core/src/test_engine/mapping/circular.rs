@@ -71,7 +71,7 @@ impl LedMapping {
             }
         }
 
-        LedMapping { maps }
+        LedMapping { maps, len: led_idx }
     }
 
     /// Create mapping for standard 7-ring circular panel
@@ -1,5 +1,5 @@
 /// Grid and serpentine LED mappings
-use super::{LedMap, LedMapping};
+use super::{LedMap, LedMapping, MAX_LEDS};
 
 impl LedMapping {
     /// Create a simple grid mapping (for testing)
@@ -11,7 +11,10 @@ impl LedMapping {
             let y = i / 16;
             *map = LedMap::new(x, y);
         }
-        LedMapping { maps }
+        LedMapping {
+            maps,
+            len: MAX_LEDS,
+        }
     }
 
     /// Create a serpentine/zigzag mapping (common for LED matrices)
@@ -23,6 +26,9 @@ impl LedMapping {
             let x = if y % 2 == 0 { i % 16 } else { 15 - (i % 16) };
             *map = LedMap::new(x, y);
         }
-        LedMapping { maps }
+        LedMapping {
+            maps,
+            len: MAX_LEDS,
+        }
     }
 }
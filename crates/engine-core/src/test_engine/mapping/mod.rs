@@ -8,7 +8,10 @@ mod sample;
 mod spiral;
 
 pub use config::MappingConfig;
-pub use sample::{bilinear_interp_channel, bilinear_interp_rgb, sample_rgb_bilinear};
+pub use sample::{
+    bilinear_interp_channel, bilinear_interp_channel_linear, bilinear_interp_rgb, sample_bilinear,
+    sample_bilinear_in, sample_rgb_bilinear, SampleSpace, MAX_CHANNELS,
+};
 
 /// Single LED mapping entry with sub-pixel precision
 #[derive(Debug, Clone, Copy)]
@@ -30,21 +33,113 @@ impl LedMap {
     }
 }
 
+/// How far from an LED `LedMapping::nearest` still picks it, in pixels
+pub const PICK_RADIUS: Fixed = Fixed::ONE;
+
+/// Most LEDs a `LedMapping` can hold
+pub const MAX_LEDS: usize = 128;
+
+/// Errors building a `LedMapping`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MappingError {
+    /// The LEDs don't fit in `MAX_LEDS`
+    TooManyLeds { led_count: usize, max: usize },
+}
+
 /// LED mapping for the entire strip
 pub struct LedMapping {
-    maps: [LedMap; 128],
+    maps: [LedMap; MAX_LEDS],
+    len: usize, // LEDs in use, from the start of `maps`
 }
 
 impl LedMapping {
     /// Create a new LED mapping from an array
-    pub fn new(maps: [LedMap; 128]) -> Self {
-        LedMapping { maps }
+    pub fn new(maps: [LedMap; MAX_LEDS]) -> Self {
+        LedMapping {
+            maps,
+            len: MAX_LEDS,
+        }
+    }
+
+    /// Create a mapping of `maps.len()` LEDs, in strip order
+    pub fn from_maps(maps: &[LedMap]) -> Result<Self, MappingError> {
+        if maps.len() > MAX_LEDS {
+            return Err(MappingError::TooManyLeds {
+                led_count: maps.len(),
+                max: MAX_LEDS,
+            });
+        }
+        let mut mapping = LedMapping {
+            maps: [LedMap::new(0, 0); MAX_LEDS],
+            len: maps.len(),
+        };
+        mapping.maps[..maps.len()].copy_from_slice(maps);
+        Ok(mapping)
+    }
+
+    /// Chain several mappings into one strip, e.g. panels wired in series
+    ///
+    /// Each zone's LEDs follow the previous zone's, in order, with their
+    /// positions moved by the zone's offset (in pixels) into the shared
+    /// image. Fails if the zones hold more than `MAX_LEDS` LEDs together.
+    pub fn concat(zones: &[(&LedMapping, Vec2)]) -> Result<Self, MappingError> {
+        let led_count = zones.iter().map(|(zone, _)| zone.len()).sum::<usize>();
+        if led_count > MAX_LEDS {
+            return Err(MappingError::TooManyLeds {
+                led_count,
+                max: MAX_LEDS,
+            });
+        }
+
+        let mut mapping = LedMapping {
+            maps: [LedMap::new(0, 0); MAX_LEDS],
+            len: led_count,
+        };
+        let combined = zones
+            .iter()
+            .flat_map(|(zone, offset)| zone.maps().iter().map(move |map| map.pos + *offset));
+        for (map, pos) in mapping.maps.iter_mut().zip(combined) {
+            map.pos = pos;
+        }
+        Ok(mapping)
+    }
+
+    /// Number of LEDs in the mapping
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The LEDs in use, in strip order
+    fn maps(&self) -> &[LedMap] {
+        &self.maps[..self.len]
     }
 
     /// Get the mapping for a specific LED index
     #[inline(always)]
     pub fn get(&self, led_index: usize) -> core::option::Option<&LedMap> {
-        self.maps.get(led_index)
+        self.maps().get(led_index)
+    }
+
+    /// Find the LED closest to `pos`, e.g. for picking with a pointer
+    ///
+    /// Returns `None` if no LED is within `PICK_RADIUS`. Equally close LEDs
+    /// resolve to the lowest index.
+    pub fn nearest(&self, pos: Vec2) -> core::option::Option<usize> {
+        let max_dist_sq = PICK_RADIUS.0 as i64 * PICK_RADIUS.0 as i64;
+        let mut best: core::option::Option<(usize, i64)> = None;
+        for (led_index, map) in self.maps().iter().enumerate() {
+            let dx = map.pos.x.0 as i64 - pos.x.0 as i64;
+            let dy = map.pos.y.0 as i64 - pos.y.0 as i64;
+            let dist_sq = dx * dx + dy * dy;
+            if dist_sq <= max_dist_sq && best.is_none_or(|(_, best_sq)| dist_sq < best_sq) {
+                best = Some((led_index, dist_sq));
+            }
+        }
+        best.map(|(led_index, _)| led_index)
     }
 }
 
@@ -63,17 +158,39 @@ pub fn apply_2d_mapping(
     width: usize,
     height: usize,
 ) {
-    let led_count = led_output.len() / 3;
+    apply_2d_mapping_in(
+        rgb_2d,
+        led_output,
+        mapping,
+        width,
+        height,
+        SampleSpace::Gamma,
+    );
+}
+
+/// `apply_2d_mapping`, blending neighboring pixels in the given color space
+///
+/// `SampleSpace::Linear` keeps LEDs between a bright and a dark pixel from
+/// coming out too dim, at the cost of a table lookup and search per channel.
+pub fn apply_2d_mapping_in(
+    rgb_2d: &[u8],
+    led_output: &mut [u8],
+    mapping: &LedMapping,
+    width: usize,
+    height: usize,
+    space: SampleSpace,
+) {
+    const CHANNELS: usize = 3;
+    let led_count = led_output.len() / CHANNELS;
     #[cfg(not(feature = "use-libm"))]
     assert!(led_count <= 128, "LED count exceeds maximum of 128");
 
     for led_idx in 0..led_count {
         if let core::option::Option::Some(map) = mapping.get(led_idx) {
-            let rgb = sample_rgb_bilinear(rgb_2d, map.pos.x, map.pos.y, width, height);
-            let dst_idx = led_idx * 3;
-            led_output[dst_idx] = rgb[0];
-            led_output[dst_idx + 1] = rgb[1];
-            led_output[dst_idx + 2] = rgb[2];
+            let pixel =
+                sample_bilinear_in(rgb_2d, map.pos.x, map.pos.y, width, height, CHANNELS, space);
+            let dst_idx = led_idx * CHANNELS;
+            led_output[dst_idx..dst_idx + CHANNELS].copy_from_slice(&pixel[..CHANNELS]);
         }
     }
 }
@@ -128,6 +245,70 @@ mod tests {
         assert_eq!(end_second_row.pos.y.to_i32(), 1);
     }
 
+    #[test]
+    fn test_concat_grids_side_by_side() {
+        let cells: vec::Vec<LedMap> = (0..64).map(|i| LedMap::new(i % 8, i / 8)).collect();
+        let grid = LedMapping::from_maps(&cells).unwrap();
+        assert_eq!(grid.len(), 64);
+
+        let left = Vec2::new(Fixed::ZERO, Fixed::ZERO);
+        let right = Vec2::new(Fixed::from_i32(8), Fixed::ZERO);
+        let mapping = LedMapping::concat(&[(&grid, left), (&grid, right)]).unwrap();
+        assert_eq!(mapping.len(), 128);
+
+        let cell = |led_index: usize| {
+            let pos = mapping.get(led_index).unwrap().pos;
+            (pos.x.to_i32(), pos.y.to_i32())
+        };
+        // Last LED of the left panel, then first of the right one
+        assert_eq!(cell(63), (7, 7));
+        assert_eq!(cell(64), (8, 0));
+        assert_eq!(cell(127), (15, 7));
+        assert_eq!(mapping.get(0).unwrap().pos, grid.get(0).unwrap().pos);
+        assert!(mapping.get(128).is_none());
+
+        assert_eq!(
+            LedMapping::concat(&[(&grid, left), (&grid, right), (&grid, right)]).err(),
+            Some(MappingError::TooManyLeds {
+                led_count: 192,
+                max: MAX_LEDS
+            })
+        );
+    }
+
+    #[test]
+    fn test_nearest_picks_cell_led() {
+        let mapping = LedMapping::grid_16x8();
+
+        // Just off the center of cell (5, 3)
+        let pick = Vec2::new(Fixed::from_f32(5.6), Fixed::from_f32(3.4));
+        assert_eq!(mapping.nearest(pick), Some(3 * 16 + 5));
+
+        // Halfway between cells (5, 3) and (6, 3) goes to the lower index
+        let pick = Vec2::new(Fixed::from_i32(6), Fixed::from_f32(3.5));
+        assert_eq!(mapping.nearest(pick), Some(3 * 16 + 5));
+
+        // Well outside the panel
+        let pick = Vec2::new(Fixed::from_i32(40), Fixed::from_i32(40));
+        assert_eq!(mapping.nearest(pick), None);
+    }
+
+    #[test]
+    fn test_linear_sampling_brightens_blends() {
+        // Black pixel then white pixel, with every LED halfway between them
+        let rgb_2d = [0, 0, 0, 255, 255, 255];
+        let mapping = LedMapping::new([LedMap::new_fixed(Fixed::HALF.0, 0); 128]);
+
+        let mut gamma = [0u8; 3];
+        apply_2d_mapping(&rgb_2d, &mut gamma, &mapping, 2, 1);
+        let mut linear = [0u8; 3];
+        apply_2d_mapping_in(&rgb_2d, &mut linear, &mapping, 2, 1, SampleSpace::Linear);
+
+        assert_eq!(gamma, [127; 3]);
+        assert!(linear[0] > gamma[0]);
+        assert_eq!(linear, [188; 3]);
+    }
+
     #[test]
     fn test_apply_mapping() {
         // Create a 16x8 RGB buffer (width=16, height=8)
@@ -1,6 +1,18 @@
 /// Image sampling with bilinear interpolation
 use lp_script::fixed::{Fixed, FIXED_SHIFT};
 
+use crate::test_engine::srgb::{linear_to_srgb, srgb_to_linear};
+
+/// Color space bilinear sampling blends in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SampleSpace {
+    /// Blend the gamma-encoded bytes directly (fast, darkens blends)
+    #[default]
+    Gamma,
+    /// Decode to linear light, blend, and re-encode
+    Linear,
+}
+
 /// Bilinear interpolation of a single channel
 ///
 /// # Arguments
@@ -38,6 +50,32 @@ pub fn bilinear_interp_channel(
     result.clamp(0, 255) as u8
 }
 
+/// Bilinear interpolation of a single sRGB channel in linear light
+///
+/// Same as `bilinear_interp_channel`, but decodes the corners with
+/// `srgb_to_linear` first and encodes the result again.
+pub fn bilinear_interp_channel_linear(
+    c00: u8,
+    c10: u8,
+    c01: u8,
+    c11: u8,
+    x_frac: Fixed,
+    y_frac: Fixed,
+) -> u8 {
+    let c00 = srgb_to_linear(c00) as i64;
+    let c10 = srgb_to_linear(c10) as i64;
+    let c01 = srgb_to_linear(c01) as i64;
+    let c11 = srgb_to_linear(c11) as i64;
+    let x_frac = x_frac.0 as i64;
+    let y_frac = y_frac.0 as i64;
+
+    let top = c00 + (((c10 - c00) * x_frac) >> FIXED_SHIFT);
+    let bottom = c01 + (((c11 - c01) * x_frac) >> FIXED_SHIFT);
+    let result = top + (((bottom - top) * y_frac) >> FIXED_SHIFT);
+
+    linear_to_srgb(result.clamp(0, u16::MAX as i64) as u16)
+}
+
 /// Bilinear interpolation of an RGB pixel
 ///
 /// # Arguments
@@ -65,24 +103,51 @@ pub fn bilinear_interp_rgb(
     ]
 }
 
-/// Sample an RGB pixel from a 2D buffer at fixed-point coordinates
+/// Most channels per pixel `sample_bilinear` supports (RGBW)
+pub const MAX_CHANNELS: usize = 4;
+
+/// Sample a pixel from a 2D buffer with `channels` bytes per pixel
 ///
 /// # Arguments
-/// * `buffer` - RGB buffer (width * height * 3 bytes)
+/// * `buffer` - Pixel buffer (width * height * channels bytes)
 /// * `x` - X coordinate in fixed-point
 /// * `y` - Y coordinate in fixed-point
 /// * `width` - Buffer width in pixels
 /// * `height` - Buffer height in pixels
+/// * `channels` - Bytes per pixel, 1..=MAX_CHANNELS (1 gray, 3 RGB, 4 RGBW)
 ///
 /// # Returns
-/// Sampled RGB pixel [r, g, b], or [0, 0, 0] if out of bounds
-pub fn sample_rgb_bilinear(
+/// The sampled channels in the first `channels` entries (the rest are 0),
+/// or all zeros if out of bounds
+pub fn sample_bilinear(
     buffer: &[u8],
     x: Fixed,
     y: Fixed,
     width: usize,
     height: usize,
-) -> [u8; 3] {
+    channels: usize,
+) -> [u8; MAX_CHANNELS] {
+    sample_bilinear_in(buffer, x, y, width, height, channels, SampleSpace::Gamma)
+}
+
+/// `sample_bilinear`, blending in the given color space
+pub fn sample_bilinear_in(
+    buffer: &[u8],
+    x: Fixed,
+    y: Fixed,
+    width: usize,
+    height: usize,
+    channels: usize,
+    space: SampleSpace,
+) -> [u8; MAX_CHANNELS] {
+    assert!(
+        (1..=MAX_CHANNELS).contains(&channels),
+        "channels must be 1..={}, got {}",
+        MAX_CHANNELS,
+        channels
+    );
+    let mut out = [0u8; MAX_CHANNELS];
+
     // Get integer and fractional parts
     let x_int = x.to_i32() as usize;
     let y_int = y.to_i32() as usize;
@@ -91,13 +156,14 @@ pub fn sample_rgb_bilinear(
 
     // Bounds check - must be within the image
     if x_int >= width || y_int >= height {
-        return [0, 0, 0];
+        return out;
     }
 
     // If we're exactly on a pixel (no fractional part), just return that pixel
     if x_frac.0 == 0 && y_frac.0 == 0 {
-        let idx = (y_int * width + x_int) * 3;
-        return [buffer[idx], buffer[idx + 1], buffer[idx + 2]];
+        let idx = (y_int * width + x_int) * channels;
+        out[..channels].copy_from_slice(&buffer[idx..idx + channels]);
+        return out;
     }
 
     // For bilinear interpolation, we need the neighboring pixels
@@ -106,17 +172,48 @@ pub fn sample_rgb_bilinear(
     let y_int_1 = if y_int + 1 < height { y_int + 1 } else { y_int };
 
     // Sample 4 neighboring pixels (some may be the same if on edge)
-    let idx_00 = (y_int * width + x_int) * 3;
-    let idx_10 = (y_int * width + x_int_1) * 3;
-    let idx_01 = (y_int_1 * width + x_int) * 3;
-    let idx_11 = (y_int_1 * width + x_int_1) * 3;
-
-    let rgb00 = [buffer[idx_00], buffer[idx_00 + 1], buffer[idx_00 + 2]];
-    let rgb10 = [buffer[idx_10], buffer[idx_10 + 1], buffer[idx_10 + 2]];
-    let rgb01 = [buffer[idx_01], buffer[idx_01 + 1], buffer[idx_01 + 2]];
-    let rgb11 = [buffer[idx_11], buffer[idx_11 + 1], buffer[idx_11 + 2]];
+    let idx_00 = (y_int * width + x_int) * channels;
+    let idx_10 = (y_int * width + x_int_1) * channels;
+    let idx_01 = (y_int_1 * width + x_int) * channels;
+    let idx_11 = (y_int_1 * width + x_int_1) * channels;
+
+    let interp = match space {
+        SampleSpace::Gamma => bilinear_interp_channel,
+        SampleSpace::Linear => bilinear_interp_channel_linear,
+    };
+    for (c, value) in out.iter_mut().enumerate().take(channels) {
+        *value = interp(
+            buffer[idx_00 + c],
+            buffer[idx_10 + c],
+            buffer[idx_01 + c],
+            buffer[idx_11 + c],
+            x_frac,
+            y_frac,
+        );
+    }
+    out
+}
 
-    bilinear_interp_rgb(rgb00, rgb10, rgb01, rgb11, x_frac, y_frac)
+/// Sample an RGB pixel from a 2D buffer at fixed-point coordinates
+///
+/// # Arguments
+/// * `buffer` - RGB buffer (width * height * 3 bytes)
+/// * `x` - X coordinate in fixed-point
+/// * `y` - Y coordinate in fixed-point
+/// * `width` - Buffer width in pixels
+/// * `height` - Buffer height in pixels
+///
+/// # Returns
+/// Sampled RGB pixel [r, g, b], or [0, 0, 0] if out of bounds
+pub fn sample_rgb_bilinear(
+    buffer: &[u8],
+    x: Fixed,
+    y: Fixed,
+    width: usize,
+    height: usize,
+) -> [u8; 3] {
+    let [r, g, b, _] = sample_bilinear(buffer, x, y, width, height, 3);
+    [r, g, b]
 }
 
 #[cfg(all(test, not(feature = "use-libm")))]
@@ -258,6 +355,51 @@ mod tests {
         assert_eq!(result, [0, 0, 0]);
     }
 
+    #[test]
+    fn test_sample_bilinear_grayscale() {
+        // 2x2 gray image
+        let buffer = [0, 100, 100, 200];
+
+        let result = sample_bilinear(&buffer, Fixed::ONE, Fixed::ZERO, 2, 2, 1);
+        assert_eq!(result, [100, 0, 0, 0]);
+
+        let half = Fixed::HALF;
+        let result = sample_bilinear(&buffer, half, half, 2, 2, 1);
+        assert_eq!(result, [100, 0, 0, 0]);
+
+        // Out of bounds
+        let result = sample_bilinear(&buffer, 2.0f32.to_fixed(), Fixed::ZERO, 2, 2, 1);
+        assert_eq!(result, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_sample_bilinear_rgbw() {
+        // 2x1 RGBW image: red, white-only
+        let buffer = [255, 0, 0, 0, 0, 0, 0, 200];
+
+        let result = sample_bilinear(&buffer, Fixed::ZERO, Fixed::ZERO, 2, 1, 4);
+        assert_eq!(result, [255, 0, 0, 0]);
+
+        let result = sample_bilinear(&buffer, Fixed::ONE, Fixed::ZERO, 2, 1, 4);
+        assert_eq!(result, [0, 0, 0, 200]);
+
+        // Halfway between the two pixels
+        let result = sample_bilinear(&buffer, Fixed::HALF, Fixed::ZERO, 2, 1, 4);
+        assert_eq!(result, [127, 0, 0, 100]);
+    }
+
+    #[test]
+    fn test_sample_bilinear_matches_rgb_sampler() {
+        let buffer = [
+            255, 0, 0, 0, 255, 0, // Row 0: Red, Green
+            0, 0, 255, 255, 255, 0, // Row 1: Blue, Yellow
+        ];
+        let (x, y) = (0.25f32.to_fixed(), 0.75f32.to_fixed());
+        let [r, g, b, w] = sample_bilinear(&buffer, x, y, 2, 2, 3);
+        assert_eq!([r, g, b], sample_rgb_bilinear(&buffer, x, y, 2, 2));
+        assert_eq!(w, 0);
+    }
+
     #[test]
     fn test_circular_panel_led_81_and_89() {
         // Reproduce the exact issue: LEDs 81 and 89 in circular_panel_7ring mapping
@@ -4,7 +4,7 @@ use lp_script::fixed::trig::{cos, sin};
 use lp_script::fixed::{Fixed, ToFixed, FIXED_ONE, FIXED_SHIFT};
 
 /// Spiral LED mappings
-use super::{LedMap, LedMapping};
+use super::{LedMap, LedMapping, MAX_LEDS};
 
 impl LedMapping {
     /// Create a spiral mapping with configurable number of arms
@@ -70,7 +70,10 @@ impl LedMapping {
             *map = LedMap::new_fixed(x_fixed, y_fixed);
         }
 
-        LedMapping { maps }
+        LedMapping {
+            maps,
+            len: MAX_LEDS,
+        }
     }
 
     /// Create a 3-arm spiral (convenience function)
@@ -22,16 +22,26 @@ pub mod test_scene;
 /// Power limiting and brightness control
 pub mod power_limit;
 
+/// sRGB encode/decode for blending in linear light
+pub mod srgb;
+
+/// Per-channel statistics of rendered frames
+pub mod frame_stats;
+
 // Re-export commonly used items
 // LoadSource is now defined in lp-script::vm::opcodes::load
+pub use frame_stats::{frame_stats, frame_stats_with_config, ChannelStats, FrameStats};
 #[allow(deprecated)]
 pub use lp_script::fixed::{
     fixed_from_f32, fixed_from_int, fixed_to_f32, Fixed, FIXED_ONE, FIXED_SHIFT,
 };
 pub use lp_script::vm::opcodes::LoadSource;
-pub use mapping::{apply_2d_mapping, LedMapping, MappingConfig};
+pub use mapping::{
+    apply_2d_mapping, apply_2d_mapping_in, LedMapping, MappingConfig, MappingError, SampleSpace,
+    MAX_LEDS,
+};
 pub use palette::{rgb_buffer_from_greyscale, Palette};
 pub use pipeline::{
-    BufferFormat, BufferRef, FxPipeline, FxPipelineConfig, PipelineError, PipelineStep,
-    RuntimeOptions,
+    fixed_to_u8_saturating, i32_to_u8_saturating, BufferFormat, BufferRef, FlipAxis, FxPipeline,
+    FxPipelineConfig, PipelineError, PipelineStep, RuntimeOptions,
 };
@@ -1,5 +1,14 @@
 /// Palette-based RGB conversion
+extern crate alloc;
+use alloc::format;
+use alloc::vec::Vec;
+
 use lp_script::fixed::{Fixed, FIXED_ONE, FIXED_SHIFT};
+use lp_script::shared::Type;
+use lp_script::vm::{LpsVm, VmLimits};
+use lp_script::LpsProgram;
+
+use super::pipeline::{fixed_to_u8_saturating, i32_to_u8_saturating, PipelineError};
 
 /// RGB color representation
 #[derive(Debug, Clone, Copy)]
@@ -15,40 +24,80 @@ impl Rgb {
     }
 }
 
-/// 16-entry palette for color mapping
+/// Palette for color mapping: evenly spaced stops, interpolated linearly
+///
+/// The built-in palettes have 16 stops; `from_program` builds a larger LUT.
 #[derive(Clone)]
 pub struct Palette {
-    colors: [Rgb; 16],
+    colors: Vec<Rgb>, // At least 2 stops
 }
 
 impl Palette {
     /// Create a new palette from an array of colors
     pub fn new(colors: [Rgb; 16]) -> Self {
-        Palette { colors }
+        Palette {
+            colors: colors.to_vec(),
+        }
+    }
+
+    /// Build a palette by sampling an LPS expression returning `vec3`
+    ///
+    /// The program is run `samples` times (at least 2) with `t` (also
+    /// `time` and `uv.x`) stepping evenly from 0 to 1; each channel is
+    /// clamped to 0..1. E.g. `mix(vec3(0.0, 0.0, 1.0), vec3(1.0, 0.5, 0.0), t)`.
+    pub fn from_program(program: &LpsProgram, samples: usize) -> Result<Self, PipelineError> {
+        let main_func = program
+            .main_function()
+            .ok_or(PipelineError::InvalidProgram(
+                "No main function found".into(),
+            ))?;
+        if main_func.return_type != Type::Vec3 {
+            return Err(PipelineError::TypeMismatch {
+                expected: Type::Vec3,
+                actual: main_func.return_type.clone(),
+                context: "Palette program output".into(),
+            });
+        }
+
+        let mut vm = LpsVm::new(program, VmLimits::default())
+            .map_err(|e| PipelineError::RuntimeError(format!("{}", e)))?;
+        let samples = samples.max(2);
+        let last = (samples - 1) as i64;
+        let mut colors = Vec::with_capacity(samples);
+        for i in 0..samples {
+            let t = Fixed(((i as i64 * FIXED_ONE as i64) / last) as i32);
+            let color = vm
+                .run_vec3(t, Fixed::ZERO, t)
+                .map_err(|e| PipelineError::RuntimeError(vm.format_error(&e)))?;
+            colors.push(Rgb::new(
+                fixed_to_u8_saturating(color.x),
+                fixed_to_u8_saturating(color.y),
+                fixed_to_u8_saturating(color.z),
+            ));
+        }
+        Ok(Palette { colors })
     }
 
     /// Create a rainbow palette
     pub fn rainbow() -> Self {
-        Palette {
-            colors: [
-                Rgb::new(255, 0, 0),   // Red
-                Rgb::new(255, 64, 0),  // Red-Orange
-                Rgb::new(255, 128, 0), // Orange
-                Rgb::new(255, 192, 0), // Orange-Yellow
-                Rgb::new(255, 255, 0), // Yellow
-                Rgb::new(192, 255, 0), // Yellow-Green
-                Rgb::new(128, 255, 0), // Light Green
-                Rgb::new(0, 255, 0),   // Green
-                Rgb::new(0, 255, 128), // Green-Cyan
-                Rgb::new(0, 255, 255), // Cyan
-                Rgb::new(0, 128, 255), // Cyan-Blue
-                Rgb::new(0, 0, 255),   // Blue
-                Rgb::new(128, 0, 255), // Blue-Purple
-                Rgb::new(192, 0, 255), // Purple
-                Rgb::new(255, 0, 192), // Purple-Magenta
-                Rgb::new(255, 0, 128), // Magenta-Red
-            ],
-        }
+        Palette::new([
+            Rgb::new(255, 0, 0),   // Red
+            Rgb::new(255, 64, 0),  // Red-Orange
+            Rgb::new(255, 128, 0), // Orange
+            Rgb::new(255, 192, 0), // Orange-Yellow
+            Rgb::new(255, 255, 0), // Yellow
+            Rgb::new(192, 255, 0), // Yellow-Green
+            Rgb::new(128, 255, 0), // Light Green
+            Rgb::new(0, 255, 0),   // Green
+            Rgb::new(0, 255, 128), // Green-Cyan
+            Rgb::new(0, 255, 255), // Cyan
+            Rgb::new(0, 128, 255), // Cyan-Blue
+            Rgb::new(0, 0, 255),   // Blue
+            Rgb::new(128, 0, 255), // Blue-Purple
+            Rgb::new(192, 0, 255), // Purple
+            Rgb::new(255, 0, 192), // Purple-Magenta
+            Rgb::new(255, 0, 128), // Magenta-Red
+        ])
     }
 
     /// Create a grayscale palette (black to white)
@@ -58,23 +107,24 @@ impl Palette {
             let val = (i * 255 / 15) as u8;
             *color = Rgb::new(val, val, val);
         }
-        Palette { colors }
+        Palette::new(colors)
     }
 
     /// Get interpolated color for a value in range [0, 1] (fixed-point)
     #[inline(always)]
     pub fn get_color(&self, value: Fixed) -> Rgb {
         // Clamp value to 0..1 range
-        let clamped = value.0.clamp(0, FIXED_ONE);
+        let clamped = value.0.clamp(0, FIXED_ONE) as i64;
 
-        // Map to palette range [0, 15]
-        // value * 15.0 in fixed-point
-        let scaled = ((clamped as i64 * 15) >> FIXED_SHIFT) as i32;
-        let index = if scaled > 14 { 14 } else { scaled as usize }; // Max index is 14 for interpolation
+        // Map to palette range [0, last]
+        // value * last in fixed-point
+        let last = self.colors.len() as i64 - 1;
+        let scaled = (clamped * last) >> FIXED_SHIFT;
+        let index = scaled.min(last - 1) as usize; // Max index is last - 1 for interpolation
 
         // Get fractional part for interpolation (0..FIXED_ONE)
-        // frac = (value * 15) - floor(value * 15)
-        let frac_fixed = (clamped * 15) - (index as i32 * FIXED_ONE);
+        // frac = (value * last) - floor(value * last)
+        let frac_fixed = ((clamped * last) - (index as i64 * FIXED_ONE as i64)) as i32;
 
         // Interpolate between current and next color using fixed-point
         // result = c1 + (c2 - c1) * frac
@@ -86,9 +136,9 @@ impl Palette {
         let b = c1.b as i32 + (((c2.b as i32 - c1.b as i32) * frac_fixed) >> FIXED_SHIFT);
 
         Rgb {
-            r: r as u8,
-            g: g as u8,
-            b: b as u8,
+            r: i32_to_u8_saturating(r),
+            g: i32_to_u8_saturating(g),
+            b: i32_to_u8_saturating(b),
         }
     }
 }
@@ -98,7 +148,7 @@ impl Palette {
 /// # Arguments
 /// * `greyscale` - Input grayscale buffer (fixed-point values 0..1)
 /// * `rgb_buffer` - Output RGB buffer (3 bytes per pixel: R, G, B)
-/// * `palette` - 16-entry palette for color mapping
+/// * `palette` - Palette for color mapping
 pub fn rgb_buffer_from_greyscale(greyscale: &[Fixed], rgb_buffer: &mut [u8], palette: &Palette) {
     let pixel_count = greyscale.len();
     assert!(rgb_buffer.len() >= pixel_count * 3, "RGB buffer too small");
@@ -134,6 +184,41 @@ mod tests {
         assert!(c1.r > 200);
     }
 
+    #[test]
+    fn test_from_program_gradient() {
+        let program = lp_script::compile_expr("vec3(t, 1.0 - t, 0.5)").unwrap();
+        let palette = Palette::from_program(&program, 256).unwrap();
+        assert_eq!(palette.colors.len(), 256);
+
+        let first = palette.get_color(Fixed::ZERO);
+        assert_eq!((first.r, first.g, first.b), (0, 255, 128));
+        let last = palette.get_color(Fixed::ONE);
+        assert_eq!((last.r, last.g, last.b), (255, 0, 128));
+        let mid = palette.get_color(Fixed::HALF);
+        assert!((126..=129).contains(&mid.r), "mid red was {}", mid.r);
+    }
+
+    #[test]
+    fn test_from_program_requires_vec3() {
+        let program = lp_script::compile_expr("t").unwrap();
+        assert!(matches!(
+            Palette::from_program(&program, 16),
+            Err(PipelineError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_program_reports_runtime_errors() {
+        // Int division by zero fails when run rather than at compile time
+        let program = lp_script::compile_expr("vec3(float(1 / int(t * 0.5)))").unwrap();
+        match Palette::from_program(&program, 4) {
+            Err(PipelineError::RuntimeError(message)) => {
+                assert!(message.contains("Division by zero"), "{}", message)
+            }
+            _ => panic!("expected a runtime error"),
+        }
+    }
+
     #[test]
     fn test_rgb_conversion() {
         let palette = Palette::rainbow();
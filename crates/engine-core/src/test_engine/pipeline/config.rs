@@ -66,7 +66,8 @@ impl FxPipelineConfig {
                     }
                 }
 
-                PipelineStep::BlurStep { input, output, .. } => {
+                PipelineStep::BlurStep { input, output, .. }
+                | PipelineStep::FlipStep { input, output, .. } => {
                     // Validate input buffer
                     if input.buffer_idx >= self.num_buffers {
                         return Err(PipelineError::InvalidBufferRef {
@@ -83,7 +84,7 @@ impl FxPipelineConfig {
                         });
                     }
 
-                    // Blur accepts any format (no format validation needed)
+                    // Blur and flip accept any format (no format validation needed)
                 }
             }
         }
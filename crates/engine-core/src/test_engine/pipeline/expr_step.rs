@@ -4,7 +4,7 @@ use alloc::vec;
 
 use lp_script::fixed::Fixed;
 use lp_script::shared::Type;
-use lp_script::vm::{execute_program_lps, execute_program_lps_vec3};
+use lp_script::vm::{execute_program_lps, execute_program_lps_rgb8, CoordConfig};
 use lp_script::LpsProgram;
 
 use super::rgb_utils::grey_to_i32;
@@ -53,7 +53,14 @@ pub fn execute_expr_step(
         BufferFormat::ImageGrey => {
             // Execute VM program into a temporary greyscale buffer
             let mut temp_grey: vec::Vec<Fixed> = vec![Fixed::ZERO; width * height];
-            execute_program_lps(program, &mut temp_grey, width, height, time);
+            execute_program_lps(
+                program,
+                &mut temp_grey,
+                width,
+                height,
+                time,
+                &CoordConfig::default(),
+            );
 
             // Write greyscale results to output buffer
             for i in 0..temp_grey.len() {
@@ -63,24 +70,21 @@ pub fn execute_expr_step(
             Ok(())
         }
         BufferFormat::ImageRgb => {
-            // Execute VM program into a temporary Vec3 buffer
-            // Vec3 outputs are 3x the size (r, g, b per pixel)
-            let mut temp_vec3: vec::Vec<Fixed> = vec![Fixed::ZERO; width * height * 3];
-            execute_program_lps_vec3(program, &mut temp_vec3, width, height, time);
+            // Execute VM program into a temporary 8-bit RGB buffer
+            // (r, g, b bytes per pixel)
+            let mut temp_rgb8: vec::Vec<u8> = vec![0; width * height * 3];
+            execute_program_lps_rgb8(
+                program,
+                &mut temp_rgb8,
+                width,
+                height,
+                time,
+                &CoordConfig::default(),
+            );
 
             // Pack RGB triplets into output buffer
-            for i in 0..(width * height) {
-                let r_fixed = temp_vec3[i * 3];
-                let g_fixed = temp_vec3[i * 3 + 1];
-                let b_fixed = temp_vec3[i * 3 + 2];
-
-                // Convert Fixed (0..1) to u8 (0..255)
-                let r = (r_fixed.to_f32().clamp(0.0, 1.0) * 255.0) as u8;
-                let g = (g_fixed.to_f32().clamp(0.0, 1.0) * 255.0) as u8;
-                let b = (b_fixed.to_f32().clamp(0.0, 1.0) * 255.0) as u8;
-
-                // Pack into i32
-                output_data[i] = super::rgb_utils::pack_rgb(r, g, b);
+            for (packed, rgb) in output_data.iter_mut().zip(temp_rgb8.chunks_exact(3)) {
+                *packed = super::rgb_utils::pack_rgb(rgb[0], rgb[1], rgb[2]);
             }
 
             Ok(())
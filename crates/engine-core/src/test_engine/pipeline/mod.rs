@@ -1,6 +1,7 @@
 /// Flexible pipeline system for LED effects
 extern crate alloc;
 use alloc::vec::Vec;
+use core::time::Duration;
 
 use lp_script::fixed::Fixed;
 use lp_script::LpsProgram;
@@ -15,7 +16,9 @@ pub mod runtime;
 
 pub use config::FxPipelineConfig;
 pub use expr_step::{execute_expr_step, validate_expr_program_type};
-pub use rgb_utils::{grey_to_i32, i32_to_grey, pack_rgb, unpack_rgb};
+pub use rgb_utils::{
+    fixed_to_u8_saturating, grey_to_i32, i32_to_grey, i32_to_u8_saturating, pack_rgb, unpack_rgb,
+};
 pub use runtime::FxPipeline;
 
 /// Buffer format identifier
@@ -78,10 +81,27 @@ pub enum PipelineStep {
     BlurStep {
         input: BufferRef,
         output: BufferRef,
-        radius: Fixed, // Blur radius in fixed-point (pixels)
+        radius: Fixed, // Blur radius as a fraction of the average image dimension
+    },
+
+    /// Mirror the buffer, e.g. for a strip wired in reverse
+    FlipStep {
+        input: BufferRef,
+        output: BufferRef,
+        axis: FlipAxis,
     },
 }
 
+/// Direction of a `FlipStep`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlipAxis {
+    Horizontal, // Mirror each row left to right
+    Vertical,   // Mirror the rows top to bottom
+    /// Reverse the pixels in index order, the order they reach the LEDs
+    /// in; for a 2D buffer this is the same as flipping both axes
+    Index,
+}
+
 /// Pipeline validation and execution errors
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PipelineError {
@@ -102,6 +122,8 @@ pub enum PipelineError {
         context: alloc::string::String,
     },
     InvalidProgram(alloc::string::String),
+    /// A program failed while running, with the VM's error message
+    RuntimeError(alloc::string::String),
     Unimplemented(alloc::string::String),
 }
 
@@ -111,6 +133,8 @@ pub struct RuntimeOptions {
     pub width: usize,
     pub height: usize,
     pub power_config: PowerLimitConfig,
+    /// Ramp brightness up from 0 over this long after the first frame (zero disables)
+    pub soft_start: Duration,
 }
 
 impl RuntimeOptions {
@@ -123,7 +147,9 @@ impl RuntimeOptions {
                 power_budget_ma: 1000,
                 led_white_power_ma: 50,
                 led_idle_power_ma: 1,
+                min_scale: Fixed::ZERO,
             },
+            soft_start: Duration::ZERO,
         }
     }
 
@@ -136,6 +162,13 @@ impl RuntimeOptions {
             width,
             height,
             power_config,
+            soft_start: Duration::ZERO,
         }
     }
+
+    /// Ramp brightness up from 0 over `soft_start` when rendering begins
+    pub const fn with_soft_start(mut self, soft_start: Duration) -> Self {
+        self.soft_start = soft_start;
+        self
+    }
 }
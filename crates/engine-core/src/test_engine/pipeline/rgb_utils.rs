@@ -1,5 +1,5 @@
 /// RGB packing/unpacking utilities for 32-bit buffers
-use lp_script::fixed::{Fixed, FIXED_ONE};
+use lp_script::fixed::Fixed;
 
 /// Pack RGB into 0x00RRGGBB format
 #[inline(always)]
@@ -28,19 +28,30 @@ pub fn i32_to_grey(val: i32) -> Fixed {
     Fixed(val)
 }
 
+/// Convert a 0..1 fixed-point channel to 0..255, rounding to nearest
+///
+/// Values outside 0..1 clamp to 0 or 255 instead of wrapping.
+#[inline(always)]
+pub fn fixed_to_u8_saturating(value: Fixed) -> u8 {
+    value.to_u8_saturating()
+}
+
+/// Clamp an integer channel to 0..255 instead of wrapping
+#[inline(always)]
+pub fn i32_to_u8_saturating(value: i32) -> u8 {
+    value.clamp(0, 255) as u8
+}
+
 /// Convert greyscale fixed-point to RGB (grey, grey, grey) packed as i32
 #[inline(always)]
 pub fn grey_to_rgb_i32(grey: Fixed) -> i32 {
-    let clamped = grey.0.clamp(0, FIXED_ONE);
-    // Convert to 0-255 range: (clamped * 255) / FIXED_ONE
-    // Use i64 to avoid overflow
-    let byte_val = ((clamped as i64 * 255) / FIXED_ONE as i64) as u8;
+    let byte_val = fixed_to_u8_saturating(grey);
     pack_rgb(byte_val, byte_val, byte_val)
 }
 
 #[cfg(all(test, not(feature = "use-libm")))]
 mod tests {
-    use lp_script::fixed::ToFixed;
+    use lp_script::fixed::{ToFixed, FIXED_ONE};
 
     use super::*;
 
@@ -75,9 +86,38 @@ mod tests {
         let (r, g, b) = unpack_rgb(white);
         assert_eq!((r, g, b), (255, 255, 255));
 
-        // 0.5 should be mid-grey
+        // 0.5 should be mid-grey (127.5 rounds up)
         let grey = grey_to_rgb_i32(Fixed::HALF);
         let (r, g, b) = unpack_rgb(grey);
-        assert_eq!((r, g, b), (127, 127, 127));
+        assert_eq!((r, g, b), (128, 128, 128));
+    }
+
+    #[test]
+    fn test_fixed_to_u8_saturating_boundaries() {
+        assert_eq!(fixed_to_u8_saturating(Fixed(-1)), 0);
+        assert_eq!(fixed_to_u8_saturating(Fixed(i32::MIN)), 0);
+        assert_eq!(fixed_to_u8_saturating(Fixed::ZERO), 0);
+        assert_eq!(fixed_to_u8_saturating(Fixed(FIXED_ONE - 1)), 255);
+        assert_eq!(fixed_to_u8_saturating(Fixed::ONE), 255);
+        assert_eq!(fixed_to_u8_saturating(Fixed(FIXED_ONE + 1)), 255);
+        assert_eq!(fixed_to_u8_saturating(Fixed(i32::MAX)), 255);
+    }
+
+    #[test]
+    fn test_fixed_to_u8_saturating_rounds_to_nearest() {
+        // 1/255 of full scale, just under and over half a step
+        let step = FIXED_ONE / 255;
+        assert_eq!(fixed_to_u8_saturating(Fixed(step)), 1);
+        assert_eq!(fixed_to_u8_saturating(Fixed(step / 2 - 1)), 0);
+        assert_eq!(fixed_to_u8_saturating(Fixed(step / 2 + 2)), 1);
+    }
+
+    #[test]
+    fn test_i32_to_u8_saturating_boundaries() {
+        assert_eq!(i32_to_u8_saturating(-1), 0);
+        assert_eq!(i32_to_u8_saturating(0), 0);
+        assert_eq!(i32_to_u8_saturating(255), 255);
+        assert_eq!(i32_to_u8_saturating(256), 255);
+        assert_eq!(i32_to_u8_saturating(i32::MAX), 255);
     }
 }
@@ -1,13 +1,17 @@
 /// Pipeline runtime with buffer management and step execution
 extern crate alloc;
+use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt::{self, Write};
 
-use lp_script::fixed::Fixed;
+use lp_script::fixed::{Fixed, FixedAccumulator};
 
 use super::super::palette::Palette;
 use super::config::FxPipelineConfig;
-use super::rgb_utils::{i32_to_grey, pack_rgb};
-use super::{Buffer, BufferFormat, BufferRef, PipelineError, PipelineStep, RuntimeOptions};
+use super::rgb_utils::{grey_to_i32, i32_to_grey, pack_rgb};
+use super::{
+    Buffer, BufferFormat, BufferRef, FlipAxis, PipelineError, PipelineStep, RuntimeOptions,
+};
 
 /// Runtime pipeline state
 pub struct FxPipeline {
@@ -68,6 +72,14 @@ impl FxPipeline {
                 } => {
                     self.execute_blur_step(input, output, *radius, step_idx)?;
                 }
+
+                PipelineStep::FlipStep {
+                    input,
+                    output,
+                    axis,
+                } => {
+                    self.execute_flip_step(input, output, *axis);
+                }
             }
         }
 
@@ -146,12 +158,7 @@ impl FxPipeline {
         // Clone input data for reading
         let input_data = input_buf.data.clone();
 
-        // Convert radius from fixed-point to pixel radius (relative to image size)
-        // radius is a fraction (e.g., 0.2 = 20% of image dimension)
-        // Multiply by average dimension to get absolute pixels
-        let avg_dimension = (self.width + self.height) / 2;
-        let radius_pixels_fp = (radius.0 as i64 * avg_dimension as i64) >> 16; // Fixed-point multiply
-        let radius_pixels = radius_pixels_fp.max(1) as usize; // Clamp to at least 1 pixel
+        let radius_pixels = blur_radius_pixels(radius, self.width, self.height);
 
         // Box blur (faster than Gaussian for embedded)
         let output_buf = &mut self.buffers[output.buffer_idx];
@@ -164,24 +171,19 @@ impl FxPipeline {
                         let mut sum_r = 0i64;
                         let mut sum_g = 0i64;
                         let mut sum_b = 0i64;
-                        let mut count = 0i64;
-
-                        // Sample within blur radius
-                        for ky in -(radius_pixels as isize)..=(radius_pixels as isize) {
-                            for kx in -(radius_pixels as isize)..=(radius_pixels as isize) {
-                                let sx =
-                                    (x as isize + kx).max(0).min(self.width as isize - 1) as usize;
-                                let sy =
-                                    (y as isize + ky).max(0).min(self.height as isize - 1) as usize;
-                                let idx = sy * self.width + sx;
 
+                        // Sample within blur radius, repeating edge pixels
+                        let xs = blur_samples(x, radius_pixels, self.width);
+                        for sy in blur_samples(y, radius_pixels, self.height) {
+                            for sx in xs.clone() {
+                                let idx = sy * self.width + sx;
                                 let (r, g, b) = super::rgb_utils::unpack_rgb(input_data[idx]);
                                 sum_r += r as i64;
                                 sum_g += g as i64;
                                 sum_b += b as i64;
-                                count += 1;
                             }
                         }
+                        let count = ((2 * radius_pixels + 1) * (2 * radius_pixels + 1)) as i64;
 
                         let avg_r = (sum_r / count) as u8;
                         let avg_g = (sum_g / count) as u8;
@@ -196,24 +198,17 @@ impl FxPipeline {
                 // Blur greyscale
                 for y in 0..self.height {
                     for x in 0..self.width {
-                        let mut sum = 0i64;
-                        let mut count = 0i64;
-
-                        for ky in -(radius_pixels as isize)..=(radius_pixels as isize) {
-                            for kx in -(radius_pixels as isize)..=(radius_pixels as isize) {
-                                let sx =
-                                    (x as isize + kx).max(0).min(self.width as isize - 1) as usize;
-                                let sy =
-                                    (y as isize + ky).max(0).min(self.height as isize - 1) as usize;
-                                let idx = sy * self.width + sx;
+                        let mut acc = FixedAccumulator::new();
 
-                                sum += input_data[idx] as i64;
-                                count += 1;
+                        let xs = blur_samples(x, radius_pixels, self.width);
+                        for sy in blur_samples(y, radius_pixels, self.height) {
+                            for sx in xs.clone() {
+                                acc.add(i32_to_grey(input_data[sy * self.width + sx]));
                             }
                         }
 
                         let idx = y * self.width + x;
-                        output_buf.data[idx] = (sum / count) as i32;
+                        output_buf.data[idx] = grey_to_i32(acc.mean());
                     }
                 }
             }
@@ -223,6 +218,43 @@ impl FxPipeline {
         Ok(())
     }
 
+    /// Execute a flip step
+    fn execute_flip_step(&mut self, input: &BufferRef, output: &BufferRef, axis: FlipAxis) {
+        let input_buf = &self.buffers[input.buffer_idx];
+        let format = input_buf.last_format;
+
+        // Clone input data for reading, the output may be the same buffer
+        let input_data = input_buf.data.clone();
+
+        let output_buf = &mut self.buffers[output.buffer_idx];
+        let (width, height) = (self.width, self.height);
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = match axis {
+                    FlipAxis::Horizontal => (width - 1 - x, y),
+                    FlipAxis::Vertical => (x, height - 1 - y),
+                    FlipAxis::Index => (width - 1 - x, height - 1 - y),
+                };
+                output_buf.data[y * width + x] = input_data[sy * width + sx];
+            }
+        }
+
+        output_buf.set_format(format);
+    }
+
+    /// Human-readable listing of every step, for debugging wrong output
+    ///
+    /// Shows each step's kind and buffers; expression steps also list the
+    /// opcodes of every function in their program.
+    pub fn describe(&self) -> String {
+        let mut out = String::new();
+        for (step_idx, step) in self.steps.iter().enumerate() {
+            // Writing to a String can't fail
+            let _ = describe_step(&mut out, step_idx, step);
+        }
+        out
+    }
+
     /// Get a buffer by index
     pub fn get_buffer(&self, idx: usize) -> Option<&Buffer> {
         self.buffers.get(idx)
@@ -268,3 +300,99 @@ impl FxPipeline {
         }
     }
 }
+
+fn describe_step(out: &mut String, step_idx: usize, step: &PipelineStep) -> fmt::Result {
+    match step {
+        PipelineStep::ExprStep {
+            program,
+            output,
+            params,
+        } => {
+            write!(
+                out,
+                "step {}: expr -> {}, params [",
+                step_idx,
+                BufferDesc(output)
+            )?;
+            for (i, param) in params.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write!(out, "{}", BufferDesc(param))?;
+            }
+            writeln!(out, "]")?;
+            for func in &program.functions {
+                writeln!(out, "  fn {} -> {:?}", func.name, func.return_type)?;
+                for (pc, op) in func.opcodes.iter().enumerate() {
+                    writeln!(out, "    {:4}: {:?}", pc, op)?;
+                }
+            }
+        }
+        PipelineStep::PaletteStep { input, output, .. } => {
+            writeln!(
+                out,
+                "step {}: palette {} -> {}",
+                step_idx,
+                BufferDesc(input),
+                BufferDesc(output)
+            )?;
+        }
+        PipelineStep::BlurStep {
+            input,
+            output,
+            radius,
+        } => {
+            writeln!(
+                out,
+                "step {}: blur {} -> {}, radius {}",
+                step_idx,
+                BufferDesc(input),
+                BufferDesc(output),
+                radius
+            )?;
+        }
+        PipelineStep::FlipStep {
+            input,
+            output,
+            axis,
+        } => {
+            writeln!(
+                out,
+                "step {}: flip {} -> {}, {:?}",
+                step_idx,
+                BufferDesc(input),
+                BufferDesc(output),
+                axis
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Formats a `BufferRef` as `buffer 0 (ImageGrey)`
+struct BufferDesc<'a>(&'a BufferRef);
+
+impl fmt::Display for BufferDesc<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "buffer {} ({:?})", self.0.buffer_idx, self.0.format)
+    }
+}
+
+/// Convert a blur radius to whole pixels for a `width` x `height` image
+///
+/// `radius` is a fraction of the average image dimension (e.g. 0.2 = 20%).
+/// The result is at least 1 pixel and at most the larger dimension, so a
+/// huge radius on a small buffer doesn't take a huge window per pixel.
+fn blur_radius_pixels(radius: Fixed, width: usize, height: usize) -> usize {
+    let avg_dimension = (width + height) / 2;
+    let radius_pixels = (radius.0 as i64 * avg_dimension as i64) >> 16; // Fixed-point multiply
+    radius_pixels.clamp(1, width.max(height).max(1) as i64) as usize
+}
+
+/// Coordinates along one axis of the blur window around `center`, where
+/// samples past the edges repeat the edge pixel
+fn blur_samples(center: usize, radius: usize, len: usize) -> impl Iterator<Item = usize> + Clone {
+    let last = len as isize - 1;
+    (-(radius as isize)..=radius as isize)
+        .map(move |offset| (center as isize + offset).clamp(0, last) as usize)
+}
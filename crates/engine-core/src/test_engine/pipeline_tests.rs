@@ -6,7 +6,7 @@ mod pipeline_tests {
     use lp_script::parse_expr;
 
     use crate::test_engine::{
-        BufferFormat, BufferRef, FxPipeline, FxPipelineConfig, Palette, PipelineStep,
+        BufferFormat, BufferRef, FlipAxis, FxPipeline, FxPipelineConfig, Palette, PipelineStep,
         RuntimeOptions,
     };
 
@@ -118,4 +118,138 @@ mod pipeline_tests {
             );
         }
     }
+
+    #[test]
+    fn test_blur_large_radius_is_clamped() {
+        // Gradient, then a blur of the given radius into buffer 1
+        let render_blur = |radius: Fixed| {
+            let config = FxPipelineConfig::new(
+                2,
+                vec![
+                    PipelineStep::ExprStep {
+                        program: parse_expr("xNorm"),
+                        output: BufferRef::new(0, BufferFormat::ImageGrey),
+                        params: vec![],
+                    },
+                    PipelineStep::BlurStep {
+                        input: BufferRef::new(0, BufferFormat::ImageGrey),
+                        output: BufferRef::new(1, BufferFormat::ImageGrey),
+                        radius,
+                    },
+                ],
+            );
+            let mut pipeline =
+                FxPipeline::new(config, RuntimeOptions::new(4, 4)).expect("Valid config");
+            pipeline.render(Fixed::ZERO).expect("Render should succeed");
+            pipeline
+        };
+
+        // A radius far wider than the 4x4 image blurs like one the size of the image
+        let wide = render_blur(Fixed::from_i32(100));
+        let image_sized = render_blur(Fixed::ONE);
+        assert_eq!(
+            wide.get_buffer(1).expect("Buffer 1 should exist").data,
+            image_sized
+                .get_buffer(1)
+                .expect("Buffer 1 should exist")
+                .data
+        );
+
+        // and still smooths the gradient, keeping values within the input range
+        let spread = |data: &[i32]| {
+            let min = *data.iter().min().unwrap();
+            let max = *data.iter().max().unwrap();
+            (min, max)
+        };
+        let (in_min, in_max) = spread(&wide.get_buffer(0).expect("Buffer 0 should exist").data);
+        let (out_min, out_max) = spread(&wide.get_buffer(1).expect("Buffer 1 should exist").data);
+        assert!(out_min >= in_min && out_max <= in_max);
+        assert!(
+            out_max - out_min < in_max - in_min,
+            "Blur should narrow the spread {}..{}, got {}..{}",
+            in_min,
+            in_max,
+            out_min,
+            out_max
+        );
+    }
+
+    #[test]
+    fn test_describe_lists_steps_and_opcodes() {
+        let config = FxPipelineConfig::new(
+            2,
+            vec![
+                PipelineStep::ExprStep {
+                    program: parse_expr("xNorm * 0.5"),
+                    output: BufferRef::new(0, BufferFormat::ImageGrey),
+                    params: vec![],
+                },
+                PipelineStep::PaletteStep {
+                    input: BufferRef::new(0, BufferFormat::ImageGrey),
+                    output: BufferRef::new(1, BufferFormat::ImageRgb),
+                    palette: Palette::rainbow(),
+                },
+            ],
+        );
+        let pipeline = FxPipeline::new(config, RuntimeOptions::new(4, 4)).expect("Valid config");
+
+        let description = pipeline.describe();
+        assert!(description.contains("step 0: expr -> buffer 0 (ImageGrey), params []"));
+        assert!(description.contains("fn main -> Fixed"));
+        assert!(description.contains("0: Load(XNorm)"));
+        assert!(description.contains("2: MulFixed"));
+        assert!(description.contains("step 1: palette buffer 0 (ImageGrey) -> buffer 1 (ImageRgb)"));
+    }
+
+    /// Render a 3x2 buffer with a distinct value per pixel, then flip it
+    /// along `axis`; returns the (input, flipped) pixels
+    fn render_flipped(axis: FlipAxis) -> (Vec<i32>, Vec<i32>) {
+        let config = FxPipelineConfig::new(
+            2,
+            vec![
+                PipelineStep::ExprStep {
+                    program: parse_expr("xNorm * 0.25 + yNorm * 0.5"),
+                    output: BufferRef::new(0, BufferFormat::ImageGrey),
+                    params: vec![],
+                },
+                PipelineStep::FlipStep {
+                    input: BufferRef::new(0, BufferFormat::ImageGrey),
+                    output: BufferRef::new(1, BufferFormat::ImageGrey),
+                    axis,
+                },
+            ],
+        );
+        let mut pipeline =
+            FxPipeline::new(config, RuntimeOptions::new(3, 2)).expect("Valid config");
+        pipeline.render(Fixed::ZERO).expect("Render should succeed");
+
+        let input = pipeline.get_buffer(0).unwrap().data.clone();
+        for (i, val) in input.iter().enumerate() {
+            assert!(!input[..i].contains(val), "Pixels should be distinct");
+        }
+        let flipped = pipeline.get_buffer(1).unwrap();
+        assert_eq!(flipped.last_format, BufferFormat::ImageGrey);
+        (input, flipped.data.clone())
+    }
+
+    #[test]
+    fn test_flip_horizontal() {
+        let (input, flipped) = render_flipped(FlipAxis::Horizontal);
+        let expected: Vec<i32> = [2, 1, 0, 5, 4, 3].iter().map(|&i| input[i]).collect();
+        assert_eq!(flipped, expected);
+    }
+
+    #[test]
+    fn test_flip_vertical() {
+        let (input, flipped) = render_flipped(FlipAxis::Vertical);
+        let expected: Vec<i32> = [3, 4, 5, 0, 1, 2].iter().map(|&i| input[i]).collect();
+        assert_eq!(flipped, expected);
+    }
+
+    #[test]
+    fn test_flip_index_reverses_led_order() {
+        let (input, flipped) = render_flipped(FlipAxis::Index);
+        let expected: Vec<i32> = input.iter().rev().copied().collect();
+        assert_eq!(flipped, expected);
+    }
 }
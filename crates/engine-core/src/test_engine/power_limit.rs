@@ -2,8 +2,11 @@
 ///
 /// This module applies brightness scaling, gamma correction, and power limiting
 /// to prevent overdrawing current from the power supply.
+use lp_script::fixed::Fixed;
 use smart_leds::RGB8;
 
+use super::pipeline::i32_to_u8_saturating;
+
 /// Default gamma correction curve (2.2)
 const GAMMA_TABLE: [u8; 256] = generate_gamma_table();
 
@@ -35,6 +38,11 @@ pub struct PowerLimitConfig {
     pub led_white_power_ma: u32,
     /// Idle power draw per LED (all channels 0) in milliamps
     pub led_idle_power_ma: u32,
+    /// Lowest scale factor power limiting may apply (0.0..=1.0)
+    ///
+    /// Keeps very bright frames from being crushed to near-black, at the cost
+    /// of exceeding the budget for those frames. 0.0 always meets the budget.
+    pub min_scale: Fixed,
 }
 
 impl Default for PowerLimitConfig {
@@ -44,6 +52,7 @@ impl Default for PowerLimitConfig {
             power_budget_ma: 1000,
             led_white_power_ma: 50,
             led_idle_power_ma: 1,
+            min_scale: Fixed::ZERO,
         }
     }
 }
@@ -51,12 +60,8 @@ impl Default for PowerLimitConfig {
 /// Apply brightness scaling to a single channel using fixed-point fixed
 #[inline]
 fn apply_brightness(value: u8, brightness_256: u32) -> u8 {
-    let scaled = (value as u32 * brightness_256) / 256;
-    if scaled > 255 {
-        255
-    } else {
-        scaled as u8
-    }
+    let scaled = (value as u64 * brightness_256 as u64) / 256;
+    i32_to_u8_saturating(scaled.min(i32::MAX as u64) as i32)
 }
 
 /// Apply gamma correction to a single channel using lookup table
@@ -71,7 +76,7 @@ fn apply_gamma(value: u8) -> u8 {
 /// * `led` - RGB8 LED color after gamma correction
 /// * `white_power_ma` - Power at full white (255, 255, 255)
 /// * `idle_power_ma` - Power at off (0, 0, 0)
-fn calculate_led_power(led: RGB8, white_power_ma: u32, idle_power_ma: u32) -> u32 {
+pub(crate) fn calculate_led_power(led: RGB8, white_power_ma: u32, idle_power_ma: u32) -> u32 {
     // Power is roughly proportional to the sum of RGB values
     // At (0,0,0): idle_power_ma
     // At (255,255,255): white_power_ma
@@ -82,6 +87,24 @@ fn calculate_led_power(led: RGB8, white_power_ma: u32, idle_power_ma: u32) -> u3
     idle_power_ma + ((white_power_ma - idle_power_ma) * channel_sum) / max_sum
 }
 
+/// Scale factor (16.16) that brings `total_power_ma` within budget
+///
+/// Never below `config.min_scale`, so the result may overshoot the budget.
+fn power_scale_factor(total_power_ma: u32, config: &PowerLimitConfig) -> u64 {
+    // Use 16-bit fixed point: scale_factor_65536 = (budget * 65536) / total
+    // This gives us more precision than 8-bit
+    let scale_factor_65536 = ((config.power_budget_ma as u64) << 16) / (total_power_ma as u64);
+    let min_scale_65536 = config.min_scale.0.clamp(0, Fixed::ONE.0) as u64;
+    scale_factor_65536.max(min_scale_65536)
+}
+
+/// Scale a channel by a 16.16 factor, rounding down
+#[inline]
+fn scale_channel(value: u8, scale_factor_65536: u64) -> u8 {
+    let scaled = (value as u64 * scale_factor_65536) >> 16;
+    i32_to_u8_saturating(scaled.min(i32::MAX as u64) as i32)
+}
+
 /// Process LED buffer with brightness, gamma, and power limiting
 ///
 /// # Arguments
@@ -110,14 +133,12 @@ pub fn apply_power_limit(leds: &mut [RGB8], config: &PowerLimitConfig) {
 
     // Step 4: If over budget, scale down using integer fixed
     if total_power_ma > config.power_budget_ma {
-        // Use 16-bit fixed point: scale_factor_65536 = (budget * 65536) / total
-        // This gives us more precision than 8-bit
-        let scale_factor_65536 = ((config.power_budget_ma as u64) << 16) / (total_power_ma as u64);
+        let scale_factor_65536 = power_scale_factor(total_power_ma, config);
 
         for led in leds.iter_mut() {
-            led.r = ((led.r as u64 * scale_factor_65536) >> 16) as u8;
-            led.g = ((led.g as u64 * scale_factor_65536) >> 16) as u8;
-            led.b = ((led.b as u64 * scale_factor_65536) >> 16) as u8;
+            led.r = scale_channel(led.r, scale_factor_65536);
+            led.g = scale_channel(led.g, scale_factor_65536);
+            led.b = scale_channel(led.b, scale_factor_65536);
         }
     }
 }
@@ -160,7 +181,7 @@ pub fn apply_power_limit_to_bytes(bytes: &mut [u8], config: &PowerLimitConfig) {
 
     // Step 4: If over budget, scale down using integer fixed
     if total_power_ma > config.power_budget_ma {
-        let scale_factor_65536 = ((config.power_budget_ma as u64) << 16) / (total_power_ma as u64);
+        let scale_factor_65536 = power_scale_factor(total_power_ma, config);
 
         for byte in bytes.iter_mut() {
             *byte = ((*byte as u64 * scale_factor_65536) >> 16) as u8;
@@ -275,6 +296,7 @@ mod tests {
             power_budget_ma: 1000,
             led_white_power_ma: 50,
             led_idle_power_ma: 1,
+            min_scale: Fixed::ZERO,
         };
 
         apply_power_limit(&mut leds, &config);
@@ -320,4 +342,35 @@ mod tests {
         let expected_r = apply_gamma(apply_brightness(100, 128));
         assert_eq!(leds[0].r, expected_r);
     }
+
+    #[test]
+    fn test_power_limiting_respects_min_scale() {
+        // 100 white LEDs draw 5000mA against a 1000mA budget: scale 0.2
+        let white = RGB8 {
+            r: 255,
+            g: 255,
+            b: 255,
+        };
+        let config = PowerLimitConfig {
+            min_scale: Fixed::from_f32(0.5),
+            ..Default::default()
+        };
+
+        let mut leds = vec![white; 100];
+        apply_power_limit(&mut leds, &config);
+        for led in &leds {
+            assert_eq!(led.r, 127, "scale should be floored at 0.5");
+            assert_eq!(led.g, 127);
+            assert_eq!(led.b, 127);
+        }
+
+        let mut bytes = vec![255u8; 300];
+        apply_power_limit_to_bytes(&mut bytes, &config);
+        assert!(bytes.iter().all(|&b| b == 127));
+
+        // Without the floor the frame is scaled all the way to the budget
+        let mut leds = vec![white; 100];
+        apply_power_limit(&mut leds, &PowerLimitConfig::default());
+        assert_eq!(leds[0].r, 50);
+    }
 }
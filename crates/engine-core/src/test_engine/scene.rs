@@ -1,6 +1,7 @@
 /// Scene configuration and runtime system
 extern crate alloc;
 use alloc::vec::Vec;
+use core::time::Duration;
 
 use crate::test_engine::power_limit::{apply_power_limit_to_bytes, PowerLimitConfig};
 use crate::test_engine::{
@@ -38,6 +39,10 @@ pub struct SceneRuntime {
     pub height: usize,
     rgb_bytes_buffer: Vec<u8>, // Reusable buffer for RGB conversion
     pub power_config: PowerLimitConfig,
+    /// Brightness ramp duration at startup (zero disables)
+    pub soft_start: Duration,
+    start_time: Option<Fixed>, // Time of the first rendered frame
+    last_tick: Option<Fixed>,  // Time of the most recent `tick`
 }
 
 impl SceneRuntime {
@@ -57,6 +62,9 @@ impl SceneRuntime {
             height: options.height,
             rgb_bytes_buffer,
             power_config: options.power_config,
+            soft_start: options.soft_start,
+            start_time: None,
+            last_tick: None,
         })
     }
 
@@ -66,10 +74,28 @@ impl SceneRuntime {
     }
 
     /// Render a single frame
+    ///
+    /// Convenience for `tick(time)` followed by `render_output`.
     pub fn render(&mut self, time: Fixed, output_buffer_idx: usize) -> Result<(), PipelineError> {
-        // Render the pipeline
+        self.tick(time)?;
+        self.render_output(output_buffer_idx);
+        Ok(())
+    }
+
+    /// Advance the scene to `time`, evaluating the pipeline without producing LED output
+    ///
+    /// Lets simulation run at a different rate than output: call this as
+    /// often as state should advance and `render_output` when a frame is sent.
+    pub fn tick(&mut self, time: Fixed) -> Result<(), PipelineError> {
         self.pipeline.render(time)?;
+        self.last_tick = Some(time);
+        Ok(())
+    }
 
+    /// Produce `led_output` from the pipeline state of the last `tick`
+    ///
+    /// Before the first tick the pipeline buffers are blank, so the output is black.
+    pub fn render_output(&mut self, output_buffer_idx: usize) {
         // Extract RGB buffer into our reusable bytes buffer (no allocation)
         self.pipeline
             .extract_rgb_bytes(output_buffer_idx, &mut self.rgb_bytes_buffer);
@@ -84,8 +110,115 @@ impl SceneRuntime {
         );
 
         // Apply power limiting and brightness directly to LED output buffer
-        apply_power_limit_to_bytes(&mut self.led_output, &self.power_config);
+        let power_config = match self.last_tick {
+            Some(time) => self.soft_start_power_config(time),
+            None => self.power_config,
+        };
+        apply_power_limit_to_bytes(&mut self.led_output, &power_config);
+    }
 
-        Ok(())
+    /// Power config with brightness scaled by the soft-start ramp at `time`
+    ///
+    /// The ramp starts at the first rendered frame; afterwards this returns
+    /// `power_config` unchanged.
+    fn soft_start_power_config(&mut self, time: Fixed) -> PowerLimitConfig {
+        let ramp_ms = self.soft_start.as_millis() as i64;
+        if ramp_ms == 0 {
+            return self.power_config;
+        }
+
+        let start = *self.start_time.get_or_insert(time);
+        // Fixed seconds to milliseconds
+        let elapsed_ms = ((time - start).0 as i64 * 1000) >> 16;
+        if elapsed_ms >= ramp_ms {
+            return self.power_config;
+        }
+
+        let elapsed_ms = elapsed_ms.max(0);
+        PowerLimitConfig {
+            brightness_256: (self.power_config.brightness_256 as i64 * elapsed_ms / ramp_ms) as u32,
+            ..self.power_config
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_engine::{BufferFormat, BufferRef, PipelineStep};
+
+    fn white_scene(soft_start: Duration) -> SceneRuntime {
+        expr_scene("vec3(1.0, 1.0, 1.0)", soft_start)
+    }
+
+    fn expr_scene(expr: &str, soft_start: Duration) -> SceneRuntime {
+        let pipeline_config = FxPipelineConfig::new(
+            2,
+            alloc::vec![PipelineStep::ExprStep {
+                program: lp_script::parse_expr(expr),
+                output: BufferRef::new(1, BufferFormat::ImageRgb),
+                params: alloc::vec![],
+            }],
+        );
+        let config = SceneConfig::new(pipeline_config, MappingConfig::Grid16x8);
+
+        let mut options = RuntimeOptions::new(16, 8).with_soft_start(soft_start);
+        options.power_config.power_budget_ma = 100_000; // No power limiting
+        SceneRuntime::new(config, options).expect("Valid scene config")
+    }
+
+    fn max_output(scene: &SceneRuntime) -> u8 {
+        scene.led_output.iter().copied().max().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_soft_start_ramps_brightness() {
+        let mut scene = white_scene(Duration::from_millis(1000));
+
+        // Ramp starts at the first frame, not at time zero
+        scene.render(Fixed::from_f32(5.0), 1).unwrap();
+        assert_eq!(max_output(&scene), 0);
+
+        scene.render(Fixed::from_f32(5.5), 1).unwrap();
+        let mid = max_output(&scene);
+        assert!(
+            mid > 0 && mid < 255,
+            "half way should be partial, got {}",
+            mid
+        );
+
+        scene.render(Fixed::from_f32(6.0), 1).unwrap();
+        assert_eq!(max_output(&scene), 255);
+
+        scene.render(Fixed::from_f32(60.0), 1).unwrap();
+        assert_eq!(max_output(&scene), 255);
+    }
+
+    #[test]
+    fn test_no_soft_start_is_full_brightness() {
+        let mut scene = white_scene(Duration::ZERO);
+        scene.render(Fixed::ZERO, 1).unwrap();
+        assert_eq!(max_output(&scene), 255);
+    }
+
+    #[test]
+    fn test_render_output_reflects_latest_tick() {
+        let mut scene = expr_scene("vec3(time, time, time)", Duration::ZERO);
+        scene.tick(Fixed::from_f32(0.25)).unwrap();
+        scene.tick(Fixed::from_f32(0.75)).unwrap();
+        scene.render_output(1);
+
+        let mut reference = expr_scene("vec3(time, time, time)", Duration::ZERO);
+        reference.render(Fixed::from_f32(0.75), 1).unwrap();
+
+        assert_eq!(scene.led_output, reference.led_output);
+        assert!(max_output(&scene) > 128);
+    }
+
+    #[test]
+    fn test_render_output_before_tick_is_black() {
+        let mut scene = white_scene(Duration::ZERO);
+        scene.render_output(1);
+        assert_eq!(max_output(&scene), 0);
     }
 }
@@ -0,0 +1,90 @@
+//! Fallible string formatting.
+//!
+//! `format!` aborts when an allocation fails. `try_format` (and the
+//! [`lp_format!`](crate::lp_format) macro) grow the string with
+//! `try_reserve` under the soft limit instead, so error paths on device can
+//! build dynamic messages without risking the allocation they're reporting.
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::{try_alloc, AllocLimitError};
+
+/// Writer that reserves before every write and records allocation failure
+struct TryWriter {
+    buf: String,
+    alloc_failed: bool,
+}
+
+impl fmt::Write for TryWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.buf.try_reserve(s.len()).is_err() {
+            self.alloc_failed = true;
+            return Err(fmt::Error);
+        }
+        self.buf.push_str(s);
+        Ok(())
+    }
+}
+
+/// Format `args` into a new `String`, respecting the soft memory limit
+///
+/// Prefer the [`lp_format!`](crate::lp_format) macro. Like `format!`, this
+/// panics if a `Display` implementation reports an error on its own.
+pub fn try_format(args: fmt::Arguments<'_>) -> Result<String, AllocLimitError> {
+    try_alloc("format", "string", || {
+        let mut writer = TryWriter {
+            buf: String::new(),
+            alloc_failed: false,
+        };
+        match fmt::write(&mut writer, args) {
+            Ok(()) => Ok(writer.buf),
+            Err(_) if writer.alloc_failed => Err(AllocLimitError::SoftLimitExceeded),
+            Err(_) => panic!("a formatting trait implementation returned an error"),
+        }
+    })
+}
+
+/// `format!` that returns `Result<String, AllocLimitError>` instead of aborting
+///
+/// ```rust
+/// let msg = lp_alloc::lp_format!("stack overflow at pc {}", 42).unwrap();
+/// assert_eq!(msg, "stack overflow at pc 42");
+/// ```
+#[macro_export]
+macro_rules! lp_format {
+    ($($arg:tt)*) => {
+        $crate::try_format(::core::format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{set_hard_limit, set_soft_limit, with_alloc_limit, AllocLimitError};
+
+    #[test]
+    fn test_lp_format_within_limit() {
+        set_hard_limit(10 * 1024 * 1024);
+        set_soft_limit(10 * 1024 * 1024);
+
+        let result = with_alloc_limit(10 * 1024 * 1024, || {
+            lp_format!("{} at pc {:04}: {:?}", "StackOverflow", 7, [1, 2])
+        });
+        assert_eq!(result.unwrap(), "StackOverflow at pc 0007: [1, 2]");
+    }
+
+    #[test]
+    fn test_lp_format_over_soft_limit() {
+        set_hard_limit(10 * 1024 * 1024);
+        set_soft_limit(10 * 1024 * 1024);
+
+        let padding = vec![b'x'; 64 * 1024];
+        let text = core::str::from_utf8(&padding).unwrap();
+        let result = with_alloc_limit(crate::allocated_bytes() + 1024, || {
+            lp_format!("message: {}", text)
+        });
+        assert_eq!(result, Err(AllocLimitError::SoftLimitExceeded));
+    }
+}
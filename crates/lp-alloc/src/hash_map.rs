@@ -0,0 +1,403 @@
+//! Fallible open-addressing hash map.
+//!
+//! `hashbrown`'s `HashMap` isn't available in `no_std` without a dependency,
+//! and `BTreeMap` lookups are O(log n) pointer chases. [`LpHashMap`] stores
+//! its entries in one linearly probed slot array that grows under the soft
+//! limit, so a failed growth is an [`AllocLimitError`] instead of an abort.
+
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::hash::{Hash, Hasher};
+use core::mem;
+
+use crate::{try_alloc, AllocLimitError};
+
+/// The Firefox/rustc hasher: fast, `no_std`, and not DoS-resistant
+///
+/// Fine for compiler symbols and other keys that don't come from an attacker.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+const FX_SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+    #[inline]
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(FX_SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            self.add_to_hash(u64::from_le_bytes(word));
+        }
+        for &byte in chunks.remainder() {
+            self.add_to_hash(byte as u64);
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Slot<K, V> {
+    Empty,
+    /// Removed entry; probing continues past it
+    Deleted,
+    Full(K, V),
+}
+
+/// Smallest slot array allocated on first insert
+const MIN_SLOTS: usize = 8;
+
+/// Hash map with linear probing, growing through [`try_alloc`]
+///
+/// Iteration order is unspecified.
+#[derive(Debug, Clone)]
+pub struct LpHashMap<K, V> {
+    /// Power-of-two length, or empty before the first insert
+    slots: Vec<Slot<K, V>>,
+    len: usize,
+    deleted: usize,
+}
+
+impl<K, V> Default for LpHashMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> LpHashMap<K, V> {
+    /// Empty map; allocates nothing until the first insert
+    pub const fn new() -> Self {
+        LpHashMap {
+            slots: Vec::new(),
+            len: 0,
+            deleted: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all entries, keeping the allocation
+    pub fn clear(&mut self) {
+        for slot in &mut self.slots {
+            *slot = Slot::Empty;
+        }
+        self.len = 0;
+        self.deleted = 0;
+    }
+
+    /// Iterate over `(key, value)` pairs in unspecified order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.slots.iter().filter_map(|slot| match slot {
+            Slot::Full(k, v) => Some((k, v)),
+            _ => None,
+        })
+    }
+}
+
+impl<K: Hash + Eq, V> LpHashMap<K, V> {
+    /// Empty map with room for `capacity` entries before it grows
+    pub fn with_capacity(capacity: usize) -> Result<Self, AllocLimitError> {
+        let mut map = Self::new();
+        if capacity > 0 {
+            map.slots = alloc_slots(slots_for(capacity))?;
+        }
+        Ok(map)
+    }
+
+    /// Insert `value` under `key`, returning the value it replaced
+    ///
+    /// Fails, leaving the map unchanged, if growing would exceed the soft limit.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, AllocLimitError> {
+        if let Some(idx) = self.find(&key) {
+            if let Slot::Full(_, old) = &mut self.slots[idx] {
+                return Ok(Some(mem::replace(old, value)));
+            }
+        }
+
+        // Keep at least a quarter of the slots empty so probes terminate quickly
+        if (self.len + self.deleted + 1) * 4 > self.slots.len() * 3 {
+            self.grow()?;
+        }
+
+        let mask = self.slots.len() - 1;
+        let mut idx = hash_of(&key) as usize & mask;
+        loop {
+            match self.slots[idx] {
+                Slot::Empty => break,
+                Slot::Deleted => {
+                    self.deleted -= 1;
+                    break;
+                }
+                Slot::Full(..) => idx = (idx + 1) & mask,
+            }
+        }
+        self.slots[idx] = Slot::Full(key, value);
+        self.len += 1;
+        Ok(None)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match &self.slots[self.find(key)?] {
+            Slot::Full(_, v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.find(key)?;
+        match &mut self.slots[idx] {
+            Slot::Full(_, v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Remove `key`, returning its value if it was present
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.find(key)?;
+        match mem::replace(&mut self.slots[idx], Slot::Deleted) {
+            Slot::Full(_, v) => {
+                self.len -= 1;
+                self.deleted += 1;
+                Some(v)
+            }
+            _ => unreachable!("find only returns full slots"),
+        }
+    }
+
+    /// Index of the full slot holding `key`
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.slots.is_empty() {
+            return None;
+        }
+        let mask = self.slots.len() - 1;
+        let mut idx = hash_of(key) as usize & mask;
+        // Bounded in case every slot is full or deleted
+        for _ in 0..self.slots.len() {
+            match &self.slots[idx] {
+                Slot::Empty => return None,
+                Slot::Full(k, _) if k.borrow() == key => return Some(idx),
+                _ => idx = (idx + 1) & mask,
+            }
+        }
+        None
+    }
+
+    /// Rehash into a larger slot array, dropping tombstones
+    fn grow(&mut self) -> Result<(), AllocLimitError> {
+        let wanted = slots_for(self.len + 1).max(MIN_SLOTS);
+        let mut slots = alloc_slots(wanted)?;
+        mem::swap(&mut self.slots, &mut slots);
+        self.deleted = 0;
+
+        let mask = self.slots.len() - 1;
+        for slot in slots {
+            if let Slot::Full(k, v) = slot {
+                let mut idx = hash_of(&k) as usize & mask;
+                while matches!(self.slots[idx], Slot::Full(..)) {
+                    idx = (idx + 1) & mask;
+                }
+                self.slots[idx] = Slot::Full(k, v);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn hash_of<Q: Hash + ?Sized>(key: &Q) -> u64 {
+    let mut hasher = FxHasher::default();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Slot count that holds `entries` at under 3/4 load
+fn slots_for(entries: usize) -> usize {
+    (entries * 4 / 3 + 1).next_power_of_two().max(MIN_SLOTS)
+}
+
+fn alloc_slots<K, V>(count: usize) -> Result<Vec<Slot<K, V>>, AllocLimitError> {
+    try_alloc("hash_map", "slots", || {
+        let mut slots = Vec::new();
+        slots
+            .try_reserve_exact(count)
+            .map_err(|_| AllocLimitError::SoftLimitExceeded)?;
+        slots.resize_with(count, || Slot::Empty);
+        Ok(slots)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::{allocated_bytes, set_hard_limit, set_soft_limit, with_alloc_limit};
+
+    /// Key whose hash ignores its value, so every key collides
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Colliding(u32);
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            state.write_u32(7);
+        }
+    }
+
+    fn init_limits() {
+        set_hard_limit(10 * 1024 * 1024);
+        set_soft_limit(10 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        init_limits();
+        with_alloc_limit(allocated_bytes() + 1024 * 1024, || {
+            let mut map = LpHashMap::new();
+            assert_eq!(map.insert("uv".to_string(), 2)?, None);
+            assert_eq!(map.insert("time".to_string(), 1)?, None);
+            assert_eq!(map.insert("uv".to_string(), 3)?, Some(2));
+
+            assert_eq!(map.len(), 2);
+            assert_eq!(map.get("uv"), Some(&3));
+            assert_eq!(map.get("coord"), None);
+
+            *map.get_mut("time").unwrap() += 10;
+            assert_eq!(map.remove("time"), Some(11));
+            assert_eq!(map.remove("time"), None);
+            assert!(!map.contains_key("time"));
+            assert_eq!(map.len(), 1);
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_collisions() {
+        init_limits();
+        with_alloc_limit(allocated_bytes() + 1024 * 1024, || {
+            let mut map = LpHashMap::new();
+            for i in 0..20 {
+                map.insert(Colliding(i), i * 10)?;
+            }
+            for i in 0..20 {
+                assert_eq!(map.get(&Colliding(i)), Some(&(i * 10)));
+            }
+
+            // Removing from the middle of a probe chain keeps later keys reachable
+            assert_eq!(map.remove(&Colliding(5)), Some(50));
+            assert_eq!(map.get(&Colliding(19)), Some(&190));
+            assert_eq!(map.get(&Colliding(5)), None);
+
+            // The tombstone is reused
+            map.insert(Colliding(5), 55)?;
+            assert_eq!(map.get(&Colliding(5)), Some(&55));
+            assert_eq!(map.len(), 20);
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_growth() {
+        init_limits();
+        with_alloc_limit(allocated_bytes() + 1024 * 1024, || {
+            let mut map = LpHashMap::new();
+            for i in 0..1000u32 {
+                map.insert(i, i * 2)?;
+            }
+            assert_eq!(map.len(), 1000);
+            for i in 0..1000u32 {
+                assert_eq!(map.get(&i), Some(&(i * 2)));
+            }
+
+            let mut keys: Vec<u32> = map.iter().map(|(k, _)| *k).collect();
+            keys.sort_unstable();
+            assert_eq!(keys, (0..1000).collect::<Vec<_>>());
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_growth_failure_leaves_map_intact() {
+        init_limits();
+        let mut map: LpHashMap<u32, u32> = LpHashMap::new();
+        map.insert(1, 10).unwrap();
+
+        // No headroom: inserts that fit the first slot array succeed, growing fails
+        let result = with_alloc_limit(allocated_bytes(), || {
+            for i in 2..100 {
+                map.insert(i, i * 10)?;
+            }
+            Ok(())
+        });
+        assert_eq!(result, Err(AllocLimitError::SoftLimitExceeded));
+        assert!(map.len() < 99);
+        for i in 1..=map.len() as u32 {
+            assert_eq!(map.get(&i), Some(&(i * 10)));
+        }
+    }
+}
@@ -7,6 +7,9 @@
 //! - **Hard memory limit**: Panics when exceeded
 //! - **Soft memory limit**: Checked via `try_alloc` and `with_alloc_limit`
 //! - **Memory tracking**: Tracks total allocated memory
+//! - **Fallible formatting**: `lp_format!` builds a `String` without aborting on failure
+//! - **Fallible hash map**: `LpHashMap` grows under the soft limit instead of aborting
+//! - **Fallible slice copies**: `try_to_vec` copies a slice into a new `Vec` without aborting
 //!
 //! ## Example
 //!
@@ -47,9 +50,15 @@ extern crate alloc;
 
 mod allocator;
 mod error;
+mod format;
+mod hash_map;
+mod vec;
 
 pub use allocator::LimitedAllocator;
 pub use error::AllocLimitError;
+pub use format::try_format;
+pub use hash_map::{FxHasher, LpHashMap};
+pub use vec::{try_vec_from_slice, TryToVec};
 
 /// The default allocator instance. Use this as the `#[global_allocator]` to enable tracking.
 pub static ALLOCATOR: LimitedAllocator = LimitedAllocator::new();
@@ -276,7 +285,6 @@ mod tests {
         assert!(matches!(result, Err(AllocLimitError::SoftLimitExceeded)));
 
         // Verify limit was restored even after error
-        assert_eq!(allocated_bytes() <= old_limit || true, true); // Limit should be restored
         let current_limit = ALLOCATOR.soft_limit();
         assert_eq!(current_limit, old_limit);
     }
@@ -0,0 +1,67 @@
+//! Fallible slice-to-`Vec` conversion.
+//!
+//! `slice.to_vec()` and `Vec::from(slice)` abort when the allocation fails.
+//! [`try_vec_from_slice`] (and [`TryToVec::try_to_vec`]) reserve the whole
+//! buffer with `try_reserve_exact` under the soft limit first.
+
+use alloc::vec::Vec;
+
+use crate::{try_alloc, AllocLimitError};
+
+/// Copy `items` into a new `Vec`, respecting the soft memory limit
+pub fn try_vec_from_slice<T: Copy>(items: &[T]) -> Result<Vec<T>, AllocLimitError> {
+    try_alloc("vec", "from_slice", || {
+        let mut vec = Vec::new();
+        vec.try_reserve_exact(items.len())
+            .map_err(|_| AllocLimitError::SoftLimitExceeded)?;
+        vec.extend_from_slice(items);
+        Ok(vec)
+    })
+}
+
+/// `to_vec` that returns `Result<Vec<T>, AllocLimitError>` instead of aborting
+///
+/// ```rust
+/// use lp_alloc::TryToVec;
+///
+/// let bytes = [1u8, 2, 3].try_to_vec().unwrap();
+/// assert_eq!(bytes, [1, 2, 3]);
+/// ```
+pub trait TryToVec<T> {
+    fn try_to_vec(&self) -> Result<Vec<T>, AllocLimitError>;
+}
+
+impl<T: Copy> TryToVec<T> for [T] {
+    fn try_to_vec(&self) -> Result<Vec<T>, AllocLimitError> {
+        try_vec_from_slice(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::{allocated_bytes, set_hard_limit, set_soft_limit, with_alloc_limit};
+
+    #[test]
+    fn test_try_to_vec_copies_bytes() {
+        set_hard_limit(10 * 1024 * 1024);
+        set_soft_limit(10 * 1024 * 1024);
+
+        let bytes: &[u8] = b"lightplayer";
+        let copy = with_alloc_limit(allocated_bytes() + 1024, || bytes.try_to_vec()).unwrap();
+        assert_eq!(copy.as_slice(), bytes);
+        assert_eq!(try_vec_from_slice::<u32>(&[]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_try_to_vec_over_soft_limit() {
+        set_hard_limit(10 * 1024 * 1024);
+        set_soft_limit(10 * 1024 * 1024);
+
+        let big = vec![0u8; 64 * 1024];
+        let result = with_alloc_limit(allocated_bytes() + 1024, || big.try_to_vec());
+        assert_eq!(result, Err(AllocLimitError::SoftLimitExceeded));
+    }
+}
@@ -0,0 +1,99 @@
+/// Overflow-safe sums of many fixed-point values
+use super::fixed::Fixed;
+
+/// Sums `Fixed` values in 64 bits and finalizes back to `Fixed`
+///
+/// A blur window or convolution kernel adds up far more samples than fit in
+/// 16.16 before the final divide; accumulating here keeps the intermediate
+/// sum exact and only narrows the result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FixedAccumulator {
+    sum: i64,    // Raw 16.16 sum of the (weighted) values
+    weight: i64, // Raw 16.16 sum of the weights
+}
+
+impl FixedAccumulator {
+    pub const fn new() -> Self {
+        FixedAccumulator { sum: 0, weight: 0 }
+    }
+
+    /// Add a value with weight 1
+    #[inline(always)]
+    pub fn add(&mut self, value: Fixed) {
+        self.sum += value.0 as i64;
+        self.weight += Fixed::ONE.0 as i64;
+    }
+
+    /// Add `value * weight`, e.g. one tap of a convolution kernel
+    #[inline(always)]
+    pub fn add_weighted(&mut self, value: Fixed, weight: Fixed) {
+        self.sum += (value.0 as i64 * weight.0 as i64) >> Fixed::SHIFT;
+        self.weight += weight.0 as i64;
+    }
+
+    /// The sum of the values, saturated to the `Fixed` range
+    #[inline(always)]
+    pub fn sum(&self) -> Fixed {
+        saturate_raw(self.sum as i128)
+    }
+
+    /// The weighted mean of the values, or zero if nothing was added
+    ///
+    /// Rounds toward zero, like integer division.
+    #[inline(always)]
+    pub fn mean(&self) -> Fixed {
+        if self.weight == 0 {
+            return Fixed::ZERO;
+        }
+        saturate_raw(((self.sum as i128) << Fixed::SHIFT) / self.weight as i128)
+    }
+}
+
+#[inline(always)]
+fn saturate_raw(raw: i128) -> Fixed {
+    Fixed(raw.clamp(i32::MIN as i128, i32::MAX as i128) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mean_of_large_values_does_not_overflow() {
+        let value = Fixed::from_i32(30_000);
+        let mut acc = FixedAccumulator::new();
+        let mut naive = Fixed::ZERO.0;
+        let mut naive_overflowed = false;
+        for _ in 0..100 {
+            acc.add(value);
+            let (next, overflowed) = naive.overflowing_add(value.0);
+            naive = next;
+            naive_overflowed |= overflowed;
+        }
+
+        assert!(
+            naive_overflowed,
+            "a Fixed sum of 100 x 30000 should overflow"
+        );
+        assert_eq!(acc.mean(), value);
+        assert_eq!(acc.sum(), Fixed(i32::MAX));
+    }
+
+    #[test]
+    fn test_weighted_mean() {
+        let mut acc = FixedAccumulator::new();
+        acc.add_weighted(Fixed::from_i32(10), Fixed::from_f32(0.25));
+        acc.add_weighted(Fixed::from_i32(20), Fixed::from_f32(0.75));
+        assert_eq!(acc.mean(), Fixed::from_f32(17.5));
+        assert_eq!(FixedAccumulator::new().mean(), Fixed::ZERO);
+    }
+
+    #[test]
+    fn test_mean_rounds_toward_zero() {
+        let mut acc = FixedAccumulator::new();
+        for raw in [-1, -1, 0] {
+            acc.add(Fixed(raw));
+        }
+        assert_eq!(acc.mean(), Fixed(0));
+    }
+}
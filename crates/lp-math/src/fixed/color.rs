@@ -0,0 +1,121 @@
+/// Color space conversions for fixed-point colors
+use super::fixed::Fixed;
+use super::vec3::Vec3;
+
+/// Convert `(hue, saturation, value)` to `(r, g, b)`
+///
+/// Hue is a fraction of a full turn and wraps, so 0.0 and 1.0 are both red,
+/// 1/3 green and 2/3 blue. Saturation and value are normally 0..1; the
+/// result then is too.
+pub fn hsv2rgb(hsv: Vec3) -> Vec3 {
+    let (s, v) = (hsv.y, hsv.z);
+    let sixths = hsv.x.frac().mul_int(6);
+    let f = sixths.frac();
+
+    let p = v * (Fixed::ONE - s);
+    let q = v * (Fixed::ONE - s * f);
+    let t = v * (Fixed::ONE - s * (Fixed::ONE - f));
+    match sixths.to_i32() {
+        0 => Vec3::new(v, t, p),
+        1 => Vec3::new(q, v, p),
+        2 => Vec3::new(p, v, t),
+        3 => Vec3::new(p, q, v),
+        4 => Vec3::new(t, p, v),
+        _ => Vec3::new(v, p, q),
+    }
+}
+
+/// Convert `(r, g, b)` to `(hue, saturation, value)`, the inverse of `hsv2rgb`
+///
+/// Hue is in 0..1. Grays, which have no hue, get hue and saturation 0.
+pub fn rgb2hsv(rgb: Vec3) -> Vec3 {
+    let (r, g, b) = (rgb.x, rgb.y, rgb.z);
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+    if delta.0 == 0 || max.0 <= 0 {
+        return Vec3::new(Fixed::ZERO, Fixed::ZERO, max);
+    }
+
+    // Sixths of a turn from red, relative to the brightest channel's primary
+    let sixths = if max == r {
+        (g - b) / delta
+    } else if max == g {
+        (b - r) / delta + Fixed::from_i32(2)
+    } else {
+        (r - g) / delta + Fixed::from_i32(4)
+    };
+    // `frac` wraps the magenta side of red, just below 0, around to below 1
+    let hue = (sixths / Fixed::from_i32(6)).frac();
+    Vec3::new(hue, delta / max, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixed::ToFixed;
+
+    fn vec3(x: f32, y: f32, z: f32) -> Vec3 {
+        Vec3::new(x.to_fixed(), y.to_fixed(), z.to_fixed())
+    }
+
+    fn assert_close(actual: Vec3, expected: Vec3) {
+        for (a, e) in [
+            (actual.x, expected.x),
+            (actual.y, expected.y),
+            (actual.z, expected.z),
+        ] {
+            assert!(
+                (a.to_f32() - e.to_f32()).abs() < 0.001,
+                "Expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn test_hsv2rgb_primaries_and_secondaries() {
+        let third = 1.0 / 3.0;
+        assert_eq!(hsv2rgb(vec3(0.0, 1.0, 1.0)), vec3(1.0, 0.0, 0.0));
+        assert_close(hsv2rgb(vec3(1.0 / 6.0, 1.0, 1.0)), vec3(1.0, 1.0, 0.0));
+        assert_close(hsv2rgb(vec3(third, 1.0, 1.0)), vec3(0.0, 1.0, 0.0));
+        assert_close(hsv2rgb(vec3(0.5, 1.0, 1.0)), vec3(0.0, 1.0, 1.0));
+        assert_close(hsv2rgb(vec3(2.0 * third, 1.0, 1.0)), vec3(0.0, 0.0, 1.0));
+        assert_close(hsv2rgb(vec3(5.0 / 6.0, 1.0, 1.0)), vec3(1.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_hsv2rgb_saturation_value_and_wrapping() {
+        assert_eq!(hsv2rgb(vec3(0.7, 0.0, 0.5)), vec3(0.5, 0.5, 0.5));
+        assert_eq!(hsv2rgb(vec3(0.25, 1.0, 0.0)), vec3(0.0, 0.0, 0.0));
+        assert_eq!(hsv2rgb(vec3(0.0, 0.5, 1.0)), vec3(1.0, 0.5, 0.5));
+        // Hue wraps in both directions
+        assert_eq!(hsv2rgb(vec3(1.25, 1.0, 1.0)), hsv2rgb(vec3(0.25, 1.0, 1.0)));
+        assert_eq!(
+            hsv2rgb(vec3(-0.75, 1.0, 1.0)),
+            hsv2rgb(vec3(0.25, 1.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn test_rgb2hsv() {
+        assert_eq!(rgb2hsv(vec3(1.0, 0.0, 0.0)), vec3(0.0, 1.0, 1.0));
+        assert_close(rgb2hsv(vec3(0.0, 0.5, 0.0)), vec3(1.0 / 3.0, 1.0, 0.5));
+        assert_close(rgb2hsv(vec3(0.0, 0.0, 1.0)), vec3(2.0 / 3.0, 1.0, 1.0));
+        assert_close(rgb2hsv(vec3(1.0, 0.0, 0.5)), vec3(11.0 / 12.0, 1.0, 1.0));
+        assert_eq!(rgb2hsv(vec3(0.25, 0.25, 0.25)), vec3(0.0, 0.0, 0.25));
+        assert_eq!(rgb2hsv(vec3(0.0, 0.0, 0.0)), vec3(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for i in 0..24 {
+            let hsv = vec3(
+                i as f32 / 24.0,
+                0.25 + (i % 4) as f32 * 0.25,
+                0.5 + (i % 3) as f32 * 0.25,
+            );
+            assert_close(rgb2hsv(hsv2rgb(hsv)), hsv);
+        }
+    }
+}
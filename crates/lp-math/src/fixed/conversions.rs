@@ -71,6 +71,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_f64_to_fixed() {
         let a = 3.14159f64.to_fixed();
         assert!((a.to_f32() - 3.14159).abs() < 0.001);
@@ -2,6 +2,7 @@
 ///
 /// Core type and conversion utilities for fixed-point fixed.
 use core::cmp::Ord;
+use core::fmt;
 use core::ops::{Add, Div, Mul, Neg, Sub};
 
 /// Fixed-point constants
@@ -9,8 +10,14 @@ const SHIFT: i32 = 16;
 const ONE: i32 = 1 << SHIFT;
 const HALF: i32 = ONE / 2;
 
+/// Fractional digits needed to print any 16.16 value exactly (2^-16 has 16 digits)
+const EXACT_DIGITS: usize = 16;
+
+/// Default fractional digits for `Display` and `Debug`
+const DEFAULT_DIGITS: usize = 5;
+
 /// Fixed-point number (16.16 format)
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Fixed(pub i32);
 
 impl Fixed {
@@ -101,11 +108,91 @@ impl Fixed {
         self.0 >> Self::SHIFT
     }
 
+    /// Convert a 0..1 value to a 0..255 channel, rounding to nearest
+    ///
+    /// Values outside 0..1 clamp to 0 or 255 instead of wrapping.
+    #[inline(always)]
+    pub const fn to_u8_saturating(self) -> u8 {
+        let clamped = if self.0 < 0 {
+            0
+        } else if self.0 > ONE {
+            ONE as i64
+        } else {
+            self.0 as i64
+        };
+        ((clamped * 255 + (ONE as i64 / 2)) >> SHIFT) as u8
+    }
+
     /// Multiply by an integer (more efficient than converting to Fixed first)
     #[inline(always)]
     pub const fn mul_int(self, i: i32) -> Fixed {
         Fixed(self.0 * i)
     }
+
+    /// Fewest fractional digits whose decimal parses back to exactly this value
+    ///
+    /// Never more than 5: a 5-digit decimal is within 0.000005 of the value,
+    /// closer than half of the 2^-16 step.
+    pub fn shortest_digits(self) -> usize {
+        let frac_bits = (self.0 as i64).unsigned_abs() & (ONE as u64 - 1);
+        (0..DEFAULT_DIGITS)
+            .find(|&digits| {
+                let scale = 10u64.pow(digits as u32);
+                let printed = ((frac_bits * scale) + HALF as u64) >> SHIFT;
+                let parsed = ((printed << SHIFT) + scale / 2) / scale;
+                parsed == frac_bits
+            })
+            .unwrap_or(DEFAULT_DIGITS)
+    }
+
+    /// Write the decimal value with `digits` fractional digits
+    ///
+    /// Uses integer arithmetic only, so the output is exact up to rounding of the
+    /// last digit (half away from zero). 16 digits represent any value exactly.
+    pub fn fmt_with_precision(self, f: &mut fmt::Formatter<'_>, digits: usize) -> fmt::Result {
+        let raw = (self.0 as i64).unsigned_abs();
+        let mut int_part = raw >> SHIFT;
+        let frac_bits = raw & (ONE as u64 - 1);
+
+        // Digits beyond EXACT_DIGITS are always zero, so compute at most that many
+        let computed = digits.min(EXACT_DIGITS);
+        let scale = 10u128.pow(computed as u32);
+        let mut frac_part = ((frac_bits as u128 * scale) + (HALF as u128)) >> SHIFT;
+        if frac_part >= scale {
+            int_part += 1;
+            frac_part -= scale;
+        }
+
+        if self.0 < 0 && (int_part != 0 || frac_part != 0) {
+            f.write_str("-")?;
+        }
+        write!(f, "{}", int_part)?;
+        if digits > 0 {
+            write!(f, ".{:0width$}", frac_part, width = computed)?;
+            for _ in computed..digits {
+                f.write_str("0")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Fixed {
+    /// Formats as a decimal; honors `{:.N}` precision (default 5 digits)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = f.precision().unwrap_or(DEFAULT_DIGITS);
+        self.fmt_with_precision(f, digits)
+    }
+}
+
+impl fmt::Debug for Fixed {
+    /// Formats as `Fixed(<decimal>, raw=<i32>)` to aid VM debugging
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Fixed(")?;
+        let digits = f.precision().unwrap_or(DEFAULT_DIGITS);
+        self.fmt_with_precision(f, digits)?;
+        write!(f, ", raw={})", self.0)
+    }
 }
 
 impl Add for Fixed {
@@ -172,6 +259,9 @@ impl Neg for Fixed {
 
 #[cfg(test)]
 mod tests {
+    extern crate std;
+    use std::format;
+
     use super::*;
 
     #[test]
@@ -263,4 +353,47 @@ mod tests {
         assert_eq!(a.min(b).to_f32(), 5.0);
         assert_eq!(a.max(b).to_f32(), 10.0);
     }
+
+    #[test]
+    fn test_fmt_precision_2_digits() {
+        assert_eq!(format!("{:.2}", Fixed::from_f32(1.5)), "1.50");
+        assert_eq!(format!("{:.2}", Fixed::ZERO), "0.00");
+        assert_eq!(format!("{:.2}", Fixed::PI), "3.14");
+        // 0.999 rounds up into the integer part
+        assert_eq!(format!("{:.2}", Fixed(ONE - 1)), "1.00");
+    }
+
+    #[test]
+    fn test_fmt_precision_5_digits() {
+        assert_eq!(format!("{:.5}", Fixed::PI), "3.14159");
+        assert_eq!(format!("{:.5}", Fixed(1)), "0.00002");
+        assert_eq!(format!("{:.5}", Fixed::from_i32(42)), "42.00000");
+        // Default Display precision is 5 digits
+        assert_eq!(format!("{}", Fixed::HALF), "0.50000");
+    }
+
+    #[test]
+    fn test_fmt_negative_values() {
+        assert_eq!(format!("{:.2}", Fixed::from_f32(-2.75)), "-2.75");
+        assert_eq!(format!("{:.5}", Fixed::from_f32(-0.25)), "-0.25000");
+        assert_eq!(format!("{:.0}", Fixed::from_i32(-3)), "-3");
+        // Tiny negatives that round to zero don't print a sign
+        assert_eq!(format!("{:.2}", Fixed(-1)), "0.00");
+        assert_eq!(format!("{:.2}", Fixed(i32::MIN)), "-32768.00");
+    }
+
+    #[test]
+    fn test_fmt_exact_digits() {
+        assert_eq!(format!("{:.16}", Fixed(1)), "0.0000152587890625");
+        assert_eq!(format!("{:.18}", Fixed(1)), "0.000015258789062500");
+    }
+
+    #[test]
+    fn test_debug_shows_raw_value() {
+        assert_eq!(format!("{:?}", Fixed::ONE), "Fixed(1.00000, raw=65536)");
+        assert_eq!(
+            format!("{:.2?}", Fixed::from_f32(-1.5)),
+            "Fixed(-1.50, raw=-98304)"
+        );
+    }
 }
@@ -31,6 +31,21 @@ pub fn smoothstep(edge0: Fixed, edge1: Fixed, x: Fixed) -> Fixed {
     t2 * Fixed::from_i32(3) - t3 * Fixed::from_i32(2)
 }
 
+/// Map `v` from `in_min..in_max` onto `out_min..out_max`, without clamping
+///
+/// Computed at full width, so only the result is rounded. An empty input
+/// range (`in_min == in_max`) returns `out_min`.
+#[inline(always)]
+pub fn remap(v: Fixed, in_min: Fixed, in_max: Fixed, out_min: Fixed, out_max: Fixed) -> Fixed {
+    let in_span = in_max.0 as i128 - in_min.0 as i128;
+    if in_span == 0 {
+        return out_min;
+    }
+    let out_span = out_max.0 as i128 - out_min.0 as i128;
+    let offset = (v.0 as i128 - in_min.0 as i128) * out_span / in_span;
+    Fixed((out_min.0 as i128 + offset) as i32)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,6 +106,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_remap() {
+        let f = Fixed::from_f32;
+        assert_eq!(remap(f(5.0), f(0.0), f(10.0), f(0.0), f(1.0)), f(0.5));
+        assert_eq!(remap(f(0.25), f(0.0), f(1.0), f(-1.0), f(1.0)), f(-0.5));
+        // Reversed output range, extrapolation beyond the input range
+        assert_eq!(remap(f(2.0), f(0.0), f(1.0), f(1.0), f(0.0)), f(-1.0));
+        // Empty input range
+        assert_eq!(remap(f(3.0), f(2.0), f(2.0), f(7.0), f(9.0)), f(7.0));
+    }
+
     #[test]
     fn test_step() {
         let edge = Fixed::from_f32(0.5);
@@ -0,0 +1,254 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// 2x2 matrix for fixed-point math (GLSL-compatible, column-major storage)
+///
+/// Storage layout (column-major):
+/// [m00, m10, m01, m11]
+/// Where m[row][col] represents the element at row `row` and column `col`
+use super::fixed::Fixed;
+use super::trig::{cos, sin};
+use super::vec2::Vec2;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Mat2 {
+    // Column-major storage: [col0, col1] where each column is [x, y]
+    // Storage: [m00, m10, m01, m11]
+    pub m: [Fixed; 4],
+}
+
+impl Mat2 {
+    /// Create a new matrix from 4 Fixed values (column-major order)
+    #[inline(always)]
+    pub const fn new(m00: Fixed, m10: Fixed, m01: Fixed, m11: Fixed) -> Self {
+        Mat2 {
+            m: [m00, m10, m01, m11],
+        }
+    }
+
+    /// Create a matrix from 2 Vec2 columns
+    #[inline(always)]
+    pub fn from_vec2(col0: Vec2, col1: Vec2) -> Self {
+        Mat2::new(col0.x, col0.y, col1.x, col1.y)
+    }
+
+    /// Create identity matrix
+    #[inline(always)]
+    pub const fn identity() -> Self {
+        Mat2::new(Fixed::ONE, Fixed(0), Fixed(0), Fixed::ONE)
+    }
+
+    /// Counter-clockwise rotation by `angle` radians
+    #[inline(always)]
+    pub fn rotation(angle: Fixed) -> Self {
+        let (s, c) = (sin(angle), cos(angle));
+        Mat2::new(c, s, -s, c)
+    }
+
+    /// Get element at row `row` and column `col`
+    #[inline(always)]
+    pub fn get(self, row: usize, col: usize) -> Fixed {
+        self.m[col * 2 + row]
+    }
+
+    /// Set element at row `row` and column `col`
+    #[inline(always)]
+    pub fn set(&mut self, row: usize, col: usize, value: Fixed) {
+        self.m[col * 2 + row] = value;
+    }
+
+    /// Matrix-matrix multiplication
+    #[allow(clippy::should_implement_trait)]
+    #[inline(always)]
+    pub fn mul(self, rhs: Self) -> Self {
+        let a = self;
+        let b = rhs;
+        Mat2::new(
+            a.m[0] * b.m[0] + a.m[2] * b.m[1],
+            a.m[1] * b.m[0] + a.m[3] * b.m[1],
+            a.m[0] * b.m[2] + a.m[2] * b.m[3],
+            a.m[1] * b.m[2] + a.m[3] * b.m[3],
+        )
+    }
+
+    /// Matrix-vector multiplication (mat2 * vec2)
+    #[inline(always)]
+    pub fn mul_vec2(self, v: Vec2) -> Vec2 {
+        Vec2::new(
+            self.m[0] * v.x + self.m[2] * v.y,
+            self.m[1] * v.x + self.m[3] * v.y,
+        )
+    }
+
+    /// Transpose matrix
+    #[inline(always)]
+    pub fn transpose(self) -> Self {
+        Mat2::new(self.m[0], self.m[2], self.m[1], self.m[3])
+    }
+
+    /// Calculate determinant
+    #[inline(always)]
+    pub fn determinant(self) -> Fixed {
+        self.m[0] * self.m[3] - self.m[2] * self.m[1]
+    }
+
+    /// Calculate inverse matrix
+    ///
+    /// Returns None if matrix is singular (determinant is zero)
+    #[inline(always)]
+    pub fn inverse(self) -> Option<Self> {
+        let det = self.determinant();
+        if det.0 == 0 {
+            return None;
+        }
+
+        let inv_det = Fixed::ONE / det;
+        Some(Mat2::new(
+            self.m[3] * inv_det,
+            -self.m[1] * inv_det,
+            -self.m[2] * inv_det,
+            self.m[0] * inv_det,
+        ))
+    }
+}
+
+// Matrix + Matrix
+impl Add for Mat2 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Mat2::new(
+            self.m[0] + rhs.m[0],
+            self.m[1] + rhs.m[1],
+            self.m[2] + rhs.m[2],
+            self.m[3] + rhs.m[3],
+        )
+    }
+}
+
+// Matrix - Matrix
+impl Sub for Mat2 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Mat2::new(
+            self.m[0] - rhs.m[0],
+            self.m[1] - rhs.m[1],
+            self.m[2] - rhs.m[2],
+            self.m[3] - rhs.m[3],
+        )
+    }
+}
+
+// Matrix * Matrix (matrix multiplication)
+impl Mul for Mat2 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        self.mul(rhs)
+    }
+}
+
+// Matrix * Vec2 (matrix-vector multiplication)
+impl Mul<Vec2> for Mat2 {
+    type Output = Vec2;
+
+    #[inline(always)]
+    fn mul(self, rhs: Vec2) -> Vec2 {
+        self.mul_vec2(rhs)
+    }
+}
+
+// Matrix * Scalar
+impl Mul<Fixed> for Mat2 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: Fixed) -> Self {
+        Mat2::new(
+            self.m[0] * rhs,
+            self.m[1] * rhs,
+            self.m[2] * rhs,
+            self.m[3] * rhs,
+        )
+    }
+}
+
+// Matrix / Scalar
+impl Div<Fixed> for Mat2 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn div(self, rhs: Fixed) -> Self {
+        Mat2::new(
+            self.m[0] / rhs,
+            self.m[1] / rhs,
+            self.m[2] / rhs,
+            self.m[3] / rhs,
+        )
+    }
+}
+
+// -Matrix
+impl Neg for Mat2 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Mat2::new(-self.m[0], -self.m[1], -self.m[2], -self.m[3])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity() {
+        let m = Mat2::identity();
+        assert_eq!(m.get(0, 0).to_f32(), 1.0);
+        assert_eq!(m.get(1, 1).to_f32(), 1.0);
+        assert_eq!(m.get(0, 1).to_f32(), 0.0);
+        assert_eq!(m.get(1, 0).to_f32(), 0.0);
+        assert_eq!(m * Mat2::identity(), m);
+    }
+
+    #[test]
+    fn test_rotation_of_unit_x() {
+        let angle = Fixed::from_f32(0.7);
+        let v = Mat2::rotation(angle) * Vec2::new(Fixed::ONE, Fixed::ZERO);
+        assert_eq!(v, Vec2::new(cos(angle), sin(angle)));
+    }
+
+    #[test]
+    fn test_transpose() {
+        let m = Mat2::from_vec2(Vec2::from_f32(1.0, 2.0), Vec2::from_f32(3.0, 4.0));
+        let t = m.transpose();
+        assert_eq!(t.get(0, 1), m.get(1, 0));
+        assert_eq!(t.get(1, 0), m.get(0, 1));
+        assert_eq!(t.transpose(), m);
+    }
+
+    #[test]
+    fn test_determinant_and_inverse() {
+        let m = Mat2::from_vec2(Vec2::from_f32(2.0, 0.0), Vec2::from_f32(1.0, 4.0));
+        assert_eq!(m.determinant().to_f32(), 8.0);
+        assert_eq!(m * m.inverse().unwrap(), Mat2::identity());
+    }
+
+    #[test]
+    fn test_inverse_singular() {
+        let m = Mat2::from_vec2(Vec2::from_f32(1.0, 2.0), Vec2::from_f32(2.0, 4.0));
+        assert_eq!(m.inverse(), None);
+    }
+
+    #[test]
+    fn test_rotations_compose() {
+        let quarter = Mat2::rotation(Fixed::from_f32(core::f32::consts::FRAC_PI_2));
+        let v = (quarter * quarter) * Vec2::from_f32(1.0, 0.0);
+        assert!((v.x.to_f32() + 1.0).abs() < 0.05);
+        assert!(v.y.to_f32().abs() < 0.05);
+    }
+}
@@ -0,0 +1,356 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// 4x4 matrix for fixed-point math (GLSL-compatible, column-major storage)
+///
+/// Storage layout (column-major):
+/// [m00, m10, m20, m30, m01, m11, ..., m33]
+/// Where m[row][col] represents the element at row `row` and column `col`
+use super::conversions::ToFixed;
+use super::fixed::Fixed;
+use super::vec4::Vec4;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Mat4 {
+    // Column-major storage: [col0, col1, col2, col3] where each column is [x, y, z, w]
+    pub m: [Fixed; 16],
+}
+
+impl Mat4 {
+    /// Create a new matrix from 16 Fixed values (column-major order)
+    #[inline(always)]
+    pub const fn new(m: [Fixed; 16]) -> Self {
+        Mat4 { m }
+    }
+
+    /// Create a matrix from 16 f32 values (column-major order)
+    #[inline(always)]
+    pub fn from_f32(m: [f32; 16]) -> Self {
+        Mat4::new(m.map(|v| v.to_fixed()))
+    }
+
+    /// Create a matrix from 4 Vec4 columns
+    #[inline(always)]
+    pub fn from_vec4(col0: Vec4, col1: Vec4, col2: Vec4, col3: Vec4) -> Self {
+        let mut m = Mat4::zero();
+        for (col, v) in [col0, col1, col2, col3].into_iter().enumerate() {
+            m.m[col * 4..col * 4 + 4].copy_from_slice(&[v.x, v.y, v.z, v.w]);
+        }
+        m
+    }
+
+    /// Create identity matrix
+    #[inline(always)]
+    pub const fn identity() -> Self {
+        let mut m = [Fixed(0); 16];
+        m[0] = Fixed::ONE;
+        m[5] = Fixed::ONE;
+        m[10] = Fixed::ONE;
+        m[15] = Fixed::ONE;
+        Mat4 { m }
+    }
+
+    /// Create zero matrix
+    #[inline(always)]
+    pub const fn zero() -> Self {
+        Mat4 { m: [Fixed(0); 16] }
+    }
+
+    /// Get element at row `row` and column `col`
+    #[inline(always)]
+    pub fn get(self, row: usize, col: usize) -> Fixed {
+        self.m[col * 4 + row]
+    }
+
+    /// Set element at row `row` and column `col`
+    #[inline(always)]
+    pub fn set(&mut self, row: usize, col: usize, value: Fixed) {
+        self.m[col * 4 + row] = value;
+    }
+
+    /// Get column `col` as Vec4
+    #[inline(always)]
+    pub fn col(self, col: usize) -> Vec4 {
+        Vec4::new(
+            self.m[col * 4],
+            self.m[col * 4 + 1],
+            self.m[col * 4 + 2],
+            self.m[col * 4 + 3],
+        )
+    }
+
+    /// Matrix-matrix multiplication
+    #[allow(clippy::should_implement_trait)]
+    #[inline(always)]
+    pub fn mul(self, rhs: Self) -> Self {
+        let mut out = Mat4::zero();
+        for col in 0..4 {
+            let v = self.mul_vec4(rhs.col(col));
+            out.m[col * 4..col * 4 + 4].copy_from_slice(&[v.x, v.y, v.z, v.w]);
+        }
+        out
+    }
+
+    /// Matrix-vector multiplication (mat4 * vec4)
+    #[inline(always)]
+    pub fn mul_vec4(self, v: Vec4) -> Vec4 {
+        let m = &self.m;
+        Vec4::new(
+            m[0] * v.x + m[4] * v.y + m[8] * v.z + m[12] * v.w,
+            m[1] * v.x + m[5] * v.y + m[9] * v.z + m[13] * v.w,
+            m[2] * v.x + m[6] * v.y + m[10] * v.z + m[14] * v.w,
+            m[3] * v.x + m[7] * v.y + m[11] * v.z + m[15] * v.w,
+        )
+    }
+
+    /// Transpose matrix
+    #[inline(always)]
+    pub fn transpose(self) -> Self {
+        let mut out = Mat4::zero();
+        for row in 0..4 {
+            for col in 0..4 {
+                out.set(col, row, self.get(row, col));
+            }
+        }
+        out
+    }
+
+    /// 2x2 sub-determinants of the top two and bottom two rows
+    ///
+    /// `s[i]` pairs columns (0,1), (0,2), (0,3), (1,2), (1,3), (2,3) of rows
+    /// 0 and 1; `c[i]` pairs the same columns of rows 2 and 3.
+    #[inline(always)]
+    fn sub_determinants(self) -> ([Fixed; 6], [Fixed; 6]) {
+        let a = |row, col| self.get(row, col);
+        let pair = |r0, r1, c0, c1| a(r0, c0) * a(r1, c1) - a(r1, c0) * a(r0, c1);
+        let cols = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+        (
+            cols.map(|(c0, c1)| pair(0, 1, c0, c1)),
+            cols.map(|(c0, c1)| pair(2, 3, c0, c1)),
+        )
+    }
+
+    /// Calculate determinant
+    #[inline(always)]
+    pub fn determinant(self) -> Fixed {
+        let (s, c) = self.sub_determinants();
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
+    /// Calculate inverse matrix
+    ///
+    /// Returns None if matrix is singular (determinant is zero)
+    #[inline(always)]
+    pub fn inverse(self) -> Option<Self> {
+        let det = self.determinant();
+        if det.0 == 0 {
+            return None;
+        }
+
+        let (s, c) = self.sub_determinants();
+        let a = |row, col| self.get(row, col);
+        // Adjugate matrix, built from the sub-determinants, divided by determinant
+        let inv_det = Fixed::ONE / det;
+        let mut out = Mat4::zero();
+        let mut put = |row, col, value: Fixed| out.set(row, col, value * inv_det);
+        put(0, 0, a(1, 1) * c[5] - a(1, 2) * c[4] + a(1, 3) * c[3]);
+        put(0, 1, -a(0, 1) * c[5] + a(0, 2) * c[4] - a(0, 3) * c[3]);
+        put(0, 2, a(3, 1) * s[5] - a(3, 2) * s[4] + a(3, 3) * s[3]);
+        put(0, 3, -a(2, 1) * s[5] + a(2, 2) * s[4] - a(2, 3) * s[3]);
+        put(1, 0, -a(1, 0) * c[5] + a(1, 2) * c[2] - a(1, 3) * c[1]);
+        put(1, 1, a(0, 0) * c[5] - a(0, 2) * c[2] + a(0, 3) * c[1]);
+        put(1, 2, -a(3, 0) * s[5] + a(3, 2) * s[2] - a(3, 3) * s[1]);
+        put(1, 3, a(2, 0) * s[5] - a(2, 2) * s[2] + a(2, 3) * s[1]);
+        put(2, 0, a(1, 0) * c[4] - a(1, 1) * c[2] + a(1, 3) * c[0]);
+        put(2, 1, -a(0, 0) * c[4] + a(0, 1) * c[2] - a(0, 3) * c[0]);
+        put(2, 2, a(3, 0) * s[4] - a(3, 1) * s[2] + a(3, 3) * s[0]);
+        put(2, 3, -a(2, 0) * s[4] + a(2, 1) * s[2] - a(2, 3) * s[0]);
+        put(3, 0, -a(1, 0) * c[3] + a(1, 1) * c[1] - a(1, 2) * c[0]);
+        put(3, 1, a(0, 0) * c[3] - a(0, 1) * c[1] + a(0, 2) * c[0]);
+        put(3, 2, -a(3, 0) * s[3] + a(3, 1) * s[1] - a(3, 2) * s[0]);
+        put(3, 3, a(2, 0) * s[3] - a(2, 1) * s[1] + a(2, 2) * s[0]);
+        Some(out)
+    }
+
+    /// Apply `f` to every component
+    #[inline(always)]
+    fn map(self, f: impl Fn(Fixed) -> Fixed) -> Self {
+        Mat4::new(self.m.map(f))
+    }
+
+    /// Combine matching components of `self` and `rhs` with `f`
+    #[inline(always)]
+    fn zip(self, rhs: Self, f: impl Fn(Fixed, Fixed) -> Fixed) -> Self {
+        let mut out = self;
+        for (a, b) in out.m.iter_mut().zip(rhs.m) {
+            *a = f(*a, b);
+        }
+        out
+    }
+}
+
+// Matrix + Matrix
+impl Add for Mat4 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a + b)
+    }
+}
+
+// Matrix - Matrix
+impl Sub for Mat4 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a - b)
+    }
+}
+
+// Matrix * Matrix (matrix multiplication)
+impl Mul for Mat4 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        self.mul(rhs)
+    }
+}
+
+// Matrix * Vec4 (matrix-vector multiplication)
+impl Mul<Vec4> for Mat4 {
+    type Output = Vec4;
+
+    #[inline(always)]
+    fn mul(self, rhs: Vec4) -> Vec4 {
+        self.mul_vec4(rhs)
+    }
+}
+
+// Matrix * Scalar
+impl Mul<Fixed> for Mat4 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: Fixed) -> Self {
+        self.map(|a| a * rhs)
+    }
+}
+
+// Matrix / Scalar
+impl Div<Fixed> for Mat4 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn div(self, rhs: Fixed) -> Self {
+        self.map(|a| a / rhs)
+    }
+}
+
+// -Matrix
+impl Neg for Mat4 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        self.map(|a| -a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Mat4 {
+        Mat4::from_f32([
+            2.0, 0.0, 1.0, 0.0, //
+            1.0, 3.0, 0.0, 0.0, //
+            0.0, 1.0, 2.0, 1.0, //
+            1.0, 0.0, 0.0, 4.0,
+        ])
+    }
+
+    #[test]
+    fn test_identity() {
+        let m = Mat4::identity();
+        for row in 0..4 {
+            for col in 0..4 {
+                let expected = if row == col { 1.0 } else { 0.0 };
+                assert_eq!(m.get(row, col).to_f32(), expected);
+            }
+        }
+        assert_eq!(sample() * Mat4::identity(), sample());
+    }
+
+    #[test]
+    fn test_from_vec4() {
+        let col2 = Vec4::from_f32(0.0, 1.0, 2.0, 1.0);
+        let m = sample();
+        assert_eq!(
+            Mat4::from_vec4(m.col(0), m.col(1), col2, m.col(3)),
+            sample()
+        );
+        assert_eq!(m.col(2), col2);
+    }
+
+    #[test]
+    fn test_add_sub_neg() {
+        let m = sample();
+        assert_eq!(m + m, m * 2.0.to_fixed());
+        assert_eq!(m - m, Mat4::zero());
+        assert_eq!(-m + m, Mat4::zero());
+        assert_eq!((m * 2.0.to_fixed()) / 2.0.to_fixed(), m);
+    }
+
+    #[test]
+    fn test_mul_vec4() {
+        // Columns are scaled by the vector components and summed
+        let v = sample() * Vec4::from_f32(1.0, 0.0, 0.0, 1.0);
+        assert_eq!(v, Vec4::from_f32(3.0, 0.0, 1.0, 4.0));
+    }
+
+    #[test]
+    fn test_mul_matrix_matches_mul_vec4() {
+        let (a, b) = (sample(), sample().transpose());
+        let v = Vec4::from_f32(1.0, 2.0, 0.5, -1.0);
+        assert_eq!((a * b) * v, a * (b * v));
+    }
+
+    #[test]
+    fn test_transpose() {
+        let m = sample();
+        let t = m.transpose();
+        assert_eq!(t.get(0, 2), m.get(2, 0));
+        assert_eq!(t.get(3, 1), m.get(1, 3));
+        assert_eq!(t.transpose(), m);
+    }
+
+    #[test]
+    fn test_determinant() {
+        assert_eq!(Mat4::identity().determinant().to_f32(), 1.0);
+        assert_eq!(
+            (Mat4::identity() * 2.0.to_fixed()).determinant().to_f32(),
+            16.0
+        );
+        assert_eq!(sample().determinant().to_f32(), 55.0);
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(Mat4::identity().inverse().unwrap(), Mat4::identity());
+
+        let product = sample() * sample().inverse().unwrap();
+        for row in 0..4 {
+            for col in 0..4 {
+                let expected = if row == col { 1.0 } else { 0.0 };
+                assert!((product.get(row, col).to_f32() - expected).abs() < 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse_singular() {
+        assert_eq!(Mat4::zero().inverse(), None);
+    }
+}
@@ -1,5 +1,7 @@
+pub mod accumulator;
 pub mod advanced;
 pub mod clamping;
+pub mod color;
 pub mod conversions;
 /// Fixed-point fixed library
 ///
@@ -29,19 +31,29 @@ pub mod conversions;
 /// - `clamping::saturate(a)` - Clamp to 0..1
 /// - `clamping::sign(a)` - Get sign (-1, 0, or 1)
 /// - `advanced::sqrt(a)` - Square root
+/// - `FixedAccumulator` - Sum or average many values without overflow
 ///
 /// # Trigonometry
 /// - `trig::sin(x)` - Sine (input in radians, 2π = full circle)
 /// - `trig::cos(x)` - Cosine (input in radians)
 /// - `trig::tan(x)` - Tangent (input in radians)
 ///
+/// # Color
+/// - `color::hsv2rgb(hsv)` / `color::rgb2hsv(rgb)` - Convert between HSV and RGB
+///
 /// # Noise
 /// - `noise::perlin3(x, y, z, octaves)` - 3D Perlin noise
+/// - `NoiseBackend` - Choose between fast and accurate `perlin3`
 #[allow(clippy::module_inception)]
 pub mod fixed;
 pub mod interpolation;
+pub mod mat2;
 pub mod mat3;
+pub mod mat4;
 pub mod noise;
+#[cfg(test)]
+mod parity_tests;
+pub mod parse;
 pub mod rounding;
 #[cfg(feature = "serde")]
 pub mod serde_impl;
@@ -52,11 +64,17 @@ pub mod vec3;
 pub mod vec4;
 
 // Re-export commonly used items at module level
+pub use accumulator::FixedAccumulator;
 pub use clamping::{saturate, sign};
+pub use color::{hsv2rgb, rgb2hsv};
 pub use conversions::ToFixed;
 pub use fixed::Fixed;
-pub use interpolation::{lerp, smoothstep, step};
+pub use interpolation::{lerp, remap, smoothstep, step};
+pub use mat2::Mat2;
 pub use mat3::Mat3;
+pub use mat4::Mat4;
+pub use noise::NoiseBackend;
+pub use parse::ParseFixedError;
 pub use rounding::{ceil, floor, frac};
 pub use trig::{cos, sin, tan};
 pub use vec2::Vec2;
@@ -5,7 +5,7 @@ use super::fixed::Fixed;
 use super::interpolation::lerp;
 
 // Permutation table for perlin noise (standard 256-entry table)
-const PERM: [u8; 256] = [
+pub(super) const PERM: [u8; 256] = [
     151, 160, 137, 91, 90, 15, 131, 13, 201, 95, 96, 53, 194, 233, 7, 225, 140, 36, 103, 30, 69,
     142, 8, 99, 37, 240, 21, 10, 23, 190, 6, 148, 247, 120, 234, 75, 0, 26, 197, 62, 94, 252, 219,
     203, 117, 35, 11, 32, 57, 177, 33, 88, 237, 149, 56, 87, 174, 20, 125, 136, 171, 168, 68, 175,
@@ -156,12 +156,139 @@ fn perlin3_single(x: Fixed, y: Fixed, z: Fixed) -> Fixed {
     lerp(y1, y2, w)
 }
 
+/// Implementation used to evaluate `perlin3`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoiseBackend {
+    /// 16.16 arithmetic throughout, truncating each step ([`perlin3`])
+    #[default]
+    FixedFast,
+    /// Rounded 8.24 intermediates and exact output scaling
+    /// ([`perlin3_accurate`]); closer to a float reference, somewhat slower
+    FixedAccurate,
+}
+
+impl NoiseBackend {
+    /// Evaluate 3D Perlin noise with this backend
+    #[inline(always)]
+    pub fn perlin3(self, x: Fixed, y: Fixed, z: Fixed, octaves: u8) -> Fixed {
+        match self {
+            NoiseBackend::FixedFast => perlin3(x, y, z, octaves),
+            NoiseBackend::FixedAccurate => perlin3_accurate(x, y, z, octaves),
+        }
+    }
+}
+
+/// Fractional bits of the accurate backend's intermediates
+const ACC_SHIFT: u32 = 24;
+const ACC_ONE: i64 = 1 << ACC_SHIFT;
+
+/// Rounded multiply of two 8.24 values
+#[inline(always)]
+fn mul_acc(a: i64, b: i64) -> i64 {
+    (a * b + (ACC_ONE >> 1)) >> ACC_SHIFT
+}
+
+#[inline(always)]
+fn fade_acc(t: i64) -> i64 {
+    // 6t^5 - 15t^4 + 10t^3 = t^3 * (t * (6t - 15) + 10)
+    let inner = mul_acc(t, 6 * t - 15 * ACC_ONE) + 10 * ACC_ONE;
+    mul_acc(mul_acc(mul_acc(t, t), t), inner)
+}
+
+#[inline(always)]
+fn lerp_acc(a: i64, b: i64, t: i64) -> i64 {
+    a + mul_acc(b - a, t)
+}
+
+#[inline(always)]
+fn grad_acc(hash: u8, x: i64, y: i64, z: i64) -> i64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    let u_val = if (h & 1) == 0 { u } else { -u };
+    let v_val = if (h & 2) == 0 { v } else { -v };
+    u_val + v_val
+}
+
+/// 3D Perlin noise computed with 8.24 intermediates
+///
+/// Same algorithm, permutation table and 0..1 mapping as [`perlin3`], but
+/// every multiply rounds, octave samples don't wrap, octaves are summed without
+/// losing low bits and the output scale is exactly 1.2. The result is rounded
+/// to the nearest 16.16 value.
+pub fn perlin3_accurate(x: Fixed, y: Fixed, z: Fixed, octaves: u8) -> Fixed {
+    let octaves = octaves.clamp(1, 8) as u32;
+
+    // Sum in 24 + 8 fractional bits so each halved amplitude stays exact
+    let mut total = 0i64;
+    for octave in 0..octaves {
+        let noise_val = perlin3_single_acc(
+            (x.0 as i64) << octave,
+            (y.0 as i64) << octave,
+            (z.0 as i64) << octave,
+        );
+        total += noise_val << (8 - octave);
+    }
+
+    // raw * 1.2 + 0.6, in 32 fractional bits
+    let scaled = total * 6 / 5 + ((3i64 << 32) + 2) / 5;
+    let raw = (scaled + (1 << 15)) >> 16;
+    Fixed(raw.clamp(0, Fixed::ONE.0 as i64) as i32)
+}
+
+/// Single octave for [`perlin3_accurate`]; inputs are raw 16.16, output is 8.24
+fn perlin3_single_acc(x: i64, y: i64, z: i64) -> i64 {
+    let xi = ((x >> Fixed::SHIFT) & 255) as usize;
+    let yi = ((y >> Fixed::SHIFT) & 255) as usize;
+    let zi = ((z >> Fixed::SHIFT) & 255) as usize;
+
+    let frac_mask = (1i64 << Fixed::SHIFT) - 1;
+    let to_acc = ACC_SHIFT - Fixed::SHIFT as u32;
+    let xf = (x & frac_mask) << to_acc;
+    let yf = (y & frac_mask) << to_acc;
+    let zf = (z & frac_mask) << to_acc;
+
+    let u = fade_acc(xf);
+    let v = fade_acc(yf);
+    let w = fade_acc(zf);
+
+    let p = |i: usize| PERM[i & 255] as usize;
+    let corner = |dx: usize, dy: usize, dz: usize| {
+        let hash = PERM[p(p(p(xi + dx) + yi + dy) + zi + dz)];
+        grad_acc(
+            hash,
+            xf - dx as i64 * ACC_ONE,
+            yf - dy as i64 * ACC_ONE,
+            zf - dz as i64 * ACC_ONE,
+        )
+    };
+
+    let y1 = lerp_acc(
+        lerp_acc(corner(0, 0, 0), corner(1, 0, 0), u),
+        lerp_acc(corner(0, 1, 0), corner(1, 1, 0), u),
+        v,
+    );
+    let y2 = lerp_acc(
+        lerp_acc(corner(0, 0, 1), corner(1, 0, 1), u),
+        lerp_acc(corner(0, 1, 1), corner(1, 1, 1), u),
+        v,
+    );
+    lerp_acc(y1, y2, w)
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::conversions::ToFixed;
     use super::*;
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_perlin3_basic() {
         let result = perlin3(0i32.to_fixed(), 0i32.to_fixed(), 0i32.to_fixed(), 3);
         let f = result.to_f32();
@@ -195,6 +322,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_perlin3_single_direct() {
         // Test perlin3_single directly with detailed debug
         let x = 0.5f32.to_fixed();
@@ -255,6 +383,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_perlin3_returns_zero_to_one() {
         // Test that perlin3 always returns values in 0..1 range
         // Test a variety of inputs
@@ -322,4 +451,51 @@ mod tests {
             range
         );
     }
+
+    #[test]
+    fn test_noise_backends_range_and_continuity() {
+        let step = Fixed::from_f32(0.01);
+        for backend in [NoiseBackend::FixedFast, NoiseBackend::FixedAccurate] {
+            for octaves in [1, 3, 8] {
+                let mut x = Fixed::from_f32(-2.0);
+                let y = Fixed::from_f32(0.37);
+                let z = Fixed::from_f32(1.91);
+                let mut prev = backend.perlin3(x, y, z, octaves);
+                for _ in 0..400 {
+                    x = x + step;
+                    let val = backend.perlin3(x, y, z, octaves);
+                    assert!(
+                        (Fixed::ZERO..=Fixed::ONE).contains(&val),
+                        "{:?} out of range: {}",
+                        backend,
+                        val
+                    );
+                    // Smooth noise: small input steps give small output steps
+                    let delta = (val - prev).abs().to_f32();
+                    let limit = 0.03 * (1 << (octaves - 1)) as f32;
+                    assert!(
+                        delta < limit,
+                        "{:?} jumped {} at x={} with {} octaves",
+                        backend,
+                        delta,
+                        x,
+                        octaves
+                    );
+                    prev = val;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_noise_backends_agree() {
+        for i in 0..50 {
+            let x = Fixed::from_f32(i as f32 * 0.173 - 4.0);
+            let y = Fixed::from_f32(i as f32 * 0.061);
+            let z = Fixed::from_f32(2.5 - i as f32 * 0.097);
+            let fast = NoiseBackend::FixedFast.perlin3(x, y, z, 3);
+            let accurate = NoiseBackend::FixedAccurate.perlin3(x, y, z, 3);
+            assert!((fast - accurate).abs().to_f32() < 0.005);
+        }
+    }
 }
@@ -0,0 +1,222 @@
+/// Parity checks between the fixed-point APIs and an `f32` reference
+///
+/// `Fixed` is the only fixed-point type, but two APIs compute with it: the
+/// `Fixed` operators/functions and the deprecated raw-`i32` helpers
+/// (`fixed_mul`, `fixed_div`, ...). These tests run the same operations
+/// through both and through `f32`, so a change to one path that makes them
+/// diverge is caught here rather than as a rendering difference.
+///
+/// Intentional differences from `f32`:
+/// - Conversions, `*` and `/` truncate toward zero (or toward negative
+///   infinity for `*`), so results may be one step (1/65536) below the
+///   rounded value.
+/// - Division by zero returns 0 instead of infinity/NaN.
+/// - `sin`/`cos` use a lookup table without interpolation, so they are only
+///   accurate to roughly the table step.
+/// - `sqrt` of a negative value returns 0 instead of NaN.
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    extern crate std;
+
+    use crate::fixed::noise::{perlin3, perlin3_accurate, PERM};
+    use crate::fixed::sin_table::SIN_TABLE_I32;
+    use crate::fixed::{
+        cos, fixed_div, fixed_from_f32, fixed_from_int, fixed_mul, fixed_to_f32, sin, sqrt, Fixed,
+    };
+
+    /// One fixed-point step
+    const STEP: f32 = 1.0 / 65536.0;
+
+    const SAMPLES: [f32; 12] = [
+        -100.0, -7.25, -3.3, -1.0, -0.1, 0.0, 0.004, 0.5, 1.0, 2.625, 9.99, 181.0,
+    ];
+
+    fn assert_close(op: &str, a: f32, b: f32, actual: f32, expected: f32, tolerance: f32) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{}({}, {}): fixed {} vs f32 {} (tolerance {})",
+            op,
+            a,
+            b,
+            actual,
+            expected,
+            tolerance
+        );
+    }
+
+    #[test]
+    fn test_legacy_helpers_match_fixed() {
+        for &a in &SAMPLES {
+            let fa = Fixed::from_f32(a);
+            assert_eq!(fixed_from_f32(a), fa.0);
+            assert_eq!(fixed_to_f32(fa.0), fa.to_f32());
+            assert_eq!(fixed_from_int(a as i32), Fixed::from_i32(a as i32).0);
+
+            for &b in &SAMPLES {
+                let fb = Fixed::from_f32(b);
+                assert_eq!(fixed_mul(fa.0, fb.0), (fa * fb).0, "mul({}, {})", a, b);
+                assert_eq!(fixed_div(fa.0, fb.0), (fa / fb).0, "div({}, {})", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_matches_f32() {
+        for &a in &SAMPLES {
+            for &b in &SAMPLES {
+                let (fa, fb) = (Fixed::from_f32(a), Fixed::from_f32(b));
+                // Compare against f32 on the already-quantized inputs
+                let (qa, qb) = (fa.to_f32(), fb.to_f32());
+
+                assert_close("add", a, b, (fa + fb).to_f32(), qa + qb, STEP);
+                assert_close("sub", a, b, (fa - fb).to_f32(), qa - qb, STEP);
+
+                let product = qa * qb;
+                if product.abs() < 32767.0 {
+                    // f32 itself has ~24 bits of mantissa
+                    let tolerance = STEP + product.abs() * f32::EPSILON;
+                    assert_close("mul", a, b, (fa * fb).to_f32(), product, tolerance);
+                }
+
+                if fb.0 == 0 {
+                    assert_eq!(fa / fb, Fixed::ZERO);
+                } else {
+                    let quotient = qa / qb;
+                    if quotient.abs() < 32767.0 {
+                        let tolerance = STEP + quotient.abs() * f32::EPSILON;
+                        assert_close("div", a, b, (fa / fb).to_f32(), quotient, tolerance);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_trig_and_sqrt_match_f32() {
+        // Table lookup without interpolation: error up to one table step
+        let table_step = core::f32::consts::TAU / SIN_TABLE_I32.len() as f32;
+        for i in -64..=64 {
+            let x = Fixed::from_f32(i as f32 * 0.1);
+            let qx = x.to_f32();
+            assert_close("sin", qx, 0.0, sin(x).to_f32(), qx.sin(), table_step);
+            assert_close("cos", qx, 0.0, cos(x).to_f32(), qx.cos(), table_step);
+        }
+
+        for &a in &SAMPLES {
+            let fa = Fixed::from_f32(a);
+            let qa = fa.to_f32();
+            let expected = if qa <= 0.0 { 0.0 } else { qa.sqrt() };
+            assert_close("sqrt", a, 0.0, sqrt(fa).to_f32(), expected, 2.0 * STEP);
+        }
+    }
+
+    /// `f32` port of `noise::perlin3`, sharing its permutation table
+    fn perlin3_f32(x: f32, y: f32, z: f32, octaves: u8) -> f32 {
+        fn fade(t: f32) -> f32 {
+            t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+        }
+        fn lerp(a: f32, b: f32, t: f32) -> f32 {
+            a + (b - a) * t
+        }
+        fn grad(hash: u8, x: f32, y: f32, z: f32) -> f32 {
+            let h = hash & 15;
+            let u = if h < 8 { x } else { y };
+            let v = if h < 4 {
+                y
+            } else if h == 12 || h == 14 {
+                x
+            } else {
+                z
+            };
+            let u = if h & 1 == 0 { u } else { -u };
+            let v = if h & 2 == 0 { v } else { -v };
+            u + v
+        }
+        fn single(x: f32, y: f32, z: f32) -> f32 {
+            let (fx, fy, fz) = (x.floor(), y.floor(), z.floor());
+            let (xi, yi, zi) = (
+                (fx as i32 & 255) as usize,
+                (fy as i32 & 255) as usize,
+                (fz as i32 & 255) as usize,
+            );
+            let (xf, yf, zf) = (x - fx, y - fy, z - fz);
+            let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+
+            let p = |i: usize| PERM[i & 255] as usize;
+            let corner = |dx: usize, dy: usize, dz: usize| {
+                let hash = PERM[p(p(p(xi + dx) + yi + dy) + zi + dz)];
+                grad(hash, xf - dx as f32, yf - dy as f32, zf - dz as f32)
+            };
+
+            let y1 = lerp(
+                lerp(corner(0, 0, 0), corner(1, 0, 0), u),
+                lerp(corner(0, 1, 0), corner(1, 1, 0), u),
+                v,
+            );
+            let y2 = lerp(
+                lerp(corner(0, 0, 1), corner(1, 0, 1), u),
+                lerp(corner(0, 1, 1), corner(1, 1, 1), u),
+                v,
+            );
+            lerp(y1, y2, w)
+        }
+
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        for _ in 0..octaves.clamp(1, 8) {
+            total += single(x * frequency, y * frequency, z * frequency) * amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        (total * 1.2 + 0.6).clamp(0.0, 1.0)
+    }
+
+    #[test]
+    fn test_perlin3_matches_f32() {
+        let points = [
+            (0.0, 0.0, 0.0),
+            (0.5, 0.25, 0.75),
+            (1.3, 2.7, 0.1),
+            (-3.6, 4.2, 7.9),
+            (12.34, -5.67, 8.9),
+            (100.5, 200.25, 3.125),
+        ];
+
+        for &(x, y, z) in &points {
+            let (fx, fy, fz) = (Fixed::from_f32(x), Fixed::from_f32(y), Fixed::from_f32(z));
+            for octaves in 1..=8 {
+                let actual = perlin3(fx, fy, fz, octaves).to_f32();
+                let expected = perlin3_f32(fx.to_f32(), fy.to_f32(), fz.to_f32(), octaves);
+                assert!(
+                    (actual - expected).abs() <= 0.002,
+                    "perlin3({}, {}, {}, {}): fixed {} vs f32 {}",
+                    x,
+                    y,
+                    z,
+                    octaves,
+                    actual,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_perlin3_accurate_matches_f32() {
+        let mut worst = 0.0f32;
+        for i in 0..200 {
+            let x = Fixed::from_f32(i as f32 * 0.173 - 17.0);
+            let y = Fixed::from_f32(i as f32 * 0.061 + 0.3);
+            let z = Fixed::from_f32(9.5 - i as f32 * 0.097);
+            for octaves in 1..=8 {
+                let actual = perlin3_accurate(x, y, z, octaves).to_f32();
+                let expected = perlin3_f32(x.to_f32(), y.to_f32(), z.to_f32(), octaves);
+                worst = worst.max((actual - expected).abs());
+            }
+        }
+        // A few 16.16 steps; the fast backend can be off by ~0.002
+        assert!(worst <= 4.0 * STEP, "max error {}", worst);
+    }
+}
@@ -0,0 +1,162 @@
+/// Exact decimal string parsing for Fixed
+///
+/// Parses directly into 16.16 fixed-point using integer arithmetic, avoiding
+/// the double rounding of going through `f32`.
+use core::fmt;
+use core::str::FromStr;
+
+use super::fixed::Fixed;
+
+/// Maximum fractional digits that contribute to the result
+///
+/// Later digits are ignored; they can only affect rounding well below 2^-16.
+const MAX_FRAC_DIGITS: u32 = 30;
+
+/// Error returned when parsing a `Fixed` from a string fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseFixedError {
+    /// The input had no digits
+    Empty,
+    /// The input contained a character that isn't part of a decimal number
+    InvalidDigit,
+    /// The value doesn't fit in 16.16 fixed-point
+    OutOfRange,
+}
+
+impl fmt::Display for ParseFixedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseFixedError::Empty => write!(f, "cannot parse fixed-point value from empty string"),
+            ParseFixedError::InvalidDigit => write!(f, "invalid digit in fixed-point value"),
+            ParseFixedError::OutOfRange => write!(f, "value out of range for 16.16 fixed-point"),
+        }
+    }
+}
+
+impl FromStr for Fixed {
+    type Err = ParseFixedError;
+
+    /// Parse a decimal like `"-3.25"`, `"0.1"`, `"5"` or `".5"`
+    ///
+    /// The result is rounded to the nearest 1/65536 (ties away from zero).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+
+        let (int_str, frac_str) = match digits.find('.') {
+            Some(dot) => (&digits[..dot], &digits[dot + 1..]),
+            None => (digits, ""),
+        };
+        if int_str.is_empty() && frac_str.is_empty() {
+            return Err(ParseFixedError::Empty);
+        }
+
+        // Integer part: anything above 2^15 can't be represented
+        let mut int_part: u64 = 0;
+        for b in int_str.bytes() {
+            let digit = decimal_digit(b)?;
+            int_part = int_part * 10 + digit as u64;
+            if int_part > 1 << 15 {
+                return Err(ParseFixedError::OutOfRange);
+            }
+        }
+
+        // Fractional part: accumulate as numerator / 10^n
+        let mut numerator: u128 = 0;
+        let mut frac_digits: u32 = 0;
+        for b in frac_str.bytes() {
+            let digit = decimal_digit(b)?;
+            if frac_digits < MAX_FRAC_DIGITS {
+                numerator = numerator * 10 + digit as u128;
+                frac_digits += 1;
+            }
+        }
+        let denominator = 10u128.pow(frac_digits);
+        let frac_raw = ((numerator << Fixed::SHIFT) + denominator / 2) / denominator;
+
+        let magnitude = (int_part << Fixed::SHIFT) + frac_raw as u64;
+        let raw = if negative {
+            if magnitude > 1 << 31 {
+                return Err(ParseFixedError::OutOfRange);
+            }
+            -(magnitude as i64)
+        } else {
+            if magnitude > i32::MAX as u64 {
+                return Err(ParseFixedError::OutOfRange);
+            }
+            magnitude as i64
+        };
+
+        Ok(Fixed(raw as i32))
+    }
+}
+
+#[inline(always)]
+fn decimal_digit(b: u8) -> Result<u8, ParseFixedError> {
+    if b.is_ascii_digit() {
+        Ok(b - b'0')
+    } else {
+        Err(ParseFixedError::InvalidDigit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tenth() {
+        // 0.1 * 65536 = 6553.6, rounds to 6554
+        assert_eq!("0.1".parse::<Fixed>(), Ok(Fixed(6554)));
+    }
+
+    #[test]
+    fn test_parse_negative() {
+        assert_eq!("-3.25".parse::<Fixed>(), Ok(Fixed::from_f32(-3.25)));
+        assert_eq!(Fixed::from_str("-0.5"), Ok(-Fixed::HALF));
+    }
+
+    #[test]
+    fn test_parse_forms() {
+        assert_eq!("5".parse::<Fixed>(), Ok(Fixed::from_i32(5)));
+        assert_eq!("+2.".parse::<Fixed>(), Ok(Fixed::from_i32(2)));
+        assert_eq!(".5".parse::<Fixed>(), Ok(Fixed::HALF));
+        assert_eq!(" 1.5 ".parse::<Fixed>(), Ok(Fixed::from_f32(1.5)));
+        assert_eq!(
+            "3.14159265358979323846264338327950288".parse::<Fixed>(),
+            Ok(Fixed::PI)
+        );
+    }
+
+    #[test]
+    fn test_parse_range_limits() {
+        assert_eq!("-32768".parse::<Fixed>(), Ok(Fixed(i32::MIN)));
+        assert_eq!("32767.99998".parse::<Fixed>(), Ok(Fixed(i32::MAX)));
+    }
+
+    #[test]
+    fn test_parse_overflow() {
+        assert_eq!("32768".parse::<Fixed>(), Err(ParseFixedError::OutOfRange));
+        assert_eq!(
+            "-32768.5".parse::<Fixed>(),
+            Err(ParseFixedError::OutOfRange)
+        );
+        assert_eq!(
+            "100000000000000000000".parse::<Fixed>(),
+            Err(ParseFixedError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!("".parse::<Fixed>(), Err(ParseFixedError::Empty));
+        assert_eq!("-.".parse::<Fixed>(), Err(ParseFixedError::Empty));
+        assert_eq!("1.2.3".parse::<Fixed>(), Err(ParseFixedError::InvalidDigit));
+        assert_eq!("abc".parse::<Fixed>(), Err(ParseFixedError::InvalidDigit));
+        assert_eq!("1e5".parse::<Fixed>(), Err(ParseFixedError::InvalidDigit));
+    }
+}
@@ -1,6 +1,9 @@
+use core::fmt;
+
+use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use super::{Fixed, Mat3, Vec2, Vec3, Vec4};
+use super::{Fixed, Mat3, ParseFixedError, Vec2, Vec3, Vec4};
 
 impl Serialize for Fixed {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -12,12 +15,63 @@ impl Serialize for Fixed {
 }
 
 impl<'de> Deserialize<'de> for Fixed {
+    /// Accepts numbers or decimal strings in human-readable formats
+    ///
+    /// Strings are parsed exactly via `Fixed::from_str`; floats are converted
+    /// from `f64` directly, without an `f32` intermediary. Binary formats
+    /// (bincode, postcard) aren't self-describing, so they read the `f32`
+    /// that `serialize` wrote.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let f = f32::deserialize(deserializer)?;
-        Ok(Fixed::from_f32(f))
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(FixedVisitor)
+        } else {
+            deserializer.deserialize_f32(FixedVisitor)
+        }
+    }
+}
+
+struct FixedVisitor;
+
+impl<'de> Visitor<'de> for FixedVisitor {
+    type Value = Fixed;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a number or decimal string in 16.16 fixed-point range")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Fixed, E> {
+        v.parse::<Fixed>().map_err(E::custom)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Fixed, E> {
+        let scaled = v * Fixed::ONE.0 as f64;
+        // Round half away from zero (no libm in no_std)
+        let rounded = if scaled >= 0.0 {
+            scaled + 0.5
+        } else {
+            scaled - 0.5
+        };
+        if rounded.is_nan() || rounded < i32::MIN as f64 || rounded >= i32::MAX as f64 + 1.0 {
+            return Err(E::custom(ParseFixedError::OutOfRange));
+        }
+        Ok(Fixed(rounded as i32))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Fixed, E> {
+        if !(-(1 << 15)..(1 << 15)).contains(&v) {
+            return Err(E::custom(ParseFixedError::OutOfRange));
+        }
+        Ok(Fixed::from_i32(v as i32))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Fixed, E> {
+        if v >= 1 << 15 {
+            return Err(E::custom(ParseFixedError::OutOfRange));
+        }
+        Ok(Fixed::from_i32(v as i32))
     }
 }
 
@@ -117,3 +171,130 @@ impl<'de> Deserialize<'de> for Mat3 {
         ))
     }
 }
+
+/// Serialize a `Fixed` as an exact decimal string instead of an `f32`
+///
+/// For use with `#[serde(with = "...")]` where values must round-trip
+/// exactly. The string is the shortest one that parses back to the same
+/// value (e.g. `"0.1"` for `Fixed(6554)`); deserializing also accepts numbers.
+/// Binary formats store the raw `i32` instead, which is exact too.
+pub mod decimal {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::fixed::Fixed;
+
+    pub fn serialize<S>(value: &Fixed, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&format_args!("{:.*}", value.shortest_digits(), value))
+        } else {
+            serializer.serialize_i32(value.0)
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Fixed, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            Fixed::deserialize(deserializer)
+        } else {
+            i32::deserialize(deserializer).map(Fixed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use std::format;
+
+    use serde::de::value::{Error, F32Deserializer, F64Deserializer, I64Deserializer};
+    use serde::de::IntoDeserializer;
+
+    use super::*;
+
+    fn from_str(s: &str) -> Result<Fixed, Error> {
+        Fixed::deserialize(IntoDeserializer::<Error>::into_deserializer(s))
+    }
+
+    #[test]
+    fn test_deserialize_decimal_string_is_exact() {
+        assert_eq!(from_str("0.1").unwrap(), Fixed(6554));
+        assert_eq!(from_str("-3.25").unwrap(), Fixed::from_f32(-3.25));
+    }
+
+    #[test]
+    fn test_deserialize_string_out_of_range() {
+        assert!(from_str("40000").is_err());
+    }
+
+    #[test]
+    fn test_deserialize_numbers() {
+        let f = Fixed::deserialize(F64Deserializer::<Error>::new(0.1)).unwrap();
+        assert_eq!(f, Fixed(6554));
+
+        let f = Fixed::deserialize(F32Deserializer::<Error>::new(1.5)).unwrap();
+        assert_eq!(f, Fixed::from_f32(1.5));
+
+        let f = Fixed::deserialize(I64Deserializer::<Error>::new(-7)).unwrap();
+        assert_eq!(f, Fixed::from_i32(-7));
+
+        assert!(Fixed::deserialize(F64Deserializer::<Error>::new(1.0e9)).is_err());
+        assert!(Fixed::deserialize(I64Deserializer::<Error>::new(1 << 20)).is_err());
+    }
+
+    #[test]
+    fn test_decimal_round_trips_exactly() {
+        for raw in [
+            0,
+            1,
+            -1,
+            6554,
+            65535,
+            -98304,
+            i32::MAX,
+            i32::MIN,
+            123_456_789,
+        ] {
+            let value = Fixed(raw);
+            let text = format!("{:.*}", value.shortest_digits(), value);
+            assert_eq!(from_str(&text).unwrap(), value, "{}", text);
+        }
+        assert_eq!(Fixed(6554).shortest_digits(), 1);
+        assert_eq!(Fixed::from_i32(3).shortest_digits(), 0);
+        assert_eq!(Fixed::from_f32(-1.25).shortest_digits(), 2);
+    }
+
+    #[test]
+    fn test_bincode_round_trip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Values {
+            plain: Fixed,
+            #[serde(with = "decimal")]
+            exact: Fixed,
+            v: Vec3,
+        }
+
+        let values = Values {
+            plain: Fixed::from_f32(-3.25),
+            exact: Fixed(6554),
+            v: Vec3::from_f32(1.0, -0.5, 2.75),
+        };
+        let bytes = bincode::serialize(&values).unwrap();
+        assert_eq!(bincode::deserialize::<Values>(&bytes).unwrap(), values);
+
+        // Binary decimals keep every bit, even where `f32` would round
+        let bytes = bincode::serialize(&Values {
+            exact: Fixed(i32::MAX),
+            ..values
+        })
+        .unwrap();
+        assert_eq!(
+            bincode::deserialize::<Values>(&bytes).unwrap().exact,
+            Fixed(i32::MAX)
+        );
+    }
+}
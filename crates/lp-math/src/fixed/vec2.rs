@@ -1,6 +1,7 @@
 use core::ops::{Add, Div, Mul, Neg, Sub};
 
 /// 2D vector for fixed-point coordinates
+use super::accumulator::FixedAccumulator;
 use super::conversions::ToFixed;
 use super::fixed::Fixed;
 
@@ -193,6 +194,73 @@ impl Vec2 {
     pub fn div_comp(self, rhs: Self) -> Self {
         Vec2::new(self.x / rhs.x, self.y / rhs.y)
     }
+
+    /// Reflect vector around normal
+    #[inline(always)]
+    pub fn reflect(self, normal: Self) -> Self {
+        // reflect = v - 2 * dot(v, n) * n
+        let dot_2 = self.dot(normal) * Fixed(2 << 16);
+        self - (normal * dot_2)
+    }
+
+    /// Flip `self` (a normal) to face against `incident`
+    ///
+    /// GLSL `faceforward(N, I, Nref)`: `N` if `dot(Nref, I) < 0`, else `-N`.
+    #[inline(always)]
+    pub fn faceforward(self, incident: Self, reference: Self) -> Self {
+        if reference.dot(incident) < Fixed::ZERO {
+            self
+        } else {
+            -self
+        }
+    }
+
+    /// Largest component
+    #[inline(always)]
+    pub fn max_component(self) -> Fixed {
+        self.x.max(self.y)
+    }
+
+    /// Smallest component
+    #[inline(always)]
+    pub fn min_component(self) -> Fixed {
+        self.x.min(self.y)
+    }
+
+    /// Sum of the components
+    #[inline(always)]
+    pub fn sum(self) -> Fixed {
+        self.x + self.y
+    }
+
+    /// Mean of the components (summed without intermediate overflow)
+    #[inline(always)]
+    pub fn average(self) -> Fixed {
+        let mut acc = FixedAccumulator::new();
+        acc.add(self.x);
+        acc.add(self.y);
+        acc.mean()
+    }
+
+    /// Convert to polar `(radius, angle)`
+    ///
+    /// The angle is `atan2(y, x)` in radians, -π..π, 0 along +x and
+    /// increasing toward +y. The zero vector maps to `(0, 0)`.
+    #[inline(always)]
+    pub fn to_polar(self) -> Vec2 {
+        use crate::fixed::advanced::atan2;
+        if self.x.0 == 0 && self.y.0 == 0 {
+            return Vec2::new(Fixed::ZERO, Fixed::ZERO);
+        }
+        Vec2::new(self.length(), atan2(self.y, self.x))
+    }
+
+    /// Convert polar `(radius, angle)` back to cartesian; inverse of `to_polar`
+    #[inline(always)]
+    pub fn from_polar(self) -> Vec2 {
+        use crate::fixed::trig::{cos, sin};
+        Vec2::new(self.x * cos(self.y), self.x * sin(self.y))
+    }
 }
 
 impl Add for Vec2 {
@@ -1,6 +1,7 @@
 use core::ops::{Add, Div, Mul, Neg, Sub};
 
 /// 3D vector for fixed-point fixed
+use super::accumulator::FixedAccumulator;
 use super::conversions::ToFixed;
 use super::fixed::Fixed;
 use super::vec2::Vec2;
@@ -235,6 +236,46 @@ impl Vec3 {
         let dot_2 = self.dot(normal) * Fixed(2 << 16);
         self - (normal * dot_2)
     }
+
+    /// Flip `self` (a normal) to face against `incident`
+    ///
+    /// GLSL `faceforward(N, I, Nref)`: `N` if `dot(Nref, I) < 0`, else `-N`.
+    #[inline(always)]
+    pub fn faceforward(self, incident: Self, reference: Self) -> Self {
+        if reference.dot(incident) < Fixed::ZERO {
+            self
+        } else {
+            -self
+        }
+    }
+
+    /// Largest component
+    #[inline(always)]
+    pub fn max_component(self) -> Fixed {
+        self.x.max(self.y).max(self.z)
+    }
+
+    /// Smallest component
+    #[inline(always)]
+    pub fn min_component(self) -> Fixed {
+        self.x.min(self.y).min(self.z)
+    }
+
+    /// Sum of the components
+    #[inline(always)]
+    pub fn sum(self) -> Fixed {
+        self.x + self.y + self.z
+    }
+
+    /// Mean of the components (summed without intermediate overflow)
+    #[inline(always)]
+    pub fn average(self) -> Fixed {
+        let mut acc = FixedAccumulator::new();
+        acc.add(self.x);
+        acc.add(self.y);
+        acc.add(self.z);
+        acc.mean()
+    }
 }
 
 // Vector + Vector
@@ -416,4 +457,42 @@ mod tests {
         assert_eq!(o.y.to_f32(), 1.0);
         assert_eq!(o.z.to_f32(), 1.0);
     }
+
+    #[test]
+    fn test_reflect() {
+        // Incoming at 45 degrees onto a floor facing +y
+        let v = Vec3::from_f32(1.0, -1.0, 0.0);
+        let n = Vec3::from_f32(0.0, 1.0, 0.0);
+        assert_eq!(v.reflect(n), Vec3::from_f32(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_faceforward() {
+        let n = Vec3::from_f32(0.0, 0.0, 1.0);
+        // dot(Nref, I) < 0: keep N
+        let i = Vec3::from_f32(0.0, 0.0, -1.0);
+        assert_eq!(n.faceforward(i, n), n);
+        // dot(Nref, I) >= 0: flip N
+        let i = Vec3::from_f32(0.5, 0.0, 1.0);
+        assert_eq!(n.faceforward(i, n), -n);
+        assert_eq!(n.faceforward(Vec3::zero(), n), -n);
+    }
+
+    #[test]
+    fn test_min_max_component() {
+        let v = Vec3::from_f32(0.25, -1.5, 2.0);
+        assert_eq!(v.max_component(), Fixed::from_f32(2.0));
+        assert_eq!(v.min_component(), Fixed::from_f32(-1.5));
+    }
+
+    #[test]
+    fn test_sum_and_average() {
+        let v = Vec3::from_f32(0.25, -1.5, 2.0);
+        assert_eq!(v.sum(), Fixed::from_f32(0.75));
+        assert_eq!(v.average(), Fixed::from_f32(0.25));
+
+        // Average doesn't overflow when the sum would
+        let big = Vec3::from_f32(30000.0, 30000.0, 30000.0);
+        assert_eq!(big.average(), Fixed::from_f32(30000.0));
+    }
 }
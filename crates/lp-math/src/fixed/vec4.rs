@@ -1,6 +1,7 @@
 use core::ops::{Add, Div, Mul, Neg, Sub};
 
 /// 4D vector for fixed-point fixed (useful for RGBA colors and homogeneous coordinates)
+use super::accumulator::FixedAccumulator;
 use super::conversions::ToFixed;
 use super::fixed::Fixed;
 use super::vec2::Vec2;
@@ -237,6 +238,55 @@ impl Vec4 {
             self.w.clamp(min, max),
         )
     }
+
+    /// Reflect vector around normal
+    #[inline(always)]
+    pub fn reflect(self, normal: Self) -> Self {
+        // reflect = v - 2 * dot(v, n) * n
+        let dot_2 = self.dot(normal) * Fixed(2 << 16);
+        self - (normal * dot_2)
+    }
+
+    /// Flip `self` (a normal) to face against `incident`
+    ///
+    /// GLSL `faceforward(N, I, Nref)`: `N` if `dot(Nref, I) < 0`, else `-N`.
+    #[inline(always)]
+    pub fn faceforward(self, incident: Self, reference: Self) -> Self {
+        if reference.dot(incident) < Fixed::ZERO {
+            self
+        } else {
+            -self
+        }
+    }
+
+    /// Largest component
+    #[inline(always)]
+    pub fn max_component(self) -> Fixed {
+        self.x.max(self.y).max(self.z).max(self.w)
+    }
+
+    /// Smallest component
+    #[inline(always)]
+    pub fn min_component(self) -> Fixed {
+        self.x.min(self.y).min(self.z).min(self.w)
+    }
+
+    /// Sum of the components
+    #[inline(always)]
+    pub fn sum(self) -> Fixed {
+        self.x + self.y + self.z + self.w
+    }
+
+    /// Mean of the components (summed without intermediate overflow)
+    #[inline(always)]
+    pub fn average(self) -> Fixed {
+        let mut acc = FixedAccumulator::new();
+        acc.add(self.x);
+        acc.add(self.y);
+        acc.add(self.z);
+        acc.add(self.w);
+        acc.mean()
+    }
 }
 
 // Vector + Vector
@@ -7,6 +7,7 @@ use crate::compiler::codegen::LocalAllocator;
 use crate::compiler::error::{TypeError, TypeErrorKind};
 use crate::compiler::func::{FunctionMetadata, FunctionTable, LocalVarInfo};
 use crate::shared::Type;
+use crate::vm::ExternalDef;
 
 /// Function analyzer for discovering locals and building types
 pub struct FunctionAnalyzer;
@@ -14,14 +15,22 @@ pub struct FunctionAnalyzer;
 impl FunctionAnalyzer {
     /// Analyze a program and build function types table
     pub fn analyze_program(program: &Program) -> Result<FunctionTable, TypeError> {
-        let mut func_table = FunctionTable::new();
+        let uniforms = program
+            .uniforms
+            .iter()
+            .map(|uniform| ExternalDef::new(uniform.name.clone(), uniform.ty.clone()))
+            .collect();
+        let mut func_table = FunctionTable::new().with_externals(uniforms);
 
         // Analyze each function
         for func in &program.functions {
             let param_types: Vec<Type> = func.params.iter().map(|p| p.ty.clone()).collect();
 
             // Discover locals in function body
-            let (locals, local_count) = Self::analyze_function_body(&func.body, &func.params)?;
+            let (locals, local_count) = Self::analyze_function_body(
+                func.body.as_deref().unwrap_or_default(),
+                &func.params,
+            )?;
 
             let metadata = FunctionMetadata {
                 params: param_types,
@@ -108,10 +117,19 @@ impl FunctionAnalyzer {
                 }
             }
 
-            StmtKind::While { body, .. } => {
+            StmtKind::While { body, .. } | StmtKind::DoWhile { body, .. } => {
                 Self::discover_locals_with_tracking(body.as_ref(), locals, local_infos)?;
             }
 
+            StmtKind::Switch { cases, .. } => {
+                // All cases share one scope
+                locals.push_scope();
+                for inner_stmt in cases.iter().flat_map(|case| &case.body) {
+                    Self::discover_locals_with_tracking(inner_stmt, locals, local_infos)?;
+                }
+                locals.pop_scope();
+            }
+
             StmtKind::For { init, body, .. } => {
                 // For loops create a scope for the init statement
                 locals.push_scope();
@@ -125,8 +143,13 @@ impl FunctionAnalyzer {
                 locals.pop_scope();
             }
 
-            // These statements don't declare variables
-            StmtKind::Return(_) | StmtKind::Expr(_) => {}
+            // These statements don't declare variables; a const's uses are
+            // replaced by its value
+            StmtKind::ConstDecl { .. }
+            | StmtKind::Return(_)
+            | StmtKind::Expr(_)
+            | StmtKind::Break
+            | StmtKind::Continue => {}
         }
 
         Ok(())
@@ -164,10 +187,19 @@ impl FunctionAnalyzer {
                 }
             }
 
-            StmtKind::While { body, .. } => {
+            StmtKind::While { body, .. } | StmtKind::DoWhile { body, .. } => {
                 Self::discover_locals(body.as_ref(), locals)?;
             }
 
+            StmtKind::Switch { cases, .. } => {
+                // All cases share one scope
+                locals.push_scope();
+                for inner_stmt in cases.iter().flat_map(|case| &case.body) {
+                    Self::discover_locals(inner_stmt, locals)?;
+                }
+                locals.pop_scope();
+            }
+
             StmtKind::For { init, body, .. } => {
                 // For loops create a scope for the init statement
                 locals.push_scope();
@@ -181,8 +213,13 @@ impl FunctionAnalyzer {
                 locals.pop_scope();
             }
 
-            // These statements don't declare variables
-            StmtKind::Return(_) | StmtKind::Expr(_) => {}
+            // These statements don't declare variables; a const's uses are
+            // replaced by its value
+            StmtKind::ConstDecl { .. }
+            | StmtKind::Return(_)
+            | StmtKind::Expr(_)
+            | StmtKind::Break
+            | StmtKind::Continue => {}
         }
 
         Ok(())
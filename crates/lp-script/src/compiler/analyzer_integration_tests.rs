@@ -20,6 +20,7 @@ mod tests {
     use crate::compiler::analyzer::FunctionAnalyzer;
     use crate::compiler::ast::Program;
     use crate::compiler::codegen::CodeGenerator;
+    use crate::compiler::error::{TypeError, TypeErrorKind};
     use crate::compiler::func::FunctionTable;
     use crate::compiler::lexer::Lexer;
     use crate::compiler::parser::Parser;
@@ -270,4 +271,117 @@ mod tests {
         let result = compile_script_with_options(program_text, &OptimizeOptions::none());
         assert!(result.is_ok(), "Nested blocks should compile successfully");
     }
+
+    // ========================================================================
+    // Incremental Re-checking
+    // ========================================================================
+
+    fn parse_and_check(source: &str) -> FunctionTable {
+        let (mut program, func_table) = parse_and_analyze(source);
+        TypeChecker::check_program(&mut program, &func_table).expect("type check should succeed");
+        func_table
+    }
+
+    #[test]
+    fn test_incremental_body_edit_keeps_callers() {
+        let func_table = parse_and_check(
+            "
+            float scale(float x) { return x * 2.0; }
+            float caller() { return scale(1.0) + 1.0; }
+            return caller();
+        ",
+        );
+
+        // Same signature, different body
+        let (mut edited, _) = parse_and_analyze(
+            "
+            float scale(float x) { float y = x + 1.0; return y * 3.0; }
+            float caller() { return scale(1.0) + 1.0; }
+            return caller();
+        ",
+        );
+        let changed = TypeChecker::check_function_incremental(&mut edited, "scale", &func_table)
+            .expect("edited body should type check");
+        assert!(
+            !changed,
+            "body-only edit should not require caller rechecks"
+        );
+    }
+
+    #[test]
+    fn test_incremental_signature_change() {
+        let func_table = parse_and_check(
+            "
+            float scale(float x) { return x * 2.0; }
+            return scale(1.0);
+        ",
+        );
+
+        let (mut edited, _) = parse_and_analyze(
+            "
+            vec2 scale(float x) { return vec2(x, x); }
+            return scale(1.0).x;
+        ",
+        );
+        let changed = TypeChecker::check_function_incremental(&mut edited, "scale", &func_table)
+            .expect("edited function should type check");
+        assert!(changed, "return type change should require caller rechecks");
+    }
+
+    #[test]
+    fn test_incremental_recursive_call_uses_new_signature() {
+        let func_table = parse_and_check(
+            "
+            float count(float n) { return n; }
+            return count(3.0);
+        ",
+        );
+
+        let (mut edited, _) = parse_and_analyze(
+            "
+            float count(float n, float step) {
+                if (n <= 0.0) { return 0.0; }
+                return count(n - step, step) + 1.0;
+            }
+            return count(3.0, 1.0);
+        ",
+        );
+        let changed = TypeChecker::check_function_incremental(&mut edited, "count", &func_table)
+            .expect("recursive call should match the new signature");
+        assert!(changed);
+    }
+
+    #[test]
+    fn test_incremental_reports_errors() {
+        let func_table = parse_and_check(
+            "
+            float scale(float x) { return x * 2.0; }
+            return scale(1.0);
+        ",
+        );
+
+        let (mut edited, _) = parse_and_analyze(
+            "
+            float scale(float x) { return vec2(x, x); }
+            return scale(1.0);
+        ",
+        );
+        let result = TypeChecker::check_function_incremental(&mut edited, "scale", &func_table);
+        assert!(matches!(
+            result,
+            Err(TypeError {
+                kind: TypeErrorKind::Mismatch { .. },
+                ..
+            })
+        ));
+
+        let missing = TypeChecker::check_function_incremental(&mut edited, "nope", &func_table);
+        assert!(matches!(
+            missing,
+            Err(TypeError {
+                kind: TypeErrorKind::UndefinedFunction(_),
+                ..
+            })
+        ));
+    }
 }
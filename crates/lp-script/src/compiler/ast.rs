@@ -19,19 +19,35 @@ pub struct FunctionDef {
     pub name: String,
     pub params: Vec<Parameter>,
     pub return_type: Type,
-    pub body: Vec<Stmt>,
+    /// `None` for a prototype, `float sq(float x);`, whose body is linked in
+    /// later with `LpsProgram::merge`
+    pub body: Option<Vec<Stmt>>,
     pub span: Span,
 }
 
 /// A complete program (for script mode)
 #[derive(Debug, Clone)]
 pub struct Program {
+    /// `uniform type name;` declarations, values set by the host
+    pub uniforms: Vec<Parameter>,
+    /// `const` declarations before the first top-level statement, visible
+    /// to every function and the top level
+    pub consts: Vec<Stmt>,
     pub functions: Vec<FunctionDef>,
     pub stmts: Vec<Stmt>,
     #[allow(dead_code)] // Metadata field - may be used for error reporting
     pub span: Span,
 }
 
+/// One `case value:` or `default:` label of a switch and the statements
+/// after it, up to the next label
+#[derive(Debug, Clone)]
+pub struct SwitchCase {
+    /// `None` for `default`
+    pub value: Option<Expr>,
+    pub body: Vec<Stmt>,
+}
+
 /// Statement with types
 #[derive(Debug, Clone)]
 pub struct Stmt {
@@ -55,6 +71,12 @@ pub enum StmtKind {
         init: Option<Expr>,
     },
 
+    /// Compile-time constant: `const float x = expr;`
+    ///
+    /// The type checker folds `init` to a literal and puts it in place of
+    /// every use of `name`, so no local is allocated.
+    ConstDecl { ty: Type, name: String, init: Expr },
+
     /// Return statement: `return expr;`
     Return(Expr),
 
@@ -74,6 +96,9 @@ pub enum StmtKind {
     /// While loop: `while (cond) body`
     While { condition: Expr, body: Box<Stmt> },
 
+    /// Do-while loop: `do body while (cond);`, the body runs at least once
+    DoWhile { body: Box<Stmt>, condition: Expr },
+
     /// For loop: `for (init; condition; increment) body`
     For {
         init: Option<Box<Stmt>>,
@@ -81,6 +106,21 @@ pub enum StmtKind {
         increment: Option<Expr>,
         body: Box<Stmt>,
     },
+
+    /// Switch over an int: `switch (selector) { case 1: ... default: ... }`
+    ///
+    /// Control falls through from one case into the next unless it `break`s.
+    /// The type checker folds each case value to an `IntNumber` literal.
+    Switch {
+        selector: Expr,
+        cases: Vec<SwitchCase>,
+    },
+
+    /// Leave the innermost loop or switch: `break;`
+    Break,
+
+    /// Skip to the innermost loop's next iteration: `continue;`
+    Continue,
 }
 
 /// Expression with types (span and optional type)
@@ -114,6 +154,7 @@ pub enum ExprKind {
     // Literals
     Number(f32),
     IntNumber(i32),
+    Bool(bool),
     Variable(String),
 
     // Binary operations
@@ -167,6 +208,30 @@ pub enum ExprKind {
         value: Box<Expr>,
     },
 
+    // Grid element access `name[row][col]` and assignment `name[row][col] = value`
+    GridIndex {
+        name: String,
+        row: Box<Expr>,
+        col: Box<Expr>,
+    },
+    GridAssign {
+        name: String,
+        row: Box<Expr>,
+        col: Box<Expr>,
+        value: Box<Expr>,
+    },
+
+    // Array element access `name[index]` and assignment `name[index] = value`
+    ArrayIndex {
+        name: String,
+        index: Box<Expr>,
+    },
+    ArrayAssign {
+        name: String,
+        index: Box<Expr>,
+        value: Box<Expr>,
+    },
+
     // Function call
     Call {
         name: String,
@@ -177,7 +242,12 @@ pub enum ExprKind {
     Vec2Constructor(Vec<Expr>),
     Vec3Constructor(Vec<Expr>),
     Vec4Constructor(Vec<Expr>),
+    BVec2Constructor(Vec<Expr>),
+    BVec3Constructor(Vec<Expr>),
+    BVec4Constructor(Vec<Expr>),
+    Mat2Constructor(Vec<Expr>),
     Mat3Constructor(Vec<Expr>),
+    Mat4Constructor(Vec<Expr>),
 
     // Swizzle (component access/reordering)
     Swizzle {
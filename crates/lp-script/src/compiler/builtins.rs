@@ -0,0 +1,324 @@
+/// Catalog of built-in variables, constants and functions
+///
+/// The type checker resolves built-in names against this table, so it is
+/// also a complete, accurate list for editor autocomplete and docs.
+use crate::fixed::Fixed;
+use crate::shared::Type;
+
+/// A parameter or result type in a built-in function signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuiltinType {
+    /// Exactly this type
+    Exact(Type),
+    /// `float` or `int`
+    Scalar,
+    /// `vec2`, `vec3` or `vec4`; every `AnyVec` in one call, including the
+    /// result, is the same vector type
+    AnyVec,
+    /// `mat2`, `mat3` or `mat4`; every `AnyMat` in one call, including the
+    /// result, is the same matrix type
+    AnyMat,
+    /// `bvec2`, `bvec3` or `bvec4`; every `AnyBVec` in one call, including
+    /// the result, is the same bvec type, with as many components as the
+    /// call's `AnyVec`
+    AnyBVec,
+}
+
+/// Signature of a built-in function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuiltinFunction {
+    pub name: &'static str,
+    pub params: &'static [BuiltinType],
+    /// Leading `params` a call must pass; the rest are optional
+    pub required: usize,
+    pub returns: BuiltinType,
+    /// Also accepts vectors, applying the function per component
+    pub componentwise: bool,
+}
+
+impl BuiltinFunction {
+    /// Whether a call may pass `count` arguments
+    pub fn accepts_arg_count(&self, count: usize) -> bool {
+        (self.required..=self.params.len()).contains(&count)
+    }
+}
+
+/// A built-in variable, constant or function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuiltinSig {
+    Variable {
+        name: &'static str,
+        ty: Type,
+    },
+    /// A float folded to a literal at compile time
+    Constant {
+        name: &'static str,
+        value: Fixed,
+    },
+    Function(BuiltinFunction),
+}
+
+impl BuiltinSig {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltinSig::Variable { name, .. } | BuiltinSig::Constant { name, .. } => name,
+            BuiltinSig::Function(func) => func.name,
+        }
+    }
+}
+
+const FLOAT: BuiltinType = BuiltinType::Exact(Type::Fixed);
+const SCALAR: BuiltinType = BuiltinType::Scalar;
+const VEC: BuiltinType = BuiltinType::AnyVec;
+const MAT: BuiltinType = BuiltinType::AnyMat;
+const BVEC: BuiltinType = BuiltinType::AnyBVec;
+
+const fn var(name: &'static str, ty: Type) -> BuiltinSig {
+    BuiltinSig::Variable { name, ty }
+}
+
+const fn constant(name: &'static str, value: Fixed) -> BuiltinSig {
+    BuiltinSig::Constant { name, value }
+}
+
+const fn func(
+    name: &'static str,
+    params: &'static [BuiltinType],
+    returns: BuiltinType,
+) -> BuiltinSig {
+    BuiltinSig::Function(BuiltinFunction {
+        name,
+        params,
+        required: params.len(),
+        returns,
+        componentwise: false,
+    })
+}
+
+/// A scalar function that also applies per component to vectors
+const fn per_component(name: &'static str, params: &'static [BuiltinType]) -> BuiltinSig {
+    BuiltinSig::Function(BuiltinFunction {
+        name,
+        params,
+        required: params.len(),
+        returns: FLOAT,
+        componentwise: true,
+    })
+}
+
+/// A function whose trailing parameters may be left out
+const fn optional(sig: BuiltinSig, required: usize) -> BuiltinSig {
+    match sig {
+        BuiltinSig::Function(func) => BuiltinSig::Function(BuiltinFunction { required, ..func }),
+        variable => variable,
+    }
+}
+
+const BUILTINS: &[BuiltinSig] = &[
+    // Variables
+    var("uv", Type::Vec2),
+    var("coord", Type::Vec2),
+    var("polar", Type::Vec2),
+    var("time", Type::Fixed),
+    var("t", Type::Fixed),
+    var("timeNorm", Type::Fixed),
+    var("centerAngle", Type::Fixed),
+    var("angle", Type::Fixed),
+    var("centerDist", Type::Fixed),
+    var("dist", Type::Fixed),
+    var("index", Type::Int32),
+    // Legacy, kept for compatibility
+    var("x", Type::Fixed),
+    var("xNorm", Type::Fixed),
+    var("y", Type::Fixed),
+    var("yNorm", Type::Fixed),
+    // Constants
+    constant("PI", Fixed::PI),
+    constant("TAU", Fixed::TAU),
+    constant("E", Fixed::E),
+    constant("PHI", Fixed::PHI),
+    // Scalar math
+    per_component("sin", &[SCALAR]),
+    per_component("cos", &[SCALAR]),
+    per_component("tan", &[SCALAR]),
+    optional(per_component("atan", &[SCALAR, SCALAR]), 1),
+    per_component("abs", &[SCALAR]),
+    per_component("floor", &[SCALAR]),
+    per_component("ceil", &[SCALAR]),
+    per_component("sqrt", &[SCALAR]),
+    per_component("sign", &[SCALAR]),
+    func("frac", &[SCALAR], FLOAT),
+    per_component("fract", &[SCALAR]),
+    per_component("saturate", &[SCALAR]),
+    per_component("pow", &[SCALAR, SCALAR]),
+    per_component("mod", &[SCALAR, SCALAR]),
+    per_component("min", &[SCALAR, SCALAR]),
+    per_component("max", &[SCALAR, SCALAR]),
+    per_component("step", &[SCALAR, SCALAR]),
+    per_component("clamp", &[SCALAR, SCALAR, SCALAR]),
+    per_component("mix", &[SCALAR, SCALAR, SCALAR]),
+    per_component("smoothstep", &[SCALAR, SCALAR, SCALAR]),
+    func("lerp", &[SCALAR, SCALAR, SCALAR], FLOAT),
+    func("remap", &[SCALAR, SCALAR, SCALAR, SCALAR, SCALAR], FLOAT),
+    func("phase", &[SCALAR], FLOAT),
+    func("wrapTime", &[SCALAR, SCALAR], FLOAT),
+    func("quantize", &[SCALAR, SCALAR], FLOAT),
+    // Casts
+    func("int", &[FLOAT], BuiltinType::Exact(Type::Int32)),
+    func("float", &[BuiltinType::Exact(Type::Int32)], FLOAT),
+    // Vectors
+    func("length", &[VEC], FLOAT),
+    func("normalize", &[VEC], VEC),
+    func("dot", &[VEC, VEC], FLOAT),
+    func("distance", &[VEC, VEC], FLOAT),
+    func("lengthSq", &[VEC], FLOAT),
+    func("distanceSq", &[VEC, VEC], FLOAT),
+    func(
+        "cross",
+        &[
+            BuiltinType::Exact(Type::Vec3),
+            BuiltinType::Exact(Type::Vec3),
+        ],
+        BuiltinType::Exact(Type::Vec3),
+    ),
+    func("reflect", &[VEC, VEC], VEC),
+    func("faceforward", &[VEC, VEC, VEC], VEC),
+    func("maxComp", &[VEC], FLOAT),
+    func("minComp", &[VEC], FLOAT),
+    func("sum", &[VEC], FLOAT),
+    func("avg", &[VEC], FLOAT),
+    // Per-component comparisons and their reductions
+    func("lessThan", &[VEC, VEC], BVEC),
+    func("lessThanEqual", &[VEC, VEC], BVEC),
+    func("greaterThan", &[VEC, VEC], BVEC),
+    func("greaterThanEqual", &[VEC, VEC], BVEC),
+    func("equal", &[VEC, VEC], BVEC),
+    func("notEqual", &[VEC, VEC], BVEC),
+    func("any", &[BVEC], BuiltinType::Exact(Type::Bool)),
+    func("all", &[BVEC], BuiltinType::Exact(Type::Bool)),
+    func("not", &[BVEC], BVEC),
+    // Coordinates
+    func(
+        "fromPolar",
+        &[BuiltinType::Exact(Type::Vec2)],
+        BuiltinType::Exact(Type::Vec2),
+    ),
+    func(
+        "tile",
+        &[BuiltinType::Exact(Type::Vec2), SCALAR],
+        BuiltinType::Exact(Type::Vec2),
+    ),
+    func(
+        "mirrorTile",
+        &[BuiltinType::Exact(Type::Vec2), SCALAR],
+        BuiltinType::Exact(Type::Vec2),
+    ),
+    func(
+        "toPixel",
+        &[BuiltinType::Exact(Type::Vec2)],
+        BuiltinType::Exact(Type::Vec2),
+    ),
+    func(
+        "toUv",
+        &[BuiltinType::Exact(Type::Vec2)],
+        BuiltinType::Exact(Type::Vec2),
+    ),
+    // Color and noise; perlin3's octaves must be a constant int in 1..=8
+    func("palette", &[SCALAR], BuiltinType::Exact(Type::Vec3)),
+    func(
+        "hsv2rgb",
+        &[BuiltinType::Exact(Type::Vec3)],
+        BuiltinType::Exact(Type::Vec3),
+    ),
+    func(
+        "rgb2hsv",
+        &[BuiltinType::Exact(Type::Vec3)],
+        BuiltinType::Exact(Type::Vec3),
+    ),
+    optional(
+        func(
+            "perlin3",
+            &[
+                BuiltinType::Exact(Type::Vec3),
+                BuiltinType::Exact(Type::Int32),
+            ],
+            FLOAT,
+        ),
+        1,
+    ),
+    // Textures; the slots must be constant ints below MAX_TEXTURES
+    func(
+        "mixTex",
+        &[
+            BuiltinType::Exact(Type::Int32),
+            BuiltinType::Exact(Type::Int32),
+            BuiltinType::Exact(Type::Vec2),
+            SCALAR,
+        ],
+        BuiltinType::Exact(Type::Vec4),
+    ),
+    // Matrices
+    func("rotate2x2", &[SCALAR], BuiltinType::Exact(Type::Mat2)),
+    func("transpose", &[MAT], MAT),
+    func("determinant", &[MAT], FLOAT),
+    func("inverse", &[MAT], MAT),
+];
+
+/// Every built-in variable and function
+pub fn builtins() -> &'static [BuiltinSig] {
+    BUILTINS
+}
+
+/// Type of the built-in variable `name`
+pub(crate) fn builtin_variable(name: &str) -> Option<Type> {
+    BUILTINS.iter().find_map(|sig| match sig {
+        BuiltinSig::Variable { name: n, ty } if *n == name => Some(ty.clone()),
+        _ => None,
+    })
+}
+
+/// Value of the built-in constant `name`
+pub(crate) fn builtin_constant(name: &str) -> Option<Fixed> {
+    BUILTINS.iter().find_map(|sig| match sig {
+        BuiltinSig::Constant { name: n, value } if *n == name => Some(*value),
+        _ => None,
+    })
+}
+
+/// Signature of the built-in function `name`
+pub(crate) fn builtin_function(name: &str) -> Option<&'static BuiltinFunction> {
+    BUILTINS.iter().find_map(|sig| match sig {
+        BuiltinSig::Function(func) if func.name == name => Some(func),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_lists_sin_and_uv() {
+        let sin = builtin_function("sin").unwrap();
+        assert_eq!(sin.params, &[BuiltinType::Scalar]);
+        assert_eq!(sin.returns, BuiltinType::Exact(Type::Fixed));
+        assert!(sin.componentwise);
+        assert!(sin.accepts_arg_count(1) && !sin.accepts_arg_count(2));
+
+        assert!(builtins().contains(&BuiltinSig::Variable {
+            name: "uv",
+            ty: Type::Vec2,
+        }));
+    }
+
+    #[test]
+    fn test_catalog_names_are_unique() {
+        for (i, sig) in builtins().iter().enumerate() {
+            assert!(
+                builtins()[i + 1..].iter().all(|s| s.name() != sig.name()),
+                "{} listed twice",
+                sig.name()
+            );
+        }
+    }
+}
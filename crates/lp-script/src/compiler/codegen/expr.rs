@@ -21,6 +21,7 @@ impl<'a> CodeGenerator<'a> {
                     self.code.push(LpsOpCode::Int32ToFixed);
                 }
             }
+            ExprKind::Bool(b) => self.gen_bool(*b),
             ExprKind::Variable(name) => {
                 if let Some(ty) = expr_ty {
                     self.gen_variable(name, ty);
@@ -108,12 +109,31 @@ impl<'a> CodeGenerator<'a> {
 
             ExprKind::Assign { target, value } => self.gen_assign_expr(target, value.as_ref()),
 
+            ExprKind::GridIndex { name, row, col } => {
+                self.gen_grid_index(name, row.as_ref(), col.as_ref())
+            }
+            ExprKind::GridAssign {
+                name,
+                row,
+                col,
+                value,
+            } => self.gen_grid_assign(name, row.as_ref(), col.as_ref(), value.as_ref()),
+            ExprKind::ArrayIndex { name, index } => self.gen_array_index(name, index.as_ref()),
+            ExprKind::ArrayAssign { name, index, value } => {
+                self.gen_array_assign(name, index.as_ref(), value.as_ref())
+            }
+
             ExprKind::Call { name, args } => self.gen_function_call(name, args),
 
             ExprKind::Vec2Constructor(args)
             | ExprKind::Vec3Constructor(args)
             | ExprKind::Vec4Constructor(args)
-            | ExprKind::Mat3Constructor(args) => self.gen_vec_constructor(args),
+            | ExprKind::BVec2Constructor(args)
+            | ExprKind::BVec3Constructor(args)
+            | ExprKind::BVec4Constructor(args)
+            | ExprKind::Mat2Constructor(args)
+            | ExprKind::Mat3Constructor(args)
+            | ExprKind::Mat4Constructor(args) => self.gen_vec_constructor(args, expr.ty.as_ref()),
 
             ExprKind::Swizzle { expr, components } => self.gen_swizzle(expr.as_ref(), components),
         }
@@ -5,6 +5,10 @@ use alloc::string::String;
 use alloc::vec::Vec;
 
 use crate::compiler::ast::{Expr, Program};
+use crate::compiler::error::{CodegenError, CodegenErrorKind};
+use crate::shared::Span;
+use crate::vm::opcodes::OpcodeSet;
+use crate::vm::{ExternalDef, FunctionDef, NativeDef};
 use crate::LpsOpCode;
 
 mod expr;
@@ -20,6 +24,21 @@ pub struct CodeGenerator<'a> {
     pub(crate) code: &'a mut Vec<LpsOpCode>,
     pub(crate) locals: &'a mut LocalAllocator,
     pub(crate) func_offsets: &'a BTreeMap<String, u32>,
+    /// Host-provided values, loaded by index with `LoadExternal`
+    pub(crate) externals: &'a [ExternalDef],
+    /// Host functions, called by index with `CallNative`
+    pub(crate) natives: &'a [NativeDef],
+    /// Loops and switches enclosing the statement being generated, innermost last
+    pub(crate) loops: Vec<LoopJumps>,
+}
+
+/// `break` and `continue` jumps of a loop or switch, patched once its end is known
+#[derive(Default)]
+pub(crate) struct LoopJumps {
+    pub(crate) breaks: Vec<usize>,
+    pub(crate) continues: Vec<usize>,
+    /// A switch, which `break` leaves but `continue` passes through
+    pub(crate) is_switch: bool,
 }
 
 impl<'a> CodeGenerator<'a> {
@@ -33,21 +52,45 @@ impl<'a> CodeGenerator<'a> {
             code,
             locals,
             func_offsets,
+            externals: &[],
+            natives: &[],
+            loops: Vec::new(),
         }
     }
 
     /// Generate opcodes for an expression (expression mode)
+    #[cfg(test)]
     pub fn generate(expr: &Expr) -> Vec<LpsOpCode> {
-        Self::generate_with_locals(expr, Vec::new())
+        Self::generate_expr(expr, Vec::new(), &[], &[])
+    }
+
+    /// Generate opcodes for an expression that reads host-provided `externals`
+    /// and calls host `natives`
+    pub fn generate_with_env(
+        expr: &Expr,
+        externals: &[ExternalDef],
+        natives: &[NativeDef],
+    ) -> Vec<LpsOpCode> {
+        Self::generate_expr(expr, Vec::new(), externals, natives)
     }
 
     /// Generate opcodes for an expression with pre-declared local variables
     ///
     /// This is useful for testing assignment expressions which need mutable locals.
     /// The locals should be ordered by index (e.g., [("x", 0), ("y", 1), ...])
+    #[cfg(test)]
     pub fn generate_with_locals(
         expr: &Expr,
         predeclared: Vec<(String, u32, crate::shared::Type)>,
+    ) -> Vec<LpsOpCode> {
+        Self::generate_expr(expr, predeclared, &[], &[])
+    }
+
+    fn generate_expr(
+        expr: &Expr,
+        predeclared: Vec<(String, u32, crate::shared::Type)>,
+        externals: &[ExternalDef],
+        natives: &[NativeDef],
     ) -> Vec<LpsOpCode> {
         let mut code = Vec::new();
         let mut locals = LocalAllocator::new();
@@ -64,6 +107,8 @@ impl<'a> CodeGenerator<'a> {
         }
 
         let mut gen = CodeGenerator::new(&mut code, &mut locals, &func_offsets);
+        gen.externals = externals;
+        gen.natives = natives;
         gen.gen_expr(expr);
         gen.code.push(LpsOpCode::Return);
 
@@ -90,3 +135,27 @@ impl<'a> CodeGenerator<'a> {
     // Statement dispatcher: codegen/stmt.rs
     // Individual generators: expr/*/..._gen.rs, stmt/*/..._gen.rs
 }
+
+/// Fail on the first opcode outside `allowed`
+pub(crate) fn check_opcode_set(
+    functions: &[FunctionDef],
+    allowed: &OpcodeSet,
+) -> Result<(), CodegenError> {
+    for func in functions {
+        if let Some((op, group)) = func.opcodes.iter().find_map(|op| {
+            op.group()
+                .filter(|g| !allowed.contains_group(*g))
+                .map(|g| (op, g))
+        }) {
+            return Err(CodegenError {
+                kind: CodegenErrorKind::DisallowedOpcode {
+                    function: func.name.clone(),
+                    opcode: op.name(),
+                    group,
+                },
+                span: Span::EMPTY,
+            });
+        }
+    }
+    Ok(())
+}
@@ -67,6 +67,8 @@ pub fn gen_program_with_functions(
     {
         let mut gen =
             super::CodeGenerator::new(&mut main_code, &mut main_locals, &function_indices);
+        gen.externals = func_table.externals();
+        gen.natives = func_table.natives();
         for stmt in &program.stmts {
             gen.gen_stmt(stmt);
         }
@@ -36,6 +36,12 @@ impl<'a> CodeGenerator<'a> {
             StmtKind::While { condition, body } => {
                 self.gen_while_stmt(condition, body.as_ref());
             }
+            StmtKind::DoWhile { body, condition } => {
+                self.gen_do_while_stmt(body.as_ref(), condition);
+            }
+            StmtKind::Switch { selector, cases } => {
+                self.gen_switch_stmt(selector, cases);
+            }
             StmtKind::For {
                 init,
                 condition,
@@ -49,6 +55,10 @@ impl<'a> CodeGenerator<'a> {
                     body.as_ref(),
                 );
             }
+            StmtKind::Break => self.gen_break(),
+            StmtKind::Continue => self.gen_continue(),
+            // Uses were replaced by the folded value while type checking
+            StmtKind::ConstDecl { .. } => {}
         }
     }
 }
@@ -0,0 +1,105 @@
+/// Compile-time values of fully folded expressions, see `eval_const_expr`
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::compiler::ast::{Expr, ExprKind};
+use crate::fixed::{Fixed, Vec2, Vec3, Vec4};
+use crate::shared::{Span, Type};
+
+/// A constant expression's value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConstValue {
+    Fixed(Fixed),
+    Int32(i32),
+    Bool(bool),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+}
+
+impl ConstValue {
+    /// Value of an expression after constant folding
+    ///
+    /// `None` unless the expression is a literal, or a vector constructor
+    /// whose arguments are all constant. Matrices aren't supported.
+    pub(crate) fn from_folded(expr: &Expr) -> Option<Self> {
+        let value = match &expr.kind {
+            ExprKind::Number(n) => match expr.ty {
+                Some(Type::Bool) => ConstValue::Bool(*n != 0.0),
+                Some(Type::Int32) => ConstValue::Int32(*n as i32),
+                _ => ConstValue::Fixed(Fixed::from_f32(*n)),
+            },
+            ExprKind::Bool(b) => ConstValue::Bool(*b),
+            ExprKind::IntNumber(i) => match expr.ty {
+                Some(Type::Bool) => ConstValue::Bool(*i != 0),
+                Some(Type::Fixed) => ConstValue::Fixed(Fixed::from_i32(*i)),
+                _ => ConstValue::Int32(*i),
+            },
+            ExprKind::Vec2Constructor(args) => {
+                let c = components(args, 2)?;
+                ConstValue::Vec2(Vec2::new(c[0], c[1]))
+            }
+            ExprKind::Vec3Constructor(args) => {
+                let c = components(args, 3)?;
+                ConstValue::Vec3(Vec3::new(c[0], c[1], c[2]))
+            }
+            ExprKind::Vec4Constructor(args) => {
+                let c = components(args, 4)?;
+                ConstValue::Vec4(Vec4::new(c[0], c[1], c[2], c[3]))
+            }
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// The literal expression for this value as a `ty`, e.g. a `const`'s
+    ///
+    /// `None` if the value isn't a `ty`; an `int` widens to `float`.
+    pub(crate) fn to_literal(self, ty: &Type, span: Span) -> Option<Expr> {
+        let number = |v: Fixed| {
+            let mut expr = Expr::new(ExprKind::Number(v.to_f32()), span);
+            expr.ty = Some(Type::Fixed);
+            expr
+        };
+        let kind = match (self, ty) {
+            (ConstValue::Fixed(v), Type::Fixed) => return Some(number(v)),
+            (ConstValue::Int32(v), Type::Fixed) => return Some(number(Fixed::from_i32(v))),
+            (ConstValue::Int32(v), Type::Int32) => ExprKind::IntNumber(v),
+            (ConstValue::Bool(v), Type::Bool) => ExprKind::Bool(v),
+            (ConstValue::Vec2(v), Type::Vec2) => {
+                ExprKind::Vec2Constructor(vec![number(v.x), number(v.y)])
+            }
+            (ConstValue::Vec3(v), Type::Vec3) => {
+                ExprKind::Vec3Constructor(vec![number(v.x), number(v.y), number(v.z)])
+            }
+            (ConstValue::Vec4(v), Type::Vec4) => {
+                ExprKind::Vec4Constructor(vec![number(v.x), number(v.y), number(v.z), number(v.w)])
+            }
+            _ => return None,
+        };
+        let mut expr = Expr::new(kind, span);
+        expr.ty = Some(ty.clone());
+        Some(expr)
+    }
+}
+
+/// Flatten constant constructor arguments, e.g. `vec3(vec2(1, 2), 3)`
+fn components(args: &[Expr], count: usize) -> Option<Vec<Fixed>> {
+    let mut out = Vec::with_capacity(count);
+    for arg in args {
+        match ConstValue::from_folded(arg)? {
+            ConstValue::Fixed(v) => out.push(v),
+            ConstValue::Int32(v) => out.push(Fixed::from_i32(v)),
+            ConstValue::Bool(v) => out.push(if v { Fixed::ONE } else { Fixed::ZERO }),
+            ConstValue::Vec2(v) => out.extend([v.x, v.y]),
+            ConstValue::Vec3(v) => out.extend([v.x, v.y, v.z]),
+            ConstValue::Vec4(v) => out.extend([v.x, v.y, v.z, v.w]),
+        }
+    }
+    // A single scalar splats, e.g. `vec3(0.5)`
+    if out.len() == 1 {
+        out.resize(count, out[0]);
+    }
+    (out.len() == count).then_some(out)
+}
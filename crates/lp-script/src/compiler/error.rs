@@ -7,6 +7,7 @@ use core::fmt;
 use lp_alloc::AllocLimitError;
 
 use crate::shared::{Span, Type};
+use crate::vm::opcodes::OpcodeGroup;
 
 /// Comprehensive compilation error
 #[derive(Debug)]
@@ -62,12 +63,41 @@ pub struct ParseError {
 
 #[derive(Debug)]
 pub enum ParseErrorKind {
-    UnexpectedToken { expected: String, found: String },
+    UnexpectedToken {
+        expected: String,
+        found: String,
+    },
     UnexpectedEof,
     InvalidExpression,
-    RecursionLimitExceeded { max: usize },
-    ExprLimitExceeded { max: usize },
-    StmtLimitExceeded { max: usize },
+    RecursionLimitExceeded {
+        max: usize,
+    },
+    ExprLimitExceeded {
+        max: usize,
+    },
+    StmtLimitExceeded {
+        max: usize,
+    },
+    /// `break` with no enclosing loop or switch, or `continue` with no
+    /// enclosing loop
+    OutsideLoop {
+        keyword: &'static str,
+    },
+    /// `name.member` where struct `struct_name` has no such member
+    UnknownMember {
+        struct_name: String,
+        member: String,
+    },
+    /// A struct local or constructor used as a value, outside a struct
+    /// declaration or whole-struct assignment
+    StructValue(String),
+    /// A struct used as a function parameter or return type, or as another
+    /// struct's member; structs only exist as locals
+    StructNotSupported {
+        struct_name: String,
+        /// What the struct was used as, e.g. "parameters"
+        usage: &'static str,
+    },
     AllocationFailed(String),
 }
 
@@ -89,6 +119,22 @@ impl fmt::Display for ParseError {
             ParseErrorKind::StmtLimitExceeded { max } => {
                 write!(f, "statement node limit exceeded (max: {})", max)
             }
+            ParseErrorKind::OutsideLoop { keyword: "break" } => {
+                write!(f, "'break' outside of a loop or switch")
+            }
+            ParseErrorKind::OutsideLoop { keyword } => write!(f, "'{}' outside of a loop", keyword),
+            ParseErrorKind::UnknownMember {
+                struct_name,
+                member,
+            } => write!(f, "struct '{}' has no member '{}'", struct_name, member),
+            ParseErrorKind::StructValue(name) => write!(
+                f,
+                "struct '{}' can only be used through its members here",
+                name
+            ),
+            ParseErrorKind::StructNotSupported { struct_name, usage } => {
+                write!(f, "struct {} are not supported ('{}')", usage, struct_name)
+            }
             ParseErrorKind::AllocationFailed(msg) => write!(f, "allocation failed: {}", msg),
         }
     }
@@ -113,13 +159,35 @@ pub struct TypeError {
 
 #[derive(Debug)]
 pub enum TypeErrorKind {
-    Mismatch { expected: Type, found: Type },
+    Mismatch {
+        expected: Type,
+        found: Type,
+    },
     UndefinedVariable(String),
     UndefinedFunction(String),
-    InvalidArgumentCount { expected: usize, found: usize },
-    InvalidOperation { op: String, types: Vec<Type> },
+    InvalidArgumentCount {
+        expected: usize,
+        found: usize,
+    },
+    InvalidOperation {
+        op: String,
+        types: Vec<Type>,
+    },
     InvalidSwizzle(String),
     MissingReturn(String),
+    /// A grid local used as a whole value instead of `name[y][x]`
+    UnindexedGrid(String),
+    /// An array local used as a whole value instead of `name[i]`
+    UnindexedArray(String),
+    /// `eval_const_expr` input, `const` initializer or `case` value that
+    /// doesn't fold to a literal
+    NotConstant,
+    /// Assignment, `++` or `--` on a `const`
+    ConstAssignment(String),
+    /// Assignment, `++` or `--` on a uniform or other host-set external
+    ExternalAssignment(String),
+    /// Two labels of one switch with the same value
+    DuplicateCase(i32),
 }
 
 impl fmt::Display for TypeError {
@@ -139,7 +207,14 @@ impl fmt::Display for TypeError {
                 write!(f, "expected {} arguments, found {}", expected, found)
             }
             TypeErrorKind::InvalidOperation { op, types } => {
-                write!(f, "invalid operation '{}' for types {:?}", op, types)
+                write!(f, "invalid operation '{}' for types (", op)?;
+                for (i, ty) in types.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", ty)?;
+                }
+                write!(f, ")")
             }
             TypeErrorKind::InvalidSwizzle(msg) => {
                 write!(f, "invalid swizzle: {}", msg)
@@ -151,6 +226,20 @@ impl fmt::Display for TypeError {
                     name
                 )
             }
+            TypeErrorKind::UnindexedGrid(name) => {
+                write!(f, "grid '{}' must be indexed as {}[y][x]", name, name)
+            }
+            TypeErrorKind::UnindexedArray(name) => {
+                write!(f, "array '{}' must be indexed as {}[i]", name, name)
+            }
+            TypeErrorKind::NotConstant => write!(f, "expression is not constant"),
+            TypeErrorKind::ConstAssignment(name) => {
+                write!(f, "cannot assign to const '{}'", name)
+            }
+            TypeErrorKind::ExternalAssignment(name) => {
+                write!(f, "cannot assign to uniform '{}'", name)
+            }
+            TypeErrorKind::DuplicateCase(value) => write!(f, "duplicate case value {}", value),
         }
     }
 }
@@ -168,6 +257,12 @@ pub enum CodegenErrorKind {
     TooManyLocals,
     TooManyOpcodes,
     AllocationFailed(String),
+    /// `function` needs `opcode`, whose group is excluded from the allowed set
+    DisallowedOpcode {
+        function: String,
+        opcode: &'static str,
+        group: OpcodeGroup,
+    },
 }
 
 impl fmt::Display for CodegenError {
@@ -184,6 +279,17 @@ impl fmt::Display for CodegenError {
             CodegenErrorKind::TooManyLocals => write!(f, "too many local variables"),
             CodegenErrorKind::TooManyOpcodes => write!(f, "program too large"),
             CodegenErrorKind::AllocationFailed(msg) => write!(f, "allocation failed: {}", msg),
+            CodegenErrorKind::DisallowedOpcode {
+                function,
+                opcode,
+                group,
+            } => write!(
+                f,
+                "'{}' needs opcode {} ({}), which is not in the allowed opcode set",
+                function,
+                opcode,
+                group.name()
+            ),
         }
     }
 }
@@ -0,0 +1,37 @@
+/// Array element access code generation
+extern crate alloc;
+
+use crate::compiler::ast::Expr;
+use crate::compiler::codegen::CodeGenerator;
+use crate::shared::Type;
+use crate::vm::opcodes::LpsOpCode;
+
+impl<'a> CodeGenerator<'a> {
+    pub(crate) fn gen_array_index(&mut self, name: &str, index: &Expr) {
+        if let Some(local_idx) = self.gen_array_elem(name, index) {
+            self.code.push(LpsOpCode::LoadLocalElemFixed(local_idx));
+        }
+    }
+
+    pub(crate) fn gen_array_assign(&mut self, name: &str, index: &Expr, value: &Expr) {
+        self.gen_expr(value);
+        // Assignment returns the assigned value
+        self.code.push(LpsOpCode::Dup1);
+
+        if let Some(local_idx) = self.gen_array_elem(name, index) {
+            self.code.push(LpsOpCode::StoreLocalElemFixed(local_idx));
+        }
+    }
+
+    /// Push the element index; the VM bounds-checks it against the array
+    fn gen_array_elem(&mut self, name: &str, index: &Expr) -> Option<u32> {
+        let local_idx = self.locals.get(name)?;
+        if !matches!(self.locals.get_type(local_idx), Some(Type::Array { .. })) {
+            return None;
+        }
+
+        self.gen_expr(index);
+
+        Some(local_idx)
+    }
+}
@@ -0,0 +1,162 @@
+/// Array local tests
+#[cfg(test)]
+mod tests {
+    use crate::compiler::error::{CompileError, ParseErrorKind, TypeErrorKind};
+    use crate::compiler::stmt::stmt_test_util::ScriptTest;
+    use crate::fixed::ToFixed;
+    use crate::vm::error::LpsVmError;
+    use crate::vm::lps_vm::LpsVm;
+    use crate::vm::opcodes::LpsOpCode;
+    use crate::vm::vm_limits::VmLimits;
+    use crate::{compile_script_with_options, OptimizeOptions};
+
+    #[test]
+    fn test_array_store_and_load() -> Result<(), String> {
+        ScriptTest::new("float v[3]; v[2] = 4.0; return v[2] + v[0];")
+            .expect_opcodes(vec![
+                // v[2] = 4.0
+                LpsOpCode::Push(4.0.to_fixed()),
+                LpsOpCode::Dup1,
+                LpsOpCode::PushInt32(2),
+                LpsOpCode::StoreLocalElemFixed(0),
+                LpsOpCode::Drop1,
+                // v[2]
+                LpsOpCode::PushInt32(2),
+                LpsOpCode::LoadLocalElemFixed(0),
+                // v[0]
+                LpsOpCode::PushInt32(0),
+                LpsOpCode::LoadLocalElemFixed(0),
+                LpsOpCode::AddFixed,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(4.0)
+            .run()
+    }
+
+    #[test]
+    fn test_array_fill_and_sum_in_loops() -> Result<(), String> {
+        ScriptTest::new(
+            "float values[8]; \
+             for i in 0..8 { values[i] = float(i) * 0.5; } \
+             float sum = 0.0; \
+             for (int i = 0; i < 8; i++) { sum += values[i]; } \
+             return sum;",
+        )
+        // 0.5 * (0 + 1 + ... + 7)
+        .expect_result_fixed(14.0)
+        .run()
+    }
+
+    #[test]
+    fn test_array_compound_assignment() -> Result<(), String> {
+        ScriptTest::new(
+            "float hist[4]; \
+             for i in 0..10 { hist[i % 4] += 1.0; } \
+             return hist[0] * 10.0 + hist[3];",
+        )
+        // Buckets 0 and 1 get 3 hits, 2 and 3 get 2
+        .expect_result_fixed(32.0)
+        .run()
+    }
+
+    #[test]
+    fn test_array_element_increment_and_decrement() -> Result<(), String> {
+        ScriptTest::new(
+            "float a[3]; a[1] = 1.0; \
+             a[1]++; ++a[1]; a[2]--; \
+             return a[1] * 10.0 + a[2];",
+        )
+        .expect_result_fixed(29.0)
+        .run()?;
+
+        // Postfix yields the old value, prefix the new one
+        ScriptTest::new(
+            "float a[2]; a[0] = 2.0; \
+             float old = a[0]++; float new = --a[0]; \
+             return old * 100.0 + new * 10.0 + a[0];",
+        )
+        .expect_result_fixed(222.0)
+        .run()
+    }
+
+    #[test]
+    fn test_array_sized_by_const() -> Result<(), String> {
+        ScriptTest::new(
+            "const int N = 4; const int M = N * 2; \
+             float a[M]; float[N - 1] b; \
+             for i in 0..M { a[i] = 1.0; } \
+             b[N - 2] = 0.5; \
+             float s = 0.0; \
+             for i in 0..M { s += a[i]; } \
+             return s + b[2];",
+        )
+        .expect_result_fixed(8.5)
+        .run()
+    }
+
+    #[test]
+    fn test_array_index_out_of_bounds() {
+        let program = compile_script_with_options(
+            "float v[4]; int i = 4; return v[i];",
+            &OptimizeOptions::none(),
+        )
+        .unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let err = vm
+            .run_scalar(0.0.to_fixed(), 0.0.to_fixed(), 0.0.to_fixed())
+            .unwrap_err();
+        assert!(matches!(
+            err.error,
+            LpsVmError::InvalidArrayIndex {
+                index: 4,
+                array_size: 4
+            }
+        ));
+    }
+
+    #[test]
+    fn test_array_index_must_be_int() {
+        let result =
+            compile_script_with_options("float v[2]; return v[1.0];", &OptimizeOptions::none());
+        match result {
+            Err(CompileError::TypeCheck(err)) => {
+                assert!(matches!(err.kind, TypeErrorKind::Mismatch { .. }))
+            }
+            _ => panic!("Expected TypeCheck error"),
+        }
+    }
+
+    #[test]
+    fn test_array_used_as_value() {
+        for src in [
+            "float v[2]; return v;",
+            "float v[2]; float w[2]; v = w; return 0.0;",
+        ] {
+            match compile_script_with_options(src, &OptimizeOptions::none()) {
+                Err(CompileError::TypeCheck(err)) => {
+                    assert!(matches!(err.kind, TypeErrorKind::UnindexedArray(_)))
+                }
+                _ => panic!("Expected TypeCheck error for {}", src),
+            }
+        }
+    }
+
+    #[test]
+    fn test_array_declaration_errors() {
+        for src in [
+            "float v[0]; return 0.0;",
+            "int v[2]; return 0.0;",
+            "float v[2] = 1.0; return 0.0;",
+            "int n = 4; float v[n]; return 0.0;",
+            "const int N = 0; float v[N]; return 0.0;",
+            "const float N = 4.0; float v[N]; return 0.0;",
+        ] {
+            match compile_script_with_options(src, &OptimizeOptions::none()) {
+                Err(CompileError::Parser(err)) => {
+                    assert!(matches!(err.kind, ParseErrorKind::UnexpectedToken { .. }))
+                }
+                _ => panic!("Expected Parse error for {}", src),
+            }
+        }
+    }
+}
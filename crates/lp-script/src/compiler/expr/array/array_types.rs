@@ -0,0 +1,87 @@
+/// Array element access type checking
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec;
+
+use crate::compiler::ast::Expr;
+use crate::compiler::error::{TypeError, TypeErrorKind};
+use crate::compiler::typechecker::{FunctionTable, SymbolTable, TypeChecker};
+use crate::shared::{Span, Type};
+
+/// Check `name[index]`: `name` must be an array local, `index` an int
+///
+/// Returns the element type (`float`).
+pub(in crate::compiler) fn check_array_index(
+    name: &str,
+    index: &mut Expr,
+    symbols: &mut SymbolTable,
+    func_table: &FunctionTable,
+    span: Span,
+) -> Result<Type, TypeError> {
+    match symbols.lookup(name) {
+        Some(Type::Array { .. }) => {}
+        // A grid needs both subscripts
+        Some(Type::Grid { .. }) => {
+            return Err(TypeError {
+                kind: TypeErrorKind::UnindexedGrid(String::from(name)),
+                span,
+            })
+        }
+        Some(ty) => {
+            return Err(TypeError {
+                kind: TypeErrorKind::InvalidOperation {
+                    op: String::from("[]"),
+                    types: vec![ty],
+                },
+                span,
+            })
+        }
+        None => {
+            return Err(TypeError {
+                kind: TypeErrorKind::UndefinedVariable(String::from(name)),
+                span,
+            })
+        }
+    }
+
+    TypeChecker::infer_type(index, symbols, func_table)?;
+    let index_ty = index.ty.clone().unwrap_or(Type::Fixed);
+    if index_ty != Type::Int32 {
+        return Err(TypeError {
+            kind: TypeErrorKind::Mismatch {
+                expected: Type::Int32,
+                found: index_ty,
+            },
+            span: index.span,
+        });
+    }
+
+    Ok(Type::Fixed)
+}
+
+/// Check `name[index] = value`; returns the assigned value's type
+pub(in crate::compiler) fn check_array_assign(
+    name: &str,
+    index: &mut Expr,
+    value: &mut Expr,
+    symbols: &mut SymbolTable,
+    func_table: &FunctionTable,
+    span: Span,
+) -> Result<Type, TypeError> {
+    let elem_ty = check_array_index(name, index, symbols, func_table, span)?;
+
+    TypeChecker::infer_type(value, symbols, func_table)?;
+    let value_ty = value.ty.clone().unwrap_or(Type::Fixed);
+    if value_ty != elem_ty {
+        return Err(TypeError {
+            kind: TypeErrorKind::Mismatch {
+                expected: elem_ty,
+                found: value_ty,
+            },
+            span: value.span,
+        });
+    }
+
+    Ok(elem_ty)
+}
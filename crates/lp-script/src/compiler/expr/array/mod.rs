@@ -0,0 +1,11 @@
+/// Array local module
+///
+/// Groups code generation, type checking, and tests for `float name[N]`
+/// element access (`name[i]`, `name[i] = v`). Subscripts are parsed with
+/// grid indexing (see grid/grid_parse.rs).
+mod array_gen;
+mod array_types;
+pub(in crate::compiler) use array_types::{check_array_assign, check_array_index};
+
+#[cfg(test)]
+mod array_tests;
@@ -15,9 +15,11 @@ impl<'a> CodeGenerator<'a> {
 
             // Duplicate value based on type (assignment returns the assigned value)
             match var_type {
-                Type::Vec2 => self.code.push(LpsOpCode::Dup2),
-                Type::Vec3 => self.code.push(LpsOpCode::Dup3),
-                Type::Vec4 => self.code.push(LpsOpCode::Dup4),
+                Type::Vec2 | Type::BVec2 => self.code.push(LpsOpCode::Dup2),
+                Type::Vec3 | Type::BVec3 => self.code.push(LpsOpCode::Dup3),
+                Type::Vec4 | Type::BVec4 | Type::Mat2 => self.code.push(LpsOpCode::Dup4),
+                Type::Mat3 => self.code.push(LpsOpCode::Dup9),
+                Type::Mat4 => self.code.push(LpsOpCode::Dup16),
                 _ => self.code.push(LpsOpCode::Dup1),
             }
 
@@ -25,9 +27,12 @@ impl<'a> CodeGenerator<'a> {
             self.code.push(match var_type {
                 Type::Fixed | Type::Bool => LpsOpCode::StoreLocalFixed(local_idx),
                 Type::Int32 => LpsOpCode::StoreLocalInt32(local_idx),
-                Type::Vec2 => LpsOpCode::StoreLocalVec2(local_idx),
-                Type::Vec3 => LpsOpCode::StoreLocalVec3(local_idx),
-                Type::Vec4 => LpsOpCode::StoreLocalVec4(local_idx),
+                Type::Vec2 | Type::BVec2 => LpsOpCode::StoreLocalVec2(local_idx),
+                Type::Vec3 | Type::BVec3 => LpsOpCode::StoreLocalVec3(local_idx),
+                Type::Vec4 | Type::BVec4 => LpsOpCode::StoreLocalVec4(local_idx),
+                Type::Mat2 => LpsOpCode::StoreLocalMat2(local_idx),
+                Type::Mat3 => LpsOpCode::StoreLocalMat3(local_idx),
+                Type::Mat4 => LpsOpCode::StoreLocalMat4(local_idx),
                 _ => LpsOpCode::StoreLocalFixed(local_idx),
             });
         }
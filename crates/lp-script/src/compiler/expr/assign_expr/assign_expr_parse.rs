@@ -34,6 +34,35 @@ impl Parser {
                         },
                         Span::new(start, end),
                     ))
+                } else if let ExprKind::GridIndex { name, row, col } = expr.kind {
+                    let start = expr.span.start;
+                    self.advance(); // consume '='
+                    let value = self.parse_assignment_expr()?; // right-associative
+                    let end = value.span.end;
+
+                    Ok(Expr::new(
+                        ExprKind::GridAssign {
+                            name,
+                            row,
+                            col,
+                            value: Box::new(value),
+                        },
+                        Span::new(start, end),
+                    ))
+                } else if let ExprKind::ArrayIndex { name, index } = expr.kind {
+                    let start = expr.span.start;
+                    self.advance(); // consume '='
+                    let value = self.parse_assignment_expr()?; // right-associative
+                    let end = value.span.end;
+
+                    Ok(Expr::new(
+                        ExprKind::ArrayAssign {
+                            name,
+                            index,
+                            value: Box::new(value),
+                        },
+                        Span::new(start, end),
+                    ))
                 } else {
                     Ok(expr)
                 }
@@ -88,6 +117,51 @@ impl Parser {
                 },
                 Span::new(start, end),
             ))
+        } else if let ExprKind::GridIndex { name, row, col } = &expr.kind {
+            // grid[y][x] += v becomes grid[y][x] = grid[y][x] + v
+            // (the subscripts are evaluated twice)
+            let (name, row, col) = (name.clone(), row.clone(), col.clone());
+            let start = expr.span.start;
+            self.advance(); // consume compound operator
+            let rhs = self.ternary()?;
+
+            let end = rhs.span.end;
+            let op = Expr::new(
+                make_op(Box::new(expr), Box::new(rhs)),
+                Span::new(start, end),
+            );
+
+            Ok(Expr::new(
+                ExprKind::GridAssign {
+                    name,
+                    row,
+                    col,
+                    value: Box::new(op),
+                },
+                Span::new(start, end),
+            ))
+        } else if let ExprKind::ArrayIndex { name, index } = &expr.kind {
+            // array[i] += v becomes array[i] = array[i] + v
+            // (the index is evaluated twice)
+            let (name, index) = (name.clone(), index.clone());
+            let start = expr.span.start;
+            self.advance(); // consume compound operator
+            let rhs = self.ternary()?;
+
+            let end = rhs.span.end;
+            let op = Expr::new(
+                make_op(Box::new(expr), Box::new(rhs)),
+                Span::new(start, end),
+            );
+
+            Ok(Expr::new(
+                ExprKind::ArrayAssign {
+                    name,
+                    index,
+                    value: Box::new(op),
+                },
+                Span::new(start, end),
+            ))
         } else {
             Ok(expr)
         }
@@ -15,8 +15,9 @@ impl<'a> CodeGenerator<'a> {
             Type::Int32 => LpsOpCode::AddInt32,
             Type::Vec2 => LpsOpCode::AddVec2,
             Type::Vec3 => LpsOpCode::AddVec3,
-            Type::Vec4 => LpsOpCode::AddVec4,
+            Type::Vec4 | Type::Mat2 => LpsOpCode::AddVec4,
             Type::Mat3 => LpsOpCode::AddMat3,
+            Type::Mat4 => LpsOpCode::AddMat4,
             _ => LpsOpCode::AddFixed,
         });
     }
@@ -29,8 +30,9 @@ impl<'a> CodeGenerator<'a> {
             Type::Int32 => LpsOpCode::SubInt32,
             Type::Vec2 => LpsOpCode::SubVec2,
             Type::Vec3 => LpsOpCode::SubVec3,
-            Type::Vec4 => LpsOpCode::SubVec4,
+            Type::Vec4 | Type::Mat2 => LpsOpCode::SubVec4,
             Type::Mat3 => LpsOpCode::SubMat3,
+            Type::Mat4 => LpsOpCode::SubMat4,
             _ => LpsOpCode::SubFixed,
         });
     }
@@ -44,7 +46,7 @@ impl<'a> CodeGenerator<'a> {
             (left_ty, right_ty),
             (
                 Type::Fixed | Type::Int32,
-                Type::Vec2 | Type::Vec3 | Type::Vec4 | Type::Mat3
+                Type::Vec2 | Type::Vec3 | Type::Vec4 | Type::Mat2 | Type::Mat3 | Type::Mat4
             )
         );
 
@@ -74,7 +76,14 @@ impl<'a> CodeGenerator<'a> {
             (Type::Vec4, Type::Vec4, Type::Vec4) => LpsOpCode::MulVec4,
 
             // Matrix-Matrix operations (matrix multiplication)
+            (Type::Mat2, Type::Mat2, Type::Mat2) => LpsOpCode::MulMat2,
             (Type::Mat3, Type::Mat3, Type::Mat3) => LpsOpCode::MulMat3,
+            (Type::Mat4, Type::Mat4, Type::Mat4) => LpsOpCode::MulMat4,
+
+            // Matrix-Vector operations
+            (Type::Mat2, Type::Vec2, Type::Vec2) => LpsOpCode::MulMat2Vec2,
+            (Type::Mat3, Type::Vec3, Type::Vec3) => LpsOpCode::MulMat3Vec3,
+            (Type::Mat4, Type::Vec4, Type::Vec4) => LpsOpCode::MulMat4Vec4,
 
             // Vector-Scalar operations
             (Type::Vec2, Type::Fixed | Type::Int32, Type::Vec2) => {
@@ -90,7 +99,8 @@ impl<'a> CodeGenerator<'a> {
                 }
                 LpsOpCode::MulVec3Scalar
             }
-            (Type::Vec4, Type::Fixed | Type::Int32, Type::Vec4) => {
+            (Type::Vec4, Type::Fixed | Type::Int32, Type::Vec4)
+            | (Type::Mat2, Type::Fixed | Type::Int32, Type::Mat2) => {
                 if matches!(right_ty, Type::Int32) {
                     self.code.push(LpsOpCode::Int32ToFixed);
                 }
@@ -104,14 +114,22 @@ impl<'a> CodeGenerator<'a> {
                 }
                 LpsOpCode::MulMat3Scalar
             }
+            (Type::Mat4, Type::Fixed | Type::Int32, Type::Mat4) => {
+                if matches!(right_ty, Type::Int32) {
+                    self.code.push(LpsOpCode::Int32ToFixed);
+                }
+                LpsOpCode::MulMat4Scalar
+            }
 
             // Scalar-Vector operations (already generated in correct order, conversion already done above)
             (Type::Fixed | Type::Int32, Type::Vec2, Type::Vec2) => LpsOpCode::MulVec2Scalar,
             (Type::Fixed | Type::Int32, Type::Vec3, Type::Vec3) => LpsOpCode::MulVec3Scalar,
-            (Type::Fixed | Type::Int32, Type::Vec4, Type::Vec4) => LpsOpCode::MulVec4Scalar,
+            (Type::Fixed | Type::Int32, Type::Vec4, Type::Vec4)
+            | (Type::Fixed | Type::Int32, Type::Mat2, Type::Mat2) => LpsOpCode::MulVec4Scalar,
 
             // Scalar-Matrix operations (already generated in correct order, conversion already done above)
             (Type::Fixed | Type::Int32, Type::Mat3, Type::Mat3) => LpsOpCode::MulMat3Scalar,
+            (Type::Fixed | Type::Int32, Type::Mat4, Type::Mat4) => LpsOpCode::MulMat4Scalar,
 
             _ => LpsOpCode::MulFixed, // Fallback
         };
@@ -135,7 +153,10 @@ impl<'a> CodeGenerator<'a> {
             // Vector-Vector operations
             (Type::Vec2, Type::Vec2, Type::Vec2) => LpsOpCode::DivVec2,
             (Type::Vec3, Type::Vec3, Type::Vec3) => LpsOpCode::DivVec3,
-            (Type::Vec4, Type::Vec4, Type::Vec4) => LpsOpCode::DivVec4,
+            // mat2 / mat2 divides component-wise, as in GLSL
+            (Type::Vec4, Type::Vec4, Type::Vec4) | (Type::Mat2, Type::Mat2, Type::Mat2) => {
+                LpsOpCode::DivVec4
+            }
 
             // Vector-Scalar operations (vec / scalar)
             (Type::Vec2, Type::Fixed | Type::Int32, Type::Vec2) => {
@@ -150,7 +171,8 @@ impl<'a> CodeGenerator<'a> {
                 }
                 LpsOpCode::DivVec3Scalar
             }
-            (Type::Vec4, Type::Fixed | Type::Int32, Type::Vec4) => {
+            (Type::Vec4, Type::Fixed | Type::Int32, Type::Vec4)
+            | (Type::Mat2, Type::Fixed | Type::Int32, Type::Mat2) => {
                 if matches!(right_ty, Type::Int32) {
                     self.code.push(LpsOpCode::Int32ToFixed);
                 }
@@ -164,6 +186,12 @@ impl<'a> CodeGenerator<'a> {
                 }
                 LpsOpCode::DivMat3Scalar
             }
+            (Type::Mat4, Type::Fixed | Type::Int32, Type::Mat4) => {
+                if matches!(right_ty, Type::Int32) {
+                    self.code.push(LpsOpCode::Int32ToFixed);
+                }
+                LpsOpCode::DivMat4Scalar
+            }
 
             _ => LpsOpCode::DivFixed, // Fallback
         };
@@ -16,6 +16,28 @@ pub(in crate::compiler) fn check_binary_arithmetic(
     symbols: &mut SymbolTable,
     func_table: &FunctionTable,
     span: crate::shared::Span,
+) -> Result<Type, TypeError> {
+    check_binary(left, right, symbols, func_table, span, false)
+}
+
+/// Type check `*`, which also allows matrix * vector
+pub(in crate::compiler) fn check_binary_mul(
+    left: &mut Expr,
+    right: &mut Expr,
+    symbols: &mut SymbolTable,
+    func_table: &FunctionTable,
+    span: crate::shared::Span,
+) -> Result<Type, TypeError> {
+    check_binary(left, right, symbols, func_table, span, true)
+}
+
+fn check_binary(
+    left: &mut Expr,
+    right: &mut Expr,
+    symbols: &mut SymbolTable,
+    func_table: &FunctionTable,
+    span: crate::shared::Span,
+    is_mul: bool,
 ) -> Result<Type, TypeError> {
     TypeChecker::infer_type(left, symbols, func_table)?;
     TypeChecker::infer_type(right, symbols, func_table)?;
@@ -23,10 +45,27 @@ pub(in crate::compiler) fn check_binary_arithmetic(
     let left_ty = left.ty.clone().unwrap();
     let right_ty = right.ty.clone().unwrap();
 
+    // Bools are 0.0/1.0 at runtime, but only an explicit cast makes them numbers
+    if left_ty.is_bool() || right_ty.is_bool() {
+        return Err(TypeError {
+            kind: TypeErrorKind::InvalidOperation {
+                op: "arithmetic".into(),
+                types: alloc::vec![left_ty, right_ty],
+            },
+            span,
+        });
+    }
+
     // Check for vector-scalar operations
     let result_ty = match (&left_ty, &right_ty) {
         // Matrix * Matrix (matrix multiplication) - check before same-type pattern
         (Type::Mat3, Type::Mat3) => Type::Mat3,
+        (Type::Mat4, Type::Mat4) => Type::Mat4,
+
+        // Matrix * Vector (returns vector)
+        (Type::Mat2, Type::Vec2) if is_mul => Type::Vec2,
+        (Type::Mat3, Type::Vec3) if is_mul => Type::Vec3,
+        (Type::Mat4, Type::Vec4) if is_mul => Type::Vec4,
 
         // Both same type
         (l, r) if l == r => l.clone(),
@@ -47,7 +86,9 @@ pub(in crate::compiler) fn check_binary_arithmetic(
         (Type::Vec4, Type::Fixed | Type::Int32) => Type::Vec4,
 
         // Matrix * Scalar (returns matrix)
+        (Type::Mat2, Type::Fixed | Type::Int32) => Type::Mat2,
         (Type::Mat3, Type::Fixed | Type::Int32) => Type::Mat3,
+        (Type::Mat4, Type::Fixed | Type::Int32) => Type::Mat4,
 
         // Scalar * Vector (returns vector)
         (Type::Fixed | Type::Int32, Type::Vec2) => Type::Vec2,
@@ -55,7 +96,9 @@ pub(in crate::compiler) fn check_binary_arithmetic(
         (Type::Fixed | Type::Int32, Type::Vec4) => Type::Vec4,
 
         // Scalar * Matrix (returns matrix)
+        (Type::Fixed | Type::Int32, Type::Mat2) => Type::Mat2,
         (Type::Fixed | Type::Int32, Type::Mat3) => Type::Mat3,
+        (Type::Fixed | Type::Int32, Type::Mat4) => Type::Mat4,
 
         // Mismatch
         _ => {
@@ -93,6 +136,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mat2_plus_vec2_error() {
+        // mat2 * vec2 is a matrix-vector product; other operators don't mix them
+        assert!(compile_expr("rotate2x2(1.0) * vec2(1.0, 0.0)").is_ok());
+
+        let result = compile_expr("rotate2x2(1.0) + vec2(1.0, 0.0)");
+        if let Err(CompileError::TypeCheck(err)) = result {
+            assert!(matches!(err.kind, TypeErrorKind::Mismatch { .. }));
+        } else {
+            panic!("Expected TypeCheck error");
+        }
+    }
+
     #[test]
     fn test_vec3_mul_vec4_error() {
         let result = compile_expr("vec3(1.0, 2.0, 3.0) * vec4(1.0, 2.0, 3.0, 4.0)");
@@ -163,7 +219,7 @@ mod tests {
         assert!(result.is_err(), "bool + vec2 should be a type error");
 
         if let Err(CompileError::TypeCheck(err)) = result {
-            assert!(matches!(err.kind, TypeErrorKind::Mismatch { .. }));
+            assert!(matches!(err.kind, TypeErrorKind::InvalidOperation { .. }));
         } else {
             panic!("Expected TypeCheck error");
         }
@@ -175,7 +231,7 @@ mod tests {
         assert!(result.is_err(), "vec3 * bool should be a type error");
 
         if let Err(CompileError::TypeCheck(err)) = result {
-            assert!(matches!(err.kind, TypeErrorKind::Mismatch { .. }));
+            assert!(matches!(err.kind, TypeErrorKind::InvalidOperation { .. }));
         } else {
             panic!("Expected TypeCheck error");
         }
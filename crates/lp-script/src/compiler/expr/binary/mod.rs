@@ -4,7 +4,7 @@
 mod binary_gen;
 mod binary_parse;
 mod binary_types;
-pub(in crate::compiler) use binary_types::check_binary_arithmetic;
+pub(in crate::compiler) use binary_types::{check_binary_arithmetic, check_binary_mul};
 
 #[cfg(test)]
 mod binary_fixed_tests;
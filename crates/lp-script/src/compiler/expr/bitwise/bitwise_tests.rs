@@ -168,10 +168,12 @@ mod tests {
 
 #[cfg(test)]
 mod integration_tests {
-    use crate::compile_script;
+    use crate::compiler::error::{CompileError, TypeErrorKind};
     use crate::fixed::ToFixed;
+    use crate::shared::Type;
     use crate::vm::lps_vm::LpsVm;
     use crate::vm::vm_limits::VmLimits;
+    use crate::{compile_expr, compile_script};
 
     #[test]
     fn test_bitwise_and_integration() {
@@ -271,4 +273,46 @@ mod integration_tests {
         // Should be 8 & (4 << 1) = 8 & 8 = 8
         assert_eq!(result.0, 8);
     }
+
+    #[test]
+    fn test_shift_on_fixed_is_invalid_operation() {
+        let err = compile_expr("1.5 << 2").unwrap_err();
+        match &err {
+            CompileError::TypeCheck(e) => match &e.kind {
+                TypeErrorKind::InvalidOperation { op, types } => {
+                    assert_eq!(op, "<<");
+                    assert_eq!(types, &vec![Type::Fixed, Type::Int32]);
+                }
+                other => panic!("expected InvalidOperation, got {:?}", other),
+            },
+            other => panic!("expected type error, got {:?}", other),
+        }
+        let message = format!("{}", err);
+        assert!(
+            message.contains("'<<' for types (float, int)"),
+            "message: {}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_shift_on_ints_compiles() {
+        assert!(compile_expr("3 << 2").is_ok());
+    }
+
+    #[test]
+    fn test_bitwise_on_non_int_operands_rejected() {
+        for source in ["2 & 1.0", "vec2(1.0) | 1", "3 >> 1.0", "~1.5"] {
+            let result = compile_expr(source);
+            assert!(
+                matches!(
+                    result,
+                    Err(CompileError::TypeCheck(ref e))
+                        if matches!(e.kind, TypeErrorKind::InvalidOperation { .. })
+                ),
+                "{} should be an invalid operation",
+                source
+            );
+        }
+    }
 }
@@ -0,0 +1,115 @@
+/// Function call tests for boolean vectors
+#[cfg(test)]
+mod tests {
+    use crate::compiler::expr::expr_test_util::ExprTest;
+    use crate::fixed::{ToFixed, Vec2, Vec3, Vec4};
+    use crate::vm::opcodes::LpsOpCode;
+
+    #[test]
+    fn test_less_than() -> Result<(), String> {
+        ExprTest::new("lessThan(vec2(1.0, 3.0), vec2(2.0, 2.0))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::LessVec2,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec2(Vec2::new(1.0.to_fixed(), 0.0.to_fixed()))
+            .run()?;
+
+        ExprTest::new("lessThanEqual(vec2(1.0, 3.0), vec2(1.0, 2.0))")
+            .expect_result_vec2(Vec2::new(1.0.to_fixed(), 0.0.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_greater_than() -> Result<(), String> {
+        ExprTest::new("greaterThan(vec3(1.0, 2.0, 3.0), vec3(2.0))")
+            .expect_result_vec3(Vec3::new(0.0.to_fixed(), 0.0.to_fixed(), 1.0.to_fixed()))
+            .run()?;
+
+        ExprTest::new("greaterThanEqual(vec3(1.0, 2.0, 3.0), vec3(2.0))")
+            .expect_result_vec3(Vec3::new(0.0.to_fixed(), 1.0.to_fixed(), 1.0.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_equal_and_not_equal() -> Result<(), String> {
+        ExprTest::new("equal(vec3(1.0, 2.0, 3.0), vec3(1.0, 0.0, 3.0))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::EqCompVec3,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec3(Vec3::new(1.0.to_fixed(), 0.0.to_fixed(), 1.0.to_fixed()))
+            .run()?;
+
+        ExprTest::new("notEqual(vec4(1.0, 2.0, 3.0, 4.0), vec4(1.0, 0.0, 3.0, 0.0))")
+            .expect_result_vec4(Vec4::new(
+                0.0.to_fixed(),
+                1.0.to_fixed(),
+                0.0.to_fixed(),
+                1.0.to_fixed(),
+            ))
+            .run()
+    }
+
+    #[test]
+    fn test_any_and_all() -> Result<(), String> {
+        ExprTest::new("any(bvec3(false, true, false))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::MaxCompVec3,
+                LpsOpCode::Return,
+            ])
+            .expect_result_bool(true)
+            .run()?;
+
+        ExprTest::new("all(lessThan(vec2(x, y), vec2(1.0)))")
+            .with_vm_params(0.5, 1.5, 0.0)
+            .expect_result_bool(false)
+            .run()?;
+
+        ExprTest::new("all(bvec4(true))")
+            .expect_result_bool(true)
+            .run()
+    }
+
+    #[test]
+    fn test_not() -> Result<(), String> {
+        ExprTest::new("not(bvec2(true, false))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::NotBVec2,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec2(Vec2::new(0.0.to_fixed(), 1.0.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_bvec_functions_check_argument_types() {
+        for src in [
+            "lessThan(vec2(1.0), vec3(1.0))",
+            "any(vec3(1.0))",
+            "not(true)",
+            "equal(1.0, 2.0)",
+        ] {
+            assert!(
+                crate::compile_expr(src).is_err(),
+                "{} should not compile",
+                src
+            );
+        }
+    }
+}
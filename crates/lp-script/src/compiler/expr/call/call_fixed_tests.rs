@@ -2,8 +2,10 @@
 #[cfg(test)]
 mod tests {
     use crate::compiler::expr::expr_test_util::ExprTest;
+    use crate::compiler::optimize::OptimizeOptions;
     use crate::fixed::ToFixed;
     use crate::shared::Type;
+    use crate::vm::opcodes::load::LoadSource;
     use crate::vm::opcodes::LpsOpCode;
 
     #[test]
@@ -154,4 +156,212 @@ mod tests {
             .expect_result_fixed(0.0)
             .run()
     }
+
+    #[test]
+    fn test_int_cast_truncates() -> Result<(), String> {
+        ExprTest::new("int(3.7)")
+            .expect_ast(|b| {
+                let arg = b.num(3.7);
+                b.call("int", vec![arg], Type::Int32)
+            })
+            .expect_opcodes(vec![
+                LpsOpCode::Push(3.7.to_fixed()),
+                LpsOpCode::FixedToInt32,
+                LpsOpCode::Return,
+            ])
+            .expect_result_int(3)
+            .run()?;
+
+        // Toward zero, not floor
+        ExprTest::new("int(x)")
+            .with_x(-3.7)
+            .expect_result_int(-3)
+            .run()?;
+
+        ExprTest::new("int(-3.7)")
+            .with_optimization(OptimizeOptions::all())
+            .expect_result_int(-3)
+            .run()
+    }
+
+    #[test]
+    fn test_float_cast() -> Result<(), String> {
+        ExprTest::new("float(5)")
+            .expect_opcodes(vec![
+                LpsOpCode::PushInt32(5),
+                LpsOpCode::Int32ToFixed,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(5.0)
+            .run()?;
+
+        ExprTest::new("float(int(x)) / 2.0")
+            .with_x(3.7)
+            .expect_result_fixed(1.5)
+            .run()
+    }
+
+    #[test]
+    fn test_wrap_time() -> Result<(), String> {
+        ExprTest::new("wrapTime(x, 2.0)")
+            .with_x(5.5)
+            .expect_opcodes(vec![
+                LpsOpCode::Load(LoadSource::XNorm),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::WrapFixed,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(1.5)
+            .run()?;
+
+        // Negative inputs wrap up into [0, period)
+        ExprTest::new("wrapTime(x, 2.0)")
+            .with_x(-0.5)
+            .expect_result_fixed(1.5)
+            .run()?;
+
+        // Exact where `mod` would overflow computing x / period
+        ExprTest::new("wrapTime(x, 0.25)")
+            .with_x(30000.1)
+            .expect_result_fixed(0.1)
+            .run()
+    }
+
+    #[test]
+    fn test_phase_wraps_time() -> Result<(), String> {
+        ExprTest::new("phase(2.0)")
+            .with_time(5.5)
+            .expect_opcodes(vec![
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::PhaseTime,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(1.5)
+            .run()?;
+
+        // Reads the clock, so it must survive optimization
+        ExprTest::new("phase(2.0)")
+            .with_time(3.25)
+            .with_optimization(OptimizeOptions::all())
+            .expect_result_fixed(1.25)
+            .run()
+    }
+
+    #[test]
+    fn test_remap() -> Result<(), String> {
+        ExprTest::new("remap(x, 0.0, time, 10, 20)")
+            .with_vm_params(0.5, 0.0, 2.0)
+            .expect_opcodes(vec![
+                LpsOpCode::Load(LoadSource::XNorm),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Load(LoadSource::Time),
+                LpsOpCode::PushInt32(10),
+                LpsOpCode::Int32ToFixed,
+                LpsOpCode::PushInt32(20),
+                LpsOpCode::Int32ToFixed,
+                LpsOpCode::RemapFixed,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(12.5)
+            .run()?;
+
+        // Reversed ranges and extrapolation
+        ExprTest::new("remap(x, 1.0, 0.0, time, 0.0)")
+            .with_vm_params(1.5, 0.0, 4.0)
+            .expect_result_fixed(6.0)
+            .run()?;
+
+        // An empty input range gives outMin instead of dividing by zero
+        ExprTest::new("remap(x, time, time, 3.0, 5.0)")
+            .with_vm_params(0.5, 0.0, 1.0)
+            .expect_result_fixed(3.0)
+            .run()
+    }
+
+    #[test]
+    fn test_remap_folds_constant_ranges() -> Result<(), String> {
+        // A single multiply-add
+        ExprTest::new("remap(x, 1.0, 3.0, 10.0, 20.0)")
+            .with_x(2.5)
+            .with_optimization(OptimizeOptions::all())
+            .expect_opcodes(vec![
+                LpsOpCode::Load(LoadSource::XNorm),
+                LpsOpCode::Push(5.0.to_fixed()),
+                LpsOpCode::MulFixed,
+                LpsOpCode::Push(5.0.to_fixed()),
+                LpsOpCode::AddFixed,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(17.5)
+            .run()?;
+
+        ExprTest::new("remap(x, 2.0, 2.0, 7.0, 9.0)")
+            .with_optimization(OptimizeOptions::all())
+            .expect_opcodes(vec![LpsOpCode::Push(7.0.to_fixed()), LpsOpCode::Return])
+            .run()?;
+
+        ExprTest::new("remap(0.5, 0.0, 2.0, 0.0, 1.0)")
+            .with_optimization(OptimizeOptions::all())
+            .expect_opcodes(vec![LpsOpCode::Push(0.25.to_fixed()), LpsOpCode::Return])
+            .run()
+    }
+
+    #[test]
+    fn test_quantize_rounds_to_levels() -> Result<(), String> {
+        // Pixel (0, 0) has the smallest dither offset, 1/32 of a step
+        ExprTest::new("quantize(x, 3.0)")
+            .with_x(0.7)
+            .expect_opcodes(vec![
+                LpsOpCode::Load(LoadSource::XNorm),
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::QuantizeFixed,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(0.5)
+            .run()
+    }
+
+    #[test]
+    fn test_quantize_averages_to_input() {
+        use crate::fixed::Fixed;
+        use crate::vm::{execute_program_lps, CoordConfig};
+
+        let (width, height) = (8, 8);
+        for value in [0.1, 0.3, 0.5, 0.62, 0.9] {
+            let program = crate::compile_expr(&alloc::format!("quantize({}, 4.0)", value)).unwrap();
+            let mut output = vec![Fixed::ZERO; width * height];
+            execute_program_lps(
+                &program,
+                &mut output,
+                width,
+                height,
+                Fixed::ZERO,
+                &CoordConfig::default(),
+            );
+
+            let levels = [0.0, 1.0 / 3.0, 2.0 / 3.0, 1.0];
+            for out in &output {
+                assert!(
+                    levels.iter().any(|l| (out.to_f32() - l).abs() < 0.001),
+                    "{} is not one of the 4 levels",
+                    out.to_f32()
+                );
+            }
+            let mean = output.iter().map(|v| v.to_f32()).sum::<f32>() / output.len() as f32;
+            assert!(
+                (mean - value).abs() < 0.02,
+                "quantize({}) averages to {}",
+                value,
+                mean
+            );
+        }
+    }
+
+    #[test]
+    fn test_cast_rejects_same_type() {
+        assert!(crate::compile_expr("int(5)").is_err());
+        assert!(crate::compile_expr("float(2.5)").is_err());
+        assert!(crate::compile_expr("int(vec2(1.0))").is_err());
+        assert!(crate::compile_expr("float(1, 2)").is_err());
+    }
 }
@@ -4,6 +4,7 @@ extern crate alloc;
 use crate::compiler::ast::{Expr, ExprKind};
 use crate::compiler::codegen::CodeGenerator;
 use crate::shared::Type;
+use crate::vm::opcodes::load::LoadSource;
 use crate::vm::opcodes::LpsOpCode;
 
 impl<'a> CodeGenerator<'a> {
@@ -19,27 +20,61 @@ impl<'a> CodeGenerator<'a> {
             return;
         }
 
+        // Then a host native, called by its registration index
+        if let Some(index) = self.natives.iter().position(|native| native.name == name) {
+            for arg in args {
+                self.gen_expr(arg);
+            }
+            self.code.push(LpsOpCode::CallNative(index as u32));
+            return;
+        }
+
         // Special case: perlin3(vec3) or perlin3(vec3, octaves)
         // Octaves is embedded in opcode, not pushed to stack
         if name == "perlin3" {
             // First arg is vec3, generate code to push its 3 components
             self.gen_expr(&args[0]);
 
-            // Extract octaves from 2nd arg or use default
-            let octaves = if args.len() >= 2 {
-                match &args[1].kind {
-                    ExprKind::Number(n) => *n as u8,
-                    ExprKind::IntNumber(n) => *n as u8,
-                    _ => 3,
-                }
-            } else {
-                3
+            // The type checker folds octaves to an in-range int literal
+            let octaves = match args.get(1).map(|arg| &arg.kind) {
+                Some(ExprKind::IntNumber(n)) => *n as u8,
+                _ => 3,
             };
 
             self.code.push(LpsOpCode::Perlin3(octaves));
             return;
         }
 
+        // Special case: mixTex(texA, texB, uv, t)
+        // The slots are embedded in the opcode, not pushed to stack
+        if name == "mixTex" {
+            // The type checker folds both slots to in-range int literals
+            let slot = |arg: &Expr| match arg.kind {
+                ExprKind::IntNumber(n) => n as u32,
+                _ => 0,
+            };
+            self.gen_expr(&args[2]);
+            self.gen_expr(&args[3]);
+            if args[3].ty == Some(Type::Int32) {
+                self.code.push(LpsOpCode::Int32ToFixed);
+            }
+            self.code
+                .push(LpsOpCode::TextureMixRGBA(slot(&args[0]), slot(&args[1])));
+            return;
+        }
+
+        // remap works on floats, so convert int arguments as they're pushed
+        if name == "remap" {
+            for arg in args {
+                self.gen_expr(arg);
+                if arg.ty == Some(Type::Int32) {
+                    self.code.push(LpsOpCode::Int32ToFixed);
+                }
+            }
+            self.code.push(LpsOpCode::RemapFixed);
+            return;
+        }
+
         // For all other functions, generate code for all arguments first
         for arg in args {
             self.gen_expr(arg);
@@ -49,23 +84,88 @@ impl<'a> CodeGenerator<'a> {
         self.gen_builtin_function(name, args);
     }
 
+    /// Emit `scalar`, or the matching `[vec2, vec3, vec4]` opcode for a vector
+    fn gen_per_component(&mut self, arg_ty: Option<&Type>, scalar: LpsOpCode, vec: [LpsOpCode; 3]) {
+        let [vec2, vec3, vec4] = vec;
+        self.code.push(match arg_ty {
+            Some(Type::Vec2) => vec2,
+            Some(Type::Vec3) => vec3,
+            Some(Type::Vec4) => vec4,
+            _ => scalar,
+        });
+    }
+
     fn gen_builtin_function(&mut self, name: &str, args: &[Expr]) {
+        let first_arg_ty = args.first().and_then(|arg| arg.ty.as_ref());
         match name {
             "sin" => self.code.push(LpsOpCode::SinFixed),
             "cos" => self.code.push(LpsOpCode::CosFixed),
-            "frac" | "fract" => self.code.push(LpsOpCode::FractFixed),
+            "frac" | "fract" => self.gen_per_component(
+                first_arg_ty,
+                LpsOpCode::FractFixed,
+                [
+                    LpsOpCode::FractVec2,
+                    LpsOpCode::FractVec3,
+                    LpsOpCode::FractVec4,
+                ],
+            ),
+
+            // Casts
+            "int" => self.code.push(LpsOpCode::FixedToInt32),
+            // A bool is already 0.0 or 1.0
+            "float" if first_arg_ty == Some(&Type::Bool) => {}
+            "float" => self.code.push(LpsOpCode::Int32ToFixed),
 
             // Math functions - use explicit opcodes
             "min" => self.code.push(LpsOpCode::MinFixed),
             "max" => self.code.push(LpsOpCode::MaxFixed),
-            "abs" => self.code.push(LpsOpCode::AbsFixed),
-            "floor" => self.code.push(LpsOpCode::FloorFixed),
-            "ceil" => self.code.push(LpsOpCode::CeilFixed),
-            "sqrt" => self.code.push(LpsOpCode::SqrtFixed),
+            "abs" => self.gen_per_component(
+                first_arg_ty,
+                LpsOpCode::AbsFixed,
+                [LpsOpCode::AbsVec2, LpsOpCode::AbsVec3, LpsOpCode::AbsVec4],
+            ),
+            "floor" => self.gen_per_component(
+                first_arg_ty,
+                LpsOpCode::FloorFixed,
+                [
+                    LpsOpCode::FloorVec2,
+                    LpsOpCode::FloorVec3,
+                    LpsOpCode::FloorVec4,
+                ],
+            ),
+            "ceil" => self.gen_per_component(
+                first_arg_ty,
+                LpsOpCode::CeilFixed,
+                [
+                    LpsOpCode::CeilVec2,
+                    LpsOpCode::CeilVec3,
+                    LpsOpCode::CeilVec4,
+                ],
+            ),
+            "sqrt" => self.gen_per_component(
+                first_arg_ty,
+                LpsOpCode::SqrtFixed,
+                [
+                    LpsOpCode::SqrtVec2,
+                    LpsOpCode::SqrtVec3,
+                    LpsOpCode::SqrtVec4,
+                ],
+            ),
             "tan" => self.code.push(LpsOpCode::TanFixed),
             "pow" => self.code.push(LpsOpCode::PowFixed),
-            "sign" => self.code.push(LpsOpCode::SignFixed),
+            "sign" => self.gen_per_component(
+                first_arg_ty,
+                LpsOpCode::SignFixed,
+                [
+                    LpsOpCode::SignVec2,
+                    LpsOpCode::SignVec3,
+                    LpsOpCode::SignVec4,
+                ],
+            ),
             "mod" => self.code.push(LpsOpCode::ModFixed),
+            "wrapTime" => self.code.push(LpsOpCode::WrapFixed),
+            "phase" => self.code.push(LpsOpCode::PhaseTime),
+            "quantize" => self.code.push(LpsOpCode::QuantizeFixed),
             "atan" => {
                 if args.len() == 2 {
                     self.code.push(LpsOpCode::Atan2Fixed);
@@ -82,80 +182,166 @@ impl<'a> CodeGenerator<'a> {
             "smoothstep" => self.code.push(LpsOpCode::SmoothstepFixed),
 
             // Vector functions - use typed opcodes based on argument type
-            "length" => {
-                if !args.is_empty() {
-                    let arg_ty = args[0].ty.as_ref().unwrap();
-                    match arg_ty {
-                        Type::Vec2 => self.code.push(LpsOpCode::Length2),
-                        Type::Vec3 => self.code.push(LpsOpCode::Length3),
-                        Type::Vec4 => self.code.push(LpsOpCode::Length4),
-                        _ => {}
-                    }
+            "length" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::Length2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::Length3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::Length4),
+                _ => {}
+            },
+            "normalize" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::Normalize2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::Normalize3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::Normalize4),
+                _ => {}
+            },
+            "dot" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::Dot2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::Dot3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::Dot4),
+                _ => {}
+            },
+            "distance" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::Distance2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::Distance3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::Distance4),
+                _ => {}
+            },
+            "lengthSq" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::LengthSq2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::LengthSq3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::LengthSq4),
+                _ => {}
+            },
+            "distanceSq" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::DistanceSq2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::DistanceSq3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::DistanceSq4),
+                _ => {}
+            },
+            "reflect" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::Reflect2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::Reflect3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::Reflect4),
+                _ => {}
+            },
+            "faceforward" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::FaceForward2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::FaceForward3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::FaceForward4),
+                _ => {}
+            },
+            "maxComp" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::MaxCompVec2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::MaxCompVec3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::MaxCompVec4),
+                _ => {}
+            },
+            "minComp" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::MinCompVec2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::MinCompVec3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::MinCompVec4),
+                _ => {}
+            },
+            "sum" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::SumVec2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::SumVec3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::SumVec4),
+                _ => {}
+            },
+            "avg" => match first_arg_ty {
+                Some(Type::Vec2) => self.code.push(LpsOpCode::AvgVec2),
+                Some(Type::Vec3) => self.code.push(LpsOpCode::AvgVec3),
+                Some(Type::Vec4) => self.code.push(LpsOpCode::AvgVec4),
+                _ => {}
+            },
+            "lessThan" | "lessThanEqual" | "greaterThan" | "greaterThanEqual" | "equal"
+            | "notEqual" => {
+                if let Some(ty) = first_arg_ty {
+                    self.push_vec_comparison(name, ty);
                 }
             }
-            "normalize" => {
-                if !args.is_empty() {
-                    let arg_ty = args[0].ty.as_ref().unwrap();
-                    match arg_ty {
-                        Type::Vec2 => self.code.push(LpsOpCode::Normalize2),
-                        Type::Vec3 => self.code.push(LpsOpCode::Normalize3),
-                        Type::Vec4 => self.code.push(LpsOpCode::Normalize4),
-                        _ => {}
-                    }
-                }
+            // bvec components are 1.0 or 0.0, so any/all are the max/min
+            "any" => match first_arg_ty {
+                Some(Type::BVec2) => self.code.push(LpsOpCode::MaxCompVec2),
+                Some(Type::BVec3) => self.code.push(LpsOpCode::MaxCompVec3),
+                Some(Type::BVec4) => self.code.push(LpsOpCode::MaxCompVec4),
+                _ => {}
+            },
+            "all" => match first_arg_ty {
+                Some(Type::BVec2) => self.code.push(LpsOpCode::MinCompVec2),
+                Some(Type::BVec3) => self.code.push(LpsOpCode::MinCompVec3),
+                Some(Type::BVec4) => self.code.push(LpsOpCode::MinCompVec4),
+                _ => {}
+            },
+            "not" => match first_arg_ty {
+                Some(Type::BVec2) => self.code.push(LpsOpCode::NotBVec2),
+                Some(Type::BVec3) => self.code.push(LpsOpCode::NotBVec3),
+                Some(Type::BVec4) => self.code.push(LpsOpCode::NotBVec4),
+                _ => {}
+            },
+            "fromPolar" => {
+                // Inverse of the `polar` built-in: back to uv around the center
+                self.code.push(LpsOpCode::FromPolarVec2);
+                self.code.push(LpsOpCode::Load(LoadSource::CenterX));
+                self.code.push(LpsOpCode::Load(LoadSource::CenterY));
+                self.code.push(LpsOpCode::AddVec2);
             }
-            "dot" => {
-                if !args.is_empty() {
-                    let arg_ty = args[0].ty.as_ref().unwrap();
-                    match arg_ty {
-                        Type::Vec2 => self.code.push(LpsOpCode::Dot2),
-                        Type::Vec3 => self.code.push(LpsOpCode::Dot3),
-                        Type::Vec4 => self.code.push(LpsOpCode::Dot4),
-                        _ => {}
-                    }
+            "tile" | "mirrorTile" => {
+                if args.get(1).and_then(|arg| arg.ty.as_ref()) == Some(&Type::Int32) {
+                    self.code.push(LpsOpCode::Int32ToFixed);
                 }
+                self.code.push(if name == "tile" {
+                    LpsOpCode::TileVec2
+                } else {
+                    LpsOpCode::MirrorTileVec2
+                });
+            }
+            "toPixel" | "toUv" => {
+                // Scale by the output resolution; constant when
+                // `OptimizeOptions::resolution` is set
+                self.code.push(LpsOpCode::Load(LoadSource::Width));
+                self.code.push(LpsOpCode::Load(LoadSource::Height));
+                self.code.push(if name == "toPixel" {
+                    LpsOpCode::MulVec2
+                } else {
+                    LpsOpCode::DivVec2
+                });
             }
-            "distance" => {
-                if !args.is_empty() {
-                    let arg_ty = args[0].ty.as_ref().unwrap();
-                    match arg_ty {
-                        Type::Vec2 => self.code.push(LpsOpCode::Distance2),
-                        Type::Vec3 => self.code.push(LpsOpCode::Distance3),
-                        Type::Vec4 => self.code.push(LpsOpCode::Distance4),
-                        _ => {}
-                    }
+            "palette" => {
+                if first_arg_ty == Some(&Type::Int32) {
+                    self.code.push(LpsOpCode::Int32ToFixed);
                 }
+                self.code.push(LpsOpCode::PaletteSample);
             }
             "cross" => {
                 // Always vec3
                 self.code.push(LpsOpCode::Cross3);
             }
+            "hsv2rgb" => self.code.push(LpsOpCode::HsvToRgb),
+            "rgb2hsv" => self.code.push(LpsOpCode::RgbToHsv),
 
             // Matrix functions
-            "transpose" => {
-                if !args.is_empty() {
-                    let arg_ty = args[0].ty.as_ref().unwrap();
-                    if arg_ty == &Type::Mat3 {
-                        self.code.push(LpsOpCode::TransposeMat3);
-                    }
-                }
-            }
-            "determinant" => {
-                if !args.is_empty() {
-                    let arg_ty = args[0].ty.as_ref().unwrap();
-                    if arg_ty == &Type::Mat3 {
-                        self.code.push(LpsOpCode::DeterminantMat3);
-                    }
-                }
-            }
-            "inverse" => {
-                if !args.is_empty() {
-                    let arg_ty = args[0].ty.as_ref().unwrap();
-                    if arg_ty == &Type::Mat3 {
-                        self.code.push(LpsOpCode::InverseMat3);
-                    }
+            "rotate2x2" => {
+                if first_arg_ty == Some(&Type::Int32) {
+                    self.code.push(LpsOpCode::Int32ToFixed);
                 }
+                self.code.push(LpsOpCode::RotateMat2);
             }
+            "transpose" => self.code.push(match first_arg_ty {
+                Some(Type::Mat2) => LpsOpCode::TransposeMat2,
+                Some(Type::Mat4) => LpsOpCode::TransposeMat4,
+                _ => LpsOpCode::TransposeMat3,
+            }),
+            "determinant" => self.code.push(match first_arg_ty {
+                Some(Type::Mat2) => LpsOpCode::DeterminantMat2,
+                Some(Type::Mat4) => LpsOpCode::DeterminantMat4,
+                _ => LpsOpCode::DeterminantMat3,
+            }),
+            "inverse" => self.code.push(match first_arg_ty {
+                Some(Type::Mat2) => LpsOpCode::InverseMat2,
+                Some(Type::Mat4) => LpsOpCode::InverseMat4,
+                _ => LpsOpCode::InverseMat3,
+            }),
 
             _ => {} // Unknown function - ignore
         }
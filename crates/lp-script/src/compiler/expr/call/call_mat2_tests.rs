@@ -0,0 +1,102 @@
+/// Function call tests for Mat2 type
+#[cfg(test)]
+mod tests {
+    use crate::compiler::expr::expr_test_util::ExprTest;
+    use crate::fixed::{cos, sin, Fixed, ToFixed, Vec2};
+    use crate::vm::opcodes::LpsOpCode;
+
+    #[test]
+    fn test_rotate2x2_unit_x() -> Result<(), String> {
+        // Rotating +x by `a` lands on (cos a, sin a)
+        for angle in [0.0, 0.5, 1.0, 2.5, -1.2] {
+            let a = angle.to_fixed();
+            ExprTest::new(&alloc::format!("rotate2x2({:?}) * vec2(1.0, 0.0)", angle))
+                .expect_result_vec2(Vec2::new(cos(a), sin(a)))
+                .run()?;
+        }
+
+        ExprTest::new("rotate2x2(time) * vec2(1.0, 0.0)")
+            .with_time(0.75)
+            .expect_opcodes(vec![
+                LpsOpCode::Load(crate::vm::opcodes::LoadSource::Time),
+                LpsOpCode::RotateMat2,
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::MulMat2Vec2,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec2(Vec2::new(cos(0.75.to_fixed()), sin(0.75.to_fixed())))
+            .run()
+    }
+
+    #[test]
+    fn test_rotate2x2_matches_cos_sin() -> Result<(), String> {
+        ExprTest::new("rotate2x2(1.3) * vec2(1.0, 0.0) - vec2(cos(1.3), sin(1.3))")
+            .expect_result_vec2(Vec2::new(Fixed::ZERO, Fixed::ZERO))
+            .run()
+    }
+
+    #[test]
+    fn test_mat2_constructor_and_mul() -> Result<(), String> {
+        // Column-major: columns (1, 2) and (3, 4)
+        ExprTest::new("mat2(1.0, 2.0, 3.0, 4.0) * vec2(1.0, 1.0)")
+            .expect_result_vec2(Vec2::from_f32(4.0, 6.0))
+            .run()?;
+
+        ExprTest::new(
+            "mat2(vec2(2.0, 0.0), vec2(0.0, 2.0)) * mat2(1.0, 2.0, 3.0, 4.0) * vec2(1.0, 0.0)",
+        )
+        .expect_result_vec2(Vec2::from_f32(2.0, 4.0))
+        .run()
+    }
+
+    #[test]
+    fn test_mat2_scalar_ops() -> Result<(), String> {
+        ExprTest::new(
+            "(2.0 * mat2(1.0, 2.0, 3.0, 4.0) - mat2(1.0, 1.0, 1.0, 1.0)) * vec2(1.0, 0.0)",
+        )
+        .expect_result_vec2(Vec2::from_f32(1.0, 3.0))
+        .run()?;
+
+        ExprTest::new("(-mat2(1.0, 2.0, 3.0, 4.0) / 2.0) * vec2(0.0, 1.0)")
+            .expect_result_vec2(Vec2::from_f32(-1.5, -2.0))
+            .run()
+    }
+
+    #[test]
+    fn test_mat2_transpose() -> Result<(), String> {
+        ExprTest::new("transpose(mat2(1.0, 2.0, 3.0, 4.0)) * vec2(1.0, 0.0)")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::Push(4.0.to_fixed()),
+                LpsOpCode::TransposeMat2,
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::MulMat2Vec2,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec2(Vec2::from_f32(1.0, 3.0))
+            .run()
+    }
+
+    #[test]
+    fn test_mat2_determinant_and_inverse() -> Result<(), String> {
+        ExprTest::new("determinant(mat2(1.0, 2.0, 3.0, 4.0))")
+            .expect_result_fixed(-2.0)
+            .run()?;
+
+        // inverse(m) * (m * v) == v
+        ExprTest::new(
+            "inverse(mat2(2.0, 0.0, 1.0, 1.0)) * (mat2(2.0, 0.0, 1.0, 1.0) * vec2(3.0, -1.0))",
+        )
+        .expect_result_vec2(Vec2::from_f32(3.0, -1.0))
+        .run()?;
+
+        // A singular matrix has no inverse; the identity comes back instead
+        ExprTest::new("inverse(mat2(1.0, 2.0, 2.0, 4.0)) * vec2(5.0, 6.0)")
+            .expect_result_vec2(Vec2::from_f32(5.0, 6.0))
+            .run()
+    }
+}
@@ -2,7 +2,7 @@
 #[cfg(test)]
 mod tests {
     use crate::compiler::expr::expr_test_util::ExprTest;
-    use crate::fixed::{Mat3, ToFixed};
+    use crate::fixed::{Mat3, ToFixed, Vec3};
     use crate::vm::opcodes::LpsOpCode;
 
     #[test]
@@ -121,4 +121,11 @@ mod tests {
             .expect_result_fixed(8.0)
             .run()
     }
+
+    #[test]
+    fn test_mat3_mul_vec3() -> Result<(), String> {
+        ExprTest::new("mat3(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0) * vec3(0.0, 1.0, 1.0)")
+            .expect_result_vec3(Vec3::from_f32(11.0, 13.0, 15.0))
+            .run()
+    }
 }
@@ -0,0 +1,100 @@
+/// Function call tests for Mat4 type
+#[cfg(test)]
+mod tests {
+    use crate::compiler::expr::expr_test_util::ExprTest;
+    use crate::fixed::{ToFixed, Vec4};
+    use crate::vm::opcodes::LpsOpCode;
+
+    // Columns (1, 2, 3, 4), (5, 6, 7, 8), (9, 10, 11, 12), (13, 14, 15, 16)
+    const M: &str = "mat4(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, \
+                     9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0)";
+
+    #[test]
+    fn test_mat4_constructor_and_mul_vec4() -> Result<(), String> {
+        ExprTest::new(&alloc::format!("{} * vec4(1.0, 0.0, 0.0, 0.0)", M))
+            .expect_result_vec4(Vec4::from_f32(1.0, 2.0, 3.0, 4.0))
+            .run()?;
+
+        ExprTest::new(&alloc::format!("{} * vec4(1.0)", M))
+            .expect_result_vec4(Vec4::from_f32(28.0, 32.0, 36.0, 40.0))
+            .run()?;
+
+        // From four column vectors
+        ExprTest::new("mat4(vec4(1.0), vec4(2.0), vec4(3.0), vec4(4.0)) * vec4(0.0, 0.0, 0.0, 1.0)")
+            .expect_result_vec4(Vec4::from_f32(4.0, 4.0, 4.0, 4.0))
+            .run()
+    }
+
+    #[test]
+    fn test_mat4_mul_mat4() -> Result<(), String> {
+        ExprTest::new(&alloc::format!(
+            "mat4(2.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 2.0) \
+             * {} * vec4(0.0, 1.0, 0.0, 0.0)",
+            M
+        ))
+        .expect_result_vec4(Vec4::from_f32(10.0, 12.0, 14.0, 16.0))
+        .run()
+    }
+
+    #[test]
+    fn test_mat4_scalar_ops() -> Result<(), String> {
+        ExprTest::new(&alloc::format!(
+            "(2.0 * {m} - {m} + -{m} / 2.0) * vec4(1.0, 0.0, 0.0, 0.0)",
+            m = M
+        ))
+        .expect_result_vec4(Vec4::from_f32(0.5, 1.0, 1.5, 2.0))
+        .run()
+    }
+
+    #[test]
+    fn test_mat4_transpose() -> Result<(), String> {
+        // The first column of the transpose is the first row
+        ExprTest::new(&alloc::format!(
+            "transpose({}) * vec4(1.0, 0.0, 0.0, 0.0)",
+            M
+        ))
+        .expect_result_vec4(Vec4::from_f32(1.0, 5.0, 9.0, 13.0))
+        .run()
+    }
+
+    #[test]
+    fn test_mat4_determinant() -> Result<(), String> {
+        ExprTest::new(
+            "determinant(mat4(2.0, 0.0, 0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0, 0.0, 5.0))",
+        )
+        .expect_result_fixed(120.0)
+        .run()?;
+
+        // Columns are linearly dependent
+        ExprTest::new(&alloc::format!("determinant({})", M))
+            .expect_result_fixed(0.0)
+            .run()
+    }
+
+    #[test]
+    fn test_mat4_inverse() -> Result<(), String> {
+        let m =
+            "mat4(1.0, 0.0, 0.0, 0.0, 2.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 3.0, 1.0)";
+
+        // inverse(m) * (m * v) == v
+        ExprTest::new(&alloc::format!(
+            "inverse({m}) * ({m} * vec4(1.0, -2.0, 0.5, 4.0))",
+            m = m
+        ))
+        .expect_result_vec4(Vec4::from_f32(1.0, -2.0, 0.5, 4.0))
+        .run()?;
+
+        // A singular matrix has no inverse; the identity comes back instead
+        ExprTest::new(&alloc::format!("inverse({}) * vec4(1.0, 2.0, 3.0, 4.0)", M))
+            .expect_opcodes(
+                (1..=16)
+                    .map(|i| LpsOpCode::Push((i as f32).to_fixed()))
+                    .chain([LpsOpCode::InverseMat4])
+                    .chain((1..=4).map(|i| LpsOpCode::Push((i as f32).to_fixed())))
+                    .chain([LpsOpCode::MulMat4Vec4, LpsOpCode::Return])
+                    .collect(),
+            )
+            .expect_result_vec4(Vec4::from_f32(1.0, 2.0, 3.0, 4.0))
+            .run()
+    }
+}
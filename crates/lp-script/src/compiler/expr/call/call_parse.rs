@@ -41,6 +41,32 @@ impl Parser {
         }
     }
 
+    // Parse `int(x)` / `float(x)`, a call to the builtin of the same name
+    pub(crate) fn parse_cast(&mut self) -> Result<Expr, ParseError> {
+        let token = self.current().clone();
+        let name = match token.kind {
+            TokenKind::Int => "int",
+            _ => "float",
+        };
+        self.advance();
+
+        self.expect(TokenKind::LParen);
+        let args = self.parse_args()?;
+        let end = if matches!(self.current().kind, TokenKind::RParen) {
+            let span = self.current().span;
+            self.advance(); // consume ')'
+            span.end
+        } else {
+            self.current().span.end
+        };
+
+        let kind = ExprKind::Call {
+            name: name.into(),
+            args,
+        };
+        Ok(Expr::new(kind, Span::new(token.span.start, end)))
+    }
+
     pub(crate) fn parse_args(&mut self) -> Result<Vec<Expr>, ParseError> {
         let mut args = Vec::new();
 
@@ -1,18 +1,21 @@
 /// Function call type checking
 extern crate alloc;
 
+use alloc::format;
 use alloc::string::ToString;
 
 use super::expand_componentwise;
-use crate::compiler::ast::Expr;
+use crate::compiler::ast::{Expr, ExprKind};
+use crate::compiler::builtins::{builtin_function, BuiltinType};
 use crate::compiler::error::{TypeError, TypeErrorKind};
 use crate::compiler::typechecker::{FunctionTable, SymbolTable, TypeChecker};
 use crate::shared::Type;
+use crate::vm::MAX_TEXTURES;
 
 /// Type check function call
 ///
 /// Infers the return type based on the function signature.
-/// Handles both user-defined and built-in functions.
+/// Handles user-defined, host native and built-in functions.
 /// May transform the expression via component-wise expansion.
 pub(in crate::compiler) fn check_call(
     name: &str,
@@ -26,6 +29,15 @@ pub(in crate::compiler) fn check_call(
         TypeChecker::infer_type(arg, symbols, func_table)?;
     }
 
+    // Unary math with vector opcodes keeps the vector whole
+    if let [arg] = &*args {
+        if expand_componentwise::has_vector_opcode(name) && func_table.lookup(name).is_none() {
+            if let Some(ty @ (Type::Vec2 | Type::Vec3 | Type::Vec4)) = &arg.ty {
+                return Ok((ty.clone(), None));
+            }
+        }
+    }
+
     // Try component-wise expansion for built-in functions with vector args
     if expand_componentwise::is_componentwise_function(name) {
         if let Some(mut expanded_expr) =
@@ -49,40 +61,13 @@ pub(in crate::compiler) fn check_call(
         }
     }
 
-    // Check if it's a user-defined function first
+    // Check if it's a user-defined function first, then a host native
     if let Some(sig) = func_table.lookup(name) {
-        // Validate argument count
-        if args.len() != sig.params.len() {
-            return Err(TypeError {
-                kind: TypeErrorKind::InvalidArgumentCount {
-                    expected: sig.params.len(),
-                    found: args.len(),
-                },
-                span,
-            });
-        }
-
-        // Validate argument types
-        for (arg, expected_ty) in args.iter().zip(sig.params.iter()) {
-            let arg_ty = arg.ty.as_ref().ok_or_else(|| TypeError {
-                kind: TypeErrorKind::InvalidOperation {
-                    op: "call".into(),
-                    types: alloc::vec![],
-                },
-                span: arg.span,
-            })?;
-            if arg_ty != expected_ty {
-                return Err(TypeError {
-                    kind: TypeErrorKind::Mismatch {
-                        expected: expected_ty.clone(),
-                        found: arg_ty.clone(),
-                    },
-                    span: arg.span,
-                });
-            }
-        }
-
+        check_args(args, &sig.params, span)?;
         Ok((sig.return_type.clone(), None))
+    } else if let Some(native) = func_table.lookup_native(name) {
+        check_args(args, &native.sig.params, span)?;
+        Ok((native.sig.return_type.clone(), None))
     } else {
         // Built-in function - determine return type
         let ty = builtin_function_return_type(name, args, span)?;
@@ -90,313 +75,269 @@ pub(in crate::compiler) fn check_call(
     }
 }
 
+/// Check a call's arguments against a fixed list of parameter types
+fn check_args(args: &[Expr], params: &[Type], span: crate::shared::Span) -> Result<(), TypeError> {
+    if args.len() != params.len() {
+        return Err(TypeError {
+            kind: TypeErrorKind::InvalidArgumentCount {
+                expected: params.len(),
+                found: args.len(),
+            },
+            span,
+        });
+    }
+
+    for (arg, expected_ty) in args.iter().zip(params) {
+        let arg_ty = arg.ty.as_ref().ok_or_else(|| TypeError {
+            kind: TypeErrorKind::InvalidOperation {
+                op: "call".into(),
+                types: alloc::vec![],
+            },
+            span: arg.span,
+        })?;
+        if arg_ty != expected_ty {
+            return Err(TypeError {
+                kind: TypeErrorKind::Mismatch {
+                    expected: expected_ty.clone(),
+                    found: arg_ty.clone(),
+                },
+                span: arg.span,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Check a built-in call against its `builtins()` signature
+///
+/// A function whose parameters all share one type (`cross`, `transpose`,
+/// casts) is an operation on that type, so other arguments are reported as
+/// an invalid operation; otherwise a wrongly typed argument is a mismatch.
 fn builtin_function_return_type(
     name: &str,
     args: &mut [Expr],
     span: crate::shared::Span,
 ) -> Result<Type, TypeError> {
-    match name {
-        // Math functions: Fixed -> Fixed
-        "sin" | "cos" | "tan" | "abs" | "floor" | "ceil" | "sqrt" | "sign" | "frac" | "fract"
-        | "saturate" => {
-            if args.len() != 1 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 1,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            Ok(Type::Fixed)
-        }
-
-        // atan: can take 1 or 2 args
-        "atan" => {
-            if args.is_empty() || args.len() > 2 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 1,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            Ok(Type::Fixed)
-        }
-
-        // Vector length: vec -> float
-        "length" => {
-            if args.len() != 1 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 1,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            let arg_ty = args[0].ty.as_ref().unwrap();
-            match arg_ty {
-                Type::Vec2 | Type::Vec3 | Type::Vec4 => Ok(Type::Fixed),
-                _ => Err(TypeError {
-                    kind: TypeErrorKind::InvalidOperation {
-                        op: "length".to_string(),
-                        types: alloc::vec![arg_ty.clone()],
-                    },
-                    span: args[0].span,
-                }),
-            }
-        }
+    let sig = builtin_function(name).ok_or_else(|| TypeError {
+        kind: TypeErrorKind::UndefinedVariable(alloc::format!("Unknown function '{}'", name)),
+        span,
+    })?;
+    if !sig.accepts_arg_count(args.len()) {
+        return Err(TypeError {
+            kind: TypeErrorKind::InvalidArgumentCount {
+                expected: if args.len() < sig.required {
+                    sig.required
+                } else {
+                    sig.params.len()
+                },
+                found: args.len(),
+            },
+            span,
+        });
+    }
 
-        // Normalize: vec -> vec (same type)
-        "normalize" => {
-            if args.len() != 1 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 1,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            let arg_ty = args[0].ty.as_ref().unwrap();
-            match arg_ty {
-                Type::Vec2 | Type::Vec3 | Type::Vec4 => Ok(arg_ty.clone()),
-                _ => Err(TypeError {
-                    kind: TypeErrorKind::InvalidOperation {
-                        op: "normalize".to_string(),
-                        types: alloc::vec![arg_ty.clone()],
-                    },
-                    span: args[0].span,
-                }),
-            }
-        }
+    // `int(b)` and `float(b)` are the explicit bool conversions
+    if matches!(name, "int" | "float") && args[0].ty == Some(Type::Bool) {
+        return Ok(if name == "int" {
+            Type::Int32
+        } else {
+            Type::Fixed
+        });
+    }
 
-        // Dot product: vec x vec -> float
-        "dot" => {
-            if args.len() != 2 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 2,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            let left_ty = args[0].ty.as_ref().unwrap();
-            let right_ty = args[1].ty.as_ref().unwrap();
-            if left_ty != right_ty {
+    let single_type = sig.params.windows(2).all(|pair| pair[0] == pair[1]);
+    let mut vec_ty: Option<&Type> = None;
+    let mut mat_ty: Option<&Type> = None;
+    let mut bvec_ty: Option<&Type> = None;
+    for (arg, param) in args.iter().zip(sig.params) {
+        let arg_ty = arg.ty.as_ref().unwrap();
+        match param {
+            BuiltinType::Exact(ty) if arg_ty == ty => {}
+            BuiltinType::Exact(ty) if !single_type => {
                 return Err(TypeError {
                     kind: TypeErrorKind::Mismatch {
-                        expected: left_ty.clone(),
-                        found: right_ty.clone(),
+                        expected: ty.clone(),
+                        found: arg_ty.clone(),
                     },
-                    span: args[1].span,
+                    span: arg.span,
                 });
             }
-            match left_ty {
-                Type::Vec2 | Type::Vec3 | Type::Vec4 => Ok(Type::Fixed),
-                _ => Err(TypeError {
-                    kind: TypeErrorKind::InvalidOperation {
-                        op: "dot".to_string(),
-                        types: alloc::vec![left_ty.clone()],
-                    },
-                    span: args[0].span,
-                }),
-            }
+            BuiltinType::Scalar if matches!(arg_ty, Type::Fixed | Type::Int32) => {}
+            BuiltinType::AnyVec => match vec_ty {
+                Some(first) if arg_ty != first => {
+                    return Err(TypeError {
+                        kind: TypeErrorKind::Mismatch {
+                            expected: first.clone(),
+                            found: arg_ty.clone(),
+                        },
+                        span: arg.span,
+                    });
+                }
+                Some(_) => {}
+                None if matches!(arg_ty, Type::Vec2 | Type::Vec3 | Type::Vec4) => {
+                    vec_ty = Some(arg_ty);
+                }
+                None => return Err(invalid_operation(name, args, arg.span)),
+            },
+            BuiltinType::AnyMat => match mat_ty {
+                Some(first) if arg_ty != first => {
+                    return Err(TypeError {
+                        kind: TypeErrorKind::Mismatch {
+                            expected: first.clone(),
+                            found: arg_ty.clone(),
+                        },
+                        span: arg.span,
+                    });
+                }
+                Some(_) => {}
+                None if matches!(arg_ty, Type::Mat2 | Type::Mat3 | Type::Mat4) => {
+                    mat_ty = Some(arg_ty);
+                }
+                None => return Err(invalid_operation(name, args, arg.span)),
+            },
+            BuiltinType::AnyBVec => match bvec_ty {
+                Some(first) if arg_ty != first => {
+                    return Err(TypeError {
+                        kind: TypeErrorKind::Mismatch {
+                            expected: first.clone(),
+                            found: arg_ty.clone(),
+                        },
+                        span: arg.span,
+                    });
+                }
+                Some(_) => {}
+                None if matches!(arg_ty, Type::BVec2 | Type::BVec3 | Type::BVec4) => {
+                    bvec_ty = Some(arg_ty);
+                }
+                None => return Err(invalid_operation(name, args, arg.span)),
+            },
+            _ => return Err(invalid_operation(name, args, arg.span)),
         }
+    }
 
-        // Distance: vec x vec -> float
-        "distance" => {
-            if args.len() != 2 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 2,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            let left_ty = args[0].ty.as_ref().unwrap();
-            let right_ty = args[1].ty.as_ref().unwrap();
-            if left_ty != right_ty {
-                return Err(TypeError {
-                    kind: TypeErrorKind::Mismatch {
-                        expected: left_ty.clone(),
-                        found: right_ty.clone(),
-                    },
-                    span: args[1].span,
-                });
-            }
-            match left_ty {
-                Type::Vec2 | Type::Vec3 | Type::Vec4 => Ok(Type::Fixed),
-                _ => Err(TypeError {
-                    kind: TypeErrorKind::InvalidOperation {
-                        op: "distance".to_string(),
-                        types: alloc::vec![left_ty.clone()],
-                    },
-                    span: args[0].span,
-                }),
-            }
-        }
+    let return_ty = match &sig.returns {
+        BuiltinType::Exact(ty) => ty.clone(),
+        BuiltinType::Scalar => Type::Fixed,
+        BuiltinType::AnyVec => vec_ty.cloned().unwrap_or(Type::Fixed),
+        BuiltinType::AnyMat => mat_ty.cloned().unwrap_or(Type::Fixed),
+        BuiltinType::AnyBVec => bvec_ty
+            .cloned()
+            .or_else(|| vec_ty.and_then(Type::to_bvec))
+            .unwrap_or(Type::Bool),
+    };
+
+    // Octaves are embedded in the opcode, so fold them to a literal
+    if name == "perlin3" && args.len() == 2 {
+        args[1] = perlin3_octaves_arg(&args[1])?;
+    }
+    // So are texture slots
+    if name == "mixTex" {
+        args[0] = texture_slot_arg(&args[0])?;
+        args[1] = texture_slot_arg(&args[1])?;
+    }
+    Ok(return_ty)
+}
 
-        // Cross product: vec3 x vec3 -> vec3
-        "cross" => {
-            if args.len() != 2 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 2,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            let left_ty = args[0].ty.as_ref().unwrap();
-            let right_ty = args[1].ty.as_ref().unwrap();
-            if left_ty != &Type::Vec3 || right_ty != &Type::Vec3 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidOperation {
-                        op: "cross".to_string(),
-                        types: alloc::vec![left_ty.clone(), right_ty.clone()],
-                    },
-                    span,
-                });
-            }
-            Ok(Type::Vec3)
-        }
+/// `name` isn't defined for the argument types passed
+fn invalid_operation(name: &str, args: &[Expr], span: crate::shared::Span) -> TypeError {
+    TypeError {
+        kind: TypeErrorKind::InvalidOperation {
+            op: name.to_string(),
+            types: args.iter().filter_map(|arg| arg.ty.clone()).collect(),
+        },
+        span,
+    }
+}
 
-        // Binary functions: Fixed x Fixed -> Fixed
-        "pow" | "mod" | "min" | "max" | "step" => {
-            if args.len() != 2 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 2,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            Ok(Type::Fixed)
-        }
+/// Validate perlin3's octaves argument and fold it to an int literal
+///
+/// Octaves are embedded in the `Perlin3(u8)` opcode, so the argument must be
+/// an Int32 known at compile time and within `PERLIN3_OCTAVES`.
+fn perlin3_octaves_arg(arg: &Expr) -> Result<Expr, TypeError> {
+    const_int_arg(arg, "perlin3 octaves", PERLIN3_OCTAVES)
+}
 
-        // Ternary functions: Fixed x Fixed x Fixed -> Fixed
-        "clamp" | "lerp" | "mix" | "smoothstep" => {
-            if args.len() != 3 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 3,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            Ok(Type::Fixed)
-        }
+/// Valid octave counts for perlin3
+const PERLIN3_OCTAVES: core::ops::RangeInclusive<i32> = 1..=8;
 
-        // Perlin noise: vec3 -> float
-        "perlin3" => {
-            if args.is_empty() || args.len() > 2 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 1,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            let arg_ty = args[0].ty.as_ref().unwrap();
-            if arg_ty != &Type::Vec3 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::Mismatch {
-                        expected: Type::Vec3,
-                        found: arg_ty.clone(),
-                    },
-                    span: args[0].span,
-                });
-            }
-            Ok(Type::Fixed)
-        }
+/// Validate a texture slot argument of `mixTex` and fold it to an int literal
+fn texture_slot_arg(arg: &Expr) -> Result<Expr, TypeError> {
+    const_int_arg(arg, "mixTex texture", 0..=MAX_TEXTURES as i32 - 1)
+}
 
-        // Matrix functions: Mat3 -> Mat3 or Mat3 -> Fixed
-        "transpose" => {
-            if args.len() != 1 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 1,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            let arg_ty = args[0].ty.as_ref().unwrap();
-            if arg_ty != &Type::Mat3 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidOperation {
-                        op: "transpose".to_string(),
-                        types: alloc::vec![arg_ty.clone()],
-                    },
-                    span: args[0].span,
-                });
-            }
-            Ok(Type::Mat3)
-        }
-        "determinant" => {
-            if args.len() != 1 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 1,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            let arg_ty = args[0].ty.as_ref().unwrap();
-            if arg_ty != &Type::Mat3 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidOperation {
-                        op: "determinant".to_string(),
-                        types: alloc::vec![arg_ty.clone()],
-                    },
-                    span: args[0].span,
-                });
-            }
-            Ok(Type::Fixed)
+/// Fold an argument that is embedded in an opcode to an int literal
+///
+/// It must be an Int32 known at compile time and within `valid`; `what`
+/// names it in the error otherwise.
+fn const_int_arg(
+    arg: &Expr,
+    what: &str,
+    valid: core::ops::RangeInclusive<i32>,
+) -> Result<Expr, TypeError> {
+    let arg_ty = arg.ty.as_ref().unwrap();
+    if arg_ty != &Type::Int32 {
+        return Err(TypeError {
+            kind: TypeErrorKind::Mismatch {
+                expected: Type::Int32,
+                found: arg_ty.clone(),
+            },
+            span: arg.span,
+        });
+    }
+
+    let value = match const_int(arg) {
+        Some(n) if valid.contains(&n) => n,
+        Some(n) => {
+            return Err(TypeError {
+                kind: TypeErrorKind::InvalidOperation {
+                    op: format!(
+                        "{} {} (expected {}..={})",
+                        what,
+                        n,
+                        valid.start(),
+                        valid.end()
+                    ),
+                    types: alloc::vec![Type::Int32],
+                },
+                span: arg.span,
+            })
         }
-        "inverse" => {
-            if args.len() != 1 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidArgumentCount {
-                        expected: 1,
-                        found: args.len(),
-                    },
-                    span,
-                });
-            }
-            let arg_ty = args[0].ty.as_ref().unwrap();
-            if arg_ty != &Type::Mat3 {
-                return Err(TypeError {
-                    kind: TypeErrorKind::InvalidOperation {
-                        op: "inverse".to_string(),
-                        types: alloc::vec![arg_ty.clone()],
-                    },
-                    span: args[0].span,
-                });
-            }
-            Ok(Type::Mat3)
+        None => {
+            return Err(TypeError {
+                kind: TypeErrorKind::InvalidOperation {
+                    op: format!("{} must be a constant", what),
+                    types: alloc::vec![Type::Int32],
+                },
+                span: arg.span,
+            })
         }
+    };
 
-        _ => Err(TypeError {
-            kind: TypeErrorKind::UndefinedVariable(alloc::format!("Unknown function '{}'", name)),
-            span,
-        }),
+    let mut folded = Expr::new(ExprKind::IntNumber(value), arg.span);
+    folded.ty = Some(Type::Int32);
+    Ok(folded)
+}
+
+/// Evaluate a constant integer expression, if it is one
+fn const_int(expr: &Expr) -> Option<i32> {
+    match &expr.kind {
+        ExprKind::IntNumber(n) => Some(*n),
+        ExprKind::Neg(inner) => const_int(inner)?.checked_neg(),
+        ExprKind::Add(l, r) => const_int(l)?.checked_add(const_int(r)?),
+        ExprKind::Sub(l, r) => const_int(l)?.checked_sub(const_int(r)?),
+        ExprKind::Mul(l, r) => const_int(l)?.checked_mul(const_int(r)?),
+        ExprKind::Div(l, r) => const_int(l)?.checked_div(const_int(r)?),
+        ExprKind::Mod(l, r) => const_int(l)?.checked_rem(const_int(r)?),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::compile_expr;
     use crate::compiler::error::{CompileError, TypeErrorKind};
+    use crate::shared::Type;
+    use crate::vm::opcodes::LpsOpCode;
+    use crate::{compile_expr, compile_script};
 
     #[test]
     fn test_cross_with_vec2() {
@@ -474,6 +415,95 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_perlin3_float_octaves_rejected() {
+        let result = compile_expr("perlin3(vec3(1.0, 2.0, 3.0), 3.0)");
+        match result {
+            Err(CompileError::TypeCheck(err)) => assert!(matches!(
+                err.kind,
+                TypeErrorKind::Mismatch {
+                    expected: Type::Int32,
+                    found: Type::Fixed,
+                }
+            )),
+            _ => panic!("Expected TypeCheck error for float octaves"),
+        }
+    }
+
+    #[test]
+    fn test_perlin3_vec2_octaves_rejected() {
+        let result = compile_expr("perlin3(vec3(1.0, 2.0, 3.0), vec2(1.0, 2.0))");
+        assert!(
+            matches!(result, Err(CompileError::TypeCheck(_))),
+            "perlin3() with vec2 octaves should be a type error"
+        );
+    }
+
+    #[test]
+    fn test_perlin3_octaves_out_of_range() {
+        for src in [
+            "perlin3(vec3(1.0, 2.0, 3.0), 0)",
+            "perlin3(vec3(1.0, 2.0, 3.0), 9)",
+        ] {
+            match compile_expr(src) {
+                Err(CompileError::TypeCheck(err)) => {
+                    assert!(matches!(err.kind, TypeErrorKind::InvalidOperation { .. }))
+                }
+                _ => panic!("Expected octave range error for {}", src),
+            }
+        }
+    }
+
+    #[test]
+    fn test_perlin3_octaves_must_be_constant() {
+        let result = compile_script(
+            "float noise(int n) { return perlin3(vec3(1.0, 2.0, 3.0), n); } return noise(3);",
+        );
+        assert!(
+            matches!(result, Err(CompileError::TypeCheck(_))),
+            "perlin3() with non-constant octaves should be a type error"
+        );
+    }
+
+    #[test]
+    fn test_perlin3_int_octaves_embedded() {
+        let program = compile_expr("perlin3(vec3(1.0, 2.0, 3.0), 5)").unwrap();
+        assert!(program.functions[0]
+            .opcodes
+            .contains(&LpsOpCode::Perlin3(5)));
+
+        // Constant int expressions fold to the embedded count
+        let program = compile_expr("perlin3(vec3(1.0, 2.0, 3.0), 2 * 3)").unwrap();
+        assert!(program.functions[0]
+            .opcodes
+            .contains(&LpsOpCode::Perlin3(6)));
+    }
+
+    #[test]
+    fn test_mix_tex_slots_must_be_constant_and_in_range() {
+        let program = compile_expr("mixTex(0, 1 + 2, uv, 0.5)").unwrap();
+        assert!(program.functions[0]
+            .opcodes
+            .contains(&LpsOpCode::TextureMixRGBA(0, 3)));
+
+        for src in ["mixTex(0, 4, uv, 0.5)", "mixTex(-1, 0, uv, 0.5)"] {
+            match compile_expr(src) {
+                Err(CompileError::TypeCheck(err)) => {
+                    assert!(matches!(err.kind, TypeErrorKind::InvalidOperation { .. }))
+                }
+                _ => panic!("Expected texture slot range error for {}", src),
+            }
+        }
+
+        let result = compile_script(
+            "vec4 sample(int t) { return mixTex(t, 0, uv, 0.5); } return sample(1);",
+        );
+        assert!(
+            matches!(result, Err(CompileError::TypeCheck(_))),
+            "mixTex() with a non-constant slot should be a type error"
+        );
+    }
+
     #[test]
     fn test_distance_mismatched_types() {
         let result = compile_expr("distance(vec3(1.0, 2.0, 3.0), vec2(4.0, 5.0))");
@@ -2,7 +2,7 @@
 #[cfg(test)]
 mod tests {
     use crate::compiler::expr::expr_test_util::ExprTest;
-    use crate::fixed::ToFixed;
+    use crate::fixed::{ToFixed, Vec2};
     use crate::vm::opcodes::LpsOpCode;
 
     #[test]
@@ -58,4 +58,293 @@ mod tests {
             .expect_result_fixed(5.0)
             .run()
     }
+
+    #[test]
+    fn test_distance_sq_matches_squared_distance() {
+        use crate::fixed::Fixed;
+        use crate::{compile_expr, LpsVm, VmLimits};
+
+        let squared = compile_expr("distanceSq(uv, vec2(0.2, 0.9))").unwrap();
+        let opcodes = &squared.functions[0].opcodes;
+        assert!(opcodes.contains(&LpsOpCode::DistanceSq2), "{:?}", opcodes);
+        assert!(
+            !opcodes.iter().any(|op| matches!(
+                op,
+                LpsOpCode::Distance2 | LpsOpCode::Length2 | LpsOpCode::SqrtFixed
+            )),
+            "{:?}",
+            opcodes
+        );
+
+        let distance = compile_expr("distance(uv, vec2(0.2, 0.9))").unwrap();
+        let mut squared_vm = LpsVm::new(&squared, VmLimits::default()).unwrap();
+        let mut distance_vm = LpsVm::new(&distance, VmLimits::default()).unwrap();
+        for (x, y) in [(0.0, 0.0), (0.5, 0.5), (0.9, 0.1), (0.2, 0.9)] {
+            let run = |vm: &mut LpsVm| {
+                vm.run_scalar(x.to_fixed(), y.to_fixed(), Fixed::ZERO)
+                    .unwrap()
+                    .to_f32()
+            };
+            let d = run(&mut distance_vm);
+            let d2 = run(&mut squared_vm);
+            assert!(
+                (d2 - d * d).abs() < 0.001,
+                "({}, {}): {} vs {}",
+                x,
+                y,
+                d2,
+                d * d
+            );
+        }
+    }
+
+    #[test]
+    fn test_length_sq() -> Result<(), String> {
+        ExprTest::new("lengthSq(vec2(3.0, 4.0))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::Push(4.0.to_fixed()),
+                LpsOpCode::LengthSq2,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(25.0)
+            .run()
+    }
+
+    #[test]
+    fn test_sum_and_avg() -> Result<(), String> {
+        ExprTest::new("sum(vec2(1.5, 2.0))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.5.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::SumVec2,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(3.5)
+            .run()?;
+
+        ExprTest::new("avg(vec2(1.5, 2.0))")
+            .expect_result_fixed(1.75)
+            .run()
+    }
+
+    #[test]
+    fn test_polar_builtin() -> Result<(), String> {
+        // Radius is the distance of uv from (0.5, 0.5)
+        ExprTest::new("polar.x")
+            .with_x(0.8)
+            .with_y(0.9)
+            .expect_result_fixed(0.5)
+            .run()?;
+
+        // Angle is atan2 in radians: 0 along +x, pi/2 along +y
+        ExprTest::new("polar.y")
+            .with_x(0.5)
+            .with_y(0.9)
+            .expect_result_fixed(core::f32::consts::FRAC_PI_2)
+            .run()?;
+        ExprTest::new("polar.y")
+            .with_x(0.1)
+            .with_y(0.5)
+            .expect_result_fixed(core::f32::consts::PI)
+            .run()
+    }
+
+    #[test]
+    fn test_from_polar_reconstructs_uv() {
+        use crate::fixed::Fixed;
+        use crate::vm::{LpsVm, VmLimits};
+
+        let program = crate::compile_expr("fromPolar(polar)").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let mut worst = 0.0f32;
+        for (x, y) in [
+            (0.5, 0.5),
+            (0.9, 0.5),
+            (0.1, 0.2),
+            (0.75, 0.8),
+            (0.3, 0.95),
+            (0.0, 1.0),
+            (1.0, 0.0),
+        ] {
+            let uv = vm
+                .run_vec2(x.to_fixed(), y.to_fixed(), Fixed::ZERO)
+                .unwrap();
+            worst = worst
+                .max((uv.x.to_f32() - x).abs())
+                .max((uv.y.to_f32() - y).abs());
+        }
+        // Limited by the polynomial atan2, worst on the diagonals
+        assert!(worst < 0.03, "fromPolar(polar) off by {}", worst);
+    }
+
+    #[test]
+    fn test_to_uv_inverts_to_pixel() {
+        use crate::fixed::Fixed;
+        use crate::vm::{execute_program_lps, CoordConfig};
+
+        let (width, height) = (7, 5);
+        let render = |expr: &str| {
+            let program = crate::compile_expr(expr).unwrap();
+            let mut output = vec![Fixed::ZERO; width * height];
+            execute_program_lps(
+                &program,
+                &mut output,
+                width,
+                height,
+                Fixed::ZERO,
+                &CoordConfig::default(),
+            );
+            output
+        };
+
+        for axis in ["x", "y"] {
+            let pairs = [
+                (
+                    alloc::format!("uv.{}", axis),
+                    alloc::format!("toUv(toPixel(uv)).{}", axis),
+                ),
+                (
+                    alloc::format!("coord.{}", axis),
+                    alloc::format!("toPixel(uv).{}", axis),
+                ),
+            ];
+            for (expected_expr, actual_expr) in pairs {
+                let expected = render(&expected_expr);
+                let actual = render(&actual_expr);
+                for (e, a) in expected.iter().zip(&actual) {
+                    assert!(
+                        (e.to_f32() - a.to_f32()).abs() < 0.001,
+                        "{} = {} but {} = {}",
+                        expected_expr,
+                        e.to_f32(),
+                        actual_expr,
+                        a.to_f32()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_resolution_folds_to_constants() {
+        use crate::fixed::Fixed;
+        use crate::vm::opcodes::LoadSource;
+        use crate::{compile_expr_with_options, OptimizeOptions};
+
+        let options = OptimizeOptions {
+            resolution: Some((16, 8)),
+            ..OptimizeOptions::all()
+        };
+        let program = compile_expr_with_options("toPixel(uv).y", &options).unwrap();
+        let opcodes = &program.main_function().unwrap().opcodes;
+
+        assert!(!opcodes
+            .iter()
+            .any(|op| matches!(op, LpsOpCode::Load(LoadSource::Width | LoadSource::Height))));
+        assert!(opcodes.contains(&LpsOpCode::Push(Fixed::from_i32(8))));
+    }
+
+    #[test]
+    fn test_from_polar_rejects_non_vec2() {
+        assert!(crate::compile_expr("fromPolar(1.0)").is_err());
+        assert!(crate::compile_expr("fromPolar(vec3(1.0))").is_err());
+    }
+
+    #[test]
+    fn test_fract_per_component() -> Result<(), String> {
+        ExprTest::new("fract(vec2(2.75, -0.25))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(2.75.to_fixed()),
+                LpsOpCode::Push(0.25.to_fixed()),
+                LpsOpCode::NegFixed,
+                LpsOpCode::FractVec2,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec2(Vec2::new(0.75.to_fixed(), 0.75.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_fract_keeps_vec2_type() {
+        let program = crate::compile_expr("fract(uv * 4.0)").unwrap();
+        assert_eq!(program.functions[0].return_type, crate::Type::Vec2);
+        assert!(program.functions[0].opcodes.contains(&LpsOpCode::FractVec2));
+    }
+
+    #[test]
+    fn test_tile() -> Result<(), String> {
+        ExprTest::new("tile(vec2(0.3, 1.6), 2.0)")
+            .expect_result_vec2(Vec2::new(0.6.to_fixed(), 0.2.to_fixed()))
+            .run()?;
+
+        // Negative coordinates wrap up from 1, and int repeats are converted
+        ExprTest::new("tile(vec2(-0.25, 0.75), 2)")
+            .expect_result_vec2(Vec2::new(0.5.to_fixed(), 0.5.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_mirror_tile() -> Result<(), String> {
+        ExprTest::new("mirrorTile(vec2(0.75, 0.25), 2.0)")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(0.75.to_fixed()),
+                LpsOpCode::Push(0.25.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::MirrorTileVec2,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec2(Vec2::new(0.5.to_fixed(), 0.5.to_fixed()))
+            .run()?;
+
+        ExprTest::new("mirrorTile(vec2(-0.25, 1.25), 1.0)")
+            .expect_result_vec2(Vec2::new(0.25.to_fixed(), 0.75.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_tile_is_continuous_at_seams() {
+        use crate::fixed::Fixed;
+        use crate::vm::LpsVm;
+
+        // A gradient sampled through the tiled coordinate, one raw step either
+        // side of each seam; `tile` restarts from 0 at a seam, so it is
+        // continuous when read as a position on the repeating tile
+        let sample = |expr: &str, x: i32| {
+            let program = crate::compile_expr(expr).unwrap();
+            let mut vm = LpsVm::new(&program, Default::default()).unwrap();
+            vm.run(Fixed(x), Fixed::ZERO, Fixed::ZERO).unwrap()[0].to_f32()
+        };
+        let wrapped_gap = |a: f32, b: f32| (a - b).abs().min(1.0 - (a - b).abs());
+
+        for seam in -3..=3 {
+            let at = Fixed::from_i32(seam).0 / 3;
+            for (expr, gap) in [
+                ("tile(uv, 3.0).x", wrapped_gap as fn(f32, f32) -> f32),
+                ("mirrorTile(uv, 3.0).x", |a: f32, b: f32| (a - b).abs()),
+            ] {
+                let (before, after) = (sample(expr, at - 1), sample(expr, at + 1));
+                assert!(
+                    (0.0..1.0).contains(&before) && (0.0..=1.0).contains(&after),
+                    "{} left 0..1 near x = {}",
+                    expr,
+                    seam as f32 / 3.0
+                );
+                assert!(
+                    gap(before, after) < 0.001,
+                    "{} jumps from {} to {} at x = {}",
+                    expr,
+                    before,
+                    after,
+                    seam as f32 / 3.0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tile_rejects_non_vec2() {
+        assert!(crate::compile_expr("tile(1.0, 2.0)").is_err());
+        assert!(crate::compile_expr("mirrorTile(vec3(1.0), 2.0)").is_err());
+    }
 }
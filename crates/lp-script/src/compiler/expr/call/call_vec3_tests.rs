@@ -2,7 +2,8 @@
 #[cfg(test)]
 mod tests {
     use crate::compiler::expr::expr_test_util::ExprTest;
-    use crate::fixed::ToFixed;
+    use crate::fixed::{ToFixed, Vec3};
+    use crate::vm::opcodes::load::LoadSource;
     use crate::vm::opcodes::LpsOpCode;
 
     #[test]
@@ -85,6 +86,27 @@ mod tests {
             .run()
     }
 
+    #[test]
+    fn test_length_sq_and_distance_sq() -> Result<(), String> {
+        ExprTest::new("lengthSq(vec3(2.0, 3.0, 6.0))")
+            .expect_result_fixed(49.0)
+            .run()?;
+
+        ExprTest::new("distanceSq(vec3(1.0, 1.0, 1.0), vec3(3.0, 4.0, 7.0))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::Push(4.0.to_fixed()),
+                LpsOpCode::Push(7.0.to_fixed()),
+                LpsOpCode::DistanceSq3,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(49.0)
+            .run()
+    }
+
     #[test]
     fn test_perlin3() -> Result<(), String> {
         // Tests perlin3 + cos nested function calls
@@ -147,4 +169,205 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_reflect() -> Result<(), String> {
+        // GLSL: I - 2 * dot(N, I) * N
+        ExprTest::new("reflect(vec3(1.0, 2.0, 0.5), vec3(0.0, 1.0, 0.0))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(0.5.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Reflect3,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec3(Vec3::from_f32(1.0, -2.0, 0.5))
+            .run()
+    }
+
+    #[test]
+    fn test_faceforward() -> Result<(), String> {
+        // GLSL: dot(Nref, I) < 0 ? N : -N
+        ExprTest::new(
+            "faceforward(vec3(0.0, 0.0, 1.0), vec3(0.2, 0.0, -1.0), vec3(0.0, 0.0, 1.0))",
+        )
+        .expect_result_vec3(Vec3::from_f32(0.0, 0.0, 1.0))
+        .run()?;
+
+        // Incident along Nref: the normal is flipped
+        ExprTest::new("faceforward(vec3(0.0, 0.0, 1.0), vec3(0.2, 0.0, 1.0), vec3(0.0, 0.0, 1.0))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(0.2.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::FaceForward3,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec3(Vec3::from_f32(0.0, 0.0, -1.0))
+            .run()?;
+
+        // Nref differs from N: only its sign against I matters
+        ExprTest::new(
+            "faceforward(vec3(1.0, 2.0, 3.0), vec3(1.0, 0.0, 0.0), vec3(-1.0, 0.0, 0.0))",
+        )
+        .expect_result_vec3(Vec3::from_f32(1.0, 2.0, 3.0))
+        .run()?;
+
+        // dot(Nref, I) == 0 counts as facing the same way (flip)
+        ExprTest::new("faceforward(vec3(1.0, 2.0, 3.0), vec3(0.0, 1.0, 0.0), vec3(1.0, 0.0, 0.0))")
+            .expect_result_vec3(Vec3::from_f32(-1.0, -2.0, -3.0))
+            .run()
+    }
+
+    #[test]
+    fn test_faceforward_requires_matching_vectors() {
+        assert!(crate::compile_expr("faceforward(vec3(0.0), vec2(0.0), vec3(0.0))").is_err());
+        assert!(crate::compile_expr("faceforward(1.0, 2.0, 3.0)").is_err());
+        assert!(crate::compile_expr("reflect(vec3(1.0), vec3(0.0))").is_ok());
+    }
+
+    #[test]
+    fn test_max_comp() -> Result<(), String> {
+        ExprTest::new("maxComp(vec3(0.25, 2.0, 0.5))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(0.25.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(0.5.to_fixed()),
+                LpsOpCode::MaxCompVec3,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(2.0)
+            .run()
+    }
+
+    #[test]
+    fn test_min_comp() -> Result<(), String> {
+        ExprTest::new("minComp(vec3(0.25, 2.0, x))")
+            .with_x(-1.5)
+            .expect_result_fixed(-1.5)
+            .run()
+    }
+
+    #[test]
+    fn test_comp_reductions_reject_scalars() {
+        assert!(crate::compile_expr("maxComp(1.0)").is_err());
+        assert!(crate::compile_expr("minComp(2)").is_err());
+        assert!(crate::compile_expr("maxComp(vec3(1.0), vec3(2.0))").is_err());
+        assert!(crate::compile_expr("minComp(vec2(1.0, 2.0))").is_ok());
+        assert!(crate::compile_expr("maxComp(vec4(1.0))").is_ok());
+    }
+
+    #[test]
+    fn test_sum_and_avg() -> Result<(), String> {
+        ExprTest::new("sum(vec3(0.25, 2.0, x))")
+            .with_x(-1.5)
+            .expect_result_fixed(0.75)
+            .run()?;
+
+        ExprTest::new("avg(vec3(0.25, 2.0, -1.5))")
+            .expect_result_fixed(0.25)
+            .run()
+    }
+
+    #[test]
+    fn test_sum_and_avg_reject_scalars() {
+        assert!(crate::compile_expr("sum(1.0)").is_err());
+        assert!(crate::compile_expr("avg(3)").is_err());
+        assert!(crate::compile_expr("avg(vec3(1.0), vec3(2.0))").is_err());
+    }
+
+    #[test]
+    fn test_abs_per_component() -> Result<(), String> {
+        ExprTest::new("abs(vec3(-1.5, 2.0, x))")
+            .with_x(-0.25)
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.5.to_fixed()),
+                LpsOpCode::NegFixed,
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Load(LoadSource::XNorm),
+                LpsOpCode::AbsVec3,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec3(Vec3::new(1.5.to_fixed(), 2.0.to_fixed(), 0.25.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_unary_math_keeps_vec3_type() {
+        for (name, opcode) in [
+            ("abs", LpsOpCode::AbsVec3),
+            ("sign", LpsOpCode::SignVec3),
+            ("floor", LpsOpCode::FloorVec3),
+            ("ceil", LpsOpCode::CeilVec3),
+            ("fract", LpsOpCode::FractVec3),
+            ("sqrt", LpsOpCode::SqrtVec3),
+        ] {
+            let src = format!("{}(vec3(uv, time))", name);
+            let program = crate::compile_expr_as(&src, crate::Type::Vec3)
+                .unwrap_or_else(|e| panic!("{}: {}", src, e));
+            assert!(program.functions[0].opcodes.contains(&opcode), "{}", src);
+        }
+        assert!(crate::compile_expr_as("floor(vec3(uv, time))", crate::Type::Fixed).is_err());
+    }
+
+    #[test]
+    fn test_hsv2rgb() -> Result<(), String> {
+        ExprTest::new("hsv2rgb(vec3(0.0, 1.0, 1.0))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::HsvToRgb,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec3(Vec3::new(1.0.to_fixed(), 0.0.to_fixed(), 0.0.to_fixed()))
+            .run()?;
+
+        // Half a turn is cyan, at half saturation the other channel rises to 0.5
+        ExprTest::new("hsv2rgb(vec3(0.5, 0.5, 1.0))")
+            .expect_result_vec3(Vec3::new(0.5.to_fixed(), 1.0.to_fixed(), 1.0.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_rgb2hsv() -> Result<(), String> {
+        ExprTest::new("rgb2hsv(vec3(0.0, 1.0, 1.0))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::RgbToHsv,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec3(Vec3::new(0.5.to_fixed(), 1.0.to_fixed(), 1.0.to_fixed()))
+            .run()?;
+
+        // Grays have no hue
+        ExprTest::new("rgb2hsv(vec3(0.5))")
+            .expect_result_vec3(Vec3::new(0.0.to_fixed(), 0.0.to_fixed(), 0.5.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_hsv_round_trip() -> Result<(), String> {
+        ExprTest::new("rgb2hsv(hsv2rgb(vec3(0.75, 0.5, 1.0)))")
+            .expect_result_vec3(Vec3::new(0.75.to_fixed(), 0.5.to_fixed(), 1.0.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_color_conversions_require_vec3() {
+        assert!(crate::compile_expr("hsv2rgb(vec2(0.5, 1.0))").is_err());
+        assert!(crate::compile_expr("rgb2hsv(1.0)").is_err());
+        assert!(crate::compile_expr("hsv2rgb(vec3(0.5), 1.0)").is_err());
+    }
 }
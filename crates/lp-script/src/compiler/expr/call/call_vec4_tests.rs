@@ -2,7 +2,7 @@
 #[cfg(test)]
 mod tests {
     use crate::compiler::expr::expr_test_util::ExprTest;
-    use crate::fixed::ToFixed;
+    use crate::fixed::{ToFixed, Vec4};
     use crate::vm::opcodes::LpsOpCode;
 
     #[test]
@@ -68,4 +68,53 @@ mod tests {
             .expect_result_fixed(7.0)
             .run()
     }
+
+    #[test]
+    fn test_length_sq_and_distance_sq() -> Result<(), String> {
+        ExprTest::new("lengthSq(vec4(1.0, 2.0, 2.0, 4.0))")
+            .expect_result_fixed(25.0)
+            .run()?;
+
+        ExprTest::new("distanceSq(vec4(1.0, 0.0, 0.0, 1.0), vec4(1.0, 2.0, 2.0, 5.0))")
+            .expect_result_fixed(24.0)
+            .run()
+    }
+
+    #[test]
+    fn test_reflect_and_faceforward() -> Result<(), String> {
+        ExprTest::new("reflect(vec4(1.0, -2.0, 3.0, 4.0), vec4(0.0, 1.0, 0.0, 0.0))")
+            .expect_result_vec4(Vec4::from_f32(1.0, 2.0, 3.0, 4.0))
+            .run()?;
+
+        ExprTest::new("faceforward(vec4(1.0, 0.0, 0.0, 0.0), vec4(0.0, 0.0, 0.0, 1.0), vec4(0.0, 0.0, 0.0, 1.0))")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::FaceForward4,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec4(Vec4::from_f32(-1.0, 0.0, 0.0, 0.0))
+            .run()
+    }
+
+    #[test]
+    fn test_sum_and_avg() -> Result<(), String> {
+        ExprTest::new("sum(vec4(1.0, 2.0, 3.0, 4.0))")
+            .expect_result_fixed(10.0)
+            .run()?;
+
+        ExprTest::new("avg(vec4(1.0, 2.0, 3.0, 4.0))")
+            .expect_result_fixed(2.5)
+            .run()
+    }
 }
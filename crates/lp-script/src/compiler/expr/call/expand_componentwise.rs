@@ -8,21 +8,18 @@ use alloc::string::String;
 use alloc::vec::Vec;
 
 use crate::compiler::ast::{Expr, ExprKind};
+use crate::compiler::builtins::builtin_function;
 use crate::shared::{Span, Type};
 
 /// Check if a function supports component-wise expansion
 pub(crate) fn is_componentwise_function(name: &str) -> bool {
-    matches!(
-        name,
-        // Single-arg component-wise functions
-        "sin" | "cos" | "tan" | "atan" | "asin" | "acos" |
-        "abs" | "floor" | "ceil" | "sqrt" | "sign" | "fract" | "saturate" |
-        "exp" | "log" | "exp2" | "log2" | "inversesqrt" |
-        "radians" | "degrees" | "trunc" | "round" |
-        // Multi-arg component-wise functions
-        "min" | "max" | "mod" | "pow" | 
-        "clamp" | "step" | "mix" | "smoothstep"
-    )
+    builtin_function(name).is_some_and(|sig| sig.componentwise)
+}
+
+/// Whether a one-argument call on a vector has its own opcode (`AbsVec3`,
+/// ...) and so type checks to the vector type without expansion
+pub(crate) fn has_vector_opcode(name: &str) -> bool {
+    matches!(name, "abs" | "sign" | "floor" | "ceil" | "fract" | "sqrt")
 }
 
 /// Expand a component-wise function call
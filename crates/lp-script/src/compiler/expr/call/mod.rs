@@ -8,11 +8,17 @@ mod expand_componentwise;
 
 pub(in crate::compiler) use call_types::check_call;
 
+#[cfg(test)]
+mod call_bvec_tests;
 #[cfg(test)]
 mod call_fixed_tests;
 #[cfg(test)]
+mod call_mat2_tests;
+#[cfg(test)]
 mod call_mat3_tests;
 #[cfg(test)]
+mod call_mat4_tests;
+#[cfg(test)]
 mod call_vec2_tests;
 #[cfg(test)]
 mod call_vec3_tests;
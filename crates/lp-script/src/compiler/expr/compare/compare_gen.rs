@@ -29,38 +29,38 @@ impl<'a> CodeGenerator<'a> {
         self.gen_expr(right);
         // Determine type from left operand (both should be same type after type checking)
         let ty = left.ty.as_ref().unwrap_or(&Type::Fixed);
-        self.code
-            .push(self.get_comparison_opcode(ComparisonOp::Less, ty));
+        self.push_comparison(ComparisonOp::Less, ty);
     }
 
     pub(crate) fn gen_greater(&mut self, left: &Expr, right: &Expr) {
         self.gen_expr(left);
         self.gen_expr(right);
         let ty = left.ty.as_ref().unwrap_or(&Type::Fixed);
-        self.code
-            .push(self.get_comparison_opcode(ComparisonOp::Greater, ty));
+        self.push_comparison(ComparisonOp::Greater, ty);
     }
 
     pub(crate) fn gen_less_eq(&mut self, left: &Expr, right: &Expr) {
         self.gen_expr(left);
         self.gen_expr(right);
         let ty = left.ty.as_ref().unwrap_or(&Type::Fixed);
-        self.code
-            .push(self.get_comparison_opcode(ComparisonOp::LessEq, ty));
+        self.push_comparison(ComparisonOp::LessEq, ty);
     }
 
     pub(crate) fn gen_greater_eq(&mut self, left: &Expr, right: &Expr) {
         self.gen_expr(left);
         self.gen_expr(right);
         let ty = left.ty.as_ref().unwrap_or(&Type::Fixed);
-        self.code
-            .push(self.get_comparison_opcode(ComparisonOp::GreaterEq, ty));
+        self.push_comparison(ComparisonOp::GreaterEq, ty);
     }
 
     pub(crate) fn gen_eq(&mut self, left: &Expr, right: &Expr) {
         self.gen_expr(left);
         self.gen_expr(right);
         let ty = left.ty.as_ref().unwrap_or(&Type::Fixed);
+        if let Some(op) = vec_eq_opcode(ty) {
+            self.code.push(op);
+            return;
+        }
         self.code
             .push(self.get_comparison_opcode(ComparisonOp::Eq, ty));
     }
@@ -69,9 +69,79 @@ impl<'a> CodeGenerator<'a> {
         self.gen_expr(left);
         self.gen_expr(right);
         let ty = left.ty.as_ref().unwrap_or(&Type::Fixed);
+        // Vectors: negate the component-wise equality
+        if let Some(op) = vec_eq_opcode(ty) {
+            self.code.push(op);
+            self.code.push(LpsOpCode::NotFixed);
+            return;
+        }
         self.code
             .push(self.get_comparison_opcode(ComparisonOp::NotEq, ty));
     }
+
+    /// Compare the two `ty` operands on the stack, per component for vectors
+    fn push_comparison(&mut self, op: ComparisonOp, ty: &Type) {
+        let func = match op {
+            ComparisonOp::Less => "lessThan",
+            ComparisonOp::LessEq => "lessThanEqual",
+            ComparisonOp::Greater => "greaterThan",
+            ComparisonOp::GreaterEq => "greaterThanEqual",
+            ComparisonOp::Eq => "equal",
+            ComparisonOp::NotEq => "notEqual",
+        };
+        if !self.push_vec_comparison(func, ty) {
+            self.code.push(self.get_comparison_opcode(op, ty));
+        }
+    }
+
+    /// Per-component comparison of the two `ty` vectors on the stack, named
+    /// like the GLSL function (`lessThan`), leaving a bvec
+    ///
+    /// Fixed-point values are totally ordered, so `greaterThan` is the
+    /// flipped `lessThanEqual`. Returns false if `ty` isn't a vector.
+    pub(crate) fn push_vec_comparison(&mut self, func: &str, ty: &Type) -> bool {
+        let (less, less_eq, eq, not) = match ty {
+            Type::Vec2 => (
+                LpsOpCode::LessVec2,
+                LpsOpCode::LessEqVec2,
+                LpsOpCode::EqCompVec2,
+                LpsOpCode::NotBVec2,
+            ),
+            Type::Vec3 => (
+                LpsOpCode::LessVec3,
+                LpsOpCode::LessEqVec3,
+                LpsOpCode::EqCompVec3,
+                LpsOpCode::NotBVec3,
+            ),
+            Type::Vec4 => (
+                LpsOpCode::LessVec4,
+                LpsOpCode::LessEqVec4,
+                LpsOpCode::EqCompVec4,
+                LpsOpCode::NotBVec4,
+            ),
+            _ => return false,
+        };
+        match func {
+            "lessThan" => self.code.push(less),
+            "lessThanEqual" => self.code.push(less_eq),
+            "greaterThan" => self.code.extend([less_eq, not]),
+            "greaterThanEqual" => self.code.extend([less, not]),
+            "equal" => self.code.push(eq),
+            "notEqual" => self.code.extend([eq, not]),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Whole-vector equality opcode, exact on the fixed-point components
+fn vec_eq_opcode(ty: &Type) -> Option<LpsOpCode> {
+    match ty {
+        Type::Vec2 | Type::BVec2 => Some(LpsOpCode::EqVec2),
+        Type::Vec3 | Type::BVec3 => Some(LpsOpCode::EqVec3),
+        Type::Vec4 | Type::BVec4 => Some(LpsOpCode::EqVec4),
+        _ => None,
+    }
 }
 
 enum ComparisonOp {
@@ -0,0 +1,189 @@
+/// Equality operator tests for vector types
+#[cfg(test)]
+mod tests {
+    use crate::compiler::error::{CompileError, TypeErrorKind};
+    use crate::compiler::expr::expr_test_util::ExprTest;
+    use crate::fixed::{ToFixed, Vec2, Vec3, Vec4};
+    use crate::vm::opcodes::load::LoadSource;
+    use crate::vm::opcodes::LpsOpCode;
+
+    #[test]
+    fn test_vec3_equal() -> Result<(), String> {
+        ExprTest::new("vec3(1.0, 2.0, 3.0) == vec3(1.0, 2.0, 3.0)")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::EqVec3,
+                LpsOpCode::Return,
+            ])
+            .expect_result_bool(true)
+            .run()?;
+
+        // A single differing component makes the vectors unequal
+        ExprTest::new("vec3(1.0, 2.0, 3.0) == vec3(1.0, 2.5, 3.0)")
+            .expect_result_bool(false)
+            .run()
+    }
+
+    #[test]
+    fn test_vec3_not_equal() -> Result<(), String> {
+        ExprTest::new("vec3(1.0, 2.0, 3.0) != vec3(1.0, 2.0, 4.0)")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(4.0.to_fixed()),
+                LpsOpCode::EqVec3,
+                LpsOpCode::NotFixed,
+                LpsOpCode::Return,
+            ])
+            .expect_result_bool(true)
+            .run()?;
+
+        ExprTest::new("vec3(1.0, 2.0, 3.0) != vec3(1.0, 2.0, 3.0)")
+            .expect_result_bool(false)
+            .run()
+    }
+
+    #[test]
+    fn test_vec3_equality_is_exact() -> Result<(), String> {
+        // Differ by one fixed-point step (1/65536): no epsilon is applied
+        ExprTest::new("vec3(x, 0.0, 0.0) == vec3(0.5, 0.0, 0.0)")
+            .with_x(0.5 + 1.0 / 65536.0)
+            .expect_result_bool(false)
+            .run()
+    }
+
+    #[test]
+    fn test_vec2_and_vec4_equality() -> Result<(), String> {
+        ExprTest::new("vec2(x, y) == vec2(0.25, 0.75)")
+            .with_vm_params(0.25, 0.75, 0.0)
+            .expect_result_bool(true)
+            .run()?;
+
+        ExprTest::new("vec4(1.0, 2.0, 3.0, 4.0) != vec4(1.0, 2.0, 3.0, 4.0)")
+            .expect_result_bool(false)
+            .run()
+    }
+
+    #[test]
+    fn test_vector_equality_requires_matching_types() {
+        let err = crate::compile_expr("vec3(1.0, 2.0, 3.0) == vec2(1.0, 2.0)").unwrap_err();
+        assert!(matches!(
+            err,
+            CompileError::TypeCheck(ref e) if matches!(e.kind, TypeErrorKind::Mismatch { .. })
+        ));
+
+        assert!(crate::compile_expr("vec2(1.0, 2.0) == 1.0").is_err());
+    }
+
+    #[test]
+    fn test_matrix_equality_is_rejected() {
+        let mat2 = "mat2(1.0, 0.0, 0.0, 1.0)";
+        let mat3 = "mat3(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0)";
+        let mat4 = "mat4(1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, \
+                    0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0)";
+        for expr in [
+            format!("{} == {}", mat2, mat2),
+            format!("{} != {}", mat3, mat3),
+            format!("{} == {}", mat4, mat4),
+            format!("{} == 1.0", mat2),
+        ] {
+            let err = crate::compile_expr(&expr).unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    CompileError::TypeCheck(ref e)
+                        if matches!(e.kind, TypeErrorKind::InvalidOperation { .. })
+                ),
+                "{}: {:?}",
+                expr,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_vec3_less_is_per_component() -> Result<(), String> {
+        ExprTest::new("vec3(1.0, 2.0, 3.0) < vec3(2.0, 2.0, 1.0)")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::LessVec3,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec3(Vec3::new(1.0.to_fixed(), 0.0.to_fixed(), 0.0.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_vec2_greater_eq_negates_less() -> Result<(), String> {
+        ExprTest::new("vec2(x, y) >= vec2(0.5, 0.5)")
+            .with_vm_params(0.5, 0.25, 0.0)
+            .expect_opcodes(vec![
+                LpsOpCode::Load(LoadSource::XNorm),
+                LpsOpCode::Load(LoadSource::YNorm),
+                LpsOpCode::Push(0.5.to_fixed()),
+                LpsOpCode::Push(0.5.to_fixed()),
+                LpsOpCode::LessVec2,
+                LpsOpCode::NotBVec2,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec2(Vec2::new(1.0.to_fixed(), 0.0.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_vec4_greater_and_less_eq() -> Result<(), String> {
+        ExprTest::new("vec4(1.0, 2.0, 3.0, 4.0) > vec4(2.5)")
+            .expect_result_vec4(Vec4::new(
+                0.0.to_fixed(),
+                0.0.to_fixed(),
+                1.0.to_fixed(),
+                1.0.to_fixed(),
+            ))
+            .run()?;
+
+        ExprTest::new("vec4(1.0, 2.0, 3.0, 4.0) <= vec4(3.0)")
+            .expect_result_vec4(Vec4::new(
+                1.0.to_fixed(),
+                1.0.to_fixed(),
+                1.0.to_fixed(),
+                0.0.to_fixed(),
+            ))
+            .run()
+    }
+
+    #[test]
+    fn test_bvec_equality() -> Result<(), String> {
+        ExprTest::new("(vec2(1.0, 2.0) < vec2(2.0)) == bvec2(true, false)")
+            .expect_result_bool(true)
+            .run()
+    }
+
+    #[test]
+    fn test_vector_comparison_requires_matching_types() {
+        for src in ["vec3(1.0) < vec2(1.0)", "vec2(1.0) > 1.0"] {
+            let err = crate::compile_expr(src).unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    CompileError::TypeCheck(ref e) if matches!(e.kind, TypeErrorKind::Mismatch { .. })
+                ),
+                "{}: {:?}",
+                src,
+                err
+            );
+        }
+    }
+}
@@ -10,3 +10,5 @@ mod compare_parse;
 mod compare_fixed_tests;
 #[cfg(test)]
 mod compare_int32_tests;
+#[cfg(test)]
+mod compare_vec_tests;
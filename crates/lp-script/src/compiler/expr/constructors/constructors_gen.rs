@@ -3,14 +3,54 @@ extern crate alloc;
 
 use crate::compiler::ast::Expr;
 use crate::compiler::codegen::CodeGenerator;
+use crate::fixed::Fixed;
+use crate::shared::Type;
+use crate::vm::opcodes::LpsOpCode;
 
 impl<'a> CodeGenerator<'a> {
-    pub(crate) fn gen_vec_constructor(&mut self, args: &[Expr]) {
+    pub(crate) fn gen_vec_constructor(&mut self, args: &[Expr], ty: Option<&Type>) {
         // Generate code for each argument (leaves values on stack in order)
         for arg in args {
             self.gen_expr(arg);
+            // Components are floats: vec2(1, 2) is vec2(1.0, 2.0); bools
+            // already are 1.0 / 0.0
+            if matches!(arg.ty, Some(Type::Int32)) {
+                self.code.push(LpsOpCode::Int32ToFixed);
+            }
         }
         // Vector constructors don't need a special opcode - args are already on stack
         // Vec2(x, y) leaves x, y on stack (that IS a vec2)
+
+        // A single scalar fills every component: vec3(s) is [s, s, s]
+        let size = ty.map_or(0, Type::size_in_i32s);
+        if let [arg] = args {
+            if arg.ty.as_ref().map_or(0, Type::size_in_i32s) == 1 {
+                match ty {
+                    Some(Type::Mat2) => self.gen_diagonal(2),
+                    Some(Type::Mat3) => self.gen_diagonal(3),
+                    Some(Type::Mat4) => self.gen_diagonal(4),
+                    _ => {
+                        for _ in 1..size {
+                            self.code.push(LpsOpCode::Dup1);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Spread the scalar on the stack over the diagonal of an `n`x`n`
+    /// matrix, zeros elsewhere: mat2(s) is [s, 0, 0, s]
+    ///
+    /// A copy of the scalar is kept on top and swapped past the `n` zeros
+    /// between diagonal entries, so it's only evaluated once.
+    fn gen_diagonal(&mut self, n: usize) {
+        for _ in 1..n {
+            self.code.push(LpsOpCode::Dup1);
+            for _ in 0..n {
+                self.code.push(LpsOpCode::Push(Fixed::ZERO));
+                self.code.push(LpsOpCode::Swap);
+            }
+        }
     }
 }
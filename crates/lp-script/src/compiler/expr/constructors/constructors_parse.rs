@@ -28,7 +28,12 @@ impl Parser {
             TokenKind::Vec2 => ExprKind::Vec2Constructor(args),
             TokenKind::Vec3 => ExprKind::Vec3Constructor(args),
             TokenKind::Vec4 => ExprKind::Vec4Constructor(args),
+            TokenKind::BVec2 => ExprKind::BVec2Constructor(args),
+            TokenKind::BVec3 => ExprKind::BVec3Constructor(args),
+            TokenKind::BVec4 => ExprKind::BVec4Constructor(args),
+            TokenKind::Mat2 => ExprKind::Mat2Constructor(args),
             TokenKind::Mat3 => ExprKind::Mat3Constructor(args),
+            TokenKind::Mat4 => ExprKind::Mat4Constructor(args),
             _ => unreachable!(),
         };
 
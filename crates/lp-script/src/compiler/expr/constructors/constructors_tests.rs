@@ -1,6 +1,7 @@
 /// Vector constructor tests
 #[cfg(test)]
 mod tests {
+    use crate::compiler::error::{CompileError, TypeErrorKind};
     use crate::compiler::expr::expr_test_util::ExprTest;
     use crate::fixed::{ToFixed, Vec2, Vec3, Vec4};
     use crate::vm::opcodes::LpsOpCode;
@@ -186,6 +187,133 @@ mod tests {
             .run()
     }
 
+    #[test]
+    fn test_mat_from_one_scalar_is_diagonal() -> Result<(), String> {
+        // The scalar is evaluated once and carried to each diagonal entry
+        ExprTest::new("mat3(2.0)")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Dup1,
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Swap,
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Swap,
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Swap,
+                LpsOpCode::Dup1,
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Swap,
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Swap,
+                LpsOpCode::Push(0.0.to_fixed()),
+                LpsOpCode::Swap,
+                LpsOpCode::Return,
+            ])
+            .expect_result_mat3(crate::fixed::Mat3::from_f32(
+                2.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 2.0,
+            ))
+            .run()?;
+
+        ExprTest::new("mat3(1)")
+            .expect_result_mat3(crate::fixed::Mat3::identity())
+            .run()?;
+        ExprTest::new("mat2(2.0) * vec2(1.0, 3.0)")
+            .expect_result_vec2(Vec2::new(2.0.to_fixed(), 6.0.to_fixed()))
+            .run()?;
+        ExprTest::new("mat4(1.0) * vec4(1.0, 2.0, 3.0, 4.0)")
+            .expect_result_vec4(Vec4::new(
+                1.0.to_fixed(),
+                2.0.to_fixed(),
+                3.0.to_fixed(),
+                4.0.to_fixed(),
+            ))
+            .run()
+    }
+
     // Type checking tests (using ExprTest validates types automatically)
     // These tests already exist above and validate type checking through execution
+
+    #[test]
+    fn test_vec3_from_one_scalar_splats() -> Result<(), String> {
+        ExprTest::new("vec3(0.5)")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(0.5.to_fixed()),
+                LpsOpCode::Dup1,
+                LpsOpCode::Dup1,
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec3(Vec3::new(0.5.to_fixed(), 0.5.to_fixed(), 0.5.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_vec2_int_components_convert_to_float() -> Result<(), String> {
+        ExprTest::new("vec2(1, 2)")
+            .expect_result_vec2(Vec2::new(1.0.to_fixed(), 2.0.to_fixed()))
+            .run()?;
+
+        ExprTest::new("vec4(3)")
+            .expect_result_vec4(Vec4::new(
+                3.0.to_fixed(),
+                3.0.to_fixed(),
+                3.0.to_fixed(),
+                3.0.to_fixed(),
+            ))
+            .run()
+    }
+
+    #[test]
+    fn test_vec_constructor_component_count() {
+        for (src, expected, found) in [
+            ("vec3(uv)", 3, 2),
+            ("vec2(uv, 1.0)", 2, 3),
+            ("mat2(vec2(1.0))", 4, 2),
+        ] {
+            match crate::compile_expr(src) {
+                Err(CompileError::TypeCheck(err)) => assert!(
+                    matches!(
+                        err.kind,
+                        TypeErrorKind::InvalidArgumentCount { expected: e, found: f }
+                            if e == expected && f == found
+                    ),
+                    "{}: {:?}",
+                    src,
+                    err.kind
+                ),
+                other => panic!("{}: expected a component count error, got {:?}", src, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_bvec_constructors() -> Result<(), String> {
+        ExprTest::new("bvec3(true, x > 0.5, false)")
+            .with_x(1.0)
+            .expect_result_vec3(Vec3::new(1.0.to_fixed(), 1.0.to_fixed(), 0.0.to_fixed()))
+            .run()?;
+
+        // Splat, and a bvec2 widened with a bool
+        ExprTest::new("bvec2(true)")
+            .expect_result_vec2(Vec2::new(1.0.to_fixed(), 1.0.to_fixed()))
+            .run()?;
+
+        ExprTest::new("bvec3(bvec2(false, true), true)")
+            .expect_result_vec3(Vec3::new(0.0.to_fixed(), 1.0.to_fixed(), 1.0.to_fixed()))
+            .run()
+    }
+
+    #[test]
+    fn test_bvec_constructor_requires_bools() {
+        for src in ["bvec2(1.0, 0.0)", "bvec3(vec3(1.0))", "bvec2(1, true)"] {
+            match crate::compile_expr(src) {
+                Err(CompileError::TypeCheck(err)) => assert!(
+                    matches!(err.kind, TypeErrorKind::Mismatch { .. }),
+                    "{}: {:?}",
+                    src,
+                    err.kind
+                ),
+                other => panic!("{}: expected a type mismatch, got {:?}", src, other),
+            }
+        }
+    }
 }
@@ -16,8 +16,9 @@ impl TypeChecker {
             Type::Bool | Type::Fixed | Type::Int32 => 1,
             Type::Vec2 => 2,
             Type::Vec3 => 3,
-            Type::Vec4 => 4,
+            Type::Vec4 | Type::Mat2 => 4,
             Type::Mat3 => 9,
+            Type::Mat4 => 16,
             Type::Void => 0,
         }
     }
@@ -84,8 +85,10 @@ impl TypeChecker {
                 Type::Fixed | Type::Int32 | Type::Bool => 1,
                 Type::Vec2 => 2,
                 Type::Vec3 => 3,
-                Type::Vec4 => 4,
+                Type::Vec4 | Type::Mat2 => 4,
                 Type::Mat3 => 9,
+                Type::Mat4 => 16,
+            Type::Mat4 => 16,
                 _ => {
                     return Err(TypeError {
                         kind: TypeErrorKind::InvalidOperation {
@@ -114,6 +117,7 @@ impl TypeChecker {
             3 => Type::Vec3,
             4 => Type::Vec4,
             9 => Type::Mat3,
+            16 => Type::Mat4,
             _ => unreachable!(),
         })
     }
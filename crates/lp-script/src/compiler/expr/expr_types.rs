@@ -26,16 +26,43 @@ impl TypeChecker {
                 expr.ty = Some(Self::check_int_number());
             }
 
+            ExprKind::Bool(_) => {
+                expr.ty = Some(Type::Bool);
+            }
+
             ExprKind::Variable(name) => {
-                let var_type =
-                    crate::compiler::expr::variable::check_variable(name, symbols, expr_span)?;
-                expr.ty = Some(var_type);
+                if let Some(value) = symbols.lookup_const(name) {
+                    // Consts are replaced by their folded value
+                    *expr = Expr {
+                        span: expr_span,
+                        ..value.clone()
+                    };
+                } else if let Some(value) =
+                    crate::compiler::expr::variable::visible_constant(name, symbols)
+                {
+                    // So are built-in constants like `PI`
+                    expr.kind = ExprKind::Number(value.to_f32());
+                    expr.ty = Some(Type::Fixed);
+                } else {
+                    let var_type =
+                        crate::compiler::expr::variable::check_variable(name, symbols, expr_span)?;
+                    expr.ty = Some(var_type);
+                }
             }
 
             // Binary arithmetic operations
+            ExprKind::Mul(left, right) => {
+                let result_ty = crate::compiler::expr::binary::check_binary_mul(
+                    left.as_mut(),
+                    right.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
+                expr.ty = Some(result_ty);
+            }
             ExprKind::Add(left, right)
             | ExprKind::Sub(left, right)
-            | ExprKind::Mul(left, right)
             | ExprKind::Div(left, right)
             | ExprKind::Mod(left, right) => {
                 let result_ty = crate::compiler::expr::binary::check_binary_arithmetic(
@@ -49,12 +76,53 @@ impl TypeChecker {
             }
 
             // Bitwise operations (Int32 only)
-            ExprKind::BitwiseAnd(left, right)
-            | ExprKind::BitwiseOr(left, right)
-            | ExprKind::BitwiseXor(left, right)
-            | ExprKind::LeftShift(left, right)
-            | ExprKind::RightShift(left, right) => {
+            ExprKind::BitwiseAnd(left, right) => {
+                let result_ty = Self::check_bitwise_binary(
+                    "&",
+                    left.as_mut(),
+                    right.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
+                expr.ty = Some(result_ty);
+            }
+            ExprKind::BitwiseOr(left, right) => {
+                let result_ty = Self::check_bitwise_binary(
+                    "|",
+                    left.as_mut(),
+                    right.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
+                expr.ty = Some(result_ty);
+            }
+            ExprKind::BitwiseXor(left, right) => {
+                let result_ty = Self::check_bitwise_binary(
+                    "^",
+                    left.as_mut(),
+                    right.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
+                expr.ty = Some(result_ty);
+            }
+            ExprKind::LeftShift(left, right) => {
+                let result_ty = Self::check_bitwise_binary(
+                    "<<",
+                    left.as_mut(),
+                    right.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
+                expr.ty = Some(result_ty);
+            }
+            ExprKind::RightShift(left, right) => {
                 let result_ty = Self::check_bitwise_binary(
+                    ">>",
                     left.as_mut(),
                     right.as_mut(),
                     symbols,
@@ -74,23 +142,55 @@ impl TypeChecker {
             ExprKind::Less(left, right)
             | ExprKind::Greater(left, right)
             | ExprKind::LessEq(left, right)
-            | ExprKind::GreaterEq(left, right)
-            | ExprKind::Eq(left, right)
-            | ExprKind::NotEq(left, right) => {
-                let ty =
-                    Self::check_comparison(left.as_mut(), right.as_mut(), symbols, func_table)?;
+            | ExprKind::GreaterEq(left, right) => {
+                let ty = Self::check_comparison(
+                    left.as_mut(),
+                    right.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
+                expr.ty = Some(ty);
+            }
+
+            ExprKind::Eq(left, right) | ExprKind::NotEq(left, right) => {
+                let ty = Self::check_equality(
+                    left.as_mut(),
+                    right.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
                 expr.ty = Some(ty);
             }
 
             // Logical operations
-            ExprKind::And(left, right) | ExprKind::Or(left, right) => {
-                let result_ty =
-                    Self::check_logical(left.as_mut(), right.as_mut(), symbols, func_table)?;
+            ExprKind::And(left, right) => {
+                let result_ty = Self::check_logical(
+                    "&&",
+                    left.as_mut(),
+                    right.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
+                expr.ty = Some(result_ty);
+            }
+            ExprKind::Or(left, right) => {
+                let result_ty = Self::check_logical(
+                    "||",
+                    left.as_mut(),
+                    right.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
                 expr.ty = Some(result_ty);
             }
 
             ExprKind::Not(operand) => {
                 Self::infer_type(operand.as_mut(), symbols, func_table)?;
+                Self::check_truth_value("!", operand, expr_span)?;
                 expr.ty = Some(Type::Bool);
             }
 
@@ -132,6 +232,59 @@ impl TypeChecker {
                 expr.ty = Some(ty);
             }
 
+            // Grid element access
+            ExprKind::GridIndex { name, row, col } => {
+                let ty = crate::compiler::expr::grid::check_grid_index(
+                    name,
+                    row.as_mut(),
+                    col.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
+                expr.ty = Some(ty);
+            }
+            ExprKind::GridAssign {
+                name,
+                row,
+                col,
+                value,
+            } => {
+                let ty = crate::compiler::expr::grid::check_grid_assign(
+                    name,
+                    row.as_mut(),
+                    col.as_mut(),
+                    value.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
+                expr.ty = Some(ty);
+            }
+
+            // Array element access
+            ExprKind::ArrayIndex { name, index } => {
+                let ty = crate::compiler::expr::array::check_array_index(
+                    name,
+                    index.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
+                expr.ty = Some(ty);
+            }
+            ExprKind::ArrayAssign { name, index, value } => {
+                let ty = crate::compiler::expr::array::check_array_assign(
+                    name,
+                    index.as_mut(),
+                    value.as_mut(),
+                    symbols,
+                    func_table,
+                    expr_span,
+                )?;
+                expr.ty = Some(ty);
+            }
+
             // Function call
             ExprKind::Call { name, args } => {
                 let (ty, expanded_expr) = crate::compiler::expr::call::check_call(
@@ -148,19 +301,45 @@ impl TypeChecker {
 
             // Vector constructors
             ExprKind::Vec2Constructor(args) => {
-                let ty = Self::check_vec_constructor(args, 2, symbols, func_table, expr_span)?;
+                let ty =
+                    Self::check_vec_constructor(args, 2, true, symbols, func_table, expr_span)?;
                 expr.ty = Some(ty);
             }
             ExprKind::Vec3Constructor(args) => {
-                let ty = Self::check_vec_constructor(args, 3, symbols, func_table, expr_span)?;
+                let ty =
+                    Self::check_vec_constructor(args, 3, true, symbols, func_table, expr_span)?;
                 expr.ty = Some(ty);
             }
             ExprKind::Vec4Constructor(args) => {
-                let ty = Self::check_vec_constructor(args, 4, symbols, func_table, expr_span)?;
+                let ty =
+                    Self::check_vec_constructor(args, 4, true, symbols, func_table, expr_span)?;
+                expr.ty = Some(ty);
+            }
+            ExprKind::BVec2Constructor(args) => {
+                let ty = Self::check_bvec_constructor(args, 2, symbols, func_table, expr_span)?;
+                expr.ty = Some(ty);
+            }
+            ExprKind::BVec3Constructor(args) => {
+                let ty = Self::check_bvec_constructor(args, 3, symbols, func_table, expr_span)?;
+                expr.ty = Some(ty);
+            }
+            ExprKind::BVec4Constructor(args) => {
+                let ty = Self::check_bvec_constructor(args, 4, symbols, func_table, expr_span)?;
                 expr.ty = Some(ty);
             }
+            ExprKind::Mat2Constructor(args) => {
+                // Same 4 components as a vec4, in column-major order
+                Self::check_vec_constructor(args, 4, true, symbols, func_table, expr_span)?;
+                expr.ty = Some(Type::Mat2);
+            }
             ExprKind::Mat3Constructor(args) => {
-                let ty = Self::check_vec_constructor(args, 9, symbols, func_table, expr_span)?;
+                let ty =
+                    Self::check_vec_constructor(args, 9, true, symbols, func_table, expr_span)?;
+                expr.ty = Some(ty);
+            }
+            ExprKind::Mat4Constructor(args) => {
+                let ty =
+                    Self::check_vec_constructor(args, 16, true, symbols, func_table, expr_span)?;
                 expr.ty = Some(ty);
             }
 
@@ -192,50 +371,163 @@ impl TypeChecker {
     // check_incdec - delegated to variable/variable_types.rs
     // check_binary_arithmetic - delegated to binary/binary_types.rs
 
+    /// `&`, `|`, `^`, `<<`, `>>`: both operands must be int
     fn check_bitwise_binary(
+        op: &str,
         left: &mut Expr,
         right: &mut Expr,
         symbols: &mut SymbolTable,
         func_table: &FunctionTable,
-        _span: crate::shared::Span,
+        span: crate::shared::Span,
     ) -> Result<Type, TypeError> {
         Self::infer_type(left, symbols, func_table)?;
         Self::infer_type(right, symbols, func_table)?;
+
+        let left_ty = left.ty.clone().unwrap_or(Type::Fixed);
+        let right_ty = right.ty.clone().unwrap_or(Type::Fixed);
+        if left_ty != Type::Int32 || right_ty != Type::Int32 {
+            return Err(TypeError {
+                kind: TypeErrorKind::InvalidOperation {
+                    op: op.into(),
+                    types: vec![left_ty, right_ty],
+                },
+                span,
+            });
+        }
         Ok(Type::Int32)
     }
 
+    /// `~`: operand must be int
     fn check_bitwise_not(
         operand: &mut Expr,
         symbols: &mut SymbolTable,
         func_table: &FunctionTable,
-        _span: crate::shared::Span,
+        span: crate::shared::Span,
     ) -> Result<Type, TypeError> {
         Self::infer_type(operand, symbols, func_table)?;
+
+        let operand_ty = operand.ty.clone().unwrap_or(Type::Fixed);
+        if operand_ty != Type::Int32 {
+            return Err(TypeError {
+                kind: TypeErrorKind::InvalidOperation {
+                    op: "~".into(),
+                    types: vec![operand_ty],
+                },
+                span,
+            });
+        }
         Ok(Type::Int32)
     }
 
+    /// `<`, `>`, `<=`, `>=`: vectors compare per component, giving a bvec
+    /// (`vec3(1.0) < v` is a `bvec3`)
     fn check_comparison(
         left: &mut Expr,
         right: &mut Expr,
         symbols: &mut SymbolTable,
         func_table: &FunctionTable,
+        span: crate::shared::Span,
+    ) -> Result<Type, TypeError> {
+        Self::infer_type(left, symbols, func_table)?;
+        Self::infer_type(right, symbols, func_table)?;
+
+        let left_ty = left.ty.clone().unwrap_or(Type::Fixed);
+        let right_ty = right.ty.clone().unwrap_or(Type::Fixed);
+        let is_vec = |ty: &Type| matches!(ty, Type::Vec2 | Type::Vec3 | Type::Vec4);
+        if !is_vec(&left_ty) && !is_vec(&right_ty) {
+            return Ok(Type::Bool);
+        }
+        if left_ty != right_ty {
+            return Err(TypeError {
+                kind: TypeErrorKind::Mismatch {
+                    expected: left_ty,
+                    found: right_ty,
+                },
+                span: right.span,
+            });
+        }
+        left_ty.to_bvec().ok_or_else(|| TypeError {
+            kind: TypeErrorKind::InvalidOperation {
+                op: "comparison".into(),
+                types: vec![left_ty.clone(), right_ty.clone()],
+            },
+            span,
+        })
+    }
+
+    /// `==` / `!=`: vector operands must both be the same vector type, and
+    /// matrices can't be compared
+    fn check_equality(
+        left: &mut Expr,
+        right: &mut Expr,
+        symbols: &mut SymbolTable,
+        func_table: &FunctionTable,
+        span: crate::shared::Span,
     ) -> Result<Type, TypeError> {
         Self::infer_type(left, symbols, func_table)?;
         Self::infer_type(right, symbols, func_table)?;
+
+        let left_ty = left.ty.clone().unwrap_or(Type::Fixed);
+        let right_ty = right.ty.clone().unwrap_or(Type::Fixed);
+        let is_mat = |ty: &Type| matches!(ty, Type::Mat2 | Type::Mat3 | Type::Mat4);
+        if is_mat(&left_ty) || is_mat(&right_ty) {
+            return Err(TypeError {
+                kind: TypeErrorKind::InvalidOperation {
+                    op: "equality".into(),
+                    types: vec![left_ty, right_ty],
+                },
+                span,
+            });
+        }
+        let is_vec = |ty: &Type| ty.to_bvec().is_some();
+        if (is_vec(&left_ty) || is_vec(&right_ty)) && left_ty != right_ty {
+            return Err(TypeError {
+                kind: TypeErrorKind::Mismatch {
+                    expected: left_ty,
+                    found: right_ty,
+                },
+                span: right.span,
+            });
+        }
         Ok(Type::Bool)
     }
 
+    /// `&&` / `||`: both operands must be single truth values
     fn check_logical(
+        op: &str,
         left: &mut Expr,
         right: &mut Expr,
         symbols: &mut SymbolTable,
         func_table: &FunctionTable,
+        span: crate::shared::Span,
     ) -> Result<Type, TypeError> {
         Self::infer_type(left, symbols, func_table)?;
         Self::infer_type(right, symbols, func_table)?;
+        Self::check_truth_value(op, left, span)?;
+        Self::check_truth_value(op, right, span)?;
         Ok(Type::Bool)
     }
 
+    /// A `bool`, or a number that's true when non-zero; a bvec has to be
+    /// reduced with `any` or `all` first
+    fn check_truth_value(
+        op: &str,
+        operand: &Expr,
+        span: crate::shared::Span,
+    ) -> Result<(), TypeError> {
+        let ty = operand.ty.clone().unwrap_or(Type::Fixed);
+        if matches!(ty, Type::Bool | Type::Fixed | Type::Int32) {
+            return Ok(());
+        }
+        Err(TypeError {
+            kind: TypeErrorKind::InvalidOperation {
+                op: op.into(),
+                types: vec![ty],
+            },
+            span,
+        })
+    }
+
     fn check_ternary(
         condition: &mut Expr,
         true_expr: &mut Expr,
@@ -261,6 +553,47 @@ impl TypeChecker {
         Self::infer_type(value, symbols, func_table)?;
         let value_ty = value.ty.clone().unwrap_or(Type::Fixed);
 
+        if symbols.lookup_const(target).is_some()
+            || crate::compiler::expr::variable::visible_constant(target, symbols).is_some()
+        {
+            return Err(TypeError {
+                kind: TypeErrorKind::ConstAssignment(target.to_string()),
+                span: value.span,
+            });
+        }
+        if symbols.is_external(target) {
+            return Err(TypeError {
+                kind: TypeErrorKind::ExternalAssignment(target.to_string()),
+                span: value.span,
+            });
+        }
+
+        // Grids and arrays are assigned element-wise
+        match symbols.lookup(target) {
+            Some(Type::Grid { .. }) => {
+                return Err(TypeError {
+                    kind: TypeErrorKind::UnindexedGrid(target.to_string()),
+                    span: value.span,
+                })
+            }
+            Some(Type::Array { .. }) => {
+                return Err(TypeError {
+                    kind: TypeErrorKind::UnindexedArray(target.to_string()),
+                    span: value.span,
+                })
+            }
+            Some(ty) if ty.is_bool() != value_ty.is_bool() => {
+                return Err(TypeError {
+                    kind: TypeErrorKind::Mismatch {
+                        expected: ty,
+                        found: value_ty,
+                    },
+                    span: value.span,
+                })
+            }
+            _ => {}
+        }
+
         // Update symbol table
         symbols.set(target.to_string(), value_ty.clone());
         Ok(value_ty)
@@ -268,27 +601,104 @@ impl TypeChecker {
 
     // check_call - delegated to call/call_types.rs
 
+    /// Arguments must supply exactly `dim` components, or with `splat` a
+    /// single scalar fills every component of a vector (`vec3(0.5)`) or the
+    /// diagonal of a matrix (`mat3(1.0)`)
+    ///
+    /// A mis-sized constructor would leave the wrong number of values on the
+    /// stack for whatever consumes it, e.g. a `return`.
     fn check_vec_constructor(
         args: &mut [Expr],
-        _dim: usize,
+        dim: usize,
+        splat: bool,
         symbols: &mut SymbolTable,
         func_table: &FunctionTable,
-        _span: crate::shared::Span,
+        span: crate::shared::Span,
     ) -> Result<Type, TypeError> {
         for arg in args.iter_mut() {
             Self::infer_type(arg, symbols, func_table)?;
+            if let Some(ty) = arg.ty.clone().filter(Type::is_bool) {
+                return Err(TypeError {
+                    kind: TypeErrorKind::Mismatch {
+                        expected: Type::Fixed,
+                        found: ty,
+                    },
+                    span: arg.span,
+                });
+            }
+        }
+
+        let components: usize = args
+            .iter()
+            .map(|arg| arg.ty.as_ref().map_or(0, Type::size_in_i32s))
+            .sum();
+        let is_splat = splat && args.len() == 1 && components == 1;
+        if components != dim && !is_splat {
+            return Err(TypeError {
+                kind: TypeErrorKind::InvalidArgumentCount {
+                    expected: dim,
+                    found: components,
+                },
+                span,
+            });
         }
 
         // Return appropriate vector type based on dimension
-        Ok(match _dim {
+        Ok(match dim {
             2 => Type::Vec2,
             3 => Type::Vec3,
             4 => Type::Vec4,
             9 => Type::Mat3,
+            16 => Type::Mat4,
             _ => Type::Fixed,
         })
     }
 
+    /// Arguments must be bools or bvecs supplying exactly `dim` components,
+    /// or a single bool filling every component (`bvec3(true)`)
+    fn check_bvec_constructor(
+        args: &mut [Expr],
+        dim: usize,
+        symbols: &mut SymbolTable,
+        func_table: &FunctionTable,
+        span: crate::shared::Span,
+    ) -> Result<Type, TypeError> {
+        for arg in args.iter_mut() {
+            Self::infer_type(arg, symbols, func_table)?;
+            let ty = arg.ty.clone().unwrap_or(Type::Fixed);
+            if !matches!(ty, Type::Bool | Type::BVec2 | Type::BVec3 | Type::BVec4) {
+                return Err(TypeError {
+                    kind: TypeErrorKind::Mismatch {
+                        expected: Type::Bool,
+                        found: ty,
+                    },
+                    span: arg.span,
+                });
+            }
+        }
+
+        let components: usize = args
+            .iter()
+            .map(|arg| arg.ty.as_ref().map_or(0, Type::size_in_i32s))
+            .sum();
+        let is_splat = args.len() == 1 && components == 1;
+        if components != dim && !is_splat {
+            return Err(TypeError {
+                kind: TypeErrorKind::InvalidArgumentCount {
+                    expected: dim,
+                    found: components,
+                },
+                span,
+            });
+        }
+
+        Ok(match dim {
+            2 => Type::BVec2,
+            3 => Type::BVec3,
+            _ => Type::BVec4,
+        })
+    }
+
     fn check_swizzle(
         swizzle_expr: &mut Expr,
         components: &str,
@@ -302,9 +712,9 @@ impl TypeChecker {
 
         // Validate that base is a vector
         let base_size = match base_ty {
-            Type::Vec2 => 2,
-            Type::Vec3 => 3,
-            Type::Vec4 => 4,
+            Type::Vec2 | Type::BVec2 => 2,
+            Type::Vec3 | Type::BVec3 => 3,
+            Type::Vec4 | Type::BVec4 => 4,
             _ => {
                 return Err(TypeError {
                     kind: TypeErrorKind::InvalidOperation {
@@ -351,8 +761,13 @@ impl TypeChecker {
             }
         }
 
-        // Result type based on component count
+        // Result type based on component count, bools for a bvec
+        let is_bvec = matches!(base_ty, Type::BVec2 | Type::BVec3 | Type::BVec4);
         Ok(match components.len() {
+            1 if is_bvec => Type::Bool,
+            2 if is_bvec => Type::BVec2,
+            3 if is_bvec => Type::BVec3,
+            4 if is_bvec => Type::BVec4,
             1 => Type::Fixed,
             2 => Type::Vec2,
             3 => Type::Vec3,
@@ -378,7 +793,14 @@ fn type_to_string(ty: &Type) -> &str {
         Type::Vec2 => "vec2",
         Type::Vec3 => "vec3",
         Type::Vec4 => "vec4",
+        Type::BVec2 => "bvec2",
+        Type::BVec3 => "bvec3",
+        Type::BVec4 => "bvec4",
+        Type::Mat2 => "mat2",
         Type::Mat3 => "mat3",
+        Type::Mat4 => "mat4",
+        Type::Grid { .. } => "grid",
+        Type::Array { .. } => "array",
         Type::Void => "void",
     }
 }
@@ -0,0 +1,45 @@
+/// Grid element access code generation
+extern crate alloc;
+
+use crate::compiler::ast::Expr;
+use crate::compiler::codegen::CodeGenerator;
+use crate::shared::Type;
+use crate::vm::opcodes::LpsOpCode;
+
+impl<'a> CodeGenerator<'a> {
+    pub(crate) fn gen_grid_index(&mut self, name: &str, row: &Expr, col: &Expr) {
+        if let Some(local_idx) = self.gen_grid_elem(name, row, col) {
+            self.code.push(LpsOpCode::LoadLocalElemFixed(local_idx));
+        }
+    }
+
+    pub(crate) fn gen_grid_assign(&mut self, name: &str, row: &Expr, col: &Expr, value: &Expr) {
+        self.gen_expr(value);
+        // Assignment returns the assigned value
+        self.code.push(LpsOpCode::Dup1);
+
+        if let Some(local_idx) = self.gen_grid_elem(name, row, col) {
+            self.code.push(LpsOpCode::StoreLocalElemFixed(local_idx));
+        }
+    }
+
+    /// Push the flat element index `row * cols + col`, checking each subscript
+    /// against its own dimension so `grid[0][cols]` can't reach row 1
+    fn gen_grid_elem(&mut self, name: &str, row: &Expr, col: &Expr) -> Option<u32> {
+        let local_idx = self.locals.get(name)?;
+        let (rows, cols) = match self.locals.get_type(local_idx) {
+            Some(Type::Grid { rows, cols }) => (*rows, *cols),
+            _ => return None,
+        };
+
+        self.gen_expr(row);
+        self.code.push(LpsOpCode::CheckIndex(rows));
+        self.code.push(LpsOpCode::PushInt32(cols as i32));
+        self.code.push(LpsOpCode::MulInt32);
+        self.gen_expr(col);
+        self.code.push(LpsOpCode::CheckIndex(cols));
+        self.code.push(LpsOpCode::AddInt32);
+
+        Some(local_idx)
+    }
+}
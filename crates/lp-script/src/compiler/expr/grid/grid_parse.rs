@@ -0,0 +1,80 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+
+/// Grid and array element access parsing
+use crate::compiler::ast::{Expr, ExprKind};
+use crate::compiler::error::{ParseError, ParseErrorKind};
+use crate::compiler::lexer::TokenKind;
+use crate::compiler::parser::Parser;
+use crate::shared::Span;
+
+impl Parser {
+    // Index: name[index] for an array, name[row][col] for a grid
+    pub(crate) fn parse_index(&mut self, name: String, start: usize) -> Result<Expr, ParseError> {
+        let (index, end) = self.parse_subscript()?;
+        if !matches!(self.current().kind, TokenKind::LBracket) {
+            return Ok(Expr::new(
+                ExprKind::ArrayIndex {
+                    name,
+                    index: Box::new(index),
+                },
+                Span::new(start, end),
+            ));
+        }
+        let row = index;
+        let (col, end) = self.parse_subscript()?;
+
+        Ok(Expr::new(
+            ExprKind::GridIndex {
+                name,
+                row: Box::new(row),
+                col: Box::new(col),
+            },
+            Span::new(start, end),
+        ))
+    }
+
+    // `++`/`--` on an element: `name[i] = name[i] + 1.0` (or `-`), with the
+    // subscripts evaluated twice as for compound assignment. The assignment
+    // yields the new value; any other target is returned unchanged.
+    pub(crate) fn element_step(
+        target: Expr,
+        step: fn(Box<Expr>, Box<Expr>) -> ExprKind,
+        span: Span,
+    ) -> Expr {
+        let one = Box::new(Expr::new(ExprKind::Number(1.0), span));
+        let kind = match target.kind.clone() {
+            ExprKind::ArrayIndex { name, index } => ExprKind::ArrayAssign {
+                name,
+                index,
+                value: Box::new(Expr::new(step(Box::new(target), one), span)),
+            },
+            ExprKind::GridIndex { name, row, col } => ExprKind::GridAssign {
+                name,
+                row,
+                col,
+                value: Box::new(Expr::new(step(Box::new(target), one), span)),
+            },
+            _ => return target,
+        };
+        Expr::new(kind, span)
+    }
+
+    // Subscript: '[' expr ']', returning the index and the end of ']'
+    fn parse_subscript(&mut self) -> Result<(Expr, usize), ParseError> {
+        self.advance(); // consume '['
+        let index = self.parse_assignment_expr()?;
+        let end = self.current().span.end;
+        if !self.expect(TokenKind::RBracket) {
+            return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedToken {
+                    expected: String::from("]"),
+                    found: format!("{:?}", self.current().kind),
+                },
+                span: self.current().span,
+            });
+        }
+        Ok((index, end))
+    }
+}
@@ -0,0 +1,183 @@
+/// Grid local tests
+#[cfg(test)]
+mod tests {
+    use crate::compiler::error::{CompileError, TypeErrorKind};
+    use crate::compiler::stmt::stmt_test_util::ScriptTest;
+    use crate::fixed::ToFixed;
+    use crate::vm::error::LpsVmError;
+    use crate::vm::lps_vm::LpsVm;
+    use crate::vm::opcodes::LpsOpCode;
+    use crate::vm::vm_limits::VmLimits;
+    use crate::{compile_script_with_options, OptimizeOptions};
+
+    #[test]
+    fn test_grid_store_and_load() -> Result<(), String> {
+        ScriptTest::new("float[2][3] g; g[1][2] = 4.0; return g[1][2] + g[0][0];")
+            .expect_opcodes(vec![
+                // g[1][2] = 4.0
+                LpsOpCode::Push(4.0.to_fixed()),
+                LpsOpCode::Dup1,
+                LpsOpCode::PushInt32(1),
+                LpsOpCode::CheckIndex(2),
+                LpsOpCode::PushInt32(3),
+                LpsOpCode::MulInt32,
+                LpsOpCode::PushInt32(2),
+                LpsOpCode::CheckIndex(3),
+                LpsOpCode::AddInt32,
+                LpsOpCode::StoreLocalElemFixed(0),
+                LpsOpCode::Drop1,
+                // g[1][2]
+                LpsOpCode::PushInt32(1),
+                LpsOpCode::CheckIndex(2),
+                LpsOpCode::PushInt32(3),
+                LpsOpCode::MulInt32,
+                LpsOpCode::PushInt32(2),
+                LpsOpCode::CheckIndex(3),
+                LpsOpCode::AddInt32,
+                LpsOpCode::LoadLocalElemFixed(0),
+                // g[0][0]
+                LpsOpCode::PushInt32(0),
+                LpsOpCode::CheckIndex(2),
+                LpsOpCode::PushInt32(3),
+                LpsOpCode::MulInt32,
+                LpsOpCode::PushInt32(0),
+                LpsOpCode::CheckIndex(3),
+                LpsOpCode::AddInt32,
+                LpsOpCode::LoadLocalElemFixed(0),
+                LpsOpCode::AddFixed,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(4.0)
+            .run()
+    }
+
+    #[test]
+    fn test_grid_compound_assignment() -> Result<(), String> {
+        ScriptTest::new(
+            "float[2][2] g; \
+             for (int i = 0; i < 4; i++) { g[i / 2][i % 2] += 1.5; g[1][1] += 1.0; } \
+             return g[0][0] + g[0][1] + g[1][0] + g[1][1];",
+        )
+        .expect_result_fixed(10.0)
+        .run()
+    }
+
+    #[test]
+    fn test_grid_element_increment_and_decrement() -> Result<(), String> {
+        ScriptTest::new(
+            "float[2][3] g; g[0][1] = 1.0; \
+             g[0][1]++; float old = g[0][1]--; ++g[1][2]; \
+             return old * 100.0 + g[0][1] * 10.0 + g[1][2];",
+        )
+        .expect_result_fixed(211.0)
+        .run()
+    }
+
+    #[test]
+    fn test_grid_sized_by_const() -> Result<(), String> {
+        ScriptTest::new(
+            "const int H = 2; const int W = H + 1; \
+             float g[H][W]; g[H - 1][W - 1] = 3.0; \
+             return g[1][2];",
+        )
+        .expect_result_fixed(3.0)
+        .run()
+    }
+
+    #[test]
+    fn test_game_of_life_step() -> Result<(), String> {
+        // A horizontal blinker in row 1 turns vertical around (1, 1)
+        ScriptTest::new(
+            "float[4][4] cur; \
+             float[4][4] next; \
+             cur[1][0] = 1.0; cur[1][1] = 1.0; cur[1][2] = 1.0; \
+             for (int y = 0; y < 4; y++) { \
+                 for (int x = 0; x < 4; x++) { \
+                     float n = 0.0; \
+                     for (int dy = -1; dy <= 1; dy++) { \
+                         for (int dx = -1; dx <= 1; dx++) { \
+                             int ny = y + dy; \
+                             int nx = x + dx; \
+                             if (ny >= 0 && ny < 4 && nx >= 0 && nx < 4) { \
+                                 if (dx != 0 || dy != 0) { n += cur[ny][nx]; } \
+                             } \
+                         } \
+                     } \
+                     next[y][x] = (n == 3.0 || (cur[y][x] == 1.0 && n == 2.0)) ? 1.0 : 0.0; \
+                 } \
+             } \
+             float alive = 0.0; \
+             float checksum = 0.0; \
+             for (int y = 0; y < 4; y++) { \
+                 for (int x = 0; x < 4; x++) { \
+                     alive += next[y][x]; \
+                     checksum += next[y][x] * float(y * 4 + x); \
+                 } \
+             } \
+             return alive * 100.0 + checksum;",
+        )
+        // 3 live cells at (0, 1), (1, 1), (2, 1): 1 + 5 + 9
+        .expect_result_fixed(315.0)
+        .run()
+    }
+
+    #[test]
+    fn test_grid_bounds_checked_per_dimension() {
+        // g[0][2] is flat element 2, inside the storage but past the row
+        let program = compile_script_with_options(
+            "float[2][2] g; int i = 2; return g[0][i];",
+            &OptimizeOptions::none(),
+        )
+        .unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let err = vm
+            .run_scalar(0.0.to_fixed(), 0.0.to_fixed(), 0.0.to_fixed())
+            .unwrap_err();
+        assert!(matches!(
+            err.error,
+            LpsVmError::InvalidArrayIndex {
+                index: 2,
+                array_size: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn test_grid_index_must_be_int() {
+        let result = compile_script_with_options(
+            "float[2][2] g; return g[0][1.0];",
+            &OptimizeOptions::none(),
+        );
+        match result {
+            Err(CompileError::TypeCheck(err)) => {
+                assert!(matches!(err.kind, TypeErrorKind::Mismatch { .. }))
+            }
+            _ => panic!("Expected TypeCheck error"),
+        }
+    }
+
+    #[test]
+    fn test_grid_requires_both_subscripts() {
+        // A single subscript parses as array indexing
+        let result =
+            compile_script_with_options("float[2][2] g; return g[0];", &OptimizeOptions::none());
+        match result {
+            Err(CompileError::TypeCheck(err)) => {
+                assert!(matches!(err.kind, TypeErrorKind::UnindexedGrid(_)))
+            }
+            _ => panic!("Expected TypeCheck error"),
+        }
+    }
+
+    #[test]
+    fn test_grid_used_as_value() {
+        let result =
+            compile_script_with_options("float[2][2] g; return g;", &OptimizeOptions::none());
+        match result {
+            Err(CompileError::TypeCheck(err)) => {
+                assert!(matches!(err.kind, TypeErrorKind::UnindexedGrid(_)))
+            }
+            _ => panic!("Expected TypeCheck error"),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+/// Grid local module
+///
+/// Groups parsing, code generation, type checking, and tests for `float[H][W]`
+/// grid element access (`grid[y][x]`, `grid[y][x] = v`).
+mod grid_gen;
+mod grid_parse;
+mod grid_types;
+pub(in crate::compiler) use grid_types::{check_grid_assign, check_grid_index};
+
+#[cfg(test)]
+mod grid_tests;
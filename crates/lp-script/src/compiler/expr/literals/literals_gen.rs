@@ -2,7 +2,7 @@
 extern crate alloc;
 
 use crate::compiler::codegen::CodeGenerator;
-use crate::fixed::ToFixed;
+use crate::fixed::{Fixed, ToFixed};
 use crate::vm::opcodes::LpsOpCode;
 
 impl<'a> CodeGenerator<'a> {
@@ -15,4 +15,10 @@ impl<'a> CodeGenerator<'a> {
         // The VM will convert to Fixed when needed, but this preserves integer semantics
         self.code.push(LpsOpCode::PushInt32(n));
     }
+
+    pub(crate) fn gen_bool(&mut self, b: bool) {
+        // Same 1.0 / 0.0 that comparisons produce
+        self.code
+            .push(LpsOpCode::Push(if b { Fixed::ONE } else { Fixed::ZERO }));
+    }
 }
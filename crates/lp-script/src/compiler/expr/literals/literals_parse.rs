@@ -1,8 +1,10 @@
 use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
 
 /// Literal parsing (numbers, parenthesized expressions)
 use crate::compiler::ast::{Expr, ExprKind};
-use crate::compiler::error::ParseError;
+use crate::compiler::error::{ParseError, ParseErrorKind};
 use crate::compiler::lexer::TokenKind;
 use crate::compiler::parser::Parser;
 use crate::shared::Span;
@@ -16,15 +18,22 @@ impl Parser {
         let result = match &token.kind {
             TokenKind::PlusPlus => {
                 self.advance();
-                // Prefix increment must be followed by a variable
+                // Prefix increment must be followed by a variable or element
                 if let TokenKind::Ident(name) = &self.current().kind {
                     let name = name.clone();
+                    let start = self.current().span.start;
                     let end = self.current().span.end;
                     self.advance();
-                    Ok(Expr::new(
-                        ExprKind::PreIncrement(name),
-                        Span::new(token.span.start, end),
-                    ))
+                    if matches!(self.current().kind, TokenKind::LBracket) {
+                        let target = self.parse_index(name, start)?;
+                        let span = Span::new(token.span.start, target.span.end);
+                        Ok(Self::element_step(target, ExprKind::Add, span))
+                    } else {
+                        Ok(Expr::new(
+                            ExprKind::PreIncrement(name),
+                            Span::new(token.span.start, end),
+                        ))
+                    }
                 } else {
                     // Error: prefix increment requires an l-value
                     // For now, create a dummy expression (will be caught by type checker)
@@ -36,15 +45,22 @@ impl Parser {
             }
             TokenKind::MinusMinus => {
                 self.advance();
-                // Prefix decrement must be followed by a variable
+                // Prefix decrement must be followed by a variable or element
                 if let TokenKind::Ident(name) = &self.current().kind {
                     let name = name.clone();
+                    let start = self.current().span.start;
                     let end = self.current().span.end;
                     self.advance();
-                    Ok(Expr::new(
-                        ExprKind::PreDecrement(name),
-                        Span::new(token.span.start, end),
-                    ))
+                    if matches!(self.current().kind, TokenKind::LBracket) {
+                        let target = self.parse_index(name, start)?;
+                        let span = Span::new(token.span.start, target.span.end);
+                        Ok(Self::element_step(target, ExprKind::Sub, span))
+                    } else {
+                        Ok(Expr::new(
+                            ExprKind::PreDecrement(name),
+                            Span::new(token.span.start, end),
+                        ))
+                    }
                 } else {
                     // If followed by a number, parse as double negation: --3.0 = -(-3.0)
                     // Otherwise, it's an error (prefix decrement requires l-value)
@@ -107,6 +123,10 @@ impl Parser {
                 self.advance();
                 Ok(Expr::new(ExprKind::IntNumber(*n), token.span))
             }
+            TokenKind::BoolLiteral(b) => {
+                self.advance();
+                Ok(Expr::new(ExprKind::Bool(*b), token.span))
+            }
             TokenKind::LParen => {
                 self.advance(); // consume '('
                 let expr = self.parse_assignment_expr()?;
@@ -115,14 +135,26 @@ impl Parser {
                 }
                 Ok(expr)
             }
-            TokenKind::Vec2 | TokenKind::Vec3 | TokenKind::Vec4 | TokenKind::Mat3 => {
-                self.parse_vec_constructor()
-            }
+            TokenKind::Vec2
+            | TokenKind::Vec3
+            | TokenKind::Vec4
+            | TokenKind::BVec2
+            | TokenKind::BVec3
+            | TokenKind::BVec4
+            | TokenKind::Mat2
+            | TokenKind::Mat3
+            | TokenKind::Mat4 => self.parse_vec_constructor(),
+            TokenKind::Float | TokenKind::Int => self.parse_cast(),
             TokenKind::Ident(_) => self.parse_ident(),
-            _ => {
-                // Error fallback
-                Ok(Expr::new(ExprKind::Number(0.0), token.span))
-            }
+            // Erroring rather than inventing a value keeps statement loops
+            // from spinning on a token nothing consumes, e.g. a stray ')'
+            _ => Err(ParseError {
+                kind: ParseErrorKind::UnexpectedToken {
+                    expected: String::from("expression"),
+                    found: format!("{:?}", token.kind),
+                },
+                span: token.span,
+            }),
         };
 
         self.exit_recursion();
@@ -88,4 +88,18 @@ mod tests {
     fn test_int_number_typecheck() -> Result<(), String> {
         ExprTest::new("42").expect_result_int(42).run()
     }
+
+    #[test]
+    fn test_bool_literals() -> Result<(), String> {
+        // Bools are the same 1.0 / 0.0 that comparisons produce
+        ExprTest::new("true")
+            .expect_opcodes(vec![LpsOpCode::Push(1.0.to_fixed()), LpsOpCode::Return])
+            .expect_result_bool(true)
+            .run()?;
+
+        ExprTest::new("false")
+            .expect_opcodes(vec![LpsOpCode::Push(0.0.to_fixed()), LpsOpCode::Return])
+            .expect_result_bool(false)
+            .run()
+    }
 }
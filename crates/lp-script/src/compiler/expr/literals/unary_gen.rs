@@ -14,8 +14,9 @@ impl<'a> CodeGenerator<'a> {
             Type::Int32 => LpsOpCode::NegInt32,
             Type::Vec2 => LpsOpCode::NegVec2,
             Type::Vec3 => LpsOpCode::NegVec3,
-            Type::Vec4 => LpsOpCode::NegVec4,
+            Type::Vec4 | Type::Mat2 => LpsOpCode::NegVec4,
             Type::Mat3 => LpsOpCode::NegMat3,
+            Type::Mat4 => LpsOpCode::NegMat4,
             _ => LpsOpCode::NegFixed,
         });
     }
@@ -1,6 +1,7 @@
 /// Logical operator tests
 #[cfg(test)]
 mod tests {
+    use crate::compiler::error::{CompileError, TypeErrorKind};
     use crate::compiler::expr::expr_test_util::ExprTest;
     use crate::fixed::ToFixed;
     use crate::vm::opcodes::LpsOpCode;
@@ -66,4 +67,38 @@ mod tests {
             .expect_result_bool(true)
             .run()
     }
+
+    #[test]
+    fn test_logical_with_bool_literals() -> Result<(), String> {
+        ExprTest::new("true && !false")
+            .expect_result_bool(true)
+            .run()?;
+
+        ExprTest::new("false || x > 0.5")
+            .with_x(0.25)
+            .expect_result_bool(false)
+            .run()
+    }
+
+    #[test]
+    fn test_logical_rejects_vectors() {
+        for (src, op) in [
+            ("bvec2(true, false) && true", "&&"),
+            ("false || vec3(1.0)", "||"),
+            ("!vec2(1.0, 0.0)", "!"),
+        ] {
+            match crate::compile_expr(src) {
+                Err(CompileError::TypeCheck(err)) => assert!(
+                    matches!(err.kind, TypeErrorKind::InvalidOperation { op: ref o, .. } if o == op),
+                    "{}: {:?}",
+                    src,
+                    err.kind
+                ),
+                other => panic!(
+                    "{}: expected an invalid operation error, got {:?}",
+                    src, other
+                ),
+            }
+        }
+    }
 }
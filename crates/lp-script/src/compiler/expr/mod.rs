@@ -1,4 +1,5 @@
 /// Expression compilation modules grouped by feature
+pub mod array;
 pub mod assign_expr;
 pub mod binary;
 pub mod bitwise;
@@ -7,6 +8,7 @@ pub mod compare;
 pub mod constructors;
 pub mod expr_test_util;
 pub(crate) mod expr_types;
+pub mod grid;
 pub mod incdec;
 pub mod literals;
 pub mod logical;
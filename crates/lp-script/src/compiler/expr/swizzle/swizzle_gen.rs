@@ -15,24 +15,28 @@ impl<'a> CodeGenerator<'a> {
         // Generate swizzle opcodes based on component string
         let source_type = expr.ty.as_ref().unwrap();
         let source_size = match source_type {
-            Type::Vec2 => 2,
-            Type::Vec3 => 3,
-            Type::Vec4 => 4,
+            Type::Vec2 | Type::BVec2 => 2,
+            Type::Vec3 | Type::BVec3 => 3,
+            Type::Vec4 | Type::BVec4 => 4,
             _ => 1,
         };
 
         // Call the helper function
+        let start = self.code.len();
         gen_swizzle_opcodes(components, source_size, self.code);
+        debug_assert_eq!(
+            swizzle_result(&self.code[start..], source_size),
+            Some(swizzle_indices(components)),
+            "swizzle .{} of a {}-component value",
+            components,
+            source_size
+        );
     }
 }
 
-/// Generate opcodes for swizzling
-/// Stack layout: components are pushed in order, so for vec2(x,y), stack is [x, y] with y on top
-fn gen_swizzle_opcodes(components: &str, source_size: usize, code: &mut Vec<LpsOpCode>) {
-    use alloc::vec::Vec as AllocVec;
-
-    // Map component characters to indices
-    let indices: AllocVec<usize> = components
+/// Source component index of each swizzle character
+fn swizzle_indices(components: &str) -> Vec<usize> {
+    components
         .chars()
         .map(|c| match c {
             'x' | 'r' | 's' => 0,
@@ -41,7 +45,42 @@ fn gen_swizzle_opcodes(components: &str, source_size: usize, code: &mut Vec<LpsO
             'w' | 'a' | 'q' => 3,
             _ => unreachable!("Type checker should validate swizzle components"),
         })
-        .collect();
+        .collect()
+}
+
+/// Run swizzle opcodes over a stack holding only the source components
+///
+/// Returns which source component ends up in each remaining slot (bottom
+/// first), or `None` if the code reads past the source or uses an opcode a
+/// swizzle shouldn't. Codegen checks every swizzle against this, so a chain
+/// like `v.xy.yx` can't leave stray values on the stack.
+pub(super) fn swizzle_result(code: &[LpsOpCode], source_size: usize) -> Option<Vec<usize>> {
+    let mut stack: Vec<usize> = (0..source_size).collect();
+    for op in code {
+        let (pops, picks): (usize, &[u8]) = match op {
+            LpsOpCode::Drop1 => (1, &[]),
+            LpsOpCode::Dup1 => (1, &[0, 0]),
+            LpsOpCode::Dup2 => (2, &[0, 1, 0, 1]),
+            LpsOpCode::Swap => (2, &[1, 0]),
+            LpsOpCode::Swizzle3to2(a, b) => (3, &[*a, *b]),
+            LpsOpCode::Swizzle3to3(a, b, c) => (3, &[*a, *b, *c]),
+            LpsOpCode::Swizzle4to2(a, b) => (4, &[*a, *b]),
+            LpsOpCode::Swizzle4to3(a, b, c) => (4, &[*a, *b, *c]),
+            LpsOpCode::Swizzle4to4(a, b, c, d) => (4, &[*a, *b, *c, *d]),
+            _ => return None,
+        };
+        let popped = stack.split_off(stack.len().checked_sub(pops)?);
+        for &pick in picks {
+            stack.push(*popped.get(pick as usize)?);
+        }
+    }
+    Some(stack)
+}
+
+/// Generate opcodes for swizzling
+/// Stack layout: components are pushed in order, so for vec2(x,y), stack is [x, y] with y on top
+pub(super) fn gen_swizzle_opcodes(components: &str, source_size: usize, code: &mut Vec<LpsOpCode>) {
+    let indices = swizzle_indices(components);
 
     // Strategy: Pop all source components into temporary positions,
     // then push back the desired components in the right order
@@ -75,7 +114,7 @@ fn gen_swizzle_opcodes(components: &str, source_size: usize, code: &mut Vec<LpsO
         }
 
         // For vec2 specifically, handle common cases efficiently
-        if source_size == 2 {
+        if source_size == 2 && components.len() == 2 {
             match components {
                 "yx" | "gr" | "ts" => code.push(LpsOpCode::Swap),
                 "xx" | "rr" | "ss" => {
@@ -89,11 +128,16 @@ fn gen_swizzle_opcodes(components: &str, source_size: usize, code: &mut Vec<LpsO
                     code.push(LpsOpCode::Drop1); // [y]
                     code.push(LpsOpCode::Dup1); // [y, y]
                 }
-                _ => {
-                    // General case for vec2: Handle by reconstruction
-                    // This is rare but possible (e.g., if type checker allows it)
-                }
+                _ => unreachable!("every other vec2 -> vec2 swizzle is the identity"),
             }
+        } else if source_size == 2 {
+            // vec2 -> vec3/vec4: [x, y] -> [x, y, x, y], then pick from that
+            code.push(LpsOpCode::Dup2);
+            gen_swizzle_opcodes(components, 4, code);
+        } else if components.len() == 4 && source_size == 3 {
+            // vec3 -> vec4: pad to [x, y, z, z]; `w` never indexes the padding
+            code.push(LpsOpCode::Dup1);
+            gen_swizzle_opcodes(components, 4, code);
         } else if components.len() == 2 && source_size == 3 {
             // vec3 -> vec2 swizzle
             code.push(LpsOpCode::Swizzle3to2(indices[0] as u8, indices[1] as u8));
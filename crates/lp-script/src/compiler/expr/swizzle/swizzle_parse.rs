@@ -8,7 +8,9 @@ use crate::compiler::parser::Parser;
 use crate::shared::Span;
 
 impl Parser {
-    // Postfix: swizzle (.xyzw, .rgba, .stpq), postfix increment/decrement (++, --)
+    // Postfix: swizzle (.xyzw, .rgba, .stpq), struct member (.name),
+    // array and grid index ([i], [y][x]),
+    // postfix increment/decrement (++, --)
     pub(crate) fn postfix(&mut self) -> Result<Expr, ParseError> {
         self.enter_recursion()?;
         let mut expr = self.primary()?;
@@ -25,50 +27,80 @@ impl Parser {
                         let end = self.current().span.end;
                         self.advance();
 
-                        expr = Expr::new(
-                            ExprKind::Swizzle {
-                                expr: Box::new(expr),
-                                components,
-                            },
-                            Span::new(start, end),
-                        );
+                        // A struct member is its own local (see struct_decl)
+                        let member = match &expr.kind {
+                            ExprKind::Variable(name) => {
+                                self.struct_member(name, &components, Span::new(start, end))?
+                            }
+                            _ => None,
+                        };
+                        expr = match member {
+                            Some(local) => {
+                                Expr::new(ExprKind::Variable(local), Span::new(start, end))
+                            }
+                            None => {
+                                self.check_not_struct_value(&expr)?;
+                                Expr::new(
+                                    ExprKind::Swizzle {
+                                        expr: Box::new(expr),
+                                        components,
+                                    },
+                                    Span::new(start, end),
+                                )
+                            }
+                        };
                     } else {
                         // Invalid swizzle, just break
                         break;
                     }
                 }
-                TokenKind::PlusPlus => {
-                    // Postfix increment: var++
-                    // Only works on variables (l-values)
+                TokenKind::LBracket => {
+                    // Array index array[i] or grid index grid[y][x]
+                    // Only works on variables (array and grid locals)
                     if let ExprKind::Variable(name) = &expr.kind {
                         let name = name.clone();
-                        let start = expr.span.start;
-                        let end = self.current().span.end;
-                        self.advance();
-                        expr = Expr::new(ExprKind::PostIncrement(name), Span::new(start, end));
+                        expr = self.parse_index(name, expr.span.start)?;
                     } else {
-                        // Not an l-value, break (will be caught by type checker)
                         break;
                     }
                 }
-                TokenKind::MinusMinus => {
-                    // Postfix decrement: var--
-                    // Only works on variables (l-values)
-                    if let ExprKind::Variable(name) = &expr.kind {
-                        let name = name.clone();
-                        let start = expr.span.start;
-                        let end = self.current().span.end;
-                        self.advance();
-                        expr = Expr::new(ExprKind::PostDecrement(name), Span::new(start, end));
-                    } else {
+                TokenKind::PlusPlus | TokenKind::MinusMinus => {
+                    // Postfix increment/decrement: var++, a[i]--, g[y][x]++
+                    // Only works on variables and elements (l-values)
+                    let increment = matches!(self.current().kind, TokenKind::PlusPlus);
+                    let span = Span::new(expr.span.start, self.current().span.end);
+                    match expr.kind {
+                        ExprKind::Variable(ref name) => {
+                            let name = name.clone();
+                            self.advance();
+                            let kind = if increment {
+                                ExprKind::PostIncrement(name)
+                            } else {
+                                ExprKind::PostDecrement(name)
+                            };
+                            expr = Expr::new(kind, span);
+                        }
+                        // Yields the old value by undoing the step on the new one
+                        ExprKind::ArrayIndex { .. } | ExprKind::GridIndex { .. } => {
+                            self.advance();
+                            let (step, undo): (fn(_, _) -> _, fn(_, _) -> _) = if increment {
+                                (ExprKind::Add, ExprKind::Sub)
+                            } else {
+                                (ExprKind::Sub, ExprKind::Add)
+                            };
+                            let assign = Self::element_step(expr, step, span);
+                            let one = Expr::new(ExprKind::Number(1.0), span);
+                            expr = Expr::new(undo(Box::new(assign), Box::new(one)), span);
+                        }
                         // Not an l-value, break (will be caught by type checker)
-                        break;
+                        _ => break,
                     }
                 }
                 _ => break,
             }
         }
 
+        self.check_not_struct_value(&expr)?;
         self.exit_recursion();
         Ok(expr)
     }
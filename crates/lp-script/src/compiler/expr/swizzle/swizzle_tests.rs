@@ -2,7 +2,8 @@
 #[cfg(test)]
 mod tests {
     use crate::compiler::expr::expr_test_util::ExprTest;
-    use crate::fixed::{ToFixed, Vec2};
+    use crate::compiler::expr::swizzle::swizzle_gen::{gen_swizzle_opcodes, swizzle_result};
+    use crate::fixed::{ToFixed, Vec2, Vec3, Vec4};
     use crate::shared::Type;
     use crate::vm::opcodes::LpsOpCode;
 
@@ -147,6 +148,117 @@ mod tests {
             .run()
     }
 
+    #[test]
+    fn test_swizzle_widens_vec2_and_vec3() -> Result<(), String> {
+        ExprTest::new("vec2(1.0, 2.0).xyx")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::Push(2.0.to_fixed()),
+                LpsOpCode::Dup2,
+                LpsOpCode::Swizzle4to3(0, 1, 0),
+                LpsOpCode::Return,
+            ])
+            .expect_result_vec3(Vec3::new(1.0.to_fixed(), 2.0.to_fixed(), 1.0.to_fixed()))
+            .run()?;
+
+        ExprTest::new("vec3(1.0, 2.0, 3.0).zyxz")
+            .expect_result_vec4(Vec4::new(
+                3.0.to_fixed(),
+                2.0.to_fixed(),
+                1.0.to_fixed(),
+                3.0.to_fixed(),
+            ))
+            .run()
+    }
+
+    #[test]
+    fn test_swizzle_chain() -> Result<(), String> {
+        ExprTest::new("vec3(1.0, 2.0, 3.0).xy.yx")
+            .expect_result_vec2(Vec2::new(2.0.to_fixed(), 1.0.to_fixed()))
+            .run()?;
+
+        ExprTest::new("vec4(1.0, 2.0, 3.0, 4.0).wzy.zx.yyxy")
+            .expect_result_vec4(Vec4::new(
+                4.0.to_fixed(),
+                4.0.to_fixed(),
+                2.0.to_fixed(),
+                4.0.to_fixed(),
+            ))
+            .run()
+    }
+
+    /// Random valid swizzle chains leave exactly the selected components on
+    /// the stack, in order
+    #[test]
+    fn test_swizzle_chains_balance_stack() -> Result<(), String> {
+        let mut seed = 0x2545_f491_u32;
+        let mut next = |n: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed as usize % n
+        };
+
+        for _ in 0..500 {
+            let mut size = 2 + next(3);
+            let source = [
+                "vec2(1.0, 2.0)",
+                "vec3(1.0, 2.0, 3.0)",
+                "vec4(1.0, 2.0, 3.0, 4.0)",
+            ];
+            let mut src = String::from(source[size - 2]);
+            let source_size = size;
+            // Source component held by each slot of the current value
+            let mut expected: Vec<usize> = (0..size).collect();
+            let mut code = Vec::new();
+
+            for _ in 0..1 + next(4) {
+                let len = 1 + next(4);
+                let components: String =
+                    (0..len).map(|_| ['x', 'y', 'z', 'w'][next(size)]).collect();
+                gen_swizzle_opcodes(&components, size, &mut code);
+                src.push('.');
+                src.push_str(&components);
+                expected = components
+                    .chars()
+                    .map(|c| expected["xyzw".find(c).unwrap()])
+                    .collect();
+                size = len;
+                if size == 1 {
+                    break;
+                }
+            }
+
+            assert_eq!(
+                swizzle_result(&code, source_size).as_ref(),
+                Some(&expected),
+                "{}: {:?}",
+                src,
+                code
+            );
+
+            let value = |i: usize| (expected[i] + 1) as f32;
+            let test = ExprTest::new(&src);
+            match expected.len() {
+                1 => test.expect_result_fixed(value(0)),
+                2 => test.expect_result_vec2(Vec2::new(value(0).to_fixed(), value(1).to_fixed())),
+                3 => test.expect_result_vec3(Vec3::new(
+                    value(0).to_fixed(),
+                    value(1).to_fixed(),
+                    value(2).to_fixed(),
+                )),
+                _ => test.expect_result_vec4(Vec4::new(
+                    value(0).to_fixed(),
+                    value(1).to_fixed(),
+                    value(2).to_fixed(),
+                    value(3).to_fixed(),
+                )),
+            }
+            .run()?;
+        }
+        Ok(())
+    }
+
     // Type checking tests (using ExprTest validates types automatically)
     // These tests already exist above and validate type checking through execution
 }
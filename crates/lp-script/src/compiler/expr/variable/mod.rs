@@ -4,7 +4,7 @@
 mod variable_gen;
 mod variable_parse;
 mod variable_types;
-pub(in crate::compiler) use variable_types::{check_incdec, check_variable};
+pub(in crate::compiler) use variable_types::{check_incdec, check_variable, visible_constant};
 
 #[cfg(test)]
 mod variable_tests;
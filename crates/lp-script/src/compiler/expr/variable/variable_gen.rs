@@ -8,6 +8,15 @@ use crate::vm::opcodes::LpsOpCode;
 
 impl<'a> CodeGenerator<'a> {
     pub(crate) fn gen_variable(&mut self, name: &str, var_type: &Type) {
+        // Externals shadow built-ins and locals shadow externals, as they do
+        // in the type checker
+        if self.locals.get(name).is_none() {
+            if let Some(index) = self.externals.iter().position(|e| e.name == name) {
+                self.code.push(LpsOpCode::LoadExternal(index as u32));
+                return;
+            }
+        }
+
         // Check if it's a vec2 built-in (uv, coord)
         match name {
             "uv" => {
@@ -31,23 +40,39 @@ impl<'a> CodeGenerator<'a> {
                         Type::Int32 => {
                             self.code.push(LpsOpCode::LoadLocalInt32(index));
                         }
-                        Type::Vec2 => {
+                        Type::Vec2 | Type::BVec2 => {
                             self.code.push(LpsOpCode::LoadLocalVec2(index));
                         }
-                        Type::Vec3 => {
+                        Type::Vec3 | Type::BVec3 => {
                             self.code.push(LpsOpCode::LoadLocalVec3(index));
                         }
-                        Type::Vec4 => {
+                        Type::Vec4 | Type::BVec4 => {
                             self.code.push(LpsOpCode::LoadLocalVec4(index));
                         }
+                        Type::Mat2 => {
+                            self.code.push(LpsOpCode::LoadLocalMat2(index));
+                        }
                         Type::Mat3 => {
                             self.code.push(LpsOpCode::LoadLocalMat3(index));
                         }
+                        Type::Mat4 => {
+                            self.code.push(LpsOpCode::LoadLocalMat4(index));
+                        }
                         _ => {
                             // Fallback for unsupported types
                             self.code.push(LpsOpCode::LoadLocalFixed(index));
                         }
                     }
+                } else if name == "index" {
+                    self.code.push(LpsOpCode::LoadIndex);
+                } else if name == "polar" {
+                    // uv relative to the center, converted to (radius, angle)
+                    self.code.push(LpsOpCode::Load(LoadSource::XNorm));
+                    self.code.push(LpsOpCode::Load(LoadSource::YNorm));
+                    self.code.push(LpsOpCode::Load(LoadSource::CenterX));
+                    self.code.push(LpsOpCode::Load(LoadSource::CenterY));
+                    self.code.push(LpsOpCode::SubVec2);
+                    self.code.push(LpsOpCode::ToPolarVec2);
                 } else {
                     // Scalar built-in
                     let source = variable_to_load_source(name);
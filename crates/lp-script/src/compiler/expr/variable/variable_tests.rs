@@ -103,4 +103,104 @@ mod tests {
             .expect_result_fixed(0.5)
             .run()
     }
+
+    #[test]
+    fn test_builtin_variable_index_opcode() -> Result<(), String> {
+        // ExprTest runs a single pixel, so index is 0
+        ExprTest::new("index")
+            .expect_opcodes(vec![LpsOpCode::LoadIndex, LpsOpCode::Return])
+            .expect_result_int(0)
+            .run()
+    }
+
+    #[test]
+    fn test_builtin_variable_index_row_major() {
+        use crate::fixed::Fixed;
+        use crate::vm::{execute_program_lps, CoordConfig};
+        use crate::{compile_expr_as, Type};
+
+        let program = compile_expr_as("index", Type::Fixed).unwrap();
+        let (width, height) = (3, 2);
+        let mut output = vec![Fixed::ZERO; width * height];
+        execute_program_lps(
+            &program,
+            &mut output,
+            width,
+            height,
+            Fixed::ZERO,
+            &CoordConfig::default(),
+        );
+
+        for (i, value) in output.iter().enumerate() {
+            assert_eq!(*value, Fixed::from_i32(i as i32), "pixel {}", i);
+        }
+    }
+
+    #[test]
+    fn test_builtin_constants_fold_to_literals() -> Result<(), String> {
+        use crate::fixed::Fixed;
+
+        for (name, value) in [
+            ("PI", Fixed::PI),
+            ("TAU", Fixed::TAU),
+            ("E", Fixed::E),
+            ("PHI", Fixed::PHI),
+        ] {
+            ExprTest::new(name)
+                .expect_opcodes(vec![LpsOpCode::Push(value), LpsOpCode::Return])
+                .expect_result_fixed(value.to_f32())
+                .run()?;
+        }
+
+        ExprTest::new("sin(x * TAU)")
+            .with_x(0.25)
+            .expect_opcodes(vec![
+                LpsOpCode::Load(LoadSource::XNorm),
+                LpsOpCode::Push(Fixed::TAU),
+                LpsOpCode::MulFixed,
+                LpsOpCode::SinFixed,
+                LpsOpCode::Return,
+            ])
+            .run()
+    }
+
+    #[test]
+    fn test_builtin_constants_shadowing_and_assignment() {
+        use crate::compiler::error::{CompileError, TypeErrorKind};
+        use crate::fixed::Fixed;
+        use crate::vm::vm_limits::VmLimits;
+        use crate::*;
+
+        // A local of the same name hides the constant
+        let program = parse_script("float PI = 3.0; return PI + E;");
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let result = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result, Fixed::from_i32(3) + Fixed::E);
+
+        for src in [
+            "PI = 3.0; return PI;",
+            "TAU += 1.0; return TAU;",
+            "E++; return E;",
+        ] {
+            match compile_script(src) {
+                Err(CompileError::TypeCheck(err)) => assert!(
+                    matches!(err.kind, TypeErrorKind::ConstAssignment(_)),
+                    "{}: {:?}",
+                    src,
+                    err.kind
+                ),
+                other => panic!(
+                    "{}: expected a const assignment error, got {:?}",
+                    src, other
+                ),
+            }
+        }
+
+        assert_eq!(
+            eval_const_expr("TAU / 2.0").unwrap(),
+            ConstValue::Fixed(Fixed::PI)
+        );
+    }
 }
@@ -3,8 +3,10 @@ extern crate alloc;
 
 use alloc::string::String;
 
+use crate::compiler::builtins::{builtin_constant, builtin_variable};
 use crate::compiler::error::{TypeError, TypeErrorKind};
 use crate::compiler::typechecker::SymbolTable;
+use crate::fixed::Fixed;
 use crate::shared::Type;
 
 /// Check variable type (including built-in variables)
@@ -15,34 +17,40 @@ pub(in crate::compiler) fn check_variable(
 ) -> Result<Type, TypeError> {
     // Check symbol table first to allow shadowing of built-ins
     if let Some(ty) = symbols.lookup(name) {
+        // Grids and arrays only exist element-wise (see grid/grid_types.rs
+        // and array/array_types.rs)
+        match ty {
+            Type::Grid { .. } => {
+                return Err(TypeError {
+                    kind: TypeErrorKind::UnindexedGrid(String::from(name)),
+                    span,
+                })
+            }
+            Type::Array { .. } => {
+                return Err(TypeError {
+                    kind: TypeErrorKind::UnindexedArray(String::from(name)),
+                    span,
+                })
+            }
+            _ => {}
+        }
         return Ok(ty);
     }
 
     // Then check built-ins
-    let var_type = match name {
-        // Vec2 built-ins (GLSL-style)
-        "uv" => Type::Vec2,    // normalized coordinates (0..1)
-        "coord" => Type::Vec2, // pixel coordinates
-
-        // Scalar built-ins
-        "time" | "t" => Type::Fixed,
-        "timeNorm" => Type::Fixed,
-        "centerAngle" | "angle" => Type::Fixed,
-        "centerDist" | "dist" => Type::Fixed,
-
-        // Legacy scalar built-ins (deprecated, kept for compatibility)
-        "x" | "xNorm" | "y" | "yNorm" => Type::Fixed,
-
-        // Not found anywhere
-        _ => {
-            return Err(TypeError {
-                kind: TypeErrorKind::UndefinedVariable(String::from(name)),
-                span,
-            });
-        }
-    };
+    builtin_variable(name).ok_or_else(|| TypeError {
+        kind: TypeErrorKind::UndefinedVariable(String::from(name)),
+        span,
+    })
+}
 
-    Ok(var_type)
+/// Value of the built-in constant `name`, unless a local, parameter or
+/// uniform shadows it
+pub(in crate::compiler) fn visible_constant(name: &str, symbols: &SymbolTable) -> Option<Fixed> {
+    match symbols.lookup(name) {
+        Some(_) => None,
+        None => builtin_constant(name),
+    }
 }
 
 /// Check increment/decrement operations
@@ -51,5 +59,17 @@ pub(in crate::compiler) fn check_incdec(
     symbols: &mut SymbolTable,
     span: crate::shared::Span,
 ) -> Result<Type, TypeError> {
+    if symbols.lookup_const(name).is_some() || visible_constant(name, symbols).is_some() {
+        return Err(TypeError {
+            kind: TypeErrorKind::ConstAssignment(String::from(name)),
+            span,
+        });
+    }
+    if symbols.is_external(name) {
+        return Err(TypeError {
+            kind: TypeErrorKind::ExternalAssignment(String::from(name)),
+            span,
+        });
+    }
     check_variable(name, symbols, span)
 }
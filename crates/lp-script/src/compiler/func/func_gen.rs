@@ -15,11 +15,22 @@ use crate::vm::{FunctionDef as VmFunctionDef, LocalVarDef, ParamDef};
 ///
 /// This is the core function generation logic using the pool-based API.
 /// It handles parameter allocation, function body code generation, and types conversion.
+/// A prototype gets no opcodes, marking it for `LpsProgram::merge` to resolve.
 pub fn gen_user_function(
     ast_func: &AstFunctionDef,
     func_table: &FunctionTable,
     function_indices: &BTreeMap<String, u32>,
 ) -> VmFunctionDef {
+    let params_defs: Vec<ParamDef> = ast_func
+        .params
+        .iter()
+        .map(|p| ParamDef::new(p.name.clone(), p.ty.clone()))
+        .collect();
+    let Some(body) = &ast_func.body else {
+        return VmFunctionDef::new(ast_func.name.clone(), ast_func.return_type.clone())
+            .with_params(params_defs);
+    };
+
     let mut func_code = Vec::new();
 
     // Get pre-analyzed types for this function
@@ -39,17 +50,22 @@ pub fn gen_user_function(
         match param.ty {
             Type::Bool | Type::Fixed => func_code.push(LpsOpCode::StoreLocalFixed(i as u32)),
             Type::Int32 => func_code.push(LpsOpCode::StoreLocalInt32(i as u32)),
-            Type::Vec2 => func_code.push(LpsOpCode::StoreLocalVec2(i as u32)),
-            Type::Vec3 => func_code.push(LpsOpCode::StoreLocalVec3(i as u32)),
-            Type::Vec4 => func_code.push(LpsOpCode::StoreLocalVec4(i as u32)),
+            Type::Vec2 | Type::BVec2 => func_code.push(LpsOpCode::StoreLocalVec2(i as u32)),
+            Type::Vec3 | Type::BVec3 => func_code.push(LpsOpCode::StoreLocalVec3(i as u32)),
+            Type::Vec4 | Type::BVec4 => func_code.push(LpsOpCode::StoreLocalVec4(i as u32)),
+            Type::Mat2 => func_code.push(LpsOpCode::StoreLocalMat2(i as u32)),
             Type::Mat3 => func_code.push(LpsOpCode::StoreLocalMat3(i as u32)),
-            Type::Void => {}
+            Type::Mat4 => func_code.push(LpsOpCode::StoreLocalMat4(i as u32)),
+            // Grids and arrays can't be parameters
+            Type::Grid { .. } | Type::Array { .. } | Type::Void => {}
         }
     }
 
     // Generate function body
     let mut gen = CodeGenerator::new(&mut func_code, &mut locals, function_indices);
-    for stmt in &ast_func.body {
+    gen.externals = func_table.externals();
+    gen.natives = func_table.natives();
+    for stmt in body {
         gen.gen_stmt(stmt);
     }
 
@@ -64,12 +80,6 @@ pub fn gen_user_function(
     }
 
     // Convert to VmFunctionDef using types
-    let params_defs: Vec<ParamDef> = ast_func
-        .params
-        .iter()
-        .map(|p| ParamDef::new(p.name.clone(), p.ty.clone()))
-        .collect();
-
     let local_defs: Vec<LocalVarDef> = metadata
         .locals
         .iter()
@@ -10,16 +10,20 @@ use crate::shared::Span;
 
 impl Parser {
     /// Check if the current position is a function definition
-    /// Function def: type name(params) { body }
+    /// Function def: type name(params) { body } or prototype: type name(params);
     pub(crate) fn is_function_definition(&mut self) -> bool {
         // Look ahead: type + identifier + (
         matches!(
             self.current().kind,
-            TokenKind::Float
+            TokenKind::Bool
+                | TokenKind::Float
                 | TokenKind::Int
                 | TokenKind::Vec2
                 | TokenKind::Vec3
                 | TokenKind::Vec4
+                | TokenKind::BVec2
+                | TokenKind::BVec3
+                | TokenKind::BVec4
                 | TokenKind::Void
         ) && {
             // Look ahead 2 tokens
@@ -57,15 +61,34 @@ impl Parser {
 
         // Parse parameter list
         self.expect(TokenKind::LParen);
-        let params = self.parse_parameters();
+        let params = self.parse_parameters()?;
         self.expect(TokenKind::RParen);
 
+        // A prototype ends at the parameter list
+        if matches!(self.current().kind, TokenKind::Semicolon) {
+            let end = self.current().span.end;
+            self.advance();
+            self.exit_recursion();
+            return Ok(FunctionDef {
+                name,
+                params,
+                return_type,
+                body: None,
+                span: Span::new(start, end),
+            });
+        }
+
         // Parse body
         self.expect(TokenKind::LBrace);
         let mut body = Vec::new();
+        self.push_scope();
+        for param in &params {
+            self.declare(&param.name, None);
+        }
         while !matches!(self.current().kind, TokenKind::RBrace | TokenKind::Eof) {
-            body.push(self.parse_stmt()?);
+            self.parse_stmt_into(&mut body)?;
         }
+        self.pop_scope();
         let end = self.current().span.end;
         self.expect(TokenKind::RBrace);
 
@@ -74,22 +97,23 @@ impl Parser {
             name,
             params,
             return_type,
-            body,
+            body: Some(body),
             span: Span::new(start, end),
         })
     }
 
     /// Parse function parameters
-    fn parse_parameters(&mut self) -> Vec<Parameter> {
+    fn parse_parameters(&mut self) -> Result<Vec<Parameter>, ParseError> {
         let mut params = Vec::new();
 
         // Empty parameter list
         if matches!(self.current().kind, TokenKind::RParen) {
-            return params;
+            return Ok(params);
         }
 
         loop {
             // Parse parameter type
+            self.reject_struct_type("parameters")?;
             let ty = self.parse_type();
 
             // Parse parameter name
@@ -111,6 +135,6 @@ impl Parser {
             }
         }
 
-        params
+        Ok(params)
     }
 }
@@ -36,7 +36,10 @@ mod parse_tests {
         let program = parser.parse_program().expect("parse should succeed");
 
         assert_eq!(program.functions.len(), 1);
-        assert!(!program.functions[0].body.is_empty());
+        assert!(program.functions[0]
+            .body
+            .as_ref()
+            .is_some_and(|body| !body.is_empty()));
     }
 
     #[test]
@@ -55,6 +58,39 @@ mod parse_tests {
         assert_eq!(program.functions[0].name, "add");
         assert_eq!(program.functions[1].name, "sub");
     }
+
+    #[test]
+    fn test_parse_prototype() {
+        let mut lexer = Lexer::new("float sq(float x); vec2 offset();");
+        let tokens = lexer.tokenize();
+        let parser = Parser::new(tokens);
+        let program = parser.parse_program().expect("parse should succeed");
+
+        assert_eq!(program.functions.len(), 2);
+        assert_eq!(program.functions[0].name, "sq");
+        assert_eq!(program.functions[0].params.len(), 1);
+        assert!(program.functions[0].body.is_none());
+        assert!(program.functions[1].body.is_none());
+    }
+
+    #[test]
+    fn test_prototype_and_definition_are_one_function() {
+        let mut lexer = Lexer::new(
+            "
+            float sq(float x);
+            float cube(float x) { return x * sq(x); }
+            float sq(float x) { return x * x; }
+        ",
+        );
+        let tokens = lexer.tokenize();
+        let parser = Parser::new(tokens);
+        let program = parser.parse_program().expect("parse should succeed");
+
+        assert_eq!(program.functions.len(), 2);
+        assert_eq!(program.functions[0].name, "sq");
+        assert!(program.functions[0].body.is_some());
+        assert_eq!(program.functions[1].name, "cube");
+    }
 }
 
 #[cfg(test)]
@@ -718,4 +754,36 @@ mod integration_tests {
             .unwrap();
         assert_eq!(result.to_f32(), 7.0);
     }
+
+    #[test]
+    fn test_function_defined_after_prototype() {
+        let script = "
+            float sq(float x);
+            float quad(float x) { return sq(sq(x)); }
+            float sq(float x) { return x * x; }
+            return quad(2.0);
+        ";
+        let program = parse_script(script);
+        program.validate().unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+
+        let result = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result.to_f32(), 16.0);
+    }
+
+    #[test]
+    fn test_prototype_without_definition_is_unresolved() {
+        let program = parse_script("float sq(float x); return sq(3.0);");
+        assert_eq!(
+            program.validate(),
+            Err(ProgramError::UnresolvedFunction("sq".into()))
+        );
+
+        // A definition with a different signature is a second function
+        assert!(
+            compile_script("float sq(float x); int sq(float x) { return 1; } return 0.0;").is_err()
+        );
+    }
 }
@@ -6,6 +6,7 @@ use alloc::string::String;
 use alloc::vec::Vec;
 
 use crate::shared::Type;
+use crate::vm::{ExternalDef, NativeDef};
 
 /// Local variable information
 #[derive(Debug, Clone)]
@@ -30,19 +31,37 @@ pub(crate) struct FunctionMetadata {
     pub(crate) local_count: u32,
 }
 
-/// Function table for tracking user-defined functions
+/// Function table for tracking user-defined and host native functions
 #[derive(Debug, Clone)]
 pub(crate) struct FunctionTable {
     functions: BTreeMap<String, FunctionMetadata>,
+    /// Called by index with `CallNative`, in registration order
+    natives: Vec<NativeDef>,
+    /// Script uniforms, read by index with `LoadExternal`
+    externals: Vec<ExternalDef>,
 }
 
 impl FunctionTable {
     pub(crate) fn new() -> Self {
         FunctionTable {
             functions: BTreeMap::new(),
+            natives: Vec::new(),
+            externals: Vec::new(),
         }
     }
 
+    /// Make host-set values readable from every function
+    pub(crate) fn with_externals(mut self, externals: Vec<ExternalDef>) -> Self {
+        self.externals = externals;
+        self
+    }
+
+    /// Make host natives callable; user functions of the same name win
+    pub(crate) fn with_natives(mut self, natives: &[NativeDef]) -> Self {
+        self.natives = natives.to_vec();
+        self
+    }
+
     /// Declare a function with full types (params, return type, locals)
     pub(crate) fn declare_with_metadata(
         &mut self,
@@ -56,10 +75,45 @@ impl FunctionTable {
         Ok(())
     }
 
+    /// Replace a function's signature, declaring it (without locals) if missing
+    #[allow(dead_code)] // Used by incremental type checking
+    pub(crate) fn set_signature(&mut self, name: &str, params: Vec<Type>, return_type: Type) {
+        match self.functions.get_mut(name) {
+            Some(metadata) => {
+                metadata.params = params;
+                metadata.return_type = return_type;
+            }
+            None => {
+                self.functions.insert(
+                    String::from(name),
+                    FunctionMetadata {
+                        params,
+                        return_type,
+                        locals: Vec::new(),
+                        local_count: 0,
+                    },
+                );
+            }
+        }
+    }
+
     /// Get function types
     pub(crate) fn lookup(&self, name: &str) -> Option<&FunctionMetadata> {
         self.functions.get(name)
     }
+
+    /// Get a native's declaration
+    pub(crate) fn lookup_native(&self, name: &str) -> Option<&NativeDef> {
+        self.natives.iter().find(|native| native.name == name)
+    }
+
+    pub(crate) fn natives(&self) -> &[NativeDef] {
+        &self.natives
+    }
+
+    pub(crate) fn externals(&self) -> &[ExternalDef] {
+        &self.externals
+    }
 }
 
 // NOTE: The old check_function implementation has been replaced with
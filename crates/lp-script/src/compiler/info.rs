@@ -0,0 +1,244 @@
+/// Function signatures and built-in usage of a script, see
+/// `compile_script_with_info`
+extern crate alloc;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::compiler::ast::{Expr, ExprKind, Program, Stmt, StmtKind};
+use crate::compiler::builtins::{builtins, BuiltinSig};
+use crate::compiler::metrics::CompileObserver;
+use crate::compiler::typechecker::FunctionTable;
+use crate::shared::Type;
+
+/// Signature of a user-defined function
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionInfo {
+    pub name: String,
+    pub params: Vec<Type>,
+    pub return_type: Type,
+}
+
+/// What a compiled script defines and uses
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScriptInfo {
+    /// User functions, in source order
+    pub functions: Vec<FunctionInfo>,
+    /// Built-in variables and functions the script references, by name;
+    /// names shadowed by a local, parameter or user function don't count
+    pub builtins: BTreeSet<&'static str>,
+}
+
+impl ScriptInfo {
+    pub fn function(&self, name: &str) -> Option<&FunctionInfo> {
+        self.functions.iter().find(|f| f.name == name)
+    }
+}
+
+/// Collects `ScriptInfo` from the type checked program
+#[derive(Default)]
+pub(crate) struct InfoRecorder {
+    pub(crate) info: ScriptInfo,
+}
+
+impl CompileObserver for InfoRecorder {
+    fn checked(&mut self, program: &Program, func_table: &FunctionTable) {
+        self.info.functions = program
+            .functions
+            .iter()
+            .map(|func| FunctionInfo {
+                name: func.name.clone(),
+                params: func.params.iter().map(|p| p.ty.clone()).collect(),
+                return_type: func.return_type.clone(),
+            })
+            .collect();
+
+        let mut refs = BuiltinRefs {
+            func_table,
+            scopes: vec![program.uniforms.iter().map(|u| u.name.as_str()).collect()],
+            found: &mut self.info.builtins,
+        };
+        refs.scopes.push(Vec::new());
+        program.consts.iter().for_each(|stmt| refs.stmt(stmt));
+        for func in &program.functions {
+            refs.scopes
+                .push(func.params.iter().map(|p| p.name.as_str()).collect());
+            func.body.iter().flatten().for_each(|stmt| refs.stmt(stmt));
+            refs.scopes.pop();
+        }
+        refs.scopes.push(Vec::new());
+        program.stmts.iter().for_each(|stmt| refs.stmt(stmt));
+    }
+}
+
+/// Walks statements tracking declared names, so a local `time` hides the
+/// built-in the way the type checker resolves it
+struct BuiltinRefs<'a> {
+    func_table: &'a FunctionTable,
+    scopes: Vec<Vec<&'a str>>,
+    found: &'a mut BTreeSet<&'static str>,
+}
+
+impl<'a> BuiltinRefs<'a> {
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(Vec::new());
+        f(self);
+        self.scopes.pop();
+    }
+
+    fn stmt(&mut self, stmt: &'a Stmt) {
+        match &stmt.kind {
+            StmtKind::VarDecl { name, init, .. } => {
+                if let Some(init) = init {
+                    self.expr(init);
+                }
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.push(name);
+                }
+            }
+            StmtKind::ConstDecl { name, init, .. } => {
+                self.expr(init);
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.push(name);
+                }
+            }
+            StmtKind::Return(expr) | StmtKind::Expr(expr) => self.expr(expr),
+            StmtKind::Block(stmts) => self.scoped(|s| stmts.iter().for_each(|stmt| s.stmt(stmt))),
+            StmtKind::If {
+                condition,
+                then_stmt,
+                else_stmt,
+            } => {
+                self.expr(condition);
+                self.scoped(|s| s.stmt(then_stmt));
+                if let Some(else_stmt) = else_stmt {
+                    self.scoped(|s| s.stmt(else_stmt));
+                }
+            }
+            StmtKind::While { condition, body } => {
+                self.expr(condition);
+                self.scoped(|s| s.stmt(body));
+            }
+            StmtKind::DoWhile { body, condition } => {
+                self.scoped(|s| s.stmt(body));
+                self.expr(condition);
+            }
+            StmtKind::Switch { selector, cases } => {
+                self.expr(selector);
+                self.scoped(|s| {
+                    for case in cases {
+                        case.value.iter().for_each(|value| s.expr(value));
+                        case.body.iter().for_each(|stmt| s.stmt(stmt));
+                    }
+                });
+            }
+            StmtKind::For {
+                init,
+                condition,
+                increment,
+                body,
+            } => self.scoped(|s| {
+                if let Some(init) = init {
+                    s.stmt(init);
+                }
+                condition.iter().chain(increment).for_each(|e| s.expr(e));
+                s.stmt(body);
+            }),
+            StmtKind::Break | StmtKind::Continue => {}
+        }
+    }
+
+    fn expr(&mut self, expr: &'a Expr) {
+        use ExprKind::*;
+
+        match &expr.kind {
+            Variable(name) => {
+                if !self
+                    .scopes
+                    .iter()
+                    .any(|scope| scope.contains(&name.as_str()))
+                {
+                    self.found.extend(builtin_name(name, false));
+                }
+            }
+            Call { name, args } => {
+                if self.func_table.lookup(name).is_none()
+                    && self.func_table.lookup_native(name).is_none()
+                {
+                    self.found.extend(builtin_name(name, true));
+                }
+                args.iter().for_each(|arg| self.expr(arg));
+            }
+            Add(left, right)
+            | Sub(left, right)
+            | Mul(left, right)
+            | Div(left, right)
+            | Mod(left, right)
+            | BitwiseAnd(left, right)
+            | BitwiseOr(left, right)
+            | BitwiseXor(left, right)
+            | LeftShift(left, right)
+            | RightShift(left, right)
+            | Less(left, right)
+            | Greater(left, right)
+            | LessEq(left, right)
+            | GreaterEq(left, right)
+            | Eq(left, right)
+            | NotEq(left, right)
+            | And(left, right)
+            | Or(left, right) => {
+                self.expr(left);
+                self.expr(right);
+            }
+            Neg(operand) | BitwiseNot(operand) | Not(operand) => self.expr(operand),
+            Ternary {
+                condition,
+                true_expr,
+                false_expr,
+            } => {
+                self.expr(condition);
+                self.expr(true_expr);
+                self.expr(false_expr);
+            }
+            Assign { value, .. } => self.expr(value),
+            GridIndex { row, col, .. } => {
+                self.expr(row);
+                self.expr(col);
+            }
+            GridAssign {
+                row, col, value, ..
+            } => {
+                self.expr(row);
+                self.expr(col);
+                self.expr(value);
+            }
+            ArrayIndex { index, .. } => self.expr(index),
+            ArrayAssign { index, value, .. } => {
+                self.expr(index);
+                self.expr(value);
+            }
+            Vec2Constructor(args)
+            | Vec3Constructor(args)
+            | Vec4Constructor(args)
+            | BVec2Constructor(args)
+            | BVec3Constructor(args)
+            | BVec4Constructor(args)
+            | Mat2Constructor(args)
+            | Mat3Constructor(args)
+            | Mat4Constructor(args) => args.iter().for_each(|arg| self.expr(arg)),
+            Swizzle { expr: inner, .. } => self.expr(inner),
+            Number(_) | IntNumber(_) | Bool(_) | PreIncrement(_) | PreDecrement(_)
+            | PostIncrement(_) | PostDecrement(_) => {}
+        }
+    }
+}
+
+/// Catalog name of the built-in variable or function called `name`
+fn builtin_name(name: &str, function: bool) -> Option<&'static str> {
+    builtins().iter().find_map(|sig| match sig {
+        BuiltinSig::Variable { name: n, .. } if !function && *n == name => Some(*n),
+        BuiltinSig::Function(f) if function && f.name == name => Some(f.name),
+        _ => None,
+    })
+}
@@ -17,6 +17,7 @@ pub enum TokenKind {
     // Literals
     FloatLiteral(f32),
     IntLiteral(i32),
+    BoolLiteral(bool), // `true` / `false`
     Ident(String),
 
     // Operators
@@ -54,11 +55,14 @@ pub enum TokenKind {
     RParen,
     LBrace,
     RBrace,
+    LBracket, // Array/grid index [
+    RBracket, // Array/grid index ]
     Comma,
     Semicolon,
     Question, // Ternary ?
     Colon,    // Ternary :
     Dot,      // Member access / swizzle
+    DotDot,   // Range: for i in 0..n
     Eq,       // Assignment =
 
     // Compound assignments
@@ -77,14 +81,29 @@ pub enum TokenKind {
     If,
     Else,
     While,
+    Do,
     For,
+    Switch,
+    Case,
+    Default,
+    Break,
+    Continue,
     Return,
+    Struct,
+    Const,
+    Uniform,
+    Bool,
     Float,
     Int,
     Vec2,
     Vec3,
     Vec4,
+    BVec2,
+    BVec3,
+    BVec4,
+    Mat2,
     Mat3,
+    Mat4,
     Void,
 
     Eof,
@@ -189,7 +208,10 @@ impl Lexer {
         let mut is_float = false;
 
         while let Some(ch) = self.current() {
-            if ch.is_numeric() || ch == '.' {
+            if ch == '.' && self.peek(1) == Some('.') {
+                // `0..n` is a range, not the float `0.`
+                break;
+            } else if ch.is_numeric() || ch == '.' {
                 if ch == '.' {
                     is_float = true;
                 }
@@ -251,14 +273,31 @@ impl Lexer {
             "if" => TokenKind::If,
             "else" => TokenKind::Else,
             "while" => TokenKind::While,
+            "do" => TokenKind::Do,
             "for" => TokenKind::For,
+            "switch" => TokenKind::Switch,
+            "case" => TokenKind::Case,
+            "default" => TokenKind::Default,
+            "break" => TokenKind::Break,
+            "continue" => TokenKind::Continue,
             "return" => TokenKind::Return,
+            "struct" => TokenKind::Struct,
+            "const" => TokenKind::Const,
+            "uniform" => TokenKind::Uniform,
+            "true" => TokenKind::BoolLiteral(true),
+            "false" => TokenKind::BoolLiteral(false),
+            "bool" => TokenKind::Bool,
             "float" => TokenKind::Float,
             "int" => TokenKind::Int,
             "vec2" => TokenKind::Vec2,
             "vec3" => TokenKind::Vec3,
             "vec4" => TokenKind::Vec4,
+            "bvec2" => TokenKind::BVec2,
+            "bvec3" => TokenKind::BVec3,
+            "bvec4" => TokenKind::BVec4,
+            "mat2" => TokenKind::Mat2,
             "mat3" => TokenKind::Mat3,
+            "mat4" => TokenKind::Mat4,
             "void" => TokenKind::Void,
             _ => TokenKind::Ident(ident),
         }
@@ -357,6 +396,14 @@ impl Lexer {
                         self.advance();
                         TokenKind::RParen
                     }
+                    '[' => {
+                        self.advance();
+                        TokenKind::LBracket
+                    }
+                    ']' => {
+                        self.advance();
+                        TokenKind::RBracket
+                    }
                     '{' => {
                         self.advance();
                         TokenKind::LBrace
@@ -384,7 +431,11 @@ impl Lexer {
                     '.' => {
                         // Check if it's a number like .5 or a dot operator
                         if let Some(next_ch) = self.peek(1) {
-                            if next_ch.is_numeric() {
+                            if next_ch == '.' {
+                                self.advance();
+                                self.advance();
+                                TokenKind::DotDot
+                            } else if next_ch.is_numeric() {
                                 // It's a number like .5
                                 let (num_str, _is_float) = self.read_number();
                                 TokenKind::FloatLiteral(num_str.parse().unwrap_or(0.0))
@@ -570,12 +621,14 @@ mod tests {
     #[test]
     fn test_delimiters() {
         assert_eq!(
-            tokenize("( ) { } , ; ? : . ="),
+            tokenize("( ) { } [ ] , ; ? : . ="),
             vec![
                 TokenKind::LParen,
                 TokenKind::RParen,
                 TokenKind::LBrace,
                 TokenKind::RBrace,
+                TokenKind::LBracket,
+                TokenKind::RBracket,
                 TokenKind::Comma,
                 TokenKind::Semicolon,
                 TokenKind::Question,
@@ -590,13 +643,22 @@ mod tests {
     #[test]
     fn test_keywords() {
         assert_eq!(
-            tokenize("if else while for return"),
+            tokenize("if else while do for switch case default break continue return struct const uniform"),
             vec![
                 TokenKind::If,
                 TokenKind::Else,
                 TokenKind::While,
+                TokenKind::Do,
                 TokenKind::For,
+                TokenKind::Switch,
+                TokenKind::Case,
+                TokenKind::Default,
+                TokenKind::Break,
+                TokenKind::Continue,
                 TokenKind::Return,
+                TokenKind::Struct,
+                TokenKind::Const,
+                TokenKind::Uniform,
                 TokenKind::Eof,
             ]
         );
@@ -605,20 +667,39 @@ mod tests {
     #[test]
     fn test_type_keywords() {
         assert_eq!(
-            tokenize("float int vec2 vec3 vec4 mat3 void"),
+            tokenize("bool float int vec2 vec3 vec4 bvec2 bvec3 bvec4 mat2 mat3 mat4 void"),
             vec![
+                TokenKind::Bool,
                 TokenKind::Float,
                 TokenKind::Int,
                 TokenKind::Vec2,
                 TokenKind::Vec3,
                 TokenKind::Vec4,
+                TokenKind::BVec2,
+                TokenKind::BVec3,
+                TokenKind::BVec4,
+                TokenKind::Mat2,
                 TokenKind::Mat3,
+                TokenKind::Mat4,
                 TokenKind::Void,
                 TokenKind::Eof,
             ]
         );
     }
 
+    #[test]
+    fn test_bool_literals() {
+        assert_eq!(
+            tokenize("true false truth"),
+            vec![
+                TokenKind::BoolLiteral(true),
+                TokenKind::BoolLiteral(false),
+                TokenKind::Ident("truth".into()),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
     #[test]
     fn test_identifiers() {
         let tokens = tokenize("foo bar baz_123 _underscore camelCase");
@@ -715,6 +796,17 @@ mod tests {
         assert!(matches!(tokens[5], TokenKind::Eof));
     }
 
+    #[test]
+    fn test_range() {
+        let tokens = tokenize("0..n 1.5..2");
+        assert_eq!(tokens[0], TokenKind::IntLiteral(0));
+        assert_eq!(tokens[1], TokenKind::DotDot);
+        assert!(matches!(tokens[2], TokenKind::Ident(ref s) if s == "n"));
+        assert_eq!(tokens[3], TokenKind::FloatLiteral(1.5));
+        assert_eq!(tokens[4], TokenKind::DotDot);
+        assert_eq!(tokens[5], TokenKind::IntLiteral(2));
+    }
+
     #[test]
     fn test_expression() {
         let tokens = tokenize("x + y * 2.5");
@@ -0,0 +1,601 @@
+/// Lint pass - non-fatal warnings about likely mistakes
+///
+/// Runs on the type-checked AST, before optimization, so the source forms
+/// the author wrote (e.g. `5 / 2`) are still visible.
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::compiler::ast::{Expr, ExprKind, FunctionDef, Program, Stmt, StmtKind};
+use crate::shared::{Span, Type};
+
+/// Which lints to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LintOptions {
+    /// Warn on `int / int` with literal operands that doesn't divide evenly
+    pub integer_division: bool,
+    /// Warn on functions that call themselves on every path, with no base case
+    pub infinite_recursion: bool,
+}
+
+impl LintOptions {
+    /// Defaults for scripts: all lints enabled
+    pub fn script() -> Self {
+        Self {
+            integer_division: true,
+            infinite_recursion: true,
+        }
+    }
+
+    /// Defaults for single expressions
+    ///
+    /// Integer division is off: expressions are usually short inline formulas
+    /// where the author has already picked the literal types deliberately.
+    pub fn expr() -> Self {
+        Self {
+            integer_division: false,
+            infinite_recursion: true,
+        }
+    }
+
+    /// All lints disabled
+    pub fn none() -> Self {
+        Self {
+            integer_division: false,
+            infinite_recursion: false,
+        }
+    }
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self::script()
+    }
+}
+
+/// A non-fatal compile diagnostic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileWarning {
+    pub kind: WarningKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarningKind {
+    /// `lhs / rhs` on int literals truncates, unlike float division
+    IntegerDivision { lhs: i32, rhs: i32 },
+    /// Every path through `function` calls itself before it can return
+    InfiniteRecursion { function: String },
+}
+
+impl fmt::Display for CompileWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Warning at {}:{}: ", self.span.start, self.span.end)?;
+        match &self.kind {
+            WarningKind::IntegerDivision { lhs, rhs } => write!(
+                f,
+                "integer division {} / {} truncates to {}; write {}.0 / {}.0 for a float result",
+                lhs,
+                rhs,
+                lhs / rhs,
+                lhs,
+                rhs
+            ),
+            WarningKind::InfiniteRecursion { function } => write!(
+                f,
+                "function '{}' calls itself on every path and never returns; add a base case",
+                function
+            ),
+        }
+    }
+}
+
+/// Lint a type-checked expression
+pub fn lint_expr(expr: &Expr, options: &LintOptions) -> Vec<CompileWarning> {
+    let mut warnings = Vec::new();
+    check_expr(expr, options, &mut warnings);
+    warnings
+}
+
+/// Lint a type-checked program (consts, function bodies, then top-level
+/// statements)
+pub fn lint_program(program: &Program, options: &LintOptions) -> Vec<CompileWarning> {
+    let mut warnings = Vec::new();
+    for stmt in &program.consts {
+        check_stmt(stmt, options, &mut warnings);
+    }
+    for func in &program.functions {
+        if options.infinite_recursion && always_recurses(func) {
+            warnings.push(CompileWarning {
+                kind: WarningKind::InfiniteRecursion {
+                    function: func.name.clone(),
+                },
+                span: func.span,
+            });
+        }
+        for stmt in func.body.iter().flatten() {
+            check_stmt(stmt, options, &mut warnings);
+        }
+    }
+    for stmt in &program.stmts {
+        check_stmt(stmt, options, &mut warnings);
+    }
+    warnings
+}
+
+fn check_stmt(stmt: &Stmt, options: &LintOptions, warnings: &mut Vec<CompileWarning>) {
+    match &stmt.kind {
+        StmtKind::VarDecl { init, .. } => {
+            if let Some(init_expr) = init {
+                check_expr(init_expr, options, warnings);
+            }
+        }
+        StmtKind::ConstDecl { init: expr, .. } | StmtKind::Return(expr) | StmtKind::Expr(expr) => {
+            check_expr(expr, options, warnings)
+        }
+        StmtKind::Block(stmts) => {
+            for s in stmts {
+                check_stmt(s, options, warnings);
+            }
+        }
+        StmtKind::If {
+            condition,
+            then_stmt,
+            else_stmt,
+        } => {
+            check_expr(condition, options, warnings);
+            check_stmt(then_stmt, options, warnings);
+            if let Some(else_s) = else_stmt {
+                check_stmt(else_s, options, warnings);
+            }
+        }
+        StmtKind::While { condition, body } => {
+            check_expr(condition, options, warnings);
+            check_stmt(body, options, warnings);
+        }
+        StmtKind::DoWhile { body, condition } => {
+            check_stmt(body, options, warnings);
+            check_expr(condition, options, warnings);
+        }
+        StmtKind::Switch { selector, cases } => {
+            check_expr(selector, options, warnings);
+            for s in cases.iter().flat_map(|case| &case.body) {
+                check_stmt(s, options, warnings);
+            }
+        }
+        StmtKind::For {
+            init,
+            condition,
+            increment,
+            body,
+        } => {
+            if let Some(init_stmt) = init {
+                check_stmt(init_stmt, options, warnings);
+            }
+            if let Some(cond) = condition {
+                check_expr(cond, options, warnings);
+            }
+            if let Some(inc) = increment {
+                check_expr(inc, options, warnings);
+            }
+            check_stmt(body, options, warnings);
+        }
+        StmtKind::Break | StmtKind::Continue => {}
+    }
+}
+
+fn check_expr(expr: &Expr, options: &LintOptions, warnings: &mut Vec<CompileWarning>) {
+    use ExprKind::*;
+
+    match &expr.kind {
+        Div(left, right) => {
+            if options.integer_division {
+                check_integer_division(expr, left, right, warnings);
+            }
+            check_expr(left, options, warnings);
+            check_expr(right, options, warnings);
+        }
+        Add(left, right)
+        | Sub(left, right)
+        | Mul(left, right)
+        | Mod(left, right)
+        | BitwiseAnd(left, right)
+        | BitwiseOr(left, right)
+        | BitwiseXor(left, right)
+        | LeftShift(left, right)
+        | RightShift(left, right)
+        | Less(left, right)
+        | Greater(left, right)
+        | LessEq(left, right)
+        | GreaterEq(left, right)
+        | Eq(left, right)
+        | NotEq(left, right)
+        | And(left, right)
+        | Or(left, right) => {
+            check_expr(left, options, warnings);
+            check_expr(right, options, warnings);
+        }
+        Neg(operand) | BitwiseNot(operand) | Not(operand) => {
+            check_expr(operand, options, warnings);
+        }
+        Ternary {
+            condition,
+            true_expr,
+            false_expr,
+        } => {
+            check_expr(condition, options, warnings);
+            check_expr(true_expr, options, warnings);
+            check_expr(false_expr, options, warnings);
+        }
+        Assign { value, .. } => check_expr(value, options, warnings),
+        GridIndex { row, col, .. } => {
+            check_expr(row, options, warnings);
+            check_expr(col, options, warnings);
+        }
+        GridAssign {
+            row, col, value, ..
+        } => {
+            check_expr(value, options, warnings);
+            check_expr(row, options, warnings);
+            check_expr(col, options, warnings);
+        }
+        ArrayIndex { index, .. } => check_expr(index, options, warnings),
+        ArrayAssign { index, value, .. } => {
+            check_expr(value, options, warnings);
+            check_expr(index, options, warnings);
+        }
+        Call { args, .. }
+        | Vec2Constructor(args)
+        | Vec3Constructor(args)
+        | Vec4Constructor(args)
+        | BVec2Constructor(args)
+        | BVec3Constructor(args)
+        | BVec4Constructor(args)
+        | Mat2Constructor(args)
+        | Mat3Constructor(args)
+        | Mat4Constructor(args) => {
+            for arg in args {
+                check_expr(arg, options, warnings);
+            }
+        }
+        Swizzle { expr: inner, .. } => check_expr(inner, options, warnings),
+        Number(_) | IntNumber(_) | Bool(_) | Variable(_) | PreIncrement(_) | PreDecrement(_)
+        | PostIncrement(_) | PostDecrement(_) => {}
+    }
+}
+
+fn check_integer_division(
+    div: &Expr,
+    left: &Expr,
+    right: &Expr,
+    warnings: &mut Vec<CompileWarning>,
+) {
+    if div.ty != Some(Type::Int32) {
+        return;
+    }
+    let (Some(lhs), Some(rhs)) = (int_literal(left), int_literal(right)) else {
+        return;
+    };
+    // Division by zero and exact quotients aren't surprising in this way
+    if lhs.checked_rem(rhs).is_some_and(|rem| rem != 0) {
+        warnings.push(CompileWarning {
+            kind: WarningKind::IntegerDivision { lhs, rhs },
+            span: div.span,
+        });
+    }
+}
+
+/// Whether every path through `func` calls `func` before returning
+///
+/// A heuristic: it doesn't evaluate conditions, so a guard that can never be
+/// taken still counts as a base case, and loops may run zero times.
+fn always_recurses(func: &FunctionDef) -> bool {
+    let Some(body) = &func.body else {
+        return false;
+    };
+    let flow = block_flow(body, func);
+    flow.always_calls && !flow.may_return
+}
+
+/// How control reaches the end of a statement, relative to self-calls
+#[derive(Debug, Clone, Copy, Default)]
+struct Flow {
+    /// Every path through the statement evaluates a self-call
+    always_calls: bool,
+    /// Some path returns before any self-call
+    may_return: bool,
+}
+
+fn block_flow(stmts: &[Stmt], func: &FunctionDef) -> Flow {
+    let mut flow = Flow::default();
+    for stmt in stmts {
+        let next = stmt_flow(stmt, func);
+        flow.may_return |= next.may_return;
+        if next.always_calls {
+            // Anything after this runs only once the recursion has happened
+            flow.always_calls = true;
+            break;
+        }
+    }
+    flow
+}
+
+fn stmt_flow(stmt: &Stmt, func: &FunctionDef) -> Flow {
+    let calls = |expr: &Expr| Flow {
+        always_calls: always_calls(expr, func),
+        may_return: false,
+    };
+    match &stmt.kind {
+        StmtKind::VarDecl { init, .. } => init.as_ref().map(calls).unwrap_or_default(),
+        StmtKind::Expr(expr) => calls(expr),
+        StmtKind::Return(expr) => {
+            let always_calls = always_calls(expr, func);
+            Flow {
+                always_calls,
+                may_return: !always_calls,
+            }
+        }
+        StmtKind::Block(stmts) => block_flow(stmts, func),
+        StmtKind::If {
+            condition,
+            then_stmt,
+            else_stmt,
+        } => {
+            if always_calls(condition, func) {
+                return calls(condition);
+            }
+            let then_flow = stmt_flow(then_stmt, func);
+            let else_flow = else_stmt
+                .as_deref()
+                .map(|s| stmt_flow(s, func))
+                .unwrap_or_default();
+            Flow {
+                always_calls: then_flow.always_calls && else_flow.always_calls,
+                may_return: then_flow.may_return || else_flow.may_return,
+            }
+        }
+        StmtKind::While { condition, body } => {
+            if always_calls(condition, func) {
+                return calls(condition);
+            }
+            Flow {
+                always_calls: false,
+                may_return: stmt_flow(body, func).may_return,
+            }
+        }
+        // The body runs at least once, but may `break` before the condition
+        StmtKind::DoWhile { body, .. } => stmt_flow(body, func),
+        StmtKind::Switch { selector, cases } => {
+            if always_calls(selector, func) {
+                return calls(selector);
+            }
+            Flow {
+                always_calls: false,
+                may_return: cases
+                    .iter()
+                    .any(|case| block_flow(&case.body, func).may_return),
+            }
+        }
+        StmtKind::For {
+            init,
+            condition,
+            body,
+            ..
+        } => {
+            let init_flow = init
+                .as_deref()
+                .map(|s| stmt_flow(s, func))
+                .unwrap_or_default();
+            if init_flow.always_calls || condition.as_ref().is_some_and(|c| always_calls(c, func)) {
+                return Flow {
+                    always_calls: true,
+                    may_return: false,
+                };
+            }
+            Flow {
+                always_calls: false,
+                may_return: stmt_flow(body, func).may_return,
+            }
+        }
+        StmtKind::ConstDecl { .. } | StmtKind::Break | StmtKind::Continue => Flow::default(),
+    }
+}
+
+/// Whether evaluating `expr` always calls `func`
+fn always_calls(expr: &Expr, func: &FunctionDef) -> bool {
+    use ExprKind::*;
+
+    match &expr.kind {
+        Call { name, args } => {
+            (name == &func.name && args.len() == func.params.len())
+                || args.iter().any(|arg| always_calls(arg, func))
+        }
+        // The right side of a short-circuit operator may be skipped
+        And(left, _) | Or(left, _) => always_calls(left, func),
+        Add(left, right)
+        | Sub(left, right)
+        | Mul(left, right)
+        | Div(left, right)
+        | Mod(left, right)
+        | BitwiseAnd(left, right)
+        | BitwiseOr(left, right)
+        | BitwiseXor(left, right)
+        | LeftShift(left, right)
+        | RightShift(left, right)
+        | Less(left, right)
+        | Greater(left, right)
+        | LessEq(left, right)
+        | GreaterEq(left, right)
+        | Eq(left, right)
+        | NotEq(left, right) => always_calls(left, func) || always_calls(right, func),
+        Neg(operand) | BitwiseNot(operand) | Not(operand) => always_calls(operand, func),
+        Ternary {
+            condition,
+            true_expr,
+            false_expr,
+        } => {
+            always_calls(condition, func)
+                || (always_calls(true_expr, func) && always_calls(false_expr, func))
+        }
+        Assign { value, .. } => always_calls(value, func),
+        GridIndex { row, col, .. } => always_calls(row, func) || always_calls(col, func),
+        GridAssign {
+            row, col, value, ..
+        } => always_calls(value, func) || always_calls(row, func) || always_calls(col, func),
+        ArrayIndex { index, .. } => always_calls(index, func),
+        ArrayAssign { index, value, .. } => always_calls(value, func) || always_calls(index, func),
+        Vec2Constructor(args)
+        | Vec3Constructor(args)
+        | Vec4Constructor(args)
+        | BVec2Constructor(args)
+        | BVec3Constructor(args)
+        | BVec4Constructor(args)
+        | Mat2Constructor(args)
+        | Mat3Constructor(args)
+        | Mat4Constructor(args) => args.iter().any(|arg| always_calls(arg, func)),
+        Swizzle { expr: inner, .. } => always_calls(inner, func),
+        Number(_) | IntNumber(_) | Bool(_) | Variable(_) | PreIncrement(_) | PreDecrement(_)
+        | PostIncrement(_) | PostDecrement(_) => false,
+    }
+}
+
+/// An int literal, including a negated one
+fn int_literal(expr: &Expr) -> Option<i32> {
+    match &expr.kind {
+        ExprKind::IntNumber(n) => Some(*n),
+        ExprKind::Neg(inner) => match inner.kind {
+            ExprKind::IntNumber(n) => n.checked_neg(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::{compile_expr_with_warnings, compile_script_with_warnings, OptimizeOptions};
+
+    fn script_warnings(source: &str) -> Vec<CompileWarning> {
+        let (_, warnings) =
+            compile_script_with_warnings(source, &OptimizeOptions::all(), &LintOptions::script())
+                .expect("script should compile");
+        warnings
+    }
+
+    #[test]
+    fn test_integer_division_warns() {
+        let warnings = script_warnings("int x = 5 / 2; return 1.0;");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].kind,
+            WarningKind::IntegerDivision { lhs: 5, rhs: 2 }
+        );
+    }
+
+    #[test]
+    fn test_float_division_does_not_warn() {
+        assert!(script_warnings("return 5.0 / 2.0;").is_empty());
+    }
+
+    #[test]
+    fn test_exact_integer_division_does_not_warn() {
+        assert!(script_warnings("int x = 6 / 2; int y = 1 / 0; return 1.0;").is_empty());
+    }
+
+    #[test]
+    fn test_integer_division_in_function_body() {
+        let warnings = script_warnings(
+            "
+            int half() { return -7 / 2; }
+            return 1.0;
+        ",
+        );
+        assert_eq!(
+            warnings[0].kind,
+            WarningKind::IntegerDivision { lhs: -7, rhs: 2 }
+        );
+    }
+
+    #[test]
+    fn test_integer_division_suppressible() {
+        let (_, warnings) = compile_script_with_warnings(
+            "int x = 5 / 2; return 1.0;",
+            &OptimizeOptions::all(),
+            &LintOptions::none(),
+        )
+        .unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_integer_division_off_in_expression_mode() {
+        let (_, warnings) =
+            compile_expr_with_warnings("5 / 2", &OptimizeOptions::all(), &LintOptions::expr())
+                .unwrap();
+        assert!(warnings.is_empty());
+
+        let (_, warnings) =
+            compile_expr_with_warnings("5 / 2", &OptimizeOptions::all(), &LintOptions::script())
+                .unwrap();
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_unconditional_recursion_warns() {
+        let warnings = script_warnings(
+            "
+            float f(float x) { return f(x - 1.0) + 1.0; }
+            return 0.0;
+        ",
+        );
+        assert_eq!(
+            warnings,
+            vec![CompileWarning {
+                kind: WarningKind::InfiniteRecursion {
+                    function: "f".into()
+                },
+                span: warnings[0].span,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_recursion_in_both_branches_warns() {
+        let warnings = script_warnings(
+            "
+            float f(float x) {
+                if (x > 0.0) { return f(x - 1.0); } else { return f(x + 1.0); }
+            }
+            return 0.0;
+        ",
+        );
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_guarded_recursion_does_not_warn() {
+        assert!(script_warnings(
+            "
+            float f(float x) {
+                if (x <= 0.0) { return 0.0; }
+                return f(x - 1.0) + 1.0;
+            }
+            float g(float x) { return x > 0.0 ? g(x - 1.0) : 0.0; }
+            return 0.0;
+        ",
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_warning_message_suggests_float_literals() {
+        let warnings = script_warnings("int x = 5 / 2; return 1.0;");
+        let message = alloc::format!("{}", warnings[0]);
+        assert!(message.contains("truncates to 2"));
+        assert!(message.contains("5.0 / 2.0"));
+    }
+}
@@ -0,0 +1,217 @@
+/// Per-phase compile timings and sizes, see `compile_script_with_metrics`
+use crate::compiler::ast::Program;
+#[cfg(feature = "std")]
+use crate::compiler::ast::{Expr, ExprKind, Stmt, StmtKind};
+use crate::compiler::typechecker::FunctionTable;
+
+/// A stage of the script compiler
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompilePhase {
+    Lex,
+    Parse,
+    /// Function analysis, type checking and lints
+    Typecheck,
+    /// AST and opcode optimization
+    Optimize,
+    /// Opcode generation and the opcode set check
+    Codegen,
+}
+
+/// Notified by the compiler as it finishes each phase
+///
+/// A phase may finish more than once (optimization runs before and after
+/// codegen); observers should add up the time between calls.
+pub(crate) trait CompileObserver {
+    fn phase_done(&mut self, _phase: CompilePhase) {}
+    fn parsed(&mut self, _program: &Program) {}
+    fn checked(&mut self, _program: &Program, _func_table: &FunctionTable) {}
+}
+
+/// Observer for plain compiles
+impl CompileObserver for () {}
+
+/// Number of statement and expression nodes in `program`
+#[cfg(feature = "std")]
+pub(crate) fn count_ast_nodes(program: &Program) -> usize {
+    program
+        .consts
+        .iter()
+        .chain(
+            program
+                .functions
+                .iter()
+                .flat_map(|func| func.body.iter().flatten()),
+        )
+        .chain(program.stmts.iter())
+        .map(count_stmt)
+        .sum()
+}
+
+#[cfg(feature = "std")]
+fn count_stmt(stmt: &Stmt) -> usize {
+    1 + match &stmt.kind {
+        StmtKind::VarDecl { init, .. } => init.as_ref().map_or(0, count_expr),
+        StmtKind::ConstDecl { init: expr, .. } | StmtKind::Return(expr) | StmtKind::Expr(expr) => {
+            count_expr(expr)
+        }
+        StmtKind::Block(stmts) => stmts.iter().map(count_stmt).sum(),
+        StmtKind::If {
+            condition,
+            then_stmt,
+            else_stmt,
+        } => {
+            count_expr(condition)
+                + count_stmt(then_stmt)
+                + else_stmt.as_deref().map_or(0, count_stmt)
+        }
+        StmtKind::While { condition, body } | StmtKind::DoWhile { body, condition } => {
+            count_expr(condition) + count_stmt(body)
+        }
+        StmtKind::Switch { selector, cases } => {
+            count_expr(selector)
+                + cases
+                    .iter()
+                    .flat_map(|case| &case.body)
+                    .map(count_stmt)
+                    .sum::<usize>()
+        }
+        StmtKind::For {
+            init,
+            condition,
+            increment,
+            body,
+        } => {
+            init.as_deref().map_or(0, count_stmt)
+                + condition.as_ref().map_or(0, count_expr)
+                + increment.as_ref().map_or(0, count_expr)
+                + count_stmt(body)
+        }
+        StmtKind::Break | StmtKind::Continue => 0,
+    }
+}
+
+#[cfg(feature = "std")]
+fn count_expr(expr: &Expr) -> usize {
+    use ExprKind::*;
+
+    1 + match &expr.kind {
+        Add(left, right)
+        | Sub(left, right)
+        | Mul(left, right)
+        | Div(left, right)
+        | Mod(left, right)
+        | BitwiseAnd(left, right)
+        | BitwiseOr(left, right)
+        | BitwiseXor(left, right)
+        | LeftShift(left, right)
+        | RightShift(left, right)
+        | Less(left, right)
+        | Greater(left, right)
+        | LessEq(left, right)
+        | GreaterEq(left, right)
+        | Eq(left, right)
+        | NotEq(left, right)
+        | And(left, right)
+        | Or(left, right) => count_expr(left) + count_expr(right),
+        Neg(operand) | BitwiseNot(operand) | Not(operand) => count_expr(operand),
+        Ternary {
+            condition,
+            true_expr,
+            false_expr,
+        } => count_expr(condition) + count_expr(true_expr) + count_expr(false_expr),
+        Assign { value, .. } => count_expr(value),
+        GridIndex { row, col, .. } => count_expr(row) + count_expr(col),
+        GridAssign {
+            row, col, value, ..
+        } => count_expr(row) + count_expr(col) + count_expr(value),
+        ArrayIndex { index, .. } => count_expr(index),
+        ArrayAssign { index, value, .. } => count_expr(index) + count_expr(value),
+        Call { args, .. }
+        | Vec2Constructor(args)
+        | Vec3Constructor(args)
+        | Vec4Constructor(args)
+        | BVec2Constructor(args)
+        | BVec3Constructor(args)
+        | BVec4Constructor(args)
+        | Mat2Constructor(args)
+        | Mat3Constructor(args)
+        | Mat4Constructor(args) => args.iter().map(count_expr).sum(),
+        Swizzle { expr: inner, .. } => count_expr(inner),
+        Number(_) | IntNumber(_) | Bool(_) | Variable(_) | PreIncrement(_) | PreDecrement(_)
+        | PostIncrement(_) | PostDecrement(_) => 0,
+    }
+}
+
+#[cfg(feature = "std")]
+pub use timed::CompileMetrics;
+#[cfg(feature = "std")]
+pub(crate) use timed::MetricsRecorder;
+
+#[cfg(feature = "std")]
+mod timed {
+    extern crate std;
+    use std::time::{Duration, Instant};
+
+    use super::{count_ast_nodes, CompileObserver, CompilePhase};
+    use crate::compiler::ast::Program;
+
+    /// Time spent in each compiler phase, plus the size of its input and output
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct CompileMetrics {
+        pub lex: Duration,
+        pub parse: Duration,
+        /// Function analysis, type checking and lints
+        pub typecheck: Duration,
+        /// AST and opcode optimization
+        pub optimize: Duration,
+        pub codegen: Duration,
+        /// Statement and expression nodes in the parsed AST
+        pub ast_nodes: usize,
+        /// Opcodes across all functions of the compiled program
+        pub opcodes: usize,
+    }
+
+    impl CompileMetrics {
+        /// Sum of all phase durations
+        pub fn total(&self) -> Duration {
+            self.lex + self.parse + self.typecheck + self.optimize + self.codegen
+        }
+    }
+
+    /// Observer that fills in `CompileMetrics` as the phases finish
+    pub(crate) struct MetricsRecorder {
+        pub(crate) metrics: CompileMetrics,
+        last: Instant,
+    }
+
+    impl MetricsRecorder {
+        pub(crate) fn start() -> Self {
+            MetricsRecorder {
+                metrics: CompileMetrics::default(),
+                last: Instant::now(),
+            }
+        }
+    }
+
+    impl CompileObserver for MetricsRecorder {
+        fn phase_done(&mut self, phase: CompilePhase) {
+            let now = Instant::now();
+            let elapsed = now - self.last;
+            self.last = now;
+            let slot = match phase {
+                CompilePhase::Lex => &mut self.metrics.lex,
+                CompilePhase::Parse => &mut self.metrics.parse,
+                CompilePhase::Typecheck => &mut self.metrics.typecheck,
+                CompilePhase::Optimize => &mut self.metrics.optimize,
+                CompilePhase::Codegen => &mut self.metrics.codegen,
+            };
+            *slot += elapsed;
+        }
+
+        fn parsed(&mut self, program: &Program) {
+            self.metrics.ast_nodes = count_ast_nodes(program);
+            // Don't bill the count to the next phase
+            self.last = Instant::now();
+        }
+    }
+}
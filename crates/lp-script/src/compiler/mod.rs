@@ -8,10 +8,16 @@ pub mod stmt;
 
 pub mod analyzer;
 pub mod ast;
+pub mod builtins;
 pub mod codegen;
+pub mod const_eval;
 pub mod error;
+pub mod info;
 pub mod lexer;
+pub mod lint;
+pub mod metrics;
 pub mod optimize;
+pub mod options;
 pub mod parser;
 pub mod symbol_table;
 pub mod test_ast;
@@ -2,8 +2,12 @@
 ///
 /// Applies algebraic identities to simplify expressions.
 extern crate alloc;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
 
 use crate::compiler::ast::{Expr, ExprKind};
+use crate::shared::Type;
 
 /// Simplify an expression tree using algebraic identities
 /// Returns true if the expression was modified
@@ -20,9 +24,14 @@ pub fn simplify_expr(expr: &mut Expr) -> bool {
             changed |= simplify_expr(left.as_mut());
             changed |= simplify_expr(right.as_mut());
         }
-        ExprKind::Neg(operand) => {
+        ExprKind::Neg(operand) | ExprKind::Not(operand) => {
             changed |= simplify_expr(operand.as_mut());
         }
+        ExprKind::Call { args, .. } => {
+            for arg in args.iter_mut() {
+                changed |= simplify_expr(arg);
+            }
+        }
         _ => {}
     }
 
@@ -85,8 +94,91 @@ pub fn simplify_expr(expr: &mut Expr) -> bool {
             }
         }
 
+        // !!x = x for a bool, x != 0 otherwise
+        ExprKind::Not(operand) => {
+            if let ExprKind::Not(inner) = &operand.kind {
+                if let Some(coerced) = truthiness(inner) {
+                    *expr = coerced;
+                    return true;
+                }
+            }
+        }
+
+        // max(0, min(1, x)), min(1, max(0, x)), clamp(x, 0, 1) = saturate(x)
+        ExprKind::Call { .. } => {
+            if let Some(x) = saturate_operand(expr) {
+                let x = x.clone();
+                expr.kind = ExprKind::Call {
+                    name: String::from("saturate"),
+                    args: vec![x],
+                };
+                return true;
+            }
+        }
+
         _ => {}
     }
 
     changed
 }
+
+/// `x` as a bool: itself if it already is one, else `x != 0` for a scalar
+fn truthiness(x: &Expr) -> Option<Expr> {
+    let zero = match x.ty.as_ref()? {
+        Type::Bool => return Some(x.clone()),
+        Type::Int32 => ExprKind::IntNumber(0),
+        Type::Fixed => ExprKind::Number(0.0),
+        _ => return None,
+    };
+    let mut zero = Expr::new(zero, x.span);
+    zero.ty = x.ty.clone();
+    let mut coerced = Expr::new(ExprKind::NotEq(Box::new(x.clone()), Box::new(zero)), x.span);
+    coerced.ty = Some(Type::Bool);
+    Some(coerced)
+}
+
+/// The `x` of a scalar clamp to `0..1`, in any of the forms above
+fn saturate_operand(expr: &Expr) -> Option<&Expr> {
+    let ExprKind::Call { name, args } = &expr.kind else {
+        return None;
+    };
+    let x = match (name.as_str(), args.as_slice()) {
+        ("clamp", [x, lo, hi]) if is_literal(lo, 0.0) && is_literal(hi, 1.0) => x,
+        ("max", [a, b]) => bounded_by(a, b, "min", 1.0, 0.0)?,
+        ("min", [a, b]) => bounded_by(a, b, "max", 0.0, 1.0)?,
+        _ => return None,
+    };
+    let scalar = |e: &Expr| e.ty == Some(Type::Fixed);
+    (scalar(expr) && scalar(x)).then_some(x)
+}
+
+/// `x` when one of `a`, `b` is the literal `outer` and the other is
+/// `inner(x, inner_bound)` in either argument order
+fn bounded_by<'e>(
+    a: &'e Expr,
+    b: &'e Expr,
+    inner: &str,
+    inner_bound: f32,
+    outer: f32,
+) -> Option<&'e Expr> {
+    let nested = match (is_literal(a, outer), is_literal(b, outer)) {
+        (true, _) => b,
+        (_, true) => a,
+        _ => return None,
+    };
+    match &nested.kind {
+        ExprKind::Call { name, args } if name == inner => match args.as_slice() {
+            [x, bound] | [bound, x] if is_literal(bound, inner_bound) => Some(x),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_literal(expr: &Expr, value: f32) -> bool {
+    match expr.kind {
+        ExprKind::Number(n) => n == value,
+        ExprKind::IntNumber(n) => n as f32 == value,
+        _ => false,
+    }
+}
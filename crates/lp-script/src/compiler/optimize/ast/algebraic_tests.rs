@@ -3,6 +3,7 @@
 mod algebraic_simplification_tests {
     use crate::compiler::optimize::ast::algebraic;
     use crate::compiler::optimize::ast_test_util::AstOptTest;
+    use crate::shared::Type;
 
     // ============================================================================
     // Addition identities
@@ -275,15 +276,36 @@ mod algebraic_simplification_tests {
 
     #[test]
     fn test_double_logical_negation() {
-        // !(!x) → x
+        // !(!x) → x != 0 for a number
         AstOptTest::new("!!time")
             .with_pass(algebraic::simplify_expr)
+            .expect_ast(|b| {
+                let time = b.var("time");
+                let zero = b.num(0.0);
+                b.not_eq(time, zero)
+            })
             .expect_semantics_preserved()
             .with_time(1.0)
             .run()
             .unwrap();
     }
 
+    #[test]
+    fn test_double_logical_negation_of_bool() {
+        // !(!x) → x for a bool
+        AstOptTest::new("!!(time > 1.0)")
+            .with_pass(algebraic::simplify_expr)
+            .expect_ast(|b| {
+                let time = b.var("time");
+                let one = b.num(1.0);
+                b.greater(time, one)
+            })
+            .expect_semantics_preserved()
+            .with_time(2.0)
+            .run()
+            .unwrap();
+    }
+
     #[test]
     fn test_double_arithmetic_negation() {
         // -(-x) → x
@@ -350,6 +372,73 @@ mod algebraic_simplification_tests {
         // TODO: Add test with proper vec2/vec3 usage once supported
     }
 
+    // ============================================================================
+    // Saturate fusion
+    // ============================================================================
+
+    #[test]
+    fn test_max_min_to_saturate() {
+        // max(0, min(1, x)) → saturate(x)
+        AstOptTest::new("max(0.0, min(1.0, time))")
+            .with_pass(algebraic::simplify_expr)
+            .expect_ast(|b| {
+                let time = b.var("time");
+                b.call("saturate", vec![time], Type::Fixed)
+            })
+            .expect_semantics_preserved()
+            .with_time(1.5)
+            .run()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_min_max_to_saturate() {
+        // min(max(x, 0), 1) → saturate(x)
+        AstOptTest::new("min(max(time, 0.0), 1.0)")
+            .with_pass(algebraic::simplify_expr)
+            .expect_ast(|b| {
+                let time = b.var("time");
+                b.call("saturate", vec![time], Type::Fixed)
+            })
+            .expect_semantics_preserved()
+            .with_time(-2.0)
+            .run()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_clamp_unit_to_saturate() {
+        // clamp(x, 0, 1) → saturate(x)
+        AstOptTest::new("clamp(time, 0.0, 1.0)")
+            .with_pass(algebraic::simplify_expr)
+            .expect_ast(|b| {
+                let time = b.var("time");
+                b.call("saturate", vec![time], Type::Fixed)
+            })
+            .expect_semantics_preserved()
+            .with_time(0.25)
+            .run()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_other_clamps_unchanged() {
+        // Bounds other than 0..1 aren't a saturate
+        AstOptTest::new("max(0.0, min(2.0, time))")
+            .with_pass(algebraic::simplify_expr)
+            .expect_ast(|b| {
+                let zero = b.num(0.0);
+                let two = b.num(2.0);
+                let time = b.var("time");
+                let inner = b.call("min", vec![two, time], Type::Fixed);
+                b.call("max", vec![zero, inner], Type::Fixed)
+            })
+            .expect_semantics_preserved()
+            .with_time(1.5)
+            .run()
+            .unwrap();
+    }
+
     // ============================================================================
     // No-op tests (ensure we don't break valid expressions)
     // ============================================================================
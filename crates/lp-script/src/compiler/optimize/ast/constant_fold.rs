@@ -1,6 +1,7 @@
 use lp_math::fixed::{
     ceil as fixed_ceil, cos as fixed_cos, floor as fixed_floor, lerp as fixed_lerp,
-    pow as fixed_pow, saturate as fixed_saturate, sin as fixed_sin, sqrt as fixed_sqrt, Fixed,
+    pow as fixed_pow, remap as fixed_remap, saturate as fixed_saturate, sin as fixed_sin,
+    sqrt as fixed_sqrt, Fixed,
 };
 
 /// Constant folding optimization (LpBox AST)
@@ -8,6 +9,9 @@ use lp_math::fixed::{
 /// TODO: Re-implement full constant folding for the new recursive AST. For now
 /// this pass simply traverses the expression tree and returns whether a change
 /// was made. No actual folding is performed yet.
+extern crate alloc;
+use alloc::boxed::Box;
+
 use crate::compiler::ast::{Expr, ExprKind};
 use crate::shared::Type;
 
@@ -70,6 +74,7 @@ fn const_value(expr: &Expr) -> Option<ConstValue> {
             Some(Type::Int32) => Some(ConstValue::Int(*n as i32)),
             _ => Some(ConstValue::Float(*n)),
         },
+        ExprKind::Bool(b) => Some(ConstValue::Bool(*b)),
         ExprKind::IntNumber(i) => {
             if matches!(expr.ty, Some(Type::Bool)) {
                 Some(ConstValue::Bool(*i != 0))
@@ -138,6 +143,14 @@ fn fold_call(name: &str, args: &mut [Expr], keep_existing_ty: bool) -> Option<Fo
             let result = fixed_cos(value.as_fixed());
             Some(replacement_number(result.to_f32(), keep_existing_ty))
         }
+        "int" if args.len() == 1 => {
+            let value = const_value(&args[0])?.as_fixed();
+            Some(replacement_int(value.0 / Fixed::ONE.0, keep_existing_ty))
+        }
+        "float" if args.len() == 1 => {
+            let value = const_value(&args[0])?.as_fixed();
+            Some(replacement_number(value.to_f32(), keep_existing_ty))
+        }
         "sqrt" if args.len() == 1 => {
             let value = const_value(&args[0])?;
             let result = fixed_sqrt(value.as_fixed());
@@ -203,6 +216,38 @@ fn fold_call(name: &str, args: &mut [Expr], keep_existing_ty: bool) -> Option<Fo
             let result = fixed_lerp(a, b, t);
             Some(replacement_number(result.to_f32(), keep_existing_ty))
         }
+        "remap" if args.len() == 5 => {
+            let in_min = const_value(&args[1])?.as_fixed();
+            let in_max = const_value(&args[2])?.as_fixed();
+            let out_min = const_value(&args[3])?.as_fixed();
+            let out_max = const_value(&args[4])?.as_fixed();
+            if let Some(v) = const_value(&args[0]) {
+                let result = fixed_remap(v.as_fixed(), in_min, in_max, out_min, out_max);
+                return Some(replacement_number(result.to_f32(), keep_existing_ty));
+            }
+            if in_min == in_max {
+                return Some(replacement_number(out_min.to_f32(), keep_existing_ty));
+            }
+            // Constant ranges: v * scale + offset
+            let scale = (out_max - out_min) / (in_max - in_min);
+            let offset = out_min - in_min * scale;
+            let span = args[0].span;
+            let number = |n: Fixed| Expr {
+                kind: ExprKind::Number(n.to_f32()),
+                span,
+                ty: Some(Type::Fixed),
+            };
+            let product = Expr {
+                kind: ExprKind::Mul(Box::new(args[0].clone()), Box::new(number(scale))),
+                span,
+                ty: Some(Type::Fixed),
+            };
+            Some(FoldReplacement::new(
+                ExprKind::Add(Box::new(product), Box::new(number(offset))),
+                Some(Type::Fixed),
+                keep_existing_ty,
+            ))
+        }
         "saturate" if args.len() == 1 => {
             let value = const_value(&args[0])?.as_fixed();
             let result = fixed_saturate(value);
@@ -353,11 +398,34 @@ pub fn fold_constants(expr: &mut Expr) -> bool {
         Assign { value, .. } => {
             changed |= fold_constants(value.as_mut());
         }
+        GridIndex { row, col, .. } => {
+            changed |= fold_constants(row.as_mut());
+            changed |= fold_constants(col.as_mut());
+        }
+        GridAssign {
+            row, col, value, ..
+        } => {
+            changed |= fold_constants(row.as_mut());
+            changed |= fold_constants(col.as_mut());
+            changed |= fold_constants(value.as_mut());
+        }
+        ArrayIndex { index, .. } => {
+            changed |= fold_constants(index.as_mut());
+        }
+        ArrayAssign { index, value, .. } => {
+            changed |= fold_constants(index.as_mut());
+            changed |= fold_constants(value.as_mut());
+        }
         Call { args, .. }
         | Vec2Constructor(args)
         | Vec3Constructor(args)
         | Vec4Constructor(args)
-        | Mat3Constructor(args) => {
+        | BVec2Constructor(args)
+        | BVec3Constructor(args)
+        | BVec4Constructor(args)
+        | Mat2Constructor(args)
+        | Mat3Constructor(args)
+        | Mat4Constructor(args) => {
             for arg in args.iter_mut() {
                 changed |= fold_constants(arg);
             }
@@ -365,7 +433,7 @@ pub fn fold_constants(expr: &mut Expr) -> bool {
         Swizzle { expr: inner, .. } => {
             changed |= fold_constants(inner.as_mut());
         }
-        Number(_) | IntNumber(_) | Variable(_) | PreIncrement(_) | PreDecrement(_)
+        Number(_) | IntNumber(_) | Bool(_) | Variable(_) | PreIncrement(_) | PreDecrement(_)
         | PostIncrement(_) | PostDecrement(_) => {}
     }
 
@@ -467,11 +535,17 @@ pub fn fold_constants(expr: &mut Expr) -> bool {
             true_expr,
             false_expr,
         } => fold_ternary(condition.as_ref(), true_expr.as_ref(), false_expr.as_ref()),
-        Assign { .. } => None,
+        Assign { .. }
+        | GridIndex { .. }
+        | GridAssign { .. }
+        | ArrayIndex { .. }
+        | ArrayAssign { .. } => None,
         Call { name, args } => fold_call(name, args.as_mut_slice(), true),
-        Vec2Constructor(_) | Vec3Constructor(_) | Vec4Constructor(_) | Mat3Constructor(_) => None,
+        Vec2Constructor(_) | Vec3Constructor(_) | Vec4Constructor(_) | BVec2Constructor(_)
+        | BVec3Constructor(_) | BVec4Constructor(_) | Mat2Constructor(_) | Mat3Constructor(_)
+        | Mat4Constructor(_) => None,
         Swizzle { .. } => None,
-        Number(_) | IntNumber(_) | Variable(_) | PreIncrement(_) | PreDecrement(_)
+        Number(_) | IntNumber(_) | Bool(_) | Variable(_) | PreIncrement(_) | PreDecrement(_)
         | PostIncrement(_) | PostDecrement(_) => None,
     };
 
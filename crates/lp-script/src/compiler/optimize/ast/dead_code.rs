@@ -1,7 +1,7 @@
 /// Dead code elimination
 ///
 /// Removes unreachable code:
-/// - Statements after return
+/// - Statements after return, break or continue
 /// - Branches of if statements with constant conditions
 /// - Empty blocks
 extern crate alloc;
@@ -13,7 +13,7 @@ use crate::shared::Span;
 
 /// Eliminate dead statements from a block
 ///
-/// Removes all statements after the first return statement
+/// Removes all statements after the first return (or break/continue)
 pub fn eliminate_dead_stmts(stmts: Vec<Stmt>) -> Vec<Stmt> {
     let mut result = Vec::new();
 
@@ -33,7 +33,8 @@ pub fn eliminate_dead_stmts(stmts: Vec<Stmt>) -> Vec<Stmt> {
 /// Check if a statement contains a return (or always returns)
 fn contains_return(stmt: &Stmt) -> bool {
     match &stmt.kind {
-        StmtKind::Return(_) => true,
+        // Jump out of the block just the same
+        StmtKind::Return(_) | StmtKind::Break | StmtKind::Continue => true,
         StmtKind::Block(stmts) => {
             // Block returns if any statement in it returns
             stmts.iter().any(contains_return)
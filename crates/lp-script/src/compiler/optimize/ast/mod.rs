@@ -2,10 +2,12 @@
 extern crate alloc;
 
 use crate::compiler::ast::{Expr, Program, Stmt};
+use crate::compiler::func::FunctionTable;
 use crate::compiler::optimize::OptimizeOptions;
 
 pub mod algebraic;
 pub mod constant_fold;
+pub mod pure_calls;
 // pub mod dead_code; // TODO: Update to new API
 
 #[cfg(test)]
@@ -36,7 +38,11 @@ pub fn optimize_expr(expr: &mut Expr, options: &OptimizeOptions) {
 }
 
 /// Optimize a program
-pub fn optimize_program(program: &mut Program, options: &OptimizeOptions) {
+pub fn optimize_program(
+    program: &mut Program,
+    func_table: &FunctionTable,
+    options: &OptimizeOptions,
+) {
     if options.max_ast_passes == 0 {
         return;
     }
@@ -50,6 +56,11 @@ pub fn optimize_program(program: &mut Program, options: &OptimizeOptions) {
             changed |= optimize_stmt(stmt, options);
         }
 
+        // Evaluate pure user function calls whose arguments are now constant
+        if options.constant_folding {
+            changed |= pure_calls::fold_pure_calls(program, func_table);
+        }
+
         // Stop if no changes
         if !changed {
             break;
@@ -94,10 +105,16 @@ fn optimize_stmt(stmt: &mut Stmt, options: &OptimizeOptions) -> bool {
                 changed |= optimize_stmt(else_s.as_mut(), options);
             }
         }
-        StmtKind::While { condition, body } => {
+        StmtKind::While { condition, body } | StmtKind::DoWhile { body, condition } => {
             optimize_expr(condition, options);
             changed |= optimize_stmt(body.as_mut(), options);
         }
+        StmtKind::Switch { selector, cases } => {
+            optimize_expr(selector, options);
+            for s in cases.iter_mut().flat_map(|case| &mut case.body) {
+                changed |= optimize_stmt(s, options);
+            }
+        }
         StmtKind::For {
             init,
             condition,
@@ -115,6 +132,8 @@ fn optimize_stmt(stmt: &mut Stmt, options: &OptimizeOptions) -> bool {
             }
             changed |= optimize_stmt(body.as_mut(), options);
         }
+        // Already folded to a literal by the type checker
+        StmtKind::ConstDecl { .. } | StmtKind::Break | StmtKind::Continue => {}
     }
 
     changed
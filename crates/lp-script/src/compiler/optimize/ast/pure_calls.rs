@@ -0,0 +1,352 @@
+/// Compile-time evaluation of pure user function calls
+///
+/// A call to a user function whose arguments are all literals is replaced by
+/// its result when the function is side-effect free: no built-in inputs
+/// (`uv`, `time`, ...), no calls to other user functions (which also rules out
+/// recursion), no loops, no texture/palette/array access and nothing that
+/// depends on VM settings, like the noise backend, normalize fallback, eq
+/// epsilon or overflow check. The function is compiled and run in a small VM
+/// to produce the value.
+extern crate alloc;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::compiler::ast::{Expr, ExprKind, FunctionDef, Program, Stmt, StmtKind};
+use crate::compiler::codegen::{CodeGenerator, LocalAllocator};
+use crate::compiler::func::func_gen::gen_user_function;
+use crate::compiler::func::FunctionTable;
+use crate::fixed::Fixed;
+use crate::shared::{Span, Type};
+use crate::vm::opcodes::LpsOpCode;
+use crate::vm::{FunctionDef as VmFunctionDef, LpsProgram, LpsVm, VmLimits};
+
+/// Instruction budget for evaluating a single call
+const MAX_EVAL_INSTRUCTIONS: usize = 1_000;
+
+/// Replace pure user function calls on constant arguments in top-level statements
+pub fn fold_pure_calls(program: &mut Program, func_table: &FunctionTable) -> bool {
+    let functions = &program.functions;
+    let mut changed = false;
+    for stmt in &mut program.stmts {
+        changed |= fold_stmt(stmt, functions, func_table);
+    }
+    changed
+}
+
+fn fold_stmt(stmt: &mut Stmt, functions: &[FunctionDef], func_table: &FunctionTable) -> bool {
+    let mut changed = false;
+    match &mut stmt.kind {
+        StmtKind::VarDecl { init, .. } => {
+            if let Some(init_expr) = init {
+                changed |= fold_expr(init_expr, functions, func_table);
+            }
+        }
+        StmtKind::Return(expr) | StmtKind::Expr(expr) => {
+            changed |= fold_expr(expr, functions, func_table);
+        }
+        StmtKind::Block(stmts) => {
+            for s in stmts {
+                changed |= fold_stmt(s, functions, func_table);
+            }
+        }
+        StmtKind::If {
+            condition,
+            then_stmt,
+            else_stmt,
+        } => {
+            changed |= fold_expr(condition, functions, func_table);
+            changed |= fold_stmt(then_stmt.as_mut(), functions, func_table);
+            if let Some(else_s) = else_stmt {
+                changed |= fold_stmt(else_s.as_mut(), functions, func_table);
+            }
+        }
+        StmtKind::While { condition, body } | StmtKind::DoWhile { body, condition } => {
+            changed |= fold_expr(condition, functions, func_table);
+            changed |= fold_stmt(body.as_mut(), functions, func_table);
+        }
+        StmtKind::Switch { selector, cases } => {
+            changed |= fold_expr(selector, functions, func_table);
+            for s in cases.iter_mut().flat_map(|case| &mut case.body) {
+                changed |= fold_stmt(s, functions, func_table);
+            }
+        }
+        StmtKind::For {
+            init,
+            condition,
+            increment,
+            body,
+        } => {
+            if let Some(init_stmt) = init {
+                changed |= fold_stmt(init_stmt.as_mut(), functions, func_table);
+            }
+            if let Some(cond) = condition {
+                changed |= fold_expr(cond, functions, func_table);
+            }
+            if let Some(inc) = increment {
+                changed |= fold_expr(inc, functions, func_table);
+            }
+            changed |= fold_stmt(body.as_mut(), functions, func_table);
+        }
+        // Already folded to a literal by the type checker
+        StmtKind::ConstDecl { .. } | StmtKind::Break | StmtKind::Continue => {}
+    }
+    changed
+}
+
+fn fold_expr(expr: &mut Expr, functions: &[FunctionDef], func_table: &FunctionTable) -> bool {
+    use ExprKind::*;
+
+    let mut changed = false;
+    match &mut expr.kind {
+        Add(left, right)
+        | Sub(left, right)
+        | Mul(left, right)
+        | Div(left, right)
+        | Mod(left, right)
+        | BitwiseAnd(left, right)
+        | BitwiseOr(left, right)
+        | BitwiseXor(left, right)
+        | LeftShift(left, right)
+        | RightShift(left, right)
+        | Less(left, right)
+        | Greater(left, right)
+        | LessEq(left, right)
+        | GreaterEq(left, right)
+        | Eq(left, right)
+        | NotEq(left, right)
+        | And(left, right)
+        | Or(left, right) => {
+            changed |= fold_expr(left, functions, func_table);
+            changed |= fold_expr(right, functions, func_table);
+        }
+        Neg(operand) | BitwiseNot(operand) | Not(operand) => {
+            changed |= fold_expr(operand, functions, func_table);
+        }
+        Ternary {
+            condition,
+            true_expr,
+            false_expr,
+        } => {
+            changed |= fold_expr(condition, functions, func_table);
+            changed |= fold_expr(true_expr, functions, func_table);
+            changed |= fold_expr(false_expr, functions, func_table);
+        }
+        Assign { value, .. } => {
+            changed |= fold_expr(value, functions, func_table);
+        }
+        GridIndex { row, col, .. } => {
+            changed |= fold_expr(row, functions, func_table);
+            changed |= fold_expr(col, functions, func_table);
+        }
+        GridAssign {
+            row, col, value, ..
+        } => {
+            changed |= fold_expr(row, functions, func_table);
+            changed |= fold_expr(col, functions, func_table);
+            changed |= fold_expr(value, functions, func_table);
+        }
+        ArrayIndex { index, .. } => {
+            changed |= fold_expr(index, functions, func_table);
+        }
+        ArrayAssign { index, value, .. } => {
+            changed |= fold_expr(index, functions, func_table);
+            changed |= fold_expr(value, functions, func_table);
+        }
+        Call { args, .. }
+        | Vec2Constructor(args)
+        | Vec3Constructor(args)
+        | Vec4Constructor(args)
+        | BVec2Constructor(args)
+        | BVec3Constructor(args)
+        | BVec4Constructor(args)
+        | Mat2Constructor(args)
+        | Mat3Constructor(args)
+        | Mat4Constructor(args) => {
+            for arg in args.iter_mut() {
+                changed |= fold_expr(arg, functions, func_table);
+            }
+        }
+        Swizzle { expr: inner, .. } => {
+            changed |= fold_expr(inner, functions, func_table);
+        }
+        Number(_) | IntNumber(_) | Bool(_) | Variable(_) | PreIncrement(_) | PreDecrement(_)
+        | PostIncrement(_) | PostDecrement(_) => {}
+    }
+
+    if let Call { name, args } = &expr.kind {
+        // Prototypes have no body to run until they're linked
+        let func = functions
+            .iter()
+            .find(|f| &f.name == name && f.body.is_some());
+        if let Some(func) = func {
+            if args.iter().all(is_literal) {
+                if let Some(folded) = eval_call(expr, func, functions, func_table) {
+                    *expr = folded;
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    changed
+}
+
+fn is_literal(expr: &Expr) -> bool {
+    matches!(
+        expr.kind,
+        ExprKind::Number(_) | ExprKind::IntNumber(_) | ExprKind::Bool(_)
+    )
+}
+
+/// Whether a compiled function body can be evaluated at compile time
+///
+/// An allowlist, so new opcodes aren't folded until they're known to give the
+/// same result in every VM. Left out are inputs, calls, textures, palettes and
+/// arrays, and opcodes whose result depends on VM settings: `Perlin3` (noise
+/// backend), `Normalize*` (normalize fallback), scalar `==`/`!=` (eq epsilon)
+/// and scalar `+ - * /` and negation (overflow check).
+fn is_pure(opcodes: &[LpsOpCode]) -> bool {
+    use LpsOpCode::*;
+
+    opcodes.iter().all(|op| match op {
+        // Backward jumps are loops
+        Jump(offset) | JumpIfZero(offset) | JumpIfNonZero(offset) => *offset >= 0,
+        Push(_) | PushInt32(_) | Dup1 | Dup2 | Dup3 | Dup4 | Dup9 | Dup16 | Drop1 | Drop2
+        | Drop3 | Drop4 | Drop9 | Drop16 | Swap => true,
+        AbsFixed | MinFixed | MaxFixed | SinFixed | CosFixed | TanFixed | AtanFixed
+        | Atan2Fixed | SqrtFixed | FloorFixed | CeilFixed | FractFixed | ModFixed | WrapFixed
+        | PowFixed | SignFixed | SaturateFixed | ClampFixed | StepFixed | LerpFixed
+        | SmoothstepFixed | RemapFixed => true,
+        GreaterFixed | LessFixed | GreaterEqFixed | LessEqFixed | AndFixed | OrFixed | NotFixed => {
+            true
+        }
+        ModInt32 | AbsInt32 | MinInt32 | MaxInt32 | GreaterInt32 | LessInt32 | GreaterEqInt32
+        | LessEqInt32 | EqInt32 | NotEqInt32 | BitwiseAndInt32 | BitwiseOrInt32
+        | BitwiseXorInt32 | BitwiseNotInt32 | LeftShiftInt32 | RightShiftInt32 | Int32ToFixed
+        | FixedToInt32 => true,
+        AddVec2 | SubVec2 | NegVec2 | AbsVec2 | SignVec2 | FloorVec2 | CeilVec2 | FractVec2
+        | SqrtVec2 | MulVec2 | DivVec2 | ModVec2 | MulVec2Scalar | DivVec2Scalar | Dot2
+        | Length2 | Distance2 | LengthSq2 | DistanceSq2 | EqVec2 | LessVec2 | LessEqVec2
+        | EqCompVec2 | NotBVec2 | Reflect2 | FaceForward2 | MaxCompVec2 | MinCompVec2 | SumVec2
+        | AvgVec2 | ToPolarVec2 | FromPolarVec2 | TileVec2 | MirrorTileVec2 => true,
+        AddVec3 | SubVec3 | NegVec3 | AbsVec3 | SignVec3 | FloorVec3 | CeilVec3 | FractVec3
+        | SqrtVec3 | MulVec3 | DivVec3 | ModVec3 | MulVec3Scalar | DivVec3Scalar | Dot3
+        | Cross3 | Length3 | Distance3 | LengthSq3 | DistanceSq3 | EqVec3 | LessVec3
+        | LessEqVec3 | EqCompVec3 | NotBVec3 | Reflect3 | FaceForward3 | MaxCompVec3
+        | MinCompVec3 | SumVec3 | AvgVec3 | HsvToRgb | RgbToHsv => true,
+        AddVec4 | SubVec4 | NegVec4 | AbsVec4 | SignVec4 | FloorVec4 | CeilVec4 | FractVec4
+        | SqrtVec4 | MulVec4 | DivVec4 | ModVec4 | MulVec4Scalar | DivVec4Scalar | Dot4
+        | Length4 | Distance4 | LengthSq4 | DistanceSq4 | EqVec4 | LessVec4 | LessEqVec4
+        | EqCompVec4 | NotBVec4 | Reflect4 | FaceForward4 | MaxCompVec4 | MinCompVec4 | SumVec4
+        | AvgVec4 => true,
+        RotateMat2 | MulMat2 | MulMat2Vec2 | TransposeMat2 | DeterminantMat2 | InverseMat2
+        | AddMat3 | SubMat3 | NegMat3 | MulMat3 | MulMat3Scalar | DivMat3Scalar | MulMat3Vec3
+        | TransposeMat3 | DeterminantMat3 | InverseMat3 | AddMat4 | SubMat4 | NegMat4 | MulMat4
+        | MulMat4Scalar | DivMat4Scalar | MulMat4Vec4 | TransposeMat4 | DeterminantMat4
+        | InverseMat4 => true,
+        Swizzle3to2(..) | Swizzle3to3(..) | Swizzle4to2(..) | Swizzle4to3(..) | Swizzle4to4(..) => {
+            true
+        }
+        LoadLocalFixed(_)
+        | StoreLocalFixed(_)
+        | LoadLocalInt32(_)
+        | StoreLocalInt32(_)
+        | LoadLocalVec2(_)
+        | StoreLocalVec2(_)
+        | LoadLocalVec3(_)
+        | StoreLocalVec3(_)
+        | LoadLocalVec4(_)
+        | StoreLocalVec4(_)
+        | LoadLocalMat2(_)
+        | StoreLocalMat2(_)
+        | LoadLocalMat3(_)
+        | StoreLocalMat3(_)
+        | LoadLocalMat4(_)
+        | StoreLocalMat4(_)
+        | LoadLocalElemFixed(_)
+        | StoreLocalElemFixed(_)
+        | CheckIndex(_) => true,
+        JumpTable(_) | Select | Return => true,
+        _ => false,
+    })
+}
+
+/// Run `call` against a compiled copy of `func` and build a literal for the result
+fn eval_call(
+    call: &Expr,
+    func: &FunctionDef,
+    functions: &[FunctionDef],
+    func_table: &FunctionTable,
+) -> Option<Expr> {
+    // Every user function needs an index so calls to them emit `Call` (and are
+    // rejected by `is_pure`) rather than being treated as unknown built-ins
+    let mut function_indices = BTreeMap::new();
+    function_indices.insert(String::from("main"), 0);
+    function_indices.insert(func.name.clone(), 1);
+    let others = functions.iter().filter(|f| f.name != func.name);
+    for (i, other) in others.enumerate() {
+        function_indices.insert(other.name.clone(), i as u32 + 2);
+    }
+
+    let callee = gen_user_function(func, func_table, &function_indices);
+    if !is_pure(&callee.opcodes) {
+        return None;
+    }
+
+    // Synthetic main: push the literal arguments and call
+    let mut main_code = Vec::new();
+    let mut locals = LocalAllocator::new();
+    {
+        let mut gen = CodeGenerator::new(&mut main_code, &mut locals, &function_indices);
+        gen.natives = func_table.natives();
+        gen.gen_expr(call);
+    }
+    main_code.push(LpsOpCode::Return);
+
+    let main =
+        VmFunctionDef::new(String::from("main"), func.return_type.clone()).with_opcodes(main_code);
+    let program = LpsProgram::new(String::from("const_eval")).with_functions(vec![main, callee]);
+
+    let limits = VmLimits {
+        max_instructions: MAX_EVAL_INSTRUCTIONS,
+        ..VmLimits::default()
+    };
+    let mut vm = LpsVm::new(&program, limits).ok()?;
+    let values = vm.run(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).ok()?;
+
+    literal_for(&func.return_type, &values, call.span)
+}
+
+fn literal_for(ty: &Type, values: &[Fixed], span: Span) -> Option<Expr> {
+    let kind = match (ty, values) {
+        (Type::Int32, [v]) => ExprKind::IntNumber(v.0),
+        (Type::Fixed | Type::Bool, [v]) => fixed_literal(*v)?,
+        (Type::Vec2, [_, _]) => ExprKind::Vec2Constructor(fixed_literals(values, span)?),
+        (Type::Vec3, [_, _, _]) => ExprKind::Vec3Constructor(fixed_literals(values, span)?),
+        (Type::Vec4, [_, _, _, _]) => ExprKind::Vec4Constructor(fixed_literals(values, span)?),
+        _ => return None,
+    };
+
+    let mut expr = Expr::new(kind, span);
+    expr.ty = Some(ty.clone());
+    Some(expr)
+}
+
+/// A `Number` literal, if the value survives the round trip through `f32`
+fn fixed_literal(value: Fixed) -> Option<ExprKind> {
+    let f = value.to_f32();
+    (Fixed::from_f32(f) == value).then_some(ExprKind::Number(f))
+}
+
+fn fixed_literals(values: &[Fixed], span: Span) -> Option<Vec<Expr>> {
+    values
+        .iter()
+        .map(|v| {
+            let mut expr = Expr::new(fixed_literal(*v)?, span);
+            expr.ty = Some(Type::Fixed);
+            Some(expr)
+        })
+        .collect()
+}
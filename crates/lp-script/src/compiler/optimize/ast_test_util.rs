@@ -218,15 +218,16 @@ fn evaluate_expr(
             .run_scalar(x, y, time)
             .map(EvalResult::Scalar)
             .map_err(|e| format!("Runtime error: {:?}", e)),
-        Type::Vec2 => vm
+        Type::Vec2 | Type::BVec2 => vm
             .run_vec2(x, y, time)
             .map(EvalResult::Vec2)
             .map_err(|e| format!("Runtime error: {:?}", e)),
-        Type::Vec3 => vm
+        Type::Vec3 | Type::BVec3 => vm
             .run_vec3(x, y, time)
             .map(EvalResult::Vec3)
             .map_err(|e| format!("Runtime error: {:?}", e)),
-        Type::Vec4 => vm
+        // A mat2 is returned as its 4 column-major components
+        Type::Vec4 | Type::BVec4 | Type::Mat2 => vm
             .run_vec4(x, y, time)
             .map(EvalResult::Vec4)
             .map_err(|e| format!("Runtime error: {:?}", e)),
@@ -234,6 +235,9 @@ fn evaluate_expr(
             .run_mat3(x, y, time)
             .map(EvalResult::Mat3)
             .map_err(|e| format!("Runtime error: {:?}", e)),
+        Type::Mat4 => Err(String::from("Cannot evaluate expression with mat4 type")),
         Type::Void => Err(String::from("Cannot evaluate expression with void type")),
+        Type::Grid { .. } => Err(String::from("Cannot evaluate expression with grid type")),
+        Type::Array { .. } => Err(String::from("Cannot evaluate expression with array type")),
     }
 }
@@ -16,7 +16,8 @@ extern crate alloc;
 use alloc::vec::Vec;
 
 use super::ast::Program;
-use crate::vm::opcodes::LpsOpCode;
+use super::func::FunctionTable;
+use crate::vm::opcodes::{LpsOpCode, OpcodeSet};
 
 pub mod ast;
 pub mod ops;
@@ -43,6 +44,14 @@ pub struct OptimizeOptions {
 
     /// Maximum number of AST optimization passes (to reach fixed point)
     pub max_ast_passes: usize,
+
+    /// Opcodes the target VM supports; compilation fails if the program
+    /// needs any other
+    pub allowed_opcodes: OpcodeSet,
+
+    /// Output `(width, height)` the program will run at, if fixed; folds
+    /// `toPixel`/`toUv` to constant scales instead of runtime loads
+    pub resolution: Option<(u32, u32)>,
 }
 
 impl OptimizeOptions {
@@ -54,6 +63,8 @@ impl OptimizeOptions {
             dead_code_elimination: true,
             peephole_optimization: true,
             max_ast_passes: 5,
+            allowed_opcodes: OpcodeSet::all(),
+            resolution: None,
         }
     }
 
@@ -65,6 +76,8 @@ impl OptimizeOptions {
             dead_code_elimination: false,
             peephole_optimization: false,
             max_ast_passes: 0,
+            allowed_opcodes: OpcodeSet::all(),
+            resolution: None,
         }
     }
 }
@@ -90,18 +103,26 @@ pub fn optimize_ast_expr(expr: &mut crate::compiler::ast::Expr, options: &Optimi
 /// Optimize a program AST (with statements)
 ///
 /// Applies AST-level optimizations to the full program.
-pub fn optimize_ast_program(program: &mut Program, options: &OptimizeOptions) {
+pub fn optimize_ast_program(
+    program: &mut Program,
+    func_table: &FunctionTable,
+    options: &OptimizeOptions,
+) {
     if options.max_ast_passes == 0 {
         return;
     }
 
-    ast::optimize_program(program, options)
+    ast::optimize_program(program, func_table, options)
 }
 
 /// Optimize a sequence of opcodes
 ///
 /// Applies opcode-level peephole optimizations.
 pub fn optimize_opcodes(opcodes: Vec<LpsOpCode>, options: &OptimizeOptions) -> Vec<LpsOpCode> {
+    let opcodes = match options.resolution {
+        Some(resolution) => ops::fold_resolution(opcodes, resolution),
+        None => opcodes,
+    };
     if !options.peephole_optimization {
         return opcodes;
     }
@@ -2,7 +2,8 @@
 extern crate alloc;
 use alloc::vec::Vec;
 
-use crate::vm::opcodes::LpsOpCode;
+use crate::fixed::Fixed;
+use crate::vm::opcodes::{LoadSource, LpsOpCode};
 
 mod peephole;
 
@@ -13,3 +14,19 @@ mod peephole_tests;
 pub fn optimize(opcodes: Vec<LpsOpCode>) -> Vec<LpsOpCode> {
     peephole::optimize(opcodes)
 }
+
+/// Replace output width/height loads with constants for a known resolution
+pub fn fold_resolution(mut opcodes: Vec<LpsOpCode>, (width, height): (u32, u32)) -> Vec<LpsOpCode> {
+    for op in &mut opcodes {
+        match op {
+            LpsOpCode::Load(LoadSource::Width) => {
+                *op = LpsOpCode::Push(Fixed::from_i32(width as i32))
+            }
+            LpsOpCode::Load(LoadSource::Height) => {
+                *op = LpsOpCode::Push(Fixed::from_i32(height as i32))
+            }
+            _ => {}
+        }
+    }
+    opcodes
+}
@@ -146,6 +146,10 @@ fn collect_jump_targets(opcodes: &[LpsOpCode]) -> alloc::collections::BTreeSet<u
                     targets.insert(target);
                 }
             }
+            // Every entry is reachable, though each follows an unconditional `Jump`
+            LpsOpCode::JumpTable(len) => {
+                targets.extend(i + 1..=i + 1 + *len as usize);
+            }
             _ => {}
         }
     }
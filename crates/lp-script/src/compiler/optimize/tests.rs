@@ -65,6 +65,29 @@ mod optimization_tests {
         assert!(program.main_function().unwrap().opcodes.len() <= 2); // Push(0.0) + Return
     }
 
+    #[test]
+    fn test_clamp_to_unit_lowers_to_saturate() {
+        use crate::vm::LpsOpCode;
+
+        let program =
+            compile_expr_with_options("max(0.0, min(1.0, uv.x))", &OptimizeOptions::all()).unwrap();
+        let opcodes = &program.main_function().unwrap().opcodes;
+        let count = |want: LpsOpCode| opcodes.iter().filter(|op| **op == want).count();
+        assert_eq!(count(LpsOpCode::SaturateFixed), 1, "{:?}", opcodes);
+        assert_eq!(count(LpsOpCode::MinFixed) + count(LpsOpCode::MaxFixed), 0);
+    }
+
+    #[test]
+    fn test_double_negation_drops_opcodes() {
+        use crate::vm::LpsOpCode;
+
+        let program = compile_expr_with_options("-(-uv.x)", &OptimizeOptions::all()).unwrap();
+        let unoptimized = compile_expr_with_options("-(-uv.x)", &OptimizeOptions::none()).unwrap();
+        let opcodes = &program.main_function().unwrap().opcodes;
+        assert!(!opcodes.contains(&LpsOpCode::NegFixed), "{:?}", opcodes);
+        assert!(opcodes.len() < unoptimized.main_function().unwrap().opcodes.len());
+    }
+
     #[test]
     fn test_constant_propagation_in_ternary() {
         // true ? x : y should fold to x
@@ -217,4 +240,113 @@ mod optimization_tests {
         let program = compile_expr_with_options("!1.0", &OptimizeOptions::all()).unwrap();
         assert!(program.main_function().unwrap().opcodes.len() <= 2);
     }
+
+    #[test]
+    fn test_pure_user_function_folding() {
+        use crate::fixed::Fixed;
+        use crate::vm::opcodes::LpsOpCode;
+
+        let script = "float two() { return 2.0; } return two() * 3.0;";
+        let program = compile_script_with_options(script, &OptimizeOptions::all()).unwrap();
+        assert_eq!(
+            program.main_function().unwrap().opcodes,
+            vec![LpsOpCode::Push(Fixed::from_f32(6.0)), LpsOpCode::Return]
+        );
+
+        // Constant arguments are passed through
+        let script = "float hyp(float a, float b) { vec2 v = vec2(a, b); return length(v); } \
+                      return hyp(1.5 + 1.5, 4.0);";
+        let program = compile_script_with_options(script, &OptimizeOptions::all()).unwrap();
+        assert_eq!(
+            program.main_function().unwrap().opcodes,
+            vec![LpsOpCode::Push(Fixed::from_f32(5.0)), LpsOpCode::Return]
+        );
+    }
+
+    #[test]
+    fn test_impure_user_functions_not_folded() {
+        use crate::vm::opcodes::LpsOpCode;
+
+        let scripts = [
+            // Reads a built-in input
+            "float now() { return time; } return now();",
+            // Loops
+            "float sum() { float s = 0.0; for (int i = 0; i < 3; i++) { s += 1.0; } return s; } return sum();",
+            // Calls another user function
+            "float inner() { return 1.0; } float outer() { return inner() * 2.0; } return outer();",
+            // Recursion
+            "int fact(int n) { if (n <= 1) { return 1; } return n * fact(n - 1); } return fact(3);",
+            // Non-constant argument
+            "float sq(float v) { return v * v; } return sq(uv.x);",
+            // Scalar arithmetic, which the VM's overflow check may reject
+            "float sq(float v) { return v * v; } return sq(3.0);",
+            "int inc(int n) { return n + 1; } return float(inc(2));",
+            // Scalar equality, which depends on the VM's eq epsilon
+            "bool same(float a, float b) { return a == b; } return same(0.1, 0.1) ? 1.0 : 0.0;",
+            // Texture blending
+            "vec4 blend(vec2 p) { return mixTex(0, 1, p, 0.5); } return blend(vec2(0.5)).x;",
+        ];
+        for script in scripts {
+            let program = compile_script_with_options(script, &OptimizeOptions::all()).unwrap();
+            assert!(
+                program
+                    .main_function()
+                    .unwrap()
+                    .opcodes
+                    .iter()
+                    .any(|op| matches!(op, LpsOpCode::Call(_))),
+                "call should be kept: {}",
+                script
+            );
+        }
+    }
+
+    #[test]
+    fn test_noise_user_function_not_folded() {
+        use crate::fixed::noise::perlin3_accurate;
+        use crate::fixed::{Fixed, NoiseBackend};
+        use crate::vm::{LpsVm, VmLimits};
+
+        // Folding would bake in the default backend, not the VM's
+        let script = "float n(float a) { return perlin3(vec3(a, 2.8, 1.1), 3); } return n(1.2);";
+        let expected = perlin3_accurate(
+            Fixed::from_f32(1.2),
+            Fixed::from_f32(2.8),
+            Fixed::from_f32(1.1),
+            3,
+        );
+        for options in [OptimizeOptions::all(), OptimizeOptions::none()] {
+            let program = compile_script_with_options(script, &options).unwrap();
+            let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+            vm.set_noise_backend(NoiseBackend::FixedAccurate);
+            let result = vm
+                .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+                .unwrap();
+            assert_eq!(result, expected, "{:?}", options);
+        }
+    }
+
+    #[test]
+    fn test_normalize_user_function_not_folded() {
+        use crate::fixed::{Fixed, Vec2, Vec4};
+        use crate::vm::{LpsVm, NormalizeFallback, VmLimits};
+
+        // Folding would bake in "no fallback", not the VM's setting
+        let script = "vec2 f(float a) { return normalize(vec2(a, 0.0)); } return f(0.0);";
+        let results: Vec<_> = [OptimizeOptions::all(), OptimizeOptions::none()]
+            .iter()
+            .map(|options| {
+                let program = compile_script_with_options(script, options).unwrap();
+                let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+                vm.set_normalize_fallback(Some(NormalizeFallback::direction(Vec4::new(
+                    Fixed::ONE,
+                    Fixed::ZERO,
+                    Fixed::ZERO,
+                    Fixed::ZERO,
+                ))));
+                vm.run_vec2(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).unwrap()
+            })
+            .collect();
+        assert_eq!(results, vec![Vec2::new(Fixed::ONE, Fixed::ZERO); 2]);
+    }
 }
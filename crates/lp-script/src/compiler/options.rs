@@ -0,0 +1,65 @@
+/// Compiler configuration: optimization settings and host natives
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::compiler::optimize::OptimizeOptions;
+use crate::vm::stack_value::InputValue;
+use crate::vm::{NativeDef, NativeFn, NativeSig};
+
+/// Options for `compile_script_with_compiler_options` and
+/// `compile_expr_with_compiler_options`
+#[derive(Debug, Clone, Default)]
+pub struct CompilerOptions {
+    pub optimize: OptimizeOptions,
+    /// Host functions callable from scripts, in `CallNative` index order
+    pub natives: Vec<NativeDef>,
+}
+
+impl CompilerOptions {
+    pub fn new(optimize: OptimizeOptions) -> Self {
+        CompilerOptions {
+            optimize,
+            natives: Vec::new(),
+        }
+    }
+
+    /// Make a host function callable from scripts as `name(...)`
+    ///
+    /// Calls are type checked against `sig` and compile to `CallNative`,
+    /// which pops the arguments and passes them to `func` in order (`bool`
+    /// as `Int32`). `func` must return a value of `sig.return_type`, or the
+    /// run fails with a type mismatch. Registering a name again replaces the
+    /// earlier native; user functions of the same name take precedence, and
+    /// names of built-ins must not be reused.
+    ///
+    /// # Example
+    /// ```
+    /// use lp_script::fixed::Fixed;
+    /// use lp_script::{
+    ///     compile_script_with_compiler_options, CompilerOptions, InputValue, NativeSig, Type,
+    /// };
+    ///
+    /// let mut options = CompilerOptions::default();
+    /// options.register_native(
+    ///     "sensor",
+    ///     NativeSig::new(&[Type::Int32], Type::Fixed),
+    ///     |_args| InputValue::Fixed(Fixed::HALF),
+    /// );
+    /// let program = compile_script_with_compiler_options("return sensor(0);", &options).unwrap();
+    /// assert_eq!(program.natives[0].name, "sensor");
+    /// ```
+    pub fn register_native(
+        &mut self,
+        name: &str,
+        sig: NativeSig,
+        func: impl Fn(&[InputValue]) -> InputValue + 'static,
+    ) -> &mut Self {
+        let native = NativeDef::new(String::from(name), sig, NativeFn::new(func));
+        match self.natives.iter_mut().find(|n| n.name == name) {
+            Some(existing) => *existing = native,
+            None => self.natives.push(native),
+        }
+        self
+    }
+}
@@ -1,10 +1,13 @@
 /// Parser: converts tokens to AST with spans
 extern crate alloc;
+use alloc::string::String;
+use alloc::vec;
 use alloc::vec::Vec;
 
-use crate::compiler::ast::{Expr, Program, Stmt};
+use crate::compiler::ast::{Expr, FunctionDef, Program, Stmt, StmtKind};
 use crate::compiler::error::{ParseError, ParseErrorKind};
 use crate::compiler::lexer::{Token, TokenKind};
+use crate::compiler::stmt::struct_decl::StructDef;
 use crate::shared::{Span, Type};
 
 // Function parsing is now in compiler::func::func_parse
@@ -17,6 +20,21 @@ pub struct Parser {
     pub(crate) pos: usize,
     recursion_depth: usize,
     max_recursion: usize,
+    loop_depth: usize,   // Loops enclosing the statement being parsed
+    switch_depth: usize, // Switches enclosing the statement being parsed
+    /// Declared struct types, lowered to per-member locals while parsing
+    pub(crate) structs: Vec<StructDef>,
+    /// Names declared in each enclosing scope
+    scopes: Vec<Vec<Declared>>,
+}
+
+/// A name declared in a parser scope
+struct Declared {
+    name: String,
+    /// Struct type (an index into `structs`) if it's a struct local
+    struct_idx: Option<usize>,
+    /// Value if it's a `const int`
+    const_int: Option<i32>,
 }
 
 impl Parser {
@@ -30,6 +48,10 @@ impl Parser {
             pos: 0,
             recursion_depth: 0,
             max_recursion,
+            loop_depth: 0,
+            switch_depth: 0,
+            structs: Vec::new(),
+            scopes: vec![Vec::new()],
         }
     }
 
@@ -58,6 +80,11 @@ impl Parser {
         &self.tokens[self.pos]
     }
 
+    /// The token after the current one
+    pub(crate) fn peek(&self) -> &Token {
+        &self.tokens[(self.pos + 1).min(self.tokens.len() - 1)]
+    }
+
     pub(crate) fn advance(&mut self) {
         if self.pos < self.tokens.len() - 1 {
             self.pos += 1;
@@ -70,17 +97,32 @@ impl Parser {
     /// Parse a full program (script mode)
     pub fn parse_program(mut self) -> Result<Program, ParseError> {
         let start = self.current().span.start;
+        let mut uniforms = Vec::new();
+        let mut consts = Vec::new();
         let mut functions = Vec::new();
         let mut stmts = Vec::new();
 
-        // Parse function definitions first (must come before statements)
-        while self.is_function_definition() {
-            functions.push(self.parse_function_def()?);
+        // Parse uniform, const, struct and function definitions first (must come before statements)
+        loop {
+            self.reject_struct_function()?;
+            if matches!(self.current().kind, TokenKind::Struct) {
+                self.parse_struct_def()?;
+            } else if matches!(self.current().kind, TokenKind::Uniform) {
+                let uniform = self.parse_uniform_decl(&uniforms)?;
+                uniforms.push(uniform);
+            } else if matches!(self.current().kind, TokenKind::Const) {
+                consts.push(self.parse_const_decl()?);
+            } else if self.is_function_definition() {
+                let func = self.parse_function_def()?;
+                add_function(&mut functions, func);
+            } else {
+                break;
+            }
         }
 
         // Parse top-level statements
         while !matches!(self.current().kind, TokenKind::Eof) {
-            stmts.push(self.parse_stmt()?);
+            self.parse_stmt_into(&mut stmts)?;
         }
 
         let end = if !stmts.is_empty() {
@@ -92,6 +134,8 @@ impl Parser {
         };
 
         let program = Program {
+            uniforms,
+            consts,
             functions,
             stmts,
             span: Span::new(start, end),
@@ -120,12 +164,18 @@ impl Parser {
 
     pub(crate) fn parse_type(&mut self) -> Type {
         let ty = match &self.current().kind {
+            TokenKind::Bool => Type::Bool,
             TokenKind::Float => Type::Fixed,
             TokenKind::Int => Type::Int32,
             TokenKind::Vec2 => Type::Vec2,
             TokenKind::Vec3 => Type::Vec3,
             TokenKind::Vec4 => Type::Vec4,
+            TokenKind::BVec2 => Type::BVec2,
+            TokenKind::BVec3 => Type::BVec3,
+            TokenKind::BVec4 => Type::BVec4,
+            TokenKind::Mat2 => Type::Mat2,
             TokenKind::Mat3 => Type::Mat3,
+            TokenKind::Mat4 => Type::Mat4,
             TokenKind::Void => Type::Void,
             _ => Type::Fixed, // Fallback
         };
@@ -133,18 +183,121 @@ impl Parser {
         ty
     }
 
+    /// Parse a loop body, where `break` and `continue` are allowed
+    pub(crate) fn parse_loop_body(&mut self) -> Result<Stmt, ParseError> {
+        self.loop_depth += 1;
+        let body = self.parse_stmt();
+        self.loop_depth -= 1;
+        body
+    }
+
+    pub(crate) fn in_loop(&self) -> bool {
+        self.loop_depth > 0
+    }
+
+    /// Parse a switch case's statements, where `break` is allowed
+    pub(crate) fn parse_switch_body_into(
+        &mut self,
+        stmts: &mut Vec<Stmt>,
+    ) -> Result<(), ParseError> {
+        self.switch_depth += 1;
+        let result = self.parse_stmt_into(stmts);
+        self.switch_depth -= 1;
+        result
+    }
+
+    pub(crate) fn in_switch(&self) -> bool {
+        self.switch_depth > 0
+    }
+
+    pub(crate) fn push_scope(&mut self) {
+        self.scopes.push(Vec::new());
+    }
+
+    pub(crate) fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Record a name declared in the current scope, shadowing outer ones
+    pub(crate) fn declare(&mut self, name: &str, struct_idx: Option<usize>) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(Declared {
+                name: String::from(name),
+                struct_idx,
+                const_int: None,
+            });
+        }
+    }
+
+    /// Record the value of `const int` `name`, just declared in this scope
+    pub(crate) fn set_const_int(&mut self, name: &str, value: i32) {
+        let declared = self
+            .scopes
+            .last_mut()
+            .and_then(|scope| scope.iter_mut().rev().find(|d| d.name == name));
+        if let Some(declared) = declared {
+            declared.const_int = Some(value);
+        }
+    }
+
+    /// The value of `name` if it's a `const int` with a known value
+    pub(crate) fn const_int(&self, name: &str) -> Option<i32> {
+        self.lookup_declared(name).and_then(|d| d.const_int)
+    }
+
+    /// The struct type of local `name`, if it's a struct local
+    pub(crate) fn struct_local(&self, name: &str) -> Option<usize> {
+        self.lookup_declared(name).and_then(|d| d.struct_idx)
+    }
+
+    /// The innermost declaration of `name`
+    fn lookup_declared(&self, name: &str) -> Option<&Declared> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .find(|d| d.name == name)
+    }
+
+    /// Parse a statement into a statement list, where a struct declaration
+    /// or assignment lowers to one statement per member
+    pub(crate) fn parse_stmt_into(&mut self, stmts: &mut Vec<Stmt>) -> Result<(), ParseError> {
+        match self.parse_struct_stmt()? {
+            Some(lowered) => stmts.extend(lowered),
+            None => stmts.push(self.parse_stmt()?),
+        }
+        Ok(())
+    }
+
     pub(crate) fn parse_stmt(&mut self) -> Result<Stmt, ParseError> {
+        // On its own (e.g. an `if` body) a lowered struct statement is a block
+        let start = self.current().span.start;
+        if let Some(lowered) = self.parse_struct_stmt()? {
+            let end = lowered.last().map_or(start, |stmt| stmt.span.end);
+            return Ok(Stmt::new(StmtKind::Block(lowered), Span::new(start, end)));
+        }
+
         match &self.current().kind {
-            TokenKind::Float
+            TokenKind::Bool
+            | TokenKind::Float
             | TokenKind::Int
             | TokenKind::Vec2
             | TokenKind::Vec3
             | TokenKind::Vec4
-            | TokenKind::Mat3 => self.parse_var_decl(),
+            | TokenKind::BVec2
+            | TokenKind::BVec3
+            | TokenKind::BVec4
+            | TokenKind::Mat2
+            | TokenKind::Mat3
+            | TokenKind::Mat4 => self.parse_var_decl(),
+            TokenKind::Const => self.parse_const_decl(),
             TokenKind::Return => self.parse_return_stmt(),
             TokenKind::If => self.parse_if_stmt(),
             TokenKind::While => self.parse_while_stmt(),
+            TokenKind::Do => self.parse_do_while_stmt(),
             TokenKind::For => self.parse_for_stmt(),
+            TokenKind::Switch => self.parse_switch_stmt(),
+            TokenKind::Break | TokenKind::Continue => self.parse_loop_control_stmt(),
             TokenKind::LBrace => self.parse_block(),
             _ => self.parse_expr_stmt(),
         }
@@ -155,6 +308,26 @@ impl Parser {
     }
 }
 
+/// Add a parsed function; a prototype and a definition with the same
+/// signature are one function, kept where the first of them was declared
+fn add_function(functions: &mut Vec<FunctionDef>, func: FunctionDef) {
+    let same_signature = |other: &FunctionDef| {
+        other.name == func.name
+            && other.return_type == func.return_type
+            && other
+                .params
+                .iter()
+                .map(|p| &p.ty)
+                .eq(func.params.iter().map(|p| &p.ty))
+    };
+    match functions.iter().position(same_signature) {
+        Some(idx) if functions[idx].body.is_none() => functions[idx] = func,
+        Some(_) if func.body.is_none() => {}
+        // A second definition is reported by the analyzer
+        _ => functions.push(func),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use lp_alloc::init_test_allocator;
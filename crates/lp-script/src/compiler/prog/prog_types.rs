@@ -1,6 +1,7 @@
 /// Program type checking
 extern crate alloc;
 use alloc::string::ToString;
+use alloc::vec::Vec;
 
 use crate::compiler::ast::{Expr, Program, Stmt, StmtKind};
 use crate::compiler::error::{TypeError, TypeErrorKind};
@@ -13,20 +14,27 @@ impl TypeChecker {
         program: &mut Program,
         func_table: &FunctionTable,
     ) -> Result<(), TypeError> {
-        // Type check each function body
+        let globals = Self::check_globals(&mut program.consts, func_table)?;
+
+        // Type check each function body; prototypes have none
         for func in &mut program.functions {
+            let Some(body) = &mut func.body else {
+                continue;
+            };
             Self::check_function_body(
-                &mut func.body,
+                body,
                 &func.return_type,
                 &func.params,
                 func.span,
                 &func.name,
+                &globals,
                 func_table,
             )?;
         }
 
         // Type check top-level statements
-        let mut symbols = SymbolTable::new();
+        let mut symbols = globals;
+        symbols.push_scope();
         for stmt in &mut program.stmts {
             Self::check_stmt(stmt, &mut symbols, func_table)?;
         }
@@ -34,6 +42,74 @@ impl TypeChecker {
         Ok(())
     }
 
+    /// Re-check a single function against a function table from a previous pass
+    ///
+    /// Intended for editors that re-typecheck on every keystroke: only the body
+    /// of `func_name` is checked, using the cached signatures of every other
+    /// function. Returns `true` if the function's signature differs from the
+    /// one in `prior_func_table`, in which case its callers must be re-checked
+    /// (and the table rebuilt) too.
+    #[allow(dead_code)] // Entry point for editor integration, exercised in tests
+    pub fn check_function_incremental(
+        program: &mut Program,
+        func_name: &str,
+        prior_func_table: &FunctionTable,
+    ) -> Result<bool, TypeError> {
+        let globals = Self::check_globals(&mut program.consts, prior_func_table)?;
+        let func = program
+            .functions
+            .iter_mut()
+            .find(|f| f.name == func_name)
+            .ok_or_else(|| TypeError {
+                kind: TypeErrorKind::UndefinedFunction(func_name.to_string()),
+                span: program.span,
+            })?;
+
+        let params: Vec<Type> = func.params.iter().map(|p| p.ty.clone()).collect();
+        let signature_changed = match prior_func_table.lookup(func_name) {
+            Some(prior) => prior.params != params || prior.return_type != func.return_type,
+            None => true,
+        };
+
+        // Recursive calls must see the new signature, not the cached one
+        let updated_table;
+        let func_table = if signature_changed {
+            let mut table = prior_func_table.clone();
+            table.set_signature(func_name, params, func.return_type.clone());
+            updated_table = table;
+            &updated_table
+        } else {
+            prior_func_table
+        };
+
+        let Some(body) = &mut func.body else {
+            return Ok(signature_changed);
+        };
+        Self::check_function_body(
+            body,
+            &func.return_type,
+            &func.params,
+            func.span,
+            &func.name,
+            &globals,
+            func_table,
+        )?;
+
+        Ok(signature_changed)
+    }
+
+    /// Symbols visible everywhere: the uniforms, then the program's `consts`
+    fn check_globals(
+        consts: &mut [Stmt],
+        func_table: &FunctionTable,
+    ) -> Result<SymbolTable, TypeError> {
+        let mut symbols = SymbolTable::with_externals(func_table.externals());
+        for stmt in consts.iter_mut() {
+            Self::check_stmt(stmt, &mut symbols, func_table)?;
+        }
+        Ok(symbols)
+    }
+
     /// Type check a function body
     fn check_function_body(
         body: &mut [Stmt],
@@ -41,9 +117,11 @@ impl TypeChecker {
         params: &[crate::compiler::ast::Parameter],
         func_span: crate::shared::Span,
         func_name: &str,
+        globals: &SymbolTable,
         func_table: &FunctionTable,
     ) -> Result<(), TypeError> {
-        let mut symbols = SymbolTable::new();
+        let mut symbols = globals.clone();
+        symbols.push_scope();
 
         // Add parameters to symbol table
         for param in params {
@@ -128,11 +206,44 @@ impl TypeChecker {
                 }
             }
 
-            // Loops don't guarantee returns (they might not execute)
-            StmtKind::While { .. } | StmtKind::For { .. } => false,
+            // Loops don't guarantee returns (they might not execute, or a
+            // do-while body might `break` first)
+            StmtKind::While { .. } | StmtKind::DoWhile { .. } | StmtKind::For { .. } => false,
+
+            // Every case falls through to the last one unless it returns or
+            // breaks, and with a default some case is always entered
+            StmtKind::Switch { cases, .. } => {
+                cases.iter().any(|case| case.value.is_none())
+                    && cases
+                        .last()
+                        .is_some_and(|case| Self::all_paths_return(&case.body))
+                    && !cases.iter().any(|case| Self::may_break(&case.body))
+            }
 
             // Other statements don't return
-            StmtKind::VarDecl { .. } | StmtKind::Expr(_) => false,
+            StmtKind::VarDecl { .. } | StmtKind::ConstDecl { .. } | StmtKind::Expr(_) => false,
+            StmtKind::Break | StmtKind::Continue => false,
         }
     }
+
+    /// Check if a statement list has a `break` that leaves the enclosing
+    /// loop or switch
+    fn may_break(stmts: &[Stmt]) -> bool {
+        stmts.iter().any(|stmt| match &stmt.kind {
+            StmtKind::Break => true,
+            StmtKind::Block(stmts) => Self::may_break(stmts),
+            StmtKind::If {
+                then_stmt,
+                else_stmt,
+                ..
+            } => {
+                Self::may_break(core::slice::from_ref(then_stmt.as_ref()))
+                    || else_stmt
+                        .as_deref()
+                        .is_some_and(|s| Self::may_break(core::slice::from_ref(s)))
+            }
+            // Inner loops and switches have their own `break`s
+            _ => false,
+        })
+    }
 }
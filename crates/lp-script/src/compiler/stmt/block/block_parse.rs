@@ -14,9 +14,11 @@ impl Parser {
         self.advance(); // consume '{'
 
         let mut stmts = Vec::new();
+        self.push_scope();
         while !matches!(self.current().kind, TokenKind::RBrace | TokenKind::Eof) {
-            stmts.push(self.parse_stmt()?);
+            self.parse_stmt_into(&mut stmts)?;
         }
+        self.pop_scope();
 
         let end = self.current().span.end;
         self.expect(TokenKind::RBrace);
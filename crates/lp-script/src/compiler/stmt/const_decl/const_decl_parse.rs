@@ -0,0 +1,79 @@
+use alloc::format;
+use alloc::string::String;
+
+/// Const declaration parsing
+use crate::compiler::ast::{Expr, ExprKind, Stmt, StmtKind};
+use crate::compiler::error::{ParseError, ParseErrorKind};
+use crate::compiler::lexer::TokenKind;
+use crate::compiler::parser::Parser;
+use crate::shared::{Span, Type};
+
+impl Parser {
+    /// Parse `const type name = value;` for a scalar or vector type
+    pub(crate) fn parse_const_decl(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.current().span.start;
+        self.advance(); // consume 'const'
+
+        let decl = if matches!(
+            self.current().kind,
+            TokenKind::Bool
+                | TokenKind::Float
+                | TokenKind::Int
+                | TokenKind::Vec2
+                | TokenKind::Vec3
+                | TokenKind::Vec4
+        ) {
+            Some(self.parse_var_decl_no_semicolon()?)
+        } else {
+            None
+        };
+
+        match decl.map(|decl| (decl.kind, decl.span)) {
+            Some((
+                StmtKind::VarDecl {
+                    ty,
+                    name,
+                    init: Some(init),
+                },
+                span,
+            )) => {
+                self.consume_semicolon();
+                // Known while parsing so it can size an array
+                if ty == Type::Int32 {
+                    if let Some(value) = self.const_int_value(&init) {
+                        self.set_const_int(&name, value);
+                    }
+                }
+                let kind = StmtKind::ConstDecl { ty, name, init };
+                Ok(Stmt::new(kind, Span::new(start, span.end)))
+            }
+            decl => Err(ParseError {
+                kind: ParseErrorKind::UnexpectedToken {
+                    expected: String::from(if decl.is_some() { "=" } else { "const type" }),
+                    found: format!("{:?}", self.current().kind),
+                },
+                span: self.current().span,
+            }),
+        }
+    }
+
+    /// Value of an int expression of literals and earlier `const int`s
+    pub(crate) fn const_int_value(&self, expr: &Expr) -> Option<i32> {
+        let operands = |left: &Expr, right: &Expr| {
+            Some((self.const_int_value(left)?, self.const_int_value(right)?))
+        };
+        match &expr.kind {
+            ExprKind::IntNumber(n) => Some(*n),
+            ExprKind::Variable(name) => self.const_int(name),
+            ExprKind::Neg(operand) => Some(self.const_int_value(operand)?.wrapping_neg()),
+            ExprKind::Add(l, r) => operands(l, r).map(|(a, b)| a.wrapping_add(b)),
+            ExprKind::Sub(l, r) => operands(l, r).map(|(a, b)| a.wrapping_sub(b)),
+            ExprKind::Mul(l, r) => operands(l, r).map(|(a, b)| a.wrapping_mul(b)),
+            ExprKind::Div(l, r) => operands(l, r).and_then(|(a, b)| a.checked_div(b)),
+            ExprKind::Mod(l, r) => operands(l, r).and_then(|(a, b)| a.checked_rem(b)),
+            ExprKind::LeftShift(l, r) => operands(l, r).map(|(a, b)| a.wrapping_shl(b as u32)),
+            ExprKind::RightShift(l, r) => operands(l, r).map(|(a, b)| a.wrapping_shr(b as u32)),
+            _ => None,
+        }
+    }
+}
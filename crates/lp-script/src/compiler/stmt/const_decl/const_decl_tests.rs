@@ -0,0 +1,185 @@
+/// Const declaration tests
+#[cfg(test)]
+mod tests {
+    use crate::compiler::error::{CompileError, ParseErrorKind, TypeErrorKind};
+    use crate::compiler::stmt::stmt_test_util::ScriptTest;
+    use crate::fixed::ToFixed;
+    use crate::vm::opcodes::LpsOpCode;
+    use crate::{compile_script_with_options, OptimizeOptions};
+
+    fn compile_error(src: &str) -> CompileError {
+        match compile_script_with_options(src, &OptimizeOptions::none()) {
+            Err(err) => err,
+            Ok(_) => panic!("Expected compile error for {}", src),
+        }
+    }
+
+    fn type_error(src: &str) -> TypeErrorKind {
+        match compile_error(src) {
+            CompileError::TypeCheck(err) => err.kind,
+            other => panic!("Expected Type error for {}, got {}", src, other),
+        }
+    }
+
+    #[test]
+    fn test_const_is_a_literal() -> Result<(), String> {
+        // No local, even without AST optimizations
+        ScriptTest::new("const float SPEED = 0.25; return SPEED * 4.0;")
+            .expect_opcodes(vec![
+                LpsOpCode::Push(0.25.to_fixed()),
+                LpsOpCode::Push(4.0.to_fixed()),
+                LpsOpCode::MulFixed,
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(1.0)
+            .run()
+    }
+
+    #[test]
+    fn test_const_from_consts_and_builtins() -> Result<(), String> {
+        ScriptTest::new(
+            "const float A = 2.0; \
+             const float B = A * cos(0.0) + A / 4.0; \
+             return B;",
+        )
+        .expect_result_fixed(2.5)
+        .run()
+    }
+
+    #[test]
+    fn test_const_int_widens_to_float() -> Result<(), String> {
+        ScriptTest::new("const float X = 3; return X / 2.0;")
+            .expect_result_fixed(1.5)
+            .run()
+    }
+
+    #[test]
+    fn test_const_int_loop_bound() -> Result<(), String> {
+        ScriptTest::new(
+            "const int N = 4; \
+             int sum = 0; \
+             for (int i = 0; i < N; i++) { sum += i; } \
+             return float(sum);",
+        )
+        .expect_result_fixed(6.0)
+        .run()
+    }
+
+    #[test]
+    fn test_const_vector() -> Result<(), String> {
+        ScriptTest::new(
+            "const float HALF = 0.5; \
+             const vec3 C = vec3(HALF, 1.0 + HALF, 3.0 * HALF); \
+             return C.y + C.z;",
+        )
+        .expect_result_fixed(3.0)
+        .run()
+    }
+
+    #[test]
+    fn test_const_in_function() -> Result<(), String> {
+        ScriptTest::new(
+            "float scale(float x) { const float K = 3.0; return x * K; } \
+             return scale(2.0);",
+        )
+        .expect_function_local_count("scale", 1)
+        .expect_result_fixed(6.0)
+        .run()
+    }
+
+    #[test]
+    fn test_global_const_in_functions() -> Result<(), String> {
+        ScriptTest::new(
+            "uniform float gain; \
+             const float K = 2.0; \
+             const int N = 3; \
+             float f() { return K; } \
+             float last() { float a[N]; a[N - 1] = K * 2.0; return a[2]; } \
+             return f() + last() + float(N);",
+        )
+        .expect_function_local_count("f", 0)
+        .expect_result_fixed(9.0)
+        .run()
+    }
+
+    #[test]
+    fn test_global_const_shadowed_in_function() -> Result<(), String> {
+        ScriptTest::new(
+            "const float K = 2.0; \
+             float f(float K) { return K; } \
+             return f(5.0) + K;",
+        )
+        .expect_result_fixed(7.0)
+        .run()
+    }
+
+    #[test]
+    fn test_assign_to_global_const_in_function() {
+        assert!(matches!(
+            type_error("const float K = 2.0; float f() { K = 1.0; return K; } return f();"),
+            TypeErrorKind::ConstAssignment(_)
+        ));
+    }
+
+    #[test]
+    fn test_const_shadowed_by_local() -> Result<(), String> {
+        ScriptTest::new(
+            "const float X = 1.0; \
+             float y = 0.0; \
+             { float X = 2.0; X = X + 1.0; y = X; } \
+             return y + X;",
+        )
+        .expect_result_fixed(4.0)
+        .run()
+    }
+
+    #[test]
+    fn test_const_from_non_constant() {
+        assert!(matches!(
+            type_error("const float X = uv.x; return X;"),
+            TypeErrorKind::NotConstant
+        ));
+        assert!(matches!(
+            type_error("float a = 1.0; const float X = a * 2.0; return X;"),
+            TypeErrorKind::NotConstant
+        ));
+    }
+
+    #[test]
+    fn test_const_type_mismatch() {
+        assert!(matches!(
+            type_error("const vec2 V = 1.0; return V.x;"),
+            TypeErrorKind::Mismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_assign_to_const() {
+        for src in [
+            "const float X = 1.0; X = 2.0; return X;",
+            "const float X = 1.0; X += 2.0; return X;",
+            "const int N = 1; N++; return 0.0;",
+        ] {
+            match type_error(src) {
+                TypeErrorKind::ConstAssignment(name) => assert!(name == "X" || name == "N"),
+                other => panic!("Expected ConstAssignment for {}, got {:?}", src, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_const_parse_errors() {
+        for (src, expected) in [
+            ("const float X; return X;", "="),
+            ("const mat2 M = mat2(1.0); return 0.0;", "const type"),
+        ] {
+            match compile_error(src) {
+                CompileError::Parser(err) => match err.kind {
+                    ParseErrorKind::UnexpectedToken { expected: e, .. } => assert_eq!(e, expected),
+                    other => panic!("Expected UnexpectedToken for {}, got {:?}", src, other),
+                },
+                other => panic!("Expected Parse error for {}, got {}", src, other),
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+/// Const declaration type checking
+use crate::compiler::ast::Expr;
+use crate::compiler::const_eval::ConstValue;
+use crate::compiler::error::{TypeError, TypeErrorKind};
+use crate::compiler::optimize::ast::constant_fold;
+use crate::compiler::typechecker::{FunctionTable, SymbolTable, TypeChecker};
+use crate::shared::Type;
+
+impl TypeChecker {
+    /// Fold a const's initializer to a literal of its type and declare it
+    ///
+    /// Folding happens whatever the optimization options, since uses of the
+    /// const are replaced by the literal (see `check_variable`).
+    pub(crate) fn check_const_decl(
+        ty: &Type,
+        name: &str,
+        init: &mut Expr,
+        symbols: &mut SymbolTable,
+        func_table: &FunctionTable,
+    ) -> Result<(), TypeError> {
+        Self::infer_type(init, symbols, func_table)?;
+        constant_fold::fold_constants(init);
+
+        let value = ConstValue::from_folded(init).ok_or(TypeError {
+            kind: TypeErrorKind::NotConstant,
+            span: init.span,
+        })?;
+        *init = value.to_literal(ty, init.span).ok_or_else(|| TypeError {
+            kind: TypeErrorKind::Mismatch {
+                expected: ty.clone(),
+                found: init.ty.clone().unwrap_or(Type::Fixed),
+            },
+            span: init.span,
+        })?;
+
+        let _ = symbols.declare_const(name.into(), init.clone());
+        Ok(())
+    }
+}
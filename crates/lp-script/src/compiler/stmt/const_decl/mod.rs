@@ -0,0 +1,10 @@
+/// Const declaration module
+///
+/// Groups parsing, type checking, and tests for `const type name = value;`.
+/// The type checker folds the value to a literal and substitutes it for each
+/// use, so a const never reaches code generation as a local.
+mod const_decl_parse;
+mod const_decl_types;
+
+#[cfg(test)]
+mod const_decl_tests;
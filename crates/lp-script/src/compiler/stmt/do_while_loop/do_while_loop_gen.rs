@@ -0,0 +1,26 @@
+/// Do-while loop code generation
+extern crate alloc;
+
+use crate::compiler::ast::{Expr, Stmt};
+use crate::compiler::codegen::CodeGenerator;
+use crate::vm::opcodes::LpsOpCode;
+
+impl<'a> CodeGenerator<'a> {
+    pub(crate) fn gen_do_while_stmt(&mut self, body: &Stmt, condition: &Expr) {
+        let loop_start = self.code.len();
+
+        // Body, entered without testing the condition
+        self.begin_loop();
+        self.gen_stmt(body);
+
+        // Condition, where `continue` goes; jump back while it holds
+        let continue_target = self.code.len();
+        self.gen_expr(condition);
+        let jump_back_idx = self.code.len();
+        self.code.push(LpsOpCode::JumpIfNonZero(
+            (loop_start as i32) - (jump_back_idx as i32) - 1,
+        ));
+
+        self.end_loop(continue_target);
+    }
+}
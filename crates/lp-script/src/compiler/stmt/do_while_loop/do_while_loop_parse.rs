@@ -0,0 +1,51 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+
+/// Do-while loop parsing
+use crate::compiler::ast::{Stmt, StmtKind};
+use crate::compiler::error::{ParseError, ParseErrorKind};
+use crate::compiler::lexer::TokenKind;
+use crate::compiler::parser::Parser;
+use crate::shared::Span;
+
+impl Parser {
+    /// Parse `do body while (cond);`
+    pub(crate) fn parse_do_while_stmt(&mut self) -> Result<Stmt, ParseError> {
+        self.enter_recursion()?;
+        let start = self.current().span.start;
+        self.advance(); // consume 'do'
+
+        let body = self.parse_loop_body()?;
+
+        // Unlike the parens, a missing `while` isn't skipped over
+        if !self.expect(TokenKind::While) {
+            return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedToken {
+                    expected: String::from("while"),
+                    found: format!("{:?}", self.current().kind),
+                },
+                span: self.current().span,
+            });
+        }
+        self.expect(TokenKind::LParen);
+        let condition = self.ternary()?;
+        let mut end = self.current().span.end;
+        self.expect(TokenKind::RParen);
+        if matches!(self.current().kind, TokenKind::Semicolon) {
+            end = self.current().span.end;
+            self.advance();
+        }
+
+        let result = Ok(Stmt::new(
+            StmtKind::DoWhile {
+                body: Box::new(body),
+                condition,
+            },
+            Span::new(start, end),
+        ));
+
+        self.exit_recursion();
+        result
+    }
+}
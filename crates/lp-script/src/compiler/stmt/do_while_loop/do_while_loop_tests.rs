@@ -0,0 +1,153 @@
+/// Do-while loop tests
+#[cfg(test)]
+mod tests {
+    use crate::compiler::error::{CompileError, ParseErrorKind};
+    use crate::compiler::stmt::stmt_test_util::ScriptTest;
+    use crate::fixed::ToFixed;
+    use crate::shared::Type;
+    use crate::vm::opcodes::LpsOpCode;
+    use crate::{compile_script_with_options, OptimizeOptions};
+
+    #[test]
+    fn test_do_while_ast_and_back_edge() -> Result<(), String> {
+        ScriptTest::new("float x = 3.0; do { x = x - 1.0; } while (x); return x;")
+            .expect_ast(|b| {
+                let init = b.num(3.0);
+                let decl = b.var_decl(Type::Fixed, "x", Some(init));
+                let x = b.typed_var("x", Type::Fixed);
+                let one = b.num(1.0);
+                let dec = b.sub(x, one, Type::Fixed);
+                let assign = b.assign("x", dec, Type::Fixed);
+                let assign_stmt = b.expr_stmt(assign);
+                let body = b.block(vec![assign_stmt]);
+                let cond = b.typed_var("x", Type::Fixed);
+                let do_while = b.do_while_stmt(body, cond);
+                let ret_var = b.typed_var("x", Type::Fixed);
+                let ret = b.return_stmt(ret_var);
+                b.program(vec![decl, do_while, ret])
+            })
+            .expect_opcodes(vec![
+                LpsOpCode::Push(3.0.to_fixed()),
+                LpsOpCode::StoreLocalFixed(0),
+                // Body, with no test before it
+                LpsOpCode::LoadLocalFixed(0),
+                LpsOpCode::Push(1.0.to_fixed()),
+                LpsOpCode::SubFixed,
+                LpsOpCode::Dup1,
+                LpsOpCode::StoreLocalFixed(0),
+                LpsOpCode::Drop1,
+                // Condition, jumping back to the body
+                LpsOpCode::LoadLocalFixed(0),
+                LpsOpCode::JumpIfNonZero(-8),
+                LpsOpCode::LoadLocalFixed(0),
+                LpsOpCode::Return,
+            ])
+            .expect_result_fixed(0.0)
+            .run()
+    }
+
+    #[test]
+    fn test_do_while_body_runs_once_when_condition_false() -> Result<(), String> {
+        ScriptTest::new(
+            "float runs = 0.0;
+             do { runs = runs + 1.0; } while (runs > 5.0);
+             return runs;",
+        )
+        .expect_result_fixed(1.0)
+        .run()
+    }
+
+    #[test]
+    fn test_do_while_counts_up() -> Result<(), String> {
+        ScriptTest::new(
+            "int i = 0;
+             float sum = 0.0;
+             do { i++; sum += float(i); } while (i < 4);
+             return sum;",
+        )
+        .expect_result_fixed(10.0) // 1 + 2 + 3 + 4
+        .run()
+    }
+
+    #[test]
+    fn test_do_while_single_statement_body() -> Result<(), String> {
+        ScriptTest::new("int i = 0; do i += 3; while (i < 10); return float(i);")
+            .expect_result_fixed(12.0)
+            .run()
+    }
+
+    #[test]
+    fn test_continue_in_do_while_tests_condition() -> Result<(), String> {
+        ScriptTest::new(
+            "int i = 0;
+             float sum = 0.0;
+             do {
+                 i++;
+                 if (i == 2) { continue; }
+                 sum += float(i);
+             } while (i < 4);
+             return sum;",
+        )
+        .expect_result_fixed(8.0) // 1 + 3 + 4
+        .run()
+    }
+
+    #[test]
+    fn test_break_in_do_while() -> Result<(), String> {
+        ScriptTest::new(
+            "float x = 0.0;
+             do {
+                 x += 1.0;
+                 if (x >= 3.0) { break; }
+             } while (x < 100.0);
+             return x;",
+        )
+        .expect_result_fixed(3.0)
+        .run()
+    }
+
+    #[test]
+    fn test_nested_do_while() -> Result<(), String> {
+        ScriptTest::new(
+            "int i = 0;
+             int n = 0;
+             do {
+                 int j = 0;
+                 do { j++; n++; } while (j < 3);
+                 i++;
+             } while (i < 2);
+             return float(n);",
+        )
+        .expect_result_fixed(6.0)
+        .run()
+    }
+
+    #[test]
+    fn test_do_while_in_function() -> Result<(), String> {
+        ScriptTest::new(
+            "float halvings(float x) {
+                 float n = 0.0;
+                 do { x = x / 2.0; n += 1.0; } while (x > 1.0);
+                 return n;
+             }
+             return halvings(8.0) + halvings(0.5);",
+        )
+        .expect_result_fixed(4.0) // 3 + 1
+        .run()
+    }
+
+    #[test]
+    fn test_do_without_while_is_an_error() {
+        let result = compile_script_with_options(
+            "float x = 0.0; do { x += 1.0; } return x;",
+            &OptimizeOptions::none(),
+        );
+        match result {
+            Err(CompileError::Parser(err)) => match err.kind {
+                ParseErrorKind::UnexpectedToken { expected, .. } => assert_eq!(expected, "while"),
+                other => panic!("Expected UnexpectedToken, got {:?}", other),
+            },
+            _ => panic!("Expected Parse error"),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+/// Do-while loop module
+mod do_while_loop_gen;
+mod do_while_loop_parse;
+#[cfg(test)]
+mod do_while_loop_tests;
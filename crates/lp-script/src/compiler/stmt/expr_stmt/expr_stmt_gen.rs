@@ -13,9 +13,11 @@ impl<'a> CodeGenerator<'a> {
         // Drop appropriate number of stack values based on expression type
         let expr_ty = expr.ty.as_ref();
         let drop_op = match expr_ty {
-            Some(Type::Vec2) => LpsOpCode::Drop2,
-            Some(Type::Vec3) => LpsOpCode::Drop3,
-            Some(Type::Vec4) => LpsOpCode::Drop4,
+            Some(Type::Vec2 | Type::BVec2) => LpsOpCode::Drop2,
+            Some(Type::Vec3 | Type::BVec3) => LpsOpCode::Drop3,
+            Some(Type::Vec4 | Type::BVec4 | Type::Mat2) => LpsOpCode::Drop4,
+            Some(Type::Mat3) => LpsOpCode::Drop9,
+            Some(Type::Mat4) => LpsOpCode::Drop16,
             _ => LpsOpCode::Drop1,
         };
         self.code.push(drop_op);
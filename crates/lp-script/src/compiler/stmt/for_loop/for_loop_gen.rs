@@ -33,9 +33,11 @@ impl<'a> CodeGenerator<'a> {
         };
 
         // Body
+        self.begin_loop();
         self.gen_stmt(body);
 
-        // Increment
+        // Increment, where `continue` goes
+        let continue_target = self.code.len();
         if let Some(inc) = increment {
             self.gen_expr(inc);
             self.code.push(LpsOpCode::Drop1); // Discard result
@@ -54,6 +56,7 @@ impl<'a> CodeGenerator<'a> {
                 *offset = (end as i32) - (jump_idx as i32) - 1;
             }
         }
+        self.end_loop(continue_target);
 
         self.locals.pop_scope();
     }
@@ -1,11 +1,13 @@
 use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
 
 /// For loop parsing
-use crate::compiler::ast::{Stmt, StmtKind};
-use crate::compiler::error::ParseError;
+use crate::compiler::ast::{Expr, ExprKind, Stmt, StmtKind};
+use crate::compiler::error::{ParseError, ParseErrorKind};
 use crate::compiler::lexer::TokenKind;
 use crate::compiler::parser::Parser;
-use crate::shared::Span;
+use crate::shared::{Span, Type};
 
 impl Parser {
     pub(crate) fn parse_for_stmt(&mut self) -> Result<Stmt, ParseError> {
@@ -13,6 +15,15 @@ impl Parser {
         let start = self.current().span.start;
         self.advance(); // consume 'for'
 
+        // The loop variable is scoped to the loop
+        self.push_scope();
+        if self.at_for_range() {
+            let result = self.parse_for_range(start);
+            self.pop_scope();
+            self.exit_recursion();
+            return result;
+        }
+
         self.expect(TokenKind::LParen);
 
         // Parse init (can be var decl or expression)
@@ -52,7 +63,7 @@ impl Parser {
 
         self.expect(TokenKind::RParen);
 
-        let body = self.parse_stmt()?;
+        let body = self.parse_loop_body()?;
         let end = body.span.end;
 
         let result = Ok(Stmt::new(
@@ -65,7 +76,74 @@ impl Parser {
             Span::new(start, end),
         ));
 
+        self.pop_scope();
         self.exit_recursion();
         result
     }
+
+    /// `for i in ...`: a loop variable followed by the contextual `in`
+    fn at_for_range(&self) -> bool {
+        matches!(self.current().kind, TokenKind::Ident(_))
+            && matches!(
+                self.tokens.get(self.pos + 1).map(|t| &t.kind),
+                Some(TokenKind::Ident(word)) if word == "in"
+            )
+    }
+
+    /// Parse `for i in start..end body` as `for (int i = start; i < end; i++) body`
+    fn parse_for_range(&mut self, start: usize) -> Result<Stmt, ParseError> {
+        let name = match &self.current().kind {
+            TokenKind::Ident(name) => name.clone(),
+            _ => String::new(),
+        };
+        let name_span = self.current().span;
+        self.advance(); // consume loop variable
+        self.advance(); // consume 'in'
+
+        let range_start = self.ternary()?;
+        if !matches!(self.current().kind, TokenKind::DotDot) {
+            return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedToken {
+                    expected: String::from("'..' in range"),
+                    found: format!("{:?}", self.current().kind),
+                },
+                span: self.current().span,
+            });
+        }
+        self.advance(); // consume '..'
+        let range_end = self.ternary()?;
+        self.declare(&name, None);
+
+        let body = self.parse_loop_body()?;
+        let end = body.span.end;
+
+        let init_span = Span::new(name_span.start, range_start.span.end);
+        let init = Stmt::new(
+            StmtKind::VarDecl {
+                ty: Type::Int32,
+                name: name.clone(),
+                init: Some(range_start),
+            },
+            init_span,
+        );
+        let condition_span = Span::new(name_span.start, range_end.span.end);
+        let condition = Expr::new(
+            ExprKind::Less(
+                Box::new(Expr::new(ExprKind::Variable(name.clone()), name_span)),
+                Box::new(range_end),
+            ),
+            condition_span,
+        );
+        let increment = Expr::new(ExprKind::PostIncrement(name), name_span);
+
+        Ok(Stmt::new(
+            StmtKind::For {
+                init: Some(Box::new(init)),
+                condition: Some(condition),
+                increment: Some(increment),
+                body: Box::new(body),
+            },
+            Span::new(start, end),
+        ))
+    }
 }
@@ -1,7 +1,10 @@
 /// For loop tests
 #[cfg(test)]
 mod tests {
+    use crate::compiler::error::{CompileError, ParseErrorKind, TypeErrorKind};
     use crate::compiler::stmt::stmt_test_util::ScriptTest;
+    use crate::shared::Type;
+    use crate::{compile_script_with_options, OptimizeOptions};
 
     #[test]
     fn test_for_loop_basic() -> Result<(), String> {
@@ -249,4 +252,92 @@ mod tests {
         .expect_result_fixed(3.0)
         .run()
     }
+
+    #[test]
+    fn test_for_range_sum() -> Result<(), String> {
+        ScriptTest::new(
+            "int n = 5;
+             int sum = 0;
+             for i in 1..n { sum += i; }
+             return float(sum);",
+        )
+        .expect_result_fixed(10.0) // 1 + 2 + 3 + 4
+        .run()
+    }
+
+    #[test]
+    fn test_for_range_matches_c_style_opcodes() {
+        let compile = |src: &str| {
+            compile_script_with_options(src, &OptimizeOptions::none())
+                .unwrap()
+                .functions[0]
+                .opcodes
+                .clone()
+        };
+        let range = compile("int n = 4; float s = 0.0; for i in 0..n { s += 1.0; } return s;");
+        let c_style = compile(
+            "int n = 4; float s = 0.0; for (int i = 0; i < n; i++) { s += 1.0; } return s;",
+        );
+        assert_eq!(range, c_style);
+    }
+
+    #[test]
+    fn test_for_range_variable_scoped_to_loop() {
+        let result = compile_script_with_options(
+            "for i in 0..3 { } return float(i);",
+            &OptimizeOptions::none(),
+        );
+        match result {
+            Err(CompileError::TypeCheck(err)) => {
+                assert!(matches!(err.kind, TypeErrorKind::UndefinedVariable(_)))
+            }
+            _ => panic!("Expected TypeCheck error"),
+        }
+    }
+
+    #[test]
+    fn test_for_range_bounds_must_be_int() {
+        for src in [
+            "for i in 0..2.5 { } return 0.0;",
+            "for i in 0.5..2 { } return 0.0;",
+        ] {
+            match compile_script_with_options(src, &OptimizeOptions::none()) {
+                Err(CompileError::TypeCheck(err)) => assert!(matches!(
+                    err.kind,
+                    TypeErrorKind::Mismatch {
+                        expected: Type::Int32,
+                        found: Type::Fixed,
+                    }
+                )),
+                _ => panic!("Expected int bound error for {}", src),
+            }
+        }
+    }
+
+    #[test]
+    fn test_for_range_requires_dot_dot() {
+        let result =
+            compile_script_with_options("for i in 0, 3 { } return 0.0;", &OptimizeOptions::none());
+        match result {
+            Err(CompileError::Parser(err)) => {
+                assert!(matches!(err.kind, ParseErrorKind::UnexpectedToken { .. }))
+            }
+            _ => panic!("Expected Parse error"),
+        }
+    }
+
+    #[test]
+    fn test_for_range_in_parens_is_an_error() {
+        // The `..` is left over when this parses as a C-style loop
+        let result = compile_script_with_options(
+            "float s = 0.0; for (i in 0..2) { s = s + 1.0; } return s;",
+            &OptimizeOptions::none(),
+        );
+        match result {
+            Err(CompileError::Parser(err)) => {
+                assert!(matches!(err.kind, ParseErrorKind::UnexpectedToken { .. }))
+            }
+            _ => panic!("Expected Parse error"),
+        }
+    }
 }
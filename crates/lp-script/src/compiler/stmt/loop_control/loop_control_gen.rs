@@ -0,0 +1,59 @@
+/// Break and continue code generation
+extern crate alloc;
+
+use crate::compiler::codegen::{CodeGenerator, LoopJumps};
+use crate::vm::opcodes::LpsOpCode;
+
+impl<'a> CodeGenerator<'a> {
+    pub(crate) fn gen_break(&mut self) {
+        let jump_idx = self.code.len();
+        self.code.push(LpsOpCode::Jump(0)); // Placeholder, patched by `end_loop`
+                                            // The parser rejects `break` outside of a loop or switch
+        self.loops
+            .last_mut()
+            .expect("break outside of a loop or switch")
+            .breaks
+            .push(jump_idx);
+    }
+
+    pub(crate) fn gen_continue(&mut self) {
+        let jump_idx = self.code.len();
+        self.code.push(LpsOpCode::Jump(0)); // Placeholder, patched by `end_loop`
+                                            // The parser rejects `continue` outside of a loop
+        self.loops
+            .iter_mut()
+            .rev()
+            .find(|jumps| !jumps.is_switch)
+            .expect("continue outside of a loop")
+            .continues
+            .push(jump_idx);
+    }
+
+    /// Start collecting the `break`/`continue` jumps of a loop body
+    pub(crate) fn begin_loop(&mut self) {
+        self.loops.push(LoopJumps::default());
+    }
+
+    /// Start collecting the `break` jumps of a switch's cases
+    pub(crate) fn begin_switch(&mut self) {
+        self.loops.push(LoopJumps {
+            is_switch: true,
+            ..LoopJumps::default()
+        });
+    }
+
+    /// Point the innermost loop's `continue`s at `continue_target` and its
+    /// `break`s at the current end of the code (for a switch, which has no
+    /// `continue`s of its own, any target will do)
+    pub(crate) fn end_loop(&mut self, continue_target: usize) {
+        let jumps = self.loops.pop().expect("end_loop without begin_loop");
+        let end = self.code.len();
+        for (jumps, target) in [(jumps.breaks, end), (jumps.continues, continue_target)] {
+            for jump_idx in jumps {
+                if let LpsOpCode::Jump(ref mut offset) = self.code[jump_idx] {
+                    *offset = (target as i32) - (jump_idx as i32) - 1;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+/// Break and continue statement parsing
+use crate::compiler::ast::{Stmt, StmtKind};
+use crate::compiler::error::{ParseError, ParseErrorKind};
+use crate::compiler::lexer::TokenKind;
+use crate::compiler::parser::Parser;
+use crate::shared::Span;
+
+impl Parser {
+    pub(crate) fn parse_loop_control_stmt(&mut self) -> Result<Stmt, ParseError> {
+        let token = self.current().clone();
+        let (kind, keyword, allowed) = match token.kind {
+            TokenKind::Break => (StmtKind::Break, "break", self.in_loop() || self.in_switch()),
+            _ => (StmtKind::Continue, "continue", self.in_loop()),
+        };
+        if !allowed {
+            return Err(ParseError {
+                kind: ParseErrorKind::OutsideLoop { keyword },
+                span: token.span,
+            });
+        }
+        self.advance(); // consume 'break' / 'continue'
+        self.consume_semicolon();
+
+        Ok(Stmt::new(kind, Span::new(token.span.start, token.span.end)))
+    }
+}
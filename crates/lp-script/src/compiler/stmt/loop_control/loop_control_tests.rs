@@ -0,0 +1,139 @@
+/// Break and continue tests
+#[cfg(test)]
+mod tests {
+    use crate::compiler::error::{CompileError, ParseErrorKind};
+    use crate::compiler::stmt::stmt_test_util::ScriptTest;
+    use crate::{compile_script_with_options, OptimizeOptions};
+
+    #[test]
+    fn test_break_in_while() -> Result<(), String> {
+        ScriptTest::new(
+            "float i = 0.0;
+             while (i < 100.0) {
+                 if (i >= 3.0) { break; }
+                 i = i + 1.0;
+             }
+             return i;",
+        )
+        .expect_result_fixed(3.0)
+        .run()
+    }
+
+    #[test]
+    fn test_continue_in_while() -> Result<(), String> {
+        ScriptTest::new(
+            "float i = 0.0;
+             float sum = 0.0;
+             while (i < 5.0) {
+                 i = i + 1.0;
+                 if (i == 3.0) { continue; }
+                 sum = sum + i;
+             }
+             return sum;",
+        )
+        .expect_result_fixed(12.0) // 1 + 2 + 4 + 5
+        .run()
+    }
+
+    #[test]
+    fn test_continue_in_for_runs_increment() -> Result<(), String> {
+        ScriptTest::new(
+            "float sum = 0.0;
+             for (int i = 0; i < 6; i++) {
+                 if (i % 2 == 0) { continue; }
+                 sum = sum + float(i);
+             }
+             return sum;",
+        )
+        .expect_result_fixed(9.0) // 1 + 3 + 5
+        .run()
+    }
+
+    #[test]
+    fn test_break_in_for() -> Result<(), String> {
+        ScriptTest::new(
+            "float sum = 0.0;
+             for (float i = 0.0; i < 10.0; i = i + 1.0) {
+                 if (i == 4.0) { break; }
+                 sum = sum + i;
+             }
+             return sum;",
+        )
+        .expect_result_fixed(6.0) // 0 + 1 + 2 + 3
+        .run()
+    }
+
+    #[test]
+    fn test_break_in_range_loop() -> Result<(), String> {
+        ScriptTest::new(
+            "float sum = 0.0;
+             for i in 0..10 {
+                 if (i > 2) break;
+                 sum += 1.0;
+             }
+             return sum;",
+        )
+        .expect_result_fixed(3.0)
+        .run()
+    }
+
+    #[test]
+    fn test_break_leaves_only_inner_loop() -> Result<(), String> {
+        ScriptTest::new(
+            "float sum = 0.0;
+             for (int i = 0; i < 3; i++) {
+                 for (int j = 0; j < 10; j++) {
+                     if (j == 2) { break; }
+                     sum = sum + 1.0;
+                 }
+                 sum = sum + 10.0;
+             }
+             return sum;",
+        )
+        .expect_result_fixed(36.0) // 3 * (2 + 10)
+        .run()
+    }
+
+    #[test]
+    fn test_statements_after_break_are_skipped() -> Result<(), String> {
+        ScriptTest::new(
+            "float x = 0.0;
+             while (x < 5.0) {
+                 x = 1.0;
+                 break;
+                 x = 2.0;
+             }
+             return x;",
+        )
+        .expect_result_fixed(1.0)
+        .run()
+    }
+
+    #[test]
+    fn test_break_outside_loop_is_an_error() {
+        for src in ["break; return 0.0;", "if (1 > 0) { continue; } return 0.0;"] {
+            let result = compile_script_with_options(src, &OptimizeOptions::none());
+            match result {
+                Err(CompileError::Parser(err)) => {
+                    assert!(matches!(err.kind, ParseErrorKind::OutsideLoop { .. }))
+                }
+                _ => panic!("Expected Parse error for {}", src),
+            }
+        }
+    }
+
+    #[test]
+    fn test_break_in_function_called_from_loop_is_an_error() {
+        let result = compile_script_with_options(
+            "float f() { break; return 0.0; }
+             float s = 0.0;
+             while (s < 1.0) { s = s + f(); }
+             return s;",
+            &OptimizeOptions::none(),
+        );
+        assert!(matches!(
+            result,
+            Err(CompileError::Parser(ref err)) if matches!(err.kind, ParseErrorKind::OutsideLoop { .. })
+        ));
+    }
+}
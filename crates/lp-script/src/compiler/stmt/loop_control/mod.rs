@@ -0,0 +1,5 @@
+/// Break and continue statement module
+mod loop_control_gen;
+mod loop_control_parse;
+#[cfg(test)]
+mod loop_control_tests;
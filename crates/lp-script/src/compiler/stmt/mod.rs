@@ -1,10 +1,16 @@
 /// Statement compilation modules grouped by feature
 pub mod block;
+pub mod const_decl;
+pub mod do_while_loop;
 pub mod expr_stmt;
 pub mod for_loop;
 pub mod if_stmt;
+pub mod loop_control;
 pub mod return_stmt;
 pub(crate) mod stmt_types;
+pub mod struct_decl;
+pub mod switch_stmt;
+pub mod uniform_decl;
 pub mod var_decl;
 pub mod while_loop;
 
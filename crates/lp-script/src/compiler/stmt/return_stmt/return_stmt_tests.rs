@@ -7,6 +7,7 @@ mod tests {
     use crate::shared::Type;
     use crate::vm::opcodes::load::LoadSource;
     use crate::vm::opcodes::LpsOpCode;
+    use crate::{compile_script_with_options, LpsVm, OptimizeOptions, VmLimits};
 
     #[test]
     fn test_return_literal() -> Result<(), String> {
@@ -124,4 +125,76 @@ mod tests {
             .expect_result_fixed(2.0)
             .run()
     }
+
+    /// Every value a run leaves on the stack, for both the plain and the
+    /// optimized build of `src`
+    fn run_stack(src: &str) -> Vec<Vec<f32>> {
+        [OptimizeOptions::none(), OptimizeOptions::all()]
+            .iter()
+            .map(|options| {
+                let program = compile_script_with_options(src, options).unwrap();
+                let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+                let stack = vm
+                    .run(0.75.to_fixed(), 0.5.to_fixed(), 0.0.to_fixed())
+                    .unwrap();
+                stack.iter().map(|v| v.to_f32()).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_early_return_in_nested_if() {
+        let src = "
+            float a = 1.0;
+            if (uv.x > 0.5) {
+                float b = 2.0;
+                if (b > a) {
+                    vec2 c = vec2(b, 3.0);
+                    return vec3(a, c);
+                }
+            }
+            return vec3(0.0);
+        ";
+        for stack in run_stack(src) {
+            assert_eq!(stack, [1.0, 2.0, 3.0]);
+        }
+    }
+
+    #[test]
+    fn test_early_return_in_nested_for() {
+        let src = "
+            for (int i = 0; i < 3; i++) {
+                float fi = float(i);
+                for (int j = 0; j < 3; j++) {
+                    if (i + j == 3) {
+                        return vec3(fi, float(j), 0.5);
+                    }
+                }
+            }
+            return vec3(9.0);
+        ";
+        for stack in run_stack(src) {
+            assert_eq!(stack, [1.0, 2.0, 0.5]);
+        }
+    }
+
+    #[test]
+    fn test_early_return_from_function_loop() {
+        // Both calls return from inside the loop; the caller's values stay put
+        let src = "
+            vec3 first(int n) {
+                for (int i = 0; i < n; i++) {
+                    if (i == 1) {
+                        return vec3(float(i));
+                    }
+                }
+                return vec3(0.0);
+            }
+            vec3 c = first(3);
+            return c + first(3) * 2.0;
+        ";
+        for stack in run_stack(src) {
+            assert_eq!(stack, [3.0, 3.0, 3.0]);
+        }
+    }
 }
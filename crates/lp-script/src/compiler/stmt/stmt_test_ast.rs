@@ -148,6 +148,16 @@ impl StmtBuilder {
         )
     }
 
+    pub fn do_while_stmt(&mut self, body: Stmt, condition: Expr) -> Stmt {
+        Stmt::new(
+            StmtKind::DoWhile {
+                body: self.box_stmt(body),
+                condition,
+            },
+            Span::EMPTY,
+        )
+    }
+
     pub fn for_stmt(
         &mut self,
         init: Option<Stmt>,
@@ -168,6 +178,8 @@ impl StmtBuilder {
 
     pub fn program(&mut self, stmts: Vec<Stmt>) -> Program {
         Program {
+            uniforms: Vec::new(),
+            consts: Vec::new(),
             functions: Vec::new(),
             stmts,
             span: Span::EMPTY,
@@ -428,6 +428,16 @@ fn stmt_eq_ignore_spans(actual: &Stmt, expected: &Stmt) -> bool {
                 body: b2,
             },
         ) => expr_eq_ignore_spans(c1, c2) && stmt_eq_ignore_spans(b1, b2),
+        (
+            StmtKind::DoWhile {
+                body: b1,
+                condition: c1,
+            },
+            StmtKind::DoWhile {
+                body: b2,
+                condition: c2,
+            },
+        ) => stmt_eq_ignore_spans(b1, b2) && expr_eq_ignore_spans(c1, c2),
         (
             StmtKind::For {
                 init: i1,
@@ -1,9 +1,10 @@
 /// Statement type checking
 extern crate alloc;
 
-use crate::compiler::ast::{Stmt, StmtKind};
-use crate::compiler::error::TypeError;
+use crate::compiler::ast::{Expr, ExprKind, Stmt, StmtKind};
+use crate::compiler::error::{TypeError, TypeErrorKind};
 use crate::compiler::typechecker::{FunctionTable, SymbolTable, TypeChecker};
+use crate::shared::Type;
 
 impl TypeChecker {
     /// Type check a statement
@@ -16,10 +17,24 @@ impl TypeChecker {
             StmtKind::VarDecl { ty, name, init } => {
                 if let Some(init_expr) = init {
                     Self::infer_type(init_expr, symbols, func_table)?;
+                    let init_ty = init_expr.ty.clone().unwrap_or(Type::Fixed);
+                    if init_ty.is_bool() != ty.is_bool() {
+                        return Err(TypeError {
+                            kind: TypeErrorKind::Mismatch {
+                                expected: ty.clone(),
+                                found: init_ty,
+                            },
+                            span: init_expr.span,
+                        });
+                    }
                 }
                 let _ = symbols.declare(name.clone(), ty.clone());
             }
 
+            StmtKind::ConstDecl { ty, name, init } => {
+                Self::check_const_decl(ty, name, init, symbols, func_table)?;
+            }
+
             StmtKind::Return(expr) => {
                 Self::infer_type(expr, symbols, func_table)?;
             }
@@ -53,6 +68,11 @@ impl TypeChecker {
                 Self::check_stmt(body.as_mut(), symbols, func_table)?;
             }
 
+            StmtKind::DoWhile { body, condition } => {
+                Self::check_stmt(body.as_mut(), symbols, func_table)?;
+                Self::infer_type(condition, symbols, func_table)?;
+            }
+
             StmtKind::For {
                 init,
                 condition,
@@ -66,14 +86,62 @@ impl TypeChecker {
                 if let Some(cond) = condition {
                     Self::infer_type(cond, symbols, func_table)?;
                 }
+                if let Some(init_stmt) = init {
+                    check_int_counter(init_stmt, condition.as_ref())?;
+                }
                 if let Some(inc) = increment {
                     Self::infer_type(inc, symbols, func_table)?;
                 }
                 Self::check_stmt(body.as_mut(), symbols, func_table)?;
                 symbols.pop_scope();
             }
+
+            StmtKind::Switch { selector, cases } => {
+                Self::check_switch(selector, cases, symbols, func_table)?;
+            }
+
+            StmtKind::Break | StmtKind::Continue => {}
         }
 
         Ok(())
     }
 }
+
+/// A loop's `int` counter must start from and be compared against `int`s
+///
+/// Covers `for i in a..b`, which declares `int i = a` and tests `i < b`; a
+/// float would be stored or compared as raw fixed-point bits.
+fn check_int_counter(init: &Stmt, condition: Option<&Expr>) -> Result<(), TypeError> {
+    let StmtKind::VarDecl {
+        ty: Type::Int32,
+        name,
+        init,
+    } = &init.kind
+    else {
+        return Ok(());
+    };
+    let bound = condition.and_then(|cond| match &cond.kind {
+        ExprKind::Less(counter, bound)
+        | ExprKind::LessEq(counter, bound)
+        | ExprKind::Greater(counter, bound)
+        | ExprKind::GreaterEq(counter, bound) => {
+            matches!(&counter.kind, ExprKind::Variable(var) if var == name).then_some(&**bound)
+        }
+        _ => None,
+    });
+    for expr in init.iter().chain(bound) {
+        match &expr.ty {
+            Some(ty) if *ty != Type::Int32 => {
+                return Err(TypeError {
+                    kind: TypeErrorKind::Mismatch {
+                        expected: Type::Int32,
+                        found: ty.clone(),
+                    },
+                    span: expr.span,
+                })
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,10 @@
+/// Struct declaration module
+///
+/// Groups parsing and tests for `struct Name { type member; ... }` types.
+/// Structs are lowered while parsing: a struct local `r` becomes one local per
+/// member, named `r.member`, so later phases only see ordinary locals.
+mod struct_decl_parse;
+pub(crate) use struct_decl_parse::StructDef;
+
+#[cfg(test)]
+mod struct_decl_tests;
@@ -0,0 +1,380 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Struct declaration parsing and lowering
+use crate::compiler::ast::{Expr, ExprKind, Stmt, StmtKind};
+use crate::compiler::error::{ParseError, ParseErrorKind};
+use crate::compiler::lexer::TokenKind;
+use crate::compiler::parser::Parser;
+use crate::shared::{Span, Type};
+
+/// A `struct Name { type member; ... }` declaration
+#[derive(Debug, Clone)]
+pub(crate) struct StructDef {
+    pub(crate) name: String,
+    pub(crate) members: Vec<(String, Type)>,
+}
+
+impl StructDef {
+    fn has_member(&self, member: &str) -> bool {
+        self.members.iter().any(|(name, _)| name == member)
+    }
+}
+
+/// Name of the local holding `member` of struct local `name`
+///
+/// The `.` keeps it apart from any identifier a script can declare.
+fn member_local(name: &str, member: &str) -> String {
+    format!("{}.{}", name, member)
+}
+
+fn unexpected(expected: String, found: String, span: Span) -> ParseError {
+    ParseError {
+        kind: ParseErrorKind::UnexpectedToken { expected, found },
+        span,
+    }
+}
+
+impl Parser {
+    /// Parse `struct Name { type member; ... }` (trailing `;` optional)
+    pub(crate) fn parse_struct_def(&mut self) -> Result<(), ParseError> {
+        self.enter_recursion()?;
+        self.advance(); // consume 'struct'
+
+        let name = self.parse_struct_ident("struct name")?;
+        if self.struct_index(&name).is_some() {
+            return Err(unexpected(
+                String::from("new struct name"),
+                name,
+                self.tokens[self.pos - 1].span,
+            ));
+        }
+        if !self.expect(TokenKind::LBrace) {
+            return Err(unexpected(
+                String::from("{"),
+                format!("{:?}", self.current().kind),
+                self.current().span,
+            ));
+        }
+
+        let mut def = StructDef {
+            name,
+            members: Vec::new(),
+        };
+        while !matches!(self.current().kind, TokenKind::RBrace | TokenKind::Eof) {
+            self.reject_struct_type("members")?;
+            if !matches!(
+                self.current().kind,
+                TokenKind::Bool
+                    | TokenKind::Float
+                    | TokenKind::Int
+                    | TokenKind::Vec2
+                    | TokenKind::Vec3
+                    | TokenKind::Vec4
+                    | TokenKind::BVec2
+                    | TokenKind::BVec3
+                    | TokenKind::BVec4
+                    | TokenKind::Mat2
+                    | TokenKind::Mat3
+                    | TokenKind::Mat4
+            ) {
+                return Err(unexpected(
+                    String::from("member type"),
+                    format!("{:?}", self.current().kind),
+                    self.current().span,
+                ));
+            }
+            let ty = self.parse_type();
+            let member = self.parse_struct_ident("member name")?;
+            if def.has_member(&member) {
+                return Err(unexpected(
+                    String::from("new member name"),
+                    member,
+                    self.tokens[self.pos - 1].span,
+                ));
+            }
+            if !self.consume_semicolon() {
+                return Err(unexpected(
+                    String::from(";"),
+                    format!("{:?}", self.current().kind),
+                    self.current().span,
+                ));
+            }
+            def.members.push((member, ty));
+        }
+
+        if def.members.is_empty() || !self.expect(TokenKind::RBrace) {
+            return Err(unexpected(
+                String::from("struct member"),
+                format!("{:?}", self.current().kind),
+                self.current().span,
+            ));
+        }
+        self.consume_semicolon();
+
+        self.structs.push(def);
+        self.exit_recursion();
+        Ok(())
+    }
+
+    /// Parse a struct local declaration (`Ray r;`, `Ray r = value;`) or
+    /// whole-struct assignment (`r = value;`), lowered to one statement per
+    /// member; `None` (consuming nothing) for any other statement
+    pub(crate) fn parse_struct_stmt(&mut self) -> Result<Option<Vec<Stmt>>, ParseError> {
+        let TokenKind::Ident(first) = &self.current().kind else {
+            return Ok(None);
+        };
+        let next = &self.peek().kind;
+
+        if let (Some(struct_idx), TokenKind::Ident(_)) = (self.struct_index(first), next) {
+            self.parse_struct_local(struct_idx).map(Some)
+        } else if let (Some(struct_idx), TokenKind::Eq) = (self.struct_local(first), next) {
+            self.parse_struct_assign(struct_idx).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// `Ray r;` (members zeroed like any local) or `Ray r = value;`
+    fn parse_struct_local(&mut self, struct_idx: usize) -> Result<Vec<Stmt>, ParseError> {
+        self.enter_recursion()?;
+        let start = self.current().span.start;
+        self.advance(); // consume struct name
+        let name = self.parse_struct_ident("variable name")?;
+
+        let values = if matches!(self.current().kind, TokenKind::Eq) {
+            self.advance(); // consume '='
+            Some(self.parse_struct_value(struct_idx)?)
+        } else {
+            None
+        };
+        let span = Span::new(start, self.current().span.end);
+        self.consume_semicolon();
+        self.declare(&name, Some(struct_idx));
+
+        let mut values = values.map(Vec::into_iter);
+        let stmts = self.structs[struct_idx]
+            .members
+            .iter()
+            .map(|(member, ty)| {
+                let init = values.as_mut().and_then(|values| values.next());
+                Stmt::new(
+                    StmtKind::VarDecl {
+                        ty: ty.clone(),
+                        name: member_local(&name, member),
+                        init,
+                    },
+                    span,
+                )
+            })
+            .collect();
+
+        self.exit_recursion();
+        Ok(stmts)
+    }
+
+    /// `r = value;`, assigning member by member
+    fn parse_struct_assign(&mut self, struct_idx: usize) -> Result<Vec<Stmt>, ParseError> {
+        self.enter_recursion()?;
+        let start = self.current().span.start;
+        let name = self.parse_struct_ident("variable name")?;
+        self.advance(); // consume '='
+
+        let value_start = self.pos;
+        let values = self.parse_struct_value(struct_idx)?;
+        let span = Span::new(start, self.current().span.end);
+        // `r = Ray(r.dir, r.origin)` must read every member before writing any
+        let reads_self = self.tokens[value_start..self.pos]
+            .iter()
+            .any(|token| matches!(&token.kind, TokenKind::Ident(ident) if *ident == name));
+        self.consume_semicolon();
+
+        let assign = |member: &str, value| {
+            let assign = ExprKind::Assign {
+                target: member_local(&name, member),
+                value: Box::new(value),
+            };
+            Stmt::new(StmtKind::Expr(Expr::new(assign, span)), span)
+        };
+        let members = &self.structs[struct_idx].members;
+        let stmts = if reads_self && members.len() > 1 {
+            // Stage the values in temporaries scoped to a block
+            let temp = |member: &str| format!("{}'", member_local(&name, member));
+            let mut staged = Vec::new();
+            for ((member, ty), value) in members.iter().zip(values) {
+                let decl = StmtKind::VarDecl {
+                    ty: ty.clone(),
+                    name: temp(member),
+                    init: Some(value),
+                };
+                staged.push(Stmt::new(decl, span));
+            }
+            for (member, _) in members {
+                let value = Expr::new(ExprKind::Variable(temp(member)), span);
+                staged.push(assign(member, value));
+            }
+            vec![Stmt::new(StmtKind::Block(staged), span)]
+        } else {
+            members
+                .iter()
+                .zip(values)
+                .map(|((member, _), value)| assign(member, value))
+                .collect()
+        };
+
+        self.exit_recursion();
+        Ok(stmts)
+    }
+
+    /// A whole struct value, one expression per member: another struct local
+    /// of the same type (`s`), or a constructor (`Ray(origin, dir)`) taking
+    /// the members in declaration order
+    fn parse_struct_value(&mut self, struct_idx: usize) -> Result<Vec<Expr>, ParseError> {
+        let token = self.current().clone();
+        let def = self.structs[struct_idx].clone();
+        let expected = || format!("{} value", def.name);
+
+        let TokenKind::Ident(name) = &token.kind else {
+            return Err(unexpected(
+                expected(),
+                format!("{:?}", token.kind),
+                token.span,
+            ));
+        };
+
+        // Another struct local, copied member by member
+        if !matches!(self.peek().kind, TokenKind::Dot | TokenKind::LParen) {
+            return match self.struct_local(name) {
+                Some(idx) if idx == struct_idx => {
+                    self.advance(); // consume local name
+                    Ok(def
+                        .members
+                        .iter()
+                        .map(|(member, _)| {
+                            Expr::new(ExprKind::Variable(member_local(name, member)), token.span)
+                        })
+                        .collect())
+                }
+                _ => Err(unexpected(expected(), name.clone(), token.span)),
+            };
+        }
+
+        // Constructor
+        if *name != def.name || !matches!(self.peek().kind, TokenKind::LParen) {
+            return Err(unexpected(expected(), name.clone(), token.span));
+        }
+        let member_count = def.members.len();
+        self.advance(); // consume struct name
+        self.advance(); // consume '('
+
+        let mut args = Vec::new();
+        while !matches!(self.current().kind, TokenKind::RParen | TokenKind::Eof) {
+            args.push(self.parse_assignment_expr()?);
+            if !matches!(self.current().kind, TokenKind::Comma) {
+                break;
+            }
+            self.advance(); // consume ','
+        }
+        let end = self.current().span.end;
+        if !self.expect(TokenKind::RParen) {
+            return Err(unexpected(
+                String::from(")"),
+                format!("{:?}", self.current().kind),
+                self.current().span,
+            ));
+        }
+        if args.len() != member_count {
+            return Err(unexpected(
+                format!("{} constructor arguments", member_count),
+                format!("{}", args.len()),
+                Span::new(token.span.start, end),
+            ));
+        }
+
+        Ok(args)
+    }
+
+    /// The local holding `name.member` if `name` is a struct local
+    pub(crate) fn struct_member(
+        &self,
+        name: &str,
+        member: &str,
+        span: Span,
+    ) -> Result<Option<String>, ParseError> {
+        let Some(struct_idx) = self.struct_local(name) else {
+            return Ok(None);
+        };
+        let def = &self.structs[struct_idx];
+        if !def.has_member(member) {
+            return Err(ParseError {
+                kind: ParseErrorKind::UnknownMember {
+                    struct_name: def.name.clone(),
+                    member: String::from(member),
+                },
+                span,
+            });
+        }
+        Ok(Some(member_local(name, member)))
+    }
+
+    /// Reject a struct local or constructor used as a plain value
+    pub(crate) fn check_not_struct_value(&self, expr: &Expr) -> Result<(), ParseError> {
+        let name = match &expr.kind {
+            ExprKind::Variable(name) if self.struct_local(name).is_some() => name,
+            ExprKind::Call { name, .. } if self.struct_index(name).is_some() => name,
+            _ => return Ok(()),
+        };
+        Err(ParseError {
+            kind: ParseErrorKind::StructValue(name.clone()),
+            span: expr.span,
+        })
+    }
+
+    /// Reject a struct name where only a scalar, vector or matrix type
+    /// can go, such as a parameter type; `usage` names the place
+    pub(crate) fn reject_struct_type(&self, usage: &'static str) -> Result<(), ParseError> {
+        match &self.current().kind {
+            TokenKind::Ident(name) if self.struct_index(name).is_some() => Err(ParseError {
+                kind: ParseErrorKind::StructNotSupported {
+                    struct_name: name.clone(),
+                    usage,
+                },
+                span: self.current().span,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject a function returning a struct, `Ray name(...)`
+    pub(crate) fn reject_struct_function(&self) -> Result<(), ParseError> {
+        let after_name = self.tokens.get(self.pos + 2).map(|t| &t.kind);
+        if matches!(self.peek().kind, TokenKind::Ident(_))
+            && matches!(after_name, Some(TokenKind::LParen))
+        {
+            self.reject_struct_type("return types")?;
+        }
+        Ok(())
+    }
+
+    fn struct_index(&self, name: &str) -> Option<usize> {
+        self.structs.iter().position(|def| def.name == name)
+    }
+
+    fn parse_struct_ident(&mut self, what: &str) -> Result<String, ParseError> {
+        match &self.current().kind {
+            TokenKind::Ident(name) => {
+                let name = name.clone();
+                self.advance();
+                Ok(name)
+            }
+            other => Err(unexpected(
+                String::from(what),
+                format!("{:?}", other),
+                self.current().span,
+            )),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+/// Struct declaration tests
+#[cfg(test)]
+mod tests {
+    use crate::compiler::error::{CompileError, ParseErrorKind};
+    use crate::compiler::stmt::stmt_test_util::ScriptTest;
+    use crate::{compile_script_with_options, OptimizeOptions};
+
+    const RAY: &str = "struct Ray { vec3 origin; vec3 dir; };";
+
+    fn parse_error(src: &str) -> ParseErrorKind {
+        match compile_script_with_options(src, &OptimizeOptions::none()) {
+            Err(CompileError::Parser(err)) => err.kind,
+            _ => panic!("Expected Parse error for {}", src),
+        }
+    }
+
+    #[test]
+    fn test_struct_construct_and_access() -> Result<(), String> {
+        ScriptTest::new(&format!(
+            "{} Ray r = Ray(vec3(1.0, 2.0, 3.0), vec3(0.0, 0.0, 1.0)); \
+             vec3 p = r.origin + r.dir * 2.0; \
+             return p.z;",
+            RAY
+        ))
+        .expect_result_fixed(5.0)
+        .run()
+    }
+
+    #[test]
+    fn test_struct_members_start_zeroed_and_assign() -> Result<(), String> {
+        ScriptTest::new(
+            "struct Particle { vec2 pos; float life; int hits; } \
+             Particle p; \
+             p.life = 2.0; \
+             p.life -= 0.5; \
+             p.hits++; \
+             return p.life + float(p.hits) + p.pos.x;",
+        )
+        .expect_result_fixed(2.5)
+        .run()
+    }
+
+    #[test]
+    fn test_struct_copy_is_by_value() -> Result<(), String> {
+        ScriptTest::new(&format!(
+            "{} Ray a = Ray(vec3(1.0), vec3(2.0)); \
+             Ray b = a; \
+             b.origin = vec3(5.0); \
+             a = b; \
+             b.dir = vec3(7.0); \
+             return a.origin.x * 10.0 + a.dir.x;",
+            RAY
+        ))
+        .expect_result_fixed(52.0)
+        .run()
+    }
+
+    #[test]
+    fn test_struct_assign_reading_itself() -> Result<(), String> {
+        // Both members are read before either is written
+        ScriptTest::new(&format!(
+            "{} Ray r = Ray(vec3(1.0), vec3(2.0)); \
+             r = Ray(r.dir, r.origin); \
+             return r.origin.x * 10.0 + r.dir.x;",
+            RAY
+        ))
+        .expect_result_fixed(21.0)
+        .run()
+    }
+
+    #[test]
+    fn test_struct_locals_in_functions_and_loops() -> Result<(), String> {
+        ScriptTest::new(&format!(
+            "{} \
+             float march(vec3 o, vec3 d) {{ \
+                 Ray r = Ray(o, d); \
+                 for i in 0..4 {{ r.origin += r.dir; }} \
+                 return r.origin.y; \
+             }} \
+             return march(vec3(0.0), vec3(0.0, 0.5, 0.0));",
+            RAY
+        ))
+        .expect_result_fixed(2.0)
+        .run()
+    }
+
+    #[test]
+    fn test_struct_shadowed_by_local() -> Result<(), String> {
+        ScriptTest::new(&format!(
+            "{} Ray r = Ray(vec3(1.0), vec3(2.0)); \
+             float total = 0.0; \
+             {{ float r = 3.0; total = r; }} \
+             return total + r.dir.x;",
+            RAY
+        ))
+        .expect_result_fixed(5.0)
+        .run()
+    }
+
+    #[test]
+    fn test_unknown_member() {
+        let kind = parse_error(&format!("{} Ray r; return r.color.x;", RAY));
+        assert!(matches!(
+            kind,
+            ParseErrorKind::UnknownMember { ref struct_name, ref member }
+                if struct_name == "Ray" && member == "color"
+        ));
+    }
+
+    #[test]
+    fn test_struct_used_as_value() {
+        for src in [
+            "Ray r; return r;",
+            "Ray r; vec3 v = r + r; return 0.0;",
+            "return Ray(vec3(0.0), vec3(1.0)).origin.x;",
+        ] {
+            let kind = parse_error(&format!("{} {}", RAY, src));
+            assert!(matches!(kind, ParseErrorKind::StructValue(_)), "{}", src);
+        }
+    }
+
+    #[test]
+    fn test_struct_value_errors() {
+        for src in [
+            "Ray r = Ray(vec3(0.0)); return 0.0;",
+            "Ray r = vec3(0.0); return 0.0;",
+            "struct Other { float x; } Ray r; Other o = r; return 0.0;",
+        ] {
+            let kind = parse_error(&format!("{} {}", RAY, src));
+            assert!(
+                matches!(kind, ParseErrorKind::UnexpectedToken { .. }),
+                "{}",
+                src
+            );
+        }
+    }
+
+    #[test]
+    fn test_struct_parameters_and_returns_not_supported() {
+        for (src, expected_usage) in [
+            (
+                "float f(Ray r) { return r.origin.x; } return 0.0;",
+                "parameters",
+            ),
+            ("float f(float k, Ray r); return 0.0;", "parameters"),
+            (
+                "Ray make() { Ray r; return r; } return 0.0;",
+                "return types",
+            ),
+            ("Ray make(); return 0.0;", "return types"),
+            (
+                "struct Beam { Ray ray; float width; } return 0.0;",
+                "members",
+            ),
+        ] {
+            let kind = parse_error(&format!("{} {}", RAY, src));
+            assert!(
+                matches!(
+                    kind,
+                    ParseErrorKind::StructNotSupported { ref struct_name, usage }
+                        if struct_name == "Ray" && usage == expected_usage
+                ),
+                "{}: {:?}",
+                src,
+                kind
+            );
+        }
+    }
+
+    #[test]
+    fn test_struct_def_errors() {
+        for src in [
+            "struct S { } return 0.0;",
+            "struct S { float x; float x; } return 0.0;",
+            "struct S { float x } return 0.0;",
+            "struct S { float x; } struct S { float y; } return 0.0;",
+        ] {
+            assert!(
+                matches!(parse_error(src), ParseErrorKind::UnexpectedToken { .. }),
+                "{}",
+                src
+            );
+        }
+    }
+}
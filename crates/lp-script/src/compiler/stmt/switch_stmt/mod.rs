@@ -0,0 +1,11 @@
+/// Switch statement module
+///
+/// Groups parsing, type checking, code generation, and tests for
+/// `switch (int) { case N: ... default: ... }`. Dense cases compile to a
+/// `JumpTable`, others to a chain of comparisons.
+mod switch_stmt_gen;
+mod switch_stmt_parse;
+mod switch_stmt_types;
+
+#[cfg(test)]
+mod switch_stmt_tests;
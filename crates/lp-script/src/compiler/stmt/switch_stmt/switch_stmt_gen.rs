@@ -0,0 +1,94 @@
+/// Switch statement code generation
+extern crate alloc;
+use alloc::vec::Vec;
+
+use crate::compiler::ast::{Expr, ExprKind, SwitchCase};
+use crate::compiler::codegen::CodeGenerator;
+use crate::vm::opcodes::LpsOpCode;
+
+/// Fewest cases worth a `JumpTable` over a chain of comparisons
+const JUMP_TABLE_MIN_CASES: usize = 3;
+
+impl<'a> CodeGenerator<'a> {
+    /// Dispatch on the selector, then generate the cases' statements in order
+    /// so each falls through into the next
+    pub(crate) fn gen_switch_stmt(&mut self, selector: &Expr, cases: &[SwitchCase]) {
+        // (value, case index), values folded to literals by the type checker
+        let values: Vec<(i32, usize)> = cases
+            .iter()
+            .enumerate()
+            .filter_map(|(i, case)| match case.value.as_ref().map(|v| &v.kind) {
+                Some(ExprKind::IntNumber(n)) => Some((*n, i)),
+                _ => None,
+            })
+            .collect();
+
+        self.gen_expr(selector);
+
+        // Jumps to a case's statements, and to the default (or the end)
+        let mut case_jumps: Vec<(usize, usize)> = Vec::new();
+        let mut default_jumps = Vec::new();
+        let min = values.iter().map(|&(n, _)| n).min().unwrap_or(0);
+        let max = values.iter().map(|&(n, _)| n).max().unwrap_or(0);
+        let span = max as i64 - min as i64 + 1;
+        if values.len() >= JUMP_TABLE_MIN_CASES && span <= 2 * values.len() as i64 {
+            // Dense: one entry per value in min..=max, then the default
+            self.code.push(LpsOpCode::PushInt32(min));
+            self.code.push(LpsOpCode::JumpTable(span as u32));
+            for value in min..=max {
+                let jump_idx = self.code.len();
+                self.code.push(LpsOpCode::Jump(0)); // Placeholder
+                match values.iter().find(|&&(n, _)| n == value) {
+                    Some(&(_, case)) => case_jumps.push((jump_idx, case)),
+                    None => default_jumps.push(jump_idx),
+                }
+            }
+        } else {
+            // Sparse: compare against each value in turn
+            for &(value, case) in &values {
+                self.code.push(LpsOpCode::Dup1);
+                self.code.push(LpsOpCode::PushInt32(value));
+                self.code.push(LpsOpCode::EqInt32);
+                self.code.push(LpsOpCode::JumpIfZero(2)); // Over the next two
+                self.code.push(LpsOpCode::Drop1);
+                case_jumps.push((self.code.len(), case));
+                self.code.push(LpsOpCode::Jump(0)); // Placeholder
+            }
+            self.code.push(LpsOpCode::Drop1);
+        }
+        default_jumps.push(self.code.len());
+        self.code.push(LpsOpCode::Jump(0)); // Placeholder
+
+        // Cases share one scope
+        self.begin_switch();
+        self.locals.push_scope();
+        let mut case_starts = Vec::with_capacity(cases.len());
+        for case in cases {
+            case_starts.push(self.code.len());
+            for stmt in &case.body {
+                self.gen_stmt(stmt);
+            }
+        }
+        self.locals.pop_scope();
+
+        let end = self.code.len();
+        let default_start = cases
+            .iter()
+            .position(|case| case.value.is_none())
+            .map_or(end, |i| case_starts[i]);
+        let jumps = case_jumps
+            .into_iter()
+            .map(|(jump_idx, case)| (jump_idx, case_starts[case]))
+            .chain(
+                default_jumps
+                    .into_iter()
+                    .map(|jump_idx| (jump_idx, default_start)),
+            );
+        for (jump_idx, target) in jumps {
+            if let LpsOpCode::Jump(ref mut offset) = self.code[jump_idx] {
+                *offset = (target as i32) - (jump_idx as i32) - 1;
+            }
+        }
+        self.end_loop(end);
+    }
+}
@@ -0,0 +1,83 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Switch statement parsing
+use crate::compiler::ast::{Stmt, StmtKind, SwitchCase};
+use crate::compiler::error::{ParseError, ParseErrorKind};
+use crate::compiler::lexer::TokenKind;
+use crate::compiler::parser::Parser;
+use crate::shared::Span;
+
+impl Parser {
+    /// Parse `switch (selector) { case value: stmts... default: stmts... }`
+    pub(crate) fn parse_switch_stmt(&mut self) -> Result<Stmt, ParseError> {
+        self.enter_recursion()?;
+        let start = self.current().span.start;
+        self.advance(); // consume 'switch'
+
+        self.expect(TokenKind::LParen);
+        let selector = self.ternary()?;
+        self.expect(TokenKind::RParen);
+        if !self.expect(TokenKind::LBrace) {
+            return Err(self.switch_error("{"));
+        }
+
+        // All cases share one scope, as in C
+        let mut cases: Vec<SwitchCase> = Vec::new();
+        self.push_scope();
+        while !matches!(self.current().kind, TokenKind::RBrace | TokenKind::Eof) {
+            match self.current().kind {
+                TokenKind::Case => {
+                    self.advance();
+                    let value = self.ternary()?;
+                    if !self.expect(TokenKind::Colon) {
+                        return Err(self.switch_error(":"));
+                    }
+                    cases.push(SwitchCase {
+                        value: Some(value),
+                        body: Vec::new(),
+                    });
+                }
+                TokenKind::Default if cases.iter().all(|case| case.value.is_some()) => {
+                    self.advance();
+                    if !self.expect(TokenKind::Colon) {
+                        return Err(self.switch_error(":"));
+                    }
+                    cases.push(SwitchCase {
+                        value: None,
+                        body: Vec::new(),
+                    });
+                }
+                // A second `default`
+                TokenKind::Default => return Err(self.switch_error("case")),
+                _ => match cases.last_mut() {
+                    Some(case) => self.parse_switch_body_into(&mut case.body)?,
+                    None => return Err(self.switch_error("case or default")),
+                },
+            }
+        }
+        self.pop_scope();
+
+        let end = self.current().span.end;
+        self.expect(TokenKind::RBrace);
+
+        let result = Ok(Stmt::new(
+            StmtKind::Switch { selector, cases },
+            Span::new(start, end),
+        ));
+
+        self.exit_recursion();
+        result
+    }
+
+    fn switch_error(&self, expected: &str) -> ParseError {
+        ParseError {
+            kind: ParseErrorKind::UnexpectedToken {
+                expected: String::from(expected),
+                found: format!("{:?}", self.current().kind),
+            },
+            span: self.current().span,
+        }
+    }
+}
@@ -0,0 +1,309 @@
+/// Switch statement tests
+#[cfg(test)]
+mod tests {
+    use crate::compiler::error::{CompileError, ParseErrorKind, TypeErrorKind};
+    use crate::compiler::stmt::stmt_test_util::ScriptTest;
+    use crate::fixed::{Fixed, ToFixed};
+    use crate::shared::Type;
+    use crate::vm::opcodes::LpsOpCode;
+    use crate::vm::vm_limits::VmLimits;
+    use crate::*;
+
+    fn compile_error(src: &str) -> CompileError {
+        match compile_script_with_options(src, &OptimizeOptions::none()) {
+            Err(err) => err,
+            Ok(_) => panic!("Expected compile error for {}", src),
+        }
+    }
+
+    /// Run `src` with its `int i` uniform set to each of `inputs`, under
+    /// every optimization level
+    fn run_each(src: &str, inputs: &[i32]) -> Vec<f32> {
+        let mut results = Vec::new();
+        for options in [OptimizeOptions::none(), OptimizeOptions::all()] {
+            let program = compile_script_with_options(src, &options).unwrap();
+            program.validate().unwrap();
+            let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+            let run: Vec<f32> = inputs
+                .iter()
+                .map(|&i| {
+                    vm.set_uniform("i", InputValue::Int32(i)).unwrap();
+                    vm.run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+                        .unwrap()
+                        .to_f32()
+                })
+                .collect();
+            if !results.is_empty() {
+                assert_eq!(run, results, "optimized and unoptimized runs differ");
+            }
+            results = run;
+        }
+        results
+    }
+
+    const PALETTE: &str = "uniform int i;
+         float v = -1.0;
+         switch (i) {
+             case 0: v = 10.0; break;
+             case 1: v = 11.0; break;
+             case 2: v = 12.0; break;
+             case 4: v = 14.0; break;
+             default: v = 99.0;
+         }
+         return v;";
+
+    #[test]
+    fn test_dense_switch_uses_jump_table() {
+        let program = compile_script_with_options(PALETTE, &OptimizeOptions::none()).unwrap();
+        let opcodes = &program.main_function().unwrap().opcodes;
+        assert!(opcodes.contains(&LpsOpCode::JumpTable(5)));
+        assert!(!opcodes.contains(&LpsOpCode::EqInt32));
+    }
+
+    #[test]
+    fn test_dense_switch_results() {
+        assert_eq!(
+            run_each(PALETTE, &[0, 1, 2, 3, 4, 5, -1, i32::MIN, i32::MAX]),
+            vec![10.0, 11.0, 12.0, 99.0, 14.0, 99.0, 99.0, 99.0, 99.0]
+        );
+    }
+
+    #[test]
+    fn test_sparse_switch_uses_comparisons() -> Result<(), String> {
+        ScriptTest::new(
+            "int i = 100; switch (i) { case 1: return 1.0; case 100: return 2.0; } return 0.0;",
+        )
+        .expect_opcodes(vec![
+            LpsOpCode::PushInt32(100),
+            LpsOpCode::StoreLocalInt32(0),
+            LpsOpCode::LoadLocalInt32(0),
+            LpsOpCode::Dup1,
+            LpsOpCode::PushInt32(1),
+            LpsOpCode::EqInt32,
+            LpsOpCode::JumpIfZero(2),
+            LpsOpCode::Drop1,
+            LpsOpCode::Jump(8),
+            LpsOpCode::Dup1,
+            LpsOpCode::PushInt32(100),
+            LpsOpCode::EqInt32,
+            LpsOpCode::JumpIfZero(2),
+            LpsOpCode::Drop1,
+            LpsOpCode::Jump(4),
+            LpsOpCode::Drop1,
+            LpsOpCode::Jump(4),
+            // case 1
+            LpsOpCode::Push(1.0.to_fixed()),
+            LpsOpCode::Return,
+            // case 100
+            LpsOpCode::Push(2.0.to_fixed()),
+            LpsOpCode::Return,
+            LpsOpCode::Push(0.0.to_fixed()),
+            LpsOpCode::Return,
+        ])
+        .expect_result_fixed(2.0)
+        .run()
+    }
+
+    #[test]
+    fn test_sparse_switch_results() {
+        let src = "uniform int i;
+             switch (i) {
+                 case -50: return 1.0;
+                 case 7: return 2.0;
+                 case 1000: return 3.0;
+             }
+             return 0.0;";
+        assert_eq!(
+            run_each(src, &[-50, 7, 1000, 0, 8]),
+            vec![1.0, 2.0, 3.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_fallthrough_and_shared_labels() {
+        let src = "uniform int i;
+             float v = 0.0;
+             switch (i) {
+                 case 0:
+                 case 1: v += 1.0;
+                 case 2: v += 10.0; break;
+                 default: v += 100.0;
+                 case 3: v += 1000.0;
+             }
+             return v;";
+        assert_eq!(
+            run_each(src, &[0, 1, 2, 3, 4]),
+            vec![11.0, 11.0, 10.0, 1000.0, 1100.0]
+        );
+    }
+
+    #[test]
+    fn test_case_values_fold_constants() {
+        let src = "uniform int i;
+             const int BASE = 10;
+             switch (i) {
+                 case BASE: return 1.0;
+                 case BASE + 1: return 2.0;
+                 case BASE * 2 - 8: return 3.0;
+                 case -BASE: return 4.0;
+             }
+             return 0.0;";
+        assert_eq!(
+            run_each(src, &[10, 11, 12, -10, 0]),
+            vec![1.0, 2.0, 3.0, 4.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_break_and_continue_in_switch_in_loop() -> Result<(), String> {
+        ScriptTest::new(
+            "float sum = 0.0;
+             for (int i = 0; i < 6; i++) {
+                 switch (i % 3) {
+                     case 0: continue;
+                     case 1: sum += 1.0; break;
+                     default: sum += 10.0;
+                 }
+                 sum += 100.0;
+             }
+             return sum;",
+        )
+        .expect_result_fixed(422.0) // 4 * 100 + 2 * 1 + 2 * 10
+        .run()
+    }
+
+    #[test]
+    fn test_loop_inside_switch_breaks_itself() -> Result<(), String> {
+        ScriptTest::new(
+            "int k = 1;
+             float n = 0.0;
+             switch (k) {
+                 case 1:
+                     while (n < 100.0) { n += 1.0; if (n >= 3.0) { break; } }
+                     n += 10.0;
+                     break;
+                 default: n = -1.0;
+             }
+             return n;",
+        )
+        .expect_result_fixed(13.0)
+        .run()
+    }
+
+    #[test]
+    fn test_case_locals_share_the_switch_scope() -> Result<(), String> {
+        ScriptTest::new(
+            "int k = 0;
+             float r = 0.0;
+             switch (k) {
+                 case 0: float x = 2.0; r = x;
+                 case 1: r += 1.0;
+             }
+             return r;",
+        )
+        .expect_result_fixed(3.0)
+        .run()
+    }
+
+    #[test]
+    fn test_switch_with_default_returns() -> Result<(), String> {
+        ScriptTest::new(
+            "float pick(int k) {
+                 switch (k) {
+                     case 0: return 5.0;
+                     default: return 7.0;
+                 }
+             }
+             return pick(0) + pick(3);",
+        )
+        .expect_result_fixed(12.0)
+        .run()
+    }
+
+    #[test]
+    fn test_switch_without_default_may_not_return() {
+        for src in [
+            "float pick(int k) { switch (k) { case 0: return 5.0; } } return pick(0);",
+            "float pick(int k) { switch (k) { default: break; case 0: return 5.0; } } return pick(0);",
+        ] {
+            match compile_error(src) {
+                CompileError::TypeCheck(err) => {
+                    assert!(matches!(err.kind, TypeErrorKind::MissingReturn(_)), "{}", src)
+                }
+                other => panic!("Expected MissingReturn for {}, got {}", src, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_switch_type_errors() {
+        for (src, expected) in [
+            (
+                "switch (1.5) { case 1: break; } return 0.0;",
+                TypeErrorKind::Mismatch {
+                    expected: Type::Int32,
+                    found: Type::Fixed,
+                },
+            ),
+            (
+                "switch (1) { case 1.5: break; } return 0.0;",
+                TypeErrorKind::Mismatch {
+                    expected: Type::Int32,
+                    found: Type::Fixed,
+                },
+            ),
+            (
+                "int k = 1; switch (1) { case k: break; } return 0.0;",
+                TypeErrorKind::NotConstant,
+            ),
+            (
+                "switch (1) { case 2: break; case 1 + 1: break; } return 0.0;",
+                TypeErrorKind::DuplicateCase(2),
+            ),
+        ] {
+            match compile_error(src) {
+                CompileError::TypeCheck(err) => {
+                    assert_eq!(
+                        format!("{:?}", err.kind),
+                        format!("{:?}", expected),
+                        "{}",
+                        src
+                    )
+                }
+                other => panic!("Expected Type error for {}, got {}", src, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_switch_parse_errors() {
+        for (src, expected) in [
+            ("switch (1) { return 0.0; } return 0.0;", "case or default"),
+            ("switch (1) { default: default: } return 0.0;", "case"),
+            ("switch (1) { case 1 break; } return 0.0;", ":"),
+        ] {
+            match compile_error(src) {
+                CompileError::Parser(err) => match err.kind {
+                    ParseErrorKind::UnexpectedToken { expected: e, .. } => {
+                        assert_eq!(e, expected, "{}", src)
+                    }
+                    other => panic!("Expected UnexpectedToken for {}, got {:?}", src, other),
+                },
+                other => panic!("Expected Parse error for {}, got {}", src, other),
+            }
+        }
+
+        // `continue` still needs a loop
+        match compile_error("switch (1) { case 1: continue; } return 0.0;") {
+            CompileError::Parser(err) => {
+                assert!(matches!(
+                    err.kind,
+                    ParseErrorKind::OutsideLoop {
+                        keyword: "continue"
+                    }
+                ))
+            }
+            other => panic!("Expected Parse error, got {}", other),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+/// Switch statement type checking
+use alloc::vec::Vec;
+
+use crate::compiler::ast::{Expr, ExprKind, SwitchCase};
+use crate::compiler::const_eval::ConstValue;
+use crate::compiler::error::{TypeError, TypeErrorKind};
+use crate::compiler::optimize::ast::constant_fold;
+use crate::compiler::typechecker::{FunctionTable, SymbolTable, TypeChecker};
+use crate::shared::Type;
+
+impl TypeChecker {
+    /// Check an int selector, fold each case value to a distinct int literal,
+    /// and check the cases' statements in one shared scope
+    pub(crate) fn check_switch(
+        selector: &mut Expr,
+        cases: &mut [SwitchCase],
+        symbols: &mut SymbolTable,
+        func_table: &FunctionTable,
+    ) -> Result<(), TypeError> {
+        Self::infer_type(selector, symbols, func_table)?;
+        expect_int(selector)?;
+
+        let mut seen = Vec::new();
+        for value in cases.iter_mut().filter_map(|case| case.value.as_mut()) {
+            Self::infer_type(value, symbols, func_table)?;
+            expect_int(value)?;
+            constant_fold::fold_constants(value);
+
+            let Some(ConstValue::Int32(n)) = ConstValue::from_folded(value) else {
+                return Err(TypeError {
+                    kind: TypeErrorKind::NotConstant,
+                    span: value.span,
+                });
+            };
+            if seen.contains(&n) {
+                return Err(TypeError {
+                    kind: TypeErrorKind::DuplicateCase(n),
+                    span: value.span,
+                });
+            }
+            seen.push(n);
+
+            let mut literal = Expr::new(ExprKind::IntNumber(n), value.span);
+            literal.ty = Some(Type::Int32);
+            *value = literal;
+        }
+
+        symbols.push_scope();
+        for stmt in cases.iter_mut().flat_map(|case| case.body.iter_mut()) {
+            Self::check_stmt(stmt, symbols, func_table)?;
+        }
+        symbols.pop_scope();
+        Ok(())
+    }
+}
+
+fn expect_int(expr: &Expr) -> Result<(), TypeError> {
+    let ty = expr.ty.clone().unwrap_or(Type::Fixed);
+    if ty != Type::Int32 {
+        return Err(TypeError {
+            kind: TypeErrorKind::Mismatch {
+                expected: Type::Int32,
+                found: ty,
+            },
+            span: expr.span,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,9 @@
+/// Uniform declaration module
+///
+/// Groups parsing and tests for top-level `uniform type name;`. Uniforms
+/// become the program's externals, read with `LoadExternal` and set by the
+/// host with `LpsVm::set_uniform`.
+mod uniform_decl_parse;
+
+#[cfg(test)]
+mod uniform_decl_tests;
@@ -0,0 +1,60 @@
+use alloc::format;
+use alloc::string::String;
+
+/// Uniform declaration parsing
+use crate::compiler::ast::Parameter;
+use crate::compiler::error::{ParseError, ParseErrorKind};
+use crate::compiler::lexer::TokenKind;
+use crate::compiler::parser::Parser;
+
+impl Parser {
+    /// Parse `uniform type name;`, given the uniforms declared so far
+    pub(crate) fn parse_uniform_decl(
+        &mut self,
+        uniforms: &[Parameter],
+    ) -> Result<Parameter, ParseError> {
+        self.advance(); // consume 'uniform'
+
+        if !matches!(
+            self.current().kind,
+            TokenKind::Bool
+                | TokenKind::Float
+                | TokenKind::Int
+                | TokenKind::Vec2
+                | TokenKind::Vec3
+                | TokenKind::Vec4
+                | TokenKind::BVec2
+                | TokenKind::BVec3
+                | TokenKind::BVec4
+                | TokenKind::Mat2
+                | TokenKind::Mat3
+                | TokenKind::Mat4
+        ) {
+            return Err(self.uniform_decl_error("uniform type"));
+        }
+        let ty = self.parse_type();
+
+        let name = match &self.current().kind {
+            TokenKind::Ident(name) if !uniforms.iter().any(|u| &u.name == name) => name.clone(),
+            TokenKind::Ident(_) => return Err(self.uniform_decl_error("new uniform name")),
+            _ => return Err(self.uniform_decl_error("identifier")),
+        };
+        self.advance();
+
+        // Values come from the host, so there's no initializer
+        if !self.consume_semicolon() {
+            return Err(self.uniform_decl_error(";"));
+        }
+        Ok(Parameter { name, ty })
+    }
+
+    fn uniform_decl_error(&self, expected: &str) -> ParseError {
+        ParseError {
+            kind: ParseErrorKind::UnexpectedToken {
+                expected: String::from(expected),
+                found: format!("{:?}", self.current().kind),
+            },
+            span: self.current().span,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+/// Uniform declaration tests
+#[cfg(test)]
+mod tests {
+    use crate::compiler::error::{CompileError, ParseErrorKind, TypeErrorKind};
+    use crate::fixed::{Fixed, Vec2};
+    use crate::shared::Type;
+    use crate::vm::error::LpsVmError;
+    use crate::vm::vm_limits::VmLimits;
+    use crate::*;
+
+    fn run_with(src: &str, uniforms: &[(&str, InputValue)]) -> Result<Fixed, LpsVmError> {
+        let program = parse_script(src);
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        for (name, value) in uniforms {
+            vm.set_uniform(name, *value)?;
+        }
+        vm.run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .map_err(|err| err.error)
+    }
+
+    fn compile_error(src: &str) -> CompileError {
+        match compile_script_with_options(src, &OptimizeOptions::none()) {
+            Err(err) => err,
+            Ok(_) => panic!("Expected compile error for {}", src),
+        }
+    }
+
+    #[test]
+    fn test_uniforms_are_program_externals() {
+        let program = parse_script(
+            "uniform float brightness; uniform vec2 offset; \
+             return brightness + offset.y;",
+        );
+        let uniforms: Vec<_> = program
+            .externals
+            .iter()
+            .map(|e| (e.name.as_str(), e.ty.clone()))
+            .collect();
+        assert_eq!(
+            uniforms,
+            vec![("brightness", Type::Fixed), ("offset", Type::Vec2)]
+        );
+        program.validate().unwrap();
+    }
+
+    #[test]
+    fn test_set_uniform_values_read_by_script() {
+        let src = "uniform float brightness; uniform vec2 offset; \
+                   return brightness * 2.0 + offset.y;";
+        let offset = InputValue::Vec2(Vec2::new(Fixed::ZERO, Fixed::from_i32(10)));
+        let result = run_with(
+            src,
+            &[
+                ("brightness", InputValue::Fixed(Fixed::from_f32(0.25))),
+                ("offset", offset),
+            ],
+        );
+        assert_eq!(result.unwrap(), Fixed::from_f32(10.5));
+    }
+
+    #[test]
+    fn test_unset_uniform_fails_at_runtime() {
+        let result = run_with("uniform float brightness; return brightness;", &[]);
+        assert!(matches!(result, Err(LpsVmError::ExternalNotBound)));
+    }
+
+    #[test]
+    fn test_set_uniform_checks_name_and_type() {
+        let src = "uniform float brightness; return brightness;";
+        assert!(matches!(
+            run_with(src, &[("gain", InputValue::Fixed(Fixed::ONE))]),
+            Err(LpsVmError::UnknownExternal(name)) if name == "gain"
+        ));
+        assert!(matches!(
+            run_with(src, &[("brightness", InputValue::Int32(1))]),
+            Err(LpsVmError::TypeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_uniform_visible_in_functions() {
+        let src = "uniform int steps; \
+                   float scaled(float x) { return x * float(steps); } \
+                   return scaled(0.5);";
+        let result = run_with(src, &[("steps", InputValue::Int32(6))]);
+        assert_eq!(result.unwrap(), Fixed::from_i32(3));
+    }
+
+    #[test]
+    fn test_local_shadows_uniform() {
+        let src = "uniform float brightness; \
+                   float a = brightness; \
+                   { float brightness = 5.0; a = a + brightness; } \
+                   return a;";
+        let result = run_with(src, &[("brightness", InputValue::Fixed(Fixed::ONE))]);
+        assert_eq!(result.unwrap(), Fixed::from_i32(6));
+    }
+
+    #[test]
+    fn test_uniform_shadows_builtin() {
+        let src = "uniform float time; return time;";
+        let result = run_with(src, &[("time", InputValue::Fixed(Fixed::from_i32(7)))]);
+        assert_eq!(result.unwrap(), Fixed::from_i32(7));
+    }
+
+    #[test]
+    fn test_uniform_assignment_rejected() {
+        for src in [
+            "uniform float brightness; brightness = 1.0; return brightness;",
+            "uniform int steps; steps++; return 0.0;",
+            "uniform float brightness; void f() { brightness += 1.0; } return 0.0;",
+        ] {
+            match compile_error(src) {
+                CompileError::TypeCheck(err) => match err.kind {
+                    TypeErrorKind::ExternalAssignment(name) => {
+                        assert!(name == "brightness" || name == "steps")
+                    }
+                    other => panic!("Expected ExternalAssignment for {}, got {:?}", src, other),
+                },
+                other => panic!("Expected Type error for {}, got {}", src, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_uniform_parse_errors() {
+        for (src, expected) in [
+            ("uniform float brightness = 1.0; return 0.0;", ";"),
+            ("uniform void on; return 0.0;", "uniform type"),
+            (
+                "uniform float a; uniform vec2 a; return 0.0;",
+                "new uniform name",
+            ),
+        ] {
+            match compile_error(src) {
+                CompileError::Parser(err) => match err.kind {
+                    ParseErrorKind::UnexpectedToken { expected: e, .. } => assert_eq!(e, expected),
+                    other => panic!("Expected UnexpectedToken for {}, got {:?}", src, other),
+                },
+                other => panic!("Expected Parse error for {}, got {}", src, other),
+            }
+        }
+    }
+}
@@ -19,10 +19,12 @@ impl<'a> CodeGenerator<'a> {
             self.code.push(match ty {
                 Type::Fixed | Type::Bool => LpsOpCode::StoreLocalFixed(local_idx),
                 Type::Int32 => LpsOpCode::StoreLocalInt32(local_idx),
-                Type::Vec2 => LpsOpCode::StoreLocalVec2(local_idx),
-                Type::Vec3 => LpsOpCode::StoreLocalVec3(local_idx),
-                Type::Vec4 => LpsOpCode::StoreLocalVec4(local_idx),
+                Type::Vec2 | Type::BVec2 => LpsOpCode::StoreLocalVec2(local_idx),
+                Type::Vec3 | Type::BVec3 => LpsOpCode::StoreLocalVec3(local_idx),
+                Type::Vec4 | Type::BVec4 => LpsOpCode::StoreLocalVec4(local_idx),
+                Type::Mat2 => LpsOpCode::StoreLocalMat2(local_idx),
                 Type::Mat3 => LpsOpCode::StoreLocalMat3(local_idx),
+                Type::Mat4 => LpsOpCode::StoreLocalMat4(local_idx),
                 _ => LpsOpCode::StoreLocalFixed(local_idx), // Fallback
             });
         }
@@ -1,11 +1,13 @@
+use alloc::format;
 use alloc::string::String;
+use alloc::vec::Vec;
 
 /// Variable declaration parsing
 use crate::compiler::ast::{Stmt, StmtKind};
-use crate::compiler::error::ParseError;
+use crate::compiler::error::{ParseError, ParseErrorKind};
 use crate::compiler::lexer::TokenKind;
 use crate::compiler::parser::Parser;
-use crate::shared::Span;
+use crate::shared::{Span, Type};
 
 impl Parser {
     pub(crate) fn parse_var_decl(&mut self) -> Result<Stmt, ParseError> {
@@ -18,8 +20,11 @@ impl Parser {
         self.enter_recursion()?;
         let start = self.current().span.start;
 
-        // Parse type
-        let ty = self.parse_type();
+        // Parse type; as in GLSL, the dimensions go after the type or after
+        // the name: `float[N] a` or `float a[N]` for an array,
+        // `float[H][W] g` or `float g[H][W]` for a grid
+        let mut ty = self.parse_type();
+        let mut dims = self.parse_dims()?;
 
         // Parse name
         let name = if let TokenKind::Ident(n) = &self.current().kind {
@@ -29,6 +34,46 @@ impl Parser {
         } else {
             String::from("error")
         };
+        let name_dims = self.parse_dims()?;
+        if !dims.is_empty() && !name_dims.is_empty() {
+            return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedToken {
+                    expected: String::from("dimensions after the type or the name"),
+                    found: String::from("both"),
+                },
+                span: Span::new(start, self.current().span.end),
+            });
+        }
+        dims.extend(name_dims);
+
+        if !dims.is_empty() {
+            ty = match (&ty, dims.as_slice()) {
+                (Type::Fixed, &[len]) => Type::Array { len },
+                (Type::Fixed, &[rows, cols]) => Type::Grid { rows, cols },
+                _ => {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::UnexpectedToken {
+                            expected: String::from("float array or grid"),
+                            found: dims.iter().fold(format!("{}", ty), |found, dim| {
+                                format!("{}[{}]", found, dim)
+                            }),
+                        },
+                        span: Span::new(start, self.current().span.end),
+                    })
+                }
+            };
+
+            // Arrays and grids start zero-filled and are assigned element-wise
+            if matches!(self.current().kind, TokenKind::Eq) {
+                return Err(ParseError {
+                    kind: ParseErrorKind::UnexpectedToken {
+                        expected: String::from(";"),
+                        found: format!("{:?}", self.current().kind),
+                    },
+                    span: self.current().span,
+                });
+            }
+        }
 
         // Parse optional initializer
         let init = if matches!(self.current().kind, TokenKind::Eq) {
@@ -39,6 +84,7 @@ impl Parser {
         };
 
         let end = self.current().span.end;
+        self.declare(&name, None);
 
         let result = Ok(Stmt::new(
             StmtKind::VarDecl { ty, name, init },
@@ -48,4 +94,38 @@ impl Parser {
         self.exit_recursion();
         result
     }
+
+    /// Parse any `[N]` array or grid dimensions at the current position
+    fn parse_dims(&mut self) -> Result<Vec<u32>, ParseError> {
+        let mut dims = Vec::new();
+        while matches!(self.current().kind, TokenKind::LBracket) {
+            dims.push(self.parse_grid_dim()?);
+        }
+        Ok(dims)
+    }
+
+    /// Parse one `[N]` grid or array dimension, a positive int literal or
+    /// `const int` expression
+    fn parse_grid_dim(&mut self) -> Result<u32, ParseError> {
+        let start = self.current().span.start;
+        let size = if self.expect(TokenKind::LBracket) {
+            let size = self.ternary()?;
+            self.const_int_value(&size)
+                .filter(|&n| n > 0)
+                .map(|n| n as u32)
+        } else {
+            None
+        };
+
+        match size {
+            Some(n) if self.expect(TokenKind::RBracket) => Ok(n),
+            _ => Err(ParseError {
+                kind: ParseErrorKind::UnexpectedToken {
+                    expected: String::from("size like [4]"),
+                    found: format!("{:?}", self.current().kind),
+                },
+                span: Span::new(start, self.current().span.end),
+            }),
+        }
+    }
 }
@@ -349,4 +349,165 @@ mod variable_integration_tests {
             .unwrap();
         assert_eq!(result.to_f32(), 21.0);
     }
+
+    #[test]
+    fn test_mat2_local() {
+        let script = "
+            mat2 m = rotate2x2(0.5);
+            m = m * 2.0;
+            vec2 v = m * vec2(1.0, 0.0);
+            return v.x;
+        ";
+        let program = parse_script(script);
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+
+        let result = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(
+            result,
+            crate::fixed::cos(Fixed::from_f32(0.5)) * Fixed::from_i32(2)
+        );
+    }
+
+    #[test]
+    fn test_mat3_and_mat4_locals() {
+        // Assignments used as expression statements duplicate and drop the
+        // whole matrix
+        let script = "
+            mat3 a = mat3(2.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 2.0);
+            mat4 b = mat4(vec4(1.0, 2.0, 3.0, 4.0), vec4(0.0, 1.0, 0.0, 0.0), vec4(0.0, 0.0, 1.0, 0.0), vec4(0.0, 0.0, 0.0, 1.0));
+            a = a * a;
+            b = transpose(b) * 3.0;
+            vec4 v = b * vec4(0.0, 1.0, 0.0, 0.0);
+            return (a * vec3(1.0, 0.0, 0.0)).x + v.x;
+        ";
+        let program = parse_script(script);
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+
+        let result = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result, Fixed::from_i32(4 + 6));
+    }
+
+    #[test]
+    fn test_bool_and_bvec_locals() {
+        // Locals of the boolean types, reassigned and passed to a function
+        let script = "
+            bool flip(bool b) { return !b; }
+            bool inside = x > 0.25;
+            bvec3 mask = lessThan(vec3(x), vec3(0.5, 1.0, 0.1));
+            mask = not(mask);
+            inside = flip(inside);
+            return (inside || mask.x) ? 1.0 : (all(mask.yz) ? 2.0 : 3.0);
+        ";
+        let program = parse_script(script);
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+
+        // inside = !(0.3 > 0.25) = false; mask = not(true, true, false)
+        let result = vm
+            .run_scalar(Fixed::from_f32(0.3), Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result.to_f32(), 3.0);
+    }
+
+    #[test]
+    fn test_bool_casts() {
+        // Bools only become numbers through an explicit float() or int()
+        let script = "
+            bool inside = x > 0.25;
+            int n = int(inside) + int(false);
+            return float(inside) * 2.0 + float(n) + float(true);
+        ";
+        let program = parse_script(script);
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+
+        let result = vm
+            .run_scalar(Fixed::from_f32(0.3), Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result.to_f32(), 4.0);
+        let result = vm
+            .run_scalar(Fixed::from_f32(0.2), Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result.to_f32(), 1.0);
+    }
+
+    #[test]
+    fn test_bool_implicit_conversion_errors() {
+        use crate::compiler::error::{CompileError, TypeErrorKind};
+
+        for src in [
+            "bool b = 2.0; return 0.0;",
+            "int i = 1; bool b; b = i; return 0.0;",
+            "float y = true; return y;",
+            "float y = 1.0; y = true; return y;",
+            "bvec2 m = vec2(1.0, 0.0); return 0.0;",
+            "return vec2(true, 2.0).x;",
+            "return vec3(bvec2(true), 1.0).x;",
+        ] {
+            match compile_script(src) {
+                Err(CompileError::TypeCheck(err)) => assert!(
+                    matches!(err.kind, TypeErrorKind::Mismatch { .. }),
+                    "{}: {:?}",
+                    src,
+                    err
+                ),
+                other => panic!(
+                    "{}: expected a type mismatch, got {:?}",
+                    src,
+                    other.map(|_| ())
+                ),
+            }
+        }
+
+        for src in ["bool b = true; return b + 1.0;", "return 2.0 * (x > 0.5);"] {
+            match compile_script(src) {
+                Err(CompileError::TypeCheck(err)) => assert!(
+                    matches!(err.kind, TypeErrorKind::InvalidOperation { .. }),
+                    "{}: {:?}",
+                    src,
+                    err
+                ),
+                other => panic!(
+                    "{}: expected an invalid operation, got {:?}",
+                    src,
+                    other.map(|_| ())
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_array_and_grid_dimensions_before_or_after_name() {
+        use crate::compiler::ast::StmtKind;
+        use crate::compiler::lexer::Lexer;
+        use crate::compiler::parser::Parser;
+
+        for (src, expected) in [
+            ("float[4] a;", Type::Array { len: 4 }),
+            ("float a[4];", Type::Array { len: 4 }),
+            ("float[2][3] g;", Type::Grid { rows: 2, cols: 3 }),
+            ("float g[2][3];", Type::Grid { rows: 2, cols: 3 }),
+        ] {
+            let tokens = Lexer::new(src).tokenize();
+            let program = Parser::new(tokens)
+                .parse_program()
+                .unwrap_or_else(|err| panic!("{}: {:?}", src, err));
+            match &program.stmts[0].kind {
+                StmtKind::VarDecl { ty, .. } => assert_eq!(ty, &expected, "{}", src),
+                other => panic!("{}: expected VarDecl, got {:?}", src, other),
+            }
+        }
+
+        for src in [
+            "float[2] g[3];",
+            "float a[2][3][4];",
+            "vec2 a[2];",
+            "float[2][3] g = 1.0;",
+        ] {
+            let tokens = Lexer::new(src).tokenize();
+            assert!(Parser::new(tokens).parse_program().is_err(), "{}", src);
+        }
+    }
 }
@@ -17,6 +17,7 @@ impl<'a> CodeGenerator<'a> {
         self.code.push(LpsOpCode::JumpIfZero(0)); // Placeholder
 
         // Body
+        self.begin_loop();
         self.gen_stmt(body);
 
         // Jump back to loop start
@@ -25,10 +26,11 @@ impl<'a> CodeGenerator<'a> {
             (loop_start as i32) - (jump_back_idx as i32) - 1,
         ));
 
-        // Patch jump to end
+        // Patch jump to end; `continue` re-tests the condition
         let end = self.code.len();
         if let LpsOpCode::JumpIfZero(ref mut offset) = self.code[jump_to_end] {
             *offset = (end as i32) - (jump_to_end as i32) - 1;
         }
+        self.end_loop(loop_start);
     }
 }
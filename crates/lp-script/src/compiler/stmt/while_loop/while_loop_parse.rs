@@ -17,7 +17,7 @@ impl Parser {
         let condition = self.ternary()?;
         self.expect(TokenKind::RParen);
 
-        let body = self.parse_stmt()?;
+        let body = self.parse_loop_body()?;
         let end = body.span.end;
 
         let result = Ok(Stmt::new(
@@ -5,28 +5,47 @@ use alloc::string::String;
 use alloc::vec::Vec;
 use alloc::{format, vec};
 
+use crate::compiler::ast::Expr;
 use crate::shared::Type;
+use crate::vm::ExternalDef;
 
 /// Symbol table for tracking variables in scope
 #[derive(Debug, Clone)]
 pub(crate) struct SymbolTable {
     scopes: Vec<BTreeMap<String, Type>>,
+    /// Folded values of the `const`s declared in each scope
+    consts: Vec<BTreeMap<String, Expr>>,
 }
 
 impl SymbolTable {
     pub(crate) fn new() -> Self {
         SymbolTable {
             scopes: vec![BTreeMap::new()],
+            consts: vec![BTreeMap::new()],
         }
     }
 
+    /// A table whose root scope holds the host-set `externals`; everything
+    /// declared later goes in an inner scope, so it may shadow them
+    pub(crate) fn with_externals(externals: &[ExternalDef]) -> Self {
+        let mut symbols = SymbolTable::new();
+        for external in externals {
+            // A repeated name keeps its first type, matching `LpsProgram::external_index`
+            let _ = symbols.declare(external.name.clone(), external.ty.clone());
+        }
+        symbols.push_scope();
+        symbols
+    }
+
     pub(crate) fn push_scope(&mut self) {
         self.scopes.push(BTreeMap::new());
+        self.consts.push(BTreeMap::new());
     }
 
     pub(crate) fn pop_scope(&mut self) {
         if self.scopes.len() > 1 {
             self.scopes.pop();
+            self.consts.pop();
         }
     }
 
@@ -44,6 +63,36 @@ impl SymbolTable {
         Ok(())
     }
 
+    /// Declare a `const`, with `value` its folded literal
+    pub(crate) fn declare_const(&mut self, name: String, value: Expr) -> Result<(), String> {
+        let ty = value.ty.clone().unwrap_or(Type::Fixed);
+        self.declare(name.clone(), ty)?;
+        if let Some(consts) = self.consts.last_mut() {
+            consts.insert(name, value);
+        }
+        Ok(())
+    }
+
+    /// The folded value of `name` if the innermost declaration of it is a
+    /// `const`
+    pub(crate) fn lookup_const(&self, name: &str) -> Option<&Expr> {
+        self.scopes
+            .iter()
+            .zip(&self.consts)
+            .rev()
+            .find(|(scope, _)| scope.contains_key(name))
+            .and_then(|(_, consts)| consts.get(name))
+    }
+
+    /// Whether `name` resolves to an external (see `with_externals`)
+    pub(crate) fn is_external(&self, name: &str) -> bool {
+        self.scopes.len() > 1
+            && self.scopes[1..]
+                .iter()
+                .all(|scope| !scope.contains_key(name))
+            && self.scopes[0].contains_key(name)
+    }
+
     pub(crate) fn lookup(&self, name: &str) -> Option<Type> {
         // Search from innermost to outermost scope
         for scope in self.scopes.iter().rev() {
@@ -10,6 +10,7 @@
 /// in the expr/ and stmt/ subdirectories respectively.
 use crate::compiler::ast::Expr;
 use crate::compiler::error::TypeError;
+use crate::vm::{ExternalDef, NativeDef};
 // Import function-related types from compiler::func
 pub(crate) use crate::compiler::func::FunctionTable;
 // Import symbol table from compiler::symbol_table
@@ -27,9 +28,21 @@ use crate::compiler::stmt::stmt_types;
 
 impl TypeChecker {
     /// Type check an expression (expression mode)
+    #[cfg(test)]
     pub fn check(expr: &mut Expr) -> Result<(), TypeError> {
-        let mut symbols = SymbolTable::new();
-        let func_table = FunctionTable::new(); // Empty for expression mode
+        Self::check_with_env(expr, &[], &[])
+    }
+
+    /// Type check an expression that may read the host-provided `externals`
+    /// and call host `natives`
+    pub fn check_with_env(
+        expr: &mut Expr,
+        externals: &[ExternalDef],
+        natives: &[NativeDef],
+    ) -> Result<(), TypeError> {
+        let mut symbols = SymbolTable::with_externals(externals);
+        // No user functions in expression mode
+        let func_table = FunctionTable::new().with_natives(natives);
         Self::infer_type(expr, &mut symbols, &func_table)
     }
 }
@@ -10,7 +10,8 @@ mod sin_table;
 /// # Features
 /// - **Arithmetic**: `+`, `-`, `*`, `/`, `%`
 /// - **Bitwise** (int only): `&`, `|`, `^`, `~`, `<<`, `>>`
-/// - **Comparisons**: `<`, `>`, `<=`, `>=`, `==`, `!=`
+/// - **Comparisons**: `<`, `>`, `<=`, `>=`, `==`, `!=` (`==`/`!=` also compare
+///   whole vectors, exactly on the fixed-point components)
 /// - **Logical**: `&&`, `||`, `!`
 /// - **Increment/Decrement**: `++`, `--` (prefix and postfix)
 /// - **Compound Assignment**: `+=`, `-=`, `*=`, `/=`, `%=`, `&=`, `|=`, `^=`, `<<=`, `>>=`
@@ -21,6 +22,7 @@ mod sin_table;
 /// - **`uv`**: vec2, normalized coordinates (0..1)
 /// - **`coord`**: vec2, pixel coordinates
 /// - **`time`**: float, time value
+/// - **`index`**: int, linear LED/pixel index (`y * width + x`)
 /// - **Legacy**: `xNorm`, `yNorm`, `centerAngle`, `centerDist`
 ///
 /// # GLSL/HLSL Shader Functions
@@ -84,16 +86,28 @@ pub use lp_math::fixed;
 
 mod compiler;
 
+pub use compiler::builtins::{builtins, BuiltinFunction, BuiltinSig, BuiltinType};
 pub use compiler::codegen::NativeFunction;
+pub use compiler::const_eval::ConstValue;
 pub use compiler::error::CompileError;
+pub use compiler::info::{FunctionInfo, ScriptInfo};
+pub use compiler::lint::{CompileWarning, LintOptions, WarningKind};
+#[cfg(feature = "std")]
+pub use compiler::metrics::CompileMetrics;
+use compiler::metrics::{CompileObserver, CompilePhase};
 pub use compiler::optimize::OptimizeOptions;
-use compiler::{codegen, lexer, optimize, parser, typechecker};
+pub use compiler::options::CompilerOptions;
+use compiler::{codegen, lexer, lint, optimize, parser, typechecker};
 pub use shared::{Span, Type};
 pub use vm::lps_vm::LpsVm;
 pub use vm::vm_limits::VmLimits;
+#[cfg(feature = "std")]
+pub use vm::StdClock;
 pub use vm::{
-    execute_program_lps, LocalStack, LocalVarDef, LpsOpCode, LpsProgram, LpsVmError, ParamDef,
-    RuntimeErrorWithContext,
+    execute_program_lps, execute_program_lps_batched, execute_program_lps_timed, Clock,
+    CoordConfig, ExternalDef, InputValue, LocalAccess, LocalStack, LocalVarDef, LpsOpCode,
+    LpsProgram, LpsVmError, NativeDef, NativeSig, NormalizeFallback, OpcodeGroup, OpcodeSet,
+    Origin, OutputValue, ParamDef, ProgramError, RuntimeErrorWithContext, Texture, MAX_TEXTURES,
 };
 
 /// Parse an expression string and generate a compiled LPS program
@@ -120,6 +134,111 @@ pub fn compile_expr_with_options(
     input: &str,
     options: &OptimizeOptions,
 ) -> Result<LpsProgram, CompileError> {
+    compile_expr_impl(input, options, None, &LintOptions::none(), &[], &[])
+        .map(|(program, _)| program)
+}
+
+/// Compile an expression, also returning lint warnings
+///
+/// Use `LintOptions::expr()` for the expression-mode defaults.
+///
+/// # Example
+/// ```
+/// use lp_script::{compile_expr_with_warnings, LintOptions, OptimizeOptions};
+/// let (program, warnings) =
+///     compile_expr_with_warnings("uv.x * 2.0", &OptimizeOptions::all(), &LintOptions::expr())
+///         .unwrap();
+/// assert!(warnings.is_empty());
+/// ```
+pub fn compile_expr_with_warnings(
+    input: &str,
+    options: &OptimizeOptions,
+    lints: &LintOptions,
+) -> Result<(LpsProgram, Vec<CompileWarning>), CompileError> {
+    compile_expr_impl(input, options, None, lints, &[], &[])
+}
+
+/// Compile an expression that must produce a value of type `expected`
+///
+/// Fails with a type mismatch if the inferred result type differs. An `int`
+/// result is accepted where `float` is expected and converted on return.
+///
+/// # Example
+/// ```
+/// use lp_script::{compile_expr_as, Type};
+/// let program = compile_expr_as("vec3(uv, time)", Type::Vec3).unwrap();
+/// assert!(compile_expr_as("vec3(uv, time)", Type::Fixed).is_err());
+/// ```
+pub fn compile_expr_as(input: &str, expected: Type) -> Result<LpsProgram, CompileError> {
+    compile_expr_impl(
+        input,
+        &OptimizeOptions::default(),
+        Some(expected),
+        &LintOptions::none(),
+        &[],
+        &[],
+    )
+    .map(|(program, _)| program)
+}
+
+/// Compile an expression that may read host-provided values
+///
+/// Each `(name, type)` in `env` declares an external, e.g. a slider or sensor
+/// reading, so `speed * uv.x` type-checks. The program reads externals with
+/// `LoadExternal`; set their values with `LpsVm::set_external` before running.
+/// Externals shadow built-ins of the same name, and names that are neither
+/// still fail as undefined variables.
+///
+/// # Example
+/// ```
+/// use lp_script::{compile_expr_with_env, Type};
+/// let program = compile_expr_with_env("speed * uv.x", &[("speed", Type::Fixed)]).unwrap();
+/// assert_eq!(program.externals[0].name, "speed");
+/// ```
+pub fn compile_expr_with_env(
+    input: &str,
+    env: &[(&str, Type)],
+) -> Result<LpsProgram, CompileError> {
+    let externals: Vec<ExternalDef> = env
+        .iter()
+        .map(|(name, ty)| ExternalDef::new((*name).into(), ty.clone()))
+        .collect();
+    compile_expr_impl(
+        input,
+        &OptimizeOptions::default(),
+        None,
+        &LintOptions::none(),
+        &externals,
+        &[],
+    )
+    .map(|(program, _)| program)
+}
+
+/// Compile an expression that may call host natives, see
+/// `CompilerOptions::register_native`
+pub fn compile_expr_with_compiler_options(
+    input: &str,
+    options: &CompilerOptions,
+) -> Result<LpsProgram, CompileError> {
+    compile_expr_impl(
+        input,
+        &options.optimize,
+        None,
+        &LintOptions::none(),
+        &[],
+        &options.natives,
+    )
+    .map(|(program, _)| program)
+}
+
+fn compile_expr_impl(
+    input: &str,
+    options: &OptimizeOptions,
+    expected: Option<Type>,
+    lints: &LintOptions,
+    externals: &[ExternalDef],
+    natives: &[NativeDef],
+) -> Result<(LpsProgram, Vec<CompileWarning>), CompileError> {
     let mut lexer = lexer::Lexer::new(input);
     let tokens = lexer.tokenize();
 
@@ -127,7 +246,10 @@ pub fn compile_expr_with_options(
     let mut expr = parser.parse()?;
 
     // Type check the AST (in-place, mutating types on nodes)
-    typechecker::TypeChecker::check(&mut expr)?;
+    typechecker::TypeChecker::check_with_env(&mut expr, externals, natives)?;
+
+    // Lint before optimization rewrites the source forms
+    let warnings = lint::lint_expr(&expr, lints);
 
     // Optimize AST (mutates in place)
     optimize::optimize_ast_expr(&mut expr, options);
@@ -142,17 +264,76 @@ pub fn compile_expr_with_options(
         })
     })?;
 
-    // Generate and optimize opcodes
-    let opcodes = codegen::CodeGenerator::generate(&expr);
+    // Check the result against the caller's expected type, allowing int -> float
+    let mut opcodes = codegen::CodeGenerator::generate_with_env(&expr, externals, natives);
+    let expr_type = match expected {
+        Some(expected) if expected == expr_type => expr_type,
+        Some(Type::Fixed) if expr_type == Type::Int32 => {
+            // Convert just before the trailing Return
+            opcodes.insert(opcodes.len() - 1, LpsOpCode::Int32ToFixed);
+            Type::Fixed
+        }
+        Some(expected) => {
+            return Err(CompileError::TypeCheck(compiler::error::TypeError {
+                kind: compiler::error::TypeErrorKind::Mismatch {
+                    expected,
+                    found: expr_type,
+                },
+                span: expr.span,
+            }))
+        }
+        None => expr_type,
+    };
+
+    // Optimize opcodes
     let optimized_opcodes = optimize::optimize_opcodes(opcodes, options);
 
     // Create main function with the expression's actual return type
     let main_function =
         vm::FunctionDef::new("main".into(), expr_type).with_opcodes(optimized_opcodes);
+    codegen::check_opcode_set(
+        core::slice::from_ref(&main_function),
+        &options.allowed_opcodes,
+    )?;
 
-    Ok(LpsProgram::new("expr".into())
+    let program = LpsProgram::new("expr".into())
         .with_functions(vec![main_function])
-        .with_source(input.into()))
+        .with_externals(externals.to_vec())
+        .with_natives(natives.to_vec())
+        .with_source(input.into());
+    Ok((program, warnings))
+}
+
+/// Evaluate a constant expression at compile time
+///
+/// The expression is type checked and constant folded; if that leaves a
+/// literal (or a vector of literals) its value is returned, otherwise it fails
+/// with "expression is not constant". Built-in variables like `uv` and `time`
+/// are never constant.
+///
+/// # Example
+/// ```
+/// use lp_script::fixed::Fixed;
+/// use lp_script::{eval_const_expr, ConstValue};
+/// assert_eq!(
+///     eval_const_expr("2.0 * 0.25").unwrap(),
+///     ConstValue::Fixed(Fixed::HALF)
+/// );
+/// assert!(eval_const_expr("uv.x").is_err());
+/// ```
+pub fn eval_const_expr(input: &str) -> Result<ConstValue, CompileError> {
+    let mut lexer = lexer::Lexer::new(input);
+    let tokens = lexer.tokenize();
+    let mut parser = parser::Parser::new(tokens);
+    let mut expr = parser.parse()?;
+
+    typechecker::TypeChecker::check_with_env(&mut expr, &[], &[])?;
+    optimize::optimize_ast_expr(&mut expr, &OptimizeOptions::default());
+
+    ConstValue::from_folded(&expr).ok_or(CompileError::TypeCheck(compiler::error::TypeError {
+        kind: compiler::error::TypeErrorKind::NotConstant,
+        span: expr.span,
+    }))
 }
 
 /// Compile a full script (with statements, variables, control flow)
@@ -188,23 +369,128 @@ pub fn compile_script_with_options(
     input: &str,
     options: &OptimizeOptions,
 ) -> Result<LpsProgram, CompileError> {
+    compile_script_with_warnings(input, options, &LintOptions::none()).map(|(program, _)| program)
+}
+
+/// Compile a script that may call host natives, see
+/// `CompilerOptions::register_native`
+pub fn compile_script_with_compiler_options(
+    input: &str,
+    options: &CompilerOptions,
+) -> Result<LpsProgram, CompileError> {
+    compile_script_impl(
+        input,
+        &options.optimize,
+        &LintOptions::none(),
+        &options.natives,
+        &mut (),
+    )
+    .map(|(program, _)| program)
+}
+
+/// Compile a script, also returning lint warnings
+///
+/// # Example
+/// ```
+/// use lp_script::{compile_script_with_warnings, LintOptions, OptimizeOptions};
+/// let script = "int half = 5 / 2; return 1.0;";
+/// let (program, warnings) =
+///     compile_script_with_warnings(script, &OptimizeOptions::all(), &LintOptions::script())
+///         .unwrap();
+/// assert_eq!(warnings.len(), 1);
+/// ```
+pub fn compile_script_with_warnings(
+    input: &str,
+    options: &OptimizeOptions,
+    lints: &LintOptions,
+) -> Result<(LpsProgram, Vec<CompileWarning>), CompileError> {
+    compile_script_impl(input, options, lints, &[], &mut ())
+}
+
+/// Compile a script, also returning how long each compiler phase took
+///
+/// For finding which phase dominates on large scripts. Phases that didn't
+/// run because of an earlier error are left at zero.
+///
+/// # Example
+/// ```
+/// use lp_script::{compile_script_with_metrics, OptimizeOptions};
+/// let (program, metrics) =
+///     compile_script_with_metrics("return sin(time);", &OptimizeOptions::default());
+/// assert!(program.is_ok());
+/// println!("{:?} total, {} opcodes", metrics.total(), metrics.opcodes);
+/// ```
+#[cfg(feature = "std")]
+pub fn compile_script_with_metrics(
+    input: &str,
+    options: &OptimizeOptions,
+) -> (Result<LpsProgram, CompileError>, CompileMetrics) {
+    let mut recorder = compiler::metrics::MetricsRecorder::start();
+    let result = compile_script_impl(input, options, &LintOptions::none(), &[], &mut recorder)
+        .map(|(program, _)| program);
+    let mut metrics = recorder.metrics;
+    if let Ok(program) = &result {
+        metrics.opcodes = program.functions.iter().map(|f| f.opcodes.len()).sum();
+    }
+    (result, metrics)
+}
+
+/// Compile a script, also returning its function signatures and the
+/// built-ins it references, e.g. for editor tooling
+///
+/// # Example
+/// ```
+/// use lp_script::{compile_script_with_info, OptimizeOptions, Type};
+/// let script = "float wave(float t) { return sin(t); } return wave(time);";
+/// let (_program, info) = compile_script_with_info(script, &OptimizeOptions::default()).unwrap();
+/// assert_eq!(info.function("wave").unwrap().params, [Type::Fixed]);
+/// assert!(info.builtins.contains("time"));
+/// ```
+pub fn compile_script_with_info(
+    input: &str,
+    options: &OptimizeOptions,
+) -> Result<(LpsProgram, ScriptInfo), CompileError> {
+    let mut recorder = compiler::info::InfoRecorder::default();
+    let (program, _) =
+        compile_script_impl(input, options, &LintOptions::none(), &[], &mut recorder)?;
+    Ok((program, recorder.info))
+}
+
+fn compile_script_impl(
+    input: &str,
+    options: &OptimizeOptions,
+    lints: &LintOptions,
+    natives: &[NativeDef],
+    observer: &mut impl CompileObserver,
+) -> Result<(LpsProgram, Vec<CompileWarning>), CompileError> {
     let mut lexer = lexer::Lexer::new(input);
     let tokens = lexer.tokenize();
+    observer.phase_done(CompilePhase::Lex);
 
     let parser = parser::Parser::new(tokens);
     let mut program = parser.parse_program()?;
+    observer.phase_done(CompilePhase::Parse);
+    observer.parsed(&program);
 
     // Analyze program to build function types table
-    let func_table = compiler::analyzer::FunctionAnalyzer::analyze_program(&program)?;
+    let func_table =
+        compiler::analyzer::FunctionAnalyzer::analyze_program(&program)?.with_natives(natives);
 
     // Type check the program with the analyzed function table
     typechecker::TypeChecker::check_program(&mut program, &func_table)?;
+    observer.checked(&program, &func_table);
+
+    // Lint before optimization rewrites the source forms
+    let warnings = lint::lint_program(&program, lints);
+    observer.phase_done(CompilePhase::Typecheck);
 
     // Optimize program AST in place
-    optimize::optimize_ast_program(&mut program, options);
+    optimize::optimize_ast_program(&mut program, &func_table, options);
+    observer.phase_done(CompilePhase::Optimize);
 
     // Generate functions using new API with function table
     let functions = codegen::CodeGenerator::generate_program_with_functions(&program, &func_table);
+    observer.phase_done(CompilePhase::Codegen);
 
     // Optimize opcodes for each function
     let optimized_functions: Vec<vm::FunctionDef> = functions
@@ -217,10 +503,16 @@ pub fn compile_script_with_options(
                 .with_opcodes(optimized_opcodes)
         })
         .collect();
+    observer.phase_done(CompilePhase::Optimize);
+    codegen::check_opcode_set(&optimized_functions, &options.allowed_opcodes)?;
+    observer.phase_done(CompilePhase::Codegen);
 
-    Ok(LpsProgram::new("script".into())
+    let program = LpsProgram::new("script".into())
         .with_functions(optimized_functions)
-        .with_source(input.into()))
+        .with_externals(func_table.externals().to_vec())
+        .with_natives(natives.to_vec())
+        .with_source(input.into());
+    Ok((program, warnings))
 }
 
 /// Parse an expression string and generate a compiled LPS program
@@ -265,9 +557,306 @@ pub fn parse_script(input: &str) -> LpsProgram {
 mod tests {
     use alloc::vec::Vec;
 
+    use super::*;
+    use crate::compiler::error::TypeErrorKind;
+    use crate::fixed::{Fixed, Vec2};
+
     #[test]
     fn auto_pool_supports_lp_vec_allocations() {
         let vec = Vec::from([42]);
         assert_eq!(vec.len(), 1);
     }
+
+    #[test]
+    fn compile_expr_as_accepts_matching_type() {
+        let program = compile_expr_as("vec3(uv.x, uv.y, sin(time))", Type::Vec3).unwrap();
+        assert_eq!(program.main_function().unwrap().return_type, Type::Vec3);
+    }
+
+    #[test]
+    fn compile_expr_as_rejects_mismatched_type() {
+        match compile_expr_as("vec3(uv.x, uv.y, sin(time))", Type::Fixed) {
+            Err(CompileError::TypeCheck(err)) => assert!(matches!(
+                err.kind,
+                TypeErrorKind::Mismatch {
+                    expected: Type::Fixed,
+                    found: Type::Vec3,
+                }
+            )),
+            other => panic!("Expected type mismatch, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn compile_expr_as_promotes_int_to_fixed() {
+        let program = compile_expr_as("2 + 3", Type::Fixed).unwrap();
+        assert_eq!(program.main_function().unwrap().return_type, Type::Fixed);
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let result = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result, Fixed::from_i32(5));
+    }
+
+    #[test]
+    fn compile_expr_with_env_reads_declared_externals() {
+        let env = [("speed", Type::Fixed), ("offset", Type::Vec2)];
+        let program = compile_expr_with_env("speed * uv.x + offset.y", &env).unwrap();
+        assert_eq!(program.externals.len(), 2);
+        program.validate().unwrap();
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        vm.set_external("speed", InputValue::Fixed(Fixed::from_i32(2)))
+            .unwrap();
+        vm.set_external(
+            "offset",
+            InputValue::Vec2(Vec2::new(Fixed::ZERO, Fixed::from_i32(10))),
+        )
+        .unwrap();
+        let result = vm
+            .run_scalar(Fixed::from_f32(0.25), Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result, Fixed::from_f32(10.5));
+    }
+
+    #[test]
+    fn compile_expr_with_env_rejects_undeclared_names() {
+        match compile_expr_with_env("speed * gain", &[("speed", Type::Fixed)]) {
+            Err(CompileError::TypeCheck(err)) => {
+                assert!(
+                    matches!(err.kind, TypeErrorKind::UndefinedVariable(ref name) if name == "gain")
+                )
+            }
+            other => panic!("Expected undefined variable, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn set_external_checks_name_and_type() {
+        let program = compile_expr_with_env("speed", &[("speed", Type::Fixed)]).unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+
+        let err = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap_err();
+        assert!(matches!(err.error, LpsVmError::ExternalNotBound));
+
+        assert!(matches!(
+            vm.set_external("gain", InputValue::Fixed(Fixed::ONE)),
+            Err(LpsVmError::UnknownExternal(name)) if name == "gain"
+        ));
+        assert!(matches!(
+            vm.set_external("speed", InputValue::Int32(1)),
+            Err(LpsVmError::TypeMismatch)
+        ));
+    }
+
+    fn double_options() -> CompilerOptions {
+        let mut options = CompilerOptions::default();
+        options.register_native(
+            "double",
+            NativeSig::new(&[Type::Fixed], Type::Fixed),
+            |args| match args {
+                [InputValue::Fixed(x)] => InputValue::Fixed(*x * Fixed::from_i32(2)),
+                _ => InputValue::Fixed(Fixed::ZERO),
+            },
+        );
+        options
+    }
+
+    #[test]
+    fn registered_native_is_callable_from_script() {
+        let program = compile_script_with_compiler_options(
+            "float y = double(uv.x); return double(y) + 1.0;",
+            &double_options(),
+        )
+        .unwrap();
+        program.validate().unwrap();
+        assert!(program
+            .main_function()
+            .unwrap()
+            .opcodes
+            .contains(&LpsOpCode::CallNative(0)));
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let result = vm
+            .run_scalar(Fixed::from_f32(0.25), Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result, Fixed::from_i32(2));
+    }
+
+    #[test]
+    fn registered_native_is_not_folded_as_pure() {
+        // A user function calling a native with a literal must still run it
+        let program = compile_script_with_compiler_options(
+            "float twice(float v) { return double(v); } return twice(3.0);",
+            &double_options(),
+        )
+        .unwrap();
+        assert_eq!(program.constant_result(), None);
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let result = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result, Fixed::from_i32(6));
+    }
+
+    #[test]
+    fn registered_native_checks_argument_types() {
+        let options = double_options();
+        match compile_expr_with_compiler_options("double(uv)", &options) {
+            Err(CompileError::TypeCheck(err)) => assert!(matches!(
+                err.kind,
+                TypeErrorKind::Mismatch {
+                    expected: Type::Fixed,
+                    found: Type::Vec2,
+                }
+            )),
+            other => panic!("Expected type mismatch, got {:?}", other.map(|_| ())),
+        }
+        match compile_expr_with_compiler_options("double(1.0, 2.0)", &options) {
+            Err(CompileError::TypeCheck(err)) => {
+                assert!(matches!(
+                    err.kind,
+                    TypeErrorKind::InvalidArgumentCount { .. }
+                ))
+            }
+            other => panic!("Expected argument count error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn unbound_native_fails_at_run_time() {
+        let mut program =
+            compile_expr_with_compiler_options("double(time)", &double_options()).unwrap();
+        program.natives[0].func = None;
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let err = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap_err();
+        assert!(matches!(err.error, LpsVmError::NativeNotBound));
+    }
+
+    #[test]
+    fn allowed_opcodes_rejects_excluded_group() {
+        let options = OptimizeOptions {
+            allowed_opcodes: OpcodeSet::all().without(OpcodeGroup::Mat3),
+            ..OptimizeOptions::default()
+        };
+        let script = "
+            float det(mat3 m) { return determinant(m); }
+            return det(mat3(2.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, uv.x));
+        ";
+
+        match compile_script_with_options(script, &options) {
+            Err(CompileError::Codegen(err)) => {
+                let message = alloc::format!("{}", err);
+                assert!(message.contains("'det' needs opcode"), "{}", message);
+                assert!(message.contains("(matrices)"), "{}", message);
+            }
+            other => panic!("Expected disallowed opcode, got {:?}", other.map(|_| ())),
+        }
+        assert!(compile_script_with_options(script, &OptimizeOptions::default()).is_ok());
+
+        // Scripts without matrices still compile under the reduced set
+        assert!(compile_expr_with_options("length(uv) * 2.0", &options).is_ok());
+    }
+
+    #[test]
+    fn compile_script_with_metrics_counts_phases() {
+        let script = "
+            float wave(float t) { return sin(t) * 0.5 + 0.5; }
+            float sum = 0.0;
+            for (int i = 0; i < 4; i++) {
+                sum += wave(time + float(i));
+            }
+            return sum / 4.0;
+        ";
+        let (program, metrics) = compile_script_with_metrics(script, &OptimizeOptions::default());
+        let program = program.unwrap();
+
+        let opcodes: usize = program.functions.iter().map(|f| f.opcodes.len()).sum();
+        assert!(opcodes > 0);
+        assert_eq!(metrics.opcodes, opcodes);
+        assert!(metrics.ast_nodes > 10, "ast_nodes = {}", metrics.ast_nodes);
+        assert_eq!(
+            metrics.total(),
+            metrics.lex + metrics.parse + metrics.typecheck + metrics.optimize + metrics.codegen
+        );
+
+        // A type error stops after type checking
+        let (result, metrics) =
+            compile_script_with_metrics("return missing * 2.0;", &OptimizeOptions::default());
+        assert!(result.is_err());
+        assert_eq!(metrics.opcodes, 0);
+        assert_eq!(metrics.codegen, core::time::Duration::ZERO);
+    }
+
+    #[test]
+    fn eval_const_expr_folds_builtin_calls() {
+        assert_eq!(
+            eval_const_expr("2.0 + sin(0.0)").unwrap(),
+            ConstValue::Fixed(Fixed::from_f32(2.0))
+        );
+        assert_eq!(
+            eval_const_expr("vec2(1, 2.0 * 0.5)").unwrap(),
+            ConstValue::Vec2(Vec2::new(Fixed::ONE, Fixed::ONE))
+        );
+    }
+
+    #[test]
+    fn eval_const_expr_rejects_builtin_variables() {
+        match eval_const_expr("uv.x") {
+            Err(CompileError::TypeCheck(err)) => {
+                assert!(matches!(err.kind, TypeErrorKind::NotConstant));
+                assert_eq!(
+                    err.to_string(),
+                    "Type error at 0:4: expression is not constant"
+                );
+            }
+            other => panic!("expected a non-constant error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn compile_script_with_info_reports_function_signatures() {
+        let script = "
+            float wave(float t, int octaves) { return sin(t) * float(octaves); }
+            vec2 offset(vec2 p) { return p + vec2(0.5, 0.5); }
+            return wave(offset(uv).x, 2) + time;
+        ";
+        let (_program, info) =
+            compile_script_with_info(script, &OptimizeOptions::default()).unwrap();
+        assert_eq!(
+            info.functions,
+            [
+                FunctionInfo {
+                    name: "wave".into(),
+                    params: vec![Type::Fixed, Type::Int32],
+                    return_type: Type::Fixed,
+                },
+                FunctionInfo {
+                    name: "offset".into(),
+                    params: vec![Type::Vec2],
+                    return_type: Type::Vec2,
+                },
+            ]
+        );
+        assert_eq!(
+            info.builtins.iter().copied().collect::<Vec<_>>(),
+            ["float", "sin", "time", "uv"]
+        );
+    }
+
+    #[test]
+    fn compile_script_with_info_skips_shadowed_builtins() {
+        let script =
+            "float f() { float uv = 1.0; return uv; } float time = 2.0; return f() + time;";
+        let (_program, info) =
+            compile_script_with_info(script, &OptimizeOptions::default()).unwrap();
+        assert!(info.builtins.is_empty(), "{:?}", info.builtins);
+    }
 }
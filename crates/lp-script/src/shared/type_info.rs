@@ -3,6 +3,7 @@ extern crate alloc;
 use core::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Type {
     Bool,
     Fixed,
@@ -10,7 +11,22 @@ pub enum Type {
     Vec2,
     Vec3,
     Vec4,
+    /// Boolean vectors, one bool (0.0 or 1.0) per component
+    BVec2,
+    BVec3,
+    BVec4,
+    Mat2,
     Mat3,
+    Mat4,
+    /// `float[rows][cols]` local, stored row-major
+    Grid {
+        rows: u32,
+        cols: u32,
+    },
+    /// `float name[len]` local
+    Array {
+        len: u32,
+    },
     Void,
 }
 
@@ -27,8 +43,32 @@ impl Type {
             Type::Vec2 => 2,  // 2x Fixed (2x i32)
             Type::Vec3 => 3,  // 3x Fixed (3x i32)
             Type::Vec4 => 4,  // 4x Fixed (4x i32)
+            Type::BVec2 => 2, // 2x bool
+            Type::BVec3 => 3, // 3x bool
+            Type::BVec4 => 4, // 4x bool
+            Type::Mat2 => 4,  // 4x Fixed (4x i32) - 2x2 matrix
             Type::Mat3 => 9,  // 9x Fixed (9x i32) - 3x3 matrix
-            Type::Void => 0,  // No storage needed
+            Type::Mat4 => 16, // 16x Fixed (16x i32) - 4x4 matrix
+            Type::Grid { rows, cols } => (*rows as usize) * (*cols as usize),
+            Type::Array { len } => *len as usize,
+            Type::Void => 0, // No storage needed
+        }
+    }
+
+    /// Whether this is `bool` or a bvec, which never convert implicitly to
+    /// or from numeric types
+    pub fn is_bool(&self) -> bool {
+        matches!(self, Type::Bool | Type::BVec2 | Type::BVec3 | Type::BVec4)
+    }
+
+    /// The bvec with one component per component of vector type `self`,
+    /// e.g. the result of comparing two `vec3`s
+    pub fn to_bvec(&self) -> Option<Type> {
+        match self {
+            Type::Vec2 | Type::BVec2 => Some(Type::BVec2),
+            Type::Vec3 | Type::BVec3 => Some(Type::BVec3),
+            Type::Vec4 | Type::BVec4 => Some(Type::BVec4),
+            _ => None,
         }
     }
 }
@@ -42,7 +82,14 @@ impl fmt::Display for Type {
             Type::Vec2 => write!(f, "vec2"),
             Type::Vec3 => write!(f, "vec3"),
             Type::Vec4 => write!(f, "vec4"),
+            Type::BVec2 => write!(f, "bvec2"),
+            Type::BVec3 => write!(f, "bvec3"),
+            Type::BVec4 => write!(f, "bvec4"),
+            Type::Mat2 => write!(f, "mat2"),
             Type::Mat3 => write!(f, "mat3"),
+            Type::Mat4 => write!(f, "mat4"),
+            Type::Grid { rows, cols } => write!(f, "float[{}][{}]", rows, cols),
+            Type::Array { len } => write!(f, "float[{}]", len),
             Type::Void => write!(f, "void"),
         }
     }
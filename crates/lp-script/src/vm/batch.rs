@@ -0,0 +1,439 @@
+/// Row-batched execution of scalar programs
+///
+/// Runs each opcode across `BATCH_LANES` pixels of a row before moving to the
+/// next, so dispatch happens once per opcode per batch instead of once per
+/// pixel. Only straight-line programs over scalar opcodes qualify; anything
+/// else (jumps, calls, locals, vectors) goes through `execute_program_lps`.
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::coord_config::CoordConfig;
+use super::error::LpsVmError;
+use super::lps_program::LpsProgram;
+use super::lps_vm::LpsVm;
+use super::opcodes::load::load_value;
+use super::opcodes::LpsOpCode;
+use super::vm_limits::VmLimits;
+use crate::fixed::trig::{cos, sin};
+use crate::fixed::{
+    atan, atan2, ceil, floor, fract, lerp, modulo, pow, saturate, sign, smoothstep, sqrt, step,
+    tan, Fixed, Vec2, FIXED_ONE,
+};
+use crate::shared::Type;
+
+/// Pixels processed together
+pub const BATCH_LANES: usize = 8;
+
+type Lanes = [i32; BATCH_LANES];
+
+/// Per-lane inputs of one batch
+struct BatchInputs {
+    x_norm: [Fixed; BATCH_LANES],
+    y_norm: [Fixed; BATCH_LANES],
+    x_px: [Fixed; BATCH_LANES],
+    y_px: [Fixed; BATCH_LANES],
+    index: Lanes,
+    time: Fixed,
+    width: usize,
+    height: usize,
+}
+
+/// Execute a scalar program on all pixels, a batch of row pixels at a time
+///
+/// Produces exactly the same buffer as `execute_program_lps`, which it falls
+/// back to for programs it can't batch. A batch that hits a runtime error is
+/// re-run through the scalar VM so the panic names the same pixel.
+///
+/// # Panics
+/// Panics if the program encounters a runtime error.
+#[inline(never)]
+pub fn execute_program_lps_batched(
+    program: &LpsProgram,
+    output: &mut [Fixed],
+    width: usize,
+    height: usize,
+    time: Fixed,
+    coords: &CoordConfig,
+) {
+    let plan = program
+        .main_function()
+        .filter(|main| main.return_type == Type::Fixed && main.params.is_empty())
+        .and_then(|main| batch_stack_depth(&main.opcodes).map(|depth| (main, depth)));
+    let (main, depth) = match plan {
+        Some(plan) if program.constant_result().is_none() => plan,
+        _ => return super::execute_program_lps(program, output, width, height, time, coords),
+    };
+
+    let mut stack = LaneStack {
+        data: vec![[0; BATCH_LANES]; depth],
+        sp: 0,
+    };
+    // Only needed to report errors, so created on first use
+    let mut scalar_vm: Option<LpsVm> = None;
+
+    for y in 0..height {
+        for x0 in (0..width).step_by(BATCH_LANES) {
+            let lanes = BATCH_LANES.min(width - x0);
+            let mut inputs = BatchInputs {
+                x_norm: [Fixed::ZERO; BATCH_LANES],
+                y_norm: [Fixed::ZERO; BATCH_LANES],
+                x_px: [Fixed::ZERO; BATCH_LANES],
+                y_px: [Fixed::ZERO; BATCH_LANES],
+                index: [0; BATCH_LANES],
+                time,
+                width,
+                height,
+            };
+            for lane in 0..BATCH_LANES {
+                // Spare lanes repeat the last pixel so they can't fail on their own
+                let x = x0 + lane.min(lanes - 1);
+                let (x_norm, y_norm, x_px, y_px) = coords.pixel_coords(x, y, width, height);
+                inputs.x_norm[lane] = x_norm;
+                inputs.y_norm[lane] = y_norm;
+                inputs.x_px[lane] = x_px;
+                inputs.y_px[lane] = y_px;
+                inputs.index[lane] = (y * width + x) as i32;
+            }
+
+            stack.sp = 0;
+            let result = match run_batch(&main.opcodes, &mut stack, &inputs) {
+                Ok(result) => result,
+                Err(_) => {
+                    let vm = scalar_vm.get_or_insert_with(|| {
+                        LpsVm::new(program, VmLimits::default()).expect("Failed to create VM")
+                    });
+                    let mut result = [0; BATCH_LANES];
+                    for (lane, value) in result.iter_mut().enumerate().take(lanes) {
+                        vm.set_index(y * width + x0 + lane);
+                        *value = vm
+                            .run_scalar_with_coords(
+                                inputs.x_norm[lane],
+                                inputs.y_norm[lane],
+                                inputs.x_px[lane],
+                                inputs.y_px[lane],
+                                time,
+                                width,
+                                height,
+                            )
+                            .unwrap_or_else(|e| {
+                                panic!("Runtime error at pixel ({}, {}): {}", x0 + lane, y, e);
+                            })
+                            .0;
+                    }
+                    result
+                }
+            };
+
+            let idx = y * width + x0;
+            for lane in 0..lanes {
+                if idx + lane < output.len() {
+                    output[idx + lane] = Fixed(result[lane]);
+                }
+            }
+        }
+    }
+}
+
+/// Stack of lane vectors, sized up front from `batch_stack_depth`
+struct LaneStack {
+    data: Vec<Lanes>,
+    sp: usize,
+}
+
+impl LaneStack {
+    #[inline(always)]
+    fn push(&mut self, lanes: Lanes) {
+        self.data[self.sp] = lanes;
+        self.sp += 1;
+    }
+
+    #[inline(always)]
+    fn pop(&mut self) -> Lanes {
+        self.sp -= 1;
+        self.data[self.sp]
+    }
+
+    #[inline(always)]
+    fn unary(&mut self, f: impl Fn(i32) -> i32) {
+        let a = &mut self.data[self.sp - 1];
+        for v in a.iter_mut() {
+            *v = f(*v);
+        }
+    }
+
+    #[inline(always)]
+    fn binary(&mut self, f: impl Fn(i32, i32) -> i32) {
+        let b = self.pop();
+        let a = &mut self.data[self.sp - 1];
+        for (v, b) in a.iter_mut().zip(b) {
+            *v = f(*v, b);
+        }
+    }
+
+    #[inline(always)]
+    fn ternary(&mut self, f: impl Fn(i32, i32, i32) -> i32) {
+        let c = self.pop();
+        let b = self.pop();
+        let a = &mut self.data[self.sp - 1];
+        for lane in 0..BATCH_LANES {
+            a[lane] = f(a[lane], b[lane], c[lane]);
+        }
+    }
+
+    /// Fail like the scalar opcode when any lane's divisor is zero
+    #[inline(always)]
+    fn check_divisor(&self) -> Result<(), LpsVmError> {
+        if self.data[self.sp - 1].contains(&0) {
+            return Err(LpsVmError::DivisionByZero);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn dup(&mut self, count: usize) {
+        for i in 0..count {
+            self.data[self.sp + i] = self.data[self.sp - count + i];
+        }
+        self.sp += count;
+    }
+}
+
+/// Run `opcodes` for every lane, returning the lanes of the result
+fn run_batch(
+    opcodes: &[LpsOpCode],
+    stack: &mut LaneStack,
+    inputs: &BatchInputs,
+) -> Result<Lanes, LpsVmError> {
+    let bool_fixed = |b: bool| if b { FIXED_ONE } else { 0 };
+    let center = Vec2::new(Fixed::HALF, Fixed::HALF);
+
+    for op in opcodes {
+        match *op {
+            LpsOpCode::Push(value) => stack.push([value.0; BATCH_LANES]),
+            LpsOpCode::PushInt32(value) => stack.push([value; BATCH_LANES]),
+            LpsOpCode::Load(source) => {
+                let mut lanes = [0; BATCH_LANES];
+                for (lane, v) in lanes.iter_mut().enumerate() {
+                    *v = load_value(
+                        source,
+                        inputs.x_norm[lane],
+                        inputs.y_norm[lane],
+                        inputs.x_px[lane],
+                        inputs.y_px[lane],
+                        inputs.time,
+                        inputs.width,
+                        inputs.height,
+                        center,
+                    )
+                    .0;
+                }
+                stack.push(lanes);
+            }
+            LpsOpCode::LoadIndex => stack.push(inputs.index),
+
+            LpsOpCode::Dup1 => stack.dup(1),
+            LpsOpCode::Dup2 => stack.dup(2),
+            LpsOpCode::Dup3 => stack.dup(3),
+            LpsOpCode::Dup4 => stack.dup(4),
+            LpsOpCode::Drop1 => stack.sp -= 1,
+            LpsOpCode::Drop2 => stack.sp -= 2,
+            LpsOpCode::Drop3 => stack.sp -= 3,
+            LpsOpCode::Drop4 => stack.sp -= 4,
+            LpsOpCode::Swap => stack.data.swap(stack.sp - 1, stack.sp - 2),
+
+            LpsOpCode::AddFixed => stack.binary(|a, b| (Fixed(a) + Fixed(b)).0),
+            LpsOpCode::SubFixed => stack.binary(|a, b| (Fixed(a) - Fixed(b)).0),
+            LpsOpCode::MulFixed => stack.binary(|a, b| (Fixed(a) * Fixed(b)).0),
+            LpsOpCode::DivFixed => {
+                stack.check_divisor()?;
+                stack.binary(|a, b| (Fixed(a) / Fixed(b)).0);
+            }
+            LpsOpCode::ModFixed => {
+                stack.check_divisor()?;
+                stack.binary(|a, b| modulo(Fixed(a), Fixed(b)).0);
+            }
+            LpsOpCode::MinFixed => stack.binary(|a, b| Fixed(a).min(Fixed(b)).0),
+            LpsOpCode::MaxFixed => stack.binary(|a, b| Fixed(a).max(Fixed(b)).0),
+            LpsOpCode::Atan2Fixed => stack.binary(|y, x| atan2(Fixed(y), Fixed(x)).0),
+            LpsOpCode::PowFixed => stack.binary(|a, b| pow(Fixed(a), Fixed(b).to_i32()).0),
+            LpsOpCode::StepFixed => stack.binary(|edge, x| step(Fixed(edge), Fixed(x)).0),
+
+            LpsOpCode::NegFixed => stack.unary(|a| (-Fixed(a)).0),
+            LpsOpCode::AbsFixed => stack.unary(|a| Fixed(a).abs().0),
+            LpsOpCode::SinFixed => stack.unary(|a| sin(Fixed(a)).0),
+            LpsOpCode::CosFixed => stack.unary(|a| cos(Fixed(a)).0),
+            LpsOpCode::TanFixed => stack.unary(|a| tan(Fixed(a)).0),
+            LpsOpCode::AtanFixed => stack.unary(|a| atan(Fixed(a)).0),
+            LpsOpCode::SqrtFixed => stack.unary(|a| sqrt(Fixed(a)).0),
+            LpsOpCode::FloorFixed => stack.unary(|a| floor(Fixed(a)).0),
+            LpsOpCode::CeilFixed => stack.unary(|a| ceil(Fixed(a)).0),
+            LpsOpCode::FractFixed => stack.unary(|a| fract(Fixed(a)).0),
+            LpsOpCode::SignFixed => stack.unary(|a| sign(Fixed(a)).0),
+            LpsOpCode::SaturateFixed => stack.unary(|a| saturate(Fixed(a)).0),
+
+            LpsOpCode::ClampFixed => {
+                stack.ternary(|x, lo, hi| Fixed(x).clamp(Fixed(lo), Fixed(hi)).0)
+            }
+            LpsOpCode::LerpFixed => stack.ternary(|a, b, t| lerp(Fixed(a), Fixed(b), Fixed(t)).0),
+            LpsOpCode::SmoothstepFixed => {
+                stack.ternary(|e0, e1, x| smoothstep(Fixed(e0), Fixed(e1), Fixed(x)).0)
+            }
+            LpsOpCode::Select => stack.ternary(|c, t, f| if c != 0 { t } else { f }),
+
+            LpsOpCode::GreaterFixed => stack.binary(|a, b| bool_fixed(a > b)),
+            LpsOpCode::LessFixed => stack.binary(|a, b| bool_fixed(a < b)),
+            LpsOpCode::GreaterEqFixed => stack.binary(|a, b| bool_fixed(a >= b)),
+            LpsOpCode::LessEqFixed => stack.binary(|a, b| bool_fixed(a <= b)),
+            LpsOpCode::EqFixed => stack.binary(|a, b| bool_fixed(a == b)),
+            LpsOpCode::NotEqFixed => stack.binary(|a, b| bool_fixed(a != b)),
+            LpsOpCode::AndFixed => stack.binary(|a, b| bool_fixed(a != 0 && b != 0)),
+            LpsOpCode::OrFixed => stack.binary(|a, b| bool_fixed(a != 0 || b != 0)),
+            LpsOpCode::NotFixed => stack.unary(|a| bool_fixed(a == 0)),
+
+            LpsOpCode::Return => return Ok(stack.pop()),
+            // `batch_stack_depth` only accepts the opcodes above
+            _ => unreachable!("opcode {} is not batchable", op.name()),
+        }
+    }
+    unreachable!("batchable programs end in Return")
+}
+
+/// Stack slots needed to batch `opcodes`, or `None` if the program can't be
+/// batched
+///
+/// Accepts straight-line code ending in a single `Return` of one value, with
+/// no opcode reading more values than the stack holds.
+fn batch_stack_depth(opcodes: &[LpsOpCode]) -> Option<usize> {
+    let (last, body) = opcodes.split_last()?;
+    if *last != LpsOpCode::Return {
+        return None;
+    }
+
+    let mut depth = 0usize;
+    let mut max_depth = 0usize;
+    for op in body {
+        let (pops, pushes) = lane_stack_effect(op)?;
+        depth = depth.checked_sub(pops)? + pushes;
+        max_depth = max_depth.max(depth);
+    }
+    if depth != 1 || max_depth > VmLimits::default().max_stack_size {
+        return None;
+    }
+    Some(max_depth)
+}
+
+/// Values popped and pushed by a batchable opcode
+fn lane_stack_effect(op: &LpsOpCode) -> Option<(usize, usize)> {
+    let effect = match op {
+        LpsOpCode::Push(_)
+        | LpsOpCode::PushInt32(_)
+        | LpsOpCode::Load(_)
+        | LpsOpCode::LoadIndex => (0, 1),
+        LpsOpCode::Dup1 => (1, 2),
+        LpsOpCode::Dup2 => (2, 4),
+        LpsOpCode::Dup3 => (3, 6),
+        LpsOpCode::Dup4 => (4, 8),
+        LpsOpCode::Drop1 => (1, 0),
+        LpsOpCode::Drop2 => (2, 0),
+        LpsOpCode::Drop3 => (3, 0),
+        LpsOpCode::Drop4 => (4, 0),
+        LpsOpCode::Swap => (2, 2),
+        LpsOpCode::NegFixed
+        | LpsOpCode::AbsFixed
+        | LpsOpCode::SinFixed
+        | LpsOpCode::CosFixed
+        | LpsOpCode::TanFixed
+        | LpsOpCode::AtanFixed
+        | LpsOpCode::SqrtFixed
+        | LpsOpCode::FloorFixed
+        | LpsOpCode::CeilFixed
+        | LpsOpCode::FractFixed
+        | LpsOpCode::SignFixed
+        | LpsOpCode::SaturateFixed
+        | LpsOpCode::NotFixed => (1, 1),
+        LpsOpCode::AddFixed
+        | LpsOpCode::SubFixed
+        | LpsOpCode::MulFixed
+        | LpsOpCode::DivFixed
+        | LpsOpCode::ModFixed
+        | LpsOpCode::MinFixed
+        | LpsOpCode::MaxFixed
+        | LpsOpCode::Atan2Fixed
+        | LpsOpCode::PowFixed
+        | LpsOpCode::StepFixed
+        | LpsOpCode::GreaterFixed
+        | LpsOpCode::LessFixed
+        | LpsOpCode::GreaterEqFixed
+        | LpsOpCode::LessEqFixed
+        | LpsOpCode::EqFixed
+        | LpsOpCode::NotEqFixed
+        | LpsOpCode::AndFixed
+        | LpsOpCode::OrFixed => (2, 1),
+        LpsOpCode::ClampFixed
+        | LpsOpCode::LerpFixed
+        | LpsOpCode::SmoothstepFixed
+        | LpsOpCode::Select => (3, 1),
+        _ => return None,
+    };
+    Some(effect)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compile_expr;
+    use crate::vm::{execute_program_lps, Origin};
+
+    fn assert_matches_scalar(src: &str, width: usize, height: usize, coords: &CoordConfig) {
+        let program = compile_expr(src).unwrap();
+        let time = Fixed::from_f32(1.75);
+        let mut scalar = vec![Fixed::ZERO; width * height];
+        let mut batched = vec![Fixed::ZERO; width * height];
+        execute_program_lps(&program, &mut scalar, width, height, time, coords);
+        execute_program_lps_batched(&program, &mut batched, width, height, time, coords);
+        assert_eq!(batched, scalar, "{}", src);
+    }
+
+    #[test]
+    fn test_batched_matches_scalar() {
+        let src = "sin(uv.x * 6.0 + time) * 0.5 + cos(coord.y * 0.3) * 0.25 \
+                   + smoothstep(0.2, 0.8, fract(centerDist * 3.0)) * 0.25 \
+                   - max(x, y) / (abs(x - 0.5) + 0.1) * 0.01";
+        assert!(batch_stack_depth(&compile_expr(src).unwrap().functions[0].opcodes).is_some());
+
+        // 13 is not a multiple of the batch width, so the last batch is partial
+        assert_matches_scalar(src, 13, 5, &CoordConfig::default());
+        assert_matches_scalar(
+            src,
+            16,
+            3,
+            &CoordConfig {
+                flip_y: true,
+                origin: Origin::PixelCorner,
+            },
+        );
+    }
+
+    #[test]
+    fn test_batched_falls_back_for_branches() {
+        let src = "uv.x > 0.5 ? sin(time) : float(index) * 0.01";
+        assert!(batch_stack_depth(&compile_expr(src).unwrap().functions[0].opcodes).is_none());
+        assert_matches_scalar(src, 9, 4, &CoordConfig::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "Runtime error at pixel (3, 0)")]
+    fn test_batched_reports_scalar_error_pixel() {
+        let program = compile_expr("1.0 / (coord.x - 3.5)").unwrap();
+        let mut output = vec![Fixed::ZERO; 8];
+        execute_program_lps_batched(
+            &program,
+            &mut output,
+            8,
+            1,
+            Fixed::ZERO,
+            &CoordConfig::default(),
+        );
+    }
+}
@@ -0,0 +1,47 @@
+/// Tick sources for profiling VM runs without `std::time`
+#[cfg(feature = "std")]
+extern crate std;
+
+/// A monotonic tick counter, e.g. a hardware cycle or microsecond timer
+///
+/// The unit is up to the implementation; only differences between two
+/// readings are reported. Any `Fn() -> u64` is a clock.
+pub trait Clock {
+    fn ticks(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn ticks(&self) -> u64 {
+        self()
+    }
+}
+
+/// `Clock` counting microseconds since it was created, for the host
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct StdClock {
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl StdClock {
+    pub fn new() -> Self {
+        StdClock {
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for StdClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for StdClock {
+    fn ticks(&self) -> u64 {
+        self.start.elapsed().as_micros() as u64
+    }
+}
@@ -0,0 +1,148 @@
+/// Pixel coordinate configuration for the execute functions
+use crate::fixed::Fixed;
+
+/// Where a pixel's coordinate is taken from within its cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Origin {
+    /// Sample at the pixel center (x + 0.5, y + 0.5)
+    #[default]
+    PixelCenter,
+    /// Sample at the pixel's top-left corner (x, y)
+    PixelCorner,
+}
+
+/// Controls how `uv` and `coord` are computed for each pixel
+///
+/// The default matches the historical behavior: pixel centers are sampled
+/// and Y increases downward (origin at the top-left).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CoordConfig {
+    /// Make Y increase upward (origin at the bottom-left)
+    pub flip_y: bool,
+    pub origin: Origin,
+}
+
+impl CoordConfig {
+    /// Compute `(x_norm, y_norm, x_px, y_px)` for the pixel at (x, y)
+    #[inline(always)]
+    pub fn pixel_coords(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> (Fixed, Fixed, Fixed, Fixed) {
+        let offset = match self.origin {
+            Origin::PixelCenter => Fixed::HALF,
+            Origin::PixelCorner => Fixed::ZERO,
+        };
+        let y = if self.flip_y {
+            height.saturating_sub(1 + y)
+        } else {
+            y
+        };
+
+        let x_px = Fixed::from_i32(x as i32) + offset;
+        let y_px = Fixed::from_i32(y as i32) + offset;
+        let x_norm = x_px / Fixed::from_i32(width as i32);
+        let y_norm = y_px / Fixed::from_i32(height as i32);
+
+        (x_norm, y_norm, x_px, y_px)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::parse_expr;
+    use crate::vm::{execute_program_lps, execute_program_lps_vec3};
+
+    #[test]
+    fn test_default_samples_pixel_centers() {
+        let config = CoordConfig::default();
+        let (x_norm, y_norm, x_px, y_px) = config.pixel_coords(0, 1, 4, 4);
+        assert_eq!(x_px, Fixed::HALF);
+        assert_eq!(y_px, Fixed::from_f32(1.5));
+        assert_eq!(x_norm, Fixed::from_f32(0.125));
+        assert_eq!(y_norm, Fixed::from_f32(0.375));
+    }
+
+    #[test]
+    fn test_pixel_corner_origin() {
+        let config = CoordConfig {
+            origin: Origin::PixelCorner,
+            ..CoordConfig::default()
+        };
+        let (x_norm, y_norm, x_px, y_px) = config.pixel_coords(2, 0, 4, 4);
+        assert_eq!(x_px, Fixed::from_i32(2));
+        assert_eq!(y_px, Fixed::ZERO);
+        assert_eq!(x_norm, Fixed::HALF);
+        assert_eq!(y_norm, Fixed::ZERO);
+    }
+
+    #[test]
+    fn test_flip_y_mirrors_output_vertically() {
+        let program = parse_expr("uv.y * 2.0 + uv.x");
+        let (width, height) = (3, 4);
+
+        let mut normal = vec![Fixed::ZERO; width * height];
+        execute_program_lps(
+            &program,
+            &mut normal,
+            width,
+            height,
+            Fixed::ZERO,
+            &CoordConfig::default(),
+        );
+
+        let flipped_config = CoordConfig {
+            flip_y: true,
+            ..CoordConfig::default()
+        };
+        let mut flipped = vec![Fixed::ZERO; width * height];
+        execute_program_lps(
+            &program,
+            &mut flipped,
+            width,
+            height,
+            Fixed::ZERO,
+            &flipped_config,
+        );
+
+        assert_ne!(normal, flipped);
+        for y in 0..height {
+            for x in 0..width {
+                assert_eq!(
+                    flipped[y * width + x],
+                    normal[(height - 1 - y) * width + x],
+                    "pixel ({}, {}) should mirror row {}",
+                    x,
+                    y,
+                    height - 1 - y
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_flip_y_vec3() {
+        let program = parse_expr("vec3(uv.y, coord.y, 0.0)");
+        let (width, height) = (2, 2);
+        let config = CoordConfig {
+            flip_y: true,
+            ..CoordConfig::default()
+        };
+
+        let mut output = vec![Fixed::ZERO; width * height * 3];
+        execute_program_lps_vec3(&program, &mut output, width, height, Fixed::ZERO, &config);
+
+        // Top row now samples the bottom of the image
+        assert_eq!(output[0], Fixed::from_f32(0.75));
+        assert_eq!(output[1], Fixed::from_f32(1.5));
+        // Bottom row samples the top
+        assert_eq!(output[2 * 3], Fixed::from_f32(0.25));
+        assert_eq!(output[2 * 3 + 1], Fixed::HALF);
+    }
+}
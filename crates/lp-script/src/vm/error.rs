@@ -3,6 +3,8 @@ extern crate alloc;
 use alloc::string::String;
 use core::fmt;
 
+use crate::shared::Span;
+
 #[derive(Debug)]
 pub enum LpsVmError {
     StackUnderflow {
@@ -44,6 +46,20 @@ pub enum LpsVmError {
     },
     InvalidFunctionIndex,
     PoolAllocationFailed,
+    PaletteNotBound,
+    /// A texture built-in sampled a slot with no `LpsVm::set_texture` image
+    UnboundTexture {
+        texture_idx: usize,
+    },
+    /// `LoadExternal` ran before the host set that external
+    ExternalNotBound,
+    /// `CallNative` ran without a host implementation, e.g. after deserializing
+    NativeNotBound,
+    /// `LpsVm::set_external` named an external the program doesn't declare
+    UnknownExternal(String),
+    /// Result of a checked arithmetic opcode doesn't fit its type
+    /// (only raised with `LpsVm::set_overflow_check`)
+    ArithmeticOverflow,
 }
 
 impl LpsVmError {
@@ -53,6 +69,7 @@ impl LpsVmError {
             error: self,
             pc,
             opcode,
+            span: None,
         }
     }
 }
@@ -63,6 +80,8 @@ pub struct RuntimeErrorWithContext {
     pub error: LpsVmError,
     pub pc: usize,
     pub opcode: &'static str,
+    /// Source range of the failing opcode, when the program has a source map
+    pub span: Option<Span>,
 }
 
 impl fmt::Display for LpsVmError {
@@ -131,6 +150,28 @@ impl fmt::Display for LpsVmError {
             LpsVmError::PoolAllocationFailed => {
                 write!(f, "Failed to allocate memory from LpPool")
             }
+            LpsVmError::PaletteNotBound => {
+                write!(f, "palette() sampled but no palette is bound to the VM")
+            }
+            LpsVmError::UnboundTexture { texture_idx } => {
+                write!(
+                    f,
+                    "texture {} sampled but nothing is bound to it",
+                    texture_idx
+                )
+            }
+            LpsVmError::ExternalNotBound => {
+                write!(f, "external read before the host set its value")
+            }
+            LpsVmError::NativeNotBound => {
+                write!(f, "native function called without a host implementation")
+            }
+            LpsVmError::UnknownExternal(name) => {
+                write!(f, "program has no external named '{}'", name)
+            }
+            LpsVmError::ArithmeticOverflow => {
+                write!(f, "Arithmetic overflow")
+            }
         }
     }
 }
@@ -5,8 +5,8 @@ use alloc::vec;
 use alloc::vec::Vec;
 
 use super::error::LpsVmError;
-use super::lps_program::LocalVarDef;
-use crate::fixed::{Fixed, Mat3};
+use super::lps_program::{LocalAccess, LocalVarDef};
+use crate::fixed::{Fixed, Mat2, Mat3, Mat4};
 use crate::shared::Type;
 
 impl LocalStack {
@@ -111,7 +111,8 @@ impl LocalStack {
 
     /// Reset locals to a given count and re-initialize their values
     ///
-    /// Used when resetting the VM for a new execution run.
+    /// Used when resetting the VM for a new execution run. Persistent locals
+    /// keep their values.
     pub fn reset_locals(
         &mut self,
         target_local_count: usize,
@@ -123,6 +124,9 @@ impl LocalStack {
         // Then re-initialize the values for all remaining locals
         let count = target_local_count.min(defs.len()).min(self.local_count);
         for (idx, def) in defs.iter().enumerate().take(count) {
+            if def.access == LocalAccess::Persistent {
+                continue;
+            }
             let meta = self.get_metadata(idx)?;
             let offset = meta.offset;
             let size = meta.size;
@@ -149,6 +153,22 @@ impl LocalStack {
         Ok(())
     }
 
+    /// Re-initialize only the persistent locals among the first `defs.len()`
+    pub fn reset_persistent(&mut self, defs: &[LocalVarDef]) {
+        for (def, meta) in defs.iter().zip(self.metadata.iter()) {
+            if def.access != LocalAccess::Persistent {
+                continue;
+            }
+            let slots = &mut self.data[meta.offset..meta.offset + meta.size];
+            slots.fill(0);
+            if let Some(ref init_value) = def.initial_value {
+                for (slot, &val) in slots.iter_mut().zip(init_value) {
+                    *slot = val;
+                }
+            }
+        }
+    }
+
     /// Get a Fixed value from a local (absolute index)
     #[inline(always)]
     pub fn get_fixed(&self, idx: usize) -> Result<Fixed, LpsVmError> {
@@ -210,7 +230,7 @@ impl LocalStack {
     pub fn get_vec2(&self, idx: usize) -> Result<(Fixed, Fixed), LpsVmError> {
         let meta = self.get_metadata(idx)?;
 
-        if meta.ty != Type::Vec2 {
+        if !matches!(meta.ty, Type::Vec2 | Type::BVec2) {
             return Err(LpsVmError::TypeMismatch);
         }
 
@@ -227,7 +247,7 @@ impl LocalStack {
             (meta.offset, meta.ty.clone())
         };
 
-        if ty != Type::Vec2 {
+        if !matches!(ty, Type::Vec2 | Type::BVec2) {
             return Err(LpsVmError::TypeMismatch);
         }
 
@@ -241,7 +261,7 @@ impl LocalStack {
     pub fn get_vec3(&self, idx: usize) -> Result<(Fixed, Fixed, Fixed), LpsVmError> {
         let meta = self.get_metadata(idx)?;
 
-        if meta.ty != Type::Vec3 {
+        if !matches!(meta.ty, Type::Vec3 | Type::BVec3) {
             return Err(LpsVmError::TypeMismatch);
         }
 
@@ -259,7 +279,7 @@ impl LocalStack {
             (meta.offset, meta.ty.clone())
         };
 
-        if ty != Type::Vec3 {
+        if !matches!(ty, Type::Vec3 | Type::BVec3) {
             return Err(LpsVmError::TypeMismatch);
         }
 
@@ -274,7 +294,7 @@ impl LocalStack {
     pub fn get_vec4(&self, idx: usize) -> Result<(Fixed, Fixed, Fixed, Fixed), LpsVmError> {
         let meta = self.get_metadata(idx)?;
 
-        if meta.ty != Type::Vec4 {
+        if !matches!(meta.ty, Type::Vec4 | Type::BVec4) {
             return Err(LpsVmError::TypeMismatch);
         }
 
@@ -300,7 +320,7 @@ impl LocalStack {
             (meta.offset, meta.ty.clone())
         };
 
-        if ty != Type::Vec4 {
+        if !matches!(ty, Type::Vec4 | Type::BVec4) {
             return Err(LpsVmError::TypeMismatch);
         }
 
@@ -311,6 +331,42 @@ impl LocalStack {
         Ok(())
     }
 
+    /// Get a Mat2 value from a local (absolute index)
+    #[inline(always)]
+    pub fn get_mat2(&self, idx: usize) -> Result<Mat2, LpsVmError> {
+        let meta = self.get_metadata(idx)?;
+
+        if meta.ty != Type::Mat2 {
+            return Err(LpsVmError::TypeMismatch);
+        }
+
+        Ok(Mat2::new(
+            Fixed(self.data[meta.offset]),
+            Fixed(self.data[meta.offset + 1]),
+            Fixed(self.data[meta.offset + 2]),
+            Fixed(self.data[meta.offset + 3]),
+        ))
+    }
+
+    /// Set a Mat2 value to a local (absolute index)
+    #[inline(always)]
+    pub fn set_mat2(&mut self, idx: usize, mat: Mat2) -> Result<(), LpsVmError> {
+        let (offset, ty) = {
+            let meta = self.get_metadata(idx)?;
+            (meta.offset, meta.ty.clone())
+        };
+
+        if ty != Type::Mat2 {
+            return Err(LpsVmError::TypeMismatch);
+        }
+
+        self.data[offset] = mat.m[0].0;
+        self.data[offset + 1] = mat.m[1].0;
+        self.data[offset + 2] = mat.m[2].0;
+        self.data[offset + 3] = mat.m[3].0;
+        Ok(())
+    }
+
     /// Get a Mat3 value from a local (absolute index)
     #[inline(always)]
     pub fn get_mat3(&self, idx: usize) -> Result<Mat3, LpsVmError> {
@@ -357,6 +413,80 @@ impl LocalStack {
         Ok(())
     }
 
+    /// Get a Mat4 value from a local (absolute index)
+    #[inline(always)]
+    pub fn get_mat4(&self, idx: usize) -> Result<Mat4, LpsVmError> {
+        let meta = self.get_metadata(idx)?;
+
+        if meta.ty != Type::Mat4 {
+            return Err(LpsVmError::TypeMismatch);
+        }
+
+        let mut mat = Mat4::zero();
+        for (i, v) in mat.m.iter_mut().enumerate() {
+            *v = Fixed(self.data[meta.offset + i]);
+        }
+        Ok(mat)
+    }
+
+    /// Set a Mat4 value to a local (absolute index)
+    #[inline(always)]
+    pub fn set_mat4(&mut self, idx: usize, mat: Mat4) -> Result<(), LpsVmError> {
+        let (offset, ty) = {
+            let meta = self.get_metadata(idx)?;
+            (meta.offset, meta.ty.clone())
+        };
+
+        if ty != Type::Mat4 {
+            return Err(LpsVmError::TypeMismatch);
+        }
+
+        for (i, v) in mat.m.iter().enumerate() {
+            self.data[offset + i] = v.0;
+        }
+        Ok(())
+    }
+
+    /// Get one Fixed element of a grid or array local (absolute index, flat
+    /// element index)
+    #[inline(always)]
+    pub fn get_elem_fixed(&self, idx: usize, elem: i32) -> Result<Fixed, LpsVmError> {
+        let offset = self.elem_offset(idx, elem)?;
+        Ok(Fixed(self.data[offset]))
+    }
+
+    /// Set one Fixed element of a grid or array local (absolute index, flat
+    /// element index)
+    #[inline(always)]
+    pub fn set_elem_fixed(
+        &mut self,
+        idx: usize,
+        elem: i32,
+        value: Fixed,
+    ) -> Result<(), LpsVmError> {
+        let offset = self.elem_offset(idx, elem)?;
+        self.data[offset] = value.0;
+        Ok(())
+    }
+
+    /// Data offset of element `elem` of a grid or array local (private helper)
+    #[inline(always)]
+    fn elem_offset(&self, idx: usize, elem: i32) -> Result<usize, LpsVmError> {
+        let meta = self.get_metadata(idx)?;
+
+        if !matches!(meta.ty, Type::Grid { .. } | Type::Array { .. }) {
+            return Err(LpsVmError::TypeMismatch);
+        }
+        if elem < 0 || elem as usize >= meta.size {
+            return Err(LpsVmError::InvalidArrayIndex {
+                index: elem,
+                array_size: meta.size,
+            });
+        }
+
+        Ok(meta.offset + elem as usize)
+    }
+
     /// Get types for a local (private helper)
     #[inline(always)]
     fn get_metadata(&self, idx: usize) -> Result<&LocalMetadata, LpsVmError> {
@@ -430,7 +560,7 @@ impl LocalStack {
     /// Get a Vec2 local by name (for debugging/testing)
     pub fn get_vec2_by_name(&self, name: &str) -> Option<(Fixed, Fixed)> {
         let meta = self.metadata.iter().find(|m| m.name.as_str() == name)?;
-        if meta.ty == Type::Vec2 {
+        if matches!(meta.ty, Type::Vec2 | Type::BVec2) {
             let x = Fixed(self.data[meta.offset]);
             let y = Fixed(self.data[meta.offset + 1]);
             Some((x, y))
@@ -442,7 +572,7 @@ impl LocalStack {
     /// Get a Vec3 local by name (for debugging/testing)
     pub fn get_vec3_by_name(&self, name: &str) -> Option<(Fixed, Fixed, Fixed)> {
         let meta = self.metadata.iter().find(|m| m.name.as_str() == name)?;
-        if meta.ty == Type::Vec3 {
+        if matches!(meta.ty, Type::Vec3 | Type::BVec3) {
             let x = Fixed(self.data[meta.offset]);
             let y = Fixed(self.data[meta.offset + 1]);
             let z = Fixed(self.data[meta.offset + 2]);
@@ -455,7 +585,7 @@ impl LocalStack {
     /// Get a Vec4 local by name (for debugging/testing)
     pub fn get_vec4_by_name(&self, name: &str) -> Option<(Fixed, Fixed, Fixed, Fixed)> {
         let meta = self.metadata.iter().find(|m| m.name == name)?;
-        if meta.ty == Type::Vec4 {
+        if matches!(meta.ty, Type::Vec4 | Type::BVec4) {
             let x = Fixed(self.data[meta.offset]);
             let y = Fixed(self.data[meta.offset + 1]);
             let z = Fixed(self.data[meta.offset + 2]);
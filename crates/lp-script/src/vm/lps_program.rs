@@ -1,9 +1,15 @@
 /// LPS Program definition
 extern crate alloc;
+use alloc::format;
 use alloc::string::String;
+use alloc::vec;
 use alloc::vec::Vec;
+use core::fmt;
 
+use super::native::NativeDef;
 use super::opcodes::LpsOpCode;
+use super::vm_limits::VmLimits;
+use crate::fixed::Fixed;
 use crate::shared::{Span, Type};
 
 /// A compiled LightPlayer Script program
@@ -11,16 +17,39 @@ use crate::shared::{Span, Type};
 /// New structure: All code is organized into functions.
 /// functions[0] is always "main" (the entry point).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LpsProgram {
     pub name: String,
     pub functions: Vec<FunctionDef>,
+    /// Source span of each main-function opcode, indexed by PC
     pub source_map: Option<Vec<Span>>,
     pub source: Option<String>,
+    /// Host-provided values read with `LoadExternal`: a script's `uniform`
+    /// declarations or an expression's env, see `LpsVm::set_uniform`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub externals: Vec<ExternalDef>,
+    /// Host functions called with `CallNative`, see `CompilerOptions::register_native`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub natives: Vec<NativeDef>,
 
     // Legacy fields (deprecated, kept for backward compatibility during migration)
     #[deprecated(note = "Use functions instead")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
     pub opcodes: Vec<LpsOpCode>,
     #[deprecated(note = "Use functions[0].locals instead")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
     pub locals: Vec<LocalDef>,
 }
 
@@ -33,6 +62,8 @@ impl LpsProgram {
             functions: Vec::new(),
             source_map: None,
             source: None,
+            externals: Vec::new(),
+            natives: Vec::new(),
             opcodes: Vec::new(),
             locals: Vec::new(),
         }
@@ -44,6 +75,23 @@ impl LpsProgram {
         self
     }
 
+    /// Declare the externals that `LoadExternal` indexes into
+    pub fn with_externals(mut self, externals: Vec<ExternalDef>) -> Self {
+        self.externals = externals;
+        self
+    }
+
+    /// Index of the external named `name`
+    pub fn external_index(&self, name: &str) -> Option<usize> {
+        self.externals.iter().position(|e| e.name == name)
+    }
+
+    /// Declare the natives that `CallNative` indexes into
+    pub fn with_natives(mut self, natives: Vec<NativeDef>) -> Self {
+        self.natives = natives;
+        self
+    }
+
     /// Get the main function (always at index 0)
     pub fn main_function(&self) -> Option<&FunctionDef> {
         self.functions.first()
@@ -64,6 +112,385 @@ impl LpsProgram {
         self
     }
 
+    /// Link a library's functions into this program
+    ///
+    /// All of `lib`'s functions except its `main` (index 0) are added, with
+    /// their `Call` indices rewritten for their new positions. A function in
+    /// this program with no opcodes is treated as a declaration and is replaced
+    /// by the library function of the same name, so existing calls to it
+    /// resolve; its parameter and return types must match the library's.
+    /// Any other name collision is rejected and leaves `self` unchanged.
+    ///
+    /// The library's externals are shared by name with this program's, with
+    /// new ones appended and `LoadExternal` renumbered to match; an external
+    /// declared with a different type on each side is rejected. Natives are
+    /// merged the same way by name and signature, renumbering `CallNative`.
+    pub fn merge(&mut self, lib: &LpsProgram) -> Result<(), ProgramError> {
+        // Where each library external (by library index) will live
+        let mut new_externals = Vec::new();
+        let mut external_map = Vec::with_capacity(lib.externals.len());
+        for external in &lib.externals {
+            let mut known = self.externals.iter().chain(&new_externals).enumerate();
+            match known.find(|(_, e)| e.name == external.name) {
+                Some((idx, e)) if e == external => external_map.push(idx),
+                Some(_) => return Err(ProgramError::ExternalConflict(external.name.clone())),
+                None => {
+                    external_map.push(self.externals.len() + new_externals.len());
+                    new_externals.push(external.clone());
+                }
+            }
+        }
+
+        // Where each library native (by library index) will live
+        let mut new_natives = Vec::new();
+        let mut native_map = Vec::with_capacity(lib.natives.len());
+        for native in &lib.natives {
+            let mut known = self.natives.iter().chain(&new_natives).enumerate();
+            match known.find(|(_, n)| n.name == native.name) {
+                Some((idx, n)) if n.sig == native.sig => native_map.push(idx),
+                Some(_) => return Err(ProgramError::NativeConflict(native.name.clone())),
+                None => {
+                    native_map.push(self.natives.len() + new_natives.len());
+                    new_natives.push(native.clone());
+                }
+            }
+        }
+
+        // Where each library function (by library index) will live
+        let mut index_map = Vec::with_capacity(lib.functions.len());
+        index_map.push(None); // The library's main is not linked
+        let mut next_index = self.functions.len();
+        for func in lib.functions.iter().skip(1) {
+            match self.functions.iter().position(|f| f.name == func.name) {
+                Some(idx) if self.functions[idx].opcodes.is_empty() => {
+                    if !self.functions[idx].same_signature(func) {
+                        return Err(ProgramError::SignatureMismatch(func.name.clone()));
+                    }
+                    index_map.push(Some(idx))
+                }
+                Some(_) => return Err(ProgramError::DuplicateFunction(func.name.clone())),
+                None => {
+                    index_map.push(Some(next_index));
+                    next_index += 1;
+                }
+            }
+        }
+
+        let mut linked = Vec::with_capacity(lib.functions.len().saturating_sub(1));
+        for func in lib.functions.iter().skip(1) {
+            let mut func = func.clone();
+            for op in func.opcodes.iter_mut() {
+                *op = match *op {
+                    LpsOpCode::Call(target) => {
+                        let new_index = index_map
+                            .get(target as usize)
+                            .copied()
+                            .flatten()
+                            .ok_or_else(|| ProgramError::InvalidCall {
+                                function: func.name.clone(),
+                                target,
+                            })?;
+                        LpsOpCode::Call(new_index as u32)
+                    }
+                    LpsOpCode::LoadExternal(index) => {
+                        let new_index =
+                            external_map.get(index as usize).copied().ok_or_else(|| {
+                                ProgramError::InvalidExternal {
+                                    function: func.name.clone(),
+                                    index,
+                                }
+                            })?;
+                        LpsOpCode::LoadExternal(new_index as u32)
+                    }
+                    LpsOpCode::CallNative(index) => {
+                        let new_index =
+                            native_map.get(index as usize).copied().ok_or_else(|| {
+                                ProgramError::InvalidNative {
+                                    function: func.name.clone(),
+                                    index,
+                                }
+                            })?;
+                        LpsOpCode::CallNative(new_index as u32)
+                    }
+                    _ => continue,
+                };
+            }
+            linked.push(func);
+        }
+
+        for (func, new_index) in linked.into_iter().zip(index_map.into_iter().skip(1)) {
+            match new_index {
+                Some(idx) if idx < self.functions.len() => self.functions[idx] = func,
+                _ => self.functions.push(func),
+            }
+        }
+        self.externals.extend(new_externals);
+        self.natives.extend(new_natives);
+        Ok(())
+    }
+
+    /// Check that the program is runnable
+    ///
+    /// Verifies that `main` exists, every function has a body, and every
+    /// `Call`, jump, `LoadExternal` and `CallNative` targets something inside
+    /// the program.
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.functions.first().map(|f| f.name.as_str()) != Some("main") {
+            return Err(ProgramError::MissingMain);
+        }
+
+        for func in &self.functions {
+            if func.opcodes.is_empty() {
+                return Err(ProgramError::UnresolvedFunction(func.name.clone()));
+            }
+            for (pc, op) in func.opcodes.iter().enumerate() {
+                match *op {
+                    LpsOpCode::LoadExternal(index) if index as usize >= self.externals.len() => {
+                        return Err(ProgramError::InvalidExternal {
+                            function: func.name.clone(),
+                            index,
+                        });
+                    }
+                    LpsOpCode::CallNative(index) if index as usize >= self.natives.len() => {
+                        return Err(ProgramError::InvalidNative {
+                            function: func.name.clone(),
+                            index,
+                        });
+                    }
+                    LpsOpCode::Call(target) if target as usize >= self.functions.len() => {
+                        return Err(ProgramError::InvalidCall {
+                            function: func.name.clone(),
+                            target,
+                        });
+                    }
+                    // Each entry is a `Jump`, checked on its own
+                    LpsOpCode::JumpTable(len) => {
+                        let entries = func.opcodes.get(pc + 1..=pc + 1 + len as usize);
+                        if !entries.is_some_and(|entries| {
+                            entries.iter().all(|op| matches!(op, LpsOpCode::Jump(_)))
+                        }) {
+                            return Err(ProgramError::InvalidJump {
+                                function: func.name.clone(),
+                                pc,
+                            });
+                        }
+                    }
+                    LpsOpCode::Jump(offset)
+                    | LpsOpCode::JumpIfZero(offset)
+                    | LpsOpCode::JumpIfNonZero(offset) => {
+                        let dest = pc as i64 + offset as i64 + 1;
+                        if dest < 0 || dest >= func.opcodes.len() as i64 {
+                            return Err(ProgramError::InvalidJump {
+                                function: func.name.clone(),
+                                pc,
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The value every run returns, if `main` is just `Push(v); Return`
+    ///
+    /// Lets `execute_program_lps` fill a buffer for solid layers without
+    /// dispatching the VM per pixel.
+    pub fn constant_result(&self) -> Option<Fixed> {
+        let main = self.main_function()?;
+        if main.return_type != Type::Fixed {
+            return None;
+        }
+        match main.opcodes.as_slice() {
+            [LpsOpCode::Push(value), LpsOpCode::Return] => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Rough relative cost of one run, e.g. for choosing a render resolution
+    ///
+    /// Sums `LpsOpCode::cost` over every function. Trip counts aren't known
+    /// statically, so code inside a loop counts `LOOP_COST_ITERATIONS` times
+    /// for each loop around it.
+    pub fn estimate_cost(&self) -> u32 {
+        self.functions
+            .iter()
+            .map(|func| opcodes_cost(&func.opcodes))
+            .fold(0, u32::saturating_add)
+    }
+
+    /// Each function's name with the names of the functions it calls
+    ///
+    /// In program order; callees are listed once each, in the order their
+    /// first `Call` appears. Natives aren't included.
+    pub fn call_graph(&self) -> Vec<(String, Vec<String>)> {
+        self.functions
+            .iter()
+            .map(|func| {
+                let mut callees: Vec<String> = Vec::new();
+                for op in &func.opcodes {
+                    if let LpsOpCode::Call(target) = *op {
+                        let name = self
+                            .functions
+                            .get(target as usize)
+                            .map_or_else(|| format!("<invalid {}>", target), |f| f.name.clone());
+                        if !callees.contains(&name) {
+                            callees.push(name);
+                        }
+                    }
+                }
+                (func.name.clone(), callees)
+            })
+            .collect()
+    }
+
+    /// `call_graph` in Graphviz DOT format, e.g. for `dot -Tsvg`
+    pub fn render_call_graph(&self) -> String {
+        let mut dot = format!("digraph {:?} {{\n", self.name);
+        for (caller, callees) in self.call_graph() {
+            dot.push_str(&format!("    {:?};\n", caller));
+            for callee in callees {
+                dot.push_str(&format!("    {:?} -> {:?};\n", caller, callee));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Hash of what the program does, for cache keys and change detection
+    ///
+    /// Covers each function's name, signature, locals and opcodes, plus the
+    /// externals and native signatures, but not the program name, source
+    /// text or source map, so reformatting a script or editing its comments
+    /// leaves the fingerprint unchanged. Stable across platforms and runs,
+    /// though not across lp-script versions that change the opcode set.
+    pub fn fingerprint(&self) -> u64 {
+        use fmt::Write;
+
+        let mut hasher = Fnv1a::new();
+        // Writing to the hasher can't fail
+        for func in &self.functions {
+            let _ = write!(
+                hasher,
+                "fn {:?} {:?} {:?} {:?} {:?};",
+                func.name, func.return_type, func.params, func.locals, func.opcodes
+            );
+        }
+        for external in &self.externals {
+            let _ = write!(hasher, "external {:?} {:?};", external.name, external.ty);
+        }
+        for native in &self.natives {
+            let _ = write!(hasher, "native {:?} {:?};", native.name, native.sig);
+        }
+        hasher.0
+    }
+
+    /// The smallest `VmLimits` that run this program without overflowing
+    ///
+    /// Found by walking every path through `main` and the functions it
+    /// calls: the deepest value stack, the deepest call chain and the most
+    /// local slots live at once. `max_instructions` keeps its default, since
+    /// loop trip counts aren't known statically. Programs the walk can't
+    /// bound (recursion, a stack that differs between paths) get
+    /// `VmLimits::default()`.
+    pub fn required_limits(&self) -> VmLimits {
+        let mut needs = vec![None; self.functions.len()];
+        let mut visiting = vec![false; self.functions.len()];
+        match self.function_needs(0, &mut needs, &mut visiting) {
+            Some(main) => VmLimits {
+                max_call_stack_depth: main.calls,
+                max_stack_size: main.stack,
+                max_locals: main.locals,
+                ..VmLimits::default()
+            },
+            None => VmLimits::default(),
+        }
+    }
+
+    /// Stack, call and local needs of one function, including its callees
+    fn function_needs(
+        &self,
+        idx: usize,
+        needs: &mut Vec<Option<FunctionNeeds>>,
+        visiting: &mut Vec<bool>,
+    ) -> Option<FunctionNeeds> {
+        if let Some(known) = needs.get(idx)?.clone() {
+            return Some(known);
+        }
+        if visiting[idx] {
+            return None; // Recursion has no static bound
+        }
+        visiting[idx] = true;
+
+        let func = &self.functions[idx];
+        let mut result = FunctionNeeds {
+            stack: 0,
+            calls: 0,
+            locals: func.locals.iter().map(|l| l.ty.size_in_i32s()).sum(),
+        };
+        let mut callee_locals = 0;
+
+        // Stack depth on entry to each pc; the arguments are already pushed
+        let entry = func.params.iter().map(|p| p.ty.size_in_i32s()).sum();
+        let mut depths: Vec<Option<usize>> = vec![None; func.opcodes.len()];
+        let mut pending = vec![(0usize, entry)];
+        result.stack = entry;
+
+        while let Some((pc, depth)) = pending.pop() {
+            match depths.get(pc)? {
+                Some(seen) if *seen == depth => continue,
+                Some(_) => return None,
+                None => depths[pc] = Some(depth),
+            }
+
+            let op = &func.opcodes[pc];
+            let (pops, pushes, peak) = match *op {
+                LpsOpCode::Call(target) => {
+                    let callee = self.functions.get(target as usize)?;
+                    let args = callee.params.iter().map(|p| p.ty.size_in_i32s()).sum();
+                    let inner = self.function_needs(target as usize, needs, visiting)?;
+                    result.calls = result.calls.max(inner.calls + 1);
+                    callee_locals = callee_locals.max(inner.locals);
+                    let base = depth.checked_sub(args)?;
+                    (args, callee.return_type.size_in_i32s(), base + inner.stack)
+                }
+                LpsOpCode::CallNative(index) => {
+                    let sig = &self.natives.get(index as usize)?.sig;
+                    let args = sig.params.iter().map(|p| p.size_in_i32s()).sum();
+                    (args, sig.return_type.size_in_i32s(), 0)
+                }
+                LpsOpCode::LoadExternal(index) => {
+                    (0, self.externals.get(index as usize)?.ty.size_in_i32s(), 0)
+                }
+                _ => {
+                    let (pops, pushes) = op.stack_effect()?;
+                    (pops, pushes, 0)
+                }
+            };
+            let after = depth.checked_sub(pops)? + pushes;
+            result.stack = result.stack.max(after).max(peak);
+
+            match *op {
+                LpsOpCode::Return => {}
+                LpsOpCode::Jump(offset) => pending.push((jump_target(pc, offset)?, after)),
+                LpsOpCode::JumpIfZero(offset) | LpsOpCode::JumpIfNonZero(offset) => {
+                    pending.push((jump_target(pc, offset)?, after));
+                    pending.push((pc + 1, after));
+                }
+                LpsOpCode::JumpTable(len) => {
+                    pending.extend((pc + 1..=pc + 1 + len as usize).map(|entry| (entry, after)));
+                }
+                _ => pending.push((pc + 1, after)),
+            }
+        }
+
+        result.locals += callee_locals;
+        visiting[idx] = false;
+        needs[idx] = Some(result.clone());
+        Some(result)
+    }
+
     // Legacy API (deprecated)
     #[deprecated(note = "Use with_functions instead")]
     pub fn with_opcodes(mut self, opcodes: Vec<LpsOpCode>) -> Self {
@@ -84,8 +511,123 @@ impl LpsProgram {
     }
 }
 
+/// What one function needs from `VmLimits`, see `LpsProgram::required_limits`
+#[derive(Debug, Clone)]
+struct FunctionNeeds {
+    stack: usize,
+    calls: usize,
+    locals: usize,
+}
+
+/// Destination of a relative jump at `pc`, if it can be one
+fn jump_target(pc: usize, offset: i32) -> Option<usize> {
+    usize::try_from(pc as i64 + offset as i64 + 1).ok()
+}
+
+/// Iterations assumed for every loop by `LpsProgram::estimate_cost`
+pub const LOOP_COST_ITERATIONS: u32 = 16;
+
+fn opcodes_cost(opcodes: &[LpsOpCode]) -> u32 {
+    // A backward jump at `pc` repeats `target..=pc`
+    let loops = || {
+        opcodes.iter().enumerate().filter_map(|(pc, op)| match *op {
+            LpsOpCode::Jump(offset)
+            | LpsOpCode::JumpIfZero(offset)
+            | LpsOpCode::JumpIfNonZero(offset)
+                if offset < 0 =>
+            {
+                Some(((pc as i64 + offset as i64 + 1).max(0) as usize, pc))
+            }
+            _ => None,
+        })
+    };
+
+    opcodes
+        .iter()
+        .enumerate()
+        .map(|(pc, op)| {
+            loops()
+                .filter(|&(start, end)| (start..=end).contains(&pc))
+                .fold(op.cost(), |cost, _| {
+                    cost.saturating_mul(LOOP_COST_ITERATIONS)
+                })
+        })
+        .fold(0, u32::saturating_add)
+}
+
+/// Structural problems found when linking or validating a program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgramError {
+    /// Function 0 is missing or isn't named `main`
+    MissingMain,
+    /// A function with this name is already defined
+    DuplicateFunction(String),
+    /// A declared function has no body
+    UnresolvedFunction(String),
+    /// A `Call` targets a function index that doesn't exist
+    InvalidCall { function: String, target: u32 },
+    /// A jump lands outside its function, or a `JumpTable` isn't followed
+    /// by its entries
+    InvalidJump { function: String, pc: usize },
+    /// An external with this name is already declared with another type
+    ExternalConflict(String),
+    /// A `LoadExternal` reads an external the program doesn't declare
+    InvalidExternal { function: String, index: u32 },
+    /// A native with this name is already declared with another signature
+    NativeConflict(String),
+    /// A `CallNative` calls a native the program doesn't declare
+    InvalidNative { function: String, index: u32 },
+    /// A declared function and the library function resolving it differ in
+    /// parameter or return types
+    SignatureMismatch(String),
+}
+
+impl fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProgramError::MissingMain => write!(f, "program has no main function"),
+            ProgramError::DuplicateFunction(name) => {
+                write!(f, "function '{}' is already defined", name)
+            }
+            ProgramError::UnresolvedFunction(name) => {
+                write!(f, "function '{}' is declared but has no body", name)
+            }
+            ProgramError::InvalidCall { function, target } => {
+                write!(f, "'{}' calls invalid function index {}", function, target)
+            }
+            ProgramError::InvalidJump { function, pc } => {
+                write!(f, "jump at pc {} in '{}' leaves the function", pc, function)
+            }
+            ProgramError::ExternalConflict(name) => {
+                write!(
+                    f,
+                    "external '{}' is already declared with another type",
+                    name
+                )
+            }
+            ProgramError::InvalidExternal { function, index } => {
+                write!(f, "'{}' reads undeclared external {}", function, index)
+            }
+            ProgramError::NativeConflict(name) => {
+                write!(
+                    f,
+                    "native '{}' is already declared with another signature",
+                    name
+                )
+            }
+            ProgramError::InvalidNative { function, index } => {
+                write!(f, "'{}' calls undeclared native {}", function, index)
+            }
+            ProgramError::SignatureMismatch(name) => {
+                write!(f, "function '{}' is declared with another signature", name)
+            }
+        }
+    }
+}
+
 /// Function parameter definition
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParamDef {
     pub name: String,
     pub ty: Type,
@@ -97,12 +639,27 @@ impl ParamDef {
     }
 }
 
+/// How long a local's value lives
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LocalAccess {
+    /// Re-initialized at the start of every run
+    #[default]
+    Scratch,
+    /// Keeps its value from one run to the next, e.g. an accumulator in a
+    /// stateful effect; see `LpsVm::reset_persistent_locals`
+    Persistent,
+}
+
 /// Local variable definition (types only, for compiled functions)
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LocalVarDef {
     pub name: String,
     pub ty: Type,
     pub initial_value: Option<Vec<i32>>, // Optional initial value (raw i32 representation)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub access: LocalAccess,
 }
 
 impl LocalVarDef {
@@ -111,6 +668,7 @@ impl LocalVarDef {
             name,
             ty,
             initial_value: None,
+            access: LocalAccess::Scratch,
         }
     }
 
@@ -118,13 +676,53 @@ impl LocalVarDef {
         self.initial_value = Some(value);
         self
     }
+
+    pub fn with_access(mut self, access: LocalAccess) -> Self {
+        self.access = access;
+        self
+    }
 }
 
 // Type alias for backward compatibility during migration
 pub type LocalDef = LocalVarDef;
 
+/// A named value supplied by the host rather than computed by the program
+///
+/// E.g. a slider or sensor reading; see `compile_expr_with_env`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExternalDef {
+    pub name: String,
+    pub ty: Type,
+}
+
+impl ExternalDef {
+    pub fn new(name: String, ty: Type) -> Self {
+        ExternalDef { name, ty }
+    }
+}
+
+/// 64-bit FNV-1a, fed through `fmt::Write`
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl fmt::Write for Fnv1a {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+        Ok(())
+    }
+}
+
 /// Compiled function definition
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FunctionDef {
     pub name: String,
     pub return_type: Type,
@@ -144,6 +742,18 @@ impl FunctionDef {
         }
     }
 
+    /// Whether `other` takes the same parameter types and returns the same
+    /// type; parameter names may differ
+    pub fn same_signature(&self, other: &FunctionDef) -> bool {
+        self.return_type == other.return_type
+            && self.params.len() == other.params.len()
+            && self
+                .params
+                .iter()
+                .zip(&other.params)
+                .all(|(a, b)| a.ty == b.ty)
+    }
+
     pub fn with_params(mut self, params: Vec<ParamDef>) -> Self {
         self.params = params;
         self
@@ -159,3 +769,436 @@ impl FunctionDef {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::compile_script;
+    use crate::vm::{LpsVm, VmLimits};
+
+    #[test]
+    fn test_merge_resolves_declared_function() {
+        let lib = compile_script("float sq(float x) { return x * x; } return 0.0;").unwrap();
+        let mut program = compile_script("float sq(float x); return sq(3.0);").unwrap();
+        assert_eq!(
+            program.validate(),
+            Err(ProgramError::UnresolvedFunction("sq".into()))
+        );
+
+        program.merge(&lib).unwrap();
+        program.validate().unwrap();
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let result = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result, Fixed::from_i32(9));
+    }
+
+    #[test]
+    fn test_merge_fixes_library_call_indices() {
+        // `quad` calls `sq`; both move to new indices in the merged program
+        let lib = compile_script(
+            "float sq(float x) { return x * x; } \
+             float quad(float x) { return sq(sq(x)); } \
+             return 0.0;",
+        )
+        .unwrap();
+        let mut program = compile_script(
+            "float unused() { return 0.0; } \
+             float quad(float x); \
+             return quad(3.0) + unused();",
+        )
+        .unwrap();
+
+        program.merge(&lib).unwrap();
+        program.validate().unwrap();
+        assert_eq!(program.functions.len(), 4);
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let result = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result, Fixed::from_i32(81));
+    }
+
+    #[test]
+    fn test_merge_rejects_name_collision() {
+        let lib = compile_script("float sq(float x) { return x * x; } return 0.0;").unwrap();
+        let mut program =
+            compile_script("float sq(float x) { return x; } return sq(2.0);").unwrap();
+        let before = program.functions.len();
+
+        assert_eq!(
+            program.merge(&lib),
+            Err(ProgramError::DuplicateFunction("sq".into()))
+        );
+        assert_eq!(program.functions.len(), before);
+    }
+
+    #[test]
+    fn test_merge_rejects_signature_mismatch() {
+        let lib = compile_script("vec3 f(float x) { return vec3(x); } return 0.0;").unwrap();
+        for src in [
+            "float f(float x); return f(1.0);",
+            "vec3 f(vec2 v); return f(vec2(1.0)).x;",
+            "vec3 f(float x, float y); return f(1.0, 2.0).x;",
+        ] {
+            let mut program = compile_script(src).unwrap();
+            let before = program.functions.len();
+            assert_eq!(
+                program.merge(&lib),
+                Err(ProgramError::SignatureMismatch("f".into())),
+                "{}",
+                src
+            );
+            assert_eq!(program.functions.len(), before);
+            assert!(program.functions[1].opcodes.is_empty());
+        }
+
+        // Parameter names don't matter
+        let mut program = compile_script("vec3 f(float t); return f(2.0).y;").unwrap();
+        program.merge(&lib).unwrap();
+        program.validate().unwrap();
+    }
+
+    #[test]
+    fn test_merge_links_library_uniforms() {
+        use crate::vm::InputValue;
+
+        let lib = compile_script(
+            "uniform float gain; \
+             float amp(float v) { return v * gain; } \
+             return 0.0;",
+        )
+        .unwrap();
+        let mut program =
+            compile_script("uniform float offset; float amp(float v); return amp(2.0) + offset;")
+                .unwrap();
+
+        program.merge(&lib).unwrap();
+        program.validate().unwrap();
+        assert_eq!(program.external_index("offset"), Some(0));
+        assert_eq!(program.external_index("gain"), Some(1));
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        vm.set_uniform("gain", InputValue::Fixed(Fixed::from_i32(3)))
+            .unwrap();
+        vm.set_uniform("offset", InputValue::Fixed(Fixed::ONE))
+            .unwrap();
+        let result = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result, Fixed::from_i32(7));
+    }
+
+    #[test]
+    fn test_merge_shares_matching_uniforms() {
+        let lib = compile_script(
+            "uniform float gain; \
+             float amp(float v) { return v * gain; } \
+             return 0.0;",
+        )
+        .unwrap();
+        let mut program =
+            compile_script("uniform float gain; float amp(float v); return amp(2.0) + gain;")
+                .unwrap();
+        program.merge(&lib).unwrap();
+        assert_eq!(program.externals.len(), 1);
+
+        // Same name, different type
+        let mut program =
+            compile_script("uniform vec2 gain; float amp(float v); return amp(gain.x);").unwrap();
+        let before = program.functions[1].opcodes.len();
+        assert_eq!(
+            program.merge(&lib),
+            Err(ProgramError::ExternalConflict("gain".into()))
+        );
+        assert_eq!(program.externals.len(), 1);
+        assert_eq!(program.functions[1].opcodes.len(), before);
+    }
+
+    #[test]
+    fn test_merge_links_library_natives() {
+        use crate::vm::native::NativeSig;
+        use crate::vm::InputValue;
+        use crate::{compile_script_with_compiler_options, CompilerOptions};
+
+        let scale = |factor: i32| {
+            move |args: &[InputValue]| match args {
+                [InputValue::Fixed(x)] => InputValue::Fixed(*x * Fixed::from_i32(factor)),
+                _ => InputValue::Fixed(Fixed::ZERO),
+            }
+        };
+        let sig = NativeSig::new(&[Type::Fixed], Type::Fixed);
+
+        let mut lib_options = CompilerOptions::default();
+        lib_options.register_native("double", sig.clone(), scale(2));
+        let lib = compile_script_with_compiler_options(
+            "float amp(float v) { return double(v); } return 0.0;",
+            &lib_options,
+        )
+        .unwrap();
+
+        let mut host_options = CompilerOptions::default();
+        host_options.register_native("triple", sig.clone(), scale(3));
+        let mut program = compile_script_with_compiler_options(
+            "float amp(float v); return amp(2.0) + triple(1.0);",
+            &host_options,
+        )
+        .unwrap();
+
+        program.merge(&lib).unwrap();
+        program.validate().unwrap();
+        let names: Vec<&str> = program.natives.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["triple", "double"]);
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let result = vm
+            .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap();
+        assert_eq!(result, Fixed::from_i32(7));
+
+        // Same name, different signature
+        let mut conflicting = CompilerOptions::default();
+        conflicting.register_native(
+            "double",
+            NativeSig::new(&[Type::Int32], Type::Fixed),
+            |_args| InputValue::Fixed(Fixed::ZERO),
+        );
+        let mut program = compile_script_with_compiler_options(
+            "float amp(float v); return amp(double(1));",
+            &conflicting,
+        )
+        .unwrap();
+        assert_eq!(
+            program.merge(&lib),
+            Err(ProgramError::NativeConflict("double".into()))
+        );
+        assert_eq!(program.natives.len(), 1);
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        use crate::compile_expr;
+
+        let arithmetic = compile_expr("uv.x * 2.0 + uv.y * 3.0 - time").unwrap();
+        let noise = compile_expr("perlin3(vec3(uv * 4.0, time), 4) + uv.x").unwrap();
+        assert!(noise.estimate_cost() > arithmetic.estimate_cost() * 10);
+
+        // The loop body counts once per assumed iteration
+        let straight = compile_script("float s = 0.0; s += sin(uv.x); return s;").unwrap();
+        let looped = compile_script(
+            "float s = 0.0; for (int i = 0; i < 4; i++) { s += sin(uv.x); } return s;",
+        )
+        .unwrap();
+        assert!(looped.estimate_cost() >= straight.estimate_cost() * LOOP_COST_ITERATIONS / 2);
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_formatting() {
+        let compact =
+            compile_script("float f(float x){return x*2.0;} return f(uv.x)+0.0;").unwrap();
+        let spaced = compile_script(
+            "
+            // Doubles its argument
+            float f(float x) {
+                return x * 2.0;
+            }
+
+            return f(uv.x) + 0.0; // the + 0.0 folds away
+        ",
+        )
+        .unwrap();
+        assert_eq!(compact.fingerprint(), spaced.fingerprint());
+        assert_eq!(compact.fingerprint(), compact.clone().fingerprint());
+
+        let changed = compile_script("float f(float x){return x*3.0;} return f(uv.x);").unwrap();
+        assert_ne!(compact.fingerprint(), changed.fingerprint());
+    }
+
+    #[test]
+    fn test_call_graph() {
+        let script = "
+            float b(float x) { return x * 2.0; }
+            float a(float x) { return b(x) + b(x + 1.0); }
+            return a(uv.x);
+        ";
+        let program = compile_script(script).unwrap();
+        let graph = program.call_graph();
+        let edges: Vec<(&str, Vec<&str>)> = graph
+            .iter()
+            .map(|(caller, callees)| {
+                (
+                    caller.as_str(),
+                    callees.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            edges,
+            vec![("main", vec!["a"]), ("b", vec![]), ("a", vec!["b"])]
+        );
+
+        let dot = program.render_call_graph();
+        assert!(dot.starts_with("digraph "), "{}", dot);
+        assert!(dot.contains("    \"main\" -> \"a\";\n"), "{}", dot);
+        assert!(dot.contains("    \"a\" -> \"b\";\n"), "{}", dot);
+        assert!(dot.contains("    \"b\";\n"), "{}", dot);
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_required_limits() {
+        use crate::compile_expr;
+        use crate::fixed::ToFixed;
+
+        let simple = compile_expr("uv.x + time").unwrap().required_limits();
+        assert_eq!(simple.max_stack_size, 2);
+        assert_eq!(simple.max_call_stack_depth, 0);
+        assert_eq!(simple.max_locals, 0);
+
+        // Each pending left operand stays on the stack while the right nests
+        let nested = compile_expr("uv.x + (uv.y * (time - (uv.x * (uv.y + (time * uv.x)))))")
+            .unwrap()
+            .required_limits();
+        assert!(nested.max_stack_size > simple.max_stack_size + 3);
+
+        let script = "
+            float sq(float x) { return x * x; }
+            float quad(float x) { float s = sq(x); return sq(s); }
+            vec3 v = vec3(uv, quad(time));
+            return v.x + v.y + v.z;
+        ";
+        let program = compile_script(script).unwrap();
+        let limits = program.required_limits();
+        assert_eq!(limits.max_call_stack_depth, 2);
+        assert_eq!(limits.max_locals, 3 + 2 + 1);
+
+        // Exactly-fitting limits still run the program
+        let mut vm = LpsVm::new(&program, limits).unwrap();
+        let result = vm
+            .run(0.5.to_fixed(), 0.25.to_fixed(), 2.0.to_fixed())
+            .unwrap();
+        assert_eq!(result, vec![(0.5 + 0.25 + 16.0).to_fixed()]);
+
+        // Recursion has no static bound
+        let recursive = compile_script(
+            "float f(float n) { if (n < 1.0) { return 0.0; } return f(n - 1.0); } return f(3.0);",
+        )
+        .unwrap();
+        assert_eq!(recursive.required_limits().max_stack_size, 256);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_call() {
+        let main = FunctionDef::new("main".into(), Type::Fixed)
+            .with_opcodes(vec![LpsOpCode::Call(5), LpsOpCode::Return]);
+        let program = LpsProgram::new("bad".into()).with_functions(vec![main]);
+        assert_eq!(
+            program.validate(),
+            Err(ProgramError::InvalidCall {
+                function: "main".into(),
+                target: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_checks_jump_table_entries() {
+        let table = |entries: Vec<LpsOpCode>| {
+            let mut opcodes = vec![
+                LpsOpCode::PushInt32(1),
+                LpsOpCode::PushInt32(0),
+                LpsOpCode::JumpTable(1),
+            ];
+            opcodes.extend(entries);
+            opcodes.extend([LpsOpCode::Push(Fixed::ONE), LpsOpCode::Return]);
+            let main = FunctionDef::new("main".into(), Type::Fixed).with_opcodes(opcodes);
+            LpsProgram::new("table".into()).with_functions(vec![main])
+        };
+
+        let program = table(vec![LpsOpCode::Jump(1), LpsOpCode::Jump(0)]);
+        program.validate().unwrap();
+        assert_eq!(program.required_limits().max_stack_size, 2);
+
+        // Missing the out-of-range entry
+        assert_eq!(
+            table(vec![LpsOpCode::Jump(0)]).validate(),
+            Err(ProgramError::InvalidJump {
+                function: "main".into(),
+                pc: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_constant_result() {
+        use crate::compile_expr;
+
+        let program = compile_expr("0.25 + 0.25").unwrap();
+        assert_eq!(program.constant_result(), Some(Fixed::HALF));
+        assert_eq!(compile_expr("uv.x").unwrap().constant_result(), None);
+        assert_eq!(compile_expr("sin(time)").unwrap().constant_result(), None);
+    }
+
+    #[test]
+    fn test_execute_constant_program_skips_vm() {
+        use crate::vm::{execute_program_lps, CoordConfig};
+
+        // More locals than `LpsVm::new` reserves, so only the fast path can
+        // fill the buffer without panicking
+        let locals = vec![LocalVarDef::new("m".into(), Type::Mat3); 300];
+        let main = FunctionDef::new("main".into(), Type::Fixed)
+            .with_locals(locals)
+            .with_opcodes(vec![LpsOpCode::Push(Fixed::HALF), LpsOpCode::Return]);
+        let program = LpsProgram::new("solid".into()).with_functions(vec![main]);
+        assert!(LpsVm::new(&program, VmLimits::default()).is_err());
+
+        let (width, height) = (8, 4);
+        let mut output = vec![Fixed::ZERO; width * height];
+        execute_program_lps(
+            &program,
+            &mut output,
+            width,
+            height,
+            Fixed::ZERO,
+            &CoordConfig::default(),
+        );
+        assert!(output.iter().all(|&v| v == Fixed::HALF));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_json_round_trip() {
+        let program = compile_script(
+            "float wave(float t) { return sin(t * 0.1) + 2.5; } \
+             vec2 p = uv * 3.0; \
+             int n = 7; \
+             return wave(time) * p.x - p.y / 3.0 + float(n);",
+        )
+        .unwrap();
+
+        let json = serde_json::to_string(&program).unwrap();
+        assert!(json.contains(r#"{"Push":"2.5"}"#));
+        let restored: LpsProgram = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+        for (a, b) in program.functions.iter().zip(&restored.functions) {
+            assert_eq!(a.opcodes, b.opcodes);
+        }
+        restored.validate().unwrap();
+
+        let (x, y, time) = (Fixed::from_f32(0.25), Fixed::HALF, Fixed::from_i32(4));
+        let expected = LpsVm::new(&program, VmLimits::default())
+            .unwrap()
+            .run_scalar(x, y, time)
+            .unwrap();
+        let actual = LpsVm::new(&restored, VmLimits::default())
+            .unwrap()
+            .run_scalar(x, y, time)
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+}
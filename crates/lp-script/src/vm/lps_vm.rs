@@ -1,12 +1,25 @@
+use alloc::boxed::Box;
 use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
 
-use crate::fixed::{Fixed, Mat3, Vec2, Vec3, Vec4};
+use crate::fixed::{Fixed, Mat3, NoiseBackend, Vec2, Vec3, Vec4};
+use crate::shared::Span;
+use crate::vm::clock::Clock;
+use crate::vm::normalize_fallback::NormalizeFallback;
+use crate::vm::opcodes::LpsOpCode;
+use crate::vm::stack_value::{InputValue, OutputValue};
+use crate::vm::texture::{Texture, MAX_TEXTURES};
 use crate::vm::vm_limits::VmLimits;
 use crate::vm::{CallStack, ValueStack};
 use crate::{LocalStack, LpsProgram, LpsVmError, RuntimeErrorWithContext};
 
+/// Clock and callback installed with `LpsVm::set_clock`
+pub(in crate::vm) struct RunTimer<'a> {
+    clock: &'a dyn Clock,
+    on_run: Box<dyn FnMut(u64) + 'a>,
+}
+
 /// LightPlayer Script Virtual Machine
 ///
 /// Executes compiled LPS programs. Designed to be reusable - create once,
@@ -19,15 +32,27 @@ pub struct LpsVm<'a> {
     pub(in crate::vm) call_stack: CallStack,
     pub(in crate::vm) limits: VmLimits,
     pub(in crate::vm) current_fn_idx: usize, // Track which function we're executing
+    pub(in crate::vm) index: i32,            // Linear LED/pixel index for the `index` built-in
+    pub(in crate::vm) noise_backend: NoiseBackend, // Implementation used by `Perlin3`
+    pub(in crate::vm) palette: &'a [Vec3],   // Stops sampled by the `palette` built-in
+    pub(in crate::vm) textures: [Option<Texture<'a>>; MAX_TEXTURES], // Images for `mixTex`
+    pub(in crate::vm) normalize_fallback: Option<NormalizeFallback>, // Short-vector `normalize`
+    pub(in crate::vm) center: Vec2,          // Normalized center for the radial built-ins
+    pub(in crate::vm) time_ms: Option<u64>,  // Host clock wrapped by the `phase` built-in
+    pub(in crate::vm) pending_inputs: usize, // Stack slots pushed by the host for the next run
+    pub(in crate::vm) overflow_check: bool,  // Checked scalar arithmetic
+    pub(in crate::vm) eq_epsilon: Fixed,     // Tolerance of scalar `==`/`!=`, 0 for exact
+    pub(in crate::vm) externals: Vec<Option<InputValue>>, // Host values, by `program.externals` index
+    pub(in crate::vm) clock: Option<RunTimer<'a>>,        // Profiling hook, see `set_clock`
+    #[cfg(feature = "vm-trace")]
+    pub(in crate::vm) trace: crate::vm::OpcodeTrace, // Recently executed opcodes
 }
 
 impl<'a> LpsVm<'a> {
     /// Create a new VM from a program with custom limits
     pub fn new(program: &'a LpsProgram, limits: VmLimits) -> Result<Self, LpsVmError> {
         // Pre-allocate locals storage for frame-based allocation
-        // Estimate: 32 i32s per frame * 64 max frames = 2048 i32s
-        let local_capacity = 32 * limits.max_call_stack_depth;
-        let mut locals = LocalStack::try_new(local_capacity)?;
+        let mut locals = LocalStack::try_new(limits.max_locals)?;
 
         // Allocate main function's locals (function 0)
         if let Some(main_fn) = program.main_function() {
@@ -42,6 +67,20 @@ impl<'a> LpsVm<'a> {
             call_stack: CallStack::try_new(limits.max_call_stack_depth)?,
             limits,
             current_fn_idx: 0, // Start in main
+            index: 0,
+            noise_backend: NoiseBackend::default(),
+            palette: &[],
+            textures: [None; MAX_TEXTURES],
+            normalize_fallback: None,
+            center: Vec2::new(Fixed::HALF, Fixed::HALF),
+            time_ms: None,
+            pending_inputs: 0,
+            overflow_check: false,
+            eq_epsilon: Fixed::ZERO,
+            externals: alloc::vec![None; program.externals.len()],
+            clock: None,
+            #[cfg(feature = "vm-trace")]
+            trace: crate::vm::OpcodeTrace::new(),
         })
     }
 
@@ -60,6 +99,156 @@ impl<'a> LpsVm<'a> {
         self.locals.get_fixed_by_name(name)
     }
 
+    /// Set the linear LED/pixel index seen by the `index` built-in on later runs
+    pub fn set_index(&mut self, index: usize) {
+        self.index = index as i32;
+    }
+
+    /// Bind the palette sampled by the `palette(t)` built-in
+    ///
+    /// The stops form a 1xN texture, evenly spaced over `t` in 0..1.
+    pub fn set_palette(&mut self, stops: &'a [Vec3]) {
+        self.palette = stops;
+    }
+
+    /// Bind `texture` to slot `slot` (0..`MAX_TEXTURES`) for the texture
+    /// built-ins, or unbind it with `None`
+    ///
+    /// # Panics
+    /// Panics if `slot` is out of range.
+    pub fn set_texture(&mut self, slot: usize, texture: Option<Texture<'a>>) {
+        assert!(slot < MAX_TEXTURES, "texture slot {} out of range", slot);
+        self.textures[slot] = texture;
+    }
+
+    /// Select the `perlin3` implementation, trading speed for accuracy
+    pub fn set_noise_backend(&mut self, backend: NoiseBackend) {
+        self.noise_backend = backend;
+    }
+
+    /// Make `normalize` return a fixed result for (near) zero-length vectors
+    ///
+    /// `None` (the default) divides by the length as-is, which only special
+    /// cases an exact zero length.
+    pub fn set_normalize_fallback(&mut self, fallback: Option<NormalizeFallback>) {
+        self.normalize_fallback = fallback;
+    }
+
+    /// Time every run with `clock`, passing the ticks each one took to `on_run`
+    ///
+    /// Works without `std`, so firmware can profile with whatever timer it
+    /// has; on the host, use `StdClock`. Replaces any earlier clock.
+    pub fn set_clock(&mut self, clock: &'a dyn Clock, on_run: impl FnMut(u64) + 'a) {
+        self.clock = Some(RunTimer {
+            clock,
+            on_run: Box::new(on_run),
+        });
+    }
+
+    /// Stop timing runs
+    pub fn clear_clock(&mut self) {
+        self.clock = None;
+    }
+
+    /// Move the center used by `centerDist`, `centerAngle`, `polar` and
+    /// `fromPolar`, in normalized coordinates (default `(0.5, 0.5)`)
+    pub fn set_center(&mut self, center: Vec2) {
+        self.center = center;
+    }
+
+    /// Give `phase(period)` the host's clock in milliseconds
+    ///
+    /// `time` is a 16.16 `Fixed` and runs out of range after about nine
+    /// hours; `phase` wraps this 64-bit clock instead, so periodic animation
+    /// stays smooth for any uptime. Until this is called `phase` wraps `time`.
+    pub fn set_time_ms(&mut self, time_ms: u64) {
+        self.time_ms = Some(time_ms);
+    }
+
+    /// Fail runs whose scalar arithmetic overflows
+    ///
+    /// With this on, the fixed and int add/sub/mul/div/neg opcodes use
+    /// checked arithmetic and return `ArithmeticOverflow` instead of a
+    /// wrapped value. Off by default, since it costs a branch per opcode.
+    pub fn set_overflow_check(&mut self, enabled: bool) {
+        self.overflow_check = enabled;
+    }
+
+    /// Make scalar `==` and `!=` compare within `epsilon`
+    ///
+    /// With a non-zero epsilon, `a == b` is true when `|a - b| <= epsilon`
+    /// and `!=` is its negation, so values computed along different paths
+    /// can still compare equal. The default 0 keeps comparisons exact.
+    /// Vector equality stays exact, and comparisons between constants are
+    /// folded exactly at compile time.
+    pub fn set_eq_epsilon(&mut self, epsilon: Fixed) {
+        self.eq_epsilon = epsilon;
+    }
+
+    /// Set the value read by the program's external `name` on later runs
+    ///
+    /// Fails if the program declares no such external or `value` doesn't
+    /// match its declared type. Externals stay set across runs.
+    pub fn set_external(&mut self, name: &str, value: InputValue) -> Result<(), LpsVmError> {
+        let idx = self
+            .program
+            .external_index(name)
+            .ok_or_else(|| LpsVmError::UnknownExternal(String::from(name)))?;
+        if !value.matches(&self.program.externals[idx].ty) {
+            return Err(LpsVmError::TypeMismatch);
+        }
+        self.externals[idx] = Some(value);
+        Ok(())
+    }
+
+    /// Set script uniform `name`, declared with `uniform type name;`
+    ///
+    /// Uniforms are the program's externals, so this is `set_external`
+    /// under the name scripts use; the same errors apply.
+    pub fn set_uniform(&mut self, name: &str, value: InputValue) -> Result<(), LpsVmError> {
+        self.set_external(name, value)
+    }
+
+    /// Set the main function's persistent locals back to their initial values
+    ///
+    /// For restarting a stateful effect, e.g. on a scene switch, without
+    /// rebuilding the VM. Scratch locals are re-initialized by every run anyway.
+    pub fn reset_persistent_locals(&mut self) {
+        if let Some(main_fn) = self.program.main_function() {
+            self.locals.reset_persistent(&main_fn.locals);
+        }
+    }
+
+    /// Push a value for the next run to find on its stack
+    ///
+    /// Lets host code pass arguments beyond `run`'s fixed (x, y, time): the
+    /// program's main function starts with the inputs on the stack, in push
+    /// order, and consumes them like any other operands. Results left over
+    /// from a previous run are discarded by the first push.
+    pub fn push_input(&mut self, value: InputValue) -> Result<(), LpsVmError> {
+        if self.pending_inputs == 0 {
+            self.stack.reset();
+        }
+        value.push_to(&mut self.stack)?;
+        self.pending_inputs += value.slots();
+        Ok(())
+    }
+
+    /// Pop one result of the main function's return type from the stack
+    ///
+    /// Returns `None` once fewer values remain than the type needs (or for
+    /// `void` programs). Call repeatedly to pop several results of that type.
+    pub fn pop_result(&mut self) -> Option<OutputValue> {
+        let ty = &self.program.main_function()?.return_type;
+        OutputValue::pop_from(ty, &mut self.stack)
+    }
+
+    /// Opcodes executed by the most recent run, oldest first
+    #[cfg(feature = "vm-trace")]
+    pub fn trace(&self) -> &crate::vm::OpcodeTrace {
+        &self.trace
+    }
+
     /// Execute the program with full coordinate information
     ///
     /// Accepts both normalized and pixel coordinates for complete builtin variable support.
@@ -74,7 +263,15 @@ impl<'a> LpsVm<'a> {
         width: usize,
         height: usize,
     ) -> Result<Vec<Fixed>, RuntimeErrorWithContext> {
-        self.run_impl(x_norm, y_norm, x_int, y_int, time, width, height)
+        let Some(start) = self.clock.as_ref().map(|timer| timer.clock.ticks()) else {
+            return self.run_impl(x_norm, y_norm, x_int, y_int, time, width, height);
+        };
+        let result = self.run_impl(x_norm, y_norm, x_int, y_int, time, width, height);
+        if let Some(timer) = self.clock.as_mut() {
+            let elapsed = timer.clock.ticks().saturating_sub(start);
+            (timer.on_run)(elapsed);
+        }
+        result
     }
 
     /// Execute the program for a single pixel (normalized coords only)
@@ -114,10 +311,16 @@ impl<'a> LpsVm<'a> {
         width: usize,
         height: usize,
     ) -> Result<Vec<Fixed>, RuntimeErrorWithContext> {
-        self.stack.reset();
+        // Keep host inputs from `push_input`; they belong to this run only
+        if self.pending_inputs == 0 {
+            self.stack.reset();
+        }
+        self.pending_inputs = 0;
         self.pc = 0;
         self.call_stack.reset(0);
         self.current_fn_idx = 0; // Reset to main
+        #[cfg(feature = "vm-trace")]
+        self.trace.reset();
 
         // Reset locals to main function's state
         if let Some(main_fn) = self.program.main_function() {
@@ -137,6 +340,7 @@ impl<'a> LpsVm<'a> {
                     error: LpsVmError::InstructionLimitExceeded,
                     pc: self.pc,
                     opcode: "LIMIT_EXCEEDED",
+                    span: self.span_at_pc(),
                 });
             }
 
@@ -151,6 +355,7 @@ impl<'a> LpsVm<'a> {
                         },
                         pc: self.pc,
                         opcode: "EOF",
+                        span: None,
                     });
                 }
                 &func.opcodes[self.pc]
@@ -165,12 +370,17 @@ impl<'a> LpsVm<'a> {
                         },
                         pc: self.pc,
                         opcode: "EOF",
+                        span: None,
                     });
                 }
                 #[allow(deprecated)]
                 &self.program.opcodes[self.pc]
             };
 
+            #[cfg(feature = "vm-trace")]
+            self.trace
+                .record(self.current_fn_idx, self.pc, opcode.name());
+
             // Dispatch the opcode - returns Some(result) if program should exit
             if let Some(result) =
                 self.dispatch_opcode(opcode, x_norm, y_norm, x_int, y_int, time, width, height)?
@@ -184,10 +394,31 @@ impl<'a> LpsVm<'a> {
         RuntimeErrorWithContext {
             error,
             pc: self.pc,
-            opcode: "opcode", // TODO: Get actual opcode name
+            opcode: self.current_opcode().map_or("opcode", LpsOpCode::name),
+            span: self.span_at_pc(),
         }
     }
 
+    /// The opcode at the current PC, if any
+    fn current_opcode(&self) -> Option<&LpsOpCode> {
+        if let Some(func) = self.program.function(self.current_fn_idx) {
+            func.opcodes.get(self.pc)
+        } else {
+            #[allow(deprecated)]
+            self.program.opcodes.get(self.pc)
+        }
+    }
+
+    /// Source span for the current PC
+    ///
+    /// The source map covers the main function only, indexed by PC.
+    fn span_at_pc(&self) -> Option<Span> {
+        if self.current_fn_idx != 0 {
+            return None;
+        }
+        self.program.source_map.as_ref()?.get(self.pc).copied()
+    }
+
     /// Get the length of the current function's opcodes (helper for bounds checking)
     pub(in crate::vm) fn current_function_len(&self) -> usize {
         if let Some(func) = self.program.function(self.current_fn_idx) {
@@ -218,6 +449,7 @@ impl<'a> LpsVm<'a> {
                 error: LpsVmError::TypeMismatch,
                 pc: self.pc,
                 opcode: "run_scalar",
+                span: None,
             });
         }
         Ok(stack[0])
@@ -236,6 +468,7 @@ impl<'a> LpsVm<'a> {
                 error: LpsVmError::TypeMismatch,
                 pc: self.pc,
                 opcode: "run_scalar",
+                span: None,
             });
         }
         Ok(stack[0])
@@ -254,6 +487,7 @@ impl<'a> LpsVm<'a> {
                 error: LpsVmError::TypeMismatch,
                 pc: self.pc,
                 opcode: "run_vec2",
+                span: None,
             });
         }
         Ok(Vec2::new(stack[0], stack[1]))
@@ -272,6 +506,7 @@ impl<'a> LpsVm<'a> {
                 error: LpsVmError::TypeMismatch,
                 pc: self.pc,
                 opcode: "run_vec3",
+                span: None,
             });
         }
         Ok(Vec3::new(stack[0], stack[1], stack[2]))
@@ -290,6 +525,7 @@ impl<'a> LpsVm<'a> {
                 error: LpsVmError::TypeMismatch,
                 pc: self.pc,
                 opcode: "run_vec4",
+                span: None,
             });
         }
         Ok(Vec4::new(stack[0], stack[1], stack[2], stack[3]))
@@ -308,6 +544,7 @@ impl<'a> LpsVm<'a> {
                 error: LpsVmError::TypeMismatch,
                 pc: self.pc,
                 opcode: "run_mat3",
+                span: None,
             });
         }
         Ok(Mat3::new(
@@ -316,6 +553,59 @@ impl<'a> LpsVm<'a> {
         ))
     }
 
+    /// Execute the program at arbitrary pixel-space positions, expecting scalar results
+    ///
+    /// Points are in the same space as `coord` (e.g. `LedMap::pos`); `uv` is derived
+    /// by dividing by `width`/`height`. This evaluates sparse positions directly
+    /// instead of rendering a full grid and resampling it.
+    /// The `index` built-in is each point's position in `points`.
+    pub fn run_at_points(
+        &mut self,
+        points: &[Vec2],
+        time: Fixed,
+        width: usize,
+        height: usize,
+    ) -> Result<Vec<Fixed>, RuntimeErrorWithContext> {
+        let mut results = Vec::with_capacity(points.len());
+        for (i, point) in points.iter().enumerate() {
+            self.set_index(i);
+            let (x_norm, y_norm) = normalize_point(*point, width, height);
+            results.push(
+                self.run_scalar_with_coords(x_norm, y_norm, point.x, point.y, time, width, height)?,
+            );
+        }
+        Ok(results)
+    }
+
+    /// Execute the program at arbitrary pixel-space positions, expecting vec3 results
+    ///
+    /// See `run_at_points()` for the coordinate conventions.
+    pub fn run_at_points_vec3(
+        &mut self,
+        points: &[Vec2],
+        time: Fixed,
+        width: usize,
+        height: usize,
+    ) -> Result<Vec<Vec3>, RuntimeErrorWithContext> {
+        let mut results = Vec::with_capacity(points.len());
+        for (i, point) in points.iter().enumerate() {
+            self.set_index(i);
+            let (x_norm, y_norm) = normalize_point(*point, width, height);
+            let stack =
+                self.run_with_coords(x_norm, y_norm, point.x, point.y, time, width, height)?;
+            if stack.len() != 3 {
+                return Err(RuntimeErrorWithContext {
+                    error: LpsVmError::TypeMismatch,
+                    pc: self.pc,
+                    opcode: "run_at_points_vec3",
+                    span: None,
+                });
+            }
+            results.push(Vec3::new(stack[0], stack[1], stack[2]));
+        }
+        Ok(results)
+    }
+
     /// Format a runtime error with full context
     pub fn format_error(&self, error: &RuntimeErrorWithContext) -> String {
         let mut output = format!("{}\n", error);
@@ -331,21 +621,29 @@ impl<'a> LpsVm<'a> {
                 if i > start {
                     output.push_str(", ");
                 }
-                let value = Fixed(self.stack.raw_slice()[i]).to_f32();
+                let value = Fixed(self.stack.raw_slice()[i]);
                 output.push_str(&format!("{}", value));
             }
             output.push_str("]\n");
         }
 
         // Show source if available
-        if let (Some(source), Some(source_map)) = (&self.program.source, &self.program.source_map) {
-            if error.pc < source_map.len() {
-                let span = source_map[error.pc];
-                let end = span.end.min(source.len());
-                if span.start < end {
-                    let snippet = &source[span.start..end];
-                    output.push_str(&format!("  source: {}\n", snippet));
-                }
+        if let (Some(source), Some(span)) = (&self.program.source, error.span) {
+            let end = span.end.min(source.len());
+            if span.start < end {
+                let snippet = &source[span.start..end];
+                output.push_str(&format!("  source: {}\n", snippet));
+            }
+        }
+
+        #[cfg(feature = "vm-trace")]
+        if !self.trace.is_empty() {
+            output.push_str("  trace (oldest first):\n");
+            for entry in self.trace.iter() {
+                output.push_str(&format!(
+                    "    fn {} PC {}: {}\n",
+                    entry.fn_idx, entry.pc, entry.opcode
+                ));
             }
         }
 
@@ -353,6 +651,15 @@ impl<'a> LpsVm<'a> {
     }
 }
 
+/// Convert a pixel-space point to normalized (0..1) coordinates
+#[inline(always)]
+fn normalize_point(point: Vec2, width: usize, height: usize) -> (Fixed, Fixed) {
+    (
+        point.x / Fixed::from_i32(width as i32),
+        point.y / Fixed::from_i32(height as i32),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,4 +677,496 @@ mod tests {
         assert_eq!(vm.call_stack.depth(), 0);
         assert_eq!(vm.call_stack.frame_base(), 0);
     }
+
+    /// Bilinearly sample a row-major grid whose samples sit at pixel centers
+    fn sample_grid_bilinear(grid: &[Fixed], width: usize, height: usize, p: Vec2) -> Fixed {
+        let gx = (p.x - Fixed::HALF).max(Fixed::ZERO);
+        let gy = (p.y - Fixed::HALF).max(Fixed::ZERO);
+        let x0 = (gx.to_i32() as usize).min(width - 1);
+        let y0 = (gy.to_i32() as usize).min(height - 1);
+        let x1 = (x0 + 1).min(width - 1);
+        let y1 = (y0 + 1).min(height - 1);
+        let fx = gx.frac();
+        let fy = gy.frac();
+
+        let top = grid[y0 * width + x0] + (grid[y0 * width + x1] - grid[y0 * width + x0]) * fx;
+        let bottom = grid[y1 * width + x0] + (grid[y1 * width + x1] - grid[y1 * width + x0]) * fx;
+        top + (bottom - top) * fy
+    }
+
+    #[test]
+    fn test_run_at_points_matches_grid_bilinear() {
+        use alloc::vec;
+
+        use crate::parse_expr;
+        use crate::vm::{execute_program_lps, CoordConfig};
+
+        let program = parse_expr("sin(uv.x * 2.0) * 0.5 + uv.y * 0.25");
+        let (width, height) = (16, 8);
+
+        let mut grid = vec![Fixed::ZERO; width * height];
+        execute_program_lps(
+            &program,
+            &mut grid,
+            width,
+            height,
+            Fixed::ZERO,
+            &CoordConfig::default(),
+        );
+
+        let points = [
+            Vec2::new(Fixed::from_f32(1.5), Fixed::from_f32(0.5)),
+            Vec2::new(Fixed::from_f32(3.25), Fixed::from_f32(2.75)),
+            Vec2::new(Fixed::from_f32(7.9), Fixed::from_f32(4.1)),
+            Vec2::new(Fixed::from_f32(12.6), Fixed::from_f32(6.3)),
+        ];
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let direct = vm
+            .run_at_points(&points, Fixed::ZERO, width, height)
+            .unwrap();
+        assert_eq!(direct.len(), points.len());
+
+        for (point, value) in points.iter().zip(direct.iter()) {
+            let resampled = sample_grid_bilinear(&grid, width, height, *point);
+            let diff = (value.to_f32() - resampled.to_f32()).abs();
+            assert!(
+                diff < 0.01,
+                "point ({}, {}): direct {} vs bilinear {}",
+                point.x.to_f32(),
+                point.y.to_f32(),
+                value.to_f32(),
+                resampled.to_f32()
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_at_points_vec3() {
+        use crate::parse_expr;
+
+        let program = parse_expr("vec3(uv, coord.x)");
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let points = [Vec2::new(Fixed::from_i32(2), Fixed::from_i32(1))];
+
+        let results = vm.run_at_points_vec3(&points, Fixed::ZERO, 4, 4).unwrap();
+        assert_eq!(results[0].x, Fixed::HALF);
+        assert_eq!(results[0].y, Fixed::from_f32(0.25));
+        assert_eq!(results[0].z, Fixed::from_i32(2));
+    }
+
+    #[test]
+    fn test_run_at_points_vec3_rejects_scalar_program() {
+        use crate::parse_expr;
+
+        let program = parse_expr("uv.x");
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let points = [Vec2::new(Fixed::ONE, Fixed::ONE)];
+
+        let err = vm
+            .run_at_points_vec3(&points, Fixed::ZERO, 4, 4)
+            .unwrap_err();
+        assert!(matches!(err.error, LpsVmError::TypeMismatch));
+    }
+
+    #[test]
+    fn test_runtime_error_reports_opcode_and_span() {
+        use alloc::string::ToString;
+        use alloc::vec;
+
+        use crate::shared::{Span, Type};
+        use crate::vm::{FunctionDef, LpsOpCode};
+
+        // "7 / 0" with each opcode mapped back to its source range
+        let main = FunctionDef::new("main".into(), Type::Int32).with_opcodes(vec![
+            LpsOpCode::PushInt32(7),
+            LpsOpCode::PushInt32(0),
+            LpsOpCode::DivInt32,
+            LpsOpCode::Return,
+        ]);
+        let program = LpsProgram::new("div".into())
+            .with_functions(vec![main])
+            .with_source("7 / 0".to_string())
+            .with_source_map(vec![
+                Span::new(0, 1),
+                Span::new(4, 5),
+                Span::new(0, 5),
+                Span::new(0, 5),
+            ]);
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let err = vm.run(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).unwrap_err();
+
+        assert!(matches!(err.error, LpsVmError::DivisionByZero));
+        assert_eq!(err.pc, 2);
+        assert_eq!(err.opcode, "DivInt32");
+        assert_eq!(err.span, Some(Span::new(0, 5)));
+        assert!(vm.format_error(&err).contains("source: 7 / 0"));
+    }
+
+    #[test]
+    fn test_runtime_error_without_source_map_has_no_span() {
+        use crate::compile_script;
+
+        let program = compile_script("int a = 7; int b = 0; return a / b;").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let err = vm.run(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).unwrap_err();
+        assert_eq!(err.opcode, "DivInt32");
+        assert_eq!(err.span, None);
+    }
+
+    #[test]
+    fn test_palette_builtin_samples_bound_stops() {
+        use crate::compile_expr;
+        use crate::fixed::ToFixed;
+
+        let red = Vec3::from_f32(1.0, 0.0, 0.0);
+        let green = Vec3::from_f32(0.0, 1.0, 0.0);
+        let blue = Vec3::from_f32(0.0, 0.0, 1.0);
+        let stops = [red, green, blue];
+
+        let program = compile_expr("palette(x)").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        vm.set_palette(&stops);
+
+        let sample = |vm: &mut LpsVm, t: f32| vm.run_vec3(t.to_fixed(), Fixed::ZERO, Fixed::ZERO);
+        assert_eq!(sample(&mut vm, 0.0).unwrap(), red);
+        assert_eq!(sample(&mut vm, 0.5).unwrap(), green);
+        assert_eq!(sample(&mut vm, 1.0).unwrap(), blue);
+        assert_eq!(
+            sample(&mut vm, 0.25).unwrap(),
+            Vec3::from_f32(0.5, 0.5, 0.0)
+        );
+        // Out-of-range t clamps to the end stops
+        assert_eq!(sample(&mut vm, -1.0).unwrap(), red);
+        assert_eq!(sample(&mut vm, 2.0).unwrap(), blue);
+    }
+
+    #[test]
+    fn test_phase_stays_smooth_at_long_uptime() {
+        use crate::compile_expr;
+
+        // 100 hours: past the range of a 16.16 `time`, and an f32 seconds
+        // clock has a 1/32 s step here, so 60 fps frames would stall
+        let start_ms: u64 = 100 * 60 * 60 * 1000;
+        assert_eq!(start_ms as f32 / 1000.0, (start_ms + 16) as f32 / 1000.0);
+
+        let program = compile_expr("phase(2.0)").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let mut previous: Option<Fixed> = None;
+        for frame in 0..10 {
+            vm.set_time_ms(start_ms + frame * 16);
+            let phase = vm
+                .run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+                .unwrap();
+            assert!(phase >= Fixed::ZERO && phase < Fixed::from_i32(2));
+            if let Some(previous) = previous {
+                let step = (phase - previous).to_f32();
+                assert!((step - 0.016).abs() < 0.0001, "step {}", step);
+            }
+            previous = Some(phase);
+        }
+    }
+
+    #[test]
+    fn test_overflow_check_reports_mul_fixed_overflow() {
+        use alloc::vec;
+
+        use crate::shared::Type;
+        use crate::vm::{FunctionDef, LpsOpCode};
+
+        // 200 * 200 is past Fixed's +-32768 range
+        let main = FunctionDef::new("main".into(), Type::Fixed).with_opcodes(vec![
+            LpsOpCode::Push(Fixed::from_i32(200)),
+            LpsOpCode::Push(Fixed::from_i32(200)),
+            LpsOpCode::MulFixed,
+            LpsOpCode::Return,
+        ]);
+        let program = LpsProgram::new("overflow".into()).with_functions(vec![main]);
+
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        assert!(vm.run(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).is_ok());
+
+        vm.set_overflow_check(true);
+        let err = vm.run(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).unwrap_err();
+        assert!(matches!(err.error, LpsVmError::ArithmeticOverflow));
+        assert_eq!(err.pc, 2);
+        assert_eq!(err.opcode, "MulFixed");
+    }
+
+    #[test]
+    fn test_eq_epsilon() {
+        use crate::compile_expr;
+        use crate::fixed::ToFixed;
+
+        // 0.1 * 3 lands one step below 0.3 in 16.16 fixed point
+        let program = compile_expr("x * 3.0 == 0.3").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let run = |vm: &mut LpsVm| {
+            vm.run_scalar(0.1.to_fixed(), Fixed::ZERO, Fixed::ZERO)
+                .unwrap()
+        };
+        assert_eq!(run(&mut vm), Fixed::ZERO);
+
+        vm.set_eq_epsilon(0.001.to_fixed());
+        assert_eq!(run(&mut vm), Fixed::ONE);
+
+        let program = compile_expr("x * 3.0 != 0.3").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        assert_eq!(run(&mut vm), Fixed::ONE);
+        vm.set_eq_epsilon(0.001.to_fixed());
+        assert_eq!(run(&mut vm), Fixed::ZERO);
+    }
+
+    #[test]
+    fn test_reset_persistent_locals() {
+        use alloc::vec;
+
+        use crate::shared::Type;
+        use crate::vm::{FunctionDef, LocalAccess, LocalVarDef, LpsOpCode};
+
+        // acc += 1.0; scratch += 1.0; return acc + scratch * 100;
+        let main = FunctionDef::new("main".into(), Type::Fixed)
+            .with_locals(vec![
+                LocalVarDef::new("acc".into(), Type::Fixed)
+                    .with_initial_value(vec![Fixed::from_i32(10).0])
+                    .with_access(LocalAccess::Persistent),
+                LocalVarDef::new("scratch".into(), Type::Fixed),
+            ])
+            .with_opcodes(vec![
+                LpsOpCode::LoadLocalFixed(0),
+                LpsOpCode::Push(Fixed::ONE),
+                LpsOpCode::AddFixed,
+                LpsOpCode::StoreLocalFixed(0),
+                LpsOpCode::LoadLocalFixed(1),
+                LpsOpCode::Push(Fixed::ONE),
+                LpsOpCode::AddFixed,
+                LpsOpCode::StoreLocalFixed(1),
+                LpsOpCode::LoadLocalFixed(0),
+                LpsOpCode::LoadLocalFixed(1),
+                LpsOpCode::Push(Fixed::from_i32(100)),
+                LpsOpCode::MulFixed,
+                LpsOpCode::AddFixed,
+                LpsOpCode::Return,
+            ]);
+        let program = LpsProgram::new("accumulate".into()).with_functions(vec![main]);
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let run = |vm: &mut LpsVm| {
+            vm.run_scalar(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+                .unwrap()
+                .to_i32()
+        };
+
+        assert_eq!(run(&mut vm), 111);
+        assert_eq!(run(&mut vm), 112);
+        assert_eq!(run(&mut vm), 113);
+
+        vm.reset_persistent_locals();
+        assert_eq!(vm.locals().get_fixed(0).unwrap(), Fixed::from_i32(10));
+        assert_eq!(run(&mut vm), 111);
+        assert_eq!(run(&mut vm), 112);
+    }
+
+    #[test]
+    fn test_set_center_moves_radial_builtins() {
+        use crate::compile_expr;
+        use crate::fixed::ToFixed;
+
+        let program = compile_expr("centerDist").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let dist_at = |vm: &mut LpsVm, x: i32, y: i32| {
+            vm.run_scalar_with_coords(
+                Fixed::ZERO,
+                Fixed::ZERO,
+                Fixed::from_i32(x),
+                Fixed::from_i32(y),
+                Fixed::ZERO,
+                16,
+                8,
+            )
+            .unwrap()
+        };
+        assert_eq!(dist_at(&mut vm, 8, 4), Fixed::ZERO);
+
+        vm.set_center(Vec2::new(0.25.to_fixed(), 0.5.to_fixed()));
+        assert_eq!(dist_at(&mut vm, 4, 4), Fixed::ZERO);
+        assert!(dist_at(&mut vm, 8, 4) > Fixed::ZERO);
+
+        // `polar` measures its radius from the same center
+        let program = compile_expr("polar.x").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        vm.set_center(Vec2::new(0.25.to_fixed(), 0.5.to_fixed()));
+        let radius = vm
+            .run_scalar(0.25.to_fixed(), 0.5.to_fixed(), Fixed::ZERO)
+            .unwrap();
+        assert_eq!(radius, Fixed::ZERO);
+    }
+
+    #[test]
+    fn test_push_inputs_and_pop_result() {
+        use alloc::vec;
+
+        use crate::shared::Type;
+        use crate::vm::{FunctionDef, InputValue, OutputValue};
+
+        // main(a: vec2, b: vec2) -> vec2 { return a + b * 2.0 }, taking its
+        // arguments from the stack
+        let main = FunctionDef::new("main".into(), Type::Vec2).with_opcodes(vec![
+            LpsOpCode::Push(Fixed::from_i32(2)),
+            LpsOpCode::MulVec2Scalar,
+            LpsOpCode::AddVec2,
+            LpsOpCode::Return,
+        ]);
+        let program = LpsProgram::new("host".into()).with_functions(vec![main]);
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+
+        let a = Vec2::new(Fixed::ONE, Fixed::from_i32(2));
+        let b = Vec2::new(Fixed::HALF, Fixed::from_i32(-1));
+        vm.push_input(InputValue::Vec2(a)).unwrap();
+        vm.push_input(InputValue::Vec2(b)).unwrap();
+        vm.run(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).unwrap();
+
+        assert_eq!(
+            vm.pop_result(),
+            Some(OutputValue::Vec2(Vec2::new(
+                Fixed::from_i32(2),
+                Fixed::ZERO
+            )))
+        );
+        assert_eq!(vm.pop_result(), None);
+
+        // Inputs are consumed by the run they were pushed for
+        let err = vm.run(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).unwrap_err();
+        assert!(matches!(err.error, LpsVmError::StackUnderflow { .. }));
+    }
+
+    #[test]
+    fn test_palette_builtin_without_palette_errors() {
+        use crate::compile_expr;
+
+        let program = compile_expr("palette(0.5)").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let err = vm
+            .run_vec3(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap_err();
+        assert!(matches!(err.error, LpsVmError::PaletteNotBound));
+        assert_eq!(err.opcode, "PaletteSample");
+    }
+
+    #[test]
+    fn test_mix_tex_crossfades_bound_textures() {
+        use crate::compile_expr;
+        use crate::fixed::Vec4;
+        use crate::vm::texture::Texture;
+
+        let red = [Vec4::from_f32(1.0, 0.0, 0.0, 1.0); 4];
+        let blue = [Vec4::from_f32(0.0, 0.0, 1.0, 1.0); 4];
+
+        let program = compile_expr("mixTex(0, 1, uv, x)").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        vm.set_texture(0, Some(Texture::new(2, 2, &red)));
+        vm.set_texture(1, Some(Texture::new(2, 2, &blue)));
+
+        let sample = |vm: &mut LpsVm, t: f32| {
+            vm.run_vec4(Fixed::from_f32(t), Fixed::ZERO, Fixed::ZERO)
+                .unwrap()
+        };
+        assert_eq!(sample(&mut vm, 0.0), red[0]);
+        assert_eq!(sample(&mut vm, 1.0), blue[0]);
+        assert_eq!(sample(&mut vm, 0.5), Vec4::from_f32(0.5, 0.0, 0.5, 1.0));
+    }
+
+    #[test]
+    fn test_mix_tex_unbound_slot_errors() {
+        use crate::compile_expr;
+        use crate::fixed::Vec4;
+        use crate::vm::texture::Texture;
+
+        let red = [Vec4::from_f32(1.0, 0.0, 0.0, 1.0)];
+        let program = compile_expr("mixTex(0, 2, uv, 0.5)").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        vm.set_texture(0, Some(Texture::new(1, 1, &red)));
+
+        let err = vm
+            .run_vec4(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO)
+            .unwrap_err();
+        assert!(matches!(
+            err.error,
+            LpsVmError::UnboundTexture { texture_idx: 2 }
+        ));
+        assert_eq!(err.opcode, "TextureMixRGBA");
+    }
+
+    #[test]
+    fn test_noise_backend_selects_perlin3_implementation() {
+        use crate::compile_expr;
+        use crate::fixed::noise::{perlin3, perlin3_accurate};
+
+        let program = compile_expr("perlin3(vec3(x * 4.0, y * 4.0, time), 3)").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let (x, y, t) = (
+            Fixed::from_f32(0.3),
+            Fixed::from_f32(0.7),
+            Fixed::from_f32(1.1),
+        );
+        let (nx, ny) = (x * Fixed::from_i32(4), y * Fixed::from_i32(4));
+
+        assert_eq!(vm.run_scalar(x, y, t).unwrap(), perlin3(nx, ny, t, 3));
+
+        vm.set_noise_backend(NoiseBackend::FixedAccurate);
+        assert_eq!(
+            vm.run_scalar(x, y, t).unwrap(),
+            perlin3_accurate(nx, ny, t, 3)
+        );
+    }
+
+    #[test]
+    fn test_normalize_fallback_on_zero_vector() {
+        use crate::compile_expr;
+        use crate::fixed::Vec4;
+        use crate::vm::NormalizeFallback;
+
+        let program = compile_expr("normalize(vec3(x, y, 0.0))").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let tiny = Fixed(300);
+
+        // Default: exact zero gives zero, tiny lengths lose precision
+        let zero = vm.run_vec3(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).unwrap();
+        assert_eq!(zero, Vec3::zero());
+        let imprecise = vm.run_vec3(tiny, Fixed::ZERO, Fixed::ZERO).unwrap();
+        assert!(imprecise.x > Fixed::from_f32(1.1));
+
+        let up = Vec4::new(Fixed::ZERO, Fixed::ONE, Fixed::ZERO, Fixed::ZERO);
+        vm.set_normalize_fallback(Some(NormalizeFallback::direction(up)));
+        let expected = Vec3::new(Fixed::ZERO, Fixed::ONE, Fixed::ZERO);
+        assert_eq!(
+            vm.run_vec3(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).unwrap(),
+            expected
+        );
+        assert_eq!(vm.run_vec3(tiny, tiny, Fixed::ZERO).unwrap(), expected);
+
+        // Long vectors are unaffected
+        let one = vm.run_vec3(Fixed::ONE, Fixed::ZERO, Fixed::ZERO).unwrap();
+        assert_eq!(one, Vec3::new(Fixed::ONE, Fixed::ZERO, Fixed::ZERO));
+    }
+
+    #[cfg(feature = "vm-trace")]
+    #[test]
+    fn test_runtime_error_includes_opcode_trace() {
+        use crate::compile_script;
+
+        let program = compile_script("int a = 7; int b = 0; return a / b;").unwrap();
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        let err = vm.run(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).unwrap_err();
+
+        let output = vm.format_error(&err);
+        assert!(output.contains("trace (oldest first):"));
+        // The opcodes leading up to the failing division, ending with it
+        let trace: Vec<&str> = vm.trace().iter().map(|e| e.opcode).collect();
+        assert_eq!(trace.last(), Some(&"DivInt32"));
+        assert!(trace.contains(&"LoadLocalInt32"));
+        for name in &trace {
+            assert!(output.contains(name));
+        }
+    }
 }
@@ -1,20 +1,43 @@
+pub mod batch;
 pub mod call_stack;
+pub mod clock;
+pub mod coord_config;
 pub mod error;
 pub mod local_stack;
 pub mod lps_program;
 pub mod lps_vm;
+pub mod native;
+pub mod normalize_fallback;
 /// Virtual Machine for LightPlayer Script
 pub mod opcodes;
+pub mod stack_value;
+pub mod texture;
+#[cfg(feature = "vm-trace")]
+pub mod trace;
 pub mod value_stack;
 pub mod vm_dispatch;
 pub mod vm_limits;
 
+pub use batch::{execute_program_lps_batched, BATCH_LANES};
 pub use call_stack::{CallFrame, CallStack};
+pub use clock::Clock;
+#[cfg(feature = "std")]
+pub use clock::StdClock;
+pub use coord_config::{CoordConfig, Origin};
 pub use error::{LpsVmError, RuntimeErrorWithContext};
 pub use local_stack::LocalStack;
-pub use lps_program::{FunctionDef, LocalVarDef, LpsProgram, ParamDef};
+pub use lps_program::{
+    ExternalDef, FunctionDef, LocalAccess, LocalVarDef, LpsProgram, ParamDef, ProgramError,
+    LOOP_COST_ITERATIONS,
+};
 pub use lps_vm::LpsVm;
-pub use opcodes::LpsOpCode;
+pub use native::{NativeDef, NativeFn, NativeSig, MAX_NATIVE_PARAMS};
+pub use normalize_fallback::NormalizeFallback;
+pub use opcodes::{LpsOpCode, OpcodeGroup, OpcodeSet};
+pub use stack_value::{InputValue, OutputValue};
+pub use texture::{Texture, MAX_TEXTURES};
+#[cfg(feature = "vm-trace")]
+pub use trace::{OpcodeTrace, TraceEntry};
 pub use value_stack::ValueStack;
 pub use vm_limits::VmLimits;
 
@@ -23,7 +46,8 @@ use crate::fixed::Fixed;
 /// Execute a program on all pixels in the buffer
 ///
 /// This is the main entry point for executing LPS programs on pixel buffers.
-/// It creates a VM instance once and reuses it for all pixels.
+/// It creates a VM instance once and reuses it for all pixels; programs that
+/// return a constant skip the VM and fill the buffer directly.
 ///
 /// # Arguments
 /// * `program` - Compiled LPS program to execute
@@ -31,6 +55,7 @@ use crate::fixed::Fixed;
 /// * `width` - Width of the image
 /// * `height` - Height of the image
 /// * `time` - Time value in 16.16 fixed-point format
+/// * `coords` - How `uv`/`coord` are derived from pixel positions
 ///
 /// # Panics
 /// Panics if the program encounters a runtime error. In production, you may want
@@ -42,32 +67,28 @@ pub fn execute_program_lps(
     width: usize,
     height: usize,
     time: Fixed,
+    coords: &CoordConfig,
 ) {
+    if let Some(value) = program.constant_result() {
+        let len = output.len().min(width * height);
+        output[..len].fill(value);
+        return;
+    }
+
     // CRITICAL: Create VM once and reuse it for all pixels to avoid cloning the program
     // Cloning the program for each pixel causes catastrophic memory usage!
     let mut vm = LpsVm::new(program, VmLimits::default()).expect("Failed to create VM");
 
     for y in 0..height {
         for x in 0..width {
-            // Calculate normalized coordinates (0..1 range)
-            // Add 0.5 to center pixels (x + 0.5, y + 0.5)
+            // Calculate normalized (0..1 range) and pixel coordinates
             // Use fixed-point arithmetic throughout to avoid float fixed
-            let x_plus_half = Fixed::from_i32(x as i32) + Fixed::HALF;
-            let x_norm = x_plus_half / Fixed::from_i32(width as i32);
-            let y_plus_half = Fixed::from_i32(y as i32) + Fixed::HALF;
-            let y_norm = y_plus_half / Fixed::from_i32(height as i32);
+            let (x_norm, y_norm, x_px, y_px) = coords.pixel_coords(x, y, width, height);
+            vm.set_index(y * width + x);
 
             // Pass both normalized AND pixel coordinates
             let result = vm
-                .run_scalar_with_coords(
-                    x_norm,
-                    y_norm,
-                    x_plus_half,
-                    y_plus_half,
-                    time,
-                    width,
-                    height,
-                )
+                .run_scalar_with_coords(x_norm, y_norm, x_px, y_px, time, width, height)
                 .unwrap_or_else(|e| {
                     panic!("Runtime error at pixel ({}, {}): {}", x, y, e);
                 });
@@ -80,6 +101,26 @@ pub fn execute_program_lps(
     }
 }
 
+/// `execute_program_lps`, reporting the ticks the frame took to `on_frame`
+///
+/// For profiling on devices without `std::time`: `clock` can be any
+/// monotonic counter the firmware has.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_program_lps_timed(
+    program: &LpsProgram,
+    output: &mut [Fixed],
+    width: usize,
+    height: usize,
+    time: Fixed,
+    coords: &CoordConfig,
+    clock: &dyn Clock,
+    mut on_frame: impl FnMut(u64),
+) {
+    let start = clock.ticks();
+    execute_program_lps(program, output, width, height, time, coords);
+    on_frame(clock.ticks().saturating_sub(start));
+}
+
 /// Execute a program that returns Vec3 (RGB) for each pixel
 /// Output buffer should be sized width * height * 3 (r, g, b values)
 pub fn execute_program_lps_vec3(
@@ -88,31 +129,62 @@ pub fn execute_program_lps_vec3(
     width: usize,
     height: usize,
     time: Fixed,
+    coords: &CoordConfig,
+) {
+    for_each_vec3_pixel(program, width, height, time, coords, |idx, r, g, b| {
+        if let Some(rgb) = output.get_mut(idx * 3..idx * 3 + 3) {
+            rgb.copy_from_slice(&[r, g, b]);
+        }
+    });
+}
+
+/// Execute a program that returns Vec3 (RGB) and write 8-bit channels
+///
+/// Same as `execute_program_lps_vec3` followed by `Fixed::to_u8_saturating`
+/// on every channel, without the intermediate `Fixed` buffer. Output buffer
+/// should be sized width * height * 3.
+pub fn execute_program_lps_rgb8(
+    program: &LpsProgram,
+    output: &mut [u8],
+    width: usize,
+    height: usize,
+    time: Fixed,
+    coords: &CoordConfig,
+) {
+    for_each_vec3_pixel(program, width, height, time, coords, |idx, r, g, b| {
+        if let Some(rgb) = output.get_mut(idx * 3..idx * 3 + 3) {
+            rgb.copy_from_slice(&[
+                r.to_u8_saturating(),
+                g.to_u8_saturating(),
+                b.to_u8_saturating(),
+            ]);
+        }
+    });
+}
+
+/// Run a Vec3 program for every pixel, passing its linear index and (r, g, b)
+fn for_each_vec3_pixel(
+    program: &LpsProgram,
+    width: usize,
+    height: usize,
+    time: Fixed,
+    coords: &CoordConfig,
+    mut write: impl FnMut(usize, Fixed, Fixed, Fixed),
 ) {
     // Create VM once and reuse it for all pixels
     let mut vm = LpsVm::new(program, VmLimits::default()).expect("Failed to create VM");
 
     for y in 0..height {
         for x in 0..width {
-            // Calculate normalized coordinates
-            let x_plus_half = Fixed::from_i32(x as i32) + Fixed::HALF;
-            let x_norm = x_plus_half / Fixed::from_i32(width as i32);
-            let y_plus_half = Fixed::from_i32(y as i32) + Fixed::HALF;
-            let y_norm = y_plus_half / Fixed::from_i32(height as i32);
+            // Calculate normalized and pixel coordinates
+            let (x_norm, y_norm, x_px, y_px) = coords.pixel_coords(x, y, width, height);
+            vm.set_index(y * width + x);
 
             // Run program - it should return 3 values on stack for Vec3
-            vm.run_with_coords(
-                x_norm,
-                y_norm,
-                x_plus_half,
-                y_plus_half,
-                time,
-                width,
-                height,
-            )
-            .unwrap_or_else(|e| {
-                panic!("Runtime error at pixel ({}, {}): {}", x, y, e);
-            });
+            vm.run_with_coords(x_norm, y_norm, x_px, y_px, time, width, height)
+                .unwrap_or_else(|e| {
+                    panic!("Runtime error at pixel ({}, {}): {}", x, y, e);
+                });
 
             // Pop 3 values from stack (b, g, r in reverse order)
             let b = vm
@@ -128,12 +200,89 @@ pub fn execute_program_lps_vec3(
                 .pop_fixed()
                 .expect("Vec3 should have red component");
 
-            let idx = (y * width + x) * 3;
-            if idx + 2 < output.len() {
-                output[idx] = r;
-                output[idx + 1] = g;
-                output[idx + 2] = b;
-            }
+            write(y * width + x, r, g, b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::compile_expr;
+
+    #[test]
+    fn test_rgb8_matches_vec3_then_convert() {
+        // Channels below 0, inside 0..1 and above 1
+        let program = compile_expr("vec3(uv.x * 2.0 - 0.5, uv.y, 1.0 - uv.x * 3.0)").unwrap();
+        let (width, height) = (8, 4);
+        let time = Fixed::ZERO;
+        let coords = CoordConfig::default();
+
+        let mut fixed = vec![Fixed::ZERO; width * height * 3];
+        execute_program_lps_vec3(&program, &mut fixed, width, height, time, &coords);
+        let expected: alloc::vec::Vec<u8> = fixed.iter().map(|c| c.to_u8_saturating()).collect();
+
+        let mut rgb8 = vec![0u8; width * height * 3];
+        execute_program_lps_rgb8(&program, &mut rgb8, width, height, time, &coords);
+
+        assert_eq!(rgb8, expected);
+        assert!(rgb8.contains(&0) && rgb8.contains(&255));
+    }
+
+    #[test]
+    fn test_clock_hooks_time_runs_and_frames() {
+        use core::cell::{Cell, RefCell};
+
+        let program = compile_expr("sin(uv.x * 3.0) + uv.y").unwrap();
+        let (width, height) = (4, 3);
+        let coords = CoordConfig::default();
+
+        // Readings always go up, by one more tick each time
+        let now = Cell::new(0u64);
+        let clock = || {
+            now.set(now.get() + 1);
+            now.get()
+        };
+
+        let deltas = RefCell::new(vec![]);
+        let mut vm = LpsVm::new(&program, VmLimits::default()).unwrap();
+        vm.set_clock(&clock, |ticks| deltas.borrow_mut().push(ticks));
+        for x in 0..width {
+            let x_norm = Fixed::from_i32(x as i32) / Fixed::from_i32(width as i32);
+            vm.run(x_norm, Fixed::HALF, Fixed::ZERO).unwrap();
+        }
+        vm.clear_clock();
+        vm.run(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO).unwrap();
+        drop(vm);
+        assert_eq!(*deltas.borrow(), vec![1; width]);
+
+        let start = now.get();
+        let mut frames = vec![];
+        let mut output = vec![Fixed::ZERO; width * height];
+        for _ in 0..2 {
+            execute_program_lps_timed(
+                &program,
+                &mut output,
+                width,
+                height,
+                Fixed::ZERO,
+                &coords,
+                &clock,
+                |ticks| frames.push(ticks),
+            );
+        }
+        assert_eq!(frames, vec![1, 1]);
+        assert_eq!(now.get(), start + 4);
+
+        // The host clock never runs backwards across a render
+        #[cfg(feature = "std")]
+        {
+            let host = StdClock::new();
+            let before = host.ticks();
+            execute_program_lps(&program, &mut output, width, height, Fixed::ZERO, &coords);
+            assert!(host.ticks() >= before);
         }
     }
 }
@@ -0,0 +1,86 @@
+/// Host functions callable from LPS scripts through `CallNative`
+extern crate alloc;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::shared::Type;
+use crate::vm::stack_value::InputValue;
+
+/// Most parameters a native function may declare
+pub const MAX_NATIVE_PARAMS: usize = 8;
+
+/// Signature every native implementation shares
+type NativeImpl = dyn Fn(&[InputValue]) -> InputValue;
+
+/// Host implementation of a native function
+///
+/// Called with one value per declared parameter (`bool` as `Int32`); the
+/// result must match the declared return type.
+#[derive(Clone)]
+pub struct NativeFn(Rc<NativeImpl>);
+
+impl NativeFn {
+    pub fn new(func: impl Fn(&[InputValue]) -> InputValue + 'static) -> Self {
+        NativeFn(Rc::new(func))
+    }
+
+    pub fn call(&self, args: &[InputValue]) -> InputValue {
+        (self.0)(args)
+    }
+}
+
+impl fmt::Debug for NativeFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NativeFn(..)")
+    }
+}
+
+/// Parameter and return types of a native function
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NativeSig {
+    pub params: Vec<Type>,
+    pub return_type: Type,
+}
+
+impl NativeSig {
+    /// # Panics
+    /// Panics with more than `MAX_NATIVE_PARAMS` parameters.
+    pub fn new(params: &[Type], return_type: Type) -> Self {
+        assert!(
+            params.len() <= MAX_NATIVE_PARAMS,
+            "native functions take at most {} parameters",
+            MAX_NATIVE_PARAMS
+        );
+        NativeSig {
+            params: params.to_vec(),
+            return_type,
+        }
+    }
+}
+
+/// A host function the program calls by index with `CallNative`
+///
+/// See `CompilerOptions::register_native`. The implementation isn't
+/// serialized: a deserialized program fails with `NativeNotBound` when it
+/// reaches the call.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NativeDef {
+    pub name: String,
+    pub sig: NativeSig,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub func: Option<NativeFn>,
+}
+
+impl NativeDef {
+    pub fn new(name: String, sig: NativeSig, func: NativeFn) -> Self {
+        NativeDef {
+            name,
+            sig,
+            func: Some(func),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+/// Fallback for `normalize` on (near) zero-length vectors
+use crate::fixed::{Fixed, Vec2, Vec3, Vec4};
+
+/// What `Normalize2/3/4` produce when the input is too short to normalize
+///
+/// Without a fallback, squaring tiny components underflows in 16.16, so short
+/// vectors come out as zero or noticeably off unit length (e.g. 1.17). With
+/// one, any input shorter than `epsilon` yields `direction` (truncated to the
+/// vector's size) instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalizeFallback {
+    /// Inputs with a length below this use the fallback
+    pub epsilon: Fixed,
+    /// Result for short inputs; Vec2/Vec3 use the leading components
+    pub direction: Vec4,
+}
+
+impl NormalizeFallback {
+    /// Below this length, the result can be more than ~1% off unit length
+    pub const DEFAULT_EPSILON: Fixed = Fixed(1 << 10); // 1/64
+
+    /// Return the zero vector for short inputs
+    pub const fn zero() -> Self {
+        Self::direction(Vec4::zero())
+    }
+
+    /// Return `direction` for short inputs
+    pub const fn direction(direction: Vec4) -> Self {
+        NormalizeFallback {
+            epsilon: Self::DEFAULT_EPSILON,
+            direction,
+        }
+    }
+
+    /// Set the length threshold
+    pub const fn with_epsilon(mut self, epsilon: Fixed) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    #[inline(always)]
+    pub fn normalize2(&self, v: Vec2) -> Vec2 {
+        if v.length() < self.epsilon {
+            Vec2::new(self.direction.x, self.direction.y)
+        } else {
+            v.normalize()
+        }
+    }
+
+    #[inline(always)]
+    pub fn normalize3(&self, v: Vec3) -> Vec3 {
+        if v.length() < self.epsilon {
+            Vec3::new(self.direction.x, self.direction.y, self.direction.z)
+        } else {
+            v.normalize()
+        }
+    }
+
+    #[inline(always)]
+    pub fn normalize4(&self, v: Vec4) -> Vec4 {
+        if v.length() < self.epsilon {
+            self.direction
+        } else {
+            v.normalize()
+        }
+    }
+}
+
+impl Default for NormalizeFallback {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_vectors_use_fallback() {
+        let fallback = NormalizeFallback::direction(Vec4::new(
+            Fixed::ONE,
+            Fixed::ZERO,
+            Fixed::ZERO,
+            Fixed::ZERO,
+        ));
+        let tiny = Vec3::new(Fixed(3), Fixed(0), Fixed(-2));
+        assert_eq!(
+            fallback.normalize3(tiny),
+            Vec3::new(Fixed::ONE, Fixed::ZERO, Fixed::ZERO)
+        );
+        assert_eq!(
+            NormalizeFallback::zero().normalize2(Vec2::new(Fixed::ZERO, Fixed::ZERO)),
+            Vec2::new(Fixed::ZERO, Fixed::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_long_vectors_normalize_normally() {
+        let v = Vec2::new(Fixed::from_i32(3), Fixed::from_i32(4));
+        assert_eq!(NormalizeFallback::zero().normalize2(v), v.normalize());
+    }
+}
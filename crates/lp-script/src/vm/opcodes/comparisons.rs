@@ -59,6 +59,87 @@ pub fn exec_not_eq_fixed(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     Ok(())
 }
 
+/// Execute EqFixed with a tolerance: pop b, a; push (|a - b| <= epsilon ? 1.0 : 0.0)
+#[inline(always)]
+pub fn exec_eq_fixed_within(stack: &mut ValueStack, epsilon: Fixed) -> Result<(), LpsVmError> {
+    let (a, b) = stack.pop2()?;
+    let result = if within(a, b, epsilon) { FIXED_ONE } else { 0 };
+    stack.push_int32(result)?;
+    Ok(())
+}
+
+/// Execute NotEqFixed with a tolerance: pop b, a; push (|a - b| > epsilon ? 1.0 : 0.0)
+#[inline(always)]
+pub fn exec_not_eq_fixed_within(stack: &mut ValueStack, epsilon: Fixed) -> Result<(), LpsVmError> {
+    let (a, b) = stack.pop2()?;
+    let result = if within(a, b, epsilon) { 0 } else { FIXED_ONE };
+    stack.push_int32(result)?;
+    Ok(())
+}
+
+/// `|a - b| <= epsilon`, without overflowing for far-apart values
+#[inline(always)]
+fn within(a: i32, b: i32, epsilon: Fixed) -> bool {
+    (a as i64 - b as i64).abs() <= epsilon.0 as i64
+}
+
+/// Execute LessVec2/3/4: pop b, a (N components each); push the bvec of `a < b`
+#[inline(always)]
+pub fn exec_less_vec<const N: usize>(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    compare_vec::<N>(stack, |a, b| a < b)
+}
+
+/// Execute LessEqVec2/3/4: pop b, a; push the bvec of `a <= b`
+#[inline(always)]
+pub fn exec_less_eq_vec<const N: usize>(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    compare_vec::<N>(stack, |a, b| a <= b)
+}
+
+/// Execute EqCompVec2/3/4: pop b, a; push the bvec of `a == b`, exact like
+/// EqVec2/3/4 but per component
+#[inline(always)]
+pub fn exec_eq_comp_vec<const N: usize>(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    compare_vec::<N>(stack, |a, b| a == b)
+}
+
+/// Execute NotBVec2/3/4: pop a bvec, push it with every component flipped
+#[inline(always)]
+pub fn exec_not_bvec<const N: usize>(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = pop_components::<N>(stack)?;
+    for value in a {
+        stack.push_int32(if value == 0 { FIXED_ONE } else { 0 })?;
+    }
+    Ok(())
+}
+
+#[inline(always)]
+fn compare_vec<const N: usize>(
+    stack: &mut ValueStack,
+    compare: impl Fn(Fixed, Fixed) -> bool,
+) -> Result<(), LpsVmError> {
+    let b = pop_components::<N>(stack)?;
+    let a = pop_components::<N>(stack)?;
+    for (a, b) in a.into_iter().zip(b) {
+        let result = if compare(Fixed(a), Fixed(b)) {
+            FIXED_ONE
+        } else {
+            0
+        };
+        stack.push_int32(result)?;
+    }
+    Ok(())
+}
+
+/// Pop an N-component vector, first component first
+#[inline(always)]
+fn pop_components<const N: usize>(stack: &mut ValueStack) -> Result<[i32; N], LpsVmError> {
+    let mut values = [0; N];
+    for value in values.iter_mut().rev() {
+        *value = stack.pop_int32()?;
+    }
+    Ok(values)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,4 +180,51 @@ mod tests {
         exec_not_eq_fixed(&mut stack).unwrap();
         assert_eq!(stack.pop_int32().unwrap(), FIXED_ONE);
     }
+
+    #[test]
+    fn test_eq_within_epsilon() {
+        let epsilon = Fixed(2);
+        for (b, equal) in [(0, true), (2, true), (-2, true), (3, false)] {
+            let mut stack = ValueStack::new(64);
+            stack.push_int32(FIXED_ONE).unwrap();
+            stack.push_int32(FIXED_ONE + b).unwrap();
+            exec_eq_fixed_within(&mut stack, epsilon).unwrap();
+            assert_eq!(
+                stack.pop_int32().unwrap(),
+                if equal { FIXED_ONE } else { 0 }
+            );
+
+            stack.push_int32(FIXED_ONE).unwrap();
+            stack.push_int32(FIXED_ONE + b).unwrap();
+            exec_not_eq_fixed_within(&mut stack, epsilon).unwrap();
+            assert_eq!(
+                stack.pop_int32().unwrap(),
+                if equal { 0 } else { FIXED_ONE }
+            );
+        }
+    }
+
+    #[test]
+    fn test_vec_comparisons_are_per_component() {
+        let mut stack = ValueStack::new(64);
+        for v in [1.0, 2.0, 3.0, 2.0, 2.0, 2.0] {
+            stack.push_fixed(v.to_fixed()).unwrap();
+        }
+        exec_less_vec::<3>(&mut stack).unwrap();
+        assert_eq!(stack.pop3().unwrap(), (FIXED_ONE, 0, 0));
+
+        for v in [1.0, 2.0, 3.0, 2.0, 2.0, 2.0] {
+            stack.push_fixed(v.to_fixed()).unwrap();
+        }
+        exec_less_eq_vec::<3>(&mut stack).unwrap();
+        assert_eq!(stack.pop3().unwrap(), (FIXED_ONE, FIXED_ONE, 0));
+
+        for v in [1.0, 2.0, 3.0, 2.0, 2.0, 2.0] {
+            stack.push_fixed(v.to_fixed()).unwrap();
+        }
+        exec_eq_comp_vec::<3>(&mut stack).unwrap();
+        exec_not_bvec::<3>(&mut stack).unwrap();
+        assert_eq!(stack.pop3().unwrap(), (FIXED_ONE, 0, FIXED_ONE));
+        assert_eq!(stack.sp(), 0);
+    }
 }
@@ -85,6 +85,20 @@ pub fn exec_jump_if_nonzero(
     }
 }
 
+/// Execute JumpTable: pop base and value, return the pc of the table entry
+///
+/// The `len` entries after `pc` are for values `base..base + len`, and the
+/// one after them is for every other value.
+#[inline(always)]
+pub fn exec_jump_table(stack: &mut ValueStack, pc: usize, len: u32) -> Result<usize, LpsVmError> {
+    let base = stack.pop_int32()?;
+    let value = stack.pop_int32()?;
+
+    // Wrapping, so values far from `base` land out of range rather than overflowing
+    let entry = value.wrapping_sub(base) as u32;
+    Ok(pc + 1 + entry.min(len) as usize)
+}
+
 /// Execute Jump: unconditional jump by offset
 /// Returns the new PC value
 #[inline(always)]
@@ -1,6 +1,6 @@
-use crate::fixed::noise::perlin3;
 use crate::fixed::{
-    atan, atan2, fract, lerp, modulo, pow, saturate, sign, smoothstep, step, tan, Fixed,
+    atan, atan2, fract, lerp, modulo, pow, remap, saturate, sign, smoothstep, step, tan, Fixed,
+    NoiseBackend, FIXED_ONE,
 };
 /// Advanced fixed-point fixed opcodes
 use crate::vm::error::LpsVmError;
@@ -48,6 +48,69 @@ pub fn exec_mod_fixed(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     Ok(())
 }
 
+/// Wrap a 16.16 value held in 64 bits into `[0, |period|)`
+///
+/// The remainder is taken on the raw bits, so it's exact however large
+/// `raw` is and only the result has to fit back into a `Fixed`.
+#[inline(always)]
+fn wrap_raw(raw: i64, period: Fixed) -> Result<Fixed, LpsVmError> {
+    if period.0 == 0 {
+        return Err(LpsVmError::DivisionByZero);
+    }
+    Ok(Fixed(raw.rem_euclid(period.0 as i64) as i32))
+}
+
+#[inline(always)]
+pub fn exec_wrap_fixed(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let (t, period) = stack.pop2()?;
+    stack.push_fixed(wrap_raw(t as i64, Fixed(period))?)?;
+    Ok(())
+}
+
+/// `phase(period)`: wrap the host's millisecond clock when one was set with
+/// `LpsVm::set_time_ms`, otherwise the `time` passed to `run`
+#[inline(always)]
+pub fn exec_phase_time(
+    stack: &mut ValueStack,
+    time: Fixed,
+    time_ms: Option<u64>,
+) -> Result<(), LpsVmError> {
+    let period = stack.pop_fixed()?;
+    let raw = match time_ms {
+        // Whole seconds and the millisecond remainder are converted apart so
+        // the shift can't overflow for any realistic uptime
+        Some(ms) => {
+            ((ms / 1000) as i64) * FIXED_ONE as i64 + ((ms % 1000) as i64 * FIXED_ONE as i64) / 1000
+        }
+        None => time.0 as i64,
+    };
+    stack.push_fixed(wrap_raw(raw, period)?)?;
+    Ok(())
+}
+
+/// 4x4 Bayer matrix, row-major; entry `b` dithers by `(b + 0.5) / 16`
+const BAYER_4X4: [i32; 16] = [0, 8, 2, 10, 12, 4, 14, 6, 3, 11, 1, 9, 15, 7, 13, 5];
+
+/// `quantize(value, levels)`: round `value` (0..1) to `levels` evenly spaced
+/// steps, adding an ordered dither offset picked by the pixel position so
+/// flat regions average out to the input instead of banding
+#[inline(always)]
+pub fn exec_quantize_fixed(
+    stack: &mut ValueStack,
+    x_int: Fixed,
+    y_int: Fixed,
+) -> Result<(), LpsVmError> {
+    let (value, levels) = stack.pop2()?;
+    let steps = (Fixed(levels).to_i32() - 1).max(1) as i64;
+
+    let cell = ((y_int.to_i32() & 3) << 2 | (x_int.to_i32() & 3)) as usize;
+    let dither = ((2 * BAYER_4X4[cell] + 1) * FIXED_ONE / 32) as i64;
+
+    let level = (value as i64 * steps + dither).div_euclid(FIXED_ONE as i64);
+    stack.push_fixed(Fixed((level * FIXED_ONE as i64 / steps) as i32))?;
+    Ok(())
+}
+
 #[inline(always)]
 pub fn exec_pow_fixed(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     let (base, exponent) = stack.pop2()?;
@@ -106,9 +169,23 @@ pub fn exec_smoothstep_fixed(stack: &mut ValueStack) -> Result<(), LpsVmError> {
 }
 
 #[inline(always)]
-pub fn exec_perlin3(stack: &mut ValueStack, octaves: u8) -> Result<(), LpsVmError> {
+pub fn exec_remap_fixed(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let out_max = stack.pop_fixed()?;
+    let out_min = stack.pop_fixed()?;
+    let (v, in_min, in_max) = stack.pop3()?;
+    let result = remap(Fixed(v), Fixed(in_min), Fixed(in_max), out_min, out_max);
+    stack.push_fixed(result)?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_perlin3(
+    stack: &mut ValueStack,
+    octaves: u8,
+    backend: NoiseBackend,
+) -> Result<(), LpsVmError> {
     let (x, y, z) = stack.pop3()?;
-    let result = perlin3(Fixed(x), Fixed(y), Fixed(z), octaves);
+    let result = backend.perlin3(Fixed(x), Fixed(y), Fixed(z), octaves);
     stack.push_fixed(result)?;
     Ok(())
 }
@@ -53,6 +53,59 @@ pub fn exec_neg_fixed(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     Ok(())
 }
 
+/// Narrow a raw 16.16 result back to `Fixed`, failing if it doesn't fit
+#[inline(always)]
+fn fit_fixed(raw: i64) -> Result<Fixed, LpsVmError> {
+    i32::try_from(raw)
+        .map(Fixed)
+        .map_err(|_| LpsVmError::ArithmeticOverflow)
+}
+
+/// Execute AddFixed, failing with `ArithmeticOverflow` instead of wrapping
+#[inline(always)]
+pub fn exec_add_fixed_checked(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let (a, b) = stack.pop2()?;
+    stack.push_fixed(fit_fixed(a as i64 + b as i64)?)?;
+    Ok(())
+}
+
+/// Execute SubFixed, failing with `ArithmeticOverflow` instead of wrapping
+#[inline(always)]
+pub fn exec_sub_fixed_checked(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let (a, b) = stack.pop2()?;
+    stack.push_fixed(fit_fixed(a as i64 - b as i64)?)?;
+    Ok(())
+}
+
+/// Execute MulFixed, failing with `ArithmeticOverflow` instead of truncating
+#[inline(always)]
+pub fn exec_mul_fixed_checked(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let (a, b) = stack.pop2()?;
+    stack.push_fixed(fit_fixed((a as i64 * b as i64) >> 16)?)?;
+    Ok(())
+}
+
+/// Execute DivFixed, failing with `ArithmeticOverflow` instead of truncating
+#[inline(always)]
+pub fn exec_div_fixed_checked(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let (a, b) = stack.pop2()?;
+
+    if b == 0 {
+        return Err(LpsVmError::DivisionByZero);
+    }
+
+    stack.push_fixed(fit_fixed(((a as i64) << 16) / b as i64)?)?;
+    Ok(())
+}
+
+/// Execute NegFixed, failing with `ArithmeticOverflow` for the minimum value
+#[inline(always)]
+pub fn exec_neg_fixed_checked(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_fixed()?;
+    stack.push_fixed(fit_fixed(-(a.0 as i64))?)?;
+    Ok(())
+}
+
 /// Execute AbsFixed: pop a; push abs(a)
 #[inline(always)]
 pub fn exec_abs_fixed(stack: &mut ValueStack) -> Result<(), LpsVmError> {
@@ -63,6 +63,56 @@ pub fn exec_neg_int32(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     Ok(())
 }
 
+/// Execute AddInt32, failing with `ArithmeticOverflow` instead of wrapping
+#[inline(always)]
+pub fn exec_add_int32_checked(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let (a, b) = stack.pop2()?;
+    let result = a.checked_add(b).ok_or(LpsVmError::ArithmeticOverflow)?;
+    stack.push_int32(result)?;
+    Ok(())
+}
+
+/// Execute SubInt32, failing with `ArithmeticOverflow` instead of wrapping
+#[inline(always)]
+pub fn exec_sub_int32_checked(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let (a, b) = stack.pop2()?;
+    let result = a.checked_sub(b).ok_or(LpsVmError::ArithmeticOverflow)?;
+    stack.push_int32(result)?;
+    Ok(())
+}
+
+/// Execute MulInt32, failing with `ArithmeticOverflow` instead of wrapping
+#[inline(always)]
+pub fn exec_mul_int32_checked(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let (a, b) = stack.pop2()?;
+    let result = a.checked_mul(b).ok_or(LpsVmError::ArithmeticOverflow)?;
+    stack.push_int32(result)?;
+    Ok(())
+}
+
+/// Execute DivInt32, failing with `ArithmeticOverflow` for `i32::MIN / -1`
+#[inline(always)]
+pub fn exec_div_int32_checked(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let (a, b) = stack.pop2()?;
+
+    if b == 0 {
+        return Err(LpsVmError::DivisionByZero);
+    }
+
+    let result = a.checked_div(b).ok_or(LpsVmError::ArithmeticOverflow)?;
+    stack.push_int32(result)?;
+    Ok(())
+}
+
+/// Execute NegInt32, failing with `ArithmeticOverflow` for `i32::MIN`
+#[inline(always)]
+pub fn exec_neg_int32_checked(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_int32()?;
+    let result = a.checked_neg().ok_or(LpsVmError::ArithmeticOverflow)?;
+    stack.push_int32(result)?;
+    Ok(())
+}
+
 /// Execute AbsInt32: pop a; push abs(a)
 #[inline(always)]
 pub fn exec_abs_int32(stack: &mut ValueStack) -> Result<(), LpsVmError> {
@@ -172,12 +222,12 @@ pub fn exec_int32_to_fixed(stack: &mut ValueStack) -> Result<(), LpsVmError> {
 }
 
 /// Execute FixedToInt32: convert Fixed to Int32 format
-/// pop a (Fixed format); push a >> 16 (raw int32)
+/// pop a (Fixed format); push a / 2^16 truncated toward zero (raw int32)
 #[inline(always)]
 pub fn exec_fixed_to_int32(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     let a = stack.pop_fixed()?;
-    // Convert from Fixed format to raw int32 by extracting integer part
-    stack.push_int32(a.to_i32())?;
+    // Integer division rounds toward zero, unlike `to_i32` (floor): -3.7 -> -3
+    stack.push_int32(a.0 / crate::fixed::Fixed::ONE.0)?;
     Ok(())
 }
 
@@ -1,10 +1,11 @@
-use crate::fixed::{Fixed, FIXED_ONE, FIXED_SHIFT};
+use crate::fixed::{Fixed, Vec2, FIXED_ONE, FIXED_SHIFT};
 /// Load coordinate/builtin variable operations
 use crate::vm::error::LpsVmError;
 use crate::vm::value_stack::ValueStack;
 
 /// Load source specifier for built-in variables
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LoadSource {
     XInt,        // Integer X coordinate (0..width-1)
     YInt,        // Integer Y coordinate (0..height-1)
@@ -14,6 +15,10 @@ pub enum LoadSource {
     TimeNorm,    // Time normalized to 0..1 range (wraps at 1.0)
     CenterDist,  // Distance from center (0 at center, 1 at farthest corner)
     CenterAngle, // Angle from center (0-1 for 0-2π, 0 = east/right)
+    CenterX,     // Normalized X of the radial center (see `LpsVm::set_center`)
+    CenterY,     // Normalized Y of the radial center
+    Width,       // Output width in pixels (0 for `LpsVm::run`)
+    Height,      // Output height in pixels (0 for `LpsVm::run`)
 }
 
 /// Execute Load: push built-in variable value onto stack
@@ -29,8 +34,30 @@ pub fn exec_load(
     time: Fixed,
     width: usize,
     height: usize,
+    center: Vec2,
 ) -> Result<(), LpsVmError> {
-    let value = match source {
+    let value = load_value(
+        source, x_norm, y_norm, x_int, y_int, time, width, height, center,
+    );
+    stack.push_fixed(value)?;
+    Ok(())
+}
+
+/// Value of a built-in variable for one pixel
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn load_value(
+    source: LoadSource,
+    x_norm: Fixed,
+    y_norm: Fixed,
+    x_int: Fixed,
+    y_int: Fixed,
+    time: Fixed,
+    width: usize,
+    height: usize,
+    center: Vec2,
+) -> Fixed {
+    match source {
         LoadSource::XNorm => x_norm,
         LoadSource::YNorm => y_norm,
         LoadSource::XInt => x_int,
@@ -40,10 +67,14 @@ pub fn exec_load(
             // Wrap time to 0..1 range
             Fixed((time.0 as i64).rem_euclid(FIXED_ONE as i64) as i32)
         }
+        LoadSource::CenterX => center.x,
+        LoadSource::CenterY => center.y,
+        LoadSource::Width => Fixed::from_i32(width as i32),
+        LoadSource::Height => Fixed::from_i32(height as i32),
         LoadSource::CenterDist => {
             // Distance from center (0 at center, 1 at farthest corner)
-            let center_x = Fixed::from_i32(width as i32 / 2).0;
-            let center_y = Fixed::from_i32(height as i32 / 2).0;
+            let center_x = center.x.mul_int(width as i32).0;
+            let center_y = center.y.mul_int(height as i32).0;
             let dx = x_int.0 - center_x;
             let dy = y_int.0 - center_y;
 
@@ -59,8 +90,8 @@ pub fn exec_load(
         LoadSource::CenterAngle => {
             // Angle from center in radians (-π to π, 0 = east/right)
             // Compatible with sin/cos which expect radians
-            let center_x = Fixed::from_i32(width as i32 / 2).0;
-            let center_y = Fixed::from_i32(height as i32 / 2).0;
+            let center_x = center.x.mul_int(width as i32).0;
+            let center_y = center.y.mul_int(height as i32).0;
             let dx = x_int.0 - center_x;
             let dy = y_int.0 - center_y;
 
@@ -106,11 +137,7 @@ pub fn exec_load(
                 radians - Fixed::PI // Convert 0..2π to -π..π
             }
         }
-    };
-
-    stack.push_fixed(value)?;
-
-    Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -118,6 +145,8 @@ mod tests {
     use super::*;
     use crate::fixed::ToFixed;
 
+    const CENTER: Vec2 = Vec2::new(Fixed::HALF, Fixed::HALF);
+
     #[test]
     fn test_load_x_norm() {
         let mut stack = ValueStack::new(64);
@@ -132,6 +161,7 @@ mod tests {
             Fixed::ZERO,
             100,
             100,
+            CENTER,
         )
         .unwrap();
 
@@ -153,6 +183,7 @@ mod tests {
             Fixed::ZERO,
             100,
             100,
+            CENTER,
         )
         .unwrap();
 
@@ -174,6 +205,7 @@ mod tests {
             5.5f32.to_fixed(),
             100,
             100,
+            CENTER,
         )
         .unwrap();
 
@@ -196,6 +228,7 @@ mod tests {
             2.3f32.to_fixed(),
             100,
             100,
+            CENTER,
         )
         .unwrap();
 
@@ -218,6 +251,7 @@ mod tests {
             Fixed::ZERO,
             100,
             100,
+            CENTER,
         )
         .unwrap();
 
@@ -241,6 +275,7 @@ mod tests {
             Fixed::ZERO,
             100,
             100,
+            CENTER,
         )
         .unwrap();
 
@@ -249,6 +284,32 @@ mod tests {
         assert_eq!(Fixed(stack.raw_slice()[0]).to_f32(), 0.0);
     }
 
+    #[test]
+    fn test_load_center_dist_off_center() {
+        let center = Vec2::new(0.25f32.to_fixed(), 0.75f32.to_fixed());
+        let load_dist = |x: f32, y: f32| {
+            let mut stack = ValueStack::new(64);
+            exec_load(
+                &mut stack,
+                LoadSource::CenterDist,
+                Fixed::ZERO,
+                Fixed::ZERO,
+                x.to_fixed(),
+                y.to_fixed(),
+                Fixed::ZERO,
+                100,
+                100,
+                center,
+            )
+            .unwrap();
+            stack.pop_fixed().unwrap()
+        };
+
+        // Zero moves to (25, 75); the old center no longer is
+        assert_eq!(load_dist(25.0, 75.0), Fixed::ZERO);
+        assert!(load_dist(50.0, 50.0) > Fixed::ZERO);
+    }
+
     #[test]
     fn test_load_stack_overflow() {
         let mut stack = ValueStack::new(2); // Small stack
@@ -266,6 +327,7 @@ mod tests {
             Fixed::ZERO,
             100,
             100,
+            CENTER,
         );
 
         assert!(matches!(result, Err(LpsVmError::StackOverflow { sp: 2 })));
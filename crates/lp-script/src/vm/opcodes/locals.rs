@@ -124,6 +124,30 @@ pub fn exec_store_local_vec4(
     Ok(())
 }
 
+/// Execute LoadLocalMat2: pop nothing; push local[idx] as 4 Fixed
+#[inline(always)]
+pub fn exec_load_local_mat2(
+    stack: &mut ValueStack,
+    locals: &LocalStack,
+    idx: usize,
+) -> Result<(), LpsVmError> {
+    let mat = locals.get_mat2(idx)?;
+    stack.push_mat2(mat)?;
+    Ok(())
+}
+
+/// Execute StoreLocalMat2: pop 4 Fixed; store to local[idx]
+#[inline(always)]
+pub fn exec_store_local_mat2(
+    stack: &mut ValueStack,
+    locals: &mut LocalStack,
+    idx: usize,
+) -> Result<(), LpsVmError> {
+    let mat = stack.pop_mat2()?;
+    locals.set_mat2(idx, mat)?;
+    Ok(())
+}
+
 /// Execute LoadLocalMat3: pop nothing; push local[idx] as 9 Fixed
 #[inline(always)]
 pub fn exec_load_local_mat3(
@@ -148,6 +172,71 @@ pub fn exec_store_local_mat3(
     Ok(())
 }
 
+/// Execute LoadLocalMat4: pop nothing; push local[idx] as 16 Fixed
+#[inline(always)]
+pub fn exec_load_local_mat4(
+    stack: &mut ValueStack,
+    locals: &LocalStack,
+    idx: usize,
+) -> Result<(), LpsVmError> {
+    let mat = locals.get_mat4(idx)?;
+    stack.push_mat4(mat)?;
+    Ok(())
+}
+
+/// Execute StoreLocalMat4: pop 16 Fixed; store to local[idx]
+#[inline(always)]
+pub fn exec_store_local_mat4(
+    stack: &mut ValueStack,
+    locals: &mut LocalStack,
+    idx: usize,
+) -> Result<(), LpsVmError> {
+    let mat = stack.pop_mat4()?;
+    locals.set_mat4(idx, mat)?;
+    Ok(())
+}
+
+/// Execute LoadLocalElemFixed: pop Int32 element index; push local[idx][elem]
+#[inline(always)]
+pub fn exec_load_local_elem_fixed(
+    stack: &mut ValueStack,
+    locals: &LocalStack,
+    idx: usize,
+) -> Result<(), LpsVmError> {
+    let elem = stack.pop_int32()?;
+    let val = locals.get_elem_fixed(idx, elem)?;
+    stack.push_fixed(val)?;
+    Ok(())
+}
+
+/// Execute StoreLocalElemFixed: pop Int32 element index, then Fixed value;
+/// store to local[idx][elem]
+#[inline(always)]
+pub fn exec_store_local_elem_fixed(
+    stack: &mut ValueStack,
+    locals: &mut LocalStack,
+    idx: usize,
+) -> Result<(), LpsVmError> {
+    let elem = stack.pop_int32()?;
+    let val = stack.pop_fixed()?;
+    locals.set_elem_fixed(idx, elem, val)?;
+    Ok(())
+}
+
+/// Execute CheckIndex: pop Int32 index; fail unless 0 <= index < len; push it back
+#[inline(always)]
+pub fn exec_check_index(stack: &mut ValueStack, len: u32) -> Result<(), LpsVmError> {
+    let index = stack.pop_int32()?;
+    if index < 0 || index as u32 >= len {
+        return Err(LpsVmError::InvalidArrayIndex {
+            index,
+            array_size: len as usize,
+        });
+    }
+    stack.push_int32(index)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
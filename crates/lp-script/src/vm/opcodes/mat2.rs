@@ -0,0 +1,50 @@
+use crate::fixed::Mat2;
+/// Mat2 operations
+use crate::vm::error::LpsVmError;
+use crate::vm::value_stack::ValueStack;
+
+#[inline(always)]
+pub fn exec_rotate_mat2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let angle = stack.pop_fixed()?;
+    stack.push_mat2(Mat2::rotation(angle))?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_mul_mat2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let b = stack.pop_mat2()?;
+    let a = stack.pop_mat2()?;
+    stack.push_mat2(a * b)?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_mul_mat2_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let vec = stack.pop_vec2()?;
+    let mat = stack.pop_mat2()?;
+    stack.push_vec2(mat * vec)?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_transpose_mat2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_mat2()?;
+    stack.push_mat2(a.transpose())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_determinant_mat2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_mat2()?;
+    stack.push_fixed(a.determinant())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_inverse_mat2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_mat2()?;
+    // Return identity if singular (GLSL behavior)
+    let result = a.inverse().unwrap_or(Mat2::identity());
+    stack.push_mat2(result)?;
+    Ok(())
+}
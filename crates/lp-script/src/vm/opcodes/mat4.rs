@@ -0,0 +1,82 @@
+use crate::fixed::Mat4;
+/// Mat4 operations
+use crate::vm::error::LpsVmError;
+use crate::vm::value_stack::ValueStack;
+
+#[inline(always)]
+pub fn exec_add_mat4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let b = stack.pop_mat4()?;
+    let a = stack.pop_mat4()?;
+    stack.push_mat4(a + b)?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_sub_mat4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let b = stack.pop_mat4()?;
+    let a = stack.pop_mat4()?;
+    stack.push_mat4(a - b)?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_neg_mat4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_mat4()?;
+    stack.push_mat4(-a)?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_mul_mat4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let b = stack.pop_mat4()?;
+    let a = stack.pop_mat4()?;
+    stack.push_mat4(a * b)?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_mul_mat4_scalar(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let scalar = stack.pop_fixed()?;
+    let mat = stack.pop_mat4()?;
+    stack.push_mat4(mat * scalar)?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_div_mat4_scalar(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let scalar = stack.pop_fixed()?;
+    let mat = stack.pop_mat4()?;
+    stack.push_mat4(mat / scalar)?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_mul_mat4_vec4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let vec = stack.pop_vec4()?;
+    let mat = stack.pop_mat4()?;
+    stack.push_vec4(mat * vec)?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_transpose_mat4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_mat4()?;
+    stack.push_mat4(a.transpose())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_determinant_mat4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_mat4()?;
+    stack.push_fixed(a.determinant())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_inverse_mat4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_mat4()?;
+    // Return identity if singular (GLSL behavior)
+    let result = a.inverse().unwrap_or(Mat4::identity());
+    stack.push_mat4(result)?;
+    Ok(())
+}
@@ -20,7 +20,9 @@ pub mod int32;
 pub mod int32_compare;
 
 // Vector opcodes
+pub mod mat2;
 pub mod mat3;
+pub mod mat4;
 pub mod vec2;
 pub mod vec3;
 pub mod vec4;
@@ -41,23 +43,36 @@ pub mod load;
 pub mod arrays;
 pub mod textures;
 
+// Optional opcode groups for reduced VM builds
+pub mod opcode_set;
+
+// Per-opcode weights for program cost estimates
+pub mod opcode_cost;
+
+// Per-opcode stack effects for static limit analysis
+pub mod opcode_stack;
+pub use opcode_set::{OpcodeGroup, OpcodeSet};
+
 /// New typed OpCode enum (not yet in use - will replace test_engine::OpCode during migration)
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(dead_code)]
 pub enum LpsOpCode {
     // Stack operations
-    Push(Fixed),
+    Push(#[cfg_attr(feature = "serde", serde(with = "lp_math::fixed::serde_impl::decimal"))] Fixed),
     PushInt32(i32),
-    Dup1,  // Duplicate top 1 stack value (for Fixed/Int32)
-    Dup2,  // Duplicate top 2 stack values (for Vec2)
-    Dup3,  // Duplicate top 3 stack values (for Vec3)
-    Dup4,  // Duplicate top 4 stack values (for Vec4)
-    Dup9,  // Duplicate top 9 stack values (for Mat3)
-    Drop1, // Drop top 1 stack value
-    Drop2, // Drop top 2 stack values
-    Drop3, // Drop top 3 stack values
-    Drop4, // Drop top 4 stack values
-    Drop9, // Drop top 9 stack values (for Mat3)
+    Dup1,   // Duplicate top 1 stack value (for Fixed/Int32)
+    Dup2,   // Duplicate top 2 stack values (for Vec2)
+    Dup3,   // Duplicate top 3 stack values (for Vec3)
+    Dup4,   // Duplicate top 4 stack values (for Vec4)
+    Dup9,   // Duplicate top 9 stack values (for Mat3)
+    Dup16,  // Duplicate top 16 stack values (for Mat4)
+    Drop1,  // Drop top 1 stack value
+    Drop2,  // Drop top 2 stack values
+    Drop3,  // Drop top 3 stack values
+    Drop4,  // Drop top 4 stack values
+    Drop9,  // Drop top 9 stack values (for Mat3)
+    Drop16, // Drop top 16 stack values (for Mat4)
     Swap,
 
     // Fixed-point arithmetic
@@ -79,6 +94,9 @@ pub enum LpsOpCode {
     CeilFixed,
     FractFixed,      // Fractional part
     ModFixed,        // Modulo
+    WrapFixed,       // pop 2, push 1: first arg wrapped into [0, period)
+    PhaseTime,       // pop 1, push 1: `time` wrapped into [0, period)
+    QuantizeFixed,   // pop 2, push 1: value rounded to levels, dithered by pixel position
     PowFixed,        // Power
     SignFixed,       // Sign function
     SaturateFixed,   // Clamp to 0..1
@@ -86,6 +104,7 @@ pub enum LpsOpCode {
     StepFixed,       // Step function
     LerpFixed,       // Linear interpolation
     SmoothstepFixed, // Smooth interpolation
+    RemapFixed,      // pop 5 (v, inMin, inMax, outMin, outMax), push 1
 
     // Noise functions
     Perlin3(u8), // 3D Perlin noise, octaves embedded
@@ -132,26 +151,55 @@ pub enum LpsOpCode {
 
     // Type conversions
     Int32ToFixed, // Convert Int32 to Fixed (multiply by 2^16)
-    FixedToInt32, // Convert Fixed to Int32 (divide by 2^16, truncate)
+    FixedToInt32, // Convert Fixed to Int32 (divide by 2^16, truncate toward zero)
 
     // Vec2 operations (operate on stack)
-    AddVec2,       // pop 4, push 2
-    SubVec2,       // pop 4, push 2
-    NegVec2,       // pop 2, push 2 (negate components)
-    MulVec2,       // pop 4, push 2 (component-wise)
-    DivVec2,       // pop 4, push 2 (component-wise)
-    ModVec2,       // pop 4, push 2 (component-wise)
-    MulVec2Scalar, // pop 3 (vec2 + scalar), push 2
-    DivVec2Scalar, // pop 3 (vec2 + scalar), push 2
-    Dot2,          // pop 4, push 1
-    Length2,       // pop 2, push 1
-    Normalize2,    // pop 2, push 2
-    Distance2,     // pop 4, push 1
+    AddVec2,        // pop 4, push 2
+    SubVec2,        // pop 4, push 2
+    NegVec2,        // pop 2, push 2 (negate components)
+    AbsVec2,        // pop 2, push 2 (abs of each component)
+    SignVec2,       // pop 2, push 2 (sign of each component)
+    FloorVec2,      // pop 2, push 2 (floor of each component)
+    CeilVec2,       // pop 2, push 2 (ceil of each component)
+    FractVec2,      // pop 2, push 2 (fract of each component)
+    SqrtVec2,       // pop 2, push 2 (sqrt of each component)
+    MulVec2,        // pop 4, push 2 (component-wise)
+    DivVec2,        // pop 4, push 2 (component-wise)
+    ModVec2,        // pop 4, push 2 (component-wise)
+    MulVec2Scalar,  // pop 3 (vec2 + scalar), push 2
+    DivVec2Scalar,  // pop 3 (vec2 + scalar), push 2
+    Dot2,           // pop 4, push 1
+    Length2,        // pop 2, push 1
+    Normalize2,     // pop 2, push 2
+    Distance2,      // pop 4, push 1
+    LengthSq2,      // pop 2, push 1 (squared length, no sqrt)
+    DistanceSq2,    // pop 4, push 1 (squared distance, no sqrt)
+    EqVec2,         // pop 4, push 1 (1.0 if all components are equal)
+    LessVec2,       // pop 4, push 2 (bvec2, 1.0 where a < b)
+    LessEqVec2,     // pop 4, push 2 (bvec2, 1.0 where a <= b)
+    EqCompVec2,     // pop 4, push 2 (bvec2, 1.0 where a == b)
+    NotBVec2,       // pop 2, push 2 (flip each bvec2 component)
+    Reflect2,       // pop 4 (I, N), push 2
+    FaceForward2,   // pop 6 (N, I, Nref), push 2
+    MaxCompVec2,    // pop 2, push 1 (largest component)
+    MinCompVec2,    // pop 2, push 1 (smallest component)
+    SumVec2,        // pop 2, push 1 (sum of components)
+    AvgVec2,        // pop 2, push 1 (mean of components)
+    ToPolarVec2,    // pop 2 (x, y), push 2 (radius, angle)
+    FromPolarVec2,  // pop 2 (radius, angle), push 2 (x, y)
+    TileVec2,       // pop 3 (uv, repeats), push 2 (uv * repeats wrapped into 0..1)
+    MirrorTileVec2, // pop 3 (uv, repeats), push 2 (like TileVec2, every other tile mirrored)
 
     // Vec3 operations
     AddVec3,       // pop 6, push 3
     SubVec3,       // pop 6, push 3
     NegVec3,       // pop 3, push 3 (negate components)
+    AbsVec3,       // pop 3, push 3 (abs of each component)
+    SignVec3,      // pop 3, push 3 (sign of each component)
+    FloorVec3,     // pop 3, push 3 (floor of each component)
+    CeilVec3,      // pop 3, push 3 (ceil of each component)
+    FractVec3,     // pop 3, push 3 (fract of each component)
+    SqrtVec3,      // pop 3, push 3 (sqrt of each component)
     MulVec3,       // pop 6, push 3 (component-wise)
     DivVec3,       // pop 6, push 3 (component-wise)
     ModVec3,       // pop 6, push 3 (component-wise)
@@ -162,11 +210,32 @@ pub enum LpsOpCode {
     Length3,       // pop 3, push 1
     Normalize3,    // pop 3, push 3
     Distance3,     // pop 6, push 1
+    LengthSq3,     // pop 3, push 1 (squared length, no sqrt)
+    DistanceSq3,   // pop 6, push 1 (squared distance, no sqrt)
+    EqVec3,        // pop 6, push 1 (1.0 if all components are equal)
+    LessVec3,      // pop 6, push 3 (bvec3, 1.0 where a < b)
+    LessEqVec3,    // pop 6, push 3 (bvec3, 1.0 where a <= b)
+    EqCompVec3,    // pop 6, push 3 (bvec3, 1.0 where a == b)
+    NotBVec3,      // pop 3, push 3 (flip each bvec3 component)
+    Reflect3,      // pop 6 (I, N), push 3
+    FaceForward3,  // pop 9 (N, I, Nref), push 3
+    MaxCompVec3,   // pop 3, push 1 (largest component)
+    MinCompVec3,   // pop 3, push 1 (smallest component)
+    SumVec3,       // pop 3, push 1 (sum of components)
+    AvgVec3,       // pop 3, push 1 (mean of components)
+    HsvToRgb,      // pop 3 (hue, saturation, value), push 3 (RGB)
+    RgbToHsv,      // pop 3 (RGB), push 3 (hue, saturation, value)
 
     // Vec4 operations
     AddVec4,       // pop 8, push 4
     SubVec4,       // pop 8, push 4
     NegVec4,       // pop 4, push 4 (negate components)
+    AbsVec4,       // pop 4, push 4 (abs of each component)
+    SignVec4,      // pop 4, push 4 (sign of each component)
+    FloorVec4,     // pop 4, push 4 (floor of each component)
+    CeilVec4,      // pop 4, push 4 (ceil of each component)
+    FractVec4,     // pop 4, push 4 (fract of each component)
+    SqrtVec4,      // pop 4, push 4 (sqrt of each component)
     MulVec4,       // pop 8, push 4 (component-wise)
     DivVec4,       // pop 8, push 4 (component-wise)
     ModVec4,       // pop 8, push 4 (component-wise)
@@ -176,6 +245,27 @@ pub enum LpsOpCode {
     Length4,       // pop 4, push 1
     Normalize4,    // pop 4, push 4
     Distance4,     // pop 8, push 1
+    LengthSq4,     // pop 4, push 1 (squared length, no sqrt)
+    DistanceSq4,   // pop 8, push 1 (squared distance, no sqrt)
+    EqVec4,        // pop 8, push 1 (1.0 if all components are equal)
+    LessVec4,      // pop 8, push 4 (bvec4, 1.0 where a < b)
+    LessEqVec4,    // pop 8, push 4 (bvec4, 1.0 where a <= b)
+    EqCompVec4,    // pop 8, push 4 (bvec4, 1.0 where a == b)
+    NotBVec4,      // pop 4, push 4 (flip each bvec4 component)
+    Reflect4,      // pop 8 (I, N), push 4
+    FaceForward4,  // pop 12 (N, I, Nref), push 4
+    MaxCompVec4,   // pop 4, push 1 (largest component)
+    MinCompVec4,   // pop 4, push 1 (smallest component)
+    SumVec4,       // pop 4, push 1 (sum of components)
+    AvgVec4,       // pop 4, push 1 (mean of components)
+
+    // Mat2 operations (add, sub, neg and scalar mul/div reuse the Vec4 opcodes)
+    RotateMat2,      // pop 1 (angle), push 4 (counter-clockwise rotation)
+    MulMat2,         // pop 8, push 4 (matrix multiplication)
+    MulMat2Vec2,     // pop 6 (mat2 + vec2), push 2
+    TransposeMat2,   // pop 4, push 4
+    DeterminantMat2, // pop 4, push 1
+    InverseMat2,     // pop 4, push 4 (returns identity if singular)
 
     // Mat3 operations
     AddMat3,         // pop 18, push 9
@@ -189,6 +279,18 @@ pub enum LpsOpCode {
     DeterminantMat3, // pop 9, push 1
     InverseMat3,     // pop 9, push 9 (returns identity if singular)
 
+    // Mat4 operations
+    AddMat4,         // pop 32, push 16
+    SubMat4,         // pop 32, push 16
+    NegMat4,         // pop 16, push 16 (negate components)
+    MulMat4,         // pop 32, push 16 (matrix multiplication)
+    MulMat4Scalar,   // pop 17 (mat4 + scalar), push 16
+    DivMat4Scalar,   // pop 17 (mat4 + scalar), push 16
+    MulMat4Vec4,     // pop 20 (mat4 + vec4), push 4
+    TransposeMat4,   // pop 16, push 16
+    DeterminantMat4, // pop 16, push 1
+    InverseMat4,     // pop 16, push 16 (returns identity if singular)
+
     // Swizzle operations (reorder stack values)
     Swizzle3to2(u8, u8),     // pop 3, push 2 (indices specify which 2 to keep)
     Swizzle3to3(u8, u8, u8), // pop 3, push 3 (indices specify reordering)
@@ -197,8 +299,10 @@ pub enum LpsOpCode {
     Swizzle4to4(u8, u8, u8, u8), // pop 4, push 4 (indices specify reordering)
 
     // Texture sampling (local index embedded, UV coords on stack)
-    TextureSampleR(u32),    // pop 2 Fixed (UV), push 1 Fixed (R)
-    TextureSampleRGBA(u32), // pop 2 Fixed (UV), push 4 Fixed (RGBA)
+    TextureSampleR(u32),      // pop 2 Fixed (UV), push 1 Fixed (R)
+    TextureSampleRGBA(u32),   // pop 2 Fixed (UV), push 4 Fixed (RGBA)
+    TextureMixRGBA(u32, u32), // pop 3 Fixed (UV, t), push 4 Fixed (RGBA of both bound slots, lerped by t)
+    PaletteSample,            // pop 1 Fixed (t), push 3 Fixed (RGB) from the bound palette
 
     // Local variables (index and type embedded for safety)
     LoadLocalFixed(u32),
@@ -211,8 +315,17 @@ pub enum LpsOpCode {
     StoreLocalVec3(u32),
     LoadLocalVec4(u32),
     StoreLocalVec4(u32),
+    LoadLocalMat2(u32),
+    StoreLocalMat2(u32),
     LoadLocalMat3(u32),
     StoreLocalMat3(u32),
+    LoadLocalMat4(u32),
+    StoreLocalMat4(u32),
+
+    // Grid and array locals (flat element index on the stack)
+    LoadLocalElemFixed(u32), // pop Int32 element index; push local[idx][elem]
+    StoreLocalElemFixed(u32), // pop Int32 element index, Fixed value; store to local[idx][elem]
+    CheckIndex(u32),         // pop Int32 index, push it back; error unless 0 <= index < n
 
     // Array operations
     GetElemInt32ArrayFixed, // pop array_ref, index; push Fixed
@@ -222,12 +335,16 @@ pub enum LpsOpCode {
     Jump(i32),          // Unconditional jump by offset
     JumpIfZero(i32),    // Pop value, jump if zero
     JumpIfNonZero(i32), // Pop value, jump if non-zero
-    Select,             // Pop false_val, true_val, condition; push selected
-    Call(u32),          // Call user-defined function at offset (pushes return address)
-    Return,             // Return from function (pops return address, or exits if main)
+    JumpTable(u32), // Pop Int32 base, value; run Jump #(value - base) of the n + 1 after (#n if out of range)
+    Select,         // Pop false_val, true_val, condition; push selected
+    Call(u32),      // Call user-defined function at offset (pushes return address)
+    CallNative(u32), // Pop the native's params, call host native #idx, push its result
+    Return,         // Return from function (pops return address, or exits if main)
 
     // Coordinate loading (legacy compatibility)
     Load(LoadSource),
+    LoadIndex,         // Push the current LED/pixel linear index (Int32)
+    LoadExternal(u32), // pop 0, push the host-set value of external #idx (1-9 slots by type)
 }
 
 impl LpsOpCode {
@@ -241,11 +358,13 @@ impl LpsOpCode {
             LpsOpCode::Dup3 => "Dup3",
             LpsOpCode::Dup4 => "Dup4",
             LpsOpCode::Dup9 => "Dup9",
+            LpsOpCode::Dup16 => "Dup16",
             LpsOpCode::Drop1 => "Drop1",
             LpsOpCode::Drop2 => "Drop2",
             LpsOpCode::Drop3 => "Drop3",
             LpsOpCode::Drop4 => "Drop4",
             LpsOpCode::Drop9 => "Drop9",
+            LpsOpCode::Drop16 => "Drop16",
             LpsOpCode::Swap => "Swap",
             LpsOpCode::AddFixed => "AddFixed",
             LpsOpCode::SubFixed => "SubFixed",
@@ -265,6 +384,9 @@ impl LpsOpCode {
             LpsOpCode::CeilFixed => "CeilFixed",
             LpsOpCode::FractFixed => "FractFixed",
             LpsOpCode::ModFixed => "ModFixed",
+            LpsOpCode::WrapFixed => "WrapFixed",
+            LpsOpCode::PhaseTime => "PhaseTime",
+            LpsOpCode::QuantizeFixed => "QuantizeFixed",
             LpsOpCode::PowFixed => "PowFixed",
             LpsOpCode::SignFixed => "SignFixed",
             LpsOpCode::SaturateFixed => "SaturateFixed",
@@ -272,6 +394,7 @@ impl LpsOpCode {
             LpsOpCode::StepFixed => "StepFixed",
             LpsOpCode::LerpFixed => "LerpFixed",
             LpsOpCode::SmoothstepFixed => "SmoothstepFixed",
+            LpsOpCode::RemapFixed => "RemapFixed",
             LpsOpCode::Perlin3(_) => "Perlin3",
             LpsOpCode::GreaterFixed => "GreaterFixed",
             LpsOpCode::LessFixed => "LessFixed",
@@ -308,6 +431,12 @@ impl LpsOpCode {
             LpsOpCode::AddVec2 => "AddVec2",
             LpsOpCode::SubVec2 => "SubVec2",
             LpsOpCode::NegVec2 => "NegVec2",
+            LpsOpCode::AbsVec2 => "AbsVec2",
+            LpsOpCode::SignVec2 => "SignVec2",
+            LpsOpCode::FloorVec2 => "FloorVec2",
+            LpsOpCode::CeilVec2 => "CeilVec2",
+            LpsOpCode::FractVec2 => "FractVec2",
+            LpsOpCode::SqrtVec2 => "SqrtVec2",
             LpsOpCode::MulVec2 => "MulVec2",
             LpsOpCode::DivVec2 => "DivVec2",
             LpsOpCode::ModVec2 => "ModVec2",
@@ -317,9 +446,32 @@ impl LpsOpCode {
             LpsOpCode::Length2 => "Length2",
             LpsOpCode::Normalize2 => "Normalize2",
             LpsOpCode::Distance2 => "Distance2",
+            LpsOpCode::LengthSq2 => "LengthSq2",
+            LpsOpCode::DistanceSq2 => "DistanceSq2",
+            LpsOpCode::EqVec2 => "EqVec2",
+            LpsOpCode::LessVec2 => "LessVec2",
+            LpsOpCode::LessEqVec2 => "LessEqVec2",
+            LpsOpCode::EqCompVec2 => "EqCompVec2",
+            LpsOpCode::NotBVec2 => "NotBVec2",
+            LpsOpCode::Reflect2 => "Reflect2",
+            LpsOpCode::FaceForward2 => "FaceForward2",
+            LpsOpCode::MaxCompVec2 => "MaxCompVec2",
+            LpsOpCode::MinCompVec2 => "MinCompVec2",
+            LpsOpCode::SumVec2 => "SumVec2",
+            LpsOpCode::AvgVec2 => "AvgVec2",
+            LpsOpCode::ToPolarVec2 => "ToPolarVec2",
+            LpsOpCode::FromPolarVec2 => "FromPolarVec2",
+            LpsOpCode::TileVec2 => "TileVec2",
+            LpsOpCode::MirrorTileVec2 => "MirrorTileVec2",
             LpsOpCode::AddVec3 => "AddVec3",
             LpsOpCode::SubVec3 => "SubVec3",
             LpsOpCode::NegVec3 => "NegVec3",
+            LpsOpCode::AbsVec3 => "AbsVec3",
+            LpsOpCode::SignVec3 => "SignVec3",
+            LpsOpCode::FloorVec3 => "FloorVec3",
+            LpsOpCode::CeilVec3 => "CeilVec3",
+            LpsOpCode::FractVec3 => "FractVec3",
+            LpsOpCode::SqrtVec3 => "SqrtVec3",
             LpsOpCode::MulVec3 => "MulVec3",
             LpsOpCode::DivVec3 => "DivVec3",
             LpsOpCode::ModVec3 => "ModVec3",
@@ -330,9 +482,30 @@ impl LpsOpCode {
             LpsOpCode::Length3 => "Length3",
             LpsOpCode::Normalize3 => "Normalize3",
             LpsOpCode::Distance3 => "Distance3",
+            LpsOpCode::LengthSq3 => "LengthSq3",
+            LpsOpCode::DistanceSq3 => "DistanceSq3",
+            LpsOpCode::EqVec3 => "EqVec3",
+            LpsOpCode::LessVec3 => "LessVec3",
+            LpsOpCode::LessEqVec3 => "LessEqVec3",
+            LpsOpCode::EqCompVec3 => "EqCompVec3",
+            LpsOpCode::NotBVec3 => "NotBVec3",
+            LpsOpCode::Reflect3 => "Reflect3",
+            LpsOpCode::FaceForward3 => "FaceForward3",
+            LpsOpCode::MaxCompVec3 => "MaxCompVec3",
+            LpsOpCode::MinCompVec3 => "MinCompVec3",
+            LpsOpCode::SumVec3 => "SumVec3",
+            LpsOpCode::AvgVec3 => "AvgVec3",
+            LpsOpCode::HsvToRgb => "HsvToRgb",
+            LpsOpCode::RgbToHsv => "RgbToHsv",
             LpsOpCode::AddVec4 => "AddVec4",
             LpsOpCode::SubVec4 => "SubVec4",
             LpsOpCode::NegVec4 => "NegVec4",
+            LpsOpCode::AbsVec4 => "AbsVec4",
+            LpsOpCode::SignVec4 => "SignVec4",
+            LpsOpCode::FloorVec4 => "FloorVec4",
+            LpsOpCode::CeilVec4 => "CeilVec4",
+            LpsOpCode::FractVec4 => "FractVec4",
+            LpsOpCode::SqrtVec4 => "SqrtVec4",
             LpsOpCode::MulVec4 => "MulVec4",
             LpsOpCode::DivVec4 => "DivVec4",
             LpsOpCode::ModVec4 => "ModVec4",
@@ -342,6 +515,25 @@ impl LpsOpCode {
             LpsOpCode::Length4 => "Length4",
             LpsOpCode::Normalize4 => "Normalize4",
             LpsOpCode::Distance4 => "Distance4",
+            LpsOpCode::LengthSq4 => "LengthSq4",
+            LpsOpCode::DistanceSq4 => "DistanceSq4",
+            LpsOpCode::EqVec4 => "EqVec4",
+            LpsOpCode::LessVec4 => "LessVec4",
+            LpsOpCode::LessEqVec4 => "LessEqVec4",
+            LpsOpCode::EqCompVec4 => "EqCompVec4",
+            LpsOpCode::NotBVec4 => "NotBVec4",
+            LpsOpCode::Reflect4 => "Reflect4",
+            LpsOpCode::FaceForward4 => "FaceForward4",
+            LpsOpCode::MaxCompVec4 => "MaxCompVec4",
+            LpsOpCode::MinCompVec4 => "MinCompVec4",
+            LpsOpCode::SumVec4 => "SumVec4",
+            LpsOpCode::AvgVec4 => "AvgVec4",
+            LpsOpCode::RotateMat2 => "RotateMat2",
+            LpsOpCode::MulMat2 => "MulMat2",
+            LpsOpCode::MulMat2Vec2 => "MulMat2Vec2",
+            LpsOpCode::TransposeMat2 => "TransposeMat2",
+            LpsOpCode::DeterminantMat2 => "DeterminantMat2",
+            LpsOpCode::InverseMat2 => "InverseMat2",
             LpsOpCode::AddMat3 => "AddMat3",
             LpsOpCode::SubMat3 => "SubMat3",
             LpsOpCode::NegMat3 => "NegMat3",
@@ -352,6 +544,16 @@ impl LpsOpCode {
             LpsOpCode::TransposeMat3 => "TransposeMat3",
             LpsOpCode::DeterminantMat3 => "DeterminantMat3",
             LpsOpCode::InverseMat3 => "InverseMat3",
+            LpsOpCode::AddMat4 => "AddMat4",
+            LpsOpCode::SubMat4 => "SubMat4",
+            LpsOpCode::NegMat4 => "NegMat4",
+            LpsOpCode::MulMat4 => "MulMat4",
+            LpsOpCode::MulMat4Scalar => "MulMat4Scalar",
+            LpsOpCode::DivMat4Scalar => "DivMat4Scalar",
+            LpsOpCode::MulMat4Vec4 => "MulMat4Vec4",
+            LpsOpCode::TransposeMat4 => "TransposeMat4",
+            LpsOpCode::DeterminantMat4 => "DeterminantMat4",
+            LpsOpCode::InverseMat4 => "InverseMat4",
             LpsOpCode::Swizzle3to2(_, _) => "Swizzle3to2",
             LpsOpCode::Swizzle3to3(_, _, _) => "Swizzle3to3",
             LpsOpCode::Swizzle4to2(_, _) => "Swizzle4to2",
@@ -359,6 +561,8 @@ impl LpsOpCode {
             LpsOpCode::Swizzle4to4(_, _, _, _) => "Swizzle4to4",
             LpsOpCode::TextureSampleR(_) => "TextureSampleR",
             LpsOpCode::TextureSampleRGBA(_) => "TextureSampleRGBA",
+            LpsOpCode::TextureMixRGBA(..) => "TextureMixRGBA",
+            LpsOpCode::PaletteSample => "PaletteSample",
             LpsOpCode::LoadLocalFixed(_) => "LoadLocalFixed",
             LpsOpCode::StoreLocalFixed(_) => "StoreLocalFixed",
             LpsOpCode::LoadLocalInt32(_) => "LoadLocalInt32",
@@ -369,17 +573,28 @@ impl LpsOpCode {
             LpsOpCode::StoreLocalVec3(_) => "StoreLocalVec3",
             LpsOpCode::LoadLocalVec4(_) => "LoadLocalVec4",
             LpsOpCode::StoreLocalVec4(_) => "StoreLocalVec4",
+            LpsOpCode::LoadLocalMat2(_) => "LoadLocalMat2",
+            LpsOpCode::StoreLocalMat2(_) => "StoreLocalMat2",
             LpsOpCode::LoadLocalMat3(_) => "LoadLocalMat3",
             LpsOpCode::StoreLocalMat3(_) => "StoreLocalMat3",
+            LpsOpCode::LoadLocalMat4(_) => "LoadLocalMat4",
+            LpsOpCode::StoreLocalMat4(_) => "StoreLocalMat4",
+            LpsOpCode::LoadLocalElemFixed(_) => "LoadLocalElemFixed",
+            LpsOpCode::StoreLocalElemFixed(_) => "StoreLocalElemFixed",
+            LpsOpCode::CheckIndex(_) => "CheckIndex",
             LpsOpCode::GetElemInt32ArrayFixed => "GetElemInt32ArrayFixed",
             LpsOpCode::GetElemInt32ArrayU8 => "GetElemInt32ArrayU8",
             LpsOpCode::Jump(_) => "Jump",
             LpsOpCode::JumpIfZero(_) => "JumpIfZero",
             LpsOpCode::JumpIfNonZero(_) => "JumpIfNonZero",
+            LpsOpCode::JumpTable(_) => "JumpTable",
             LpsOpCode::Select => "Select",
             LpsOpCode::Call(_) => "Call",
+            LpsOpCode::CallNative(_) => "CallNative",
             LpsOpCode::Return => "Return",
             LpsOpCode::Load(_) => "Load",
+            LpsOpCode::LoadIndex => "LoadIndex",
+            LpsOpCode::LoadExternal(_) => "LoadExternal",
         }
     }
 }
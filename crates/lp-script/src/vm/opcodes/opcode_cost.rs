@@ -0,0 +1,116 @@
+/// Rough per-opcode execution cost, see `LpsProgram::estimate_cost`
+use super::LpsOpCode;
+
+impl LpsOpCode {
+    /// Relative cost of executing this opcode once, with `AddFixed` as 1
+    ///
+    /// These are ballpark figures for comparing programs, not cycle counts.
+    pub fn cost(&self) -> u32 {
+        match self {
+            LpsOpCode::MulFixed | LpsOpCode::MulInt32 | LpsOpCode::LerpFixed => 2,
+            LpsOpCode::DivFixed
+            | LpsOpCode::DivInt32
+            | LpsOpCode::ModInt32
+            | LpsOpCode::SmoothstepFixed
+            | LpsOpCode::RemapFixed
+            | LpsOpCode::QuantizeFixed
+            | LpsOpCode::Call(_)
+            | LpsOpCode::CallNative(_) => 4,
+            LpsOpCode::ModFixed | LpsOpCode::WrapFixed | LpsOpCode::PhaseTime => 6,
+            LpsOpCode::SinFixed | LpsOpCode::CosFixed | LpsOpCode::SqrtFixed => 8,
+            LpsOpCode::TanFixed
+            | LpsOpCode::AtanFixed
+            | LpsOpCode::Atan2Fixed
+            | LpsOpCode::PowFixed => 12,
+
+            LpsOpCode::MulVec2 | LpsOpCode::MulVec2Scalar | LpsOpCode::Dot2 => 4,
+            LpsOpCode::MulVec3 | LpsOpCode::MulVec3Scalar | LpsOpCode::Dot3 => 6,
+            LpsOpCode::MulVec4 | LpsOpCode::MulVec4Scalar | LpsOpCode::Dot4 => 8,
+            LpsOpCode::DivVec2 | LpsOpCode::DivVec2Scalar | LpsOpCode::Reflect2 => 8,
+            LpsOpCode::DivVec3 | LpsOpCode::DivVec3Scalar | LpsOpCode::Reflect3 => 12,
+            LpsOpCode::DivVec4 | LpsOpCode::DivVec4Scalar | LpsOpCode::Reflect4 => 16,
+            LpsOpCode::ModVec2 | LpsOpCode::Cross3 | LpsOpCode::HsvToRgb => 12,
+            // Three divisions
+            LpsOpCode::RgbToHsv => 14,
+            LpsOpCode::ModVec3 => 18,
+            LpsOpCode::ModVec4 => 24,
+            LpsOpCode::Length2 | LpsOpCode::Distance2 => 12,
+            LpsOpCode::Length3 | LpsOpCode::Distance3 => 14,
+            LpsOpCode::Length4 | LpsOpCode::Distance4 => 16,
+            // A dot product, without the sqrt
+            LpsOpCode::LengthSq2 | LpsOpCode::DistanceSq2 => 4,
+            LpsOpCode::LengthSq3 | LpsOpCode::DistanceSq3 => 6,
+            LpsOpCode::LengthSq4 | LpsOpCode::DistanceSq4 => 8,
+            LpsOpCode::AbsVec2
+            | LpsOpCode::SignVec2
+            | LpsOpCode::FloorVec2
+            | LpsOpCode::CeilVec2
+            | LpsOpCode::FractVec2
+            | LpsOpCode::LessVec2
+            | LpsOpCode::LessEqVec2
+            | LpsOpCode::EqCompVec2
+            | LpsOpCode::NotBVec2 => 2,
+            LpsOpCode::AbsVec3
+            | LpsOpCode::SignVec3
+            | LpsOpCode::FloorVec3
+            | LpsOpCode::CeilVec3
+            | LpsOpCode::FractVec3
+            | LpsOpCode::LessVec3
+            | LpsOpCode::LessEqVec3
+            | LpsOpCode::EqCompVec3
+            | LpsOpCode::NotBVec3 => 3,
+            LpsOpCode::AbsVec4
+            | LpsOpCode::SignVec4
+            | LpsOpCode::FloorVec4
+            | LpsOpCode::CeilVec4
+            | LpsOpCode::FractVec4
+            | LpsOpCode::LessVec4
+            | LpsOpCode::LessEqVec4
+            | LpsOpCode::EqCompVec4
+            | LpsOpCode::NotBVec4 => 4,
+            LpsOpCode::SqrtVec2 => 16,
+            LpsOpCode::SqrtVec3 => 24,
+            LpsOpCode::SqrtVec4 => 32,
+            LpsOpCode::Normalize2 => 20,
+            LpsOpCode::Normalize3 => 26,
+            LpsOpCode::Normalize4 => 32,
+            LpsOpCode::ToPolarVec2 | LpsOpCode::FromPolarVec2 => 24,
+            LpsOpCode::TileVec2 | LpsOpCode::MirrorTileVec2 => 6,
+
+            LpsOpCode::DeterminantMat2 => 4,
+            LpsOpCode::MulMat2Vec2 => 8,
+            LpsOpCode::InverseMat2 => 12,
+            LpsOpCode::RotateMat2 | LpsOpCode::MulMat2 => 16,
+            LpsOpCode::MulMat3Scalar | LpsOpCode::MulMat3Vec3 => 18,
+            LpsOpCode::DivMat3Scalar | LpsOpCode::DeterminantMat3 => 36,
+            LpsOpCode::MulMat3 => 54,
+            LpsOpCode::InverseMat3 => 80,
+            LpsOpCode::MulMat4Scalar | LpsOpCode::MulMat4Vec4 => 32,
+            LpsOpCode::DivMat4Scalar => 64,
+            LpsOpCode::DeterminantMat4 => 88,
+            LpsOpCode::MulMat4 => 128,
+            LpsOpCode::InverseMat4 => 240,
+
+            LpsOpCode::TextureSampleR(_) | LpsOpCode::PaletteSample => 12,
+            LpsOpCode::TextureSampleRGBA(_) => 20,
+            LpsOpCode::TextureMixRGBA(..) => 44,
+            LpsOpCode::Perlin3(octaves) => 60 * (*octaves).max(1) as u32,
+
+            _ => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cost_orders_common_opcodes() {
+        assert_eq!(LpsOpCode::AddFixed.cost(), 1);
+        assert!(LpsOpCode::SinFixed.cost() > LpsOpCode::MulFixed.cost());
+        assert!(LpsOpCode::InverseMat3.cost() > LpsOpCode::MulMat3Vec3.cost());
+        assert!(LpsOpCode::InverseMat4.cost() > LpsOpCode::InverseMat3.cost());
+        assert!(LpsOpCode::Perlin3(3).cost() > LpsOpCode::Perlin3(1).cost());
+    }
+}
@@ -0,0 +1,160 @@
+/// Opcode groups a size-constrained VM build can leave out
+use super::LpsOpCode;
+
+/// An optional family of opcodes
+///
+/// Opcodes outside every group (stack, scalar and vector math, control flow,
+/// locals other than matrices) are always available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpcodeGroup {
+    /// `mat2`, `mat3` and `mat4` arithmetic, locals and 9- and 16-wide stack ops
+    Mat3,
+    /// `TextureSample*`
+    Texture,
+    /// `GetElem*` array reads
+    Array,
+    /// `Perlin3`
+    Noise,
+    /// `PaletteSample`
+    Palette,
+}
+
+impl OpcodeGroup {
+    pub const ALL: [OpcodeGroup; 5] = [
+        OpcodeGroup::Mat3,
+        OpcodeGroup::Texture,
+        OpcodeGroup::Array,
+        OpcodeGroup::Noise,
+        OpcodeGroup::Palette,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OpcodeGroup::Mat3 => "matrices",
+            OpcodeGroup::Texture => "textures",
+            OpcodeGroup::Array => "arrays",
+            OpcodeGroup::Noise => "noise",
+            OpcodeGroup::Palette => "palette",
+        }
+    }
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// The opcode groups a compiled program may use
+///
+/// Set `OptimizeOptions::allowed_opcodes` to make compilation fail when a
+/// script needs an opcode the target VM doesn't dispatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpcodeSet {
+    groups: u8,
+}
+
+impl OpcodeSet {
+    /// Every opcode
+    pub const fn all() -> Self {
+        OpcodeSet { groups: u8::MAX }
+    }
+
+    /// Only the opcodes outside every `OpcodeGroup`
+    pub const fn core() -> Self {
+        OpcodeSet { groups: 0 }
+    }
+
+    pub const fn with(self, group: OpcodeGroup) -> Self {
+        OpcodeSet {
+            groups: self.groups | group.bit(),
+        }
+    }
+
+    pub const fn without(self, group: OpcodeGroup) -> Self {
+        OpcodeSet {
+            groups: self.groups & !group.bit(),
+        }
+    }
+
+    pub const fn contains_group(&self, group: OpcodeGroup) -> bool {
+        self.groups & group.bit() != 0
+    }
+
+    /// Whether `op` may appear in a program compiled for this set
+    pub fn allows(&self, op: &LpsOpCode) -> bool {
+        op.group().is_none_or(|group| self.contains_group(group))
+    }
+}
+
+impl Default for OpcodeSet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl LpsOpCode {
+    /// The optional group this opcode belongs to, or `None` for core opcodes
+    pub fn group(&self) -> Option<OpcodeGroup> {
+        match self {
+            LpsOpCode::Dup9
+            | LpsOpCode::Drop9
+            | LpsOpCode::Dup16
+            | LpsOpCode::Drop16
+            | LpsOpCode::RotateMat2
+            | LpsOpCode::MulMat2
+            | LpsOpCode::MulMat2Vec2
+            | LpsOpCode::TransposeMat2
+            | LpsOpCode::DeterminantMat2
+            | LpsOpCode::InverseMat2
+            | LpsOpCode::AddMat3
+            | LpsOpCode::SubMat3
+            | LpsOpCode::NegMat3
+            | LpsOpCode::MulMat3
+            | LpsOpCode::MulMat3Scalar
+            | LpsOpCode::DivMat3Scalar
+            | LpsOpCode::MulMat3Vec3
+            | LpsOpCode::TransposeMat3
+            | LpsOpCode::DeterminantMat3
+            | LpsOpCode::InverseMat3
+            | LpsOpCode::AddMat4
+            | LpsOpCode::SubMat4
+            | LpsOpCode::NegMat4
+            | LpsOpCode::MulMat4
+            | LpsOpCode::MulMat4Scalar
+            | LpsOpCode::DivMat4Scalar
+            | LpsOpCode::MulMat4Vec4
+            | LpsOpCode::TransposeMat4
+            | LpsOpCode::DeterminantMat4
+            | LpsOpCode::InverseMat4
+            | LpsOpCode::LoadLocalMat2(_)
+            | LpsOpCode::StoreLocalMat2(_)
+            | LpsOpCode::LoadLocalMat3(_)
+            | LpsOpCode::StoreLocalMat3(_)
+            | LpsOpCode::LoadLocalMat4(_)
+            | LpsOpCode::StoreLocalMat4(_) => Some(OpcodeGroup::Mat3),
+            LpsOpCode::TextureSampleR(_)
+            | LpsOpCode::TextureSampleRGBA(_)
+            | LpsOpCode::TextureMixRGBA(..) => Some(OpcodeGroup::Texture),
+            LpsOpCode::GetElemInt32ArrayFixed | LpsOpCode::GetElemInt32ArrayU8 => {
+                Some(OpcodeGroup::Array)
+            }
+            LpsOpCode::Perlin3(_) => Some(OpcodeGroup::Noise),
+            LpsOpCode::PaletteSample => Some(OpcodeGroup::Palette),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_set_rejects_grouped_opcodes() {
+        let set = OpcodeSet::core().with(OpcodeGroup::Noise);
+        assert!(set.allows(&LpsOpCode::AddFixed));
+        assert!(set.allows(&LpsOpCode::Perlin3(3)));
+        assert!(!set.allows(&LpsOpCode::MulMat3));
+        assert!(!set.allows(&LpsOpCode::PaletteSample));
+        assert!(OpcodeSet::all().allows(&LpsOpCode::MulMat3));
+    }
+}
@@ -0,0 +1,123 @@
+/// Per-opcode stack effects, see `LpsProgram::required_limits`
+use super::LpsOpCode;
+
+impl LpsOpCode {
+    /// Stack slots popped and then pushed by one execution
+    ///
+    /// `None` for `Call`, `CallNative` and `LoadExternal`, whose effect
+    /// depends on the callee's signature or the external's type.
+    pub fn stack_effect(&self) -> Option<(usize, usize)> {
+        use LpsOpCode::*;
+
+        let effect = match self {
+            Push(_) | PushInt32(_) | Load(_) | LoadIndex => (0, 1),
+            Dup1 => (1, 2),
+            Dup2 => (2, 4),
+            Dup3 => (3, 6),
+            Dup4 => (4, 8),
+            Dup9 => (9, 18),
+            Dup16 => (16, 32),
+            Drop1 => (1, 0),
+            Drop2 => (2, 0),
+            Drop3 => (3, 0),
+            Drop4 => (4, 0),
+            Drop9 => (9, 0),
+            Drop16 => (16, 0),
+            Swap => (2, 2),
+
+            NegFixed | AbsFixed | SinFixed | CosFixed | TanFixed | AtanFixed | SqrtFixed
+            | FloorFixed | CeilFixed | FractFixed | PhaseTime | SignFixed | SaturateFixed
+            | NotFixed | NegInt32 | AbsInt32 | BitwiseNotInt32 | Int32ToFixed | FixedToInt32
+            | CheckIndex(_) => (1, 1),
+            AddFixed | SubFixed | MulFixed | DivFixed | MinFixed | MaxFixed | Atan2Fixed
+            | ModFixed | WrapFixed | QuantizeFixed | PowFixed | StepFixed | GreaterFixed
+            | LessFixed | GreaterEqFixed | LessEqFixed | EqFixed | NotEqFixed | AndFixed
+            | OrFixed | AddInt32 | SubInt32 | MulInt32 | DivInt32 | ModInt32 | MinInt32
+            | MaxInt32 | GreaterInt32 | LessInt32 | GreaterEqInt32 | LessEqInt32 | EqInt32
+            | NotEqInt32 | BitwiseAndInt32 | BitwiseOrInt32 | BitwiseXorInt32 | LeftShiftInt32
+            | RightShiftInt32 => (2, 1),
+            ClampFixed | LerpFixed | SmoothstepFixed | Perlin3(_) | Select => (3, 1),
+            RemapFixed => (5, 1),
+
+            NegVec2 | AbsVec2 | SignVec2 | FloorVec2 | CeilVec2 | FractVec2 | SqrtVec2
+            | Normalize2 | ToPolarVec2 | FromPolarVec2 => (2, 2),
+            Length2 | LengthSq2 | MaxCompVec2 | MinCompVec2 | SumVec2 | AvgVec2 => (2, 1),
+            AddVec2 | SubVec2 | MulVec2 | DivVec2 | ModVec2 | Reflect2 => (4, 2),
+            Dot2 | Distance2 | DistanceSq2 | EqVec2 => (4, 1),
+            LessVec2 | LessEqVec2 | EqCompVec2 => (4, 2),
+            NotBVec2 => (2, 2),
+            MulVec2Scalar | DivVec2Scalar | TileVec2 | MirrorTileVec2 => (3, 2),
+            FaceForward2 => (6, 2),
+
+            NegVec3 | AbsVec3 | SignVec3 | FloorVec3 | CeilVec3 | FractVec3 | SqrtVec3
+            | Normalize3 | HsvToRgb | RgbToHsv => (3, 3),
+            Length3 | LengthSq3 | MaxCompVec3 | MinCompVec3 | SumVec3 | AvgVec3 => (3, 1),
+            AddVec3 | SubVec3 | MulVec3 | DivVec3 | ModVec3 | Cross3 | Reflect3 => (6, 3),
+            Dot3 | Distance3 | DistanceSq3 | EqVec3 => (6, 1),
+            LessVec3 | LessEqVec3 | EqCompVec3 => (6, 3),
+            NotBVec3 => (3, 3),
+            MulVec3Scalar | DivVec3Scalar => (4, 3),
+            FaceForward3 => (9, 3),
+
+            NegVec4 | AbsVec4 | SignVec4 | FloorVec4 | CeilVec4 | FractVec4 | SqrtVec4
+            | Normalize4 => (4, 4),
+            Length4 | LengthSq4 | MaxCompVec4 | MinCompVec4 | SumVec4 | AvgVec4 => (4, 1),
+            AddVec4 | SubVec4 | MulVec4 | DivVec4 | ModVec4 | Reflect4 | MulMat2 => (8, 4),
+            Dot4 | Distance4 | DistanceSq4 | EqVec4 => (8, 1),
+            LessVec4 | LessEqVec4 | EqCompVec4 => (8, 4),
+            NotBVec4 => (4, 4),
+            MulVec4Scalar | DivVec4Scalar => (5, 4),
+            FaceForward4 => (12, 4),
+
+            RotateMat2 => (1, 4),
+            MulMat2Vec2 => (6, 2),
+            TransposeMat2 | InverseMat2 => (4, 4),
+            DeterminantMat2 => (4, 1),
+            AddMat3 | SubMat3 | MulMat3 => (18, 9),
+            NegMat3 | TransposeMat3 | InverseMat3 => (9, 9),
+            MulMat3Scalar | DivMat3Scalar => (10, 9),
+            MulMat3Vec3 => (12, 3),
+            DeterminantMat3 => (9, 1),
+            AddMat4 | SubMat4 | MulMat4 => (32, 16),
+            NegMat4 | TransposeMat4 | InverseMat4 => (16, 16),
+            MulMat4Scalar | DivMat4Scalar => (17, 16),
+            MulMat4Vec4 => (20, 4),
+            DeterminantMat4 => (16, 1),
+
+            Swizzle3to2(..) => (3, 2),
+            Swizzle3to3(..) => (3, 3),
+            Swizzle4to2(..) => (4, 2),
+            Swizzle4to3(..) => (4, 3),
+            Swizzle4to4(..) => (4, 4),
+
+            TextureSampleR(_) => (2, 1),
+            TextureSampleRGBA(_) => (2, 4),
+            TextureMixRGBA(..) => (3, 4),
+            PaletteSample => (1, 3),
+            GetElemInt32ArrayFixed => (2, 1),
+            GetElemInt32ArrayU8 => (2, 4),
+
+            LoadLocalFixed(_) | LoadLocalInt32(_) => (0, 1),
+            LoadLocalVec2(_) => (0, 2),
+            LoadLocalVec3(_) => (0, 3),
+            LoadLocalVec4(_) | LoadLocalMat2(_) => (0, 4),
+            LoadLocalMat3(_) => (0, 9),
+            LoadLocalMat4(_) => (0, 16),
+            StoreLocalFixed(_) | StoreLocalInt32(_) => (1, 0),
+            StoreLocalVec2(_) => (2, 0),
+            StoreLocalVec3(_) => (3, 0),
+            StoreLocalVec4(_) | StoreLocalMat2(_) => (4, 0),
+            StoreLocalMat3(_) => (9, 0),
+            StoreLocalMat4(_) => (16, 0),
+            LoadLocalElemFixed(_) => (1, 1),
+            StoreLocalElemFixed(_) => (2, 0),
+
+            Jump(_) | Return => (0, 0),
+            JumpIfZero(_) | JumpIfNonZero(_) => (1, 0),
+            JumpTable(_) => (2, 0),
+
+            Call(_) | CallNative(_) | LoadExternal(_) => return None,
+        };
+        Some(effect)
+    }
+}
@@ -1,6 +1,8 @@
-use crate::fixed::Fixed;
-/// Texture sampling opcodes (stub implementations)
+use crate::fixed::{lerp, Fixed, Vec3, Vec4};
+/// Texture sampling opcodes (stub implementations, except the palette and
+/// `TextureMixRGBA`)
 use crate::vm::error::LpsVmError;
+use crate::vm::texture::Texture;
 use crate::vm::value_stack::ValueStack;
 
 /// Execute TextureSampleR: pop 2 Fixed (UV), push 1 Fixed (R)
@@ -39,6 +41,66 @@ pub fn exec_texture_sample_rgba(
     Ok(())
 }
 
+/// Execute TextureMixRGBA: pop 3 Fixed (UV, t), push 4 Fixed (RGBA)
+///
+/// Samples slots `a` and `b` at the same UV and interpolates linearly from
+/// `a` (t = 0) to `b` (t = 1).
+#[inline(always)]
+pub fn exec_texture_mix_rgba(
+    stack: &mut ValueStack,
+    textures: &[Option<Texture>],
+    a: u32,
+    b: u32,
+) -> Result<(), LpsVmError> {
+    let t = stack.pop_fixed()?;
+    let uv = stack.pop_vec2()?;
+    let bound = |idx: u32| {
+        textures
+            .get(idx as usize)
+            .copied()
+            .flatten()
+            .ok_or(LpsVmError::UnboundTexture {
+                texture_idx: idx as usize,
+            })
+    };
+    let (from, to) = (bound(a)?.sample(uv), bound(b)?.sample(uv));
+    stack.push_vec4(Vec4::new(
+        lerp(from.x, to.x, t),
+        lerp(from.y, to.y, t),
+        lerp(from.z, to.z, t),
+        lerp(from.w, to.w, t),
+    ))?;
+    Ok(())
+}
+
+/// Execute PaletteSample: pop 1 Fixed (t), push 3 Fixed (RGB)
+///
+/// The palette is a 1xN strip of evenly spaced stops; `t` is clamped to 0..1
+/// and interpolated linearly between the two nearest stops.
+#[inline(always)]
+pub fn exec_palette_sample(stack: &mut ValueStack, palette: &[Vec3]) -> Result<(), LpsVmError> {
+    let t = stack.pop_fixed()?;
+    let color = sample_palette(palette, t).ok_or(LpsVmError::PaletteNotBound)?;
+    stack.push_vec3(color)?;
+    Ok(())
+}
+
+fn sample_palette(palette: &[Vec3], t: Fixed) -> Option<Vec3> {
+    let last = palette.len().checked_sub(1)?;
+    let pos = t.clamp(Fixed::ZERO, Fixed::ONE).mul_int(last as i32);
+    let idx = (pos.to_i32() as usize).min(last);
+    if idx == last {
+        return Some(palette[last]);
+    }
+
+    let (a, b, f) = (palette[idx], palette[idx + 1], pos.frac());
+    Some(Vec3::new(
+        lerp(a.x, b.x, f),
+        lerp(a.y, b.y, f),
+        lerp(a.z, b.z, f),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +151,29 @@ mod tests {
             })
         ));
     }
+
+    #[test]
+    fn test_palette_sample_interpolates_and_clamps() {
+        let palette = [Vec3::from_f32(0.0, 0.0, 0.0), Vec3::from_f32(1.0, 0.5, 0.0)];
+        let sample = |t: f32| {
+            let mut stack = ValueStack::new(64);
+            stack.push_fixed(t.to_fixed()).unwrap();
+            exec_palette_sample(&mut stack, &palette).unwrap();
+            stack.pop_vec3().unwrap()
+        };
+
+        assert_eq!(sample(0.5), Vec3::from_f32(0.5, 0.25, 0.0));
+        assert_eq!(sample(-3.0), palette[0]);
+        assert_eq!(sample(7.0), palette[1]);
+    }
+
+    #[test]
+    fn test_palette_sample_unbound() {
+        let mut stack = ValueStack::new(64);
+        stack.push_fixed(Fixed::HALF).unwrap();
+        assert!(matches!(
+            exec_palette_sample(&mut stack, &[]),
+            Err(LpsVmError::PaletteNotBound)
+        ));
+    }
 }
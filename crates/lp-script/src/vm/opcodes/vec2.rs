@@ -1,6 +1,7 @@
-use crate::fixed::{modulo, Vec2};
+use crate::fixed::{modulo, Fixed, Vec2};
 /// Vec2 operations
 use crate::vm::error::LpsVmError;
+use crate::vm::normalize_fallback::NormalizeFallback;
 use crate::vm::value_stack::ValueStack;
 
 #[inline(always)]
@@ -26,6 +27,14 @@ pub fn exec_neg_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     Ok(())
 }
 
+/// Apply a scalar function to each component, e.g. `abs` for `AbsVec2`
+#[inline(always)]
+pub fn exec_map_vec2(stack: &mut ValueStack, f: fn(Fixed) -> Fixed) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec2()?;
+    stack.push_vec2(Vec2::new(f(a.x), f(a.y)))?;
+    Ok(())
+}
+
 #[inline(always)]
 pub fn exec_mul_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     let b = stack.pop_vec2()?;
@@ -42,6 +51,109 @@ pub fn exec_div_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     Ok(())
 }
 
+#[inline(always)]
+pub fn exec_reflect2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let n = stack.pop_vec2()?;
+    let i = stack.pop_vec2()?;
+    stack.push_vec2(i.reflect(n))?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_faceforward2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let nref = stack.pop_vec2()?;
+    let i = stack.pop_vec2()?;
+    let n = stack.pop_vec2()?;
+    stack.push_vec2(n.faceforward(i, nref))?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_max_comp_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec2()?;
+    stack.push_fixed(a.max_component())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_min_comp_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec2()?;
+    stack.push_fixed(a.min_component())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_sum_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec2()?;
+    stack.push_fixed(a.sum())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_avg_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec2()?;
+    stack.push_fixed(a.average())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_to_polar_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec2()?;
+    stack.push_vec2(a.to_polar())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_from_polar_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec2()?;
+    stack.push_vec2(a.from_polar())?;
+    Ok(())
+}
+
+/// `uv * repeats` wrapped into `[0, 1)` per component
+///
+/// The product is taken at full width and wrapped on the raw value, so the
+/// result runs up to the last step below 1 and restarts at exactly 0 on
+/// every seam, negative coordinates included.
+#[inline(always)]
+pub fn exec_tile_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let repeats = stack.pop_fixed()?;
+    let uv = stack.pop_vec2()?;
+    let tile = |c: Fixed| Fixed(scaled(c, repeats).rem_euclid(Fixed::ONE.0 as i64) as i32);
+    stack.push_vec2(Vec2::new(tile(uv.x), tile(uv.y)))?;
+    Ok(())
+}
+
+/// Like `exec_tile_vec2`, but every other tile is mirrored, so the result
+/// runs 0 → 1 → 0 with no jump at the seams
+#[inline(always)]
+pub fn exec_mirror_tile_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let repeats = stack.pop_fixed()?;
+    let uv = stack.pop_vec2()?;
+    let one = Fixed::ONE.0 as i64;
+    let mirror = |c: Fixed| {
+        let t = scaled(c, repeats).rem_euclid(2 * one);
+        Fixed(if t > one { 2 * one - t } else { t } as i32)
+    };
+    stack.push_vec2(Vec2::new(mirror(uv.x), mirror(uv.y)))?;
+    Ok(())
+}
+
+/// Raw `a * b` without the 32-bit overflow of `Fixed` multiplication
+#[inline(always)]
+fn scaled(a: Fixed, b: Fixed) -> i64 {
+    (a.0 as i64 * b.0 as i64) >> Fixed::SHIFT
+}
+
+/// Exact component-wise equality (no epsilon), pushes 1.0 or 0.0
+#[inline(always)]
+pub fn exec_eq_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let b = stack.pop_vec2()?;
+    let a = stack.pop_vec2()?;
+    stack.push_fixed(if a == b { Fixed::ONE } else { Fixed::ZERO })?;
+    Ok(())
+}
+
 #[inline(always)]
 pub fn exec_mod_vec2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     let b = stack.pop_vec2()?;
@@ -82,9 +194,16 @@ pub fn exec_length2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
 }
 
 #[inline(always)]
-pub fn exec_normalize2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+pub fn exec_normalize2(
+    stack: &mut ValueStack,
+    fallback: Option<NormalizeFallback>,
+) -> Result<(), LpsVmError> {
     let a = stack.pop_vec2()?;
-    stack.push_vec2(a.normalize())?;
+    let result = match fallback {
+        Some(fallback) => fallback.normalize2(a),
+        None => a.normalize(),
+    };
+    stack.push_vec2(result)?;
     Ok(())
 }
 
@@ -95,3 +214,18 @@ pub fn exec_distance2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     stack.push_fixed(a.distance(b))?;
     Ok(())
 }
+
+#[inline(always)]
+pub fn exec_length_sq2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec2()?;
+    stack.push_fixed(a.length_squared())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_distance_sq2(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let b = stack.pop_vec2()?;
+    let a = stack.pop_vec2()?;
+    stack.push_fixed((a - b).length_squared())?;
+    Ok(())
+}
@@ -1,6 +1,7 @@
-use crate::fixed::{modulo, Vec3};
+use crate::fixed::{hsv2rgb, modulo, rgb2hsv, Fixed, Vec3};
 /// Vec3 operations
 use crate::vm::error::LpsVmError;
+use crate::vm::normalize_fallback::NormalizeFallback;
 use crate::vm::value_stack::ValueStack;
 
 #[inline(always)]
@@ -26,6 +27,14 @@ pub fn exec_neg_vec3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     Ok(())
 }
 
+/// Apply a scalar function to each component, e.g. `abs` for `AbsVec3`
+#[inline(always)]
+pub fn exec_map_vec3(stack: &mut ValueStack, f: fn(Fixed) -> Fixed) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec3()?;
+    stack.push_vec3(Vec3::new(f(a.x), f(a.y), f(a.z)))?;
+    Ok(())
+}
+
 #[inline(always)]
 pub fn exec_mul_vec3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     let b = stack.pop_vec3()?;
@@ -42,6 +51,74 @@ pub fn exec_div_vec3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     Ok(())
 }
 
+#[inline(always)]
+pub fn exec_reflect3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let n = stack.pop_vec3()?;
+    let i = stack.pop_vec3()?;
+    stack.push_vec3(i.reflect(n))?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_faceforward3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let nref = stack.pop_vec3()?;
+    let i = stack.pop_vec3()?;
+    let n = stack.pop_vec3()?;
+    stack.push_vec3(n.faceforward(i, nref))?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_max_comp_vec3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec3()?;
+    stack.push_fixed(a.max_component())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_min_comp_vec3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec3()?;
+    stack.push_fixed(a.min_component())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_sum_vec3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec3()?;
+    stack.push_fixed(a.sum())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_avg_vec3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec3()?;
+    stack.push_fixed(a.average())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_hsv_to_rgb(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec3()?;
+    stack.push_vec3(hsv2rgb(a))?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_rgb_to_hsv(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec3()?;
+    stack.push_vec3(rgb2hsv(a))?;
+    Ok(())
+}
+
+/// Exact component-wise equality (no epsilon), pushes 1.0 or 0.0
+#[inline(always)]
+pub fn exec_eq_vec3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let b = stack.pop_vec3()?;
+    let a = stack.pop_vec3()?;
+    stack.push_fixed(if a == b { Fixed::ONE } else { Fixed::ZERO })?;
+    Ok(())
+}
+
 #[inline(always)]
 pub fn exec_mod_vec3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     let b = stack.pop_vec3()?;
@@ -94,9 +171,16 @@ pub fn exec_length3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
 }
 
 #[inline(always)]
-pub fn exec_normalize3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+pub fn exec_normalize3(
+    stack: &mut ValueStack,
+    fallback: Option<NormalizeFallback>,
+) -> Result<(), LpsVmError> {
     let a = stack.pop_vec3()?;
-    stack.push_vec3(a.normalize())?;
+    let result = match fallback {
+        Some(fallback) => fallback.normalize3(a),
+        None => a.normalize(),
+    };
+    stack.push_vec3(result)?;
     Ok(())
 }
 
@@ -107,3 +191,18 @@ pub fn exec_distance3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     stack.push_fixed(a.distance(b))?;
     Ok(())
 }
+
+#[inline(always)]
+pub fn exec_length_sq3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec3()?;
+    stack.push_fixed(a.length_squared())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_distance_sq3(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let b = stack.pop_vec3()?;
+    let a = stack.pop_vec3()?;
+    stack.push_fixed((a - b).length_squared())?;
+    Ok(())
+}
@@ -1,6 +1,7 @@
-use crate::fixed::{modulo, Vec4};
+use crate::fixed::{modulo, Fixed, Vec4};
 /// Vec4 operations
 use crate::vm::error::LpsVmError;
+use crate::vm::normalize_fallback::NormalizeFallback;
 use crate::vm::value_stack::ValueStack;
 
 #[inline(always)]
@@ -26,6 +27,14 @@ pub fn exec_neg_vec4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     Ok(())
 }
 
+/// Apply a scalar function to each component, e.g. `abs` for `AbsVec4`
+#[inline(always)]
+pub fn exec_map_vec4(stack: &mut ValueStack, f: fn(Fixed) -> Fixed) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec4()?;
+    stack.push_vec4(Vec4::new(f(a.x), f(a.y), f(a.z), f(a.w)))?;
+    Ok(())
+}
+
 #[inline(always)]
 pub fn exec_mul_vec4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     let b = stack.pop_vec4()?;
@@ -42,6 +51,60 @@ pub fn exec_div_vec4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     Ok(())
 }
 
+#[inline(always)]
+pub fn exec_reflect4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let n = stack.pop_vec4()?;
+    let i = stack.pop_vec4()?;
+    stack.push_vec4(i.reflect(n))?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_faceforward4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let nref = stack.pop_vec4()?;
+    let i = stack.pop_vec4()?;
+    let n = stack.pop_vec4()?;
+    stack.push_vec4(n.faceforward(i, nref))?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_max_comp_vec4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec4()?;
+    stack.push_fixed(a.max_component())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_min_comp_vec4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec4()?;
+    stack.push_fixed(a.min_component())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_sum_vec4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec4()?;
+    stack.push_fixed(a.sum())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_avg_vec4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec4()?;
+    stack.push_fixed(a.average())?;
+    Ok(())
+}
+
+/// Exact component-wise equality (no epsilon), pushes 1.0 or 0.0
+#[inline(always)]
+pub fn exec_eq_vec4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let b = stack.pop_vec4()?;
+    let a = stack.pop_vec4()?;
+    stack.push_fixed(if a == b { Fixed::ONE } else { Fixed::ZERO })?;
+    Ok(())
+}
+
 #[inline(always)]
 pub fn exec_mod_vec4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     let b = stack.pop_vec4()?;
@@ -87,9 +150,16 @@ pub fn exec_length4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
 }
 
 #[inline(always)]
-pub fn exec_normalize4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+pub fn exec_normalize4(
+    stack: &mut ValueStack,
+    fallback: Option<NormalizeFallback>,
+) -> Result<(), LpsVmError> {
     let a = stack.pop_vec4()?;
-    stack.push_vec4(a.normalize())?;
+    let result = match fallback {
+        Some(fallback) => fallback.normalize4(a),
+        None => a.normalize(),
+    };
+    stack.push_vec4(result)?;
     Ok(())
 }
 
@@ -100,3 +170,18 @@ pub fn exec_distance4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
     stack.push_fixed(a.distance(b))?;
     Ok(())
 }
+
+#[inline(always)]
+pub fn exec_length_sq4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let a = stack.pop_vec4()?;
+    stack.push_fixed(a.length_squared())?;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn exec_distance_sq4(stack: &mut ValueStack) -> Result<(), LpsVmError> {
+    let b = stack.pop_vec4()?;
+    let a = stack.pop_vec4()?;
+    stack.push_fixed((a - b).length_squared())?;
+    Ok(())
+}
@@ -0,0 +1,130 @@
+/// Typed values passed between host code and the VM stack
+use crate::fixed::{Fixed, Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
+use crate::shared::Type;
+use crate::vm::error::LpsVmError;
+use crate::vm::value_stack::ValueStack;
+
+/// A value pushed by the host before a run, see `LpsVm::push_input`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputValue {
+    Fixed(Fixed),
+    Int32(i32),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+    Mat2(Mat2),
+    Mat3(Mat3),
+    Mat4(Mat4),
+}
+
+impl InputValue {
+    /// Number of stack slots the value occupies
+    pub fn slots(&self) -> usize {
+        match self {
+            InputValue::Fixed(_) | InputValue::Int32(_) => 1,
+            InputValue::Vec2(_) => 2,
+            InputValue::Vec3(_) => 3,
+            InputValue::Vec4(_) | InputValue::Mat2(_) => 4,
+            InputValue::Mat3(_) => 9,
+            InputValue::Mat4(_) => 16,
+        }
+    }
+
+    /// Whether the value can stand in for a value of type `ty`
+    ///
+    /// `bool` values are passed as `Int32`, `bvec`s as vectors of 0.0 / 1.0.
+    pub fn matches(&self, ty: &Type) -> bool {
+        matches!(
+            (self, ty),
+            (InputValue::Fixed(_), Type::Fixed)
+                | (InputValue::Int32(_), Type::Int32 | Type::Bool)
+                | (InputValue::Vec2(_), Type::Vec2 | Type::BVec2)
+                | (InputValue::Vec3(_), Type::Vec3 | Type::BVec3)
+                | (InputValue::Vec4(_), Type::Vec4 | Type::BVec4)
+                | (InputValue::Mat2(_), Type::Mat2)
+                | (InputValue::Mat3(_), Type::Mat3)
+                | (InputValue::Mat4(_), Type::Mat4)
+        )
+    }
+
+    /// Pop a value of type `ty` (`bool` as `Int32`, `bvec`s as vectors), e.g.
+    /// a native's argument
+    pub(in crate::vm) fn pop_from(ty: &Type, stack: &mut ValueStack) -> Result<Self, LpsVmError> {
+        let value = match ty {
+            Type::Fixed => InputValue::Fixed(stack.pop_fixed()?),
+            Type::Int32 | Type::Bool => InputValue::Int32(stack.pop_int32()?),
+            Type::Vec2 | Type::BVec2 => InputValue::Vec2(stack.pop_vec2()?),
+            Type::Vec3 | Type::BVec3 => InputValue::Vec3(stack.pop_vec3()?),
+            Type::Vec4 | Type::BVec4 => InputValue::Vec4(stack.pop_vec4()?),
+            Type::Mat2 => InputValue::Mat2(stack.pop_mat2()?),
+            Type::Mat3 => InputValue::Mat3(stack.pop_mat3()?),
+            Type::Mat4 => InputValue::Mat4(stack.pop_mat4()?),
+            Type::Grid { .. } | Type::Array { .. } | Type::Void => {
+                return Err(LpsVmError::TypeMismatch)
+            }
+        };
+        Ok(value)
+    }
+
+    pub(in crate::vm) fn push_to(self, stack: &mut ValueStack) -> Result<(), LpsVmError> {
+        match self {
+            InputValue::Fixed(v) => stack.push_fixed(v),
+            InputValue::Int32(v) => stack.push_int32(v),
+            InputValue::Vec2(v) => stack.push_vec2(v),
+            InputValue::Vec3(v) => stack.push_vec3(v),
+            InputValue::Vec4(v) => stack.push_vec4(v),
+            InputValue::Mat2(v) => stack.push_mat2(v),
+            InputValue::Mat3(v) => stack.push_mat3(v),
+            InputValue::Mat4(v) => stack.push_mat4(v),
+        }
+    }
+}
+
+/// A result popped by the host after a run, see `LpsVm::pop_result`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputValue {
+    Fixed(Fixed),
+    Int32(i32),
+    Bool(bool),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+    BVec2([bool; 2]),
+    BVec3([bool; 3]),
+    BVec4([bool; 4]),
+    Mat2(Mat2),
+    Mat3(Mat3),
+    Mat4(Mat4),
+}
+
+impl OutputValue {
+    /// Pop a value of type `ty`, or `None` if the stack is too shallow or
+    /// `ty` has no stack value (`void`, grids, arrays)
+    pub(in crate::vm) fn pop_from(ty: &Type, stack: &mut ValueStack) -> Option<Self> {
+        let value = match ty {
+            Type::Fixed => OutputValue::Fixed(stack.pop_fixed().ok()?),
+            Type::Int32 => OutputValue::Int32(stack.pop_int32().ok()?),
+            Type::Bool => OutputValue::Bool(stack.pop_int32().ok()? != 0),
+            Type::Vec2 => OutputValue::Vec2(stack.pop_vec2().ok()?),
+            Type::Vec3 => OutputValue::Vec3(stack.pop_vec3().ok()?),
+            Type::Vec4 => OutputValue::Vec4(stack.pop_vec4().ok()?),
+            Type::BVec2 => OutputValue::BVec2(pop_bools(stack)?),
+            Type::BVec3 => OutputValue::BVec3(pop_bools(stack)?),
+            Type::BVec4 => OutputValue::BVec4(pop_bools(stack)?),
+            Type::Mat2 => OutputValue::Mat2(stack.pop_mat2().ok()?),
+            Type::Mat3 => OutputValue::Mat3(stack.pop_mat3().ok()?),
+            Type::Mat4 => OutputValue::Mat4(stack.pop_mat4().ok()?),
+            Type::Grid { .. } | Type::Array { .. } | Type::Void => return None,
+        };
+        Some(value)
+    }
+}
+
+/// Pop `N` bools, the first component deepest on the stack
+fn pop_bools<const N: usize>(stack: &mut ValueStack) -> Option<[bool; N]> {
+    let mut values = [false; N];
+    for value in values.iter_mut().rev() {
+        *value = stack.pop_int32().ok()? != 0;
+    }
+    Some(values)
+}
@@ -0,0 +1,50 @@
+/// Host images sampled by the texture built-ins
+use crate::fixed::{Fixed, Vec2, Vec4};
+
+/// Number of texture slots a VM has, see `LpsVm::set_texture`
+pub const MAX_TEXTURES: usize = 4;
+
+/// An RGBA image bound to a texture slot
+///
+/// Pixels are row-major from the top-left. Sampling clamps `uv` to 0..1 and
+/// reads the nearest pixel.
+#[derive(Debug, Clone, Copy)]
+pub struct Texture<'a> {
+    width: usize,
+    height: usize,
+    pixels: &'a [Vec4],
+}
+
+impl<'a> Texture<'a> {
+    /// # Panics
+    /// Panics if `pixels` doesn't hold exactly `width * height` pixels, or
+    /// the texture is empty.
+    pub fn new(width: usize, height: usize, pixels: &'a [Vec4]) -> Self {
+        assert!(
+            width > 0 && height > 0 && pixels.len() == width * height,
+            "texture needs width * height pixels"
+        );
+        Texture {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Color of the pixel nearest `uv`
+    pub fn sample(&self, uv: Vec2) -> Vec4 {
+        let texel = |c: Fixed, size: usize| {
+            let pos = c.clamp(Fixed::ZERO, Fixed::ONE).mul_int(size as i32);
+            (pos.to_i32() as usize).min(size - 1)
+        };
+        self.pixels[texel(uv.y, self.height) * self.width + texel(uv.x, self.width)]
+    }
+}
@@ -0,0 +1,88 @@
+//! Opcode trace ring buffer (`vm-trace` feature)
+//!
+//! Records the last few executed opcodes so runtime errors can show how
+//! execution reached the failing instruction. Fixed size, so recording never
+//! allocates.
+
+/// Number of executed opcodes kept in the trace
+pub const TRACE_LEN: usize = 8;
+
+/// One executed opcode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub fn_idx: usize,
+    pub pc: usize,
+    pub opcode: &'static str,
+}
+
+/// Circular buffer of the last [`TRACE_LEN`] executed opcodes
+#[derive(Debug, Clone)]
+pub struct OpcodeTrace {
+    entries: [TraceEntry; TRACE_LEN],
+    next: usize,
+    len: usize,
+}
+
+impl OpcodeTrace {
+    pub fn new() -> Self {
+        OpcodeTrace {
+            entries: [TraceEntry {
+                fn_idx: 0,
+                pc: 0,
+                opcode: "",
+            }; TRACE_LEN],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Forget all recorded opcodes
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+
+    /// Record an executed opcode, overwriting the oldest entry when full
+    #[inline(always)]
+    pub fn record(&mut self, fn_idx: usize, pc: usize, opcode: &'static str) {
+        self.entries[self.next] = TraceEntry { fn_idx, pc, opcode };
+        self.next = (self.next + 1) % TRACE_LEN;
+        self.len = (self.len + 1).min(TRACE_LEN);
+    }
+
+    /// Recorded opcodes, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &TraceEntry> {
+        let start = (self.next + TRACE_LEN - self.len) % TRACE_LEN;
+        (0..self.len).map(move |i| &self.entries[(start + i) % TRACE_LEN])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Default for OpcodeTrace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_keeps_most_recent() {
+        let mut trace = OpcodeTrace::new();
+        for pc in 0..TRACE_LEN + 3 {
+            trace.record(0, pc, "Push");
+        }
+        let pcs: alloc::vec::Vec<usize> = trace.iter().map(|e| e.pc).collect();
+        let expected: alloc::vec::Vec<usize> = (3..TRACE_LEN + 3).collect();
+        assert_eq!(pcs, expected);
+
+        trace.reset();
+        assert!(trace.is_empty());
+    }
+}
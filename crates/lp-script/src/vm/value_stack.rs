@@ -4,7 +4,7 @@ use alloc::vec;
 use alloc::vec::Vec;
 
 use super::error::LpsVmError;
-use crate::fixed::{Fixed, Mat3, Vec2, Vec3, Vec4};
+use crate::fixed::{Fixed, Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
 
 /// VM Stack for LPS execution
 ///
@@ -324,6 +324,19 @@ impl ValueStack {
         Ok((a, b, c, d, e, f, g, h, i))
     }
 
+    /// Push a Mat2 onto the stack (as 4 Fixed values)
+    #[inline(always)]
+    pub fn push_mat2(&mut self, m: Mat2) -> Result<(), LpsVmError> {
+        self.push4(m.m[0].0, m.m[1].0, m.m[2].0, m.m[3].0)
+    }
+
+    /// Pop a Mat2 from the stack
+    #[inline(always)]
+    pub fn pop_mat2(&mut self) -> Result<Mat2, LpsVmError> {
+        let (m00, m10, m01, m11) = self.pop4()?;
+        Ok(Mat2::new(Fixed(m00), Fixed(m10), Fixed(m01), Fixed(m11)))
+    }
+
     /// Push a Mat3 onto the stack (as 9 Fixed values)
     #[inline(always)]
     pub fn push_mat3(&mut self, m: Mat3) -> Result<(), LpsVmError> {
@@ -350,6 +363,36 @@ impl ValueStack {
         ))
     }
 
+    /// Push a Mat4 onto the stack (as 16 Fixed values)
+    #[inline(always)]
+    pub fn push_mat4(&mut self, m: Mat4) -> Result<(), LpsVmError> {
+        if self.sp + 16 > self.max_size {
+            return Err(LpsVmError::StackOverflow { sp: self.sp });
+        }
+        for (i, v) in m.m.iter().enumerate() {
+            self.data[self.sp + i] = v.0;
+        }
+        self.sp += 16;
+        Ok(())
+    }
+
+    /// Pop a Mat4 from the stack
+    #[inline(always)]
+    pub fn pop_mat4(&mut self) -> Result<Mat4, LpsVmError> {
+        if self.sp < 16 {
+            return Err(LpsVmError::StackUnderflow {
+                required: 16,
+                actual: self.sp,
+            });
+        }
+        self.sp -= 16;
+        let mut m = Mat4::zero();
+        for (i, v) in m.m.iter_mut().enumerate() {
+            *v = Fixed(self.data[self.sp + i]);
+        }
+        Ok(m)
+    }
+
     // === Stack manipulation (dup/drop/swap) ===
 
     /// Duplicate top 1 stack value
@@ -530,6 +573,40 @@ impl ValueStack {
         Ok(())
     }
 
+    /// Duplicate top 16 stack values
+    #[inline(always)]
+    pub fn dup16(&mut self) -> Result<(), LpsVmError> {
+        if self.sp < 16 {
+            return Err(LpsVmError::StackUnderflow {
+                required: 16,
+                actual: self.sp,
+            });
+        }
+        if self.sp + 16 > self.max_size {
+            return Err(LpsVmError::StackOverflow { sp: self.sp });
+        }
+
+        for i in 0..16 {
+            self.data[self.sp + i] = self.data[self.sp - 16 + i];
+        }
+        self.sp += 16;
+
+        Ok(())
+    }
+
+    /// Remove top 16 values from stack
+    #[inline(always)]
+    pub fn drop16(&mut self) -> Result<(), LpsVmError> {
+        if self.sp < 16 {
+            return Err(LpsVmError::StackUnderflow {
+                required: 16,
+                actual: self.sp,
+            });
+        }
+        self.sp -= 16;
+        Ok(())
+    }
+
     /// Swap top two stack items
     #[inline(always)]
     pub fn swap(&mut self) -> Result<(), LpsVmError> {
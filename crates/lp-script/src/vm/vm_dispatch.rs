@@ -5,13 +5,16 @@
 extern crate alloc;
 use alloc::vec::Vec;
 
-use crate::fixed::Fixed;
+use crate::fixed::{ceil, floor, fract, sign, sqrt, Fixed};
 use crate::vm::error::{LpsVmError, RuntimeErrorWithContext};
 use crate::vm::lps_vm::LpsVm;
+use crate::vm::native::MAX_NATIVE_PARAMS;
 use crate::vm::opcodes::{
     arrays, comparisons, control_flow, fixed_advanced, fixed_basic, fixed_logic, int32,
-    int32_compare, load, locals, mat3, textures, vec2, vec3, vec4, LpsOpCode, ReturnAction,
+    int32_compare, load, locals, mat2, mat3, mat4, textures, vec2, vec3, vec4, LpsOpCode,
+    ReturnAction,
 };
+use crate::vm::stack_value::InputValue;
 
 impl<'a> LpsVm<'a> {
     /// Dispatch a single opcode
@@ -78,6 +81,12 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::Dup16 => {
+                self.stack.dup16().map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::Drop1 => {
                 self.stack.drop1().map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
@@ -108,6 +117,12 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::Drop16 => {
+                self.stack.drop16().map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::Swap => {
                 self.stack.swap().map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
@@ -142,6 +157,20 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::JumpTable(len) => {
+                let new_pc = control_flow::exec_jump_table(&mut self.stack, self.pc, *len)
+                    .map_err(|e| self.runtime_error(e))?;
+                let max_pc = self.current_function_len();
+                if new_pc >= max_pc {
+                    return Err(self.runtime_error(LpsVmError::ProgramCounterOutOfBounds {
+                        pc: new_pc,
+                        max: max_pc,
+                    }));
+                }
+                self.pc = new_pc;
+                Ok(None)
+            }
+
             LpsOpCode::JumpIfNonZero(offset) => {
                 let offset = *offset;
                 if let Some(new_pc) =
@@ -178,6 +207,33 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::CallNative(idx) => {
+                let native = self
+                    .program
+                    .natives
+                    .get(*idx as usize)
+                    .ok_or_else(|| self.runtime_error(LpsVmError::InvalidFunctionIndex))?;
+                let func = native
+                    .func
+                    .as_ref()
+                    .ok_or_else(|| self.runtime_error(LpsVmError::NativeNotBound))?;
+                let params = &native.sig.params;
+                let mut args = [InputValue::Int32(0); MAX_NATIVE_PARAMS];
+                for (arg, ty) in args[..params.len()].iter_mut().zip(params).rev() {
+                    *arg = InputValue::pop_from(ty, &mut self.stack)
+                        .map_err(|e| self.runtime_error(e))?;
+                }
+                let result = func.call(&args[..params.len()]);
+                if !result.matches(&native.sig.return_type) {
+                    return Err(self.runtime_error(LpsVmError::TypeMismatch));
+                }
+                result
+                    .push_to(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::Return => {
                 match control_flow::exec_return(&self.stack, &mut self.call_stack, &mut self.locals)
                     .map_err(|e| self.runtime_error(e))?
@@ -210,12 +266,35 @@ impl<'a> LpsVm<'a> {
                     time,
                     width,
                     height,
+                    self.center,
                 )
                 .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
 
+            LpsOpCode::LoadIndex => {
+                self.stack
+                    .push_int32(self.index)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::LoadExternal(idx) => {
+                let value = self
+                    .externals
+                    .get(*idx as usize)
+                    .copied()
+                    .flatten()
+                    .ok_or_else(|| self.runtime_error(LpsVmError::ExternalNotBound))?;
+                value
+                    .push_to(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             // === Local Variables ===
             LpsOpCode::LoadLocalFixed(idx) => {
                 let local_idx = self.call_stack.frame_base() + *idx as usize;
@@ -297,6 +376,22 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::LoadLocalMat2(idx) => {
+                let local_idx = self.call_stack.frame_base() + *idx as usize;
+                locals::exec_load_local_mat2(&mut self.stack, &self.locals, local_idx)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::StoreLocalMat2(idx) => {
+                let local_idx = self.call_stack.frame_base() + *idx as usize;
+                locals::exec_store_local_mat2(&mut self.stack, &mut self.locals, local_idx)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::LoadLocalMat3(idx) => {
                 let local_idx = self.call_stack.frame_base() + *idx as usize;
                 locals::exec_load_local_mat3(&mut self.stack, &self.locals, local_idx)
@@ -313,33 +408,97 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::LoadLocalMat4(idx) => {
+                let local_idx = self.call_stack.frame_base() + *idx as usize;
+                locals::exec_load_local_mat4(&mut self.stack, &self.locals, local_idx)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::StoreLocalMat4(idx) => {
+                let local_idx = self.call_stack.frame_base() + *idx as usize;
+                locals::exec_store_local_mat4(&mut self.stack, &mut self.locals, local_idx)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::LoadLocalElemFixed(idx) => {
+                let local_idx = self.call_stack.frame_base() + *idx as usize;
+                locals::exec_load_local_elem_fixed(&mut self.stack, &self.locals, local_idx)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::StoreLocalElemFixed(idx) => {
+                let local_idx = self.call_stack.frame_base() + *idx as usize;
+                locals::exec_store_local_elem_fixed(&mut self.stack, &mut self.locals, local_idx)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::CheckIndex(len) => {
+                locals::exec_check_index(&mut self.stack, *len)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             // === Basic Fixed-point Arithmetic ===
             LpsOpCode::AddFixed => {
-                fixed_basic::exec_add_fixed(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                if self.overflow_check {
+                    fixed_basic::exec_add_fixed_checked(&mut self.stack)
+                } else {
+                    fixed_basic::exec_add_fixed(&mut self.stack)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
 
             LpsOpCode::SubFixed => {
-                fixed_basic::exec_sub_fixed(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                if self.overflow_check {
+                    fixed_basic::exec_sub_fixed_checked(&mut self.stack)
+                } else {
+                    fixed_basic::exec_sub_fixed(&mut self.stack)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
 
             LpsOpCode::MulFixed => {
-                fixed_basic::exec_mul_fixed(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                if self.overflow_check {
+                    fixed_basic::exec_mul_fixed_checked(&mut self.stack)
+                } else {
+                    fixed_basic::exec_mul_fixed(&mut self.stack)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
 
             LpsOpCode::DivFixed => {
-                fixed_basic::exec_div_fixed(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                if self.overflow_check {
+                    fixed_basic::exec_div_fixed_checked(&mut self.stack)
+                } else {
+                    fixed_basic::exec_div_fixed(&mut self.stack)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
 
             LpsOpCode::NegFixed => {
-                fixed_basic::exec_neg_fixed(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                if self.overflow_check {
+                    fixed_basic::exec_neg_fixed_checked(&mut self.stack)
+                } else {
+                    fixed_basic::exec_neg_fixed(&mut self.stack)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
@@ -429,6 +588,27 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::WrapFixed => {
+                fixed_advanced::exec_wrap_fixed(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::PhaseTime => {
+                fixed_advanced::exec_phase_time(&mut self.stack, time, self.time_ms)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::QuantizeFixed => {
+                fixed_advanced::exec_quantize_fixed(&mut self.stack, x_int, y_int)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::PowFixed => {
                 fixed_advanced::exec_pow_fixed(&mut self.stack)
                     .map_err(|e| self.runtime_error(e))?;
@@ -478,8 +658,15 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::RemapFixed => {
+                fixed_advanced::exec_remap_fixed(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::Perlin3(octaves) => {
-                fixed_advanced::exec_perlin3(&mut self.stack, *octaves)
+                fixed_advanced::exec_perlin3(&mut self.stack, *octaves, self.noise_backend)
                     .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
@@ -533,39 +720,68 @@ impl<'a> LpsVm<'a> {
             }
 
             LpsOpCode::EqFixed => {
-                comparisons::exec_eq_fixed(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                if self.eq_epsilon == Fixed::ZERO {
+                    comparisons::exec_eq_fixed(&mut self.stack)
+                } else {
+                    comparisons::exec_eq_fixed_within(&mut self.stack, self.eq_epsilon)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
 
             LpsOpCode::NotEqFixed => {
-                comparisons::exec_not_eq_fixed(&mut self.stack)
-                    .map_err(|e| self.runtime_error(e))?;
+                if self.eq_epsilon == Fixed::ZERO {
+                    comparisons::exec_not_eq_fixed(&mut self.stack)
+                } else {
+                    comparisons::exec_not_eq_fixed_within(&mut self.stack, self.eq_epsilon)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
 
             // === Int32 Arithmetic ===
             LpsOpCode::AddInt32 => {
-                int32::exec_add_int32(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                if self.overflow_check {
+                    int32::exec_add_int32_checked(&mut self.stack)
+                } else {
+                    int32::exec_add_int32(&mut self.stack)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
 
             LpsOpCode::SubInt32 => {
-                int32::exec_sub_int32(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                if self.overflow_check {
+                    int32::exec_sub_int32_checked(&mut self.stack)
+                } else {
+                    int32::exec_sub_int32(&mut self.stack)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
 
             LpsOpCode::MulInt32 => {
-                int32::exec_mul_int32(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                if self.overflow_check {
+                    int32::exec_mul_int32_checked(&mut self.stack)
+                } else {
+                    int32::exec_mul_int32(&mut self.stack)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
 
             LpsOpCode::DivInt32 => {
-                int32::exec_div_int32(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                if self.overflow_check {
+                    int32::exec_div_int32_checked(&mut self.stack)
+                } else {
+                    int32::exec_div_int32(&mut self.stack)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
@@ -577,7 +793,12 @@ impl<'a> LpsVm<'a> {
             }
 
             LpsOpCode::NegInt32 => {
-                int32::exec_neg_int32(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                if self.overflow_check {
+                    int32::exec_neg_int32_checked(&mut self.stack)
+                } else {
+                    int32::exec_neg_int32(&mut self.stack)
+                }
+                .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
@@ -712,6 +933,43 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::AbsVec2 => {
+                vec2::exec_map_vec2(&mut self.stack, Fixed::abs)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::SignVec2 => {
+                vec2::exec_map_vec2(&mut self.stack, sign).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::FloorVec2 => {
+                vec2::exec_map_vec2(&mut self.stack, floor).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::CeilVec2 => {
+                vec2::exec_map_vec2(&mut self.stack, ceil).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::FractVec2 => {
+                vec2::exec_map_vec2(&mut self.stack, fract).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::SqrtVec2 => {
+                vec2::exec_map_vec2(&mut self.stack, sqrt).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::MulVec2 => {
                 vec2::exec_mul_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
@@ -724,6 +982,100 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::Reflect2 => {
+                vec2::exec_reflect2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::FaceForward2 => {
+                vec2::exec_faceforward2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MaxCompVec2 => {
+                vec2::exec_max_comp_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MinCompVec2 => {
+                vec2::exec_min_comp_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::SumVec2 => {
+                vec2::exec_sum_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::AvgVec2 => {
+                vec2::exec_avg_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::ToPolarVec2 => {
+                vec2::exec_to_polar_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::FromPolarVec2 => {
+                vec2::exec_from_polar_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::TileVec2 => {
+                vec2::exec_tile_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MirrorTileVec2 => {
+                vec2::exec_mirror_tile_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::EqVec2 => {
+                vec2::exec_eq_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::LessVec2 => {
+                comparisons::exec_less_vec::<2>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::LessEqVec2 => {
+                comparisons::exec_less_eq_vec::<2>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::EqCompVec2 => {
+                comparisons::exec_eq_comp_vec::<2>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::NotBVec2 => {
+                comparisons::exec_not_bvec::<2>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::ModVec2 => {
                 vec2::exec_mod_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
@@ -755,7 +1107,8 @@ impl<'a> LpsVm<'a> {
             }
 
             LpsOpCode::Normalize2 => {
-                vec2::exec_normalize2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                vec2::exec_normalize2(&mut self.stack, self.normalize_fallback)
+                    .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
@@ -766,6 +1119,18 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::LengthSq2 => {
+                vec2::exec_length_sq2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::DistanceSq2 => {
+                vec2::exec_distance_sq2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             // === Vec3 Operations ===
             LpsOpCode::AddVec3 => {
                 vec3::exec_add_vec3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
@@ -785,6 +1150,43 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::AbsVec3 => {
+                vec3::exec_map_vec3(&mut self.stack, Fixed::abs)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::SignVec3 => {
+                vec3::exec_map_vec3(&mut self.stack, sign).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::FloorVec3 => {
+                vec3::exec_map_vec3(&mut self.stack, floor).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::CeilVec3 => {
+                vec3::exec_map_vec3(&mut self.stack, ceil).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::FractVec3 => {
+                vec3::exec_map_vec3(&mut self.stack, fract).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::SqrtVec3 => {
+                vec3::exec_map_vec3(&mut self.stack, sqrt).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::MulVec3 => {
                 vec3::exec_mul_vec3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
@@ -797,6 +1199,88 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::Reflect3 => {
+                vec3::exec_reflect3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::FaceForward3 => {
+                vec3::exec_faceforward3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MaxCompVec3 => {
+                vec3::exec_max_comp_vec3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::HsvToRgb => {
+                vec3::exec_hsv_to_rgb(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::RgbToHsv => {
+                vec3::exec_rgb_to_hsv(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MinCompVec3 => {
+                vec3::exec_min_comp_vec3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::SumVec3 => {
+                vec3::exec_sum_vec3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::AvgVec3 => {
+                vec3::exec_avg_vec3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::EqVec3 => {
+                vec3::exec_eq_vec3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::LessVec3 => {
+                comparisons::exec_less_vec::<3>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::LessEqVec3 => {
+                comparisons::exec_less_eq_vec::<3>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::EqCompVec3 => {
+                comparisons::exec_eq_comp_vec::<3>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::NotBVec3 => {
+                comparisons::exec_not_bvec::<3>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::ModVec3 => {
                 vec3::exec_mod_vec3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
@@ -834,7 +1318,8 @@ impl<'a> LpsVm<'a> {
             }
 
             LpsOpCode::Normalize3 => {
-                vec3::exec_normalize3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                vec3::exec_normalize3(&mut self.stack, self.normalize_fallback)
+                    .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
@@ -845,6 +1330,18 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::LengthSq3 => {
+                vec3::exec_length_sq3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::DistanceSq3 => {
+                vec3::exec_distance_sq3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             // === Vec4 Operations ===
             LpsOpCode::AddVec4 => {
                 vec4::exec_add_vec4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
@@ -864,6 +1361,43 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::AbsVec4 => {
+                vec4::exec_map_vec4(&mut self.stack, Fixed::abs)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::SignVec4 => {
+                vec4::exec_map_vec4(&mut self.stack, sign).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::FloorVec4 => {
+                vec4::exec_map_vec4(&mut self.stack, floor).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::CeilVec4 => {
+                vec4::exec_map_vec4(&mut self.stack, ceil).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::FractVec4 => {
+                vec4::exec_map_vec4(&mut self.stack, fract).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::SqrtVec4 => {
+                vec4::exec_map_vec4(&mut self.stack, sqrt).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::MulVec4 => {
                 vec4::exec_mul_vec4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
@@ -876,6 +1410,76 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::Reflect4 => {
+                vec4::exec_reflect4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::FaceForward4 => {
+                vec4::exec_faceforward4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MaxCompVec4 => {
+                vec4::exec_max_comp_vec4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MinCompVec4 => {
+                vec4::exec_min_comp_vec4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::SumVec4 => {
+                vec4::exec_sum_vec4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::AvgVec4 => {
+                vec4::exec_avg_vec4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::EqVec4 => {
+                vec4::exec_eq_vec4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::LessVec4 => {
+                comparisons::exec_less_vec::<4>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::LessEqVec4 => {
+                comparisons::exec_less_eq_vec::<4>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::EqCompVec4 => {
+                comparisons::exec_eq_comp_vec::<4>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::NotBVec4 => {
+                comparisons::exec_not_bvec::<4>(&mut self.stack)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             LpsOpCode::ModVec4 => {
                 vec4::exec_mod_vec4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
@@ -907,7 +1511,8 @@ impl<'a> LpsVm<'a> {
             }
 
             LpsOpCode::Normalize4 => {
-                vec4::exec_normalize4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                vec4::exec_normalize4(&mut self.stack, self.normalize_fallback)
+                    .map_err(|e| self.runtime_error(e))?;
                 self.pc += 1;
                 Ok(None)
             }
@@ -918,6 +1523,55 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::LengthSq4 => {
+                vec4::exec_length_sq4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::DistanceSq4 => {
+                vec4::exec_distance_sq4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            // === Mat2 Operations ===
+            LpsOpCode::RotateMat2 => {
+                mat2::exec_rotate_mat2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MulMat2 => {
+                mat2::exec_mul_mat2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MulMat2Vec2 => {
+                mat2::exec_mul_mat2_vec2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::TransposeMat2 => {
+                mat2::exec_transpose_mat2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::DeterminantMat2 => {
+                mat2::exec_determinant_mat2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::InverseMat2 => {
+                mat2::exec_inverse_mat2(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             // === Mat3 Operations ===
             LpsOpCode::AddMat3 => {
                 mat3::exec_add_mat3(&mut self.stack).map_err(|e| self.runtime_error(e))?;
@@ -979,6 +1633,67 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            // === Mat4 Operations ===
+            LpsOpCode::AddMat4 => {
+                mat4::exec_add_mat4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::SubMat4 => {
+                mat4::exec_sub_mat4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::NegMat4 => {
+                mat4::exec_neg_mat4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MulMat4 => {
+                mat4::exec_mul_mat4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MulMat4Scalar => {
+                mat4::exec_mul_mat4_scalar(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::DivMat4Scalar => {
+                mat4::exec_div_mat4_scalar(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::MulMat4Vec4 => {
+                mat4::exec_mul_mat4_vec4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::TransposeMat4 => {
+                mat4::exec_transpose_mat4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::DeterminantMat4 => {
+                mat4::exec_determinant_mat4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::InverseMat4 => {
+                mat4::exec_inverse_mat4(&mut self.stack).map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             // === Swizzle Operations ===
             LpsOpCode::Swizzle3to2(idx0, idx1) => {
                 self.stack
@@ -1035,6 +1750,20 @@ impl<'a> LpsVm<'a> {
                 Ok(None)
             }
 
+            LpsOpCode::TextureMixRGBA(a, b) => {
+                textures::exec_texture_mix_rgba(&mut self.stack, &self.textures, *a, *b)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
+            LpsOpCode::PaletteSample => {
+                textures::exec_palette_sample(&mut self.stack, self.palette)
+                    .map_err(|e| self.runtime_error(e))?;
+                self.pc += 1;
+                Ok(None)
+            }
+
             // === Array Operations ===
             LpsOpCode::GetElemInt32ArrayFixed => {
                 arrays::exec_get_elem_int32_array_fixed(&mut self.stack)
@@ -4,6 +4,8 @@ pub struct VmLimits {
     pub max_call_stack_depth: usize,
     pub max_stack_size: usize,
     pub max_instructions: usize,
+    /// Local variable storage shared by all frames, in i32 slots
+    pub max_locals: usize,
 }
 
 impl Default for VmLimits {
@@ -12,6 +14,7 @@ impl Default for VmLimits {
             max_call_stack_depth: 64,
             max_stack_size: 256,
             max_instructions: 10_000,
+            max_locals: 2048,
         }
     }
 }
@@ -30,11 +33,13 @@ mod tests {
             max_call_stack_depth: 32,
             max_stack_size: 128,
             max_instructions: 5000,
+            max_locals: 256,
         };
 
         let vm = LpsVm::new(&program, custom_limits).unwrap();
         assert_eq!(vm.limits.max_call_stack_depth, 32);
         assert_eq!(vm.limits.max_stack_size, 128);
         assert_eq!(vm.limits.max_instructions, 5000);
+        assert_eq!(vm.locals().capacity(), 256);
     }
 }
@@ -1,6 +1,6 @@
 use core::error::Error;
 
-use schemars::JsonSchema;
+use schemars::{JsonSchema, Schema, SchemaGenerator};
 use serde::{Deserialize, Serialize};
 
 use crate::entities::circle::CircleEntity;
@@ -24,6 +24,14 @@ impl EntityKind {
             _ => Err(format!("Unknown entity kind: {}", s).into()),
         }
     }
+
+    /// Schema of the entity's input, with its definitions added to `generator`
+    pub fn input_schema(&self, generator: &mut SchemaGenerator) -> Schema {
+        match self {
+            EntityKind::Circle => generator.subschema_for::<circle::Input>(),
+            EntityKind::Lfo => generator.subschema_for::<lfo::Input>(),
+        }
+    }
 }
 
 pub fn create_entity(kind: EntityKind) -> Box<dyn EntityInstance> {
@@ -26,7 +26,7 @@ impl EntityInstance for CircleEntity {
 }
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
-struct Input {
+pub(crate) struct Input {
     image_size: SizeInt,
     radius: f32,
 }
@@ -1,9 +1,15 @@
 use core::error::Error;
+use std::collections::HashMap;
 
 use indexmap::IndexMap;
+use schemars::generate::SchemaSettings;
+use schemars::{json_schema, Schema};
+use serde_json::{Map as JsonMap, Value as JsonValue};
 
+use crate::entities::EntityKind;
 use crate::entity::entity_id::{EntityId, EntitySource};
 use crate::entity::entity_instance::EntityInstance;
+use crate::expr::Expr;
 use crate::scene::scene_node::SceneNode;
 use crate::scene::SceneConfig;
 
@@ -42,6 +48,47 @@ impl Scene {
         }
     }
 
+    /// Schema of every node's editable parameters, keyed by node id
+    ///
+    /// Each node lists its entity's `input` and its `bindings`, as in
+    /// `NodeConfig`, so a UI for the whole scene can be generated from one
+    /// JSON Schema.
+    pub fn parameter_schema(&self) -> Result<Schema, Box<dyn Error>> {
+        let mut generator = SchemaSettings::default().into_generator();
+        let mut properties = JsonMap::new();
+        for (id, node) in self.nodes.iter() {
+            let entity_id = EntityId::parse_str(&node.config.entity_id)?;
+            let kind = match entity_id.source {
+                EntitySource::BuiltIn => EntityKind::parse_str(&entity_id.specifier)?,
+                EntitySource::Scene => {
+                    return Err(
+                        format!("No schema for scene entity: {}", entity_id.specifier).into(),
+                    )
+                }
+            };
+            let node_schema = json_schema!({
+                "type": "object",
+                "properties": {
+                    "input": kind.input_schema(&mut generator),
+                    "bindings": generator.subschema_for::<HashMap<String, Expr>>(),
+                },
+                "required": ["input"],
+            });
+            properties.insert(id.clone(), node_schema.into());
+        }
+
+        let mut schema = json_schema!({
+            "type": "object",
+            "properties": properties,
+            "required": self.nodes.keys().collect::<Vec<_>>(),
+        });
+        let definitions = generator.take_definitions(true);
+        if !definitions.is_empty() {
+            schema.insert("$defs".to_string(), JsonValue::Object(definitions));
+        }
+        Ok(schema)
+    }
+
     pub fn apply_config(&mut self, config: &SceneConfig) {
         // remove old nodes
         self.nodes.retain(|id, node| {
@@ -74,3 +121,57 @@ impl Scene {
         // apply bindings
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::entities::create_entity;
+    use crate::scene::scene_node::SceneNode;
+    use crate::scene::NodeConfig;
+
+    fn node(entity_id: &str, kind: EntityKind) -> SceneNode {
+        SceneNode {
+            last_updated_frame: None,
+            config: NodeConfig {
+                entity_id: entity_id.to_string(),
+                input: JsonValue::Null,
+                bindings: HashMap::new(),
+            },
+            instance: create_entity(kind),
+            current_input: JsonValue::Null,
+            current_output: JsonValue::Null,
+            input_bindings: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_parameter_schema_lists_every_node() {
+        let mut scene = Scene::new();
+        scene
+            .nodes
+            .insert("wave".to_string(), node("builtin:lfo", EntityKind::Lfo));
+        scene.nodes.insert(
+            "dot".to_string(),
+            node("builtin:circle", EntityKind::Circle),
+        );
+
+        let schema = scene.parameter_schema().unwrap();
+        let schema = schema.as_value();
+        assert_eq!(schema["required"], json!(["wave", "dot"]));
+
+        // Follow each node's `input` reference into the shared definitions
+        let input_fields = |node_id: &str| {
+            let reference = schema["properties"][node_id]["properties"]["input"]["$ref"]
+                .as_str()
+                .unwrap();
+            let name = reference.strip_prefix("#/$defs/").unwrap();
+            let fields = schema["$defs"][name]["properties"].as_object().unwrap();
+            fields.keys().cloned().collect::<Vec<_>>()
+        };
+        assert_eq!(input_fields("wave"), ["period_ms", "shape", "min", "max"]);
+        assert_eq!(input_fields("dot"), ["image_size", "radius"]);
+        assert!(schema["properties"]["wave"]["properties"]["bindings"].is_object());
+    }
+}
@@ -0,0 +1,150 @@
+//! Minimal unified diff output for previewing transformations.
+
+/// Lines of unchanged context shown around each change
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Produce a unified diff between `old` and `new`, labelled with `path`
+///
+/// Returns an empty string when the inputs are identical.
+pub fn unified_diff(old: &str, new: &str, path: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    if ops.iter().all(|op| matches!(op, Op::Equal(..))) {
+        return String::new();
+    }
+
+    let mut out = format!("--- a/{}\n+++ b/{}\n", path, path);
+    for (start, end) in hunk_ranges(&ops) {
+        let hunk = &ops[start..end];
+        let (old_start, new_start) = hunk_origin(&ops, start);
+        let old_len = hunk
+            .iter()
+            .filter(|op| !matches!(op, Op::Insert(_)))
+            .count();
+        let new_len = hunk
+            .iter()
+            .filter(|op| !matches!(op, Op::Delete(_)))
+            .count();
+
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range_header(old_start, old_len),
+            range_header(new_start, new_len)
+        ));
+        for op in hunk {
+            match *op {
+                Op::Equal(i, _) => out.push_str(&format!(" {}\n", old_lines[i])),
+                Op::Delete(i) => out.push_str(&format!("-{}\n", old_lines[i])),
+                Op::Insert(j) => out.push_str(&format!("+{}\n", new_lines[j])),
+            }
+        }
+    }
+    out
+}
+
+/// Line-level edit script via longest common subsequence
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Op> {
+    let (n, m) = (old.len(), new.len());
+    // lcs[i][j] = LCS length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(Op::Equal(i, j));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(Op::Delete(i));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(j));
+            j += 1;
+        }
+    }
+    ops.extend((i..n).map(Op::Delete));
+    ops.extend((j..m).map(Op::Insert));
+    ops
+}
+
+/// Group changes into hunks (op index ranges) including surrounding context
+fn hunk_ranges(ops: &[Op]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (idx, op) in ops.iter().enumerate() {
+        if matches!(op, Op::Equal(..)) {
+            continue;
+        }
+        let start = idx.saturating_sub(CONTEXT);
+        let end = (idx + 1 + CONTEXT).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+/// Zero-based old/new line numbers at the start of a hunk
+fn hunk_origin(ops: &[Op], start: usize) -> (usize, usize) {
+    let old = ops[..start]
+        .iter()
+        .filter(|op| !matches!(op, Op::Insert(_)))
+        .count();
+    let new = ops[..start]
+        .iter()
+        .filter(|op| !matches!(op, Op::Delete(_)))
+        .count();
+    (old, new)
+}
+
+fn range_header(start: usize, len: usize) -> String {
+    // Empty ranges point at the line before the hunk, per the unified format
+    let first = if len == 0 { start } else { start + 1 };
+    if len == 1 {
+        format!("{}", first)
+    } else {
+        format!("{},{}", first, len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_inputs_produce_no_diff() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "f.rs"), "");
+    }
+
+    #[test]
+    fn separate_changes_produce_separate_hunks() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "one\n2\n3\n4\n5\n6\n7\n8\n9\nten\n";
+        let diff = unified_diff(old, new, "f.rs");
+        assert_eq!(
+            diff,
+            "--- a/f.rs\n+++ b/f.rs\n\
+             @@ -1,4 +1,4 @@\n-1\n+one\n 2\n 3\n 4\n\
+             @@ -7,4 +7,4 @@\n 7\n 8\n 9\n-10\n+ten\n"
+        );
+    }
+}
@@ -1,3 +1,5 @@
+pub mod diff;
 pub mod transform;
 
+pub use diff::unified_diff;
 pub use transform::*;
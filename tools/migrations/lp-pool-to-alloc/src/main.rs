@@ -2,6 +2,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use clap::Parser;
+use lp_pool_to_alloc::Rule;
 use walkdir::WalkDir;
 
 #[derive(Parser)]
@@ -12,6 +13,14 @@ struct Args {
     #[arg(long)]
     dry_run: bool,
 
+    /// Print a unified diff of each changed file instead of rewriting it
+    #[arg(long)]
+    show_diff: bool,
+
+    /// Only apply the given rule (may be repeated)
+    #[arg(long = "only", value_name = "RULE")]
+    only: Vec<Rule>,
+
     /// Verbose output
     #[arg(short, long)]
     verbose: bool,
@@ -35,17 +44,19 @@ fn main() {
 }
 
 fn process_directory(dir: &Path, args: &Args) {
-    for entry in WalkDir::new(dir).into_iter().filter_entry(|e| {
-        let path = e.path();
-        // Skip target directories and hidden files
-        !path.to_string_lossy().contains("/target/")
-            && !path.to_string_lossy().contains("/.git/")
-            && path.extension().map_or(false, |ext| ext == "rs")
-    }) {
-        if let Ok(entry) = entry {
-            if entry.file_type().is_file() {
-                process_file(entry.path(), args);
-            }
+    for entry in WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|e| {
+            let path = e.path();
+            // Skip target directories and hidden files
+            !path.to_string_lossy().contains("/target/")
+                && !path.to_string_lossy().contains("/.git/")
+                && path.extension().is_some_and(|ext| ext == "rs")
+        })
+        .flatten()
+    {
+        if entry.file_type().is_file() {
+            process_file(entry.path(), args);
         }
     }
 }
@@ -64,14 +75,27 @@ fn process_file(file_path: &Path, args: &Args) {
     };
 
     // Parse and transform the file
-    let transformed = lp_pool_to_alloc::transform_file(&content, file_path);
+    let rules: &[Rule] = if args.only.is_empty() {
+        &Rule::ALL
+    } else {
+        &args.only
+    };
+    let result = lp_pool_to_alloc::transform_file_with_rules(&content, file_path, rules);
+    let transformed = result.content;
 
     if transformed != content {
         if args.verbose {
-            println!("  -> File modified");
+            let fired: Vec<&str> = result.fired.iter().map(|r| r.name()).collect();
+            println!("  -> File modified ({})", fired.join(", "));
         }
 
-        if !args.dry_run {
+        if args.show_diff {
+            let path = file_path.to_string_lossy();
+            print!(
+                "{}",
+                lp_pool_to_alloc::unified_diff(&content, &transformed, &path)
+            );
+        } else if !args.dry_run {
             if let Err(e) = fs::write(file_path, transformed) {
                 eprintln!("Error writing {}: {}", file_path.display(), e);
             }
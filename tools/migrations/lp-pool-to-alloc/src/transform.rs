@@ -1,27 +1,118 @@
 //! Transformations for migrating from lp-pool to lp-alloc.
 
+use std::fmt;
+use std::str::FromStr;
+
+/// An individual transformation pass, in the order they are applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    Imports,
+    Types,
+    ErrorTypes,
+    PoolScopes,
+    CompilerTryCalls,
+    TestSetup,
+}
+
+impl Rule {
+    /// All rules, in application order
+    pub const ALL: [Rule; 6] = [
+        Rule::Imports,
+        Rule::Types,
+        Rule::ErrorTypes,
+        Rule::PoolScopes,
+        Rule::CompilerTryCalls,
+        Rule::TestSetup,
+    ];
+
+    /// Name used on the command line (`--only <rule>`)
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::Imports => "imports",
+            Rule::Types => "types",
+            Rule::ErrorTypes => "error-types",
+            Rule::PoolScopes => "pool-scopes",
+            Rule::CompilerTryCalls => "compiler-try-calls",
+            Rule::TestSetup => "test-setup",
+        }
+    }
+
+    fn apply(self, content: &str) -> String {
+        match self {
+            Rule::Imports => transform_imports(content),
+            Rule::Types => transform_types(content),
+            Rule::ErrorTypes => transform_error_types(content),
+            Rule::PoolScopes => remove_pool_scopes(content),
+            Rule::CompilerTryCalls => transform_compiler_try_calls(content),
+            Rule::TestSetup => add_test_setup(content),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rule::ALL
+            .into_iter()
+            .find(|rule| rule.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Rule::ALL.iter().map(|r| r.name()).collect();
+                format!(
+                    "unknown rule '{}' (expected one of: {})",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Result of transforming a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transformed {
+    pub content: String,
+    /// Rules that changed the content, in application order
+    pub fired: Vec<Rule>,
+}
+
 /// Transform a Rust source file
 pub fn transform_file(content: &str, file_path: &std::path::Path) -> String {
+    transform_file_with_rules(content, file_path, &Rule::ALL).content
+}
+
+/// Transform a Rust source file using only `rules`, reporting which ones fired
+pub fn transform_file_with_rules(
+    content: &str,
+    file_path: &std::path::Path,
+    rules: &[Rule],
+) -> Transformed {
     let is_compiler = file_path
         .to_string_lossy()
         .contains("lp-script/src/compiler");
 
-    // First do simple string-based replacements
     let mut transformed = content.to_string();
-    transformed = transform_imports(&transformed);
-    transformed = transform_types(&transformed);
-    transformed = transform_error_types(&transformed);
-    transformed = remove_pool_scopes(&transformed);
-
-    // Then parse and do AST-based transformations for complex cases
-    if is_compiler {
-        transformed = transform_compiler_try_calls(&transformed);
+    let mut fired = Vec::new();
+    for rule in Rule::ALL {
+        if !rules.contains(&rule) || (rule == Rule::CompilerTryCalls && !is_compiler) {
+            continue;
+        }
+        let next = rule.apply(&transformed);
+        if next != transformed {
+            fired.push(rule);
+            transformed = next;
+        }
     }
 
-    // Add test setup if needed
-    transformed = add_test_setup(&transformed);
-
-    transformed
+    Transformed {
+        content: transformed,
+        fired,
+    }
 }
 
 /// Transform import statements
@@ -208,3 +299,74 @@ fn add_test_setup(content: &str) -> String {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::diff::unified_diff;
+
+    const LP_VEC_SOURCE: &str = "\
+use lp_pool::LpVec;
+
+fn total(values: &LpVec<u8>) -> usize {
+    values.len()
+}
+";
+
+    #[test]
+    fn lp_vec_diff_output() {
+        let path = Path::new("crates/demo/src/lib.rs");
+        let result = transform_file_with_rules(LP_VEC_SOURCE, path, &Rule::ALL);
+
+        assert_eq!(result.fired, vec![Rule::Imports, Rule::Types]);
+        assert_eq!(
+            unified_diff(LP_VEC_SOURCE, &result.content, "crates/demo/src/lib.rs"),
+            "\
+--- a/crates/demo/src/lib.rs
++++ b/crates/demo/src/lib.rs
+@@ -1,5 +1,5 @@
+-use lp_pool::LpVec;
++use alloc::vec::Vec;
+ 
+-fn total(values: &LpVec<u8>) -> usize {
++fn total(values: &Vec<u8>) -> usize {
+     values.len()
+ }
+"
+        );
+    }
+
+    #[test]
+    fn only_selected_rules_run() {
+        let path = Path::new("crates/demo/src/lib.rs");
+        let result = transform_file_with_rules(LP_VEC_SOURCE, path, &[Rule::Types]);
+
+        assert_eq!(result.fired, vec![Rule::Types]);
+        assert!(result.content.starts_with("use lp_pool::LpVec;\n"));
+        assert!(result.content.contains("values: &Vec<u8>"));
+    }
+
+    #[test]
+    fn compiler_rule_only_fires_for_compiler_files() {
+        let source = "use core::fmt;\nfn f(v: &mut Vec<u8>) { v.try_push(1); }\n";
+        let other = transform_file_with_rules(source, Path::new("src/lib.rs"), &Rule::ALL);
+        assert!(other.fired.is_empty());
+
+        let compiler = transform_file_with_rules(
+            source,
+            Path::new("crates/lp-script/src/compiler/mod.rs"),
+            &Rule::ALL,
+        );
+        assert_eq!(compiler.fired, vec![Rule::CompilerTryCalls]);
+    }
+
+    #[test]
+    fn rule_names_round_trip() {
+        for rule in Rule::ALL {
+            assert_eq!(rule.name().parse::<Rule>(), Ok(rule));
+        }
+        assert!("bogus".parse::<Rule>().is_err());
+    }
+}